@@ -0,0 +1,10 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/lab.proto");
+        // Builds need no system protoc.
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::compile_protos("proto/lab.proto").expect("compile proto/lab.proto");
+    }
+}
@@ -0,0 +1,170 @@
+//! JSONL journals of bus events and paper journal rows, and the range reads
+//! behind `/events/export` and `/journal/export`.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_stream::wrappers::LinesStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::state::{unix_now_ms, PublishedEvent};
+
+/// Every event published on the bus.
+pub const EVENTS_FILE: &str = "events.jsonl";
+/// The paper journal, row for row with the replay CSV.
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Appends one JSON object per line, each stamped with the Unix
+/// milliseconds it was written at as `recorded_ms`.
+#[derive(Debug)]
+pub struct JsonlJournal {
+    writer: LineWriter<File>,
+}
+
+impl JsonlJournal {
+    /// Opens `path` for appending, creating it and its directory as needed.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: LineWriter::new(file),
+        })
+    }
+
+    /// Writes `record`, which must serialize to a JSON object.
+    pub fn append<T: Serialize>(&mut self, recorded_ms: u64, record: &T) -> io::Result<()> {
+        let mut line = serde_json::to_value(record)?;
+        let Some(fields) = line.as_object_mut() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "journal records must be JSON objects",
+            ));
+        };
+        fields.insert("recorded_ms".to_string(), recorded_ms.into());
+        serde_json::to_writer(&mut self.writer, &line)?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+/// Journals events until the bus closes; run it on a blocking thread.
+pub fn record_events(
+    mut events: broadcast::Receiver<Arc<PublishedEvent>>,
+    mut journal: JsonlJournal,
+) -> io::Result<()> {
+    loop {
+        match events.blocking_recv() {
+            Ok(event) => journal.append(unix_now_ms(), &*event)?,
+            Err(RecvError::Lagged(skipped)) => {
+                eprintln!("event journal missed {skipped} events");
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+/// `recorded_ms` bounds of an export: from inclusive, to exclusive.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+pub struct ExportRange {
+    pub from_ms: Option<u64>,
+    pub to_ms: Option<u64>,
+}
+
+impl ExportRange {
+    fn contains(self, line: &str) -> bool {
+        #[derive(Deserialize)]
+        struct Recorded {
+            recorded_ms: u64,
+        }
+
+        // A half-written last line fails to parse and is left out.
+        let Ok(Recorded { recorded_ms }) = serde_json::from_str(line) else {
+            return false;
+        };
+        self.from_ms.is_none_or(|from| recorded_ms >= from)
+            && self.to_ms.is_none_or(|to| recorded_ms < to)
+    }
+}
+
+/// Lines of the journal at `path` recorded within `range`, newline
+/// included. A journal that was never written reads as empty.
+pub async fn read_range(
+    path: &Path,
+    range: ExportRange,
+) -> io::Result<impl Stream<Item = io::Result<String>> + Send + 'static> {
+    let reader: Box<dyn AsyncRead + Send + Unpin> = match tokio::fs::File::open(path).await {
+        Ok(file) => Box::new(file),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Box::new(tokio::io::empty()),
+        Err(err) => return Err(err),
+    };
+    let lines = LinesStream::new(BufReader::new(reader).lines());
+    Ok(lines.filter_map(move |line| match line {
+        Ok(line) => range.contains(&line).then(|| Ok(line + "\n")),
+        Err(err) => Some(Err(err)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::state::{PaperOrderSide, RiskAlertKind, RuntimeEvent};
+
+    #[tokio::test]
+    async fn journals_events_and_reads_back_a_time_range() {
+        let dir = std::env::temp_dir().join(format!("api-export-{}", std::process::id()));
+        let path = dir.join(EVENTS_FILE);
+        let mut journal = JsonlJournal::open(&path).unwrap();
+        journal
+            .append(
+                1_000,
+                &RuntimeEvent::paper_fill("btc-a", PaperOrderSide::Buy, 1.0, 0.5, 0.0),
+            )
+            .unwrap();
+        journal
+            .append(
+                2_000,
+                &RuntimeEvent::risk_alert(RiskAlertKind::Halt, "drawdown", 2_000),
+            )
+            .unwrap();
+        journal
+            .append(3_000, &RuntimeEvent::connected(None))
+            .unwrap();
+        assert!(journal.append(4_000, &[1, 2]).is_err());
+        drop(journal);
+
+        let read = |range| {
+            let path = path.clone();
+            async move {
+                read_range(&path, range)
+                    .await
+                    .unwrap()
+                    .collect::<io::Result<Vec<String>>>()
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(read(ExportRange::default()).await.len(), 3);
+        let lines = read(ExportRange {
+            from_ms: Some(2_000),
+            to_ms: Some(3_000),
+        })
+        .await;
+        assert_eq!(lines.len(), 1);
+        let event: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(event["event_type"], "risk_alert");
+        assert_eq!(event["recorded_ms"], 2_000);
+
+        let missing = read_range(&dir.join(JOURNAL_FILE), ExportRange::default())
+            .await
+            .unwrap();
+        assert_eq!(missing.collect::<Vec<_>>().await.len(), 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
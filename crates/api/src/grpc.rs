@@ -0,0 +1,365 @@
+//! gRPC mirror of the control and telemetry API for non-browser clients.
+//! The schema is `proto/lab.proto`.
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use core_sim::{run_scenario, Scenario, SimConfig, SimReport};
+use rust_decimal::prelude::ToPrimitive;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use crate::routes::apply_settings_patch;
+use crate::state::{
+    AppState, ExecutionMode as StateExecutionMode, PublishedEvent, RuntimeEvent, RuntimeSettings,
+    RuntimeSettingsPatch, SizingMode as StateSizingMode,
+};
+
+pub mod proto {
+    tonic::include_proto!("lab.v1");
+}
+
+use proto::lab_server::{Lab, LabServer};
+
+/// Serves the `lab.v1.Lab` service on `addr` until the server fails.
+pub async fn serve(addr: SocketAddr, state: AppState) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(LabServer::new(LabService::new(state)))
+        .serve(addr)
+        .await
+}
+
+#[derive(Clone, Debug)]
+pub struct LabService {
+    state: AppState,
+}
+
+impl LabService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<proto::Event, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Lab for LabService {
+    async fn start_run(
+        &self,
+        request: Request<proto::StartRunRequest>,
+    ) -> Result<Response<proto::StartRunReply>, Status> {
+        let request = request.into_inner();
+        let scenario = request
+            .scenario_toml
+            .map(|toml| Scenario::from_toml(&toml))
+            .transpose()
+            .map_err(|_| Status::invalid_argument("invalid scenario"))?;
+
+        let run_id = self
+            .state
+            .start_run()
+            .map_err(|_| Status::resource_exhausted("run ids exhausted"))?;
+        let _ = self.state.publish_event(RuntimeEvent::run_started(run_id));
+        if let Some(scenario) = scenario {
+            let outcome = run_scenario(&SimConfig::default(), &scenario, request.seed);
+            self.state
+                .record_run_summary(run_id, outcome.report)
+                .map_err(|err| Status::internal(format!("run summary not saved: {err}")))?;
+        }
+        Ok(Response::new(proto::StartRunReply { run_id }))
+    }
+
+    async fn get_run_summary(
+        &self,
+        request: Request<proto::GetRunSummaryRequest>,
+    ) -> Result<Response<proto::RunSummary>, Status> {
+        let run_id = request.into_inner().run_id;
+        self.state
+            .run_summary(run_id)
+            .map(|report| Response::new(run_summary(report)))
+            .ok_or_else(|| Status::not_found(format!("no summary for run {run_id}")))
+    }
+
+    async fn get_settings(
+        &self,
+        _request: Request<proto::GetSettingsRequest>,
+    ) -> Result<Response<proto::Settings>, Status> {
+        Ok(Response::new(settings(self.state.runtime_settings())))
+    }
+
+    async fn update_settings(
+        &self,
+        request: Request<proto::SettingsPatch>,
+    ) -> Result<Response<proto::Settings>, Status> {
+        let patch = settings_patch(request.into_inner())?;
+        apply_settings_patch(&self.state, patch)
+            .map(|updated| Response::new(settings(updated)))
+            .map_err(Status::invalid_argument)
+    }
+
+    async fn get_portfolio(
+        &self,
+        _request: Request<proto::GetPortfolioRequest>,
+    ) -> Result<Response<proto::Portfolio>, Status> {
+        let summary = self.state.portfolio_summary();
+        let markets = self
+            .state
+            .market_portfolios()
+            .markets
+            .into_iter()
+            .map(|market| proto::MarketPosition {
+                source: market.source,
+                market_id: market.market_id,
+                position_qty: market.position_qty,
+                avg_px: market.avg_px,
+                mark_px: market.mark_px,
+                realized_pnl: market.realized_pnl.to_f64().unwrap_or_default(),
+                unrealized_pnl: market.unrealized_pnl.to_f64().unwrap_or_default(),
+            })
+            .collect();
+        Ok(Response::new(proto::Portfolio {
+            equity: summary.equity.to_f64().unwrap_or_default(),
+            pnl: summary.pnl.to_f64().unwrap_or_default(),
+            position_qty: summary.position_qty,
+            fills: summary.fills,
+            markets,
+        }))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        _request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let connected = tokio_stream::once(Arc::new(PublishedEvent::new(RuntimeEvent::connected(
+            self.state.active_run_id(),
+        ))));
+        // Lagged receivers drop what they missed, as the WebSocket does.
+        let published =
+            BroadcastStream::new(self.state.subscribe_events()).filter_map(|event| event.ok());
+        let events = connected
+            .chain(published)
+            .filter_map(|event| event_message(&event).map(Ok));
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+fn event_message(event: &PublishedEvent) -> Option<proto::Event> {
+    let json = serde_json::to_value(event).ok()?;
+    Some(proto::Event {
+        event_type: json["event_type"].as_str()?.to_string(),
+        json: json.to_string(),
+    })
+}
+
+fn run_summary(report: SimReport) -> proto::RunSummary {
+    proto::RunSummary {
+        total_return_pct: report.total_return_pct,
+        sharpe: report.sharpe,
+        max_drawdown_pct: report.max_drawdown_pct,
+        hit_rate: report.hit_rate,
+        avg_trade_pnl: report.avg_trade_pnl,
+        trades: report.trades,
+        lag_triggers: report.lag_triggers,
+        halt_events: report.halt_events,
+    }
+}
+
+fn settings(settings: RuntimeSettings) -> proto::Settings {
+    let execution_mode = match settings.execution_mode {
+        StateExecutionMode::Paper => proto::ExecutionMode::Paper,
+        StateExecutionMode::DryRun => proto::ExecutionMode::DryRun,
+        StateExecutionMode::Live => proto::ExecutionMode::Live,
+    };
+    let sizing_mode = match settings.sizing_mode {
+        StateSizingMode::Fixed => proto::SizingMode::Fixed,
+        StateSizingMode::Kelly => proto::SizingMode::Kelly,
+        StateSizingMode::Divergence => proto::SizingMode::Divergence,
+    };
+    proto::Settings {
+        execution_mode: execution_mode.into(),
+        trading_paused: settings.trading_paused,
+        lag_threshold_pct: settings.lag_threshold_pct,
+        risk_per_trade_pct: settings.risk_per_trade_pct,
+        daily_loss_cap_pct: settings.daily_loss_cap_pct,
+        lag_cooldown_secs: settings.lag_cooldown_secs,
+        lag_persistence_evals: settings.lag_persistence_evals,
+        market: settings.market,
+        forecast_horizon_minutes: settings.forecast_horizon_minutes.into(),
+        live_feature_enabled: settings.live_feature_enabled,
+        strategy: settings.strategy,
+        available_strategies: settings.available_strategies,
+        sizing_mode: sizing_mode.into(),
+        kelly_fraction: settings.kelly_fraction,
+        decision_interval_ms: settings.decision_interval_ms,
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn settings_patch(patch: proto::SettingsPatch) -> Result<RuntimeSettingsPatch, Status> {
+    let execution_mode = patch
+        .execution_mode
+        .map(|mode| match proto::ExecutionMode::try_from(mode) {
+            Ok(proto::ExecutionMode::Paper) => Ok(StateExecutionMode::Paper),
+            Ok(proto::ExecutionMode::DryRun) => Ok(StateExecutionMode::DryRun),
+            Ok(proto::ExecutionMode::Live) => Ok(StateExecutionMode::Live),
+            _ => Err(Status::invalid_argument(
+                "execution_mode must be paper, dry_run or live",
+            )),
+        })
+        .transpose()?;
+    let sizing_mode = patch
+        .sizing_mode
+        .map(|mode| match proto::SizingMode::try_from(mode) {
+            Ok(proto::SizingMode::Fixed) => Ok(StateSizingMode::Fixed),
+            Ok(proto::SizingMode::Kelly) => Ok(StateSizingMode::Kelly),
+            Ok(proto::SizingMode::Divergence) => Ok(StateSizingMode::Divergence),
+            _ => Err(Status::invalid_argument(
+                "sizing_mode must be fixed, kelly or divergence",
+            )),
+        })
+        .transpose()?;
+    Ok(RuntimeSettingsPatch {
+        execution_mode,
+        trading_paused: patch.trading_paused,
+        lag_threshold_pct: patch.lag_threshold_pct,
+        risk_per_trade_pct: patch.risk_per_trade_pct,
+        daily_loss_cap_pct: patch.daily_loss_cap_pct,
+        lag_cooldown_secs: patch.lag_cooldown_secs,
+        lag_persistence_evals: patch.lag_persistence_evals,
+        strategy: patch.strategy,
+        sizing_mode,
+        kelly_fraction: patch.kelly_fraction,
+        decision_interval_ms: patch.decision_interval_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::state::PaperOrderSide;
+
+    const SCENARIO: &str = r#"
+name = "flat"
+ticks = 50
+start_price = 0.5
+max_step = 0.0
+base_lag_ms = 120
+spread = 0.01
+"#;
+
+    #[tokio::test]
+    async fn starts_runs_and_serves_their_summaries() {
+        let service = LabService::new(AppState::new());
+
+        let plain = service
+            .start_run(Request::new(proto::StartRunRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        let missing = service
+            .get_run_summary(Request::new(proto::GetRunSummaryRequest {
+                run_id: plain.run_id,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), tonic::Code::NotFound);
+
+        let simulated = service
+            .start_run(Request::new(proto::StartRunRequest {
+                scenario_toml: Some(SCENARIO.to_string()),
+                seed: 7,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(simulated.run_id, plain.run_id + 1);
+        assert!(service
+            .get_run_summary(Request::new(proto::GetRunSummaryRequest {
+                run_id: simulated.run_id,
+            }))
+            .await
+            .is_ok());
+
+        let invalid = service
+            .start_run(Request::new(proto::StartRunRequest {
+                scenario_toml: Some("ticks = [".to_string()),
+                seed: 0,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn updates_settings_with_the_rest_checks() {
+        let service = LabService::new(AppState::new());
+
+        let updated = service
+            .update_settings(Request::new(proto::SettingsPatch {
+                trading_paused: Some(true),
+                sizing_mode: Some(proto::SizingMode::Kelly.into()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(updated.trading_paused);
+        assert_eq!(updated.sizing_mode(), proto::SizingMode::Kelly);
+
+        for patch in [
+            proto::SettingsPatch {
+                lag_threshold_pct: Some(0.0),
+                ..Default::default()
+            },
+            proto::SettingsPatch {
+                execution_mode: Some(proto::ExecutionMode::Live.into()),
+                ..Default::default()
+            },
+            proto::SettingsPatch {
+                sizing_mode: Some(proto::SizingMode::Unspecified.into()),
+                ..Default::default()
+            },
+        ] {
+            let err = service
+                .update_settings(Request::new(patch))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_published_events_as_json() {
+        let state = AppState::new();
+        let service = LabService::new(state.clone());
+
+        let mut events = service
+            .stream_events(Request::new(proto::StreamEventsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            events.next().await.unwrap().unwrap().event_type,
+            "connected"
+        );
+
+        state
+            .publish_event(RuntimeEvent::paper_fill(
+                "btc-a",
+                PaperOrderSide::Buy,
+                2.0,
+                0.5,
+                0.0,
+            ))
+            .unwrap();
+        let fill = events.next().await.unwrap().unwrap();
+        assert_eq!(fill.event_type, "paper_fill");
+        let payload: serde_json::Value = serde_json::from_str(&fill.json).unwrap();
+        assert_eq!(payload["market_id"], "btc-a");
+    }
+}
@@ -1,3 +1,6 @@
+pub mod export;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod routes;
 pub mod state;
 pub mod ws;
@@ -19,18 +22,23 @@ mod tests {
         http::{header, Request, StatusCode},
     };
     use futures_util::StreamExt;
+    use runtime::clock::{Clock, ManualClock};
+    use rust_decimal::Decimal;
     use serde::Deserialize;
     use serde_json::Value;
-    use std::time::Duration;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
     use tokio::net::TcpListener;
     use tokio_tungstenite::tungstenite::Message;
     use tower::ServiceExt;
 
     use crate::{
-        app, routes,
+        app, export, routes,
         state::{
-            AppState, DiscoveredMarket as StateDiscoveredMarket, FeedMode, PaperOrderSide,
-            RuntimeEvent, SourceCount as StateSourceCount,
+            AppState, DiscoveredMarket as StateDiscoveredMarket, FeedBlock, FeedErrorCount,
+            FeedErrorsResponse, FeedMode, PaperOrderSide, QuarantinedMessage, RiskAlertKind,
+            RiskStatus, RuntimeEvent, SignalAction, SourceCount as StateSourceCount,
+            StrategySignal,
         },
     };
 
@@ -50,18 +58,25 @@ mod tests {
     struct SourceCount {
         source: String,
         count: u64,
+        endpoint: Option<String>,
+        blocked: Option<Value>,
     }
 
     #[derive(Debug, Deserialize)]
     struct FeedHealthResponse {
         mode: FeedMode,
         source_counts: Vec<SourceCount>,
+        loop_alive: bool,
+        last_tick_age_ms: Option<u64>,
+        market_list_cache: Value,
     }
 
     #[derive(Debug, Deserialize)]
     struct DiscoveredMarket {
         source: String,
         market_id: String,
+        liquidity_score: Option<f64>,
+        selected: bool,
     }
 
     #[derive(Debug, Deserialize)]
@@ -78,6 +93,13 @@ mod tests {
         polymarket_yes_bid: Option<f64>,
         polymarket_yes_ask: Option<f64>,
         polymarket_yes_mid: Option<f64>,
+        kalshi_market_id: Option<String>,
+        kalshi_yes_bid: Option<f64>,
+        kalshi_yes_ask: Option<f64>,
+        kalshi_yes_mid: Option<f64>,
+        btc_composite_raw: Option<f64>,
+        btc_composite_smoothed: Option<f64>,
+        tick: u64,
         ts: u64,
     }
 
@@ -89,6 +111,8 @@ mod tests {
         intents_per_sec: u64,
         fills_per_sec: u64,
         lag_triggers: u64,
+        throttled_intents: u64,
+        spread_skips: u64,
         halted: bool,
     }
 
@@ -148,6 +172,7 @@ mod tests {
             PaperOrderSide::Buy,
             5.0,
             0.52,
+            0.01,
         ))
         .await
     }
@@ -217,6 +242,101 @@ mod tests {
         assert_eq!(result_three.location.as_deref(), Some("/runs/3"));
     }
 
+    #[tokio::test]
+    async fn post_runs_generates_a_scenario_from_the_body() {
+        let app = app();
+        let scenario = r#"
+name = "flash-crash"
+ticks = 50
+start_price = 0.5
+max_step = 0.0
+base_lag_ms = 120
+spread = 0.01
+
+[[events]]
+at_tick = 10
+kind = "flash_crash"
+drop_pct = 20.0
+
+[[events]]
+at_tick = 20
+kind = "feed_gap"
+duration_ticks = 5
+"#;
+        let request = |payload: String| {
+            Request::post("/runs")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(payload))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                serde_json::json!({ "scenario": scenario, "seed": 7 }).to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["run_id"], 1);
+        assert_eq!(payload["scenario"]["name"], "flash-crash");
+        assert_eq!(payload["scenario"]["gap_ticks"], 5);
+        let drawdown = payload["scenario"]["max_drawdown_pct"].as_f64().unwrap();
+        assert!((drawdown - 20.0).abs() < 1e-9, "drawdown was {drawdown}");
+
+        let summary = send_get(&app, "/runs/1/summary").await;
+        assert_eq!(summary.status(), StatusCode::OK);
+        let summary: Value = parse_json(summary).await;
+        assert!(summary["trades"].as_u64().unwrap() > 0);
+        assert!(summary["max_drawdown_pct"].as_f64().is_some());
+        assert_eq!(
+            send_get(&app, "/runs/2/summary").await.status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let invalid = app
+            .oneshot(request(
+                serde_json::json!({ "scenario": "ticks = \"many\"" }).to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn post_simulations_batch_reports_outcome_distributions() {
+        let app = app();
+        let request = |payload: Value| {
+            Request::post("/simulations/batch")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(payload.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                serde_json::json!({ "runs": 8, "ticks": 200, "seed": 3 }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["runs"], 8);
+        let halt_rate = payload["halt_rate"].as_f64().unwrap();
+        assert!((0.0..=1.0).contains(&halt_rate));
+        let equity = &payload["terminal_equity"];
+        assert!(equity["p5"].as_f64().unwrap() <= equity["p95"].as_f64().unwrap());
+        assert!(payload["max_drawdown_pct"]["max"].as_f64().unwrap() >= 0.0);
+
+        let empty = app
+            .oneshot(request(serde_json::json!({ "runs": 0, "ticks": 200 })))
+            .await
+            .unwrap();
+        assert_eq!(empty.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn get_feed_health_returns_mode_and_source_counts() {
         let app = app();
@@ -224,6 +344,28 @@ mod tests {
         assert_eq!(res.status(), 200);
     }
 
+    #[tokio::test]
+    async fn readyz_fails_until_the_decision_loop_ticks() {
+        let state = AppState::new();
+        let app = routes::router(state.clone());
+        assert_eq!(
+            send_get(&app, "/readyz").await.status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        state.record_loop_heartbeat(
+            1,
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        );
+        let response = send_get(&app, "/readyz").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["loop_alive"], true);
+    }
+
     #[tokio::test]
     async fn get_feed_health_returns_typed_payload() {
         let app = routes::router(AppState::with_feed_data_for_test(
@@ -232,15 +374,24 @@ mod tests {
                 StateSourceCount {
                     source: "polymarket".to_owned(),
                     count: 12,
+                    endpoint: None,
+                    blocked: None,
                 },
                 StateSourceCount {
                     source: "kalshi".to_owned(),
                     count: 4,
+                    endpoint: Some("wss://beta-ws.kraken.com/v2".to_owned()),
+                    blocked: Some(FeedBlock {
+                        status: 451,
+                        until_ms: 1_700_000_900_000,
+                    }),
                 },
             ],
             vec![StateDiscoveredMarket {
                 source: "polymarket".to_owned(),
                 market_id: "btc-up-down".to_owned(),
+                liquidity_score: None,
+                selected: true,
             }],
         ));
         let response = send_get(&app, "/feed/health").await;
@@ -248,11 +399,87 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
         let payload: FeedHealthResponse = parse_json(response).await;
         assert_eq!(payload.mode, FeedMode::Sim);
+        assert!(!payload.loop_alive);
+        assert_eq!(payload.last_tick_age_ms, None);
+        assert_eq!(
+            payload.market_list_cache,
+            serde_json::json!({ "not_modified": 0, "unchanged": 0, "changed": 0 })
+        );
         assert_eq!(payload.source_counts.len(), 2);
         assert_eq!(payload.source_counts[0].source, "polymarket");
         assert_eq!(payload.source_counts[0].count, 12);
         assert_eq!(payload.source_counts[1].source, "kalshi");
         assert_eq!(payload.source_counts[1].count, 4);
+        assert_eq!(payload.source_counts[0].endpoint, None);
+        assert_eq!(
+            payload.source_counts[1].endpoint.as_deref(),
+            Some("wss://beta-ws.kraken.com/v2")
+        );
+        assert_eq!(payload.source_counts[0].blocked, None);
+        assert_eq!(
+            payload.source_counts[1].blocked,
+            Some(serde_json::json!({ "status": 451, "until_ms": 1_700_000_900_000_u64 }))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_feed_errors_returns_counts_and_quarantined_payloads() {
+        let state = AppState::new();
+        state.set_feed_errors(FeedErrorsResponse {
+            counts: vec![FeedErrorCount {
+                source: "coinbase".to_owned(),
+                count: 3,
+            }],
+            recent: vec![QuarantinedMessage {
+                source: "coinbase".to_owned(),
+                reason: "invalid_price".to_owned(),
+                payload: r#"{"type":"match","price":"-1"}"#.to_owned(),
+                ts: 1_772_280_000_000,
+            }],
+        });
+        let app = routes::router(state);
+
+        let response = send_get(&app, "/feed/errors").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["counts"][0]["source"], "coinbase");
+        assert_eq!(payload["counts"][0]["count"], 3);
+        assert_eq!(payload["recent"][0]["reason"], "invalid_price");
+        assert_eq!(
+            payload["recent"][0]["payload"],
+            r#"{"type":"match","price":"-1"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn get_risk_status_reports_drawdown_and_var() {
+        let state = AppState::new();
+        state.set_risk_status(RiskStatus {
+            halted: false,
+            equity: Decimal::from(9_900),
+            peak_equity: Decimal::from(10_100),
+            drawdown_pct: 1.98,
+            var_95: Some(42.5),
+            var_limit: Some(100.0),
+            var_size_scale: 1.0,
+            var_samples: 12,
+            losing_streak: 2,
+            cooldown_resume_at_ms: None,
+        });
+        let app = routes::router(state);
+
+        let response = send_get(&app, "/risk/status").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["halted"], false);
+        assert_eq!(payload["peak_equity"].as_f64(), Some(10_100.0));
+        assert_eq!(payload["var_95"].as_f64(), Some(42.5));
+        assert_eq!(payload["var_limit"].as_f64(), Some(100.0));
+        assert_eq!(payload["var_samples"], 12);
+        assert_eq!(payload["losing_streak"], 2);
+        assert!(payload["cooldown_resume_at_ms"].is_null());
     }
 
     #[tokio::test]
@@ -262,15 +489,21 @@ mod tests {
             vec![StateSourceCount {
                 source: "polymarket".to_owned(),
                 count: 3,
+                endpoint: None,
+                blocked: None,
             }],
             vec![
                 StateDiscoveredMarket {
                     source: "polymarket".to_owned(),
                     market_id: "btc-up-down".to_owned(),
+                    liquidity_score: Some(0.82),
+                    selected: true,
                 },
                 StateDiscoveredMarket {
                     source: "polymarket".to_owned(),
-                    market_id: "eth-up-down".to_owned(),
+                    market_id: "btc-up-down-later".to_owned(),
+                    liquidity_score: Some(0.41),
+                    selected: false,
                 },
             ],
         ));
@@ -281,16 +514,19 @@ mod tests {
         assert_eq!(payload.markets.len(), 2);
         assert_eq!(payload.markets[0].source, "polymarket");
         assert_eq!(payload.markets[0].market_id, "btc-up-down");
+        assert_eq!(payload.markets[0].liquidity_score, Some(0.82));
+        assert!(payload.markets[0].selected);
         assert_eq!(payload.markets[1].source, "polymarket");
-        assert_eq!(payload.markets[1].market_id, "eth-up-down");
+        assert_eq!(payload.markets[1].market_id, "btc-up-down-later");
+        assert!(!payload.markets[1].selected);
     }
 
     #[tokio::test]
     async fn get_portfolio_summary_returns_typed_payload() {
         let state = AppState::new();
         state.set_portfolio_summary(crate::state::PortfolioSummary {
-            equity: 123.45,
-            pnl: 23.45,
+            equity: Decimal::new(12_345, 2),
+            pnl: Decimal::new(2_345, 2),
             position_qty: 7.0,
             fills: 42,
         });
@@ -306,6 +542,65 @@ mod tests {
         assert_eq!(payload["fills"].as_u64(), Some(42));
     }
 
+    #[tokio::test]
+    async fn get_portfolio_markets_lists_each_market_separately() {
+        let state = AppState::new();
+        state.set_market_portfolios(vec![
+            crate::state::MarketPortfolio {
+                source: "polymarket".to_owned(),
+                market_id: "btc-up-down".to_owned(),
+                position_qty: 10.0,
+                avg_px: 0.4,
+                mark_px: 0.55,
+                realized_pnl: Decimal::ZERO,
+                unrealized_pnl: Decimal::new(15, 1),
+            },
+            crate::state::MarketPortfolio {
+                source: "kalshi".to_owned(),
+                market_id: "KXBTC15M-A".to_owned(),
+                position_qty: 0.0,
+                avg_px: 0.0,
+                mark_px: 0.6,
+                realized_pnl: Decimal::new(-5, 1),
+                unrealized_pnl: Decimal::ZERO,
+            },
+        ]);
+        let app = routes::router(state);
+
+        let response = send_get(&app, "/portfolio/markets").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["markets"][0]["market_id"], "btc-up-down");
+        assert_eq!(payload["markets"][0]["unrealized_pnl"].as_f64(), Some(1.5));
+        assert_eq!(payload["markets"][1]["realized_pnl"].as_f64(), Some(-0.5));
+    }
+
+    #[tokio::test]
+    async fn get_pnl_daily_keeps_the_most_recent_days() {
+        let state = AppState::new();
+        for (date, pnl) in [("2024-03-01", -5), ("2024-03-02", 3), ("2024-03-03", 1)] {
+            state.push_daily_pnl(
+                crate::state::DayPnl {
+                    date: date.to_owned(),
+                    start_equity: Decimal::from(100),
+                    end_equity: Decimal::from(100 + pnl),
+                    pnl: Decimal::from(pnl),
+                },
+                2,
+            );
+        }
+        let app = routes::router(state);
+
+        let response = send_get(&app, "/pnl/daily").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["days"].as_array().map(Vec::len), Some(2));
+        assert_eq!(payload["days"][0]["date"], "2024-03-02");
+        assert_eq!(payload["days"][1]["pnl"].as_f64(), Some(1.0));
+    }
+
     #[tokio::test]
     async fn get_prices_snapshot_returns_typed_payload() {
         let state = AppState::new();
@@ -317,7 +612,15 @@ mod tests {
             polymarket_yes_bid: Some(0.481),
             polymarket_yes_ask: Some(0.487),
             polymarket_yes_mid: Some(0.484),
-            ts: 77,
+            kalshi_market_id: Some("KXBTC15M-TEST".to_owned()),
+            kalshi_yes_bid: Some(0.47),
+            kalshi_yes_ask: Some(0.51),
+            kalshi_yes_mid: Some(0.49),
+            btc_composite_raw: Some(64_101.0),
+            btc_composite_smoothed: Some(64_098.5),
+            assets: Vec::new(),
+            tick: 77,
+            ts: 1_700_000_000_077,
         });
         let app = routes::router(state);
 
@@ -332,7 +635,14 @@ mod tests {
         assert_eq!(payload.polymarket_yes_bid, Some(0.481));
         assert_eq!(payload.polymarket_yes_ask, Some(0.487));
         assert_eq!(payload.polymarket_yes_mid, Some(0.484));
-        assert_eq!(payload.ts, 77);
+        assert_eq!(payload.kalshi_market_id.as_deref(), Some("KXBTC15M-TEST"));
+        assert_eq!(payload.kalshi_yes_bid, Some(0.47));
+        assert_eq!(payload.kalshi_yes_ask, Some(0.51));
+        assert_eq!(payload.kalshi_yes_mid, Some(0.49));
+        assert_eq!(payload.btc_composite_raw, Some(64_101.0));
+        assert_eq!(payload.btc_composite_smoothed, Some(64_098.5));
+        assert_eq!(payload.tick, 77);
+        assert_eq!(payload.ts, 1_700_000_000_077);
     }
 
     #[tokio::test]
@@ -345,6 +655,8 @@ mod tests {
             intents_per_sec: 1200,
             fills_per_sec: 840,
             lag_triggers: 15,
+            throttled_intents: 4,
+            spread_skips: 9,
             halted: false,
         });
         let app = routes::router(state);
@@ -359,6 +671,8 @@ mod tests {
         assert_eq!(payload.intents_per_sec, 1200);
         assert_eq!(payload.fills_per_sec, 840);
         assert_eq!(payload.lag_triggers, 15);
+        assert_eq!(payload.throttled_intents, 4);
+        assert_eq!(payload.spread_skips, 9);
         assert!(!payload.halted);
     }
 
@@ -399,6 +713,192 @@ mod tests {
         assert_eq!(payload["daily_loss_cap_pct"].as_f64(), Some(2.5));
     }
 
+    #[tokio::test]
+    async fn settings_log_is_stamped_with_the_loop_tick_and_clock() {
+        let clock = Arc::new(ManualClock::new(1_700_000_000_000));
+        let state = AppState::new().with_clock(clock.clone());
+        state.record_loop_heartbeat(42, clock.now_ms());
+        clock.advance(250);
+        let app = routes::router(state.clone());
+
+        let response = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "trading_paused": true }),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let log = state.execution_logs().pop().unwrap();
+        assert_eq!(log.event, "settings_update");
+        assert_eq!((log.tick, log.ts), (42, 1_700_000_000_250));
+    }
+
+    #[tokio::test]
+    async fn patch_settings_bounds_the_decision_interval() {
+        let app = app();
+
+        let rejected = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "decision_interval_ms": 50 }),
+        )
+        .await;
+        let accepted = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "decision_interval_ms": 750 }),
+        )
+        .await;
+
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(accepted.status(), StatusCode::OK);
+        let payload: Value = parse_json(accepted).await;
+        assert_eq!(payload["decision_interval_ms"], 750);
+    }
+
+    #[tokio::test]
+    async fn patch_settings_rejects_inconsistent_combinations() {
+        let app = app();
+
+        let risk_above_cap = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "risk_per_trade_pct": 3.0 }),
+        )
+        .await;
+        let unreachable_lag = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "lag_threshold_pct": 99.0 }),
+        )
+        .await;
+        let raised_together = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "risk_per_trade_pct": 3.0, "daily_loss_cap_pct": 4.0 }),
+        )
+        .await;
+
+        assert_eq!(risk_above_cap.status(), StatusCode::BAD_REQUEST);
+        let payload: Value = parse_json(risk_above_cap).await;
+        let message = payload["error"].as_str().unwrap();
+        assert!(message.contains("risk_per_trade_pct (3)"));
+        assert!(message.contains("daily_loss_cap_pct (2)"));
+        assert_eq!(unreachable_lag.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(raised_together.status(), StatusCode::OK);
+        let payload: Value = parse_json(raised_together).await;
+        assert_eq!(payload["risk_per_trade_pct"].as_f64(), Some(3.0));
+    }
+
+    #[tokio::test]
+    async fn patch_settings_rejects_zero_lag_persistence() {
+        let app = app();
+
+        let response = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({
+                "lag_cooldown_secs": 10,
+                "lag_persistence_evals": 0
+            }),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn patch_settings_selects_only_registered_strategies() {
+        let state = AppState::new();
+        state.set_runtime_settings(crate::state::RuntimeSettings {
+            available_strategies: vec!["lag_divergence".to_string(), "spread_momentum".to_string()],
+            ..crate::state::RuntimeSettings::default()
+        });
+        let app = routes::router(state);
+
+        let rejected = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "strategy": "martingale" }),
+        )
+        .await;
+        let accepted = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "strategy": "spread_momentum" }),
+        )
+        .await;
+
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(accepted.status(), StatusCode::OK);
+        let payload: Value = parse_json(accepted).await;
+        assert_eq!(payload["strategy"], "spread_momentum");
+    }
+
+    #[tokio::test]
+    async fn patch_settings_switches_to_kelly_sizing() {
+        let app = app();
+
+        let rejected = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "sizing_mode": "kelly", "kelly_fraction": 1.5 }),
+        )
+        .await;
+        let accepted = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({ "sizing_mode": "kelly", "kelly_fraction": 0.25 }),
+        )
+        .await;
+
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(accepted.status(), StatusCode::OK);
+        let payload: Value = parse_json(accepted).await;
+        assert_eq!(payload["sizing_mode"], "kelly");
+        assert_eq!(payload["kelly_fraction"].as_f64(), Some(0.25));
+    }
+
+    #[tokio::test]
+    async fn strategy_params_round_trip_and_reject_out_of_range_lookback() {
+        let app = app();
+
+        let defaults: Value = parse_json(send_get(&app, "/strategy/params").await).await;
+        let rejected = send_patch_json(
+            &app,
+            "/strategy/params",
+            serde_json::json!({ "momentum_lookback_secs": 0 }),
+        )
+        .await;
+        let rejected_exit = send_patch_json(
+            &app,
+            "/strategy/params",
+            serde_json::json!({ "exit_threshold_fraction": 1.0 }),
+        )
+        .await;
+        let accepted = send_patch_json(
+            &app,
+            "/strategy/params",
+            serde_json::json!({
+                "momentum_lookback_secs": 120,
+                "momentum_threshold_bps": 8.5,
+                "exit_threshold_fraction": 0.25
+            }),
+        )
+        .await;
+
+        assert_eq!(defaults["momentum_lookback_secs"], 60);
+        assert_eq!(defaults["exit_threshold_fraction"].as_f64(), Some(0.5));
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(rejected_exit.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(accepted.status(), StatusCode::OK);
+        let payload: Value = parse_json(send_get(&app, "/strategy/params").await).await;
+        assert_eq!(payload["momentum_lookback_secs"], 120);
+        assert_eq!(payload["momentum_threshold_bps"].as_f64(), Some(8.5));
+        assert_eq!(payload["exit_threshold_fraction"].as_f64(), Some(0.25));
+    }
+
     #[tokio::test]
     async fn patch_settings_rejects_live_mode_when_feature_disabled() {
         let app = app();
@@ -415,6 +915,24 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn patch_settings_allows_dry_run_mode_when_feature_disabled() {
+        let app = app();
+
+        let response = send_patch_json(
+            &app,
+            "/settings",
+            serde_json::json!({
+                "execution_mode": "dry_run"
+            }),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        assert_eq!(payload["execution_mode"], "dry_run");
+    }
+
     #[tokio::test]
     async fn get_strategy_stats_returns_top_kpis() {
         let app = app();
@@ -430,6 +948,85 @@ mod tests {
         assert!(payload.get("btc_usd").is_some());
     }
 
+    #[tokio::test]
+    async fn get_strategy_signals_lists_evaluations_without_trades() {
+        let state = AppState::new();
+        state.push_strategy_signal(
+            StrategySignal {
+                tick: 7,
+                ts: 1_700_000_000_000,
+                source: "polymarket".to_string(),
+                market_id: "btc-up".to_string(),
+                mid_yes: 0.5,
+                fair_yes_px: 0.52,
+                divergence_pct: 4.0,
+                triggered: true,
+                action: SignalAction::SpreadSkip,
+            },
+            500,
+        );
+        let app = routes::router(state);
+
+        let response = send_get(&app, "/strategy/signals").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload: Value = parse_json(response).await;
+        let signals = payload["signals"].as_array().unwrap();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0]["market_id"], "btc-up");
+        assert_eq!(signals[0]["triggered"], true);
+        assert_eq!(signals[0]["action"], "spread_skip");
+    }
+
+    #[tokio::test]
+    async fn export_routes_stream_journal_lines_in_range() {
+        let state = AppState::new();
+        assert_eq!(
+            send_get(&routes::router(state.clone()), "/events/export")
+                .await
+                .status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let dir = std::env::temp_dir().join(format!("api-export-route-{}", std::process::id()));
+        let mut journal = export::JsonlJournal::open(&dir.join(export::EVENTS_FILE)).unwrap();
+        for (recorded_ms, run_id) in [(1_000, 1), (2_000, 2), (3_000, 3)] {
+            journal
+                .append(recorded_ms, &RuntimeEvent::run_started(run_id))
+                .unwrap();
+        }
+        drop(journal);
+        state.set_export_dir(dir.clone());
+        let app = routes::router(state);
+
+        let response = send_get(&app, "/events/export?from_ms=1500&to_ms=3000").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/x-ndjson"
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let lines: Vec<Value> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["run_id"], 2);
+
+        let journal = send_get(&app, "/journal/export").await;
+        assert_eq!(journal.status(), StatusCode::OK);
+        assert!(to_bytes(journal.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            send_get(&app, "/events/export?from_ms=soon").await.status(),
+            StatusCode::BAD_REQUEST
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[tokio::test]
     async fn get_btc_15m_forecast_returns_fixed_horizon_payload() {
         let app = app();
@@ -544,6 +1141,37 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn websocket_connected_event_names_the_active_run() {
+        let state = AppState::new();
+        state.start_run().unwrap();
+        let run_id = state.start_run().unwrap();
+        let app = routes::router(state.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let url = format!("ws://{addr}/ws/events");
+        let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let payload = match message {
+            Message::Text(text) => text,
+            other => panic!("expected text websocket message, got {other:?}"),
+        };
+        let value: Value = serde_json::from_str(payload.as_ref()).unwrap();
+        assert_eq!(value["event_type"], "connected");
+        assert_eq!(value["run_id"].as_u64(), Some(run_id));
+
+        server.abort();
+    }
+
     #[tokio::test]
     async fn websocket_forwards_published_events() {
         let state = AppState::new();
@@ -586,6 +1214,57 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn websocket_batches_events_within_the_requested_window() {
+        let state = AppState::new();
+        let app = routes::router(state.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        assert!(
+            tokio_tungstenite::connect_async(format!("ws://{addr}/ws/events?batch_ms=5000"))
+                .await
+                .is_err()
+        );
+
+        let url = format!("ws://{addr}/ws/events?batch_ms=200");
+        let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let mut next_frame = async || {
+            let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            match message {
+                Message::Text(text) => serde_json::from_str::<Value>(text.as_ref()).unwrap(),
+                other => panic!("expected text websocket message, got {other:?}"),
+            }
+        };
+
+        let connected = next_frame().await;
+        assert_eq!(connected[0]["event_type"], "connected");
+
+        for run_id in [1, 2, 3] {
+            state
+                .publish_event(RuntimeEvent::run_started(run_id))
+                .unwrap();
+        }
+        let batch = next_frame().await;
+        let run_ids: Vec<u64> = batch
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["run_id"].as_u64().unwrap())
+            .collect();
+        assert_eq!(run_ids, vec![1, 2, 3]);
+
+        server.abort();
+    }
+
     #[tokio::test]
     async fn websocket_emits_paper_fill_event_payload() {
         let msg = next_ws_json().await;
@@ -594,6 +1273,7 @@ mod tests {
         assert_eq!(msg["side"], "buy");
         assert_eq!(msg["qty"].as_f64(), Some(5.0));
         assert_eq!(msg["fill_px"].as_f64(), Some(0.52));
+        assert_eq!(msg["fee"].as_f64(), Some(0.01));
     }
 
     #[tokio::test]
@@ -628,6 +1308,129 @@ mod tests {
         assert!(msg["requested_qty"].as_f64().is_some());
     }
 
+    #[tokio::test]
+    async fn websocket_emits_feed_reconnect_event_payload() {
+        let msg = next_ws_json_for_event(RuntimeEvent::feed_reconnect(
+            "okx",
+            3,
+            1_500,
+            false,
+            "idle_timeout",
+        ))
+        .await;
+
+        assert_eq!(msg["event_type"], "feed_reconnect");
+        assert_eq!(msg["source"], "okx");
+        assert_eq!(msg["attempt"].as_u64(), Some(3));
+        assert_eq!(msg["delay_ms"].as_u64(), Some(1_500));
+        assert_eq!(msg["circuit_open"].as_bool(), Some(false));
+        assert_eq!(msg["reason"], "idle_timeout");
+    }
+
+    #[tokio::test]
+    async fn websocket_emits_feed_throttle_event_payload() {
+        let msg = next_ws_json_for_event(RuntimeEvent::feed_throttle(
+            "gamma-api.polymarket.com",
+            "retry_after",
+            Some(12_000),
+        ))
+        .await;
+
+        assert_eq!(msg["event_type"], "feed_throttle");
+        assert_eq!(msg["host"], "gamma-api.polymarket.com");
+        assert_eq!(msg["reason"], "retry_after");
+        assert_eq!(msg["retry_after_ms"].as_u64(), Some(12_000));
+    }
+
+    #[tokio::test]
+    async fn websocket_emits_bracket_triggered_event_payload() {
+        let msg = next_ws_json_for_event(RuntimeEvent::bracket_triggered(
+            "btc-updown-15m-1700000000",
+            "stop_loss",
+            0.5,
+            0.39,
+            0.38,
+            -10.0,
+            Decimal::new(-12, 1),
+        ))
+        .await;
+
+        assert_eq!(msg["event_type"], "bracket_triggered");
+        assert_eq!(msg["market_id"], "btc-updown-15m-1700000000");
+        assert_eq!(msg["kind"], "stop_loss");
+        assert_eq!(msg["trigger_px"].as_f64(), Some(0.39));
+        assert_eq!(msg["qty"].as_f64(), Some(-10.0));
+        assert_eq!(msg["realized_pnl"].as_f64(), Some(-1.2));
+    }
+
+    #[tokio::test]
+    async fn websocket_emits_cooldown_started_event_payload() {
+        let msg =
+            next_ws_json_for_event(RuntimeEvent::cooldown_started(3, 1_700_000_300_000)).await;
+
+        assert_eq!(msg["event_type"], "cooldown_started");
+        assert_eq!(msg["losing_streak"].as_u64(), Some(3));
+        assert_eq!(msg["resume_at_ms"].as_u64(), Some(1_700_000_300_000));
+    }
+
+    #[tokio::test]
+    async fn websocket_emits_strategy_signal_event_payload() {
+        let msg = next_ws_json_for_event(RuntimeEvent::strategy_signal(StrategySignal {
+            tick: 3,
+            ts: 1_700_000_000_000,
+            source: "kalshi".to_string(),
+            market_id: "btc-down".to_string(),
+            mid_yes: 0.4,
+            fair_yes_px: 0.401,
+            divergence_pct: 0.25,
+            triggered: false,
+            action: SignalAction::NoIntent,
+        }))
+        .await;
+
+        assert_eq!(msg["event_type"], "strategy_signal");
+        assert_eq!(msg["source"], "kalshi");
+        assert_eq!(msg["divergence_pct"].as_f64(), Some(0.25));
+        assert_eq!(msg["triggered"], false);
+        assert_eq!(msg["action"], "no_intent");
+    }
+
+    #[tokio::test]
+    async fn websocket_emits_risk_alert_event_payload() {
+        let msg = next_ws_json_for_event(RuntimeEvent::risk_alert(
+            RiskAlertKind::DailyCapBreach,
+            "pnl=-21.00 cap=20.00",
+            1_700_000_000_000,
+        ))
+        .await;
+
+        assert_eq!(msg["event_type"], "risk_alert");
+        assert_eq!(msg["kind"], "daily_cap_breach");
+        assert_eq!(msg["detail"], "pnl=-21.00 cap=20.00");
+        assert_eq!(msg["ts"].as_u64(), Some(1_700_000_000_000));
+    }
+
+    #[tokio::test]
+    async fn websocket_emits_market_resolved_event_payload() {
+        let msg = next_ws_json_for_event(RuntimeEvent::market_resolved(
+            "polymarket",
+            "btc-updown-15m-1700000000",
+            "yes",
+            1.0,
+            12.5,
+            Decimal::new(475, 2),
+        ))
+        .await;
+
+        assert_eq!(msg["event_type"], "market_resolved");
+        assert_eq!(msg["source"], "polymarket");
+        assert_eq!(msg["market_id"], "btc-updown-15m-1700000000");
+        assert_eq!(msg["outcome"], "yes");
+        assert_eq!(msg["settle_px"].as_f64(), Some(1.0));
+        assert_eq!(msg["qty"].as_f64(), Some(12.5));
+        assert_eq!(msg["realized_pnl"].as_f64(), Some(4.75));
+    }
+
     #[tokio::test]
     async fn websocket_emits_price_snapshot_event_payload() {
         let msg =
@@ -639,7 +1442,24 @@ mod tests {
                 polymarket_yes_bid: Some(0.49),
                 polymarket_yes_ask: Some(0.51),
                 polymarket_yes_mid: Some(0.50),
-                ts: 901,
+                kalshi_market_id: None,
+                kalshi_yes_bid: None,
+                kalshi_yes_ask: None,
+                kalshi_yes_mid: None,
+                btc_composite_raw: Some(64_122.1),
+                btc_composite_smoothed: Some(64_120.0),
+                assets: vec![crate::state::AssetPriceSnapshot {
+                    asset: "eth".to_owned(),
+                    composite_raw: Some(3_401.5),
+                    composite_smoothed: Some(3_400.9),
+                    forecast_15m: Some(3_404.2),
+                    polymarket_market_id: Some("eth-updown-15m".to_owned()),
+                    polymarket_yes_mid: Some(0.53),
+                    kalshi_market_id: None,
+                    kalshi_yes_mid: None,
+                }],
+                tick: 901,
+                ts: 1_700_000_000_901,
             }))
             .await;
 
@@ -651,7 +1471,14 @@ mod tests {
         assert_eq!(msg["polymarket_yes_bid"].as_f64(), Some(0.49));
         assert_eq!(msg["polymarket_yes_ask"].as_f64(), Some(0.51));
         assert_eq!(msg["polymarket_yes_mid"].as_f64(), Some(0.50));
-        assert_eq!(msg["ts"].as_u64(), Some(901));
+        assert!(msg["kalshi_market_id"].is_null());
+        assert_eq!(msg["btc_composite_smoothed"].as_f64(), Some(64_120.0));
+        assert_eq!(msg["assets"][0]["asset"], "eth");
+        assert_eq!(msg["assets"][0]["composite_raw"].as_f64(), Some(3_401.5));
+        assert_eq!(msg["assets"][0]["polymarket_market_id"], "eth-updown-15m");
+        assert!(msg["assets"][0]["kalshi_yes_mid"].is_null());
+        assert_eq!(msg["tick"].as_u64(), Some(901));
+        assert_eq!(msg["ts"].as_u64(), Some(1_700_000_000_901));
     }
 
     #[tokio::test]
@@ -664,6 +1491,8 @@ mod tests {
                 intents_per_sec: 1400,
                 fills_per_sec: 990,
                 lag_triggers: 22,
+                throttled_intents: 0,
+                spread_skips: 2,
                 halted: false,
             },
         ))
@@ -676,6 +1505,8 @@ mod tests {
         assert_eq!(msg["intents_per_sec"].as_u64(), Some(1400));
         assert_eq!(msg["fills_per_sec"].as_u64(), Some(990));
         assert_eq!(msg["lag_triggers"].as_u64(), Some(22));
+        assert_eq!(msg["throttled_intents"].as_u64(), Some(0));
+        assert_eq!(msg["spread_skips"].as_u64(), Some(2));
         assert_eq!(msg["halted"].as_bool(), Some(false));
     }
 }
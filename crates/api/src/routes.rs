@@ -1,19 +1,23 @@
 use axum::{
-    extract::State,
+    body::Bytes,
+    extract::{Path, Query, State},
     http::{header, StatusCode},
-    response::{Html, IntoResponse},
+    response::{Html, IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-use serde::Serialize;
+use core_sim::{run_batch, run_scenario, Distribution, Scenario, SimConfig, SimReport};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::{
+    export::{self, ExportRange},
     state::{
-        AppState, BtcForecastSummary, DiscoveredMarketsResponse, ExecutionLogEntry,
-        FeedHealthResponse, PortfolioSummary, PriceSnapshot, RuntimeEvent, RuntimeSettings,
-        RuntimeSettingsPatch, StrategyPerfSummary, StrategyStatsSummary,
+        AppState, BtcForecastSummary, DailyPnlResponse, DiscoveredMarketsResponse,
+        ExecutionLogEntry, FeedErrorsResponse, FeedHealthResponse, MarketPortfoliosResponse,
+        PortfolioSummary, PriceSnapshot, RiskStatus, RuntimeEvent, RuntimeSettings,
+        RuntimeSettingsPatch, StrategyParams, StrategyParamsPatch, StrategyPerfSummary,
+        StrategySignal, StrategyStatsSummary,
     },
     ws,
 };
@@ -21,16 +25,30 @@ use crate::{
 pub fn router(state: AppState) -> Router {
     Router::new()
         .route("/", get(dashboard_index))
+        .route("/readyz", get(readyz))
         .route("/feed/health", get(feed_health))
+        .route("/feed/errors", get(feed_errors))
         .route("/markets/discovered", get(markets_discovered))
         .route("/prices/snapshot", get(prices_snapshot))
         .route("/settings", get(settings_get).patch(settings_patch))
+        .route(
+            "/strategy/params",
+            get(strategy_params_get).patch(strategy_params_patch),
+        )
         .route("/strategy/perf", get(strategy_perf))
+        .route("/risk/status", get(risk_status))
         .route("/strategy/stats", get(strategy_stats))
+        .route("/strategy/signals", get(strategy_signals))
         .route("/forecast/btc-15m", get(btc_forecast_15m))
         .route("/logs/execution", get(execution_logs))
+        .route("/events/export", get(events_export))
+        .route("/journal/export", get(journal_export))
         .route("/portfolio/summary", get(portfolio_summary))
+        .route("/portfolio/markets", get(portfolio_markets))
+        .route("/pnl/daily", get(pnl_daily))
         .route("/runs", post(start_run))
+        .route("/runs/:run_id/summary", get(run_summary))
+        .route("/simulations/batch", post(simulate_batch))
         .route("/static/styles.css", get(dashboard_styles))
         .route("/static/app.js", get(dashboard_script))
         .route("/ws/events", get(ws::events_socket))
@@ -58,10 +76,26 @@ async fn dashboard_script() -> impl IntoResponse {
     )
 }
 
+/// 503 while the decision loop is not ticking, so orchestrators stop routing
+/// to a server whose loop has died.
+async fn readyz(State(state): State<AppState>) -> impl IntoResponse {
+    let liveness = state.loop_liveness(state.now_ms());
+    let status = if liveness.loop_alive {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(liveness))
+}
+
 async fn feed_health(State(state): State<AppState>) -> Json<FeedHealthResponse> {
     Json(state.feed_health())
 }
 
+async fn feed_errors(State(state): State<AppState>) -> Json<FeedErrorsResponse> {
+    Json(state.feed_errors())
+}
+
 async fn markets_discovered(State(state): State<AppState>) -> Json<DiscoveredMarketsResponse> {
     Json(state.discovered_markets())
 }
@@ -70,6 +104,14 @@ async fn portfolio_summary(State(state): State<AppState>) -> Json<PortfolioSumma
     Json(state.portfolio_summary())
 }
 
+async fn portfolio_markets(State(state): State<AppState>) -> Json<MarketPortfoliosResponse> {
+    Json(state.market_portfolios())
+}
+
+async fn pnl_daily(State(state): State<AppState>) -> Json<DailyPnlResponse> {
+    Json(state.daily_pnl())
+}
+
 async fn prices_snapshot(State(state): State<AppState>) -> Json<PriceSnapshot> {
     Json(state.price_snapshot())
 }
@@ -78,6 +120,10 @@ async fn strategy_perf(State(state): State<AppState>) -> Json<StrategyPerfSummar
     Json(state.strategy_perf_summary())
 }
 
+async fn risk_status(State(state): State<AppState>) -> Json<RiskStatus> {
+    Json(state.risk_status())
+}
+
 async fn strategy_stats(State(state): State<AppState>) -> Json<StrategyStatsSummary> {
     Json(state.strategy_stats_summary())
 }
@@ -94,37 +140,58 @@ async fn settings_patch(
     State(state): State<AppState>,
     Json(patch): Json<RuntimeSettingsPatch>,
 ) -> Result<Json<RuntimeSettings>, (StatusCode, Json<serde_json::Value>)> {
-    validate_settings_patch(&state, &patch).map_err(|message| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(json!({ "error": message.to_string() })),
-        )
-    })?;
+    apply_settings_patch(&state, patch)
+        .map(Json)
+        .map_err(|message| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": message.to_string() })),
+            )
+        })
+}
+
+/// Validates and applies `patch`, logs the new settings and publishes them.
+/// Patches that leave the settings inconsistent as a whole are refused.
+pub(crate) fn apply_settings_patch(
+    state: &AppState,
+    patch: RuntimeSettingsPatch,
+) -> Result<RuntimeSettings, String> {
+    validate_settings_patch(state, &patch)?;
 
-    let settings = state.patch_runtime_settings(patch);
+    let settings = state
+        .try_patch_runtime_settings(patch)
+        .map_err(|conflict| conflict.to_string())?;
     let log = ExecutionLogEntry {
-        ts: SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|duration| duration.as_secs())
-            .unwrap_or(0),
+        tick: state.loop_tick(),
+        ts: state.now_ms(),
         event: "settings_update".to_string(),
         headline: "Settings Updated".to_string(),
         detail: format!(
-            "mode={} paused={} lag={} risk={} daily_cap={}",
+            "mode={} paused={} lag={} risk={} daily_cap={} cooldown={}s persistence={} strategy={} sizing={} interval={}ms",
             match settings.execution_mode {
                 crate::state::ExecutionMode::Paper => "paper",
+                crate::state::ExecutionMode::DryRun => "dry_run",
                 crate::state::ExecutionMode::Live => "live",
             },
             settings.trading_paused,
             settings.lag_threshold_pct,
             settings.risk_per_trade_pct,
             settings.daily_loss_cap_pct,
+            settings.lag_cooldown_secs,
+            settings.lag_persistence_evals,
+            settings.strategy,
+            match settings.sizing_mode {
+                crate::state::SizingMode::Fixed => "fixed".to_string(),
+                crate::state::SizingMode::Kelly => format!("kelly x{}", settings.kelly_fraction),
+                crate::state::SizingMode::Divergence => "divergence".to_string(),
+            },
+            settings.decision_interval_ms,
         ),
     };
     state.push_execution_log(log.clone(), 500);
     let _ = state.publish_event(RuntimeEvent::execution_log(log));
     let _ = state.publish_event(RuntimeEvent::settings_updated(settings.clone()));
-    Ok(Json(settings))
+    Ok(settings)
 }
 
 fn validate_settings_patch(
@@ -149,6 +216,41 @@ fn validate_settings_patch(
         }
     }
 
+    if let Some(value) = patch.lag_cooldown_secs {
+        if value > 3_600 {
+            return Err("lag_cooldown_secs must be <= 3600");
+        }
+    }
+
+    if let Some(value) = patch.lag_persistence_evals {
+        if !(1..=100).contains(&value) {
+            return Err("lag_persistence_evals must be >= 1 and <= 100");
+        }
+    }
+
+    if let Some(value) = patch.kelly_fraction {
+        if !value.is_finite() || value <= 0.0 || value > 1.0 {
+            return Err("kelly_fraction must be > 0 and <= 1");
+        }
+    }
+
+    if let Some(value) = patch.decision_interval_ms {
+        if !(100..=60_000).contains(&value) {
+            return Err("decision_interval_ms must be >= 100 and <= 60000");
+        }
+    }
+
+    if let Some(strategy) = patch.strategy.as_deref() {
+        let settings = state.runtime_settings();
+        if !settings
+            .available_strategies
+            .iter()
+            .any(|name| name == strategy)
+        {
+            return Err("strategy must be one of available_strategies");
+        }
+    }
+
     if let Some(crate::state::ExecutionMode::Live) = patch.execution_mode {
         let settings = state.runtime_settings();
         if !settings.live_feature_enabled {
@@ -159,6 +261,61 @@ fn validate_settings_patch(
     Ok(())
 }
 
+async fn strategy_params_get(State(state): State<AppState>) -> Json<StrategyParams> {
+    Json(state.strategy_params())
+}
+
+async fn strategy_params_patch(
+    State(state): State<AppState>,
+    Json(patch): Json<StrategyParamsPatch>,
+) -> Result<Json<StrategyParams>, (StatusCode, Json<serde_json::Value>)> {
+    validate_strategy_params_patch(&patch).map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": message.to_string() })),
+        )
+    })?;
+
+    let params = state.patch_strategy_params(patch);
+    let log = ExecutionLogEntry {
+        tick: state.loop_tick(),
+        ts: state.now_ms(),
+        event: "strategy_params_update".to_string(),
+        headline: "Strategy Params Updated".to_string(),
+        detail: format!(
+            "momentum_lookback={}s momentum_threshold={}bps exit_threshold_fraction={}",
+            params.momentum_lookback_secs,
+            params.momentum_threshold_bps,
+            params.exit_threshold_fraction,
+        ),
+    };
+    state.push_execution_log(log.clone(), 500);
+    let _ = state.publish_event(RuntimeEvent::execution_log(log));
+    Ok(Json(params))
+}
+
+fn validate_strategy_params_patch(patch: &StrategyParamsPatch) -> Result<(), &'static str> {
+    if let Some(value) = patch.momentum_lookback_secs {
+        if !(1..=900).contains(&value) {
+            return Err("momentum_lookback_secs must be >= 1 and <= 900");
+        }
+    }
+
+    if let Some(value) = patch.momentum_threshold_bps {
+        if !value.is_finite() || value <= 0.0 || value > 1_000.0 {
+            return Err("momentum_threshold_bps must be > 0 and <= 1000");
+        }
+    }
+
+    if let Some(value) = patch.exit_threshold_fraction {
+        if !value.is_finite() || !(0.0..1.0).contains(&value) {
+            return Err("exit_threshold_fraction must be >= 0 and < 1");
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Serialize)]
 struct ExecutionLogsResponse {
     logs: Vec<ExecutionLogEntry>,
@@ -170,21 +327,237 @@ async fn execution_logs(State(state): State<AppState>) -> Json<ExecutionLogsResp
     })
 }
 
+#[derive(Debug, Serialize)]
+struct StrategySignalsResponse {
+    signals: Vec<StrategySignal>,
+}
+
+async fn strategy_signals(State(state): State<AppState>) -> Json<StrategySignalsResponse> {
+    Json(StrategySignalsResponse {
+        signals: state.strategy_signals(),
+    })
+}
+
+async fn events_export(
+    State(state): State<AppState>,
+    Query(range): Query<ExportRange>,
+) -> Result<Response, StatusCode> {
+    export_jsonl(&state, export::EVENTS_FILE, range).await
+}
+
+async fn journal_export(
+    State(state): State<AppState>,
+    Query(range): Query<ExportRange>,
+) -> Result<Response, StatusCode> {
+    export_jsonl(&state, export::JOURNAL_FILE, range).await
+}
+
+/// Streams the records of one journal file that fall in `range`, as
+/// newline-delimited JSON. 404 when no export directory is configured.
+async fn export_jsonl(
+    state: &AppState,
+    file_name: &str,
+    range: ExportRange,
+) -> Result<Response, StatusCode> {
+    let dir = state.export_dir().ok_or(StatusCode::NOT_FOUND)?;
+    let lines = export::read_range(&dir.join(file_name), range)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        axum::body::Body::from_stream(lines),
+    )
+        .into_response())
+}
+
+/// Optional `POST /runs` body: a TOML stress scenario to generate.
+#[derive(Debug, Deserialize)]
+struct StartRunRequest {
+    scenario: String,
+    #[serde(default)]
+    seed: u64,
+}
+
 #[derive(Debug, Serialize)]
 struct StartRunResponse {
     run_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scenario: Option<ScenarioRunSummary>,
+}
+
+#[derive(Debug, Serialize)]
+struct ScenarioRunSummary {
+    name: String,
+    seed: u64,
+    ticks: u64,
+    gap_ticks: u64,
+    min_price: f64,
+    max_price: f64,
+    /// Largest peak-to-trough fall in price, as a percentage of the peak.
+    max_drawdown_pct: f64,
+    max_spread: f64,
+    max_lag_ms: u64,
 }
 
-async fn start_run(State(state): State<AppState>) -> Result<impl IntoResponse, StatusCode> {
+impl ScenarioRunSummary {
+    fn run(scenario: &Scenario, seed: u64) -> Self {
+        let ticks = scenario.run(seed);
+        let mut summary = Self {
+            name: scenario.name.clone(),
+            seed,
+            ticks: ticks.len() as u64,
+            gap_ticks: 0,
+            min_price: f64::INFINITY,
+            max_price: 0.0,
+            max_drawdown_pct: 0.0,
+            max_spread: 0.0,
+            max_lag_ms: 0,
+        };
+        let mut peak = 0.0_f64;
+        for tick in &ticks {
+            summary.max_spread = summary.max_spread.max(tick.spread);
+            summary.max_lag_ms = summary.max_lag_ms.max(tick.lag_ms);
+            let Some(price) = tick.price else {
+                summary.gap_ticks += 1;
+                continue;
+            };
+            summary.min_price = summary.min_price.min(price);
+            summary.max_price = summary.max_price.max(price);
+            peak = peak.max(price);
+            if peak > 0.0 {
+                summary.max_drawdown_pct =
+                    summary.max_drawdown_pct.max((peak - price) / peak * 100.0);
+            }
+        }
+        if !summary.min_price.is_finite() {
+            summary.min_price = 0.0;
+        }
+        summary
+    }
+}
+
+const MAX_BATCH_RUNS: u64 = 10_000;
+const MAX_BATCH_TICKS: u64 = 100_000;
+
+#[derive(Debug, Deserialize)]
+struct BatchSimulationRequest {
+    runs: u64,
+    ticks: u64,
+    #[serde(default)]
+    seed: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchSimulationResponse {
+    runs: u64,
+    ticks: u64,
+    seed: u64,
+    halted_runs: u64,
+    halt_rate: f64,
+    terminal_equity: DistributionSummary,
+    max_drawdown_pct: DistributionSummary,
+}
+
+#[derive(Debug, Serialize)]
+struct DistributionSummary {
+    mean: f64,
+    min: f64,
+    p5: f64,
+    p50: f64,
+    p95: f64,
+    max: f64,
+}
+
+impl From<Distribution> for DistributionSummary {
+    fn from(distribution: Distribution) -> Self {
+        Self {
+            mean: distribution.mean,
+            min: distribution.min,
+            p5: distribution.p5,
+            p50: distribution.p50,
+            p95: distribution.p95,
+            max: distribution.max,
+        }
+    }
+}
+
+/// Runs seeds `seed..seed + runs` of the default sim and reports the spread
+/// of outcomes.
+async fn simulate_batch(
+    Json(request): Json<BatchSimulationRequest>,
+) -> Result<Json<BatchSimulationResponse>, StatusCode> {
+    if !(1..=MAX_BATCH_RUNS).contains(&request.runs)
+        || !(1..=MAX_BATCH_TICKS).contains(&request.ticks)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let report = tokio::task::spawn_blocking(move || {
+        run_batch(
+            &SimConfig::default(),
+            request.seed,
+            request.runs,
+            request.ticks,
+        )
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(BatchSimulationResponse {
+        runs: request.runs,
+        ticks: request.ticks,
+        seed: request.seed,
+        halted_runs: report.halted_runs,
+        halt_rate: report.halt_rate,
+        terminal_equity: report.terminal_equity.into(),
+        max_drawdown_pct: report.max_drawdown_pct.into(),
+    }))
+}
+
+async fn start_run(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<impl IntoResponse, StatusCode> {
+    let scenario = if body.is_empty() {
+        None
+    } else {
+        let request: StartRunRequest =
+            serde_json::from_slice(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+        let scenario =
+            Scenario::from_toml(&request.scenario).map_err(|_| StatusCode::BAD_REQUEST)?;
+        Some((scenario, request.seed))
+    };
+
     let run_id = state
         .start_run()
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     let _ = state.publish_event(RuntimeEvent::run_started(run_id));
+    let scenario = match scenario {
+        Some((scenario, seed)) => {
+            let outcome = run_scenario(&SimConfig::default(), &scenario, seed);
+            state
+                .record_run_summary(run_id, outcome.report)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Some(ScenarioRunSummary::run(&scenario, seed))
+        }
+        None => None,
+    };
     let location = format!("/runs/{run_id}");
 
     Ok((
         StatusCode::CREATED,
         [(header::LOCATION, location)],
-        Json(StartRunResponse { run_id }),
+        Json(StartRunResponse { run_id, scenario }),
     ))
 }
+
+/// Performance report of a run that simulated a scenario.
+async fn run_summary(
+    State(state): State<AppState>,
+    Path(run_id): Path<u64>,
+) -> Result<Json<SimReport>, StatusCode> {
+    state
+        .run_summary(run_id)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
@@ -1,60 +1,176 @@
+use core_sim::SimReport;
+use runtime::clock::{Clock, SystemClock};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicU64, Ordering},
-    Arc, RwLock,
+    Arc, OnceLock, RwLock,
 };
-
 use tokio::sync::broadcast;
 
+/// Decision intervals without a heartbeat before the loop counts as dead.
+const LOOP_STALE_INTERVALS: u64 = 3;
+/// Floor on the staleness window, so short intervals tolerate a slow tick.
+const LOOP_STALE_MIN_MS: u64 = 5_000;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum FeedMode {
     PaperLive,
     Sim,
+    Replay,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct SourceCount {
     pub source: String,
     pub count: u64,
+    /// Endpoint the source is connected to, for feeds with fallback hosts.
+    pub endpoint: Option<String>,
+    /// Set while the venue refuses this host and is being left alone.
+    pub blocked: Option<FeedBlock>,
+}
+
+/// A venue that answered with a geo-block or ban status.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct FeedBlock {
+    pub status: u16,
+    /// When the source is next contacted, unix milliseconds.
+    pub until_ms: u64,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct FeedHealthResponse {
     pub mode: FeedMode,
     pub source_counts: Vec<SourceCount>,
+    /// Whether the decision loop has ticked recently enough to be running.
+    pub loop_alive: bool,
+    /// Milliseconds since the loop's last tick; `None` before the first.
+    pub last_tick_age_ms: Option<u64>,
+    pub market_list_cache: FeedCacheStats,
+}
+
+/// How often market listings came back unchanged since startup, so their
+/// parse was skipped.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize)]
+pub struct FeedCacheStats {
+    /// The venue answered 304 Not Modified.
+    pub not_modified: u64,
+    /// The listing was downloaded but matched the cached one.
+    pub unchanged: u64,
+    /// The listing changed and was parsed again.
+    pub changed: u64,
+}
+
+/// Heartbeat of the decision loop, as reported by `/readyz`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct LoopLiveness {
+    pub loop_alive: bool,
+    pub last_tick_age_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct FeedErrorCount {
+    pub source: String,
+    pub count: u64,
 }
 
+/// A raw payload that failed to parse, truncated to a bounded size.
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct QuarantinedMessage {
+    pub source: String,
+    pub reason: String,
+    pub payload: String,
+    pub ts: u64,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
+pub struct FeedErrorsResponse {
+    pub counts: Vec<FeedErrorCount>,
+    pub recent: Vec<QuarantinedMessage>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
 pub struct DiscoveredMarket {
     pub source: String,
     pub market_id: String,
+    /// Discovery's `0..=1` ranking of spread, liquidity, volume and expiry;
+    /// `None` for markets not taken from a venue listing.
+    pub liquidity_score: Option<f64>,
+    /// Whether this is the market traded for its venue and asset.
+    pub selected: bool,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
 pub struct DiscoveredMarketsResponse {
     pub markets: Vec<DiscoveredMarket>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
 pub struct PortfolioSummary {
-    pub equity: f64,
-    pub pnl: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub equity: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub pnl: Decimal,
     pub position_qty: f64,
     pub fills: u64,
 }
 
+/// One market's share of the paper portfolio.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct MarketPortfolio {
+    pub source: String,
+    pub market_id: String,
+    pub position_qty: f64,
+    /// Average entry price of the open quantity; zero when flat.
+    pub avg_px: f64,
+    pub mark_px: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub realized_pnl: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub unrealized_pnl: Decimal,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct MarketPortfoliosResponse {
+    pub markets: Vec<MarketPortfolio>,
+}
+
+/// PnL of one closed trading day.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct DayPnl {
+    /// Local calendar date at the configured trading-day offset.
+    pub date: String,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub start_equity: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub end_equity: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub pnl: Decimal,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct DailyPnlResponse {
+    /// Closed days, oldest first.
+    pub days: Vec<DayPnl>,
+}
+
 impl Default for PortfolioSummary {
     fn default() -> Self {
         Self {
-            equity: 0.0,
-            pnl: 0.0,
+            equity: Decimal::ZERO,
+            pnl: Decimal::ZERO,
             position_qty: 0.0,
             fills: 0,
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
 pub struct PriceSnapshot {
     pub coinbase_btc_usd: Option<f64>,
     pub binance_btc_usdt: Option<f64>,
@@ -63,22 +179,55 @@ pub struct PriceSnapshot {
     pub polymarket_yes_bid: Option<f64>,
     pub polymarket_yes_ask: Option<f64>,
     pub polymarket_yes_mid: Option<f64>,
+    pub kalshi_market_id: Option<String>,
+    pub kalshi_yes_bid: Option<f64>,
+    pub kalshi_yes_ask: Option<f64>,
+    pub kalshi_yes_mid: Option<f64>,
+    /// BTC composite straight from the aggregator.
+    pub btc_composite_raw: Option<f64>,
+    /// The same composite after EWMA smoothing.
+    pub btc_composite_smoothed: Option<f64>,
+    /// One entry per tracked asset, BTC first.
+    pub assets: Vec<AssetPriceSnapshot>,
+    /// Decision loop tick the snapshot was taken on.
+    pub tick: u64,
+    /// Unix milliseconds.
     pub ts: u64,
 }
 
-impl Default for PriceSnapshot {
-    fn default() -> Self {
-        Self {
-            coinbase_btc_usd: None,
-            binance_btc_usdt: None,
-            kraken_btc_usd: None,
-            polymarket_market_id: None,
-            polymarket_yes_bid: None,
-            polymarket_yes_ask: None,
-            polymarket_yes_mid: None,
-            ts: 0,
-        }
-    }
+/// Spot composite, forecast and preferred market quotes for one asset.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct AssetPriceSnapshot {
+    pub asset: String,
+    pub composite_raw: Option<f64>,
+    pub composite_smoothed: Option<f64>,
+    pub forecast_15m: Option<f64>,
+    pub polymarket_market_id: Option<String>,
+    pub polymarket_yes_mid: Option<f64>,
+    pub kalshi_market_id: Option<String>,
+    pub kalshi_yes_mid: Option<f64>,
+}
+
+/// Halt state, drawdown, and value at risk for the paper book.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct RiskStatus {
+    pub halted: bool,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub equity: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub peak_equity: Decimal,
+    pub drawdown_pct: f64,
+    /// 95% one-minute VaR in USD; `None` until enough PnL samples exist.
+    pub var_95: Option<f64>,
+    /// VaR at which new orders shrink to nothing; `None` disables the gate.
+    pub var_limit: Option<f64>,
+    /// Multiplier the VaR soft gate applies to order size.
+    pub var_size_scale: f64,
+    pub var_samples: usize,
+    /// Consecutive losing closes toward the next cooldown.
+    pub losing_streak: u32,
+    /// When new entries resume; `None` outside a loss-streak cooldown.
+    pub cooldown_resume_at_ms: Option<u64>,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
@@ -89,6 +238,10 @@ pub struct StrategyPerfSummary {
     pub intents_per_sec: u64,
     pub fills_per_sec: u64,
     pub lag_triggers: u64,
+    /// Intents rejected by the order rate throttle this session.
+    pub throttled_intents: u64,
+    /// Intents skipped because the book was wider than the spread limit.
+    pub spread_skips: u64,
     pub halted: bool,
 }
 
@@ -101,6 +254,8 @@ impl Default for StrategyPerfSummary {
             intents_per_sec: 0,
             fills_per_sec: 0,
             lag_triggers: 0,
+            throttled_intents: 0,
+            spread_skips: 0,
             halted: false,
         }
     }
@@ -110,6 +265,9 @@ impl Default for StrategyPerfSummary {
 #[serde(rename_all = "snake_case")]
 pub enum ExecutionMode {
     Paper,
+    /// Venue orders are signed and logged but never sent; fills stay on
+    /// paper.
+    DryRun,
     Live,
 }
 
@@ -119,6 +277,20 @@ impl Default for ExecutionMode {
     }
 }
 
+/// How strategy intents are sized.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingMode {
+    /// The strategy's fixed base quantity.
+    #[default]
+    Fixed,
+    /// Fractional Kelly on the fair-value edge, capped by `risk_per_trade_pct`.
+    Kelly,
+    /// The base quantity times the divergence in thresholds, capped by
+    /// `risk_per_trade_pct`.
+    Divergence,
+}
+
 #[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct RuntimeSettings {
     pub execution_mode: ExecutionMode,
@@ -126,9 +298,20 @@ pub struct RuntimeSettings {
     pub lag_threshold_pct: f64,
     pub risk_per_trade_pct: f64,
     pub daily_loss_cap_pct: f64,
+    pub lag_cooldown_secs: u64,
+    pub lag_persistence_evals: u32,
     pub market: String,
     pub forecast_horizon_minutes: u16,
     pub live_feature_enabled: bool,
+    /// Name of the strategy the live loop runs.
+    pub strategy: String,
+    /// Strategies registered with the runtime; `strategy` must be one of them.
+    pub available_strategies: Vec<String>,
+    pub sizing_mode: SizingMode,
+    /// Share of the full Kelly stake traded in Kelly mode.
+    pub kelly_fraction: f64,
+    /// Period of the live decision loop.
+    pub decision_interval_ms: u64,
 }
 
 impl Default for RuntimeSettings {
@@ -139,13 +322,126 @@ impl Default for RuntimeSettings {
             lag_threshold_pct: 0.3,
             risk_per_trade_pct: 0.5,
             daily_loss_cap_pct: 2.0,
+            lag_cooldown_secs: 30,
+            lag_persistence_evals: 2,
             market: "BTC/USD".to_string(),
             forecast_horizon_minutes: 15,
             live_feature_enabled: false,
+            strategy: "lag_divergence".to_string(),
+            available_strategies: vec!["lag_divergence".to_string()],
+            sizing_mode: SizingMode::Fixed,
+            kelly_fraction: 0.5,
+            decision_interval_ms: 1_500,
+        }
+    }
+}
+
+impl RuntimeSettings {
+    /// Overwrites every field `patch` sets.
+    pub fn apply(&mut self, patch: RuntimeSettingsPatch) {
+        if let Some(execution_mode) = patch.execution_mode {
+            self.execution_mode = execution_mode;
+        }
+        if let Some(trading_paused) = patch.trading_paused {
+            self.trading_paused = trading_paused;
+        }
+        if let Some(lag_threshold_pct) = patch.lag_threshold_pct {
+            self.lag_threshold_pct = lag_threshold_pct;
+        }
+        if let Some(risk_per_trade_pct) = patch.risk_per_trade_pct {
+            self.risk_per_trade_pct = risk_per_trade_pct;
+        }
+        if let Some(daily_loss_cap_pct) = patch.daily_loss_cap_pct {
+            self.daily_loss_cap_pct = daily_loss_cap_pct;
+        }
+        if let Some(lag_cooldown_secs) = patch.lag_cooldown_secs {
+            self.lag_cooldown_secs = lag_cooldown_secs;
+        }
+        if let Some(lag_persistence_evals) = patch.lag_persistence_evals {
+            self.lag_persistence_evals = lag_persistence_evals;
+        }
+        if let Some(strategy) = patch.strategy {
+            self.strategy = strategy;
+        }
+        if let Some(sizing_mode) = patch.sizing_mode {
+            self.sizing_mode = sizing_mode;
+        }
+        if let Some(kelly_fraction) = patch.kelly_fraction {
+            self.kelly_fraction = kelly_fraction;
+        }
+        if let Some(decision_interval_ms) = patch.decision_interval_ms {
+            self.decision_interval_ms = decision_interval_ms;
+        }
+    }
+
+    pub fn check_invariants(&self) -> Result<(), SettingsConflict> {
+        check_settings_invariants(
+            self.lag_threshold_pct,
+            self.risk_per_trade_pct,
+            self.daily_loss_cap_pct,
+        )
+    }
+}
+
+/// Sanity bound on the lag threshold. Divergence is measured against the
+/// mid, so larger lags are possible on cheap contracts, but a threshold
+/// this wide is almost certainly a fraction typed as a percentage.
+pub const MAX_LAG_THRESHOLD_PCT: f64 = 50.0;
+
+/// Settings that are each in range but make no sense together.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SettingsConflict {
+    /// One losing trade could spend more than the whole day's loss budget.
+    RiskAboveDailyCap {
+        risk_per_trade_pct: f64,
+        daily_loss_cap_pct: f64,
+    },
+    /// The lag threshold is past [`MAX_LAG_THRESHOLD_PCT`].
+    LagThresholdTooHigh { lag_threshold_pct: f64 },
+}
+
+impl fmt::Display for SettingsConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RiskAboveDailyCap {
+                risk_per_trade_pct,
+                daily_loss_cap_pct,
+            } => write!(
+                f,
+                "risk_per_trade_pct ({risk_per_trade_pct}) must not exceed daily_loss_cap_pct \
+                 ({daily_loss_cap_pct}); lower the per-trade risk or raise the daily cap"
+            ),
+            Self::LagThresholdTooHigh { lag_threshold_pct } => write!(
+                f,
+                "lag_threshold_pct ({lag_threshold_pct}) is above the {MAX_LAG_THRESHOLD_PCT} \
+                 sanity bound and would only trade on extreme dislocations; it is a percentage, \
+                 so 0.3 means 0.3%. Lower it, or set trading_paused to stop trading"
+            ),
         }
     }
 }
 
+impl std::error::Error for SettingsConflict {}
+
+/// Checks the settings that constrain each other, for startup config and
+/// settings patches alike. Each value is assumed to be in its own range.
+pub fn check_settings_invariants(
+    lag_threshold_pct: f64,
+    risk_per_trade_pct: f64,
+    daily_loss_cap_pct: f64,
+) -> Result<(), SettingsConflict> {
+    if risk_per_trade_pct > daily_loss_cap_pct {
+        return Err(SettingsConflict::RiskAboveDailyCap {
+            risk_per_trade_pct,
+            daily_loss_cap_pct,
+        });
+    }
+    if lag_threshold_pct > MAX_LAG_THRESHOLD_PCT {
+        return Err(SettingsConflict::LagThresholdTooHigh { lag_threshold_pct });
+    }
+    Ok(())
+}
+
 #[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
 pub struct RuntimeSettingsPatch {
     pub execution_mode: Option<ExecutionMode>,
@@ -153,12 +449,47 @@ pub struct RuntimeSettingsPatch {
     pub lag_threshold_pct: Option<f64>,
     pub risk_per_trade_pct: Option<f64>,
     pub daily_loss_cap_pct: Option<f64>,
+    pub lag_cooldown_secs: Option<u64>,
+    pub lag_persistence_evals: Option<u32>,
+    pub strategy: Option<String>,
+    pub sizing_mode: Option<SizingMode>,
+    pub kelly_fraction: Option<f64>,
+    pub decision_interval_ms: Option<u64>,
+}
+
+/// Tunables for the running strategy, served at `/strategy/params`.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct StrategyParams {
+    pub momentum_lookback_secs: u64,
+    pub momentum_threshold_bps: f64,
+    /// The lag strategy closes a position once its divergence shrinks below
+    /// this fraction of the entry threshold.
+    pub exit_threshold_fraction: f64,
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            momentum_lookback_secs: 60,
+            momentum_threshold_bps: 5.0,
+            exit_threshold_fraction: 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+pub struct StrategyParamsPatch {
+    pub momentum_lookback_secs: Option<u64>,
+    pub momentum_threshold_bps: Option<f64>,
+    pub exit_threshold_fraction: Option<f64>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
 pub struct StrategyStatsSummary {
-    pub balance: f64,
-    pub total_pnl: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub balance: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub total_pnl: Decimal,
     pub exec_latency_us: u64,
     pub win_rate: f64,
     pub btc_usd: f64,
@@ -167,8 +498,8 @@ pub struct StrategyStatsSummary {
 impl Default for StrategyStatsSummary {
     fn default() -> Self {
         Self {
-            balance: 0.0,
-            total_pnl: 0.0,
+            balance: Decimal::ZERO,
+            total_pnl: Decimal::ZERO,
             exec_latency_us: 0,
             win_rate: 0.0,
             btc_usd: 0.0,
@@ -182,6 +513,8 @@ pub struct BtcForecastSummary {
     pub current_btc_usd: f64,
     pub forecast_btc_usd: f64,
     pub delta_pct: f64,
+    pub tick: u64,
+    /// Unix milliseconds.
     pub ts: u64,
 }
 
@@ -192,6 +525,7 @@ impl Default for BtcForecastSummary {
             current_btc_usd: 0.0,
             forecast_btc_usd: 0.0,
             delta_pct: 0.0,
+            tick: 0,
             ts: 0,
         }
     }
@@ -199,12 +533,52 @@ impl Default for BtcForecastSummary {
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
 pub struct ExecutionLogEntry {
+    /// Decision loop tick; the loop's latest tick for entries raised outside
+    /// it, such as settings changes.
+    pub tick: u64,
+    /// Unix milliseconds.
     pub ts: u64,
     pub event: String,
     pub headline: String,
     pub detail: String,
 }
 
+/// What the decision loop did with one strategy evaluation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalAction {
+    /// The strategy or its trigger gate produced no order intent.
+    NoIntent,
+    /// A loss-streak cooldown rejected an intent that would add exposure.
+    CooldownReject,
+    /// The quote's spread was wider than the configured limit.
+    SpreadSkip,
+    /// The per-minute intent limit was spent.
+    Throttled,
+    /// The intent was filled on paper.
+    Filled,
+    /// The risk gate, or the live-mode feature flag, rejected the intent.
+    RiskReject,
+}
+
+/// One strategy evaluation of a tracked quote, kept whether or not it led
+/// to a trade.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct StrategySignal {
+    pub tick: u64,
+    /// Unix milliseconds.
+    pub ts: u64,
+    pub source: String,
+    pub market_id: String,
+    pub mid_yes: f64,
+    pub fair_yes_px: f64,
+    /// `(fair - mid) / mid` in percent.
+    pub divergence_pct: f64,
+    /// The divergence cleared the lag threshold.
+    pub triggered: bool,
+    pub action: SignalAction,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum StartRunError {
     RunIdOverflow,
@@ -217,6 +591,37 @@ pub enum PaperOrderSide {
     Sell,
 }
 
+/// Risk conditions worth paging an operator about.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskAlertKind {
+    /// Trading halted on drawdown from the equity peak.
+    Halt,
+    /// Trading was paused from the dashboard or `PATCH /settings`.
+    KillSwitch,
+    /// The trading day's loss reached the daily cap.
+    DailyCapBreach,
+    /// A feed failed often enough to open its reconnect circuit.
+    FeedDegraded,
+    /// A venue refused this host with a geo-block or ban status.
+    FeedBlocked,
+    /// Too few of the most recent closes were winners.
+    WinRateCollapse,
+}
+
+impl RiskAlertKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Halt => "halt",
+            Self::KillSwitch => "kill_switch",
+            Self::DailyCapBreach => "daily_cap_breach",
+            Self::FeedDegraded => "feed_degraded",
+            Self::FeedBlocked => "feed_blocked",
+            Self::WinRateCollapse => "win_rate_collapse",
+        }
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize)]
 #[serde(tag = "event_type", rename_all = "snake_case")]
 pub enum RuntimeEvent {
@@ -237,6 +642,8 @@ pub enum RuntimeEvent {
         side: PaperOrderSide,
         qty: f64,
         fill_px: f64,
+        /// Venue fee charged on the fill, in USD.
+        fee: f64,
     },
     RiskReject {
         market_id: String,
@@ -247,9 +654,60 @@ pub enum RuntimeEvent {
         mode: FeedMode,
         source_counts: Vec<SourceCount>,
     },
+    FeedReconnect {
+        source: String,
+        attempt: u32,
+        delay_ms: u64,
+        circuit_open: bool,
+        reason: String,
+    },
+    FeedThrottle {
+        host: String,
+        reason: String,
+        retry_after_ms: Option<u64>,
+    },
+    BracketTriggered {
+        market_id: String,
+        kind: String,
+        entry_px: f64,
+        trigger_px: f64,
+        fill_px: f64,
+        qty: f64,
+        #[serde(with = "rust_decimal::serde::float")]
+        realized_pnl: Decimal,
+    },
+    CooldownStarted {
+        losing_streak: u32,
+        resume_at_ms: u64,
+    },
+    DayRolled {
+        date: String,
+        #[serde(with = "rust_decimal::serde::float")]
+        start_equity: Decimal,
+        #[serde(with = "rust_decimal::serde::float")]
+        end_equity: Decimal,
+        #[serde(with = "rust_decimal::serde::float")]
+        pnl: Decimal,
+    },
+    RiskAlert {
+        kind: RiskAlertKind,
+        detail: String,
+        ts: u64,
+    },
+    MarketResolved {
+        source: String,
+        market_id: String,
+        outcome: String,
+        settle_px: f64,
+        qty: f64,
+        #[serde(with = "rust_decimal::serde::float")]
+        realized_pnl: Decimal,
+    },
     PortfolioSnapshot {
-        equity: f64,
-        pnl: f64,
+        #[serde(with = "rust_decimal::serde::float")]
+        equity: Decimal,
+        #[serde(with = "rust_decimal::serde::float")]
+        pnl: Decimal,
         position_qty: f64,
         fills: u64,
     },
@@ -261,6 +719,14 @@ pub enum RuntimeEvent {
         polymarket_yes_bid: Option<f64>,
         polymarket_yes_ask: Option<f64>,
         polymarket_yes_mid: Option<f64>,
+        kalshi_market_id: Option<String>,
+        kalshi_yes_bid: Option<f64>,
+        kalshi_yes_ask: Option<f64>,
+        kalshi_yes_mid: Option<f64>,
+        btc_composite_raw: Option<f64>,
+        btc_composite_smoothed: Option<f64>,
+        assets: Vec<AssetPriceSnapshot>,
+        tick: u64,
         ts: u64,
     },
     StrategyPerf {
@@ -270,6 +736,8 @@ pub enum RuntimeEvent {
         intents_per_sec: u64,
         fills_per_sec: u64,
         lag_triggers: u64,
+        throttled_intents: u64,
+        spread_skips: u64,
         halted: bool,
     },
     SettingsUpdated {
@@ -278,10 +746,18 @@ pub enum RuntimeEvent {
         lag_threshold_pct: f64,
         risk_per_trade_pct: f64,
         daily_loss_cap_pct: f64,
+        lag_cooldown_secs: u64,
+        lag_persistence_evals: u32,
+        strategy: String,
+        sizing_mode: SizingMode,
+        kelly_fraction: f64,
+        decision_interval_ms: u64,
     },
     StrategyStats {
-        balance: f64,
-        total_pnl: f64,
+        #[serde(with = "rust_decimal::serde::float")]
+        balance: Decimal,
+        #[serde(with = "rust_decimal::serde::float")]
+        total_pnl: Decimal,
         exec_latency_us: u64,
         win_rate: f64,
         btc_usd: f64,
@@ -291,19 +767,33 @@ pub enum RuntimeEvent {
         current_btc_usd: f64,
         forecast_btc_usd: f64,
         delta_pct: f64,
+        tick: u64,
         ts: u64,
     },
     ExecutionLog {
+        tick: u64,
         ts: u64,
         event: String,
         headline: String,
         detail: String,
     },
+    StrategySignal {
+        tick: u64,
+        ts: u64,
+        source: String,
+        market_id: String,
+        mid_yes: f64,
+        fair_yes_px: f64,
+        divergence_pct: f64,
+        triggered: bool,
+        action: SignalAction,
+    },
 }
 
 impl RuntimeEvent {
-    pub fn connected() -> Self {
-        Self::Connected { run_id: None }
+    /// Sent first on each event stream, naming the run active at the time.
+    pub fn connected(run_id: Option<u64>) -> Self {
+        Self::Connected { run_id }
     }
 
     pub fn run_started(run_id: u64) -> Self {
@@ -329,12 +819,14 @@ impl RuntimeEvent {
         side: PaperOrderSide,
         qty: f64,
         fill_px: f64,
+        fee: f64,
     ) -> Self {
         Self::PaperFill {
             market_id: market_id.into(),
             side,
             qty,
             fill_px,
+            fee,
         }
     }
 
@@ -357,6 +849,97 @@ impl RuntimeEvent {
         }
     }
 
+    pub fn feed_reconnect(
+        source: impl Into<String>,
+        attempt: u32,
+        delay_ms: u64,
+        circuit_open: bool,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self::FeedReconnect {
+            source: source.into(),
+            attempt,
+            delay_ms,
+            circuit_open,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn feed_throttle(
+        host: impl Into<String>,
+        reason: impl Into<String>,
+        retry_after_ms: Option<u64>,
+    ) -> Self {
+        Self::FeedThrottle {
+            host: host.into(),
+            reason: reason.into(),
+            retry_after_ms,
+        }
+    }
+
+    /// `qty` is the signed YES quantity traded to flatten the position.
+    pub fn bracket_triggered(
+        market_id: impl Into<String>,
+        kind: impl Into<String>,
+        entry_px: f64,
+        trigger_px: f64,
+        fill_px: f64,
+        qty: f64,
+        realized_pnl: Decimal,
+    ) -> Self {
+        Self::BracketTriggered {
+            market_id: market_id.into(),
+            kind: kind.into(),
+            entry_px,
+            trigger_px,
+            fill_px,
+            qty,
+            realized_pnl,
+        }
+    }
+
+    pub fn cooldown_started(losing_streak: u32, resume_at_ms: u64) -> Self {
+        Self::CooldownStarted {
+            losing_streak,
+            resume_at_ms,
+        }
+    }
+
+    pub fn day_rolled(day: DayPnl) -> Self {
+        Self::DayRolled {
+            date: day.date,
+            start_equity: day.start_equity,
+            end_equity: day.end_equity,
+            pnl: day.pnl,
+        }
+    }
+
+    pub fn risk_alert(kind: RiskAlertKind, detail: impl Into<String>, ts: u64) -> Self {
+        Self::RiskAlert {
+            kind,
+            detail: detail.into(),
+            ts,
+        }
+    }
+
+    pub fn market_resolved(
+        source: impl Into<String>,
+        market_id: impl Into<String>,
+        outcome: impl Into<String>,
+        settle_px: f64,
+        qty: f64,
+        realized_pnl: Decimal,
+    ) -> Self {
+        Self::MarketResolved {
+            source: source.into(),
+            market_id: market_id.into(),
+            outcome: outcome.into(),
+            settle_px,
+            qty,
+            realized_pnl,
+        }
+    }
+
     pub fn portfolio_snapshot(summary: PortfolioSummary) -> Self {
         Self::PortfolioSnapshot {
             equity: summary.equity,
@@ -375,6 +958,14 @@ impl RuntimeEvent {
             polymarket_yes_bid: snapshot.polymarket_yes_bid,
             polymarket_yes_ask: snapshot.polymarket_yes_ask,
             polymarket_yes_mid: snapshot.polymarket_yes_mid,
+            kalshi_market_id: snapshot.kalshi_market_id,
+            kalshi_yes_bid: snapshot.kalshi_yes_bid,
+            kalshi_yes_ask: snapshot.kalshi_yes_ask,
+            kalshi_yes_mid: snapshot.kalshi_yes_mid,
+            btc_composite_raw: snapshot.btc_composite_raw,
+            btc_composite_smoothed: snapshot.btc_composite_smoothed,
+            assets: snapshot.assets,
+            tick: snapshot.tick,
             ts: snapshot.ts,
         }
     }
@@ -387,6 +978,8 @@ impl RuntimeEvent {
             intents_per_sec: summary.intents_per_sec,
             fills_per_sec: summary.fills_per_sec,
             lag_triggers: summary.lag_triggers,
+            throttled_intents: summary.throttled_intents,
+            spread_skips: summary.spread_skips,
             halted: summary.halted,
         }
     }
@@ -398,6 +991,12 @@ impl RuntimeEvent {
             lag_threshold_pct: settings.lag_threshold_pct,
             risk_per_trade_pct: settings.risk_per_trade_pct,
             daily_loss_cap_pct: settings.daily_loss_cap_pct,
+            lag_cooldown_secs: settings.lag_cooldown_secs,
+            lag_persistence_evals: settings.lag_persistence_evals,
+            strategy: settings.strategy,
+            sizing_mode: settings.sizing_mode,
+            kelly_fraction: settings.kelly_fraction,
+            decision_interval_ms: settings.decision_interval_ms,
         }
     }
 
@@ -417,34 +1016,124 @@ impl RuntimeEvent {
             current_btc_usd: summary.current_btc_usd,
             forecast_btc_usd: summary.forecast_btc_usd,
             delta_pct: summary.delta_pct,
+            tick: summary.tick,
             ts: summary.ts,
         }
     }
 
     pub fn execution_log(entry: ExecutionLogEntry) -> Self {
         Self::ExecutionLog {
+            tick: entry.tick,
             ts: entry.ts,
             event: entry.event,
             headline: entry.headline,
             detail: entry.detail,
         }
     }
+
+    pub fn strategy_signal(signal: StrategySignal) -> Self {
+        Self::StrategySignal {
+            tick: signal.tick,
+            ts: signal.ts,
+            source: signal.source,
+            market_id: signal.market_id,
+            mid_yes: signal.mid_yes,
+            fair_yes_px: signal.fair_yes_px,
+            divergence_pct: signal.divergence_pct,
+            triggered: signal.triggered,
+            action: signal.action,
+        }
+    }
+}
+
+/// A record serialized with the run that was active when it was made, as a
+/// top-level `run_id` field left out when no run was.
+#[derive(Debug, serde::Serialize)]
+pub struct InRun<'a, T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<u64>,
+    #[serde(flatten)]
+    pub record: &'a T,
+}
+
+/// An event as delivered to bus subscribers, who all share one copy.
+#[derive(Debug)]
+pub struct PublishedEvent {
+    pub event: RuntimeEvent,
+    /// The run active when the event was published.
+    pub run_id: Option<u64>,
+    json: OnceLock<Option<Arc<str>>>,
+}
+
+impl PublishedEvent {
+    pub fn new(event: RuntimeEvent) -> Self {
+        Self::in_run(event, None)
+    }
+
+    /// `event` attributed to `run_id`, unless it names a run itself.
+    pub fn in_run(event: RuntimeEvent, run_id: Option<u64>) -> Self {
+        let names_run = matches!(
+            event,
+            RuntimeEvent::Connected { .. } | RuntimeEvent::RunStarted { .. }
+        );
+        Self {
+            event,
+            run_id: run_id.filter(|_| !names_run),
+            json: OnceLock::new(),
+        }
+    }
+
+    /// The event as JSON, serialized by the first subscriber to ask and
+    /// shared with the rest.
+    pub fn json(&self) -> Option<Arc<str>> {
+        self.json
+            .get_or_init(|| serde_json::to_string(self).ok().map(Arc::from))
+            .clone()
+    }
+}
+
+impl serde::Serialize for PublishedEvent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        InRun {
+            run_id: self.run_id,
+            record: &self.event,
+        }
+        .serialize(serializer)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct AppState {
     next_run_id: Arc<AtomicU64>,
-    events_tx: broadcast::Sender<RuntimeEvent>,
+    /// Events are shared, so each subscriber gets a pointer rather than a
+    /// copy of every payload.
+    events_tx: broadcast::Sender<Arc<PublishedEvent>>,
     feed_mode: FeedMode,
     source_counts: Arc<RwLock<Vec<SourceCount>>>,
+    market_list_cache: Arc<RwLock<FeedCacheStats>>,
+    feed_errors: Arc<RwLock<FeedErrorsResponse>>,
     discovered_markets: Arc<RwLock<Vec<DiscoveredMarket>>>,
     portfolio_summary: Arc<RwLock<PortfolioSummary>>,
     price_snapshot: Arc<RwLock<PriceSnapshot>>,
     strategy_perf_summary: Arc<RwLock<StrategyPerfSummary>>,
+    risk_status: Arc<RwLock<RiskStatus>>,
     runtime_settings: Arc<RwLock<RuntimeSettings>>,
+    strategy_params: Arc<RwLock<StrategyParams>>,
     strategy_stats_summary: Arc<RwLock<StrategyStatsSummary>>,
     btc_forecast_summary: Arc<RwLock<BtcForecastSummary>>,
     execution_logs: Arc<RwLock<Vec<ExecutionLogEntry>>>,
+    strategy_signals: Arc<RwLock<Vec<StrategySignal>>>,
+    market_portfolios: Arc<RwLock<Vec<MarketPortfolio>>>,
+    daily_pnl: Arc<RwLock<Vec<DayPnl>>>,
+    run_summaries: Arc<RwLock<HashMap<u64, SimReport>>>,
+    run_summary_dir: Arc<RwLock<Option<PathBuf>>>,
+    export_dir: Arc<RwLock<Option<PathBuf>>>,
+    /// Unix ms of the decision loop's last tick; zero until it first ticks.
+    loop_heartbeat_ms: Arc<AtomicU64>,
+    loop_tick: Arc<AtomicU64>,
+    /// The decision loop's clock, so handlers stamp and age against the same
+    /// time the loop does.
+    clock: Arc<dyn Clock>,
 }
 
 impl Default for AppState {
@@ -455,14 +1144,27 @@ impl Default for AppState {
             events_tx,
             feed_mode: FeedMode::PaperLive,
             source_counts: Arc::new(RwLock::new(Vec::new())),
+            market_list_cache: Arc::new(RwLock::new(FeedCacheStats::default())),
+            feed_errors: Arc::new(RwLock::new(FeedErrorsResponse::default())),
             discovered_markets: Arc::new(RwLock::new(Vec::new())),
             portfolio_summary: Arc::new(RwLock::new(PortfolioSummary::default())),
             price_snapshot: Arc::new(RwLock::new(PriceSnapshot::default())),
             strategy_perf_summary: Arc::new(RwLock::new(StrategyPerfSummary::default())),
+            risk_status: Arc::new(RwLock::new(RiskStatus::default())),
             runtime_settings: Arc::new(RwLock::new(RuntimeSettings::default())),
+            strategy_params: Arc::new(RwLock::new(StrategyParams::default())),
             strategy_stats_summary: Arc::new(RwLock::new(StrategyStatsSummary::default())),
             btc_forecast_summary: Arc::new(RwLock::new(BtcForecastSummary::default())),
             execution_logs: Arc::new(RwLock::new(Vec::new())),
+            strategy_signals: Arc::new(RwLock::new(Vec::new())),
+            market_portfolios: Arc::new(RwLock::new(Vec::new())),
+            daily_pnl: Arc::new(RwLock::new(Vec::new())),
+            run_summaries: Arc::new(RwLock::new(HashMap::new())),
+            run_summary_dir: Arc::new(RwLock::new(None)),
+            export_dir: Arc::new(RwLock::new(None)),
+            loop_heartbeat_ms: Arc::new(AtomicU64::new(0)),
+            loop_tick: Arc::new(AtomicU64::new(0)),
+            clock: Arc::new(SystemClock),
         }
     }
 }
@@ -472,6 +1174,25 @@ impl AppState {
         Self::default()
     }
 
+    /// State reporting `feed_mode` from `/feed/health`.
+    pub fn with_feed_mode(feed_mode: FeedMode) -> Self {
+        Self {
+            feed_mode,
+            ..Self::default()
+        }
+    }
+
+    /// Reads time from `clock` instead of the system clock; it should be the
+    /// one the decision loop runs on.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
     pub fn start_run(&self) -> Result<u64, StartRunError> {
         let previous = self
             .next_run_id
@@ -483,18 +1204,89 @@ impl AppState {
         Ok(previous + 1)
     }
 
-    pub fn subscribe_events(&self) -> broadcast::Receiver<RuntimeEvent> {
+    /// The most recently started run, which every published event is
+    /// attributed to.
+    pub fn active_run_id(&self) -> Option<u64> {
+        match self.next_run_id.load(Ordering::Relaxed) {
+            0 => None,
+            run_id => Some(run_id),
+        }
+    }
+
+    /// Where finished runs write `summary.json`, normally next to the
+    /// replay CSV.
+    pub fn set_run_summary_dir(&self, dir: PathBuf) {
+        *self
+            .run_summary_dir
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(dir);
+    }
+
+    /// Where the event and paper journals are kept as JSONL for the export
+    /// routes.
+    pub fn set_export_dir(&self, dir: PathBuf) {
+        *self
+            .export_dir
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(dir);
+    }
+
+    pub fn export_dir(&self) -> Option<PathBuf> {
+        self.export_dir
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Keeps a finished run's report for `GET /runs/{id}/summary` and
+    /// writes it, tagged with `run_id`, to the summary dir if one is set.
+    pub fn record_run_summary(&self, run_id: u64, report: SimReport) -> io::Result<()> {
+        self.run_summaries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(run_id, report);
+
+        let dir = self
+            .run_summary_dir
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        if let Some(dir) = dir {
+            fs::create_dir_all(&dir)?;
+            let summary = InRun {
+                run_id: Some(run_id),
+                record: &report,
+            };
+            let json = serde_json::to_vec_pretty(&summary).map_err(io::Error::other)?;
+            fs::write(dir.join("summary.json"), json)?;
+        }
+        Ok(())
+    }
+
+    pub fn run_summary(&self, run_id: u64) -> Option<SimReport> {
+        self.run_summaries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&run_id)
+            .copied()
+    }
+
+    pub fn subscribe_events(&self) -> broadcast::Receiver<Arc<PublishedEvent>> {
         self.events_tx.subscribe()
     }
 
     pub fn publish_event(
         &self,
         event: RuntimeEvent,
-    ) -> Result<usize, broadcast::error::SendError<RuntimeEvent>> {
-        self.events_tx.send(event)
+    ) -> Result<usize, broadcast::error::SendError<Arc<PublishedEvent>>> {
+        self.events_tx.send(Arc::new(PublishedEvent::in_run(
+            event,
+            self.active_run_id(),
+        )))
     }
 
     pub fn feed_health(&self) -> FeedHealthResponse {
+        let liveness = self.loop_liveness(self.now_ms());
         FeedHealthResponse {
             mode: self.feed_mode,
             source_counts: self
@@ -502,6 +1294,70 @@ impl AppState {
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner())
                 .clone(),
+            loop_alive: liveness.loop_alive,
+            last_tick_age_ms: liveness.last_tick_age_ms,
+            market_list_cache: *self
+                .market_list_cache
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        }
+    }
+
+    /// Called by the decision loop once per tick; a loop that panics or
+    /// hangs stops calling it and goes stale.
+    pub fn record_loop_heartbeat(&self, tick: u64, ts_ms: u64) {
+        self.loop_tick.store(tick, Ordering::Relaxed);
+        self.loop_heartbeat_ms
+            .store(ts_ms.max(1), Ordering::Relaxed);
+    }
+
+    /// The decision loop's latest tick; zero until it first ticks.
+    pub fn loop_tick(&self) -> u64 {
+        self.loop_tick.load(Ordering::Relaxed)
+    }
+
+    /// The loop is alive while its last tick is within three decision
+    /// intervals, and never less than five seconds, of `now_ms`.
+    pub fn loop_liveness(&self, now_ms: u64) -> LoopLiveness {
+        let last_tick_age_ms = match self.loop_heartbeat_ms.load(Ordering::Relaxed) {
+            0 => None,
+            heartbeat_ms => Some(now_ms.saturating_sub(heartbeat_ms)),
+        };
+        let stale_after_ms = self
+            .runtime_settings()
+            .decision_interval_ms
+            .saturating_mul(LOOP_STALE_INTERVALS)
+            .max(LOOP_STALE_MIN_MS);
+        LoopLiveness {
+            loop_alive: last_tick_age_ms.is_some_and(|age_ms| age_ms <= stale_after_ms),
+            last_tick_age_ms,
+        }
+    }
+
+    pub fn feed_errors(&self) -> FeedErrorsResponse {
+        self.feed_errors
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub fn market_portfolios(&self) -> MarketPortfoliosResponse {
+        MarketPortfoliosResponse {
+            markets: self
+                .market_portfolios
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone(),
+        }
+    }
+
+    pub fn daily_pnl(&self) -> DailyPnlResponse {
+        DailyPnlResponse {
+            days: self
+                .daily_pnl
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone(),
         }
     }
 
@@ -536,6 +1392,13 @@ impl AppState {
             .clone()
     }
 
+    pub fn risk_status(&self) -> RiskStatus {
+        self.risk_status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
     pub fn runtime_settings(&self) -> RuntimeSettings {
         self.runtime_settings
             .read()
@@ -543,6 +1406,13 @@ impl AppState {
             .clone()
     }
 
+    pub fn strategy_params(&self) -> StrategyParams {
+        *self
+            .strategy_params
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     pub fn strategy_stats_summary(&self) -> StrategyStatsSummary {
         *self
             .strategy_stats_summary
@@ -564,6 +1434,13 @@ impl AppState {
             .clone()
     }
 
+    pub fn strategy_signals(&self) -> Vec<StrategySignal> {
+        self.strategy_signals
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
     pub fn set_feed_source_counts(&self, source_counts: Vec<SourceCount>) {
         *self
             .source_counts
@@ -571,6 +1448,20 @@ impl AppState {
             .unwrap_or_else(|poisoned| poisoned.into_inner()) = source_counts;
     }
 
+    pub fn set_market_list_cache(&self, stats: FeedCacheStats) {
+        *self
+            .market_list_cache
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = stats;
+    }
+
+    pub fn set_feed_errors(&self, feed_errors: FeedErrorsResponse) {
+        *self
+            .feed_errors
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = feed_errors;
+    }
+
     pub fn set_discovered_markets(&self, discovered_markets: Vec<DiscoveredMarket>) {
         *self
             .discovered_markets
@@ -578,6 +1469,13 @@ impl AppState {
             .unwrap_or_else(|poisoned| poisoned.into_inner()) = discovered_markets;
     }
 
+    pub fn set_market_portfolios(&self, markets: Vec<MarketPortfolio>) {
+        *self
+            .market_portfolios
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = markets;
+    }
+
     pub fn set_portfolio_summary(&self, summary: PortfolioSummary) {
         *self
             .portfolio_summary
@@ -599,6 +1497,13 @@ impl AppState {
             .unwrap_or_else(|poisoned| poisoned.into_inner()) = summary;
     }
 
+    pub fn set_risk_status(&self, status: RiskStatus) {
+        *self
+            .risk_status
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = status;
+    }
+
     pub fn set_runtime_settings(&self, settings: RuntimeSettings) {
         *self
             .runtime_settings
@@ -606,29 +1511,41 @@ impl AppState {
             .unwrap_or_else(|poisoned| poisoned.into_inner()) = settings;
     }
 
-    pub fn patch_runtime_settings(&self, patch: RuntimeSettingsPatch) -> RuntimeSettings {
+    /// Applies `patch` only if the patched settings pass
+    /// [`RuntimeSettings::check_invariants`]; checked under the same lock,
+    /// so concurrent patches cannot combine into a conflict.
+    pub fn try_patch_runtime_settings(
+        &self,
+        patch: RuntimeSettingsPatch,
+    ) -> Result<RuntimeSettings, SettingsConflict> {
         let mut guard = self
             .runtime_settings
             .write()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut patched = guard.clone();
+        patched.apply(patch);
+        patched.check_invariants()?;
+        *guard = patched.clone();
+        Ok(patched)
+    }
 
-        if let Some(execution_mode) = patch.execution_mode {
-            guard.execution_mode = execution_mode;
-        }
-        if let Some(trading_paused) = patch.trading_paused {
-            guard.trading_paused = trading_paused;
-        }
-        if let Some(lag_threshold_pct) = patch.lag_threshold_pct {
-            guard.lag_threshold_pct = lag_threshold_pct;
+    pub fn patch_strategy_params(&self, patch: StrategyParamsPatch) -> StrategyParams {
+        let mut guard = self
+            .strategy_params
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(momentum_lookback_secs) = patch.momentum_lookback_secs {
+            guard.momentum_lookback_secs = momentum_lookback_secs;
         }
-        if let Some(risk_per_trade_pct) = patch.risk_per_trade_pct {
-            guard.risk_per_trade_pct = risk_per_trade_pct;
+        if let Some(momentum_threshold_bps) = patch.momentum_threshold_bps {
+            guard.momentum_threshold_bps = momentum_threshold_bps;
         }
-        if let Some(daily_loss_cap_pct) = patch.daily_loss_cap_pct {
-            guard.daily_loss_cap_pct = daily_loss_cap_pct;
+        if let Some(exit_threshold_fraction) = patch.exit_threshold_fraction {
+            guard.exit_threshold_fraction = exit_threshold_fraction;
         }
 
-        guard.clone()
+        *guard
     }
 
     pub fn set_strategy_stats_summary(&self, summary: StrategyStatsSummary) {
@@ -645,6 +1562,19 @@ impl AppState {
             .unwrap_or_else(|poisoned| poisoned.into_inner()) = summary;
     }
 
+    pub fn push_daily_pnl(&self, day: DayPnl, max_days: usize) {
+        let mut guard = self
+            .daily_pnl
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        guard.push(day);
+
+        if guard.len() > max_days {
+            let overflow = guard.len() - max_days;
+            guard.drain(0..overflow);
+        }
+    }
+
     pub fn push_execution_log(&self, entry: ExecutionLogEntry, max_entries: usize) {
         let mut guard = self
             .execution_logs
@@ -658,41 +1588,48 @@ impl AppState {
         }
     }
 
-    #[cfg(test)]
-    pub(crate) fn with_next_run_id_for_test(next_run_id: u64) -> Self {
-        let (events_tx, _) = broadcast::channel(256);
-        Self {
-            next_run_id: Arc::new(AtomicU64::new(next_run_id)),
-            events_tx,
-            feed_mode: FeedMode::PaperLive,
-            source_counts: Arc::new(RwLock::new(Vec::new())),
-            discovered_markets: Arc::new(RwLock::new(Vec::new())),
-            portfolio_summary: Arc::new(RwLock::new(PortfolioSummary::default())),
-            price_snapshot: Arc::new(RwLock::new(PriceSnapshot::default())),
-            strategy_perf_summary: Arc::new(RwLock::new(StrategyPerfSummary::default())),
-            runtime_settings: Arc::new(RwLock::new(RuntimeSettings::default())),
-            strategy_stats_summary: Arc::new(RwLock::new(StrategyStatsSummary::default())),
-            btc_forecast_summary: Arc::new(RwLock::new(BtcForecastSummary::default())),
-            execution_logs: Arc::new(RwLock::new(Vec::new())),
+    pub fn push_strategy_signal(&self, signal: StrategySignal, max_entries: usize) {
+        let mut guard = self
+            .strategy_signals
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        guard.push(signal);
+
+        if guard.len() > max_entries {
+            let overflow = guard.len() - max_entries;
+            guard.drain(0..overflow);
         }
     }
 
     #[cfg(test)]
-    pub(crate) fn with_feed_mode_for_test(feed_mode: FeedMode) -> Self {
+    pub(crate) fn with_next_run_id_for_test(next_run_id: u64) -> Self {
         let (events_tx, _) = broadcast::channel(256);
         Self {
-            next_run_id: Arc::new(AtomicU64::new(0)),
+            next_run_id: Arc::new(AtomicU64::new(next_run_id)),
             events_tx,
-            feed_mode,
+            feed_mode: FeedMode::PaperLive,
             source_counts: Arc::new(RwLock::new(Vec::new())),
+            market_list_cache: Arc::new(RwLock::new(FeedCacheStats::default())),
+            feed_errors: Arc::new(RwLock::new(FeedErrorsResponse::default())),
             discovered_markets: Arc::new(RwLock::new(Vec::new())),
             portfolio_summary: Arc::new(RwLock::new(PortfolioSummary::default())),
             price_snapshot: Arc::new(RwLock::new(PriceSnapshot::default())),
             strategy_perf_summary: Arc::new(RwLock::new(StrategyPerfSummary::default())),
+            risk_status: Arc::new(RwLock::new(RiskStatus::default())),
             runtime_settings: Arc::new(RwLock::new(RuntimeSettings::default())),
+            strategy_params: Arc::new(RwLock::new(StrategyParams::default())),
             strategy_stats_summary: Arc::new(RwLock::new(StrategyStatsSummary::default())),
             btc_forecast_summary: Arc::new(RwLock::new(BtcForecastSummary::default())),
             execution_logs: Arc::new(RwLock::new(Vec::new())),
+            strategy_signals: Arc::new(RwLock::new(Vec::new())),
+            market_portfolios: Arc::new(RwLock::new(Vec::new())),
+            daily_pnl: Arc::new(RwLock::new(Vec::new())),
+            run_summaries: Arc::new(RwLock::new(HashMap::new())),
+            run_summary_dir: Arc::new(RwLock::new(None)),
+            export_dir: Arc::new(RwLock::new(None)),
+            loop_heartbeat_ms: Arc::new(AtomicU64::new(0)),
+            loop_tick: Arc::new(AtomicU64::new(0)),
+            clock: Arc::new(SystemClock),
         }
     }
 
@@ -708,28 +1645,94 @@ impl AppState {
             events_tx,
             feed_mode,
             source_counts: Arc::new(RwLock::new(source_counts)),
+            market_list_cache: Arc::new(RwLock::new(FeedCacheStats::default())),
+            feed_errors: Arc::new(RwLock::new(FeedErrorsResponse::default())),
             discovered_markets: Arc::new(RwLock::new(discovered_markets)),
             portfolio_summary: Arc::new(RwLock::new(PortfolioSummary::default())),
             price_snapshot: Arc::new(RwLock::new(PriceSnapshot::default())),
             strategy_perf_summary: Arc::new(RwLock::new(StrategyPerfSummary::default())),
+            risk_status: Arc::new(RwLock::new(RiskStatus::default())),
             runtime_settings: Arc::new(RwLock::new(RuntimeSettings::default())),
+            strategy_params: Arc::new(RwLock::new(StrategyParams::default())),
             strategy_stats_summary: Arc::new(RwLock::new(StrategyStatsSummary::default())),
             btc_forecast_summary: Arc::new(RwLock::new(BtcForecastSummary::default())),
             execution_logs: Arc::new(RwLock::new(Vec::new())),
+            strategy_signals: Arc::new(RwLock::new(Vec::new())),
+            market_portfolios: Arc::new(RwLock::new(Vec::new())),
+            daily_pnl: Arc::new(RwLock::new(Vec::new())),
+            run_summaries: Arc::new(RwLock::new(HashMap::new())),
+            run_summary_dir: Arc::new(RwLock::new(None)),
+            export_dir: Arc::new(RwLock::new(None)),
+            loop_heartbeat_ms: Arc::new(AtomicU64::new(0)),
+            loop_tick: Arc::new(AtomicU64::new(0)),
+            clock: Arc::new(SystemClock),
         }
     }
 }
 
+pub(crate) fn unix_now_ms() -> u64 {
+    SystemClock.now_ms()
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::Ordering;
 
+    use core_sim::SimReport;
+    use rust_decimal::Decimal;
+
     use super::{
-        AppState, BtcForecastSummary, DiscoveredMarket, ExecutionLogEntry, FeedMode,
-        PortfolioSummary, PriceSnapshot, RuntimeSettingsPatch, SourceCount, StrategyPerfSummary,
+        AppState, BtcForecastSummary, DiscoveredMarket, ExecutionLogEntry, FeedMode, LoopLiveness,
+        PaperOrderSide, PortfolioSummary, PriceSnapshot, RuntimeEvent, RuntimeSettingsPatch,
+        SettingsConflict, SignalAction, SourceCount, StrategyPerfSummary, StrategySignal,
         StrategyStatsSummary,
     };
 
+    #[test]
+    fn published_events_serialize_once_for_all_subscribers() {
+        let state = AppState::default();
+        let mut first = state.subscribe_events();
+        let mut second = state.subscribe_events();
+        state.publish_event(RuntimeEvent::run_started(7)).unwrap();
+
+        let first = first.try_recv().unwrap();
+        let second = second.try_recv().unwrap();
+        let json = first.json().unwrap();
+        assert!(std::sync::Arc::ptr_eq(&json, &second.json().unwrap()));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap()["event_type"],
+            "run_started"
+        );
+    }
+
+    #[test]
+    fn published_events_carry_the_active_run_id() {
+        let state = AppState::default();
+        let mut events = state.subscribe_events();
+        let fill = || RuntimeEvent::paper_fill("btc-a", PaperOrderSide::Buy, 1.0, 0.5, 0.0);
+
+        state.publish_event(fill()).unwrap();
+        let unattributed = events.try_recv().unwrap();
+        assert_eq!(unattributed.run_id, None);
+        let json: serde_json::Value = serde_json::from_str(&unattributed.json().unwrap()).unwrap();
+        assert!(json.get("run_id").is_none());
+
+        let run_id = state.start_run().unwrap();
+        assert_eq!(state.active_run_id(), Some(run_id));
+        state
+            .publish_event(RuntimeEvent::run_started(run_id))
+            .unwrap();
+        state.publish_event(fill()).unwrap();
+
+        let started = events.try_recv().unwrap();
+        let json = started.json().unwrap();
+        assert_eq!(json.matches("\"run_id\"").count(), 1);
+        let fill = events.try_recv().unwrap();
+        let json: serde_json::Value = serde_json::from_str(&fill.json().unwrap()).unwrap();
+        assert_eq!(json["event_type"], "paper_fill");
+        assert_eq!(json["run_id"].as_u64(), Some(run_id));
+    }
+
     #[test]
     fn start_run_returns_overflow_error_at_u64_max() {
         let state = AppState::new();
@@ -738,13 +1741,101 @@ mod tests {
         assert!(state.start_run().is_err());
     }
 
+    #[test]
+    fn run_summaries_are_kept_and_written_as_summary_json() {
+        let dir = std::env::temp_dir().join(format!("api-run-summary-{}", std::process::id()));
+        let state = AppState::new();
+        let report = SimReport {
+            total_return_pct: 1.5,
+            trades: 4,
+            ..SimReport::default()
+        };
+
+        state.record_run_summary(1, report).unwrap();
+        assert!(!dir.join("summary.json").exists());
+        state.set_run_summary_dir(dir.clone());
+        state.record_run_summary(2, report).unwrap();
+
+        assert_eq!(state.run_summary(2), Some(report));
+        assert_eq!(state.run_summary(3), None);
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(dir.join("summary.json")).unwrap()).unwrap();
+        assert_eq!(written["run_id"], 2);
+        assert_eq!(written["trades"], 4);
+        assert_eq!(written["total_return_pct"], 1.5);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn conflicting_settings_patches_leave_the_settings_untouched() {
+        let state = AppState::default();
+        let before = state.runtime_settings();
+
+        let conflict = state
+            .try_patch_runtime_settings(RuntimeSettingsPatch {
+                trading_paused: Some(true),
+                daily_loss_cap_pct: Some(0.25),
+                ..RuntimeSettingsPatch::default()
+            })
+            .unwrap_err();
+        assert_eq!(
+            conflict,
+            SettingsConflict::RiskAboveDailyCap {
+                risk_per_trade_pct: 0.5,
+                daily_loss_cap_pct: 0.25,
+            }
+        );
+        assert_eq!(state.runtime_settings(), before);
+
+        let patched = state
+            .try_patch_runtime_settings(RuntimeSettingsPatch {
+                risk_per_trade_pct: Some(0.25),
+                daily_loss_cap_pct: Some(0.25),
+                ..RuntimeSettingsPatch::default()
+            })
+            .unwrap();
+        assert_eq!(patched.daily_loss_cap_pct, 0.25);
+        assert_eq!(state.runtime_settings(), patched);
+    }
+
     #[test]
     fn feed_health_returns_configured_mode() {
-        let state = AppState::with_feed_mode_for_test(FeedMode::Sim);
+        let state = AppState::with_feed_mode(FeedMode::Sim);
 
         assert_eq!(state.feed_health().mode, FeedMode::Sim);
     }
 
+    #[test]
+    fn loop_goes_stale_three_intervals_after_its_last_heartbeat() {
+        let state = AppState::new();
+        assert_eq!(
+            state.loop_liveness(10_000),
+            LoopLiveness {
+                loop_alive: false,
+                last_tick_age_ms: None,
+            }
+        );
+
+        state.record_loop_heartbeat(1, 10_000);
+        assert!(state.loop_liveness(15_000).loop_alive);
+        assert_eq!(
+            state.loop_liveness(15_001),
+            LoopLiveness {
+                loop_alive: false,
+                last_tick_age_ms: Some(5_001),
+            }
+        );
+
+        state
+            .try_patch_runtime_settings(RuntimeSettingsPatch {
+                decision_interval_ms: Some(10_000),
+                ..RuntimeSettingsPatch::default()
+            })
+            .unwrap();
+        assert!(state.loop_liveness(40_000).loop_alive);
+        assert!(!state.loop_liveness(40_001).loop_alive);
+    }
+
     #[test]
     fn feed_health_and_discovered_markets_return_seeded_values() {
         let state = AppState::with_feed_data_for_test(
@@ -752,10 +1843,14 @@ mod tests {
             vec![SourceCount {
                 source: "polymarket".to_owned(),
                 count: 5,
+                endpoint: None,
+                blocked: None,
             }],
             vec![DiscoveredMarket {
                 source: "polymarket".to_owned(),
                 market_id: "btc-up-down".to_owned(),
+                liquidity_score: None,
+                selected: true,
             }],
         );
 
@@ -775,10 +1870,14 @@ mod tests {
         state.set_feed_source_counts(vec![SourceCount {
             source: "kalshi".to_owned(),
             count: 9,
+            endpoint: None,
+            blocked: None,
         }]);
         state.set_discovered_markets(vec![DiscoveredMarket {
             source: "kalshi".to_owned(),
             market_id: "eth-up-down".to_owned(),
+            liquidity_score: None,
+            selected: true,
         }]);
 
         let feed_health = state.feed_health();
@@ -790,14 +1889,14 @@ mod tests {
         assert_eq!(discovered.markets[0].market_id, "eth-up-down");
 
         state.set_portfolio_summary(PortfolioSummary {
-            equity: 12.4,
-            pnl: 2.4,
+            equity: Decimal::new(124, 1),
+            pnl: Decimal::new(24, 1),
             position_qty: 3.0,
             fills: 7,
         });
         let portfolio = state.portfolio_summary();
-        assert_eq!(portfolio.equity, 12.4);
-        assert_eq!(portfolio.pnl, 2.4);
+        assert_eq!(portfolio.equity, Decimal::new(124, 1));
+        assert_eq!(portfolio.pnl, Decimal::new(24, 1));
         assert_eq!(portfolio.position_qty, 3.0);
         assert_eq!(portfolio.fills, 7);
 
@@ -809,7 +1908,15 @@ mod tests {
             polymarket_yes_bid: Some(0.49),
             polymarket_yes_ask: Some(0.51),
             polymarket_yes_mid: Some(0.5),
-            ts: 10,
+            kalshi_market_id: Some("KXBTC15M-TEST".to_owned()),
+            kalshi_yes_bid: Some(0.47),
+            kalshi_yes_ask: Some(0.53),
+            kalshi_yes_mid: Some(0.5),
+            btc_composite_raw: Some(64_100.0),
+            btc_composite_smoothed: Some(64_090.0),
+            assets: Vec::new(),
+            tick: 10,
+            ts: 1_700_000_000_000,
         });
         let snapshot = state.price_snapshot();
         assert_eq!(snapshot.coinbase_btc_usd, Some(64_100.1));
@@ -822,7 +1929,11 @@ mod tests {
         assert_eq!(snapshot.polymarket_yes_bid, Some(0.49));
         assert_eq!(snapshot.polymarket_yes_ask, Some(0.51));
         assert_eq!(snapshot.polymarket_yes_mid, Some(0.5));
-        assert_eq!(snapshot.ts, 10);
+        assert_eq!(snapshot.kalshi_market_id.as_deref(), Some("KXBTC15M-TEST"));
+        assert_eq!(snapshot.kalshi_yes_mid, Some(0.5));
+        assert_eq!(snapshot.btc_composite_smoothed, Some(64_090.0));
+        assert_eq!(snapshot.tick, 10);
+        assert_eq!(snapshot.ts, 1_700_000_000_000);
 
         state.set_strategy_perf_summary(StrategyPerfSummary {
             execution_mode: "paper".to_owned(),
@@ -831,6 +1942,8 @@ mod tests {
             intents_per_sec: 1100,
             fills_per_sec: 700,
             lag_triggers: 10,
+            throttled_intents: 3,
+            spread_skips: 6,
             halted: false,
         });
         let perf = state.strategy_perf_summary();
@@ -840,41 +1953,54 @@ mod tests {
         assert_eq!(perf.intents_per_sec, 1100);
         assert_eq!(perf.fills_per_sec, 700);
         assert_eq!(perf.lag_triggers, 10);
+        assert_eq!(perf.throttled_intents, 3);
+        assert_eq!(perf.spread_skips, 6);
         assert!(!perf.halted);
 
-        let patched = state.patch_runtime_settings(RuntimeSettingsPatch {
-            trading_paused: Some(true),
-            lag_threshold_pct: Some(0.44),
-            risk_per_trade_pct: Some(0.7),
-            daily_loss_cap_pct: Some(2.8),
-            ..RuntimeSettingsPatch::default()
-        });
+        let patched = state
+            .try_patch_runtime_settings(RuntimeSettingsPatch {
+                trading_paused: Some(true),
+                lag_threshold_pct: Some(0.44),
+                risk_per_trade_pct: Some(0.7),
+                daily_loss_cap_pct: Some(2.8),
+                lag_cooldown_secs: Some(45),
+                lag_persistence_evals: Some(3),
+                ..RuntimeSettingsPatch::default()
+            })
+            .unwrap();
         assert!(patched.trading_paused);
+        assert_eq!(patched.lag_cooldown_secs, 45);
+        assert_eq!(patched.lag_persistence_evals, 3);
         assert_eq!(patched.lag_threshold_pct, 0.44);
         assert_eq!(patched.risk_per_trade_pct, 0.7);
         assert_eq!(patched.daily_loss_cap_pct, 2.8);
 
         state.set_strategy_stats_summary(StrategyStatsSummary {
-            balance: 10_100.0,
-            total_pnl: 100.0,
+            balance: Decimal::from(10_100),
+            total_pnl: Decimal::from(100),
             exec_latency_us: 77,
             win_rate: 60.0,
             btc_usd: 66_000.0,
         });
-        assert_eq!(state.strategy_stats_summary().balance, 10_100.0);
+        assert_eq!(
+            state.strategy_stats_summary().balance,
+            Decimal::from(10_100)
+        );
 
         state.set_btc_forecast_summary(BtcForecastSummary {
             horizon_minutes: 15,
             current_btc_usd: 66_000.0,
             forecast_btc_usd: 66_120.0,
             delta_pct: 0.18,
-            ts: 12,
+            tick: 12,
+            ts: 1_700_000_000_000,
         });
         assert_eq!(state.btc_forecast_summary().horizon_minutes, 15);
 
         state.push_execution_log(
             ExecutionLogEntry {
-                ts: 12,
+                tick: 12,
+                ts: 1_700_000_000_000,
                 event: "paper_fill".to_string(),
                 headline: "Filled BUY".to_string(),
                 detail: "qty 1 @ 0.51".to_string(),
@@ -882,5 +2008,25 @@ mod tests {
             128,
         );
         assert_eq!(state.execution_logs().len(), 1);
+
+        for tick in 1..=3 {
+            state.push_strategy_signal(
+                StrategySignal {
+                    tick,
+                    ts: 1_700_000_000_000,
+                    source: "polymarket".to_string(),
+                    market_id: "btc-a".to_string(),
+                    mid_yes: 0.5,
+                    fair_yes_px: 0.51,
+                    divergence_pct: 2.0,
+                    triggered: true,
+                    action: SignalAction::Throttled,
+                },
+                2,
+            );
+        }
+        let signals = state.strategy_signals();
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals[0].tick, 2);
     }
 }
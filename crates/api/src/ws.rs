@@ -1,24 +1,56 @@
+use std::sync::Arc;
+
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
+    http::StatusCode,
     response::Response,
 };
+use serde::Deserialize;
+use tokio::time::{self, Duration, Instant};
+
+use crate::state::{AppState, PublishedEvent, RuntimeEvent};
+
+/// Longest coalescing window a connection may ask for.
+const MAX_BATCH_MS: u64 = 1_000;
+/// A batch this large goes out without waiting for its window to close.
+const MAX_BATCH_EVENTS: usize = 256;
 
-use crate::state::{AppState, RuntimeEvent};
+#[derive(Debug, Default, Deserialize)]
+pub struct EventStreamParams {
+    /// When set, events arriving within this many milliseconds of the first
+    /// one are sent together as a single JSON array frame.
+    batch_ms: Option<u64>,
+}
 
-pub async fn events_socket(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
-    ws.on_upgrade(move |socket| stream_events(socket, state))
+pub async fn events_socket(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Query(params): Query<EventStreamParams>,
+) -> Result<Response, StatusCode> {
+    let window = match params.batch_ms {
+        None | Some(0) => None,
+        Some(ms) if ms <= MAX_BATCH_MS => Some(Duration::from_millis(ms)),
+        Some(_) => return Err(StatusCode::BAD_REQUEST),
+    };
+    Ok(ws.on_upgrade(move |socket| stream_events(socket, state, window)))
 }
 
-async fn stream_events(mut socket: WebSocket, state: AppState) {
-    let connected = RuntimeEvent::connected();
-    if send_event(&mut socket, &connected).await.is_err() {
+async fn stream_events(mut socket: WebSocket, state: AppState, window: Option<Duration>) {
+    let connected = PublishedEvent::new(RuntimeEvent::connected(state.active_run_id()));
+    let sent = match window {
+        Some(_) => send_batch(&mut socket, &mut connected.json().into_iter().collect()).await,
+        None => send_event(&mut socket, &connected).await,
+    };
+    if sent.is_err() {
         return;
     }
 
     let mut events = state.subscribe_events();
+    let mut batch: Vec<Arc<str>> = Vec::new();
+    let mut flush_at: Option<Instant> = None;
     loop {
         tokio::select! {
             inbound = socket.recv() => {
@@ -31,23 +63,62 @@ async fn stream_events(mut socket: WebSocket, state: AppState) {
             event = events.recv() => {
                 match event {
                     Ok(event) => {
-                        if send_event(&mut socket, &event).await.is_err() {
-                            return;
+                        let Some(window) = window else {
+                            if send_event(&mut socket, &event).await.is_err() {
+                                return;
+                            }
+                            continue;
+                        };
+                        batch.extend(event.json());
+                        flush_at.get_or_insert_with(|| Instant::now() + window);
+                        if batch.len() >= MAX_BATCH_EVENTS {
+                            flush_at = None;
+                            if send_batch(&mut socket, &mut batch).await.is_err() {
+                                return;
+                            }
                         }
                     }
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
-                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                        let _ = send_batch(&mut socket, &mut batch).await;
+                        return;
+                    }
+                }
+            }
+            _ = time::sleep_until(flush_at.unwrap_or_else(Instant::now)), if flush_at.is_some() => {
+                flush_at = None;
+                if send_batch(&mut socket, &mut batch).await.is_err() {
+                    return;
                 }
             }
         }
     }
 }
 
-async fn send_event(socket: &mut WebSocket, event: &RuntimeEvent) -> Result<(), ()> {
-    let payload = event_json(event)?;
-    socket.send(Message::Text(payload)).await.map_err(|_| ())
+/// Sends the event's shared JSON, so every socket reuses one serialization.
+async fn send_event(socket: &mut WebSocket, event: &PublishedEvent) -> Result<(), ()> {
+    let payload = event.json().ok_or(())?;
+    socket
+        .send(Message::Text(payload.to_string()))
+        .await
+        .map_err(|_| ())
 }
 
-fn event_json(event: &RuntimeEvent) -> Result<String, ()> {
-    serde_json::to_string(event).map_err(|_| ())
+/// Sends the collected events as one JSON array and empties `batch`; an
+/// empty batch sends nothing.
+async fn send_batch(socket: &mut WebSocket, batch: &mut Vec<Arc<str>>) -> Result<(), ()> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+    let mut payload =
+        String::with_capacity(batch.iter().map(|json| json.len() + 1).sum::<usize>() + 1);
+    payload.push('[');
+    for (idx, json) in batch.drain(..).enumerate() {
+        if idx > 0 {
+            payload.push(',');
+        }
+        payload.push_str(&json);
+    }
+    payload.push(']');
+    socket.send(Message::Text(payload)).await.map_err(|_| ())
 }
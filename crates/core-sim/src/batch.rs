@@ -0,0 +1,349 @@
+use std::collections::VecDeque;
+
+use rayon::prelude::*;
+
+use crate::config::SimConfig;
+use crate::fills::{Fill, FillSampler};
+use crate::generators::MarketLagGenerator;
+use crate::orderbook::Side;
+use crate::report::{ReportRecorder, SimReport};
+use crate::rng::{Lcg, Rng};
+use crate::scenario::Scenario;
+use crate::state::SimState;
+
+const START_PRICE: f64 = 100.0;
+
+/// How one seeded run ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimOutcome {
+    pub seed: u64,
+    pub terminal_equity: f64,
+    /// Largest fall in equity from its running peak, in percent.
+    pub max_drawdown_pct: f64,
+    pub halted: bool,
+    pub trades: u64,
+    pub report: SimReport,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Distribution {
+    pub mean: f64,
+    pub min: f64,
+    pub p5: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    /// Nearest-rank percentiles; all zero for no samples.
+    pub fn from_samples(mut samples: Vec<f64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(f64::total_cmp);
+        let rank = |pct: f64| {
+            let index = ((pct / 100.0) * samples.len() as f64).ceil() as usize;
+            samples[index.saturating_sub(1).min(samples.len() - 1)]
+        };
+
+        Self {
+            mean: samples.iter().sum::<f64>() / samples.len() as f64,
+            min: samples[0],
+            p5: rank(5.0),
+            p50: rank(50.0),
+            p95: rank(95.0),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchReport {
+    pub outcomes: Vec<SimOutcome>,
+    pub halted_runs: u64,
+    pub halt_rate: f64,
+    pub terminal_equity: Distribution,
+    pub max_drawdown_pct: Distribution,
+}
+
+/// Runs a lag-arb sim for `ticks` decisions: the market price trails a
+/// fair price from `price_process` by a generated lag, and the sim holds
+/// `max_position_pct` of equity on the side of any divergence past
+/// `divergence_threshold`, paying taker fees on every change. Each change
+/// fills only as far as `fill_model` allows. Breaching the daily loss cap
+/// flattens the position and halts trading.
+pub fn run_seeded(config: &SimConfig, seed: u64, ticks: u64) -> SimOutcome {
+    let jitter_ms = config.market_lag_ms / 4;
+    let mut rng = Lcg::new(seed);
+    let mut fair = config.price_process.path_from_rng(rng.fork(), START_PRICE);
+    let mut lag = MarketLagGenerator::from_rng(rng.fork(), config.market_lag_ms, jitter_ms);
+    let fill_sampler = config.fill_model.sampler_from_rng(rng.fork());
+    let path = (0..ticks).map(|_| (Some(fair.next_price()), lag.next_lag_ms()));
+
+    simulate(
+        config,
+        seed,
+        config.market_lag_ms.saturating_add(jitter_ms),
+        fill_sampler,
+        path,
+    )
+}
+
+/// The same sim as [`run_seeded`], driven by a stress scenario's prices
+/// and lags. Feed-gap ticks leave the market where it was and skip the
+/// decision.
+pub fn run_scenario(config: &SimConfig, scenario: &Scenario, seed: u64) -> SimOutcome {
+    let mut rng = Lcg::new(seed);
+    let ticks = scenario.run_with_rng(&mut rng);
+    let max_lag_ms = ticks.iter().map(|tick| tick.lag_ms).max().unwrap_or(0);
+    let fill_sampler = config.fill_model.sampler_from_rng(rng.fork());
+    let path = ticks.into_iter().map(|tick| (tick.price, tick.lag_ms));
+
+    simulate(config, seed, max_lag_ms, fill_sampler, path)
+}
+
+/// Steps the sim along `path`, one `(fair price, lag)` per decision.
+fn simulate(
+    config: &SimConfig,
+    seed: u64,
+    max_lag_ms: u64,
+    mut fill_sampler: FillSampler,
+    path: impl Iterator<Item = (Option<f64>, u64)>,
+) -> SimOutcome {
+    let interval_ms = config.decision_interval_ms.max(1);
+    let max_lag_ticks = (max_lag_ms / interval_ms) as usize;
+
+    let mut history = VecDeque::with_capacity(max_lag_ticks + 1);
+    let mut state = SimState::default();
+    let mut recorder = ReportRecorder::new(state.equity);
+    let mut market_px = START_PRICE;
+
+    for (fair_px, lag_ms) in path {
+        let Some(fair_px) = fair_px else {
+            continue;
+        };
+        history.push_back(fair_px);
+        if history.len() > max_lag_ticks + 1 {
+            history.pop_front();
+        }
+        let lag_ticks = (lag_ms / interval_ms) as usize;
+        market_px = history[history.len().saturating_sub(lag_ticks + 1)];
+        if market_px <= 0.0 {
+            continue;
+        }
+
+        let was_halted = state.halted;
+        state.mark_to_market(market_px, config);
+        recorder.mark(state.equity);
+        if state.halted && !was_halted {
+            recorder.halt();
+        }
+
+        let target = if state.halted {
+            0.0
+        } else {
+            let divergence = (fair_px - market_px) / market_px;
+            let size = config.max_position_pct * state.equity / market_px;
+            if divergence > config.divergence_threshold {
+                size
+            } else if divergence < -config.divergence_threshold {
+                -size
+            } else {
+                0.0
+            }
+        };
+        let trade = target - state.position;
+        if trade != 0.0 {
+            let side = if trade > 0.0 { Side::Buy } else { Side::Sell };
+            let qty = trade.abs() * fill_sampler.capture_fraction();
+            let fill = Fill {
+                price: market_px,
+                qty,
+                fee: config.fees.taker_fee(qty * market_px),
+            };
+            let reduces = state.position * trade < 0.0;
+            let realized_before = state.realized_pnl;
+            state.apply_fill(side, &fill);
+            recorder.trade(reduces.then_some(state.realized_pnl - realized_before - fill.fee));
+            if target != 0.0 && target * trade > 0.0 {
+                recorder.lag_trigger();
+            }
+        }
+    }
+
+    let terminal_equity = state.cash + state.position * market_px;
+    let report = recorder.finish(terminal_equity);
+    SimOutcome {
+        seed,
+        terminal_equity,
+        max_drawdown_pct: recorder.max_drawdown_pct(),
+        halted: state.halted,
+        trades: report.trades,
+        report,
+    }
+}
+
+/// Runs seeds `base_seed..base_seed + runs` in parallel and summarises them.
+pub fn run_batch(config: &SimConfig, base_seed: u64, runs: u64, ticks: u64) -> BatchReport {
+    let outcomes: Vec<SimOutcome> = (0..runs)
+        .into_par_iter()
+        .map(|offset| run_seeded(config, base_seed.wrapping_add(offset), ticks))
+        .collect();
+    let halted_runs = outcomes.iter().filter(|outcome| outcome.halted).count() as u64;
+
+    BatchReport {
+        halted_runs,
+        halt_rate: if runs == 0 {
+            0.0
+        } else {
+            halted_runs as f64 / runs as f64
+        },
+        terminal_equity: Distribution::from_samples(
+            outcomes
+                .iter()
+                .map(|outcome| outcome.terminal_equity)
+                .collect(),
+        ),
+        max_drawdown_pct: Distribution::from_samples(
+            outcomes
+                .iter()
+                .map(|outcome| outcome.max_drawdown_pct)
+                .collect(),
+        ),
+        outcomes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_batch, run_scenario, run_seeded, Distribution};
+    use crate::config::{FillModel, PriceProcess, SimConfig};
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn batches_are_reproducible_and_summarised() {
+        let config = SimConfig::default();
+        let report = run_batch(&config, 100, 16, 500);
+
+        assert_eq!(report.outcomes.len(), 16);
+        assert_eq!(report.outcomes[3], run_seeded(&config, 103, 500));
+        assert_eq!(report, run_batch(&config, 100, 16, 500));
+        assert!(report.outcomes.iter().all(|outcome| outcome.trades > 0));
+        assert!(report.terminal_equity.min <= report.terminal_equity.p50);
+        assert!(report.terminal_equity.p50 <= report.terminal_equity.max);
+        assert_eq!(report.halt_rate, report.halted_runs as f64 / 16.0);
+    }
+
+    #[test]
+    fn a_tight_loss_cap_halts_runs() {
+        let config = SimConfig {
+            daily_loss_cap_pct: 0.0,
+            max_position_pct: 0.5,
+            ..SimConfig::default()
+        };
+        let report = run_batch(&config, 1, 8, 500);
+
+        assert!(report.halted_runs > 0);
+        assert!(report
+            .outcomes
+            .iter()
+            .filter(|outcome| outcome.halted)
+            .all(|outcome| outcome.max_drawdown_pct > 0.0));
+    }
+
+    #[test]
+    fn runs_follow_the_configured_price_process() {
+        let jumpy = SimConfig {
+            price_process: PriceProcess::JumpDiffusion {
+                drift: 0.0,
+                volatility: 0.001,
+                jump_intensity: 0.02,
+                jump_mean: -0.05,
+                jump_std: 0.02,
+            },
+            ..SimConfig::default()
+        };
+        let gbm = SimConfig {
+            price_process: PriceProcess::Gbm {
+                drift: 0.0,
+                volatility: 0.001,
+            },
+            ..SimConfig::default()
+        };
+
+        let jumpy = run_batch(&jumpy, 1, 8, 500);
+        let gbm = run_batch(&gbm, 1, 8, 500);
+        assert!(jumpy.max_drawdown_pct.mean > gbm.max_drawdown_pct.mean);
+    }
+
+    #[test]
+    fn partial_fills_are_seeded_per_run() {
+        let partial = SimConfig {
+            fill_model: FillModel::Partial {
+                min_fraction: 0.2,
+                max_fraction: 0.6,
+            },
+            ..SimConfig::default()
+        };
+        let full = SimConfig::default();
+
+        assert_eq!(run_seeded(&partial, 9, 500), run_seeded(&partial, 9, 500));
+        assert_ne!(run_seeded(&partial, 9, 500), run_seeded(&full, 9, 500));
+    }
+
+    #[test]
+    fn outcomes_carry_a_performance_report() {
+        let outcome = run_seeded(&SimConfig::default(), 5, 2_000);
+        let report = outcome.report;
+
+        assert_eq!(report.trades, outcome.trades);
+        assert_eq!(report.max_drawdown_pct, outcome.max_drawdown_pct);
+        assert!(report.lag_triggers > 0 && report.lag_triggers <= report.trades);
+        assert!((0.0..=1.0).contains(&report.hit_rate));
+        assert!(
+            (report.total_return_pct - (outcome.terminal_equity / 100_000.0 - 1.0) * 100.0).abs()
+                < 1e-9
+        );
+        assert_eq!(report.halt_events, u64::from(outcome.halted));
+    }
+
+    #[test]
+    fn scenarios_drive_the_same_sim() {
+        let scenario = Scenario::from_toml(
+            r#"
+            name = "gap"
+            ticks = 400
+            start_price = 100.0
+            max_step = 0.5
+            base_lag_ms = 200
+            spread = 0.02
+
+            [[events]]
+            at_tick = 100
+            kind = "feed_gap"
+            duration_ticks = 50
+            "#,
+        )
+        .unwrap();
+        let outcome = run_scenario(&SimConfig::default(), &scenario, 3);
+
+        assert_eq!(outcome, run_scenario(&SimConfig::default(), &scenario, 3));
+        assert!(outcome.trades > 0);
+    }
+
+    #[test]
+    fn distribution_uses_nearest_rank_percentiles() {
+        let distribution = Distribution::from_samples((1..=20).map(f64::from).collect());
+
+        assert_eq!(distribution.min, 1.0);
+        assert_eq!(distribution.p5, 1.0);
+        assert_eq!(distribution.p50, 10.0);
+        assert_eq!(distribution.p95, 19.0);
+        assert_eq!(distribution.mean, 10.5);
+        assert_eq!(
+            Distribution::from_samples(Vec::new()),
+            Distribution::default()
+        );
+    }
+}
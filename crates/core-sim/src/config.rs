@@ -1,3 +1,105 @@
+use crate::fills::FillSampler;
+use crate::generators::{GbmGenerator, JumpDiffusionGenerator, PriceGenerator, PricePath};
+use crate::rng::{Lcg, Rng};
+
+/// Per-fill fees, in basis points of notional, with a floor per fill.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FeeSchedule {
+    pub maker_bps: f64,
+    pub taker_bps: f64,
+    pub min_fee: f64,
+}
+
+impl FeeSchedule {
+    pub fn maker_fee(&self, notional: f64) -> f64 {
+        fee(self.maker_bps, self.min_fee, notional)
+    }
+
+    pub fn taker_fee(&self, notional: f64) -> f64 {
+        fee(self.taker_bps, self.min_fee, notional)
+    }
+}
+
+fn fee(bps: f64, min_fee: f64, notional: f64) -> f64 {
+    (notional.abs() * bps / 10_000.0).max(min_fee)
+}
+
+/// Which generator drives the simulated fair price. Rates are per tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceProcess {
+    UniformWalk {
+        max_step: f64,
+    },
+    Gbm {
+        drift: f64,
+        volatility: f64,
+    },
+    JumpDiffusion {
+        drift: f64,
+        volatility: f64,
+        jump_intensity: f64,
+        jump_mean: f64,
+        jump_std: f64,
+    },
+}
+
+impl PriceProcess {
+    pub fn path(&self, seed: u64, start_price: f64) -> PricePath {
+        self.path_from_rng(Lcg::new(seed), start_price)
+    }
+
+    pub fn path_from_rng<R: Rng>(&self, rng: R, start_price: f64) -> PricePath<R> {
+        match *self {
+            Self::UniformWalk { max_step } => {
+                PricePath::UniformWalk(PriceGenerator::from_rng(rng, start_price, max_step))
+            }
+            Self::Gbm { drift, volatility } => {
+                PricePath::Gbm(GbmGenerator::from_rng(rng, start_price, drift, volatility))
+            }
+            Self::JumpDiffusion {
+                drift,
+                volatility,
+                jump_intensity,
+                jump_mean,
+                jump_std,
+            } => PricePath::JumpDiffusion(JumpDiffusionGenerator::from_rng(
+                rng,
+                start_price,
+                drift,
+                volatility,
+                jump_intensity,
+                jump_mean,
+                jump_std,
+            )),
+        }
+    }
+}
+
+/// How much of the displayed size a marketable order captures at each
+/// price it reaches.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum FillModel {
+    /// Every displayed lot is still there when the order arrives.
+    #[default]
+    Full,
+    /// Captures a uniform random fraction in `[min_fraction, max_fraction]`
+    /// of each price's displayed size; the rest is pulled first.
+    Partial {
+        min_fraction: f64,
+        max_fraction: f64,
+    },
+}
+
+impl FillModel {
+    pub fn sampler(&self, seed: u64) -> FillSampler {
+        FillSampler::new(*self, seed)
+    }
+
+    pub fn sampler_from_rng<R: Rng>(&self, rng: R) -> FillSampler<R> {
+        FillSampler::from_rng(*self, rng)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SimConfig {
     pub divergence_threshold: f64,
@@ -5,7 +107,9 @@ pub struct SimConfig {
     pub daily_loss_cap_pct: f64,
     pub market_lag_ms: u64,
     pub decision_interval_ms: u64,
-    pub fee_bps: f64,
+    pub fees: FeeSchedule,
+    pub price_process: PriceProcess,
+    pub fill_model: FillModel,
 }
 
 impl Default for SimConfig {
@@ -16,7 +120,13 @@ impl Default for SimConfig {
             daily_loss_cap_pct: 0.02,
             market_lag_ms: 120,
             decision_interval_ms: 50,
-            fee_bps: 2.0,
+            fees: FeeSchedule {
+                maker_bps: 0.0,
+                taker_bps: 2.0,
+                min_fee: 0.0,
+            },
+            price_process: PriceProcess::UniformWalk { max_step: 0.5 },
+            fill_model: FillModel::Full,
         }
     }
 }
@@ -1,7 +1,11 @@
+use crate::config::FillModel;
+use crate::rng::{Lcg, Rng};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Fill {
     pub price: f64,
     pub qty: f64,
+    pub fee: f64,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -10,6 +14,8 @@ pub struct FillSummary {
     pub filled_qty: f64,
     pub avg_price: f64,
     pub remaining_qty: f64,
+    /// Sum of the fills' fees.
+    pub fees: f64,
 }
 
 impl Default for FillSummary {
@@ -19,6 +25,90 @@ impl Default for FillSummary {
             filled_qty: 0.0,
             avg_price: 0.0,
             remaining_qty: 0.0,
+            fees: 0.0,
+        }
+    }
+}
+
+/// Seeded draws of the fraction of displayed size a [`FillModel`] lets an
+/// order capture.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FillSampler<R = Lcg> {
+    model: FillModel,
+    rng: R,
+}
+
+impl FillSampler {
+    pub fn new(model: FillModel, seed: u64) -> Self {
+        Self::from_rng(model, Lcg::new(seed))
+    }
+}
+
+impl<R: Rng> FillSampler<R> {
+    pub fn from_rng(model: FillModel, rng: R) -> Self {
+        if let FillModel::Partial {
+            min_fraction,
+            max_fraction,
+        } = model
+        {
+            assert!(
+                (0.0..=1.0).contains(&min_fraction) && (0.0..=1.0).contains(&max_fraction),
+                "fill fractions must be within [0, 1]"
+            );
+            assert!(
+                min_fraction <= max_fraction,
+                "min_fraction must not exceed max_fraction"
+            );
         }
+
+        Self { model, rng }
+    }
+
+    pub fn model(&self) -> FillModel {
+        self.model
+    }
+
+    /// Always 1.0 under [`FillModel::Full`], without drawing.
+    pub fn capture_fraction(&mut self) -> f64 {
+        match self.model {
+            FillModel::Full => 1.0,
+            FillModel::Partial {
+                min_fraction,
+                max_fraction,
+            } => min_fraction + (max_fraction - min_fraction) * self.rng.next_unit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FillSampler;
+    use crate::config::FillModel;
+
+    #[test]
+    fn partial_fractions_are_seeded_and_bounded() {
+        let model = FillModel::Partial {
+            min_fraction: 0.25,
+            max_fraction: 0.75,
+        };
+        let mut sampler = model.sampler(7);
+        let draws: Vec<f64> = (0..100).map(|_| sampler.capture_fraction()).collect();
+
+        assert!(draws.iter().all(|draw| (0.25..=0.75).contains(draw)));
+        let mut replay = FillSampler::new(model, 7);
+        assert!(draws.iter().all(|draw| *draw == replay.capture_fraction()));
+        assert_eq!(FillModel::Full.sampler(7).capture_fraction(), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "min_fraction must not exceed max_fraction")]
+    fn inverted_fraction_bounds_are_rejected() {
+        FillSampler::new(
+            FillModel::Partial {
+                min_fraction: 0.8,
+                max_fraction: 0.2,
+            },
+            1,
+        );
     }
 }
@@ -1,12 +1,20 @@
+use crate::rng::{Lcg, Rng};
+
 #[derive(Debug, Clone)]
-pub struct PriceGenerator {
-    state: u64,
+pub struct PriceGenerator<R = Lcg> {
+    rng: R,
     price: f64,
     max_step: f64,
 }
 
 impl PriceGenerator {
     pub fn new(seed: u64, start_price: f64, max_step: f64) -> Self {
+        Self::from_rng(Lcg::new(seed), start_price, max_step)
+    }
+}
+
+impl<R: Rng> PriceGenerator<R> {
+    pub fn from_rng(rng: R, start_price: f64, max_step: f64) -> Self {
         assert!(
             start_price.is_finite() && start_price >= 0.0,
             "start_price must be finite and non-negative"
@@ -17,31 +25,201 @@ impl PriceGenerator {
         );
 
         Self {
-            state: seed,
+            rng,
             price: start_price,
             max_step,
         }
     }
 
     pub fn next_price(&mut self) -> f64 {
-        let unit = next_unit(&mut self.state);
+        let unit = self.rng.next_unit();
         let delta = (unit * 2.0 - 1.0) * self.max_step;
         self.price = (self.price + delta).max(0.0);
         self.price
     }
+
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    pub fn max_step(&self) -> f64 {
+        self.max_step
+    }
+
+    pub fn set_max_step(&mut self, max_step: f64) {
+        assert!(
+            max_step.is_finite() && max_step >= 0.0,
+            "max_step must be finite and non-negative"
+        );
+        self.max_step = max_step;
+    }
+
+    /// Scales the current price at once, e.g. `0.8` for a 20% gap down.
+    pub fn shock(&mut self, factor: f64) {
+        assert!(
+            factor.is_finite() && factor >= 0.0,
+            "shock factor must be finite and non-negative"
+        );
+        self.price *= factor;
+    }
+}
+
+/// Geometric Brownian motion with per-tick `drift` and `volatility` of log
+/// returns.
+#[derive(Debug, Clone)]
+pub struct GbmGenerator<R = Lcg> {
+    rng: R,
+    price: f64,
+    drift: f64,
+    volatility: f64,
+}
+
+impl GbmGenerator {
+    pub fn new(seed: u64, start_price: f64, drift: f64, volatility: f64) -> Self {
+        Self::from_rng(Lcg::new(seed), start_price, drift, volatility)
+    }
+}
+
+impl<R: Rng> GbmGenerator<R> {
+    pub fn from_rng(rng: R, start_price: f64, drift: f64, volatility: f64) -> Self {
+        assert!(
+            start_price.is_finite() && start_price > 0.0,
+            "start_price must be finite and positive"
+        );
+        assert!(drift.is_finite(), "drift must be finite");
+        assert!(
+            volatility.is_finite() && volatility >= 0.0,
+            "volatility must be finite and non-negative"
+        );
+
+        Self {
+            rng,
+            price: start_price,
+            drift,
+            volatility,
+        }
+    }
+
+    pub fn next_price(&mut self) -> f64 {
+        self.price *= gbm_factor(&mut self.rng, self.drift, self.volatility);
+        self.price
+    }
+}
+
+/// GBM plus Poisson-timed jumps whose log sizes are normal with
+/// `jump_mean` and `jump_std`. `jump_intensity` is the expected number of
+/// jumps per tick.
+#[derive(Debug, Clone)]
+pub struct JumpDiffusionGenerator<R = Lcg> {
+    rng: R,
+    price: f64,
+    drift: f64,
+    volatility: f64,
+    jump_intensity: f64,
+    jump_mean: f64,
+    jump_std: f64,
+}
+
+impl JumpDiffusionGenerator {
+    pub fn new(
+        seed: u64,
+        start_price: f64,
+        drift: f64,
+        volatility: f64,
+        jump_intensity: f64,
+        jump_mean: f64,
+        jump_std: f64,
+    ) -> Self {
+        Self::from_rng(
+            Lcg::new(seed),
+            start_price,
+            drift,
+            volatility,
+            jump_intensity,
+            jump_mean,
+            jump_std,
+        )
+    }
+}
+
+impl<R: Rng> JumpDiffusionGenerator<R> {
+    pub fn from_rng(
+        rng: R,
+        start_price: f64,
+        drift: f64,
+        volatility: f64,
+        jump_intensity: f64,
+        jump_mean: f64,
+        jump_std: f64,
+    ) -> Self {
+        let diffusion = GbmGenerator::from_rng(rng, start_price, drift, volatility);
+        assert!(
+            jump_intensity.is_finite() && jump_intensity >= 0.0,
+            "jump_intensity must be finite and non-negative"
+        );
+        assert!(jump_mean.is_finite(), "jump_mean must be finite");
+        assert!(
+            jump_std.is_finite() && jump_std >= 0.0,
+            "jump_std must be finite and non-negative"
+        );
+
+        Self {
+            rng: diffusion.rng,
+            price: diffusion.price,
+            drift,
+            volatility,
+            jump_intensity,
+            jump_mean,
+            jump_std,
+        }
+    }
+
+    pub fn next_price(&mut self) -> f64 {
+        let mut factor = gbm_factor(&mut self.rng, self.drift, self.volatility);
+        for _ in 0..self.rng.next_poisson(self.jump_intensity) {
+            let log_jump = self.jump_mean + self.jump_std * self.rng.next_normal();
+            factor *= log_jump.exp();
+        }
+        self.price *= factor;
+        self.price
+    }
+}
+
+/// A price process picked at runtime from [`PriceProcess`](crate::PriceProcess).
+#[derive(Debug, Clone)]
+pub enum PricePath<R = Lcg> {
+    UniformWalk(PriceGenerator<R>),
+    Gbm(GbmGenerator<R>),
+    JumpDiffusion(JumpDiffusionGenerator<R>),
+}
+
+impl<R: Rng> PricePath<R> {
+    pub fn next_price(&mut self) -> f64 {
+        match self {
+            Self::UniformWalk(generator) => generator.next_price(),
+            Self::Gbm(generator) => generator.next_price(),
+            Self::JumpDiffusion(generator) => generator.next_price(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct MarketLagGenerator {
-    state: u64,
+pub struct MarketLagGenerator<R = Lcg> {
+    rng: R,
     base_lag_ms: u64,
     jitter_ms: u64,
 }
 
 impl MarketLagGenerator {
     pub fn new(seed: u64, base_lag_ms: u64, jitter_ms: u64) -> Self {
+        Self::from_rng(Lcg::new(seed), base_lag_ms, jitter_ms)
+    }
+}
+
+impl<R: Rng> MarketLagGenerator<R> {
+    pub fn from_rng(rng: R, base_lag_ms: u64, jitter_ms: u64) -> Self {
         Self {
-            state: seed,
+            rng,
             base_lag_ms,
             jitter_ms,
         }
@@ -57,30 +235,22 @@ impl MarketLagGenerator {
         let width = max - min;
 
         if width == u64::MAX {
-            return next_u64(&mut self.state);
+            return self.rng.next_u64();
         }
 
         let span = width + 1;
-        let offset = next_u64(&mut self.state) % span;
+        let offset = self.rng.next_u64() % span;
         min + offset
     }
 }
 
-fn next_u64(state: &mut u64) -> u64 {
-    *state = state
-        .wrapping_mul(6364136223846793005)
-        .wrapping_add(1442695040888963407);
-    *state
-}
-
-fn next_unit(state: &mut u64) -> f64 {
-    let value = next_u64(state);
-    (value as f64) / (u64::MAX as f64)
+fn gbm_factor<R: Rng>(rng: &mut R, drift: f64, volatility: f64) -> f64 {
+    ((drift - 0.5 * volatility * volatility) + volatility * rng.next_normal()).exp()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{MarketLagGenerator, PriceGenerator};
+    use super::{GbmGenerator, JumpDiffusionGenerator, MarketLagGenerator, PriceGenerator};
 
     #[test]
     fn seeded_generators_are_deterministic() {
@@ -129,6 +299,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn gbm_log_returns_match_drift_and_volatility() {
+        let mut gbm = GbmGenerator::new(11, 100.0, 0.0, 0.01);
+        let mut previous = 100.0_f64;
+        let returns: Vec<f64> = (0..20_000)
+            .map(|_| {
+                let price = gbm.next_price();
+                let log_return = (price / previous).ln();
+                previous = price;
+                log_return
+            })
+            .collect();
+
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance =
+            returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+        assert!((mean + 0.5e-4).abs() < 2e-4, "mean was {mean}");
+        assert!(
+            (variance.sqrt() - 0.01).abs() < 5e-4,
+            "vol was {}",
+            variance.sqrt()
+        );
+        assert!(previous > 0.0);
+    }
+
+    #[test]
+    fn jump_diffusion_adds_jumps_at_the_configured_rate() {
+        // No diffusion, so every move is a jump of exactly -10% in log terms.
+        let mut jumps = JumpDiffusionGenerator::new(5, 100.0, 0.0, 0.0, 0.05, -0.1, 0.0);
+        let mut previous = 100.0_f64;
+        let mut jump_count = 0;
+        for _ in 0..10_000 {
+            let price = jumps.next_price();
+            let moves = ((previous / price).ln() / 0.1).round() as u32;
+            jump_count += moves;
+            previous = price;
+        }
+
+        assert!((400..=600).contains(&jump_count), "{jump_count} jumps");
+    }
+
     #[test]
     #[should_panic(expected = "start_price must be finite and non-negative")]
     fn price_generator_rejects_invalid_start_price() {
@@ -0,0 +1,145 @@
+use crate::fills::FillSummary;
+use crate::generators::MarketLagGenerator;
+use crate::orderbook::{OrderBook, Side};
+
+/// A market order decided at `decided_at_ms` that reaches the book later.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingOrder {
+    pub side: Side,
+    pub qty: f64,
+    pub decided_at_ms: u64,
+    pub arrives_at_ms: u64,
+    /// Touch on the order's side when it was decided, if there was one.
+    pub decision_px: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelayedExecution {
+    pub order: PendingOrder,
+    pub fill: FillSummary,
+    /// What the delay cost against filling the same quantity at the
+    /// decision-time touch; negative when the book moved in our favour.
+    pub latency_cost: f64,
+}
+
+/// Holds decided orders back by a generated lag before they hit the book,
+/// so the book can move between decision and execution.
+#[derive(Debug, Clone)]
+pub struct LatencyInjector {
+    lag: MarketLagGenerator,
+    /// Ordered by arrival, then by decision.
+    pending: Vec<PendingOrder>,
+}
+
+impl LatencyInjector {
+    pub fn new(lag: MarketLagGenerator) -> Self {
+        Self {
+            lag,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Queues a market order decided at `now_ms` and returns when it arrives.
+    pub fn submit(&mut self, now_ms: u64, side: Side, qty: f64, book: &OrderBook) -> u64 {
+        let arrives_at_ms = now_ms.saturating_add(self.lag.next_lag_ms());
+        let touch = match side {
+            Side::Buy => book.best_ask(),
+            Side::Sell => book.best_bid(),
+        };
+        let order = PendingOrder {
+            side,
+            qty,
+            decided_at_ms: now_ms,
+            arrives_at_ms,
+            decision_px: touch.map(|level| level.price),
+        };
+
+        let position = self
+            .pending
+            .partition_point(|pending| pending.arrives_at_ms <= arrives_at_ms);
+        self.pending.insert(position, order);
+        arrives_at_ms
+    }
+
+    /// Executes every order that has arrived by `now_ms` against `book` as
+    /// it stands now.
+    pub fn release_due(&mut self, now_ms: u64, book: &mut OrderBook) -> Vec<DelayedExecution> {
+        let due = self
+            .pending
+            .partition_point(|pending| pending.arrives_at_ms <= now_ms);
+
+        self.pending
+            .drain(..due)
+            .map(|order| {
+                let fill = match order.side {
+                    Side::Buy => book.execute_market_buy(order.qty),
+                    Side::Sell => book.execute_market_sell(order.qty),
+                };
+                let latency_cost = match (order.decision_px, order.side) {
+                    (Some(px), Side::Buy) => (fill.avg_price - px) * fill.filled_qty,
+                    (Some(px), Side::Sell) => (px - fill.avg_price) * fill.filled_qty,
+                    (None, _) => 0.0,
+                };
+                DelayedExecution {
+                    order,
+                    fill,
+                    latency_cost,
+                }
+            })
+            .collect()
+    }
+
+    pub fn pending(&self) -> &[PendingOrder] {
+        &self.pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LatencyInjector;
+    use crate::generators::MarketLagGenerator;
+    use crate::orderbook::{BookDelta, OrderBook, Side};
+
+    #[test]
+    fn orders_execute_against_the_book_after_the_lag() {
+        let mut book = OrderBook::default_with_liquidity();
+        let mut injector = LatencyInjector::new(MarketLagGenerator::new(1, 120, 0));
+
+        assert_eq!(injector.submit(1_000, Side::Buy, 1.0, &book), 1_120);
+        assert!(injector.release_due(1_119, &mut book).is_empty());
+
+        // The 100 ask is lifted by someone else before our order lands.
+        book.apply_delta(BookDelta {
+            side: Side::Sell,
+            price: 100.0,
+            qty: 0.0,
+        })
+        .unwrap();
+        let executions = injector.release_due(1_120, &mut book);
+
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].order.decision_px, Some(100.0));
+        assert_eq!(executions[0].fill.avg_price, 101.0);
+        assert_eq!(executions[0].latency_cost, 1.0);
+        assert!(injector.pending().is_empty());
+    }
+
+    #[test]
+    fn jittered_orders_release_in_arrival_order() {
+        let book = OrderBook::default_with_liquidity();
+        let mut injector = LatencyInjector::new(MarketLagGenerator::new(7, 100, 50));
+
+        let arrivals: Vec<u64> = (0..20)
+            .map(|i| injector.submit(i * 10, Side::Sell, 0.1, &book))
+            .collect();
+        let mut sorted = arrivals.clone();
+        sorted.sort_unstable();
+
+        let pending: Vec<u64> = injector
+            .pending()
+            .iter()
+            .map(|order| order.arrives_at_ms)
+            .collect();
+        assert_eq!(pending, sorted);
+    }
+}
@@ -1,14 +1,37 @@
+mod batch;
 mod config;
 mod fills;
 mod generators;
+mod latency;
 mod orderbook;
+mod report;
+mod rng;
+mod scenario;
+mod session;
 mod state;
+mod venues;
+mod yes_market;
 
-pub use config::SimConfig;
-pub use fills::{Fill, FillSummary};
-pub use generators::{MarketLagGenerator, PriceGenerator};
-pub use orderbook::OrderBook;
-pub use state::SimState;
+pub use batch::{run_batch, run_scenario, run_seeded, BatchReport, Distribution, SimOutcome};
+pub use config::{FeeSchedule, FillModel, PriceProcess, SimConfig};
+pub use fills::{Fill, FillSampler, FillSummary};
+pub use generators::{
+    GbmGenerator, JumpDiffusionGenerator, MarketLagGenerator, PriceGenerator, PricePath,
+};
+pub use latency::{DelayedExecution, LatencyInjector, PendingOrder};
+pub use orderbook::{
+    BookDelta, LimitOrderResult, OrderBook, OrderBookError, OrderEvent, OrderFill, OrderId,
+    PriceLevel, RestingOrder, Side,
+};
+pub use report::SimReport;
+#[cfg(feature = "chacha")]
+pub use rng::ChaChaRng;
+pub use rng::{Lcg, Rng};
+pub use scenario::{Scenario, ScenarioChange, ScenarioError, ScenarioTick, ScheduledChange};
+pub use session::{SessionCalendar, SessionPhase};
+pub use state::{MarketState, MultiMarketState, SimState};
+pub use venues::{MultiVenueGenerator, MultiVenueTick, VenueQuote, VenueSpec};
+pub use yes_market::{SimYesQuote, YesMarketSpec, YesQuoteSimulator};
 
 pub fn workspace_bootstrap() -> bool {
     true
@@ -31,7 +54,8 @@ mod tests {
         assert_eq!(config.daily_loss_cap_pct, 0.02);
         assert_eq!(config.market_lag_ms, 120);
         assert_eq!(config.decision_interval_ms, 50);
-        assert_eq!(config.fee_bps, 2.0);
+        assert_eq!(config.fees.taker_bps, 2.0);
+        assert_eq!(config.fees.maker_bps, 0.0);
     }
 
     #[test]
@@ -42,6 +66,8 @@ mod tests {
         assert_eq!(state.position, 0.0);
         assert_eq!(state.avg_price, 0.0);
         assert_eq!(state.realized_pnl, 0.0);
+        assert_eq!(state.fees_paid, 0.0);
+        assert_eq!(state.day_start_equity, 100_000.0);
         assert!(!state.halted);
     }
 }
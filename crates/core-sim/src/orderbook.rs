@@ -1,4 +1,5 @@
-use crate::fills::{Fill, FillSummary};
+use crate::config::{FeeSchedule, FillModel};
+use crate::fills::{Fill, FillSampler, FillSummary};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct PriceLevel {
@@ -6,9 +7,90 @@ pub struct PriceLevel {
     pub qty: f64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderBookError {
+    InvalidPrice,
+    InvalidQty,
+    UnknownOrder,
+}
+
+/// A limit order waiting in the book.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RestingOrder {
+    pub id: OrderId,
+    pub side: Side,
+    pub price: f64,
+    pub qty: f64,
+    /// Displayed size at the same price that trades before this order.
+    pub queue_ahead: f64,
+}
+
+/// Part of a resting order taken by incoming flow, at the order's price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderFill {
+    pub id: OrderId,
+    pub price: f64,
+    pub qty: f64,
+    /// Maker fee on this fill.
+    pub fee: f64,
+}
+
+/// What happened to resting orders, in the order it happened.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderEvent {
+    Filled(OrderFill),
+    Cancelled {
+        id: OrderId,
+        remaining_qty: f64,
+    },
+    /// `kept_priority` is false when the order went to the back of its
+    /// new price's queue.
+    Replaced {
+        id: OrderId,
+        price: f64,
+        qty: f64,
+        kept_priority: bool,
+    },
+}
+
+/// Absolute size at one displayed price; zero removes the level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookDelta {
+    pub side: Side,
+    pub price: f64,
+    pub qty: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitOrderResult {
+    pub id: OrderId,
+    /// What crossed the opposite side on arrival.
+    pub fill: FillSummary,
+    pub resting_qty: f64,
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct OrderBook {
+    /// Best (highest) price first.
+    bids: Vec<PriceLevel>,
+    /// Best (lowest) price first.
     asks: Vec<PriceLevel>,
+    /// Limit orders in price-time priority, best first.
+    resting_bids: Vec<RestingOrder>,
+    resting_asks: Vec<RestingOrder>,
+    next_order_id: u64,
+    events: Vec<OrderEvent>,
+    fees: FeeSchedule,
+    fill_sampler: FillSampler,
 }
 
 impl OrderBook {
@@ -16,91 +98,545 @@ impl OrderBook {
         Self::default()
     }
 
-    pub fn from_asks(mut asks: Vec<PriceLevel>) -> Self {
-        asks.retain(|level| {
-            level.price.is_finite() && level.price > 0.0 && level.qty.is_finite() && level.qty > 0.0
-        });
+    pub fn from_asks(asks: Vec<PriceLevel>) -> Self {
+        Self::from_levels(Vec::new(), asks)
+    }
+
+    pub fn from_levels(mut bids: Vec<PriceLevel>, mut asks: Vec<PriceLevel>) -> Self {
+        bids.retain(is_valid_level);
+        asks.retain(is_valid_level);
+        bids.sort_by(|left, right| right.price.total_cmp(&left.price));
         asks.sort_by(|left, right| left.price.total_cmp(&right.price));
 
-        Self { asks }
+        Self {
+            bids,
+            asks,
+            ..Self::default()
+        }
+    }
+
+    /// Charges `fees` on every fill: taker fees in the returned
+    /// [`FillSummary`], maker fees on resting order fills.
+    pub fn with_fees(mut self, fees: FeeSchedule) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    pub fn fees(&self) -> FeeSchedule {
+        self.fees
+    }
+
+    /// Thins displayed size at each price a marketable order reaches, as
+    /// drawn by `model` from `seed`. Resting limit orders are never pulled.
+    pub fn with_fill_model(mut self, model: FillModel, seed: u64) -> Self {
+        self.fill_sampler = FillSampler::new(model, seed);
+        self
+    }
+
+    pub fn fill_model(&self) -> FillModel {
+        self.fill_sampler.model()
     }
 
     pub fn default_with_liquidity() -> Self {
-        Self::from_asks(vec![
-            PriceLevel {
-                price: 100.0,
-                qty: 1.0,
-            },
-            PriceLevel {
-                price: 101.0,
-                qty: 2.0,
-            },
-            PriceLevel {
-                price: 102.0,
-                qty: 5.0,
-            },
-        ])
+        Self::from_levels(
+            vec![
+                PriceLevel {
+                    price: 99.0,
+                    qty: 1.0,
+                },
+                PriceLevel {
+                    price: 98.0,
+                    qty: 2.0,
+                },
+                PriceLevel {
+                    price: 97.0,
+                    qty: 5.0,
+                },
+            ],
+            vec![
+                PriceLevel {
+                    price: 100.0,
+                    qty: 1.0,
+                },
+                PriceLevel {
+                    price: 101.0,
+                    qty: 2.0,
+                },
+                PriceLevel {
+                    price: 102.0,
+                    qty: 5.0,
+                },
+            ],
+        )
+    }
+
+    /// Displayed liquidity; resting limit orders are listed separately.
+    pub fn bids(&self) -> &[PriceLevel] {
+        &self.bids
     }
 
     pub fn asks(&self) -> &[PriceLevel] {
         &self.asks
     }
 
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids.first()
+    }
+
     pub fn best_ask(&self) -> Option<&PriceLevel> {
         self.asks.first()
     }
 
+    pub fn resting_bids(&self) -> &[RestingOrder] {
+        &self.resting_bids
+    }
+
+    pub fn resting_asks(&self) -> &[RestingOrder] {
+        &self.resting_asks
+    }
+
     pub fn execute_market_buy(&mut self, qty: f64) -> FillSummary {
-        if !qty.is_finite() || qty <= 0.0 {
-            return FillSummary::default();
+        take(
+            &mut self.asks,
+            &mut self.resting_asks,
+            Side::Sell,
+            qty,
+            None,
+            self.fees,
+            &mut self.fill_sampler,
+            &mut self.events,
+        )
+    }
+
+    pub fn execute_market_sell(&mut self, qty: f64) -> FillSummary {
+        take(
+            &mut self.bids,
+            &mut self.resting_bids,
+            Side::Buy,
+            qty,
+            None,
+            self.fees,
+            &mut self.fill_sampler,
+            &mut self.events,
+        )
+    }
+
+    /// Crosses whatever the opposite side offers at `price` or better and
+    /// rests the remainder behind earlier orders at the same price.
+    pub fn place_limit(
+        &mut self,
+        side: Side,
+        price: f64,
+        qty: f64,
+    ) -> Result<LimitOrderResult, OrderBookError> {
+        validate_order(price, qty)?;
+
+        let id = OrderId(self.next_order_id);
+        self.next_order_id += 1;
+
+        Ok(self.place_with_id(id, side, price, qty))
+    }
+
+    fn place_with_id(&mut self, id: OrderId, side: Side, price: f64, qty: f64) -> LimitOrderResult {
+        let fill = match side {
+            Side::Buy => take(
+                &mut self.asks,
+                &mut self.resting_asks,
+                Side::Sell,
+                qty,
+                Some(price),
+                self.fees,
+                &mut self.fill_sampler,
+                &mut self.events,
+            ),
+            Side::Sell => take(
+                &mut self.bids,
+                &mut self.resting_bids,
+                Side::Buy,
+                qty,
+                Some(price),
+                self.fees,
+                &mut self.fill_sampler,
+                &mut self.events,
+            ),
+        };
+
+        let resting_qty = fill.remaining_qty;
+        if resting_qty > 0.0 {
+            let (levels, resting) = match side {
+                Side::Buy => (&self.bids, &mut self.resting_bids),
+                Side::Sell => (&self.asks, &mut self.resting_asks),
+            };
+            let queue_ahead = displayed_at(levels, price);
+            let position = resting.partition_point(|order| !is_better(side, price, order.price));
+            resting.insert(
+                position,
+                RestingOrder {
+                    id,
+                    side,
+                    price,
+                    qty: resting_qty,
+                    queue_ahead,
+                },
+            );
+        }
+
+        LimitOrderResult {
+            id,
+            fill,
+            resting_qty,
         }
+    }
 
-        let mut remaining = qty;
-        let mut filled_qty = 0.0;
-        let mut total_notional = 0.0;
-        let mut fills = Vec::new();
+    /// Removes a resting order, returning what was left of it.
+    pub fn cancel(&mut self, id: OrderId) -> Option<RestingOrder> {
+        let order = self.remove_resting(id)?;
+        self.events.push(OrderEvent::Cancelled {
+            id,
+            remaining_qty: order.qty,
+        });
+        Some(order)
+    }
 
-        for level in &mut self.asks {
-            if remaining <= 0.0 {
-                break;
-            }
-            if level.qty <= 0.0 {
-                continue;
-            }
+    /// Amends a resting order's price and quantity.
+    ///
+    /// Shrinking an order at the same price keeps its place in the queue.
+    /// Any other change re-enters it at the back of its new price's queue,
+    /// crossing the opposite side first when the new price reaches it.
+    pub fn replace(
+        &mut self,
+        id: OrderId,
+        new_price: f64,
+        new_qty: f64,
+    ) -> Result<LimitOrderResult, OrderBookError> {
+        validate_order(new_price, new_qty)?;
+
+        let resting = self
+            .resting_bids
+            .iter_mut()
+            .chain(self.resting_asks.iter_mut())
+            .find(|order| order.id == id)
+            .ok_or(OrderBookError::UnknownOrder)?;
+        let kept_priority = new_price == resting.price && new_qty <= resting.qty;
+        self.events.push(OrderEvent::Replaced {
+            id,
+            price: new_price,
+            qty: new_qty,
+            kept_priority,
+        });
 
-            let fill_qty = remaining.min(level.qty);
-            level.qty -= fill_qty;
-            remaining -= fill_qty;
-            filled_qty += fill_qty;
-            total_notional += fill_qty * level.price;
-            fills.push(Fill {
-                price: level.price,
-                qty: fill_qty,
+        if kept_priority {
+            resting.qty = new_qty;
+            return Ok(LimitOrderResult {
+                id,
+                fill: FillSummary::default(),
+                resting_qty: new_qty,
             });
         }
 
-        self.asks.retain(|level| level.qty > 0.0);
+        let side = resting.side;
+        self.remove_resting(id);
+        Ok(self.place_with_id(id, side, new_price, new_qty))
+    }
+
+    fn remove_resting(&mut self, id: OrderId) -> Option<RestingOrder> {
+        for resting in [&mut self.resting_bids, &mut self.resting_asks] {
+            if let Some(position) = resting.iter().position(|order| order.id == id) {
+                return Some(resting.remove(position));
+            }
+        }
+        None
+    }
 
-        let avg_price = if filled_qty > 0.0 {
-            total_notional / filled_qty
-        } else {
-            0.0
+    /// Adds displayed bid liquidity, first filling resting asks it crosses.
+    pub fn add_bid(&mut self, level: PriceLevel) {
+        if !is_valid_level(&level) {
+            return;
+        }
+        let crossed = take(
+            &mut Vec::new(),
+            &mut self.resting_asks,
+            Side::Sell,
+            level.qty,
+            Some(level.price),
+            self.fees,
+            &mut self.fill_sampler,
+            &mut self.events,
+        );
+        insert_level(
+            &mut self.bids,
+            Side::Buy,
+            level.price,
+            crossed.remaining_qty,
+        );
+    }
+
+    /// Adds displayed ask liquidity, first filling resting bids it crosses.
+    pub fn add_ask(&mut self, level: PriceLevel) {
+        if !is_valid_level(&level) {
+            return;
+        }
+        let crossed = take(
+            &mut Vec::new(),
+            &mut self.resting_bids,
+            Side::Buy,
+            level.qty,
+            Some(level.price),
+            self.fees,
+            &mut self.fill_sampler,
+            &mut self.events,
+        );
+        insert_level(
+            &mut self.asks,
+            Side::Sell,
+            level.price,
+            crossed.remaining_qty,
+        );
+    }
+
+    /// Replaces all displayed liquidity. Resting orders stay, and fill
+    /// against any new level that crosses them.
+    pub fn apply_snapshot(&mut self, bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) {
+        self.bids.clear();
+        self.asks.clear();
+        for level in bids {
+            self.add_bid(level);
+        }
+        for level in asks {
+            self.add_ask(level);
+        }
+        self.clamp_queues();
+    }
+
+    /// Sets the displayed size at one price, as streamed after a snapshot.
+    pub fn apply_delta(&mut self, delta: BookDelta) -> Result<(), OrderBookError> {
+        if !delta.price.is_finite() || delta.price <= 0.0 {
+            return Err(OrderBookError::InvalidPrice);
+        }
+        if !delta.qty.is_finite() || delta.qty < 0.0 {
+            return Err(OrderBookError::InvalidQty);
+        }
+
+        let levels = match delta.side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        levels.retain(|level| level.price != delta.price);
+        let level = PriceLevel {
+            price: delta.price,
+            qty: delta.qty,
         };
+        match delta.side {
+            Side::Buy => self.add_bid(level),
+            Side::Sell => self.add_ask(level),
+        }
+        self.clamp_queues();
+        Ok(())
+    }
 
-        FillSummary {
-            fills,
-            filled_qty,
-            avg_price,
-            remaining_qty: remaining,
+    /// Displayed size that shrinks is taken to have been cancelled from the
+    /// back of the queue, so it only moves resting orders up when less is
+    /// left than was ahead of them. Size that grows joins behind them.
+    fn clamp_queues(&mut self) {
+        for (levels, resting) in [
+            (&self.bids, &mut self.resting_bids),
+            (&self.asks, &mut self.resting_asks),
+        ] {
+            for order in resting.iter_mut() {
+                order.queue_ahead = order.queue_ahead.min(displayed_at(levels, order.price));
+            }
         }
     }
+
+    /// Fills, cancels and replaces since the last call, oldest first.
+    pub fn drain_order_events(&mut self) -> Vec<OrderEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+fn validate_order(price: f64, qty: f64) -> Result<(), OrderBookError> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err(OrderBookError::InvalidPrice);
+    }
+    if !qty.is_finite() || qty <= 0.0 {
+        return Err(OrderBookError::InvalidQty);
+    }
+    Ok(())
+}
+
+fn is_valid_level(level: &PriceLevel) -> bool {
+    level.price.is_finite() && level.price > 0.0 && level.qty.is_finite() && level.qty > 0.0
+}
+
+/// Whether `price` has priority over `other` on the `side` of the book.
+fn is_better(side: Side, price: f64, other: f64) -> bool {
+    match side {
+        Side::Buy => price > other,
+        Side::Sell => price < other,
+    }
+}
+
+fn insert_level(levels: &mut Vec<PriceLevel>, side: Side, price: f64, qty: f64) {
+    if qty <= 0.0 {
+        return;
+    }
+    let position = levels.partition_point(|level| is_better(side, level.price, price));
+    match levels.get_mut(position) {
+        Some(level) if level.price == price => level.qty += qty,
+        _ => levels.insert(position, PriceLevel { price, qty }),
+    }
+}
+
+fn displayed_at(levels: &[PriceLevel], price: f64) -> f64 {
+    levels
+        .iter()
+        .filter(|level| level.price == price)
+        .map(|level| level.qty)
+        .sum()
+}
+
+/// Takes up to `amount` from displayed levels that share one price.
+fn take_displayed(levels: &mut [PriceLevel], amount: f64) -> f64 {
+    let mut taken = 0.0;
+    for level in levels {
+        let qty = (amount - taken).min(level.qty);
+        level.qty -= qty;
+        taken += qty;
+    }
+    taken
+}
+
+/// Takes up to `qty` from one side of the book, best price first. At each
+/// price a resting order fills once the displayed size queued ahead of it
+/// has traded. `limit` stops the walk at prices worse than it. `sampler`
+/// decides how much displayed size at each price is pulled before the
+/// order gets there.
+#[allow(clippy::too_many_arguments)]
+fn take(
+    levels: &mut Vec<PriceLevel>,
+    resting: &mut Vec<RestingOrder>,
+    side: Side,
+    qty: f64,
+    limit: Option<f64>,
+    fees: FeeSchedule,
+    sampler: &mut FillSampler,
+    events: &mut Vec<OrderEvent>,
+) -> FillSummary {
+    if !qty.is_finite() || qty <= 0.0 {
+        return FillSummary::default();
+    }
+
+    let mut remaining = qty;
+    let mut filled_qty = 0.0;
+    let mut total_notional = 0.0;
+    let mut total_fees = 0.0;
+    let mut fills = Vec::new();
+    let (mut level_idx, mut order_idx) = (0, 0);
+
+    while remaining > 0.0 {
+        let price = match (levels.get(level_idx), resting.get(order_idx)) {
+            (Some(level), Some(order)) if is_better(side, order.price, level.price) => order.price,
+            (Some(level), _) => level.price,
+            (None, Some(order)) => order.price,
+            (None, None) => break,
+        };
+        if limit.is_some_and(|limit| is_better(side, limit, price)) {
+            break;
+        }
+
+        let level_end = level_idx
+            + levels[level_idx..]
+                .iter()
+                .take_while(|level| level.price == price)
+                .count();
+        let order_end = order_idx
+            + resting[order_idx..]
+                .iter()
+                .take_while(|order| order.price == price)
+                .count();
+        let displayed = &mut levels[level_idx..level_end];
+        let capture = sampler.capture_fraction();
+        for level in displayed.iter_mut() {
+            level.qty *= capture;
+        }
+        let displayed_total: f64 = displayed.iter().map(|level| level.qty).sum();
+        let mut displayed_taken = 0.0;
+        let mut fill_qty = 0.0;
+
+        for order in &mut resting[order_idx..order_end] {
+            let ahead = (order.queue_ahead - displayed_taken)
+                .max(0.0)
+                .min(displayed_total - displayed_taken);
+            let taken = take_displayed(displayed, ahead.min(remaining));
+            displayed_taken += taken;
+            remaining -= taken;
+            fill_qty += taken;
+            if remaining <= 0.0 {
+                break;
+            }
+
+            let taken = remaining.min(order.qty);
+            order.qty -= taken;
+            remaining -= taken;
+            fill_qty += taken;
+            events.push(OrderEvent::Filled(OrderFill {
+                id: order.id,
+                price,
+                qty: taken,
+                fee: fees.maker_fee(taken * price),
+            }));
+        }
+        if remaining > 0.0 {
+            let taken = take_displayed(displayed, remaining);
+            displayed_taken += taken;
+            remaining -= taken;
+            fill_qty += taken;
+        }
+        for order in &mut resting[order_idx..order_end] {
+            order.queue_ahead = (order.queue_ahead - displayed_taken)
+                .min(displayed_total - displayed_taken)
+                .max(0.0);
+        }
+
+        level_idx = level_end;
+        order_idx = order_end;
+        filled_qty += fill_qty;
+        total_notional += fill_qty * price;
+        let fee = fees.taker_fee(fill_qty * price);
+        total_fees += fee;
+        fills.push(Fill {
+            price,
+            qty: fill_qty,
+            fee,
+        });
+    }
+
+    levels.retain(|level| level.qty > 0.0);
+    resting.retain(|order| order.qty > 0.0);
+
+    let avg_price = if filled_qty > 0.0 {
+        total_notional / filled_qty
+    } else {
+        0.0
+    };
+
+    FillSummary {
+        fills,
+        filled_qty,
+        avg_price,
+        remaining_qty: remaining,
+        fees: total_fees,
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::config::{FeeSchedule, FillModel};
     use crate::fills::Fill;
 
-    use super::{OrderBook, PriceLevel};
+    use super::{
+        BookDelta, OrderBook, OrderBookError, OrderEvent, OrderFill, OrderId, PriceLevel, Side,
+    };
 
     #[test]
     fn crossing_order_fills_at_best_level() {
@@ -113,14 +649,17 @@ mod tests {
                 Fill {
                     price: 100.0,
                     qty: 1.0,
+                    fee: 0.0,
                 },
                 Fill {
                     price: 101.0,
                     qty: 2.0,
+                    fee: 0.0,
                 },
                 Fill {
                     price: 102.0,
                     qty: 1.0,
+                    fee: 0.0,
                 },
             ]
         );
@@ -129,6 +668,312 @@ mod tests {
         assert_eq!(fill.avg_price, 101.0);
     }
 
+    #[test]
+    fn market_sell_walks_bids_from_the_highest_price() {
+        let mut book = OrderBook::default_with_liquidity();
+
+        assert_eq!(book.best_bid().map(|level| level.price), Some(99.0));
+        let fill = book.execute_market_sell(2.0);
+
+        assert_eq!(
+            fill.fills,
+            vec![
+                Fill {
+                    price: 99.0,
+                    qty: 1.0,
+                    fee: 0.0,
+                },
+                Fill {
+                    price: 98.0,
+                    qty: 1.0,
+                    fee: 0.0,
+                },
+            ]
+        );
+        assert_eq!(fill.avg_price, 98.5);
+        assert_eq!(book.best_bid().map(|level| level.qty), Some(1.0));
+        assert_eq!(book.asks(), OrderBook::default_with_liquidity().asks());
+
+        let exhausted = book.execute_market_sell(10.0);
+        assert_eq!(exhausted.filled_qty, 6.0);
+        assert_eq!(exhausted.remaining_qty, 4.0);
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.execute_market_sell(f64::NAN).filled_qty, 0.0);
+    }
+
+    #[test]
+    fn limit_orders_cross_then_rest_in_price_time_priority() {
+        let mut book = OrderBook::default_with_liquidity();
+
+        // Takes the 100 ask and rests the rest at 100.5.
+        let aggressive = book.place_limit(Side::Buy, 100.5, 3.0).unwrap();
+        assert_eq!(aggressive.fill.filled_qty, 1.0);
+        assert_eq!(aggressive.resting_qty, 2.0);
+        let first = book.place_limit(Side::Buy, 99.5, 1.0).unwrap();
+        let second = book.place_limit(Side::Buy, 99.5, 1.0).unwrap();
+        let ids: Vec<OrderId> = book.resting_bids().iter().map(|order| order.id).collect();
+        assert_eq!(ids, vec![aggressive.id, first.id, second.id]);
+
+        // A market sell takes the better-priced resting orders before the 99 bid.
+        let sold = book.execute_market_sell(3.5);
+        assert_eq!(sold.fills.last().map(|fill| fill.price), Some(99.5));
+        assert_eq!(
+            book.drain_order_events(),
+            vec![
+                OrderEvent::Filled(OrderFill {
+                    id: aggressive.id,
+                    price: 100.5,
+                    qty: 2.0,
+                    fee: 0.0,
+                }),
+                OrderEvent::Filled(OrderFill {
+                    id: first.id,
+                    price: 99.5,
+                    qty: 1.0,
+                    fee: 0.0,
+                }),
+                OrderEvent::Filled(OrderFill {
+                    id: second.id,
+                    price: 99.5,
+                    qty: 0.5,
+                    fee: 0.0,
+                }),
+            ]
+        );
+        assert_eq!(book.best_bid().map(|level| level.qty), Some(1.0));
+
+        // New ask liquidity at 99.4 crosses the rest of the resting bid.
+        book.add_ask(PriceLevel {
+            price: 99.4,
+            qty: 2.0,
+        });
+        assert_eq!(
+            book.drain_order_events(),
+            vec![OrderEvent::Filled(OrderFill {
+                id: second.id,
+                price: 99.5,
+                qty: 0.5,
+                fee: 0.0,
+            })]
+        );
+        assert!(book.resting_bids().is_empty());
+        assert_eq!(book.best_ask().map(|level| level.qty), Some(1.5));
+    }
+
+    #[test]
+    fn resting_orders_can_be_cancelled_by_id() {
+        let mut book = OrderBook::default_with_liquidity();
+        let order = book.place_limit(Side::Sell, 100.5, 2.0).unwrap();
+
+        assert_eq!(order.fill.filled_qty, 0.0);
+        assert_eq!(book.resting_asks().len(), 1);
+        assert_eq!(book.cancel(order.id).map(|order| order.qty), Some(2.0));
+        assert_eq!(book.cancel(order.id), None);
+        assert!(book.resting_asks().is_empty());
+        assert_eq!(
+            book.drain_order_events(),
+            vec![OrderEvent::Cancelled {
+                id: order.id,
+                remaining_qty: 2.0,
+            }]
+        );
+        assert_eq!(
+            book.place_limit(Side::Buy, f64::NAN, 1.0),
+            Err(OrderBookError::InvalidPrice)
+        );
+        assert_eq!(
+            book.place_limit(Side::Buy, 99.0, 0.0),
+            Err(OrderBookError::InvalidQty)
+        );
+    }
+
+    #[test]
+    fn replace_keeps_priority_only_when_shrinking_at_the_same_price() {
+        let mut book = OrderBook::default_with_liquidity();
+        let first = book.place_limit(Side::Buy, 99.5, 2.0).unwrap();
+        let second = book.place_limit(Side::Buy, 99.5, 2.0).unwrap();
+
+        book.replace(first.id, 99.5, 1.0).unwrap();
+        assert_eq!(book.resting_bids()[0].id, first.id);
+        assert_eq!(book.resting_bids()[0].qty, 1.0);
+
+        book.replace(first.id, 99.5, 3.0).unwrap();
+        let ids: Vec<OrderId> = book.resting_bids().iter().map(|order| order.id).collect();
+        assert_eq!(ids, vec![second.id, first.id]);
+
+        // Repricing through the 100 ask fills it and rests the remainder.
+        let crossed = book.replace(second.id, 100.0, 2.0).unwrap();
+        assert_eq!(crossed.fill.filled_qty, 1.0);
+        assert_eq!(crossed.resting_qty, 1.0);
+        assert_eq!(book.resting_bids()[0].id, second.id);
+        assert_eq!(
+            book.drain_order_events(),
+            vec![
+                OrderEvent::Replaced {
+                    id: first.id,
+                    price: 99.5,
+                    qty: 1.0,
+                    kept_priority: true,
+                },
+                OrderEvent::Replaced {
+                    id: first.id,
+                    price: 99.5,
+                    qty: 3.0,
+                    kept_priority: false,
+                },
+                OrderEvent::Replaced {
+                    id: second.id,
+                    price: 100.0,
+                    qty: 2.0,
+                    kept_priority: false,
+                },
+            ]
+        );
+        assert_eq!(
+            book.replace(OrderId(99), 99.0, 1.0),
+            Err(OrderBookError::UnknownOrder)
+        );
+    }
+
+    #[test]
+    fn snapshot_and_deltas_drive_the_displayed_book() {
+        let mut book = OrderBook::default_with_liquidity();
+        let resting = book.place_limit(Side::Buy, 99.5, 1.0).unwrap();
+
+        book.apply_snapshot(
+            vec![PriceLevel {
+                price: 99.2,
+                qty: 4.0,
+            }],
+            vec![PriceLevel {
+                price: 99.8,
+                qty: 3.0,
+            }],
+        );
+        assert_eq!(book.bids().len(), 1);
+        assert_eq!(book.best_ask().map(|level| level.price), Some(99.8));
+        assert_eq!(book.resting_bids()[0].id, resting.id);
+
+        book.apply_delta(BookDelta {
+            side: Side::Buy,
+            price: 99.2,
+            qty: 1.5,
+        })
+        .unwrap();
+        book.apply_delta(BookDelta {
+            side: Side::Buy,
+            price: 99.3,
+            qty: 2.0,
+        })
+        .unwrap();
+        book.apply_delta(BookDelta {
+            side: Side::Sell,
+            price: 99.8,
+            qty: 0.0,
+        })
+        .unwrap();
+        assert_eq!(
+            book.bids(),
+            &[
+                PriceLevel {
+                    price: 99.3,
+                    qty: 2.0,
+                },
+                PriceLevel {
+                    price: 99.2,
+                    qty: 1.5,
+                },
+            ]
+        );
+        assert_eq!(book.best_ask(), None);
+
+        // An ask priced through the resting bid fills it first.
+        book.apply_delta(BookDelta {
+            side: Side::Sell,
+            price: 99.4,
+            qty: 3.0,
+        })
+        .unwrap();
+        assert!(book.resting_bids().is_empty());
+        assert_eq!(book.best_ask().map(|level| level.qty), Some(2.0));
+        assert_eq!(book.drain_order_events().len(), 1);
+        assert_eq!(
+            book.apply_delta(BookDelta {
+                side: Side::Sell,
+                price: 99.4,
+                qty: -1.0,
+            }),
+            Err(OrderBookError::InvalidQty)
+        );
+    }
+
+    #[test]
+    fn passive_orders_fill_only_after_the_size_queued_ahead_trades() {
+        let mut book = OrderBook::default_with_liquidity();
+        let order = book.place_limit(Side::Buy, 98.0, 1.0).unwrap();
+        assert_eq!(book.resting_bids()[0].queue_ahead, 2.0);
+
+        // Size added after us queues behind; half the size ahead cancels.
+        book.apply_delta(BookDelta {
+            side: Side::Buy,
+            price: 98.0,
+            qty: 1.0,
+        })
+        .unwrap();
+        book.add_bid(PriceLevel {
+            price: 98.0,
+            qty: 4.0,
+        });
+        assert_eq!(book.resting_bids()[0].queue_ahead, 1.0);
+
+        // The 99 bid and 0.5 of the size ahead trade: no passive fill yet.
+        book.execute_market_sell(1.5);
+        assert!(book.drain_order_events().is_empty());
+        assert_eq!(book.resting_bids()[0].queue_ahead, 0.5);
+
+        let sold = book.execute_market_sell(1.0);
+        assert_eq!(sold.filled_qty, 1.0);
+        assert_eq!(
+            book.drain_order_events(),
+            vec![OrderEvent::Filled(OrderFill {
+                id: order.id,
+                price: 98.0,
+                qty: 0.5,
+                fee: 0.0,
+            })]
+        );
+        assert_eq!(book.resting_bids()[0].queue_ahead, 0.0);
+        assert_eq!(book.best_bid().map(|level| level.qty), Some(4.0));
+    }
+
+    #[test]
+    fn fills_pay_taker_and_maker_fees_with_a_floor() {
+        let fees = FeeSchedule {
+            maker_bps: 0.0,
+            taker_bps: 10.0,
+            min_fee: 0.05,
+        };
+        let mut book = OrderBook::default_with_liquidity().with_fees(fees);
+        let resting = book.place_limit(Side::Sell, 100.5, 1.0).unwrap();
+
+        // The taker pays 10bps at 100 and at 100.5; the free maker side
+        // still pays the floor.
+        let fill = book.execute_market_buy(2.0);
+        assert_eq!(fill.fills.len(), 2);
+        assert!((fill.fills[0].fee - 0.1).abs() < 1e-12);
+        assert!((fill.fees - 0.2005).abs() < 1e-12);
+        assert_eq!(
+            book.drain_order_events(),
+            vec![OrderEvent::Filled(OrderFill {
+                id: resting.id,
+                price: 100.5,
+                qty: 1.0,
+                fee: 0.05,
+            })]
+        );
+        assert_eq!(fees.taker_fee(1.0), 0.05);
+    }
+
     #[test]
     fn invalid_market_buy_qty_is_no_op() {
         let mut book = OrderBook::default_with_liquidity();
@@ -238,4 +1083,29 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn partial_fill_model_pulls_displayed_size_before_the_order_arrives() {
+        let half = FillModel::Partial {
+            min_fraction: 0.5,
+            max_fraction: 0.5,
+        };
+        let mut book = OrderBook::default_with_liquidity().with_fill_model(half, 3);
+        let fill = book.execute_market_buy(3.0);
+
+        assert_eq!(
+            fill.fills.iter().map(|fill| fill.qty).collect::<Vec<_>>(),
+            vec![0.5, 1.0, 1.5]
+        );
+        assert_eq!(fill.remaining_qty, 0.0);
+        assert_eq!(
+            book.asks(),
+            &[PriceLevel {
+                price: 102.0,
+                qty: 1.0,
+            }]
+        );
+        // Deeper bids are untouched until an order reaches them.
+        assert_eq!(book.bids(), OrderBook::default_with_liquidity().bids());
+    }
 }
@@ -0,0 +1,165 @@
+use serde::Serialize;
+
+/// Performance statistics computed once a run ends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct SimReport {
+    pub total_return_pct: f64,
+    /// Mean over standard deviation of per-tick equity returns, not
+    /// annualized; zero while equity has not moved.
+    pub sharpe: f64,
+    /// Largest fall in equity from its running peak, in percent.
+    pub max_drawdown_pct: f64,
+    /// Share of position-reducing trades that made money after fees.
+    pub hit_rate: f64,
+    /// Net PnL per position-reducing trade.
+    pub avg_trade_pnl: f64,
+    pub trades: u64,
+    /// Trades opened because the lagged market diverged past the threshold.
+    pub lag_triggers: u64,
+    /// Times the daily loss cap halted trading.
+    pub halt_events: u64,
+}
+
+/// Accumulates a [`SimReport`] tick by tick.
+#[derive(Debug, Clone)]
+pub(crate) struct ReportRecorder {
+    start_equity: f64,
+    last_equity: f64,
+    peak_equity: f64,
+    max_drawdown_pct: f64,
+    /// Welford's running mean and sum of squared deviations of returns.
+    return_count: u64,
+    return_mean: f64,
+    return_m2: f64,
+    trades: u64,
+    closed_trades: u64,
+    winning_trades: u64,
+    closed_pnl: f64,
+    lag_triggers: u64,
+    halt_events: u64,
+}
+
+impl ReportRecorder {
+    pub(crate) fn new(start_equity: f64) -> Self {
+        Self {
+            start_equity,
+            last_equity: start_equity,
+            peak_equity: start_equity,
+            max_drawdown_pct: 0.0,
+            return_count: 0,
+            return_mean: 0.0,
+            return_m2: 0.0,
+            trades: 0,
+            closed_trades: 0,
+            winning_trades: 0,
+            closed_pnl: 0.0,
+            lag_triggers: 0,
+            halt_events: 0,
+        }
+    }
+
+    pub(crate) fn max_drawdown_pct(&self) -> f64 {
+        self.max_drawdown_pct
+    }
+
+    pub(crate) fn mark(&mut self, equity: f64) {
+        if self.last_equity > 0.0 {
+            let value = equity / self.last_equity - 1.0;
+            self.return_count += 1;
+            let delta = value - self.return_mean;
+            self.return_mean += delta / self.return_count as f64;
+            self.return_m2 += delta * (value - self.return_mean);
+        }
+        self.last_equity = equity;
+        self.peak_equity = self.peak_equity.max(equity);
+        if self.peak_equity > 0.0 {
+            self.max_drawdown_pct = self
+                .max_drawdown_pct
+                .max((self.peak_equity - equity) / self.peak_equity * 100.0);
+        }
+    }
+
+    /// `closed_pnl` is the trade's realized PnL net of its fee when it
+    /// reduced the position, `None` when it only added to it.
+    pub(crate) fn trade(&mut self, closed_pnl: Option<f64>) {
+        self.trades += 1;
+        if let Some(pnl) = closed_pnl {
+            self.closed_trades += 1;
+            self.closed_pnl += pnl;
+            if pnl > 0.0 {
+                self.winning_trades += 1;
+            }
+        }
+    }
+
+    pub(crate) fn lag_trigger(&mut self) {
+        self.lag_triggers += 1;
+    }
+
+    pub(crate) fn halt(&mut self) {
+        self.halt_events += 1;
+    }
+
+    pub(crate) fn finish(&self, terminal_equity: f64) -> SimReport {
+        let return_std = if self.return_count > 1 {
+            (self.return_m2 / (self.return_count - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        let per_closed = |value: f64| {
+            if self.closed_trades == 0 {
+                0.0
+            } else {
+                value / self.closed_trades as f64
+            }
+        };
+
+        SimReport {
+            total_return_pct: if self.start_equity > 0.0 {
+                (terminal_equity / self.start_equity - 1.0) * 100.0
+            } else {
+                0.0
+            },
+            sharpe: if return_std > 0.0 {
+                self.return_mean / return_std
+            } else {
+                0.0
+            },
+            max_drawdown_pct: self.max_drawdown_pct,
+            hit_rate: per_closed(self.winning_trades as f64),
+            avg_trade_pnl: per_closed(self.closed_pnl),
+            trades: self.trades,
+            lag_triggers: self.lag_triggers,
+            halt_events: self.halt_events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReportRecorder;
+
+    #[test]
+    fn report_summarises_equity_and_closed_trades() {
+        let mut recorder = ReportRecorder::new(100.0);
+        for equity in [110.0, 99.0, 108.9] {
+            recorder.mark(equity);
+        }
+        recorder.lag_trigger();
+        recorder.trade(None);
+        recorder.trade(Some(4.0));
+        recorder.trade(Some(-1.0));
+        recorder.halt();
+
+        let report = recorder.finish(108.9);
+        assert!((report.total_return_pct - 8.9).abs() < 1e-9);
+        assert!((report.max_drawdown_pct - 10.0).abs() < 1e-9);
+        // Returns of +10%, -10% and +10%.
+        assert!((report.sharpe - (0.1 / 3.0) / (0.04_f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert_eq!(report.hit_rate, 0.5);
+        assert_eq!(report.avg_trade_pnl, 1.5);
+        assert_eq!(report.trades, 3);
+        assert_eq!(report.lag_triggers, 1);
+        assert_eq!(report.halt_events, 1);
+    }
+}
@@ -0,0 +1,262 @@
+use serde::Deserialize;
+
+use crate::generators::{MarketLagGenerator, PriceGenerator};
+use crate::rng::{Lcg, Rng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioError {
+    InvalidToml,
+    InvalidStartPrice,
+    InvalidMaxStep,
+    InvalidSpread,
+    InvalidDropPct,
+    InvalidMultiplier,
+    EventPastEnd,
+}
+
+/// A deterministic stress path: baseline generator settings plus changes
+/// scheduled at specific ticks.
+///
+/// ```toml
+/// name = "flash-crash"
+/// ticks = 200
+/// start_price = 0.5
+/// max_step = 0.002
+/// base_lag_ms = 120
+/// jitter_ms = 20
+/// spread = 0.01
+///
+/// [[events]]
+/// at_tick = 50
+/// kind = "flash_crash"
+/// drop_pct = 20.0
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    pub ticks: u64,
+    pub start_price: f64,
+    pub max_step: f64,
+    pub base_lag_ms: u64,
+    #[serde(default)]
+    pub jitter_ms: u64,
+    pub spread: f64,
+    #[serde(default)]
+    pub events: Vec<ScheduledChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ScheduledChange {
+    pub at_tick: u64,
+    #[serde(flatten)]
+    pub change: ScenarioChange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScenarioChange {
+    /// Drops the price by `drop_pct` percent in a single tick.
+    FlashCrash { drop_pct: f64 },
+    /// Widens the quoted spread by `multiplier`.
+    SpreadBlowout {
+        multiplier: f64,
+        duration_ticks: u64,
+    },
+    /// Withholds prices while the generators keep running.
+    FeedGap { duration_ticks: u64 },
+    /// Scales the price step by `multiplier`.
+    VolatilitySpike {
+        multiplier: f64,
+        duration_ticks: u64,
+    },
+}
+
+impl ScenarioChange {
+    fn is_active(&self, at_tick: u64, tick: u64) -> bool {
+        let duration = match *self {
+            Self::FlashCrash { .. } => 1,
+            Self::SpreadBlowout { duration_ticks, .. }
+            | Self::FeedGap { duration_ticks }
+            | Self::VolatilitySpike { duration_ticks, .. } => duration_ticks,
+        };
+        tick >= at_tick && tick - at_tick < duration
+    }
+}
+
+/// One generated tick; `price` is `None` inside a feed gap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScenarioTick {
+    pub tick: u64,
+    pub price: Option<f64>,
+    pub spread: f64,
+    pub lag_ms: u64,
+}
+
+impl Scenario {
+    pub fn from_toml(source: &str) -> Result<Self, ScenarioError> {
+        let scenario: Self = toml::from_str(source).map_err(|_| ScenarioError::InvalidToml)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        if !self.start_price.is_finite() || self.start_price <= 0.0 {
+            return Err(ScenarioError::InvalidStartPrice);
+        }
+        if !self.max_step.is_finite() || self.max_step < 0.0 {
+            return Err(ScenarioError::InvalidMaxStep);
+        }
+        if !self.spread.is_finite() || self.spread < 0.0 {
+            return Err(ScenarioError::InvalidSpread);
+        }
+        for event in &self.events {
+            if event.at_tick >= self.ticks {
+                return Err(ScenarioError::EventPastEnd);
+            }
+            match event.change {
+                ScenarioChange::FlashCrash { drop_pct }
+                    if !drop_pct.is_finite() || !(0.0..=100.0).contains(&drop_pct) =>
+                {
+                    return Err(ScenarioError::InvalidDropPct);
+                }
+                ScenarioChange::SpreadBlowout { multiplier, .. }
+                | ScenarioChange::VolatilitySpike { multiplier, .. }
+                    if !multiplier.is_finite() || multiplier < 0.0 =>
+                {
+                    return Err(ScenarioError::InvalidMultiplier);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates every tick of the scenario. The same seed always yields
+    /// the same path.
+    pub fn run(&self, seed: u64) -> Vec<ScenarioTick> {
+        self.run_with_rng(&mut Lcg::new(seed))
+    }
+
+    /// Prices and lags draw from their own forks of `rng`.
+    pub fn run_with_rng<R: Rng>(&self, rng: &mut R) -> Vec<ScenarioTick> {
+        let mut prices = PriceGenerator::from_rng(rng.fork(), self.start_price, self.max_step);
+        let mut lags = MarketLagGenerator::from_rng(rng.fork(), self.base_lag_ms, self.jitter_ms);
+
+        (0..self.ticks)
+            .map(|tick| {
+                let mut step_multiplier = 1.0;
+                let mut spread_multiplier = 1.0;
+                let mut gap = false;
+                for event in &self.events {
+                    if !event.change.is_active(event.at_tick, tick) {
+                        continue;
+                    }
+                    match event.change {
+                        ScenarioChange::FlashCrash { drop_pct } => {
+                            prices.shock(1.0 - drop_pct / 100.0);
+                        }
+                        ScenarioChange::SpreadBlowout { multiplier, .. } => {
+                            spread_multiplier *= multiplier;
+                        }
+                        ScenarioChange::FeedGap { .. } => gap = true,
+                        ScenarioChange::VolatilitySpike { multiplier, .. } => {
+                            step_multiplier *= multiplier;
+                        }
+                    }
+                }
+
+                prices.set_max_step(self.max_step * step_multiplier);
+                let price = prices.next_price();
+                ScenarioTick {
+                    tick,
+                    price: (!gap).then_some(price),
+                    spread: self.spread * spread_multiplier,
+                    lag_ms: lags.next_lag_ms(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scenario, ScenarioChange, ScenarioError};
+
+    const STRESS: &str = r#"
+name = "stress"
+ticks = 40
+start_price = 0.5
+max_step = 0.001
+base_lag_ms = 120
+spread = 0.01
+
+[[events]]
+at_tick = 10
+kind = "flash_crash"
+drop_pct = 20.0
+
+[[events]]
+at_tick = 15
+kind = "spread_blowout"
+multiplier = 5.0
+duration_ticks = 3
+
+[[events]]
+at_tick = 20
+kind = "feed_gap"
+duration_ticks = 4
+
+[[events]]
+at_tick = 30
+kind = "volatility_spike"
+multiplier = 10.0
+duration_ticks = 5
+"#;
+
+    #[test]
+    fn scheduled_changes_apply_at_their_ticks() {
+        let scenario = Scenario::from_toml(STRESS).unwrap();
+        assert_eq!(
+            scenario.events[0].change,
+            ScenarioChange::FlashCrash { drop_pct: 20.0 }
+        );
+
+        let ticks = scenario.run(7);
+        assert_eq!(ticks.len(), 40);
+        assert_eq!(ticks, scenario.run(7));
+
+        let before = ticks[9].price.unwrap();
+        let after = ticks[10].price.unwrap();
+        assert!(after < before * 0.81, "{before} -> {after}");
+        assert_eq!(ticks[15].spread, 0.05);
+        assert_eq!(ticks[18].spread, 0.01);
+        assert!(ticks[20..24].iter().all(|tick| tick.price.is_none()));
+        assert!(ticks[24].price.is_some());
+        assert!(ticks.iter().all(|tick| tick.lag_ms == 120));
+
+        let calm = (ticks[25].price.unwrap() - ticks[26].price.unwrap()).abs();
+        assert!(calm <= 0.001 + 1e-12);
+        let spike = ticks[30..35]
+            .windows(2)
+            .map(|pair| (pair[1].price.unwrap() - pair[0].price.unwrap()).abs())
+            .fold(0.0, f64::max);
+        assert!(spike > 0.001, "spike step was {spike}");
+    }
+
+    #[test]
+    fn rejects_malformed_scenarios() {
+        assert_eq!(
+            Scenario::from_toml("name = 1").unwrap_err(),
+            ScenarioError::InvalidToml
+        );
+        assert_eq!(
+            Scenario::from_toml(&STRESS.replace("drop_pct = 20.0", "drop_pct = 120.0"))
+                .unwrap_err(),
+            ScenarioError::InvalidDropPct
+        );
+        assert_eq!(
+            Scenario::from_toml(&STRESS.replace("at_tick = 30", "at_tick = 40")).unwrap_err(),
+            ScenarioError::EventPastEnd
+        );
+    }
+}
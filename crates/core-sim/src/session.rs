@@ -0,0 +1,181 @@
+const DAY_MS: u64 = 86_400_000;
+
+/// Where a timestamp falls in the trading day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionPhase {
+    Closed,
+    /// The market live now expires at `expiry_ms`.
+    Open {
+        expiry_ms: u64,
+    },
+    /// Within `no_entry_ms` of expiry: positions may only be reduced.
+    NoNewEntries {
+        expiry_ms: u64,
+    },
+}
+
+/// Daily open and close, in ms since UTC midnight, and the fixed-length
+/// markets that roll over between them. A market starts at the open and
+/// every `interval_ms` after; one that would run past the close is not
+/// listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionCalendar {
+    open_ms: u64,
+    close_ms: u64,
+    interval_ms: u64,
+    no_entry_ms: u64,
+}
+
+impl SessionCalendar {
+    pub fn new(open_ms: u64, close_ms: u64, interval_ms: u64, no_entry_ms: u64) -> Self {
+        assert!(
+            open_ms < close_ms && close_ms <= DAY_MS,
+            "session must open before it closes, within one day"
+        );
+        assert!(
+            interval_ms > 0 && interval_ms <= close_ms - open_ms,
+            "interval_ms must fit inside the session"
+        );
+        assert!(
+            no_entry_ms < interval_ms,
+            "no_entry_ms must be shorter than interval_ms"
+        );
+
+        Self {
+            open_ms,
+            close_ms,
+            interval_ms,
+            no_entry_ms,
+        }
+    }
+
+    pub fn interval_ms(&self) -> u64 {
+        self.interval_ms
+    }
+
+    pub fn phase(&self, ts_ms: u64) -> SessionPhase {
+        match self.expiry_at(ts_ms) {
+            None => SessionPhase::Closed,
+            Some(expiry_ms) if expiry_ms - ts_ms <= self.no_entry_ms => {
+                SessionPhase::NoNewEntries { expiry_ms }
+            }
+            Some(expiry_ms) => SessionPhase::Open { expiry_ms },
+        }
+    }
+
+    /// Expiry of the market live at `ts_ms`, if the session is open. A
+    /// market is live from its start up to, not including, its expiry.
+    pub fn expiry_at(&self, ts_ms: u64) -> Option<u64> {
+        let day_start = ts_ms - ts_ms % DAY_MS;
+        let time_of_day = ts_ms % DAY_MS;
+        if time_of_day < self.open_ms || time_of_day >= self.last_expiry_ms() {
+            return None;
+        }
+
+        let index = (time_of_day - self.open_ms) / self.interval_ms;
+        Some(day_start + self.open_ms + (index + 1) * self.interval_ms)
+    }
+
+    /// Feeds [`strategy::ExpiryGuard::adjusted_threshold`].
+    pub fn seconds_to_expiry(&self, ts_ms: u64) -> Option<f64> {
+        self.expiry_at(ts_ms)
+            .map(|expiry_ms| (expiry_ms - ts_ms) as f64 / 1_000.0)
+    }
+
+    /// Expiries in `(from_ms, to_ms]`, oldest first: the markets a sim
+    /// stepping from `from_ms` to `to_ms` has to settle.
+    pub fn expiries_between(&self, from_ms: u64, to_ms: u64) -> Vec<u64> {
+        if to_ms <= from_ms {
+            return Vec::new();
+        }
+
+        let per_day = (self.close_ms - self.open_ms) / self.interval_ms;
+        (from_ms / DAY_MS..=to_ms / DAY_MS)
+            .flat_map(|day| {
+                (1..=per_day)
+                    .map(move |index| day * DAY_MS + self.open_ms + index * self.interval_ms)
+            })
+            .filter(|expiry_ms| (from_ms + 1..=to_ms).contains(expiry_ms))
+            .collect()
+    }
+
+    fn last_expiry_ms(&self) -> u64 {
+        let per_day = (self.close_ms - self.open_ms) / self.interval_ms;
+        self.open_ms + per_day * self.interval_ms
+    }
+}
+
+impl Default for SessionCalendar {
+    /// Round-the-clock 15m markets with no entries in the last 30 seconds,
+    /// matching [`strategy::ExpiryGuard::default`].
+    fn default() -> Self {
+        Self::new(0, DAY_MS, 900_000, 30_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strategy::ExpiryGuard;
+
+    use super::{SessionCalendar, SessionPhase, DAY_MS};
+
+    #[test]
+    fn markets_roll_over_every_fifteen_minutes() {
+        let calendar = SessionCalendar::default();
+
+        assert_eq!(calendar.phase(0), SessionPhase::Open { expiry_ms: 900_000 });
+        assert_eq!(
+            calendar.phase(870_000),
+            SessionPhase::NoNewEntries { expiry_ms: 900_000 }
+        );
+        assert_eq!(
+            calendar.phase(900_000),
+            SessionPhase::Open {
+                expiry_ms: 1_800_000
+            }
+        );
+        assert_eq!(calendar.expiry_at(DAY_MS - 1), Some(DAY_MS));
+        assert_eq!(
+            calendar.expiries_between(850_000, 2_700_000),
+            vec![900_000, 1_800_000, 2_700_000]
+        );
+        assert!(calendar.expiries_between(900_000, 900_000).is_empty());
+    }
+
+    #[test]
+    fn a_bounded_session_is_closed_outside_its_hours() {
+        // 13:30 to 20:00 UTC, with the 19:45 market the last to fit.
+        let open_ms = 48_600_000;
+        let calendar = SessionCalendar::new(open_ms, 72_000_000, 900_000, 60_000);
+
+        assert_eq!(calendar.phase(open_ms - 1), SessionPhase::Closed);
+        assert_eq!(calendar.expiry_at(open_ms), Some(open_ms + 900_000));
+        assert_eq!(calendar.phase(72_000_000), SessionPhase::Closed);
+        assert_eq!(
+            calendar.expiry_at(DAY_MS + open_ms + 1),
+            Some(DAY_MS + open_ms + 900_000)
+        );
+        assert_eq!(calendar.expiries_between(0, DAY_MS).len(), 26);
+    }
+
+    #[test]
+    fn the_expiry_guard_blocks_entries_in_the_no_entry_window() {
+        let calendar = SessionCalendar::default();
+        let guard = ExpiryGuard::default();
+
+        let early = calendar.seconds_to_expiry(0).unwrap();
+        assert_eq!(guard.adjusted_threshold(0.3, early), Some(0.3));
+        let late = calendar.seconds_to_expiry(875_000).unwrap();
+        assert!(matches!(
+            calendar.phase(875_000),
+            SessionPhase::NoNewEntries { .. }
+        ));
+        assert_eq!(guard.adjusted_threshold(0.3, late), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "no_entry_ms must be shorter than interval_ms")]
+    fn no_entry_window_must_fit_in_the_interval() {
+        SessionCalendar::new(0, DAY_MS, 60_000, 60_000);
+    }
+}
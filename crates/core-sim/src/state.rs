@@ -1,10 +1,21 @@
+use std::collections::BTreeMap;
+
+use crate::config::SimConfig;
+use crate::fills::{Fill, FillSummary};
+use crate::orderbook::{OrderBook, Side};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SimState {
     pub equity: f64,
     pub cash: f64,
+    /// Signed: positive long, negative short.
     pub position: f64,
     pub avg_price: f64,
+    /// Gross of fees, which come out of `cash`.
     pub realized_pnl: f64,
+    pub fees_paid: f64,
+    /// Equity the daily loss cap is measured from.
+    pub day_start_equity: f64,
     pub halted: bool,
 }
 
@@ -16,7 +27,262 @@ impl Default for SimState {
             position: 0.0,
             avg_price: 0.0,
             realized_pnl: 0.0,
+            fees_paid: 0.0,
+            day_start_equity: 100_000.0,
             halted: false,
         }
     }
 }
+
+impl SimState {
+    /// Books one fill: cash moves by the notional and fee, and any part that
+    /// reduces the position realizes PnL against the average price.
+    pub fn apply_fill(&mut self, side: Side, fill: &Fill) {
+        let signed_qty = match side {
+            Side::Buy => fill.qty,
+            Side::Sell => -fill.qty,
+        };
+        let closing = if self.position * signed_qty < 0.0 {
+            signed_qty.abs().min(self.position.abs()) * self.position.signum()
+        } else {
+            0.0
+        };
+        self.realized_pnl += (fill.price - self.avg_price) * closing;
+
+        let position = self.position + signed_qty;
+        if position == 0.0 {
+            self.avg_price = 0.0;
+        } else if self.position * position <= 0.0 {
+            // Opened, or flipped through flat: the remainder is new at the fill price.
+            self.avg_price = fill.price;
+        } else if closing == 0.0 {
+            self.avg_price = (self.avg_price * self.position + fill.price * signed_qty) / position;
+        }
+        self.position = position;
+        self.cash -= signed_qty * fill.price + fill.fee;
+        self.fees_paid += fill.fee;
+    }
+
+    /// Closes the whole position at `settle_px` without a fee, as when a
+    /// market expires.
+    pub fn settle(&mut self, settle_px: f64) {
+        let side = if self.position > 0.0 {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+        let fill = Fill {
+            price: settle_px,
+            qty: self.position.abs(),
+            fee: 0.0,
+        };
+        self.apply_fill(side, &fill);
+    }
+
+    pub fn unrealized_pnl(&self, mark_px: f64) -> f64 {
+        (mark_px - self.avg_price) * self.position
+    }
+
+    /// Revalues equity at `mark_px` and halts once the loss since the start
+    /// of the day exceeds the config's daily loss cap. Halting is sticky.
+    pub fn mark_to_market(&mut self, mark_px: f64, config: &SimConfig) {
+        self.equity = self.cash + self.position * mark_px;
+        if self.day_start_equity - self.equity > config.daily_loss_cap_pct * self.day_start_equity {
+            self.halted = true;
+        }
+    }
+}
+
+/// One market's book and account inside a multi-market sim.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MarketState {
+    pub book: OrderBook,
+    pub account: SimState,
+}
+
+impl MarketState {
+    pub fn new(book: OrderBook) -> Self {
+        Self {
+            book,
+            ..Self::default()
+        }
+    }
+
+    /// Sweeps this market's book and books the fills against the position.
+    pub fn execute_market_order(&mut self, side: Side, qty: f64) -> FillSummary {
+        let summary = match side {
+            Side::Buy => self.book.execute_market_buy(qty),
+            Side::Sell => self.book.execute_market_sell(qty),
+        };
+        for fill in &summary.fills {
+            self.account.apply_fill(side, fill);
+        }
+        summary
+    }
+
+    /// Mid of the displayed touch, when both sides are quoted.
+    pub fn mark_price(&self) -> Option<f64> {
+        match (self.book.best_bid(), self.book.best_ask()) {
+            (Some(bid), Some(ask)) => Some((bid.price + ask.price) / 2.0),
+            _ => None,
+        }
+    }
+
+    /// Open position marked at the mid; zero without a two-sided book.
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.mark_price()
+            .map(|mark| self.account.unrealized_pnl(mark))
+            .unwrap_or(0.0)
+    }
+
+    pub fn net_pnl(&self) -> f64 {
+        self.account.realized_pnl + self.unrealized_pnl() - self.account.fees_paid
+    }
+}
+
+/// Per-market sim state keyed by market id, mirroring the paper-live loop.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MultiMarketState {
+    markets: BTreeMap<String, MarketState>,
+}
+
+impl MultiMarketState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a market, replacing any existing state under the same id.
+    pub fn insert_market(&mut self, market_id: impl Into<String>, book: OrderBook) {
+        self.markets
+            .insert(market_id.into(), MarketState::new(book));
+    }
+
+    pub fn market(&self, market_id: &str) -> Option<&MarketState> {
+        self.markets.get(market_id)
+    }
+
+    pub fn market_mut(&mut self, market_id: &str) -> Option<&mut MarketState> {
+        self.markets.get_mut(market_id)
+    }
+
+    pub fn markets(&self) -> impl Iterator<Item = (&str, &MarketState)> {
+        self.markets
+            .iter()
+            .map(|(market_id, state)| (market_id.as_str(), state))
+    }
+
+    /// `None` when `market_id` has not been inserted.
+    pub fn execute_market_order(
+        &mut self,
+        market_id: &str,
+        side: Side,
+        qty: f64,
+    ) -> Option<FillSummary> {
+        self.markets
+            .get_mut(market_id)
+            .map(|market| market.execute_market_order(side, qty))
+    }
+
+    pub fn net_pnl(&self) -> f64 {
+        self.markets.values().map(MarketState::net_pnl).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultiMarketState, Side, SimState};
+    use crate::config::SimConfig;
+    use crate::fills::Fill;
+    use crate::orderbook::OrderBook;
+
+    fn fill(price: f64, qty: f64, fee: f64) -> Fill {
+        Fill { price, qty, fee }
+    }
+
+    #[test]
+    fn fills_update_position_cash_and_realized_pnl() {
+        let mut state = SimState::default();
+
+        state.apply_fill(Side::Buy, &fill(0.40, 100.0, 0.1));
+        state.apply_fill(Side::Buy, &fill(0.50, 100.0, 0.1));
+        assert_eq!(state.position, 200.0);
+        assert!((state.avg_price - 0.45).abs() < 1e-12);
+
+        // Selling 300 closes 200 at a 0.15 gain each and opens 100 short.
+        state.apply_fill(Side::Sell, &fill(0.60, 300.0, 0.2));
+        assert_eq!(state.position, -100.0);
+        assert_eq!(state.avg_price, 0.60);
+        assert!((state.realized_pnl - 30.0).abs() < 1e-9);
+        assert!((state.fees_paid - 0.4).abs() < 1e-12);
+        assert!((state.cash - (100_000.0 - 40.0 - 50.0 + 180.0 - 0.4)).abs() < 1e-9);
+        assert!((state.unrealized_pnl(0.55) - 5.0).abs() < 1e-9);
+
+        state.mark_to_market(0.55, &SimConfig::default());
+        assert!((state.equity - (state.cash - 55.0)).abs() < 1e-9);
+        assert!(!state.halted);
+    }
+
+    #[test]
+    fn marking_past_the_daily_loss_cap_halts() {
+        let config = SimConfig::default();
+        let mut state = SimState::default();
+        state.apply_fill(Side::Buy, &fill(100.0, 100.0, 0.0));
+
+        // 2% of 100k is 2,000: a 19 point drop on 100 shares is inside it.
+        state.mark_to_market(81.0, &config);
+        assert!(!state.halted);
+        state.mark_to_market(79.0, &config);
+        assert!(state.halted);
+        state.mark_to_market(100.0, &config);
+        assert!(state.halted);
+    }
+
+    #[test]
+    fn settling_at_expiry_flattens_without_a_fee() {
+        let mut state = SimState::default();
+        state.apply_fill(Side::Sell, &fill(0.30, 100.0, 0.1));
+
+        // The YES side resolved to 1: the short loses 0.70 a share.
+        state.settle(1.0);
+        assert_eq!(state.position, 0.0);
+        assert!((state.realized_pnl + 70.0).abs() < 1e-9);
+        assert!((state.fees_paid - 0.1).abs() < 1e-12);
+        state.settle(1.0);
+        assert!((state.cash - (100_000.0 - 70.0 - 0.1)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn markets_keep_separate_books_positions_and_pnl() {
+        let mut sim = MultiMarketState::new();
+        sim.insert_market("btc-updown-15m", OrderBook::default_with_liquidity());
+        sim.insert_market("eth-updown-15m", OrderBook::default_with_liquidity());
+
+        // Buy 1 at 100 and 2 at 101, then sell 2 into the 99 and 98 bids.
+        sim.execute_market_order("btc-updown-15m", Side::Buy, 3.0)
+            .unwrap();
+        sim.execute_market_order("btc-updown-15m", Side::Sell, 2.0)
+            .unwrap();
+        sim.execute_market_order("eth-updown-15m", Side::Sell, 1.0)
+            .unwrap();
+
+        let btc = sim.market("btc-updown-15m").unwrap();
+        let avg = 302.0 / 3.0;
+        assert_eq!(btc.account.position, 1.0);
+        assert!((btc.account.avg_price - avg).abs() < 1e-9);
+        assert!((btc.account.realized_pnl - (99.0 + 98.0 - 2.0 * avg)).abs() < 1e-9);
+        assert_eq!(btc.book.best_ask().map(|level| level.price), Some(102.0));
+
+        let eth = sim.market("eth-updown-15m").unwrap();
+        assert_eq!(eth.account.position, -1.0);
+        assert_eq!(eth.account.avg_price, 99.0);
+        // Marked at the 98/100 mid.
+        assert_eq!(eth.unrealized_pnl(), 0.0);
+        assert_eq!(eth.book.best_ask().map(|level| level.price), Some(100.0));
+
+        assert_eq!(sim.markets().count(), 2);
+        assert_eq!(
+            sim.execute_market_order("sol-updown-15m", Side::Buy, 1.0),
+            None
+        );
+    }
+}
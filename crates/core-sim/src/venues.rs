@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+
+use crate::config::PriceProcess;
+use crate::generators::{MarketLagGenerator, PricePath};
+use crate::rng::{Lcg, Rng};
+
+/// How one simulated venue distorts the latent price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueSpec {
+    pub name: String,
+    pub base_lag_ms: u64,
+    pub jitter_ms: u64,
+    /// Standard deviation of multiplicative print noise, in basis points.
+    pub noise_bps: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueQuote {
+    pub venue: String,
+    pub px: f64,
+    /// When the quote was published.
+    pub ts_ms: u64,
+    /// When the latent price it copies was current.
+    pub source_ts_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiVenueTick {
+    pub ts_ms: u64,
+    pub true_px: f64,
+    pub quotes: Vec<VenueQuote>,
+}
+
+#[derive(Debug, Clone)]
+struct Venue {
+    spec: VenueSpec,
+    lag: MarketLagGenerator,
+    noise: Lcg,
+}
+
+/// Per-venue prices that are noisy copies of one latent price, each seen
+/// through its own generated lag.
+#[derive(Debug, Clone)]
+pub struct MultiVenueGenerator {
+    truth: PricePath,
+    interval_ms: u64,
+    now_ms: u64,
+    /// Latent prices still inside the longest lag, oldest first.
+    history: VecDeque<(u64, f64)>,
+    max_lag_ms: u64,
+    venues: Vec<Venue>,
+}
+
+impl MultiVenueGenerator {
+    pub fn new(
+        seed: u64,
+        process: PriceProcess,
+        start_price: f64,
+        interval_ms: u64,
+        venues: Vec<VenueSpec>,
+    ) -> Self {
+        assert!(interval_ms > 0, "interval_ms must be positive");
+        let max_lag_ms = venues
+            .iter()
+            .map(|venue| venue.base_lag_ms.saturating_add(venue.jitter_ms))
+            .max()
+            .unwrap_or(0);
+        let mut root = Lcg::new(seed);
+        let truth = process.path_from_rng(root.fork(), start_price);
+        let venues = venues
+            .into_iter()
+            .map(|spec| {
+                assert!(
+                    spec.noise_bps.is_finite() && spec.noise_bps >= 0.0,
+                    "noise_bps must be finite and non-negative"
+                );
+                Venue {
+                    lag: MarketLagGenerator::from_rng(
+                        root.fork(),
+                        spec.base_lag_ms,
+                        spec.jitter_ms,
+                    ),
+                    noise: root.fork(),
+                    spec,
+                }
+            })
+            .collect();
+
+        Self {
+            truth,
+            interval_ms,
+            now_ms: 0,
+            history: VecDeque::new(),
+            max_lag_ms,
+            venues,
+        }
+    }
+
+    /// Advances the latent price by one interval and publishes every
+    /// venue's view of it.
+    pub fn next_tick(&mut self) -> MultiVenueTick {
+        self.now_ms += self.interval_ms;
+        let true_px = self.truth.next_price();
+        self.history.push_back((self.now_ms, true_px));
+        while self
+            .history
+            .get(1)
+            .is_some_and(|(ts, _)| self.now_ms - ts >= self.max_lag_ms)
+        {
+            self.history.pop_front();
+        }
+
+        let now_ms = self.now_ms;
+        let history = &self.history;
+        let quotes = self
+            .venues
+            .iter_mut()
+            .map(|venue| {
+                let seen_at = now_ms.saturating_sub(venue.lag.next_lag_ms());
+                // The newest latent price at or before `seen_at`.
+                let index = history.partition_point(|(ts, _)| *ts <= seen_at);
+                let (source_ts_ms, px) = history[index.saturating_sub(1)];
+                let noise = venue.spec.noise_bps / 10_000.0 * venue.noise.next_normal();
+                VenueQuote {
+                    venue: venue.spec.name.clone(),
+                    px: px * (1.0 + noise),
+                    ts_ms: now_ms,
+                    source_ts_ms,
+                }
+            })
+            .collect();
+
+        MultiVenueTick {
+            ts_ms: now_ms,
+            true_px,
+            quotes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultiVenueGenerator, VenueSpec};
+    use crate::config::PriceProcess;
+
+    fn venue(name: &str, base_lag_ms: u64, noise_bps: f64) -> VenueSpec {
+        VenueSpec {
+            name: name.to_string(),
+            base_lag_ms,
+            jitter_ms: 0,
+            noise_bps,
+        }
+    }
+
+    #[test]
+    fn venues_copy_the_latent_price_from_their_own_lag() {
+        let mut generator = MultiVenueGenerator::new(
+            3,
+            PriceProcess::Gbm {
+                drift: 0.0,
+                volatility: 0.001,
+            },
+            60_000.0,
+            100,
+            vec![
+                venue("fast", 0, 0.0),
+                venue("slow", 300, 0.0),
+                venue("noisy", 0, 5.0),
+            ],
+        );
+
+        let ticks: Vec<_> = (0..20).map(|_| generator.next_tick()).collect();
+        let last = &ticks[19];
+        assert_eq!(last.quotes[0].px, last.true_px);
+        assert_eq!(last.quotes[1].source_ts_ms, last.ts_ms - 300);
+        assert_eq!(last.quotes[1].px, ticks[16].true_px);
+        let noise_bps = (last.quotes[2].px / last.true_px - 1.0).abs() * 10_000.0;
+        assert!(
+            noise_bps > 0.0 && noise_bps < 25.0,
+            "noise was {noise_bps}bps"
+        );
+
+        // Before the slow venue's lag has elapsed it repeats the first price.
+        assert_eq!(ticks[0].quotes[1].px, ticks[0].true_px);
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+
+use strategy::{StrategyError, YesProbabilityModel};
+
+use crate::generators::MarketLagGenerator;
+
+/// A simulated up/down binary on the spot path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YesMarketSpec {
+    /// Spot level the market resolves YES above.
+    pub strike: f64,
+    pub expiry_ms: u64,
+    pub vol_bps_per_sqrt_min: f64,
+    /// How late the market reprices relative to spot.
+    pub base_lag_ms: u64,
+    pub jitter_ms: u64,
+    /// Full width of the YES book.
+    pub spread: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimYesQuote {
+    pub ts_ms: u64,
+    pub best_yes_bid: f64,
+    pub best_yes_ask: f64,
+    pub mid_yes: f64,
+    /// What the mid would be without the market's lag.
+    pub fair_yes: f64,
+}
+
+/// Prices YES off the spot path with the logistic fair-value model, but
+/// from the spot it saw `lag` ago, so the quote trails spot moves the way a
+/// slow prediction market does.
+#[derive(Debug, Clone)]
+pub struct YesQuoteSimulator {
+    spec: YesMarketSpec,
+    model: YesProbabilityModel,
+    lag: MarketLagGenerator,
+    /// Spot prints still inside the longest lag, oldest first.
+    spot_history: VecDeque<(u64, f64)>,
+}
+
+impl YesQuoteSimulator {
+    pub fn new(seed: u64, spec: YesMarketSpec) -> Result<Self, StrategyError> {
+        assert!(
+            spec.spread.is_finite() && (0.0..1.0).contains(&spec.spread),
+            "spread must be in [0, 1)"
+        );
+
+        Ok(Self {
+            model: YesProbabilityModel::new(spec.vol_bps_per_sqrt_min)?,
+            lag: MarketLagGenerator::new(seed, spec.base_lag_ms, spec.jitter_ms),
+            spec,
+            spot_history: VecDeque::new(),
+        })
+    }
+
+    /// Records a spot print and quotes YES as of `ts_ms`.
+    pub fn on_spot(&mut self, ts_ms: u64, spot_px: f64) -> Result<SimYesQuote, StrategyError> {
+        self.spot_history.push_back((ts_ms, spot_px));
+        let max_lag_ms = self.spec.base_lag_ms.saturating_add(self.spec.jitter_ms);
+        while self
+            .spot_history
+            .get(1)
+            .is_some_and(|(ts, _)| ts_ms.saturating_sub(*ts) >= max_lag_ms)
+        {
+            self.spot_history.pop_front();
+        }
+
+        let seen_at = ts_ms.saturating_sub(self.lag.next_lag_ms());
+        let index = self.spot_history.partition_point(|(ts, _)| *ts <= seen_at);
+        let (_, seen_px) = self.spot_history[index.saturating_sub(1)];
+
+        let minutes_remaining = self.spec.expiry_ms.saturating_sub(ts_ms) as f64 / 60_000.0;
+        let fair_yes = self
+            .model
+            .fair_yes(spot_px, self.spec.strike, minutes_remaining)?;
+        let half_spread = self.spec.spread / 2.0;
+        let mid_yes = self
+            .model
+            .fair_yes(seen_px, self.spec.strike, minutes_remaining)?
+            .clamp(half_spread, 1.0 - half_spread);
+
+        Ok(SimYesQuote {
+            ts_ms,
+            best_yes_bid: mid_yes - half_spread,
+            best_yes_ask: mid_yes + half_spread,
+            mid_yes,
+            fair_yes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strategy::{LagDivergenceStrategy, Signal, Strategy, StrategyInputs};
+
+    use super::{YesMarketSpec, YesQuoteSimulator};
+
+    #[test]
+    fn yes_quote_trails_a_spot_jump_and_triggers_the_lag_strategy() {
+        let spec = YesMarketSpec {
+            strike: 60_000.0,
+            expiry_ms: 900_000,
+            vol_bps_per_sqrt_min: 10.0,
+            base_lag_ms: 500,
+            jitter_ms: 0,
+            spread: 0.02,
+        };
+        let mut market = YesQuoteSimulator::new(1, spec).unwrap();
+        let mut strategy = LagDivergenceStrategy::default();
+
+        for ts_ms in (0..1_000).step_by(100) {
+            let quote = market.on_spot(ts_ms, 60_000.0).unwrap();
+            assert_eq!(quote.mid_yes, 0.5);
+        }
+
+        // Spot jumps 0.2%; the market keeps quoting the old level for 500ms.
+        let mut intents = Vec::new();
+        for ts_ms in (1_000..1_600).step_by(100) {
+            let quote = market.on_spot(ts_ms, 60_120.0).unwrap();
+            if ts_ms < 1_500 {
+                assert_eq!(quote.mid_yes, 0.5);
+            } else {
+                assert_eq!(quote.mid_yes, quote.fair_yes);
+            }
+            intents.extend(strategy.on_tick(&StrategyInputs {
+                market_id: "btc-updown-15m",
+                underlying: "btc",
+                spot_px: 60_120.0,
+                ts_ms,
+                mid_yes: quote.mid_yes,
+                best_yes_bid: quote.best_yes_bid,
+                best_yes_ask: quote.best_yes_ask,
+                fair_yes_px: quote.fair_yes,
+                spread_signal: 0.0,
+                threshold_pct: 1.0,
+                base_qty: 1.0,
+                position_qty: 0.0,
+            }));
+        }
+
+        assert_eq!(intents.len(), 5);
+        assert!(intents.iter().all(|intent| intent.side == Signal::Buy));
+    }
+}
@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use api::state::{PaperOrderSide, PublishedEvent, RiskAlertKind, RuntimeEvent};
+use reqwest::Client;
+use runtime::throttle::IntentThrottle;
+use serde_json::json;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Duration;
+
+use crate::now_unix_ms;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Groups of alerts that can be switched on or off for the chat channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertCategory {
+    /// Drawdown and daily-cap halts, and the kill switch.
+    Halts,
+    /// Fills at or above the large-fill notional.
+    Fills,
+    WinRate,
+    StaleFeeds,
+}
+
+impl AlertCategory {
+    pub const ALL: [Self; 4] = [Self::Halts, Self::Fills, Self::WinRate, Self::StaleFeeds];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "halts" => Some(Self::Halts),
+            "fills" => Some(Self::Fills),
+            "win_rate" => Some(Self::WinRate),
+            "stale_feeds" => Some(Self::StaleFeeds),
+            _ => None,
+        }
+    }
+}
+
+/// Slack or Discord incoming webhook that receives formatted alerts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatAlertConfig {
+    pub url: String,
+    pub categories: Vec<AlertCategory>,
+    /// Fills whose notional, in USD, reaches this are reported.
+    pub large_fill_usd: f64,
+    /// Messages posted in any rolling minute; the rest are counted and
+    /// mentioned in the next message that goes out.
+    pub max_per_min: u32,
+}
+
+/// Message format of the receiving service, picked from the webhook URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChatPlatform {
+    Slack,
+    Discord,
+}
+
+impl ChatPlatform {
+    fn for_url(url: &str) -> Self {
+        let host = url
+            .split("://")
+            .nth(1)
+            .and_then(|rest| rest.split(['/', ':']).next())
+            .unwrap_or_default();
+        if host == "discord.com" || host.ends_with(".discord.com") || host == "discordapp.com" {
+            Self::Discord
+        } else {
+            Self::Slack
+        }
+    }
+
+    fn payload(self, title: &str, detail: &str) -> serde_json::Value {
+        match self {
+            Self::Slack => json!({ "text": format!("*{title}*: {detail}") }),
+            Self::Discord => json!({ "content": format!("**{title}**: {detail}") }),
+        }
+    }
+}
+
+/// Turns bus events into chat messages for the enabled categories, at no
+/// more than `max_per_min` a minute.
+#[derive(Debug)]
+pub struct ChatAlerts {
+    config: ChatAlertConfig,
+    platform: ChatPlatform,
+    throttle: IntentThrottle,
+    suppressed: u64,
+}
+
+impl ChatAlerts {
+    pub fn new(config: ChatAlertConfig) -> Self {
+        Self {
+            platform: ChatPlatform::for_url(&config.url),
+            throttle: IntentThrottle::new(config.max_per_min.max(1))
+                .expect("limit is at least one"),
+            suppressed: 0,
+            config,
+        }
+    }
+
+    /// Message body to post for `event`, if it is an enabled alert and the
+    /// rate limit allows it.
+    pub fn message(&mut self, event: &RuntimeEvent, now_ms: u64) -> Option<serde_json::Value> {
+        let (category, title, detail) = self.describe(event)?;
+        if !self.config.categories.contains(&category) {
+            return None;
+        }
+        if !self.throttle.try_acquire(now_ms) {
+            self.suppressed = self.suppressed.saturating_add(1);
+            return None;
+        }
+
+        let detail = match std::mem::take(&mut self.suppressed) {
+            0 => detail,
+            suppressed => format!("{detail} ({suppressed} earlier alerts suppressed)"),
+        };
+        Some(self.platform.payload(title, &detail))
+    }
+
+    fn describe(&self, event: &RuntimeEvent) -> Option<(AlertCategory, &'static str, String)> {
+        match event {
+            RuntimeEvent::RiskAlert { kind, detail, .. } => {
+                let (category, title) = match kind {
+                    RiskAlertKind::Halt => (AlertCategory::Halts, "Trading halted"),
+                    RiskAlertKind::DailyCapBreach => {
+                        (AlertCategory::Halts, "Daily loss cap breached")
+                    }
+                    RiskAlertKind::KillSwitch => (AlertCategory::Halts, "Kill switch engaged"),
+                    RiskAlertKind::FeedDegraded => (AlertCategory::StaleFeeds, "Feed degraded"),
+                    RiskAlertKind::FeedBlocked => (AlertCategory::StaleFeeds, "Feed blocked"),
+                    RiskAlertKind::WinRateCollapse => {
+                        (AlertCategory::WinRate, "Win rate collapsed")
+                    }
+                };
+                Some((category, title, detail.clone()))
+            }
+            RuntimeEvent::PaperFill {
+                market_id,
+                side,
+                qty,
+                fill_px,
+                ..
+            } => {
+                let side = match side {
+                    PaperOrderSide::Buy => "buy",
+                    PaperOrderSide::Sell => "sell",
+                };
+                self.large_fill(market_id, side, *qty, *fill_px)
+            }
+            RuntimeEvent::BracketTriggered {
+                market_id,
+                kind,
+                qty,
+                fill_px,
+                ..
+            } => self.large_fill(market_id, kind, *qty, *fill_px),
+            _ => None,
+        }
+    }
+
+    fn large_fill(
+        &self,
+        market_id: &str,
+        label: &str,
+        qty: f64,
+        fill_px: f64,
+    ) -> Option<(AlertCategory, &'static str, String)> {
+        let notional = qty.abs() * fill_px;
+        (notional >= self.config.large_fill_usd).then(|| {
+            (
+                AlertCategory::Fills,
+                "Large fill",
+                format!(
+                    "{label} {:.2} {market_id} @ {fill_px:.3} (${notional:.2})",
+                    qty.abs()
+                ),
+            )
+        })
+    }
+
+    /// Posts alerts until the event bus closes. A failed post is logged and
+    /// dropped; chat alerts are a convenience, not a delivery guarantee.
+    pub async fn run(
+        mut self,
+        client: Client,
+        mut events: broadcast::Receiver<Arc<PublishedEvent>>,
+    ) {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            };
+            let Some(body) = self.message(&event.event, now_unix_ms()) else {
+                continue;
+            };
+            let request = client
+                .post(&self.config.url)
+                .timeout(REQUEST_TIMEOUT)
+                .json(&body);
+            tokio::spawn(async move {
+                match request.send().await {
+                    Ok(response) if response.status().is_success() => {}
+                    Ok(response) => eprintln!("chat alert rejected: {}", response.status()),
+                    Err(err) => eprintln!("chat alert failed: {err}"),
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alerts(url: &str, categories: Vec<AlertCategory>, max_per_min: u32) -> ChatAlerts {
+        ChatAlerts::new(ChatAlertConfig {
+            url: url.to_string(),
+            categories,
+            large_fill_usd: 50.0,
+            max_per_min,
+        })
+    }
+
+    #[test]
+    fn formats_for_the_platform_and_skips_disabled_or_small_events() {
+        let halt = RuntimeEvent::risk_alert(RiskAlertKind::Halt, "drawdown=5.10%", 0);
+        let small = RuntimeEvent::paper_fill("btc-a", PaperOrderSide::Buy, 10.0, 0.5, 0.0);
+        let large = RuntimeEvent::paper_fill("btc-a", PaperOrderSide::Sell, 200.0, 0.5, 0.0);
+
+        let mut slack = alerts(
+            "https://hooks.slack.com/services/T/B/x",
+            vec![AlertCategory::Halts],
+            10,
+        );
+        assert_eq!(
+            slack.message(&halt, 0),
+            Some(json!({ "text": "*Trading halted*: drawdown=5.10%" }))
+        );
+        assert_eq!(slack.message(&large, 0), None);
+
+        let mut discord = alerts(
+            "https://discord.com/api/webhooks/1/abc",
+            AlertCategory::ALL.to_vec(),
+            10,
+        );
+        assert_eq!(discord.message(&small, 0), None);
+        assert_eq!(
+            discord.message(&large, 0),
+            Some(json!({ "content": "**Large fill**: sell 200.00 btc-a @ 0.500 ($100.00)" }))
+        );
+    }
+
+    #[test]
+    fn rate_limits_and_reports_suppressed_alerts() {
+        let mut slack = alerts("https://hooks.slack.com/x", AlertCategory::ALL.to_vec(), 1);
+        let degraded = RuntimeEvent::risk_alert(RiskAlertKind::FeedDegraded, "okx", 0);
+
+        assert!(slack.message(&degraded, 0).is_some());
+        assert_eq!(slack.message(&degraded, 1_000), None);
+        assert_eq!(slack.message(&degraded, 2_000), None);
+        assert_eq!(
+            slack.message(&degraded, 60_000),
+            Some(json!({ "text": "*Feed degraded*: okx (2 earlier alerts suppressed)" }))
+        );
+    }
+}
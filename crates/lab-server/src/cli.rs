@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+use core_sim::{run_batch, run_scenario, Distribution, Scenario, SimConfig};
+use runtime::forecasts::{parse_forecasts_csv, write_forecast_recording};
+use runtime::live::PredictorSource;
+use serde_json::{json, Value};
+
+use crate::config::Config;
+
+/// Market latency risk lab: dashboard, paper trading and backtests.
+#[derive(Debug, Parser)]
+#[command(name = "lab-server", version)]
+pub struct Cli {
+    #[command(flatten)]
+    pub config: ConfigFlags,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum Command {
+    /// Serve the dashboard and API; the default.
+    Serve,
+    /// Serve while replaying recorded ticks instead of connecting to venues.
+    Replay {
+        /// Directory written by `record`.
+        dir: String,
+        /// Speed-up over the recorded inter-arrival times.
+        #[arg(long)]
+        speed: Option<String>,
+    },
+    /// Run the lag-arb sim offline and print its report as JSON.
+    Backtest {
+        #[arg(long, default_value_t = 100)]
+        runs: u64,
+        #[arg(long, default_value_t = 5_000)]
+        ticks: u64,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// TOML stress scenario to run once instead of a Monte Carlo batch.
+        #[arg(long)]
+        scenario: Option<PathBuf>,
+    },
+    /// Add a CSV of external forecasts to a recording, to be replayed as
+    /// predictor ticks alongside it.
+    ImportForecasts {
+        /// CSV with `ts_ms` and `predicted_yes_px` columns, and optionally
+        /// `source` and `confidence`.
+        file: PathBuf,
+        /// Recording directory to add the forecasts to.
+        #[arg(long)]
+        into: PathBuf,
+        /// Predictor label for rows without a `source`.
+        #[arg(long)]
+        source: Option<String>,
+    },
+    /// Serve and record feed ticks for later replay.
+    Record {
+        /// Comma-separated streams, or `all`.
+        #[arg(long, default_value = "all")]
+        sources: String,
+    },
+}
+
+/// Defines one `--flag` per config env var, each overriding its variable.
+macro_rules! config_flags {
+    ($($(#[doc = $doc:literal])* $field:ident => $key:literal,)*) => {
+        #[derive(Debug, Default, Clone, PartialEq, Args)]
+        pub struct ConfigFlags {
+            $(
+                $(#[doc = $doc])*
+                #[arg(long, global = true, value_name = "VALUE")]
+                pub $field: Option<String>,
+            )*
+        }
+
+        impl ConfigFlags {
+            fn overrides(&self) -> HashMap<&'static str, String> {
+                let mut overrides = HashMap::new();
+                $(
+                    if let Some(value) = &self.$field {
+                        overrides.insert($key, value.clone());
+                    }
+                )*
+                overrides
+            }
+        }
+    };
+}
+
+config_flags! {
+    /// LAB_SERVER_ADDR
+    addr => "LAB_SERVER_ADDR",
+    /// LAB_TLS_CERT_PATH
+    tls_cert_path => "LAB_TLS_CERT_PATH",
+    /// LAB_TLS_KEY_PATH
+    tls_key_path => "LAB_TLS_KEY_PATH",
+    /// LAB_TLS_REDIRECT_ADDR
+    tls_redirect_addr => "LAB_TLS_REDIRECT_ADDR",
+    /// LAB_GRPC_ADDR: needs a build with the grpc feature
+    grpc_addr => "LAB_GRPC_ADDR",
+    /// LAB_SERVER_MODE: paper-live, sim or replay
+    mode => "LAB_SERVER_MODE",
+    /// LAB_SERVER_REPLAY_OUTPUT
+    replay_output => "LAB_SERVER_REPLAY_OUTPUT",
+    /// LAB_EXECUTION_MODE
+    execution_mode => "LAB_EXECUTION_MODE",
+    /// LAB_LIVE_FEATURE_ENABLED
+    live_feature_enabled => "LAB_LIVE_FEATURE_ENABLED",
+    /// LAB_LAG_THRESHOLD_PCT
+    lag_threshold_pct => "LAB_LAG_THRESHOLD_PCT",
+    /// LAB_RISK_PER_TRADE_PCT
+    risk_per_trade_pct => "LAB_RISK_PER_TRADE_PCT",
+    /// LAB_DAILY_LOSS_CAP_PCT
+    daily_loss_cap_pct => "LAB_DAILY_LOSS_CAP_PCT",
+    /// LAB_TRADING_DAY_UTC_OFFSET: Z or +HH:MM/-HH:MM
+    trading_day_utc_offset => "LAB_TRADING_DAY_UTC_OFFSET",
+    /// LAB_MAX_DRAWDOWN_PCT
+    max_drawdown_pct => "LAB_MAX_DRAWDOWN_PCT",
+    /// LAB_VAR_LIMIT_PCT
+    var_limit_pct => "LAB_VAR_LIMIT_PCT",
+    /// LAB_LAG_COOLDOWN_SECS
+    lag_cooldown_secs => "LAB_LAG_COOLDOWN_SECS",
+    /// LAB_LAG_PERSISTENCE_EVALS
+    lag_persistence_evals => "LAB_LAG_PERSISTENCE_EVALS",
+    /// LAB_MAX_INTENTS_PER_MIN
+    max_intents_per_min => "LAB_MAX_INTENTS_PER_MIN",
+    /// LAB_DECISION_INTERVAL_MS
+    decision_interval_ms => "LAB_DECISION_INTERVAL_MS",
+    /// LAB_QUOTE_POLL_MS
+    quote_poll_ms => "LAB_QUOTE_POLL_MS",
+    /// LAB_FEED_BLOCK_COOLDOWN_SECS
+    feed_block_cooldown_secs => "LAB_FEED_BLOCK_COOLDOWN_SECS",
+    /// LAB_LOSS_STREAK_LIMIT
+    loss_streak_limit => "LAB_LOSS_STREAK_LIMIT",
+    /// LAB_LOSS_STREAK_COOLDOWN_SECS
+    loss_streak_cooldown_secs => "LAB_LOSS_STREAK_COOLDOWN_SECS",
+    /// LAB_BTC_AGGREGATOR
+    btc_aggregator => "LAB_BTC_AGGREGATOR",
+    /// LAB_BTC_VWAP_WINDOW_MS
+    btc_vwap_window_ms => "LAB_BTC_VWAP_WINDOW_MS",
+    /// LAB_BTC_EWMA_HALF_LIFE_MS
+    btc_ewma_half_life_ms => "LAB_BTC_EWMA_HALF_LIFE_MS",
+    /// LAB_BTC_OUTLIER_MODE
+    btc_outlier_mode => "LAB_BTC_OUTLIER_MODE",
+    /// LAB_ASSETS
+    assets => "LAB_ASSETS",
+    /// LAB_MARKET_FILTERS
+    market_filters => "LAB_MARKET_FILTERS",
+    /// LAB_MARKET_HORIZON_MINS
+    market_horizon_mins => "LAB_MARKET_HORIZON_MINS",
+    /// LAB_RECORD_DIR
+    record_dir => "LAB_RECORD_DIR",
+    /// LAB_RECORD_ROTATE_MB
+    record_rotate_mb => "LAB_RECORD_ROTATE_MB",
+    /// LAB_RECORD_SOURCES
+    record_sources => "LAB_RECORD_SOURCES",
+    /// LAB_RECORD_FORMAT: csv, or parquet in a build with the parquet feature
+    record_format => "LAB_RECORD_FORMAT",
+    /// LAB_REPLAY_INPUT_DIR
+    replay_input_dir => "LAB_REPLAY_INPUT_DIR",
+    /// LAB_REPLAY_SPEED
+    replay_speed => "LAB_REPLAY_SPEED",
+    /// LAB_BACKFILL_TRADES
+    backfill_trades => "LAB_BACKFILL_TRADES",
+    /// LAB_REGIME_CALM_BPS
+    regime_calm_bps => "LAB_REGIME_CALM_BPS",
+    /// LAB_REGIME_VOLATILE_BPS
+    regime_volatile_bps => "LAB_REGIME_VOLATILE_BPS",
+    /// LAB_PREDICTOR_WEIGHTS_PATH
+    predictor_weights_path => "LAB_PREDICTOR_WEIGHTS_PATH",
+    /// LAB_PREDICTORS_FILE
+    predictors_file => "LAB_PREDICTORS_FILE",
+    /// LAB_MAX_MARKET_POSITION
+    max_market_position => "LAB_MAX_MARKET_POSITION",
+    /// LAB_BRACKET_STOP
+    bracket_stop => "LAB_BRACKET_STOP",
+    /// LAB_BRACKET_TARGET
+    bracket_target => "LAB_BRACKET_TARGET",
+    /// LAB_MAX_SPREAD
+    max_spread => "LAB_MAX_SPREAD",
+    /// LAB_MIN_TOUCH_QTY
+    min_touch_qty => "LAB_MIN_TOUCH_QTY",
+    /// LAB_WEBHOOK_URLS
+    webhook_urls => "LAB_WEBHOOK_URLS",
+    /// LAB_WEBHOOK_MAX_ATTEMPTS
+    webhook_max_attempts => "LAB_WEBHOOK_MAX_ATTEMPTS",
+    /// LAB_CHAT_ALERTS
+    chat_alerts => "LAB_CHAT_ALERTS",
+    /// LAB_CHAT_LARGE_FILL_USD
+    chat_large_fill_usd => "LAB_CHAT_LARGE_FILL_USD",
+    /// LAB_CHAT_MAX_ALERTS_PER_MIN
+    chat_max_alerts_per_min => "LAB_CHAT_MAX_ALERTS_PER_MIN",
+    /// LAB_STATSD_ADDR
+    statsd_addr => "LAB_STATSD_ADDR",
+    /// LAB_STATSD_PREFIX
+    statsd_prefix => "LAB_STATSD_PREFIX",
+    /// LAB_STATSD_TAGS
+    statsd_tags => "LAB_STATSD_TAGS",
+    /// LAB_REDIS_URL
+    redis_url => "LAB_REDIS_URL",
+    /// LAB_REDIS_CHANNEL_PREFIX
+    redis_channel_prefix => "LAB_REDIS_CHANNEL_PREFIX",
+    /// LAB_PROXY_URL
+    proxy_url => "LAB_PROXY_URL",
+    /// LAB_VENUE_PROXIES
+    venue_proxies => "LAB_VENUE_PROXIES",
+}
+
+impl Cli {
+    pub fn command(&self) -> Command {
+        self.command.clone().unwrap_or(Command::Serve)
+    }
+
+    /// Config overrides from the flags, plus whatever the subcommand implies.
+    pub fn overrides(&self) -> HashMap<&'static str, String> {
+        let mut overrides = self.config.overrides();
+        match &self.command {
+            Some(Command::Replay { dir, speed }) => {
+                overrides.insert("LAB_SERVER_MODE", "replay".to_string());
+                overrides.insert("LAB_REPLAY_INPUT_DIR", dir.clone());
+                if let Some(speed) = speed {
+                    overrides.insert("LAB_REPLAY_SPEED", speed.clone());
+                }
+            }
+            Some(Command::Record { sources }) => {
+                overrides.insert("LAB_RECORD_SOURCES", sources.clone());
+            }
+            Some(Command::Serve | Command::Backtest { .. } | Command::ImportForecasts { .. })
+            | None => {}
+        }
+        overrides
+    }
+}
+
+/// The sim config a backtest runs with: the lag threshold, per-trade risk
+/// and daily loss cap come from the server config, in percent.
+pub fn backtest_sim_config(config: &Config) -> SimConfig {
+    SimConfig {
+        divergence_threshold: config.lag_threshold_pct / 100.0,
+        max_position_pct: config.per_trade_risk_pct / 100.0,
+        daily_loss_cap_pct: config.daily_loss_cap_pct / 100.0,
+        ..SimConfig::default()
+    }
+}
+
+pub fn run_backtest(
+    sim_config: &SimConfig,
+    runs: u64,
+    ticks: u64,
+    seed: u64,
+    scenario: Option<&PathBuf>,
+) -> Result<Value, String> {
+    if let Some(path) = scenario {
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("cannot read scenario {}: {err}", path.display()))?;
+        let scenario = Scenario::from_toml(&source)
+            .map_err(|err| format!("invalid scenario {}: {err:?}", path.display()))?;
+        let outcome = run_scenario(sim_config, &scenario, seed);
+        return Ok(json!({
+            "scenario": scenario.name,
+            "seed": seed,
+            "terminal_equity": outcome.terminal_equity,
+            "report": outcome.report,
+        }));
+    }
+
+    let report = run_batch(sim_config, seed, runs, ticks);
+    let distribution = |distribution: Distribution| {
+        json!({
+            "mean": distribution.mean,
+            "min": distribution.min,
+            "p5": distribution.p5,
+            "p50": distribution.p50,
+            "p95": distribution.p95,
+            "max": distribution.max,
+        })
+    };
+    Ok(json!({
+        "runs": runs,
+        "ticks": ticks,
+        "seed": seed,
+        "halted_runs": report.halted_runs,
+        "halt_rate": report.halt_rate,
+        "terminal_equity": distribution(report.terminal_equity),
+        "max_drawdown_pct": distribution(report.max_drawdown_pct),
+    }))
+}
+
+/// Converts `file` into a predictors recording in `into` and summarises it
+/// as JSON.
+pub fn run_import_forecasts(
+    file: &Path,
+    into: &Path,
+    source: Option<&str>,
+) -> Result<Value, String> {
+    let default_source = source
+        .map(|label| {
+            PredictorSource::parse(label)
+                .ok_or_else(|| format!("invalid predictor label `{label}`"))
+        })
+        .transpose()?;
+    let raw = fs::read_to_string(file)
+        .map_err(|err| format!("cannot read forecasts {}: {err}", file.display()))?;
+    let forecasts = parse_forecasts_csv(&raw, default_source.as_ref())
+        .map_err(|err| format!("invalid forecasts {}: {err:?}", file.display()))?;
+    fs::create_dir_all(into)
+        .map_err(|err| format!("cannot create recording dir {}: {err}", into.display()))?;
+    let name = file
+        .file_stem()
+        .map_or("forecasts".into(), |stem| stem.to_string_lossy());
+    let path = write_forecast_recording(into, &name, &forecasts)
+        .map_err(|err| format!("cannot write forecasts into {}: {err}", into.display()))?;
+
+    let mut sources: Vec<&str> = forecasts
+        .iter()
+        .map(|forecast| forecast.source.as_str())
+        .collect();
+    sources.sort_unstable();
+    sources.dedup();
+    Ok(json!({
+        "path": path.display().to_string(),
+        "forecasts": forecasts.len(),
+        "sources": sources,
+        "first_ts_ms": forecasts.first().map(|forecast| forecast.ts_ms),
+        "last_ts_ms": forecasts.last().map(|forecast| forecast.ts_ms),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use clap::Parser;
+    use core_sim::SimConfig;
+
+    use super::{run_backtest, run_import_forecasts, Cli, Command};
+
+    #[test]
+    fn flags_and_subcommands_become_config_overrides() {
+        let cli = Cli::parse_from([
+            "lab-server",
+            "--lag-threshold-pct",
+            "0.5",
+            "replay",
+            "artifacts/ticks",
+            "--speed",
+            "10",
+            "--assets",
+            "btc,eth",
+        ]);
+
+        let overrides = cli.overrides();
+        assert_eq!(overrides["LAB_LAG_THRESHOLD_PCT"], "0.5");
+        assert_eq!(overrides["LAB_ASSETS"], "btc,eth");
+        assert_eq!(overrides["LAB_REPLAY_INPUT_DIR"], "artifacts/ticks");
+        assert_eq!(overrides["LAB_REPLAY_SPEED"], "10");
+        assert_eq!(overrides["LAB_SERVER_MODE"], "replay");
+        assert_eq!(overrides.len(), 5);
+
+        let serve = Cli::parse_from(["lab-server"]);
+        assert_eq!(serve.command(), Command::Serve);
+        assert!(serve.overrides().is_empty());
+        let record = Cli::parse_from(["lab-server", "record"]);
+        assert_eq!(record.overrides()["LAB_RECORD_SOURCES"], "all");
+    }
+
+    #[test]
+    fn backtest_reports_batch_distributions() {
+        let cli = Cli::parse_from(["lab-server", "backtest", "--runs", "4", "--ticks", "200"]);
+        let Command::Backtest {
+            runs,
+            ticks,
+            seed,
+            scenario,
+        } = cli.command()
+        else {
+            panic!("expected backtest");
+        };
+
+        let report =
+            run_backtest(&SimConfig::default(), runs, ticks, seed, scenario.as_ref()).unwrap();
+        assert_eq!(report["runs"], 4);
+        assert!(report["terminal_equity"]["p50"].as_f64().is_some());
+        assert!(run_backtest(
+            &SimConfig::default(),
+            1,
+            1,
+            0,
+            Some(&"does/not/exist.toml".into())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn imports_forecasts_into_a_recording_dir() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("lab-server-forecasts-{unique}"));
+        fs::create_dir_all(&root).unwrap();
+        let file = root.join("lstm.csv");
+        fs::write(
+            &file,
+            "ts_ms,predicted_yes_px
+2000,0.6
+1000,0.55
+",
+        )
+        .unwrap();
+        let cli = Cli::parse_from([
+            "lab-server",
+            "import-forecasts",
+            file.to_str().unwrap(),
+            "--into",
+            root.join("ticks").to_str().unwrap(),
+            "--source",
+            "lstm",
+        ]);
+        let Command::ImportForecasts { file, into, source } = cli.command() else {
+            panic!("expected import-forecasts");
+        };
+
+        let summary = run_import_forecasts(&file, &into, source.as_deref()).unwrap();
+        assert_eq!(summary["forecasts"], 2);
+        assert_eq!(summary["sources"][0], "lstm");
+        assert_eq!(summary["first_ts_ms"], 1_000);
+        assert!(into.join("predictors-import-lstm.csv").exists());
+        assert!(run_import_forecasts(&file, &into, None).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
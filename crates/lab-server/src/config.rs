@@ -1,8 +1,23 @@
 use std::{
-    env, fmt,
+    collections::HashMap,
+    env, fmt, fs,
     net::{AddrParseError, SocketAddr},
 };
 
+use api::state::{check_settings_invariants, SettingsConflict};
+use runtime::brackets::BracketConfig;
+use runtime::execution::ClobCredentials;
+use runtime::live::{
+    Asset, HttpProxy, HttpProxyError, MarketFilterError, MarketSelection, RegimeThresholds,
+    SpreadLimit,
+};
+use runtime::metrics::StatsdConfig;
+use runtime::recorder::{RecordFormat, RecordStream, TickRecorderConfig};
+
+use crate::chat_alerts::{AlertCategory, ChatAlertConfig};
+use crate::predictors::{parse_predictor_endpoints, PredictorConfigError, PredictorEndpoint};
+use crate::redis_mirror::RedisMirrorConfig;
+
 const DEFAULT_LISTEN_ADDR: &str = "0.0.0.0:8080";
 const DEFAULT_MODE: RunMode = RunMode::PaperLive;
 const DEFAULT_REPLAY_OUTPUT_PATH: &str = "artifacts/replay.csv";
@@ -11,11 +26,81 @@ const DEFAULT_LIVE_FEATURE_ENABLED: bool = false;
 const DEFAULT_LAG_THRESHOLD_PCT: f64 = 0.3;
 const DEFAULT_PER_TRADE_RISK_PCT: f64 = 0.5;
 const DEFAULT_DAILY_LOSS_CAP_PCT: f64 = 2.0;
+const DEFAULT_MAX_DRAWDOWN_PCT: f64 = 5.0;
+const MAX_TRADING_DAY_UTC_OFFSET_MINS: i32 = 14 * 60;
+const DEFAULT_LAG_COOLDOWN_SECS: u64 = 30;
+const DEFAULT_LAG_PERSISTENCE_EVALS: u32 = 2;
+const MAX_LAG_COOLDOWN_SECS: u64 = 3_600;
+const MAX_LAG_PERSISTENCE_EVALS: u32 = 100;
+const DEFAULT_MAX_INTENTS_PER_MIN: u32 = 30;
+const MAX_MAX_INTENTS_PER_MIN: u32 = 10_000;
+const DEFAULT_DECISION_INTERVAL_MS: u64 = 1_500;
+const MIN_DECISION_INTERVAL_MS: u64 = 100;
+const MAX_DECISION_INTERVAL_MS: u64 = 60_000;
+const DEFAULT_QUOTE_POLL_MS: u64 = 15_000;
+const MIN_QUOTE_POLL_MS: u64 = 1_000;
+const MAX_QUOTE_POLL_MS: u64 = 300_000;
+const DEFAULT_FEED_BLOCK_COOLDOWN_SECS: u64 = 900;
+const MIN_FEED_BLOCK_COOLDOWN_SECS: u64 = 60;
+const MAX_FEED_BLOCK_COOLDOWN_SECS: u64 = 86_400;
+const DEFAULT_LOSS_STREAK_LIMIT: u32 = 3;
+const MAX_LOSS_STREAK_LIMIT: u32 = 100;
+const DEFAULT_LOSS_STREAK_COOLDOWN_SECS: u64 = 300;
+const MAX_LOSS_STREAK_COOLDOWN_SECS: u64 = 86_400;
+const DEFAULT_BTC_AGGREGATOR: BtcAggregatorMode = BtcAggregatorMode::Median;
+const DEFAULT_BTC_VWAP_WINDOW_MS: u64 = 3_000;
+const MAX_BTC_VWAP_WINDOW_MS: u64 = 60_000;
+const DEFAULT_BTC_EWMA_HALF_LIFE_MS: u64 = 2_000;
+const MAX_BTC_EWMA_HALF_LIFE_MS: u64 = 600_000;
+const DEFAULT_BTC_OUTLIER_MODE: BtcOutlierMode = BtcOutlierMode::Fixed;
+const DEFAULT_RECORD_DIR: &str = "artifacts/ticks";
+const DEFAULT_RECORD_ROTATE_MB: u64 = 64;
+const MAX_RECORD_ROTATE_MB: u64 = 4_096;
+const DEFAULT_REPLAY_SPEED: f64 = 1.0;
+const MAX_REPLAY_SPEED: f64 = 1_000.0;
+const DEFAULT_BACKFILL_TRADES: u64 = 200;
+const MAX_BACKFILL_TRADES: u64 = 500;
+const DEFAULT_REGIME_CALM_BPS: f64 = 4.0;
+const DEFAULT_REGIME_VOLATILE_BPS: f64 = 12.0;
+const DEFAULT_PREDICTOR_WEIGHTS_PATH: &str = "artifacts/predictor_weights.json";
+const DEFAULT_MAX_MARKET_POSITION: f64 = 100.0;
+const DEFAULT_BRACKET_STOP: f64 = 0.10;
+const DEFAULT_BRACKET_TARGET: f64 = 0.20;
+const DEFAULT_MAX_SPREAD: SpreadLimit = SpreadLimit::Absolute(0.05);
+const DEFAULT_MIN_TOUCH_QTY: f64 = 5.0;
+const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u64 = 5;
+const MAX_WEBHOOK_MAX_ATTEMPTS: u64 = 10;
+const DEFAULT_CHAT_LARGE_FILL_USD: f64 = 50.0;
+const DEFAULT_CHAT_MAX_ALERTS_PER_MIN: u64 = 6;
+const MAX_CHAT_MAX_ALERTS_PER_MIN: u64 = 60;
+const DEFAULT_STATSD_PREFIX: &str = "lab";
+const DEFAULT_REDIS_CHANNEL_PREFIX: &str = "lab:events";
+/// Standard proxy variables read when `LAB_PROXY_URL` is unset; every venue
+/// is reached over TLS, so the HTTPS ones win.
+const PROXY_ENV_FALLBACKS: [&str; 4] = ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"];
+/// Venues `LAB_VENUE_PROXIES` can route separately.
+const PROXY_VENUES: [&str; 8] = [
+    "coinbase",
+    "binance",
+    "kraken",
+    "okx",
+    "bybit",
+    "bitstamp",
+    "polymarket",
+    "kalshi",
+];
+const DEFAULT_MARKET_HORIZON_MINS: u64 = 15;
+const MAX_MARKET_HORIZON_MINS: u64 = 1_440;
+
+/// Reads one setting by env var name.
+type Lookup<'a> = &'a dyn Fn(&str) -> Result<String, env::VarError>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RunMode {
     PaperLive,
     Sim,
+    /// Paper trading on recorded ticks from `LAB_REPLAY_INPUT_DIR`.
+    Replay,
 }
 
 impl RunMode {
@@ -23,6 +108,7 @@ impl RunMode {
         match value {
             "paper-live" => Some(Self::PaperLive),
             "sim" => Some(Self::Sim),
+            "replay" => Some(Self::Replay),
             _ => None,
         }
     }
@@ -31,6 +117,7 @@ impl RunMode {
         match self {
             Self::PaperLive => "paper-live",
             Self::Sim => "sim",
+            Self::Replay => "replay",
         }
     }
 }
@@ -38,6 +125,8 @@ impl RunMode {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExecutionMode {
     Paper,
+    /// Signs venue orders without sending them.
+    DryRun,
     Live,
 }
 
@@ -45,15 +134,116 @@ impl ExecutionMode {
     fn parse(value: &str) -> Option<Self> {
         match value {
             "paper" => Some(Self::Paper),
+            "dry_run" => Some(Self::DryRun),
             "live" => Some(Self::Live),
             _ => None,
         }
     }
 }
 
+/// How venue BTC trades are combined into the composite price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BtcAggregatorMode {
+    Median,
+    Vwap,
+}
+
+impl BtcAggregatorMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "median" => Some(Self::Median),
+            "vwap" => Some(Self::Vwap),
+            _ => None,
+        }
+    }
+}
+
+/// Which outlier band the median aggregator applies to venue prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BtcOutlierMode {
+    /// Fixed basis-point band around the baseline median.
+    Fixed,
+    /// Band scaled by the median absolute deviation of fresh venue prices.
+    Mad,
+}
+
+impl BtcOutlierMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "fixed" => Some(Self::Fixed),
+            "mad" => Some(Self::Mad),
+            _ => None,
+        }
+    }
+}
+
+/// Certificate chain and private key, both PEM, for serving HTTPS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    pub cert_path: String,
+    pub key_path: String,
+    /// Plain HTTP listener that redirects every request to HTTPS.
+    pub redirect_addr: Option<SocketAddr>,
+}
+
+/// Outbound webhooks that receive risk alerts; off while `urls` is empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    pub urls: Vec<String>,
+    /// Key for the `X-Lab-Signature` HMAC; requests go unsigned without it.
+    pub secret: Option<String>,
+    /// Deliveries given up after this many failed attempts per URL.
+    pub max_attempts: u32,
+}
+
+/// Forward proxies for market data connections, HTTP polls and WebSocket
+/// streams alike.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// Proxy for venues without an override; `None` connects directly.
+    pub default: Option<HttpProxy>,
+    /// Per-venue overrides; a `None` entry connects that venue directly.
+    pub venues: HashMap<String, Option<HttpProxy>>,
+}
+
+impl ProxyConfig {
+    /// Proxy `venue` connects through, if any.
+    pub fn for_venue(&self, venue: &str) -> Option<&HttpProxy> {
+        match self.venues.get(venue) {
+            Some(proxy) => proxy.as_ref(),
+            None => self.default.as_ref(),
+        }
+    }
+}
+
+/// Settings for the BTC composite pipeline: aggregation, outlier filtering
+/// and smoothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BtcCompositeConfig {
+    pub aggregator: BtcAggregatorMode,
+    pub vwap_window_ms: u64,
+    pub ewma_half_life_ms: u64,
+    pub outlier_mode: BtcOutlierMode,
+}
+
+impl Default for BtcCompositeConfig {
+    fn default() -> Self {
+        Self {
+            aggregator: DEFAULT_BTC_AGGREGATOR,
+            vwap_window_ms: DEFAULT_BTC_VWAP_WINDOW_MS,
+            ewma_half_life_ms: DEFAULT_BTC_EWMA_HALF_LIFE_MS,
+            outlier_mode: DEFAULT_BTC_OUTLIER_MODE,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub listen_addr: SocketAddr,
+    /// Serves HTTPS on `listen_addr` when set.
+    pub tls: Option<TlsConfig>,
+    /// Serves the gRPC API here when set; needs the `grpc` build feature.
+    pub grpc_addr: Option<SocketAddr>,
     pub mode: RunMode,
     pub replay_output_path: String,
     pub execution_mode: ExecutionMode,
@@ -61,11 +251,82 @@ pub struct Config {
     pub lag_threshold_pct: f64,
     pub per_trade_risk_pct: f64,
     pub daily_loss_cap_pct: f64,
+    /// Trading days start at local midnight at this offset from UTC, in
+    /// minutes; the daily loss cap resets at each boundary.
+    pub trading_day_utc_offset_mins: i32,
+    /// Trading halts once equity falls this far below its session peak.
+    pub max_drawdown_pct: f64,
+    /// VaR, as a percentage of starting equity, at which order size shrinks
+    /// to nothing; `None` only reports VaR.
+    pub var_limit_pct: Option<f64>,
+    pub lag_cooldown_secs: u64,
+    pub lag_persistence_evals: u32,
+    /// Intents allowed through in any rolling minute; the rest are rejected
+    /// as throttled.
+    pub max_intents_per_min: u32,
+    /// Period of the paper-live decision loop; adjustable at runtime via
+    /// `PATCH /settings`.
+    pub decision_interval_ms: u64,
+    /// How often the prediction market venues are polled for quotes.
+    pub quote_poll_ms: u64,
+    /// How long a venue answering 403, 418 or 451 is left alone.
+    pub feed_block_cooldown_secs: u64,
+    /// Consecutive losing closes that start an entry cooldown.
+    pub loss_streak_limit: u32,
+    pub loss_streak_cooldown_secs: u64,
+    pub btc_composite: BtcCompositeConfig,
+    /// Assets whose spot composite and 15m markets are tracked; BTC is always
+    /// first.
+    pub assets: Vec<Asset>,
+    /// Keyword filters and settlement horizon deciding which listed markets
+    /// are tracked for each asset.
+    pub markets: MarketSelection,
+    /// Tick recording for offline research; off unless streams are listed.
+    pub recorder: TickRecorderConfig,
+    /// Directory of recorded ticks to replay instead of connecting to venues.
+    pub replay_input_dir: Option<String>,
+    /// Replay speed-up over the recorded inter-arrival times.
+    pub replay_speed: f64,
+    /// Recent trades fetched to warm strategies up on each newly quoted
+    /// Polymarket market; zero turns the backfill off.
+    pub backfill_trades: usize,
+    /// Realized volatility bounds for the automatic sizing regime.
+    pub regime_thresholds: RegimeThresholds,
+    /// Where learned predictor ensemble weights are kept across restarts.
+    pub predictor_weights_path: String,
+    /// Forecast endpoints polled for the fused fair value, built-in sources
+    /// first.
+    pub predictors: Vec<PredictorEndpoint>,
+    /// Largest net YES position, in shares, held in any one market.
+    pub max_market_position: f64,
+    /// Stop-loss and take-profit distances armed on every open position.
+    pub brackets: BracketConfig,
+    /// Intents are skipped while the YES book is wider than this.
+    pub max_spread: SpreadLimit,
+    /// YES shares that must rest at the touch before an intent is emitted.
+    pub min_touch_qty: f64,
+    pub webhooks: WebhookConfig,
+    /// Slack or Discord channel for formatted alerts; off when unset.
+    pub chat_alerts: Option<ChatAlertConfig>,
+    /// UDP statsd/DogStatsD agent for loop metrics; off when unset.
+    pub statsd: Option<StatsdConfig>,
+    /// Redis pub/sub mirror of the event bus; off when unset.
+    pub redis_mirror: Option<RedisMirrorConfig>,
+    /// Polymarket CLOB account that live orders go to; live mode has no
+    /// venue when unset.
+    pub clob: Option<ClobCredentials>,
+    pub proxies: ProxyConfig,
 }
 
 #[derive(Debug)]
 pub enum ConfigError {
     InvalidListenAddr(AddrParseError),
+    InvalidTlsCertPath,
+    InvalidTlsKeyPath,
+    IncompleteTlsConfig,
+    InvalidTlsRedirectAddr(AddrParseError),
+    RedirectWithoutTls,
+    InvalidGrpcAddr(AddrParseError),
     InvalidMode,
     InvalidReplayOutputPath,
     InvalidExecutionMode,
@@ -73,7 +334,65 @@ pub enum ConfigError {
     InvalidLagThresholdPct,
     InvalidPerTradeRiskPct,
     InvalidDailyLossCapPct,
+    /// Lag, per-trade risk and daily cap are each valid but inconsistent.
+    ConflictingSettings(SettingsConflict),
+    InvalidTradingDayUtcOffset,
+    InvalidMaxDrawdownPct,
+    InvalidVarLimitPct,
+    InvalidLagCooldownSecs,
+    InvalidLagPersistenceEvals,
+    InvalidMaxIntentsPerMin,
+    InvalidDecisionIntervalMs,
+    InvalidQuotePollMs,
+    InvalidFeedBlockCooldownSecs,
+    InvalidLossStreakLimit,
+    InvalidLossStreakCooldownSecs,
+    InvalidBtcAggregator,
+    InvalidBtcVwapWindowMs,
+    InvalidBtcEwmaHalfLifeMs,
+    InvalidBtcOutlierMode,
+    InvalidAssets,
+    InvalidMarketFilters(MarketFilterError),
+    InvalidMarketHorizonMins,
+    InvalidRecordDir,
+    InvalidRecordRotateMb,
+    InvalidRecordSources,
+    InvalidRecordFormat,
+    InvalidReplayInputDir,
+    MissingReplayInputDir,
+    InvalidReplaySpeed,
+    InvalidBackfillTrades,
+    InvalidRegimeCalmBps,
+    InvalidRegimeVolatileBps,
+    InvalidPredictorWeightsPath,
+    InvalidPredictorsFile(PredictorConfigError),
+    InvalidMaxMarketPosition,
+    InvalidBracketStop,
+    InvalidBracketTarget,
+    InvalidMaxSpread,
+    InvalidMinTouchQty,
+    InvalidWebhookUrls,
+    InvalidWebhookSecret,
+    InvalidWebhookMaxAttempts,
+    InvalidChatWebhookUrl,
+    InvalidChatAlerts,
+    InvalidChatLargeFillUsd,
+    InvalidChatMaxAlertsPerMin,
+    InvalidStatsdAddr,
+    InvalidStatsdPrefix,
+    InvalidStatsdTags,
+    InvalidRedisUrl,
+    InvalidRedisChannelPrefix,
+    InvalidClobPrivateKey,
+    IncompleteClobCredentials,
+    /// Names the variable the proxy URL came from.
+    InvalidProxyUrl(&'static str, HttpProxyError),
+    InvalidVenueProxies,
     NonUnicodeListenAddr,
+    NonUnicodeTlsCertPath,
+    NonUnicodeTlsKeyPath,
+    NonUnicodeTlsRedirectAddr,
+    NonUnicodeGrpcAddr,
     NonUnicodeMode,
     NonUnicodeReplayOutput,
     NonUnicodeExecutionMode,
@@ -81,6 +400,58 @@ pub enum ConfigError {
     NonUnicodeLagThresholdPct,
     NonUnicodePerTradeRiskPct,
     NonUnicodeDailyLossCapPct,
+    NonUnicodeTradingDayUtcOffset,
+    NonUnicodeMaxDrawdownPct,
+    NonUnicodeVarLimitPct,
+    NonUnicodeLagCooldownSecs,
+    NonUnicodeLagPersistenceEvals,
+    NonUnicodeMaxIntentsPerMin,
+    NonUnicodeDecisionIntervalMs,
+    NonUnicodeQuotePollMs,
+    NonUnicodeFeedBlockCooldownSecs,
+    NonUnicodeLossStreakLimit,
+    NonUnicodeLossStreakCooldownSecs,
+    NonUnicodeBtcAggregator,
+    NonUnicodeBtcVwapWindowMs,
+    NonUnicodeBtcEwmaHalfLifeMs,
+    NonUnicodeBtcOutlierMode,
+    NonUnicodeAssets,
+    NonUnicodeMarketFilters,
+    NonUnicodeMarketHorizonMins,
+    NonUnicodeRecordDir,
+    NonUnicodeRecordRotateMb,
+    NonUnicodeRecordSources,
+    NonUnicodeRecordFormat,
+    NonUnicodeReplayInputDir,
+    NonUnicodeReplaySpeed,
+    NonUnicodeBackfillTrades,
+    NonUnicodeRegimeCalmBps,
+    NonUnicodeRegimeVolatileBps,
+    NonUnicodePredictorWeightsPath,
+    NonUnicodePredictorsFile,
+    NonUnicodeMaxMarketPosition,
+    NonUnicodeBracketStop,
+    NonUnicodeBracketTarget,
+    NonUnicodeMaxSpread,
+    NonUnicodeMinTouchQty,
+    NonUnicodeWebhookUrls,
+    NonUnicodeWebhookSecret,
+    NonUnicodeWebhookMaxAttempts,
+    NonUnicodeChatWebhookUrl,
+    NonUnicodeChatAlerts,
+    NonUnicodeChatLargeFillUsd,
+    NonUnicodeChatMaxAlertsPerMin,
+    NonUnicodeStatsdAddr,
+    NonUnicodeStatsdPrefix,
+    NonUnicodeStatsdTags,
+    NonUnicodeRedisUrl,
+    NonUnicodeRedisChannelPrefix,
+    NonUnicodeClobPrivateKey,
+    NonUnicodeClobApiKey,
+    NonUnicodeClobApiSecret,
+    NonUnicodeClobApiPassphrase,
+    NonUnicodeProxyUrl(&'static str),
+    NonUnicodeVenueProxies,
 }
 
 impl fmt::Display for ConfigError {
@@ -89,8 +460,35 @@ impl fmt::Display for ConfigError {
             Self::InvalidListenAddr(err) => {
                 write!(f, "LAB_SERVER_ADDR is not a valid socket address: {err}")
             }
+            Self::InvalidTlsCertPath => {
+                write!(f, "LAB_TLS_CERT_PATH must not be empty or whitespace")
+            }
+            Self::InvalidTlsKeyPath => {
+                write!(f, "LAB_TLS_KEY_PATH must not be empty or whitespace")
+            }
+            Self::IncompleteTlsConfig => {
+                write!(
+                    f,
+                    "LAB_TLS_CERT_PATH and LAB_TLS_KEY_PATH must be set together"
+                )
+            }
+            Self::InvalidTlsRedirectAddr(err) => {
+                write!(
+                    f,
+                    "LAB_TLS_REDIRECT_ADDR is not a valid socket address: {err}"
+                )
+            }
+            Self::RedirectWithoutTls => {
+                write!(
+                    f,
+                    "LAB_TLS_REDIRECT_ADDR needs LAB_TLS_CERT_PATH and LAB_TLS_KEY_PATH"
+                )
+            }
+            Self::InvalidGrpcAddr(err) => {
+                write!(f, "LAB_GRPC_ADDR is not a valid socket address: {err}")
+            }
             Self::InvalidMode => {
-                write!(f, "LAB_SERVER_MODE must be one of: paper-live, sim")
+                write!(f, "LAB_SERVER_MODE must be one of: paper-live, sim, replay")
             }
             Self::InvalidReplayOutputPath => {
                 write!(
@@ -99,7 +497,7 @@ impl fmt::Display for ConfigError {
                 )
             }
             Self::InvalidExecutionMode => {
-                write!(f, "LAB_EXECUTION_MODE must be one of: paper, live")
+                write!(f, "LAB_EXECUTION_MODE must be one of: paper, dry_run, live")
             }
             Self::InvalidLiveFeatureEnabled => {
                 write!(f, "LAB_LIVE_FEATURE_ENABLED must be true or false")
@@ -122,9 +520,282 @@ impl fmt::Display for ConfigError {
                     "LAB_DAILY_LOSS_CAP_PCT must be a finite percentage between 0 and 100"
                 )
             }
+            Self::ConflictingSettings(err) => write!(f, "conflicting settings: {err}"),
+            Self::InvalidTradingDayUtcOffset => {
+                write!(
+                    f,
+                    "LAB_TRADING_DAY_UTC_OFFSET must be Z or +HH:MM/-HH:MM within 14 hours of UTC"
+                )
+            }
+            Self::InvalidMaxDrawdownPct => {
+                write!(
+                    f,
+                    "LAB_MAX_DRAWDOWN_PCT must be a finite percentage between 0 and 100"
+                )
+            }
+            Self::InvalidVarLimitPct => {
+                write!(
+                    f,
+                    "LAB_VAR_LIMIT_PCT must be a finite percentage between 0 and 100"
+                )
+            }
+            Self::InvalidLagCooldownSecs => {
+                write!(
+                    f,
+                    "LAB_LAG_COOLDOWN_SECS must be an integer between 0 and {MAX_LAG_COOLDOWN_SECS}"
+                )
+            }
+            Self::InvalidLagPersistenceEvals => {
+                write!(
+                    f,
+                    "LAB_LAG_PERSISTENCE_EVALS must be an integer between 1 and {MAX_LAG_PERSISTENCE_EVALS}"
+                )
+            }
+            Self::InvalidMaxIntentsPerMin => {
+                write!(
+                    f,
+                    "LAB_MAX_INTENTS_PER_MIN must be an integer between 1 and {MAX_MAX_INTENTS_PER_MIN}"
+                )
+            }
+            Self::InvalidDecisionIntervalMs => {
+                write!(
+                    f,
+                    "LAB_DECISION_INTERVAL_MS must be an integer between {MIN_DECISION_INTERVAL_MS} and {MAX_DECISION_INTERVAL_MS}"
+                )
+            }
+            Self::InvalidQuotePollMs => {
+                write!(
+                    f,
+                    "LAB_QUOTE_POLL_MS must be an integer between {MIN_QUOTE_POLL_MS} and {MAX_QUOTE_POLL_MS}"
+                )
+            }
+            Self::InvalidFeedBlockCooldownSecs => {
+                write!(
+                    f,
+                    "LAB_FEED_BLOCK_COOLDOWN_SECS must be an integer between {MIN_FEED_BLOCK_COOLDOWN_SECS} and {MAX_FEED_BLOCK_COOLDOWN_SECS}"
+                )
+            }
+            Self::InvalidLossStreakLimit => {
+                write!(
+                    f,
+                    "LAB_LOSS_STREAK_LIMIT must be an integer between 1 and {MAX_LOSS_STREAK_LIMIT}"
+                )
+            }
+            Self::InvalidLossStreakCooldownSecs => {
+                write!(
+                    f,
+                    "LAB_LOSS_STREAK_COOLDOWN_SECS must be an integer between 1 and {MAX_LOSS_STREAK_COOLDOWN_SECS}"
+                )
+            }
+            Self::InvalidBtcAggregator => {
+                write!(f, "LAB_BTC_AGGREGATOR must be one of: median, vwap")
+            }
+            Self::InvalidBtcVwapWindowMs => {
+                write!(
+                    f,
+                    "LAB_BTC_VWAP_WINDOW_MS must be an integer between 1 and {MAX_BTC_VWAP_WINDOW_MS}"
+                )
+            }
+            Self::InvalidBtcEwmaHalfLifeMs => {
+                write!(
+                    f,
+                    "LAB_BTC_EWMA_HALF_LIFE_MS must be an integer between 1 and {MAX_BTC_EWMA_HALF_LIFE_MS}"
+                )
+            }
+            Self::InvalidBtcOutlierMode => {
+                write!(f, "LAB_BTC_OUTLIER_MODE must be one of: fixed, mad")
+            }
+            Self::InvalidAssets => {
+                write!(
+                    f,
+                    "LAB_ASSETS must be a comma-separated list of: btc, eth, sol"
+                )
+            }
+            Self::InvalidMarketFilters(err) => {
+                write!(f, "LAB_MARKET_FILTERS is invalid: {err}")
+            }
+            Self::InvalidMarketHorizonMins => {
+                write!(
+                    f,
+                    "LAB_MARKET_HORIZON_MINS must be an integer between 1 and {MAX_MARKET_HORIZON_MINS}"
+                )
+            }
+            Self::InvalidRecordDir => {
+                write!(f, "LAB_RECORD_DIR must not be empty or whitespace")
+            }
+            Self::InvalidRecordRotateMb => {
+                write!(
+                    f,
+                    "LAB_RECORD_ROTATE_MB must be an integer between 1 and {MAX_RECORD_ROTATE_MB}"
+                )
+            }
+            Self::InvalidRecordSources => {
+                write!(
+                    f,
+                    "LAB_RECORD_SOURCES must be a comma-separated list of: spot, quotes, predictors"
+                )
+            }
+            Self::InvalidRecordFormat => {
+                write!(
+                    f,
+                    "LAB_RECORD_FORMAT must be csv, or parquet in a build with the parquet feature"
+                )
+            }
+            Self::InvalidReplayInputDir => {
+                write!(f, "LAB_REPLAY_INPUT_DIR must not be empty or whitespace")
+            }
+            Self::MissingReplayInputDir => {
+                write!(f, "LAB_SERVER_MODE=replay needs LAB_REPLAY_INPUT_DIR")
+            }
+            Self::InvalidReplaySpeed => {
+                write!(
+                    f,
+                    "LAB_REPLAY_SPEED must be a number above 0 and at most {MAX_REPLAY_SPEED}"
+                )
+            }
+            Self::InvalidBackfillTrades => {
+                write!(
+                    f,
+                    "LAB_BACKFILL_TRADES must be an integer between 0 and {MAX_BACKFILL_TRADES}"
+                )
+            }
+            Self::InvalidRegimeCalmBps => {
+                write!(f, "LAB_REGIME_CALM_BPS must be a number above 0")
+            }
+            Self::InvalidRegimeVolatileBps => {
+                write!(
+                    f,
+                    "LAB_REGIME_VOLATILE_BPS must be a number above LAB_REGIME_CALM_BPS"
+                )
+            }
+            Self::InvalidPredictorWeightsPath => {
+                write!(
+                    f,
+                    "LAB_PREDICTOR_WEIGHTS_PATH must not be empty or whitespace"
+                )
+            }
+            Self::InvalidPredictorsFile(err) => {
+                write!(f, "LAB_PREDICTORS_FILE is invalid: {err}")
+            }
+            Self::InvalidMaxMarketPosition => {
+                write!(f, "LAB_MAX_MARKET_POSITION must be a number above 0")
+            }
+            Self::InvalidBracketStop => {
+                write!(f, "LAB_BRACKET_STOP must be a number between 0 and 1")
+            }
+            Self::InvalidBracketTarget => {
+                write!(f, "LAB_BRACKET_TARGET must be a number between 0 and 1")
+            }
+            Self::InvalidMaxSpread => {
+                write!(
+                    f,
+                    "LAB_MAX_SPREAD must be a YES price width between 0 and 1, or a percentage of the mid such as 8%"
+                )
+            }
+            Self::InvalidMinTouchQty => {
+                write!(
+                    f,
+                    "LAB_MIN_TOUCH_QTY must be a number of shares of 0 or more"
+                )
+            }
+            Self::InvalidWebhookUrls => {
+                write!(
+                    f,
+                    "LAB_WEBHOOK_URLS must be a comma-separated list of http(s) URLs"
+                )
+            }
+            Self::InvalidWebhookSecret => {
+                write!(f, "LAB_WEBHOOK_SECRET must not be empty or whitespace")
+            }
+            Self::InvalidWebhookMaxAttempts => {
+                write!(
+                    f,
+                    "LAB_WEBHOOK_MAX_ATTEMPTS must be an integer between 1 and {MAX_WEBHOOK_MAX_ATTEMPTS}"
+                )
+            }
+            Self::InvalidChatWebhookUrl => {
+                write!(f, "LAB_CHAT_WEBHOOK_URL must be an http(s) URL")
+            }
+            Self::InvalidChatAlerts => {
+                write!(
+                    f,
+                    "LAB_CHAT_ALERTS must be a comma-separated list of: halts, fills, win_rate, stale_feeds, or all"
+                )
+            }
+            Self::InvalidChatLargeFillUsd => {
+                write!(f, "LAB_CHAT_LARGE_FILL_USD must be a number above 0")
+            }
+            Self::InvalidChatMaxAlertsPerMin => {
+                write!(
+                    f,
+                    "LAB_CHAT_MAX_ALERTS_PER_MIN must be an integer between 1 and {MAX_CHAT_MAX_ALERTS_PER_MIN}"
+                )
+            }
+            Self::InvalidStatsdAddr => {
+                write!(f, "LAB_STATSD_ADDR must be a host:port address")
+            }
+            Self::InvalidStatsdPrefix => {
+                write!(
+                    f,
+                    "LAB_STATSD_PREFIX must be letters, digits, `_`, `-` or inner `.`"
+                )
+            }
+            Self::InvalidStatsdTags => {
+                write!(
+                    f,
+                    "LAB_STATSD_TAGS must be a comma-separated list of key:value tags"
+                )
+            }
+            Self::InvalidRedisUrl => {
+                write!(
+                    f,
+                    "LAB_REDIS_URL must be a redis://[[user]:password@]host[:port] URL"
+                )
+            }
+            Self::InvalidRedisChannelPrefix => {
+                write!(
+                    f,
+                    "LAB_REDIS_CHANNEL_PREFIX must not be empty or contain whitespace"
+                )
+            }
+            Self::InvalidClobPrivateKey => {
+                write!(f, "LAB_CLOB_PRIVATE_KEY must be 32 hex-encoded bytes")
+            }
+            Self::IncompleteClobCredentials => {
+                write!(
+                    f,
+                    "LAB_CLOB_PRIVATE_KEY, LAB_CLOB_API_KEY, LAB_CLOB_API_SECRET and \
+                     LAB_CLOB_API_PASSPHRASE must be set together"
+                )
+            }
+            Self::InvalidProxyUrl(key, err) => {
+                write!(
+                    f,
+                    "{key} is not a usable proxy ({err}); set LAB_PROXY_URL=none to ignore it"
+                )
+            }
+            Self::InvalidVenueProxies => {
+                write!(
+                    f,
+                    "LAB_VENUE_PROXIES must be a comma-separated list of venue=http://host:port or venue=none, for venues: {}",
+                    PROXY_VENUES.join(", ")
+                )
+            }
             Self::NonUnicodeListenAddr => {
                 write!(f, "LAB_SERVER_ADDR contains non-unicode data")
             }
+            Self::NonUnicodeTlsCertPath => {
+                write!(f, "LAB_TLS_CERT_PATH contains non-unicode data")
+            }
+            Self::NonUnicodeTlsKeyPath => {
+                write!(f, "LAB_TLS_KEY_PATH contains non-unicode data")
+            }
+            Self::NonUnicodeTlsRedirectAddr => {
+                write!(f, "LAB_TLS_REDIRECT_ADDR contains non-unicode data")
+            }
+            Self::NonUnicodeGrpcAddr => {
+                write!(f, "LAB_GRPC_ADDR contains non-unicode data")
+            }
             Self::NonUnicodeMode => {
                 write!(f, "LAB_SERVER_MODE contains non-unicode data")
             }
@@ -146,54 +817,392 @@ impl fmt::Display for ConfigError {
             Self::NonUnicodeDailyLossCapPct => {
                 write!(f, "LAB_DAILY_LOSS_CAP_PCT contains non-unicode data")
             }
-        }
-    }
-}
-
-impl std::error::Error for ConfigError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
-            Self::InvalidListenAddr(err) => Some(err),
-            Self::InvalidMode => None,
-            Self::InvalidReplayOutputPath => None,
-            Self::InvalidExecutionMode => None,
-            Self::InvalidLiveFeatureEnabled => None,
-            Self::InvalidLagThresholdPct => None,
-            Self::InvalidPerTradeRiskPct => None,
-            Self::InvalidDailyLossCapPct => None,
-            Self::NonUnicodeListenAddr => None,
-            Self::NonUnicodeMode => None,
-            Self::NonUnicodeReplayOutput => None,
-            Self::NonUnicodeExecutionMode => None,
-            Self::NonUnicodeLiveFeatureEnabled => None,
-            Self::NonUnicodeLagThresholdPct => None,
-            Self::NonUnicodePerTradeRiskPct => None,
-            Self::NonUnicodeDailyLossCapPct => None,
-        }
-    }
-}
-
-impl Config {
-    pub fn from_env() -> Result<Self, ConfigError> {
-        let listen_addr = match env::var("LAB_SERVER_ADDR") {
-            Ok(value) => value.parse().map_err(ConfigError::InvalidListenAddr)?,
-            Err(env::VarError::NotPresent) => DEFAULT_LISTEN_ADDR
-                .parse()
-                .expect("default listen address must be valid"),
-            Err(env::VarError::NotUnicode(_)) => {
-                return Err(ConfigError::NonUnicodeListenAddr);
+            Self::NonUnicodeTradingDayUtcOffset => {
+                write!(f, "LAB_TRADING_DAY_UTC_OFFSET contains non-unicode data")
             }
-        };
-
-        let mode = match env::var("LAB_SERVER_MODE") {
-            Ok(value) => RunMode::parse(value.as_str()).ok_or(ConfigError::InvalidMode)?,
-            Err(env::VarError::NotPresent) => DEFAULT_MODE,
-            Err(env::VarError::NotUnicode(_)) => {
-                return Err(ConfigError::NonUnicodeMode);
+            Self::NonUnicodeMaxDrawdownPct => {
+                write!(f, "LAB_MAX_DRAWDOWN_PCT contains non-unicode data")
+            }
+            Self::NonUnicodeVarLimitPct => {
+                write!(f, "LAB_VAR_LIMIT_PCT contains non-unicode data")
+            }
+            Self::NonUnicodeLagCooldownSecs => {
+                write!(f, "LAB_LAG_COOLDOWN_SECS contains non-unicode data")
+            }
+            Self::NonUnicodeLagPersistenceEvals => {
+                write!(f, "LAB_LAG_PERSISTENCE_EVALS contains non-unicode data")
+            }
+            Self::NonUnicodeMaxIntentsPerMin => {
+                write!(f, "LAB_MAX_INTENTS_PER_MIN contains non-unicode data")
+            }
+            Self::NonUnicodeDecisionIntervalMs => {
+                write!(f, "LAB_DECISION_INTERVAL_MS contains non-unicode data")
+            }
+            Self::NonUnicodeQuotePollMs => {
+                write!(f, "LAB_QUOTE_POLL_MS contains non-unicode data")
+            }
+            Self::NonUnicodeFeedBlockCooldownSecs => {
+                write!(f, "LAB_FEED_BLOCK_COOLDOWN_SECS contains non-unicode data")
+            }
+            Self::NonUnicodeLossStreakLimit => {
+                write!(f, "LAB_LOSS_STREAK_LIMIT contains non-unicode data")
+            }
+            Self::NonUnicodeLossStreakCooldownSecs => {
+                write!(f, "LAB_LOSS_STREAK_COOLDOWN_SECS contains non-unicode data")
+            }
+            Self::NonUnicodeBtcAggregator => {
+                write!(f, "LAB_BTC_AGGREGATOR contains non-unicode data")
+            }
+            Self::NonUnicodeBtcVwapWindowMs => {
+                write!(f, "LAB_BTC_VWAP_WINDOW_MS contains non-unicode data")
+            }
+            Self::NonUnicodeBtcEwmaHalfLifeMs => {
+                write!(f, "LAB_BTC_EWMA_HALF_LIFE_MS contains non-unicode data")
+            }
+            Self::NonUnicodeBtcOutlierMode => {
+                write!(f, "LAB_BTC_OUTLIER_MODE contains non-unicode data")
+            }
+            Self::NonUnicodeMarketFilters => {
+                write!(f, "LAB_MARKET_FILTERS contains non-unicode data")
+            }
+            Self::NonUnicodeMarketHorizonMins => {
+                write!(f, "LAB_MARKET_HORIZON_MINS contains non-unicode data")
+            }
+            Self::NonUnicodeAssets => {
+                write!(f, "LAB_ASSETS contains non-unicode data")
+            }
+            Self::NonUnicodeRecordDir => {
+                write!(f, "LAB_RECORD_DIR contains non-unicode data")
+            }
+            Self::NonUnicodeRecordRotateMb => {
+                write!(f, "LAB_RECORD_ROTATE_MB contains non-unicode data")
+            }
+            Self::NonUnicodeRecordSources => {
+                write!(f, "LAB_RECORD_SOURCES contains non-unicode data")
+            }
+            Self::NonUnicodeRecordFormat => {
+                write!(f, "LAB_RECORD_FORMAT contains non-unicode data")
+            }
+            Self::NonUnicodeReplayInputDir => {
+                write!(f, "LAB_REPLAY_INPUT_DIR contains non-unicode data")
+            }
+            Self::NonUnicodeReplaySpeed => {
+                write!(f, "LAB_REPLAY_SPEED contains non-unicode data")
+            }
+            Self::NonUnicodeBackfillTrades => {
+                write!(f, "LAB_BACKFILL_TRADES contains non-unicode data")
+            }
+            Self::NonUnicodeRegimeCalmBps => {
+                write!(f, "LAB_REGIME_CALM_BPS contains non-unicode data")
+            }
+            Self::NonUnicodeRegimeVolatileBps => {
+                write!(f, "LAB_REGIME_VOLATILE_BPS contains non-unicode data")
+            }
+            Self::NonUnicodePredictorWeightsPath => {
+                write!(f, "LAB_PREDICTOR_WEIGHTS_PATH contains non-unicode data")
+            }
+            Self::NonUnicodePredictorsFile => {
+                write!(f, "LAB_PREDICTORS_FILE contains non-unicode data")
+            }
+            Self::NonUnicodeMaxMarketPosition => {
+                write!(f, "LAB_MAX_MARKET_POSITION contains non-unicode data")
+            }
+            Self::NonUnicodeBracketStop => {
+                write!(f, "LAB_BRACKET_STOP contains non-unicode data")
+            }
+            Self::NonUnicodeBracketTarget => {
+                write!(f, "LAB_BRACKET_TARGET contains non-unicode data")
+            }
+            Self::NonUnicodeMaxSpread => {
+                write!(f, "LAB_MAX_SPREAD contains non-unicode data")
+            }
+            Self::NonUnicodeMinTouchQty => {
+                write!(f, "LAB_MIN_TOUCH_QTY contains non-unicode data")
+            }
+            Self::NonUnicodeWebhookUrls => {
+                write!(f, "LAB_WEBHOOK_URLS contains non-unicode data")
+            }
+            Self::NonUnicodeWebhookSecret => {
+                write!(f, "LAB_WEBHOOK_SECRET contains non-unicode data")
+            }
+            Self::NonUnicodeWebhookMaxAttempts => {
+                write!(f, "LAB_WEBHOOK_MAX_ATTEMPTS contains non-unicode data")
+            }
+            Self::NonUnicodeChatWebhookUrl => {
+                write!(f, "LAB_CHAT_WEBHOOK_URL contains non-unicode data")
+            }
+            Self::NonUnicodeChatAlerts => {
+                write!(f, "LAB_CHAT_ALERTS contains non-unicode data")
+            }
+            Self::NonUnicodeChatLargeFillUsd => {
+                write!(f, "LAB_CHAT_LARGE_FILL_USD contains non-unicode data")
+            }
+            Self::NonUnicodeChatMaxAlertsPerMin => {
+                write!(f, "LAB_CHAT_MAX_ALERTS_PER_MIN contains non-unicode data")
+            }
+            Self::NonUnicodeStatsdAddr => {
+                write!(f, "LAB_STATSD_ADDR contains non-unicode data")
+            }
+            Self::NonUnicodeStatsdPrefix => {
+                write!(f, "LAB_STATSD_PREFIX contains non-unicode data")
+            }
+            Self::NonUnicodeStatsdTags => {
+                write!(f, "LAB_STATSD_TAGS contains non-unicode data")
+            }
+            Self::NonUnicodeRedisUrl => {
+                write!(f, "LAB_REDIS_URL contains non-unicode data")
+            }
+            Self::NonUnicodeRedisChannelPrefix => {
+                write!(f, "LAB_REDIS_CHANNEL_PREFIX contains non-unicode data")
+            }
+            Self::NonUnicodeClobPrivateKey => {
+                write!(f, "LAB_CLOB_PRIVATE_KEY contains non-unicode data")
+            }
+            Self::NonUnicodeClobApiKey => {
+                write!(f, "LAB_CLOB_API_KEY contains non-unicode data")
+            }
+            Self::NonUnicodeClobApiSecret => {
+                write!(f, "LAB_CLOB_API_SECRET contains non-unicode data")
+            }
+            Self::NonUnicodeClobApiPassphrase => {
+                write!(f, "LAB_CLOB_API_PASSPHRASE contains non-unicode data")
+            }
+            Self::NonUnicodeProxyUrl(key) => {
+                write!(f, "{key} contains non-unicode data")
+            }
+            Self::NonUnicodeVenueProxies => {
+                write!(f, "LAB_VENUE_PROXIES contains non-unicode data")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidListenAddr(err) => Some(err),
+            Self::InvalidTlsCertPath => None,
+            Self::InvalidTlsKeyPath => None,
+            Self::IncompleteTlsConfig => None,
+            Self::InvalidTlsRedirectAddr(err) => Some(err),
+            Self::RedirectWithoutTls => None,
+            Self::InvalidGrpcAddr(err) => Some(err),
+            Self::InvalidMode => None,
+            Self::InvalidReplayOutputPath => None,
+            Self::InvalidExecutionMode => None,
+            Self::InvalidLiveFeatureEnabled => None,
+            Self::InvalidLagThresholdPct => None,
+            Self::InvalidPerTradeRiskPct => None,
+            Self::InvalidDailyLossCapPct => None,
+            Self::ConflictingSettings(err) => Some(err),
+            Self::InvalidTradingDayUtcOffset => None,
+            Self::InvalidMaxDrawdownPct => None,
+            Self::InvalidVarLimitPct => None,
+            Self::InvalidLagCooldownSecs => None,
+            Self::InvalidLagPersistenceEvals => None,
+            Self::InvalidMaxIntentsPerMin => None,
+            Self::InvalidDecisionIntervalMs => None,
+            Self::InvalidQuotePollMs => None,
+            Self::InvalidFeedBlockCooldownSecs => None,
+            Self::InvalidLossStreakLimit => None,
+            Self::InvalidLossStreakCooldownSecs => None,
+            Self::InvalidBtcAggregator => None,
+            Self::InvalidBtcVwapWindowMs => None,
+            Self::InvalidBtcEwmaHalfLifeMs => None,
+            Self::InvalidBtcOutlierMode => None,
+            Self::InvalidAssets => None,
+            Self::InvalidMarketFilters(err) => Some(err),
+            Self::InvalidMarketHorizonMins => None,
+            Self::InvalidRecordDir => None,
+            Self::InvalidRecordRotateMb => None,
+            Self::InvalidRecordSources => None,
+            Self::InvalidRecordFormat => None,
+            Self::InvalidReplayInputDir => None,
+            Self::MissingReplayInputDir => None,
+            Self::InvalidReplaySpeed => None,
+            Self::InvalidBackfillTrades => None,
+            Self::InvalidRegimeCalmBps => None,
+            Self::InvalidRegimeVolatileBps => None,
+            Self::InvalidPredictorWeightsPath => None,
+            Self::InvalidPredictorsFile(err) => Some(err),
+            Self::InvalidMaxMarketPosition => None,
+            Self::InvalidBracketStop => None,
+            Self::InvalidBracketTarget => None,
+            Self::InvalidMaxSpread => None,
+            Self::InvalidMinTouchQty => None,
+            Self::InvalidWebhookUrls => None,
+            Self::InvalidWebhookSecret => None,
+            Self::InvalidWebhookMaxAttempts => None,
+            Self::InvalidChatWebhookUrl => None,
+            Self::InvalidChatAlerts => None,
+            Self::InvalidChatLargeFillUsd => None,
+            Self::InvalidChatMaxAlertsPerMin => None,
+            Self::InvalidStatsdAddr => None,
+            Self::InvalidStatsdPrefix => None,
+            Self::InvalidStatsdTags => None,
+            Self::InvalidRedisUrl => None,
+            Self::InvalidRedisChannelPrefix => None,
+            Self::InvalidClobPrivateKey => None,
+            Self::IncompleteClobCredentials => None,
+            Self::InvalidProxyUrl(_, err) => Some(err),
+            Self::InvalidVenueProxies => None,
+            Self::NonUnicodeListenAddr => None,
+            Self::NonUnicodeTlsCertPath => None,
+            Self::NonUnicodeTlsKeyPath => None,
+            Self::NonUnicodeTlsRedirectAddr => None,
+            Self::NonUnicodeGrpcAddr => None,
+            Self::NonUnicodeMode => None,
+            Self::NonUnicodeReplayOutput => None,
+            Self::NonUnicodeExecutionMode => None,
+            Self::NonUnicodeLiveFeatureEnabled => None,
+            Self::NonUnicodeLagThresholdPct => None,
+            Self::NonUnicodePerTradeRiskPct => None,
+            Self::NonUnicodeDailyLossCapPct => None,
+            Self::NonUnicodeTradingDayUtcOffset => None,
+            Self::NonUnicodeMaxDrawdownPct => None,
+            Self::NonUnicodeVarLimitPct => None,
+            Self::NonUnicodeLagCooldownSecs => None,
+            Self::NonUnicodeLagPersistenceEvals => None,
+            Self::NonUnicodeMaxIntentsPerMin => None,
+            Self::NonUnicodeDecisionIntervalMs => None,
+            Self::NonUnicodeQuotePollMs => None,
+            Self::NonUnicodeFeedBlockCooldownSecs => None,
+            Self::NonUnicodeLossStreakLimit => None,
+            Self::NonUnicodeLossStreakCooldownSecs => None,
+            Self::NonUnicodeBtcAggregator => None,
+            Self::NonUnicodeBtcVwapWindowMs => None,
+            Self::NonUnicodeBtcEwmaHalfLifeMs => None,
+            Self::NonUnicodeBtcOutlierMode => None,
+            Self::NonUnicodeAssets => None,
+            Self::NonUnicodeMarketFilters => None,
+            Self::NonUnicodeMarketHorizonMins => None,
+            Self::NonUnicodeRecordDir => None,
+            Self::NonUnicodeRecordRotateMb => None,
+            Self::NonUnicodeRecordSources => None,
+            Self::NonUnicodeRecordFormat => None,
+            Self::NonUnicodeReplayInputDir => None,
+            Self::NonUnicodeReplaySpeed => None,
+            Self::NonUnicodeBackfillTrades => None,
+            Self::NonUnicodeRegimeCalmBps => None,
+            Self::NonUnicodeRegimeVolatileBps => None,
+            Self::NonUnicodePredictorWeightsPath => None,
+            Self::NonUnicodePredictorsFile => None,
+            Self::NonUnicodeMaxMarketPosition => None,
+            Self::NonUnicodeBracketStop => None,
+            Self::NonUnicodeBracketTarget => None,
+            Self::NonUnicodeMaxSpread => None,
+            Self::NonUnicodeMinTouchQty => None,
+            Self::NonUnicodeWebhookUrls => None,
+            Self::NonUnicodeWebhookSecret => None,
+            Self::NonUnicodeWebhookMaxAttempts => None,
+            Self::NonUnicodeChatWebhookUrl => None,
+            Self::NonUnicodeChatAlerts => None,
+            Self::NonUnicodeChatLargeFillUsd => None,
+            Self::NonUnicodeChatMaxAlertsPerMin => None,
+            Self::NonUnicodeStatsdAddr => None,
+            Self::NonUnicodeStatsdPrefix => None,
+            Self::NonUnicodeStatsdTags => None,
+            Self::NonUnicodeRedisUrl => None,
+            Self::NonUnicodeRedisChannelPrefix => None,
+            Self::NonUnicodeClobPrivateKey => None,
+            Self::NonUnicodeClobApiKey => None,
+            Self::NonUnicodeClobApiSecret => None,
+            Self::NonUnicodeClobApiPassphrase => None,
+            Self::NonUnicodeProxyUrl(_) => None,
+            Self::NonUnicodeVenueProxies => None,
+        }
+    }
+}
+
+impl Config {
+    #[cfg(test)]
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_env_with_overrides(&HashMap::new())
+    }
+
+    /// Like [`Config::from_env`], but `overrides` (keyed by env var name)
+    /// win over the environment; the CLI passes its flags this way.
+    pub fn from_env_with_overrides(
+        overrides: &HashMap<&'static str, String>,
+    ) -> Result<Self, ConfigError> {
+        let lookup = |key: &str| match overrides.get(key) {
+            Some(value) => Ok(value.clone()),
+            None => env::var(key),
+        };
+        Self::from_lookup(&lookup)
+    }
+
+    fn from_lookup(lookup: Lookup<'_>) -> Result<Self, ConfigError> {
+        let listen_addr = match lookup("LAB_SERVER_ADDR") {
+            Ok(value) => value.parse().map_err(ConfigError::InvalidListenAddr)?,
+            Err(env::VarError::NotPresent) => DEFAULT_LISTEN_ADDR
+                .parse()
+                .expect("default listen address must be valid"),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeListenAddr);
+            }
+        };
+
+        let tls_cert_path = match lookup("LAB_TLS_CERT_PATH") {
+            Ok(value) => {
+                if value.trim().is_empty() {
+                    return Err(ConfigError::InvalidTlsCertPath);
+                }
+                Some(value)
+            }
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeTlsCertPath);
+            }
+        };
+        let tls_key_path = match lookup("LAB_TLS_KEY_PATH") {
+            Ok(value) => {
+                if value.trim().is_empty() {
+                    return Err(ConfigError::InvalidTlsKeyPath);
+                }
+                Some(value)
+            }
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeTlsKeyPath);
+            }
+        };
+        let tls_redirect_addr = match lookup("LAB_TLS_REDIRECT_ADDR") {
+            Ok(value) => Some(value.parse().map_err(ConfigError::InvalidTlsRedirectAddr)?),
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeTlsRedirectAddr);
+            }
+        };
+        let tls = match (tls_cert_path, tls_key_path) {
+            (Some(cert_path), Some(key_path)) => Some(TlsConfig {
+                cert_path,
+                key_path,
+                redirect_addr: tls_redirect_addr,
+            }),
+            (None, None) if tls_redirect_addr.is_some() => {
+                return Err(ConfigError::RedirectWithoutTls);
+            }
+            (None, None) => None,
+            _ => return Err(ConfigError::IncompleteTlsConfig),
+        };
+        let grpc_addr = match lookup("LAB_GRPC_ADDR") {
+            Ok(value) => Some(value.parse().map_err(ConfigError::InvalidGrpcAddr)?),
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeGrpcAddr);
+            }
+        };
+
+        let mode = match lookup("LAB_SERVER_MODE") {
+            Ok(value) => RunMode::parse(value.as_str()).ok_or(ConfigError::InvalidMode)?,
+            Err(env::VarError::NotPresent) => DEFAULT_MODE,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeMode);
             }
         };
 
-        let replay_output_path = match env::var("LAB_SERVER_REPLAY_OUTPUT") {
+        let replay_output_path = match lookup("LAB_SERVER_REPLAY_OUTPUT") {
             Ok(value) => {
                 if value.trim().is_empty() {
                     return Err(ConfigError::InvalidReplayOutputPath);
@@ -206,7 +1215,7 @@ impl Config {
             }
         };
 
-        let execution_mode = match env::var("LAB_EXECUTION_MODE") {
+        let execution_mode = match lookup("LAB_EXECUTION_MODE") {
             Ok(value) => {
                 ExecutionMode::parse(value.as_str()).ok_or(ConfigError::InvalidExecutionMode)?
             }
@@ -216,7 +1225,7 @@ impl Config {
             }
         };
 
-        let live_feature_enabled = match env::var("LAB_LIVE_FEATURE_ENABLED") {
+        let live_feature_enabled = match lookup("LAB_LIVE_FEATURE_ENABLED") {
             Ok(value) => {
                 parse_bool(value.as_str()).ok_or(ConfigError::InvalidLiveFeatureEnabled)?
             }
@@ -224,200 +1233,1996 @@ impl Config {
             Err(env::VarError::NotUnicode(_)) => {
                 return Err(ConfigError::NonUnicodeLiveFeatureEnabled);
             }
-        };
+        };
+
+        let lag_threshold_pct = parse_percentage_env(
+            lookup,
+            "LAB_LAG_THRESHOLD_PCT",
+            DEFAULT_LAG_THRESHOLD_PCT,
+            ConfigError::InvalidLagThresholdPct,
+            ConfigError::NonUnicodeLagThresholdPct,
+        )?;
+
+        let per_trade_risk_pct = parse_percentage_env(
+            lookup,
+            "LAB_RISK_PER_TRADE_PCT",
+            DEFAULT_PER_TRADE_RISK_PCT,
+            ConfigError::InvalidPerTradeRiskPct,
+            ConfigError::NonUnicodePerTradeRiskPct,
+        )?;
+
+        let daily_loss_cap_pct = parse_percentage_env(
+            lookup,
+            "LAB_DAILY_LOSS_CAP_PCT",
+            DEFAULT_DAILY_LOSS_CAP_PCT,
+            ConfigError::InvalidDailyLossCapPct,
+            ConfigError::NonUnicodeDailyLossCapPct,
+        )?;
+        check_settings_invariants(lag_threshold_pct, per_trade_risk_pct, daily_loss_cap_pct)
+            .map_err(ConfigError::ConflictingSettings)?;
+
+        let trading_day_utc_offset_mins = match lookup("LAB_TRADING_DAY_UTC_OFFSET") {
+            Ok(value) => parse_utc_offset(&value).ok_or(ConfigError::InvalidTradingDayUtcOffset)?,
+            Err(env::VarError::NotPresent) => 0,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeTradingDayUtcOffset);
+            }
+        };
+
+        let max_drawdown_pct = parse_percentage_env(
+            lookup,
+            "LAB_MAX_DRAWDOWN_PCT",
+            DEFAULT_MAX_DRAWDOWN_PCT,
+            ConfigError::InvalidMaxDrawdownPct,
+            ConfigError::NonUnicodeMaxDrawdownPct,
+        )?;
+
+        let var_limit_pct = match lookup("LAB_VAR_LIMIT_PCT") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed.is_finite() && parsed > 0.0 && parsed <= 100.0 => Some(parsed),
+                _ => return Err(ConfigError::InvalidVarLimitPct),
+            },
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeVarLimitPct);
+            }
+        };
+
+        let lag_cooldown_secs = parse_bounded_u64_env(
+            lookup,
+            "LAB_LAG_COOLDOWN_SECS",
+            DEFAULT_LAG_COOLDOWN_SECS,
+            0..=MAX_LAG_COOLDOWN_SECS,
+            ConfigError::InvalidLagCooldownSecs,
+            ConfigError::NonUnicodeLagCooldownSecs,
+        )?;
+
+        let lag_persistence_evals = parse_bounded_u64_env(
+            lookup,
+            "LAB_LAG_PERSISTENCE_EVALS",
+            u64::from(DEFAULT_LAG_PERSISTENCE_EVALS),
+            1..=u64::from(MAX_LAG_PERSISTENCE_EVALS),
+            ConfigError::InvalidLagPersistenceEvals,
+            ConfigError::NonUnicodeLagPersistenceEvals,
+        )? as u32;
+
+        let max_intents_per_min = parse_bounded_u64_env(
+            lookup,
+            "LAB_MAX_INTENTS_PER_MIN",
+            u64::from(DEFAULT_MAX_INTENTS_PER_MIN),
+            1..=u64::from(MAX_MAX_INTENTS_PER_MIN),
+            ConfigError::InvalidMaxIntentsPerMin,
+            ConfigError::NonUnicodeMaxIntentsPerMin,
+        )? as u32;
+
+        let decision_interval_ms = parse_bounded_u64_env(
+            lookup,
+            "LAB_DECISION_INTERVAL_MS",
+            DEFAULT_DECISION_INTERVAL_MS,
+            MIN_DECISION_INTERVAL_MS..=MAX_DECISION_INTERVAL_MS,
+            ConfigError::InvalidDecisionIntervalMs,
+            ConfigError::NonUnicodeDecisionIntervalMs,
+        )?;
+
+        let quote_poll_ms = parse_bounded_u64_env(
+            lookup,
+            "LAB_QUOTE_POLL_MS",
+            DEFAULT_QUOTE_POLL_MS,
+            MIN_QUOTE_POLL_MS..=MAX_QUOTE_POLL_MS,
+            ConfigError::InvalidQuotePollMs,
+            ConfigError::NonUnicodeQuotePollMs,
+        )?;
+        let feed_block_cooldown_secs = parse_bounded_u64_env(
+            lookup,
+            "LAB_FEED_BLOCK_COOLDOWN_SECS",
+            DEFAULT_FEED_BLOCK_COOLDOWN_SECS,
+            MIN_FEED_BLOCK_COOLDOWN_SECS..=MAX_FEED_BLOCK_COOLDOWN_SECS,
+            ConfigError::InvalidFeedBlockCooldownSecs,
+            ConfigError::NonUnicodeFeedBlockCooldownSecs,
+        )?;
+
+        let loss_streak_limit = parse_bounded_u64_env(
+            lookup,
+            "LAB_LOSS_STREAK_LIMIT",
+            u64::from(DEFAULT_LOSS_STREAK_LIMIT),
+            1..=u64::from(MAX_LOSS_STREAK_LIMIT),
+            ConfigError::InvalidLossStreakLimit,
+            ConfigError::NonUnicodeLossStreakLimit,
+        )? as u32;
+
+        let loss_streak_cooldown_secs = parse_bounded_u64_env(
+            lookup,
+            "LAB_LOSS_STREAK_COOLDOWN_SECS",
+            DEFAULT_LOSS_STREAK_COOLDOWN_SECS,
+            1..=MAX_LOSS_STREAK_COOLDOWN_SECS,
+            ConfigError::InvalidLossStreakCooldownSecs,
+            ConfigError::NonUnicodeLossStreakCooldownSecs,
+        )?;
+
+        let btc_aggregator = match lookup("LAB_BTC_AGGREGATOR") {
+            Ok(value) => {
+                BtcAggregatorMode::parse(value.as_str()).ok_or(ConfigError::InvalidBtcAggregator)?
+            }
+            Err(env::VarError::NotPresent) => DEFAULT_BTC_AGGREGATOR,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeBtcAggregator);
+            }
+        };
+
+        let btc_vwap_window_ms = parse_bounded_u64_env(
+            lookup,
+            "LAB_BTC_VWAP_WINDOW_MS",
+            DEFAULT_BTC_VWAP_WINDOW_MS,
+            1..=MAX_BTC_VWAP_WINDOW_MS,
+            ConfigError::InvalidBtcVwapWindowMs,
+            ConfigError::NonUnicodeBtcVwapWindowMs,
+        )?;
+
+        let btc_ewma_half_life_ms = parse_bounded_u64_env(
+            lookup,
+            "LAB_BTC_EWMA_HALF_LIFE_MS",
+            DEFAULT_BTC_EWMA_HALF_LIFE_MS,
+            1..=MAX_BTC_EWMA_HALF_LIFE_MS,
+            ConfigError::InvalidBtcEwmaHalfLifeMs,
+            ConfigError::NonUnicodeBtcEwmaHalfLifeMs,
+        )?;
+
+        let btc_outlier_mode = match lookup("LAB_BTC_OUTLIER_MODE") {
+            Ok(value) => {
+                BtcOutlierMode::parse(value.as_str()).ok_or(ConfigError::InvalidBtcOutlierMode)?
+            }
+            Err(env::VarError::NotPresent) => DEFAULT_BTC_OUTLIER_MODE,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeBtcOutlierMode);
+            }
+        };
+
+        let assets = match lookup("LAB_ASSETS") {
+            Ok(value) => parse_assets(&value).ok_or(ConfigError::InvalidAssets)?,
+            Err(env::VarError::NotPresent) => vec![Asset::Btc],
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeAssets);
+            }
+        };
+        let market_horizon_mins = parse_bounded_u64_env(
+            lookup,
+            "LAB_MARKET_HORIZON_MINS",
+            DEFAULT_MARKET_HORIZON_MINS,
+            1..=MAX_MARKET_HORIZON_MINS,
+            ConfigError::InvalidMarketHorizonMins,
+            ConfigError::NonUnicodeMarketHorizonMins,
+        )?;
+        let markets = MarketSelection::new(market_horizon_mins * 60 * 1_000);
+        let markets = match lookup("LAB_MARKET_FILTERS") {
+            Ok(value) => markets
+                .with_filters(&value)
+                .map_err(ConfigError::InvalidMarketFilters)?,
+            Err(env::VarError::NotPresent) => markets,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeMarketFilters);
+            }
+        };
+
+        let record_dir = match lookup("LAB_RECORD_DIR") {
+            Ok(value) => {
+                if value.trim().is_empty() {
+                    return Err(ConfigError::InvalidRecordDir);
+                }
+                value
+            }
+            Err(env::VarError::NotPresent) => DEFAULT_RECORD_DIR.to_owned(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeRecordDir);
+            }
+        };
+
+        let record_rotate_mb = parse_bounded_u64_env(
+            lookup,
+            "LAB_RECORD_ROTATE_MB",
+            DEFAULT_RECORD_ROTATE_MB,
+            1..=MAX_RECORD_ROTATE_MB,
+            ConfigError::InvalidRecordRotateMb,
+            ConfigError::NonUnicodeRecordRotateMb,
+        )?;
+
+        let record_streams = match lookup("LAB_RECORD_SOURCES") {
+            Ok(value) => parse_record_streams(&value).ok_or(ConfigError::InvalidRecordSources)?,
+            Err(env::VarError::NotPresent) => Vec::new(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeRecordSources);
+            }
+        };
+
+        let record_format = match lookup("LAB_RECORD_FORMAT") {
+            Ok(value) => RecordFormat::parse(&value)
+                .filter(|format| format.is_available())
+                .ok_or(ConfigError::InvalidRecordFormat)?,
+            Err(env::VarError::NotPresent) => RecordFormat::Csv,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeRecordFormat);
+            }
+        };
+
+        let replay_input_dir = match lookup("LAB_REPLAY_INPUT_DIR") {
+            Ok(value) => {
+                if value.trim().is_empty() {
+                    return Err(ConfigError::InvalidReplayInputDir);
+                }
+                Some(value)
+            }
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeReplayInputDir);
+            }
+        };
+        if mode == RunMode::Replay && replay_input_dir.is_none() {
+            return Err(ConfigError::MissingReplayInputDir);
+        }
+
+        let replay_speed = match lookup("LAB_REPLAY_SPEED") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed > 0.0 && parsed <= MAX_REPLAY_SPEED => parsed,
+                _ => return Err(ConfigError::InvalidReplaySpeed),
+            },
+            Err(env::VarError::NotPresent) => DEFAULT_REPLAY_SPEED,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeReplaySpeed);
+            }
+        };
+
+        let backfill_trades = parse_bounded_u64_env(
+            lookup,
+            "LAB_BACKFILL_TRADES",
+            DEFAULT_BACKFILL_TRADES,
+            0..=MAX_BACKFILL_TRADES,
+            ConfigError::InvalidBackfillTrades,
+            ConfigError::NonUnicodeBackfillTrades,
+        )? as usize;
+
+        let regime_calm_bps = match lookup("LAB_REGIME_CALM_BPS") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed.is_finite() && parsed > 0.0 => parsed,
+                _ => return Err(ConfigError::InvalidRegimeCalmBps),
+            },
+            Err(env::VarError::NotPresent) => DEFAULT_REGIME_CALM_BPS,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeRegimeCalmBps);
+            }
+        };
+
+        let regime_volatile_bps = match lookup("LAB_REGIME_VOLATILE_BPS") {
+            Ok(value) => value
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| ConfigError::InvalidRegimeVolatileBps)?,
+            Err(env::VarError::NotPresent) => DEFAULT_REGIME_VOLATILE_BPS,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeRegimeVolatileBps);
+            }
+        };
+        let regime_thresholds = RegimeThresholds::new(regime_calm_bps, regime_volatile_bps)
+            .map_err(|_| ConfigError::InvalidRegimeVolatileBps)?;
+
+        let predictor_weights_path = match lookup("LAB_PREDICTOR_WEIGHTS_PATH") {
+            Ok(value) => {
+                if value.trim().is_empty() {
+                    return Err(ConfigError::InvalidPredictorWeightsPath);
+                }
+                value
+            }
+            Err(env::VarError::NotPresent) => DEFAULT_PREDICTOR_WEIGHTS_PATH.to_owned(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodePredictorWeightsPath);
+            }
+        };
+
+        let predictor_url = |key: &str| lookup(key).ok().filter(|url| !url.trim().is_empty());
+        let mut predictors: Vec<PredictorEndpoint> = predictor_url("LAB_TRADINGVIEW_PREDICT_URL")
+            .map(PredictorEndpoint::tradingview)
+            .into_iter()
+            .chain(predictor_url("LAB_CRYPTOQUANT_PREDICT_URL").map(PredictorEndpoint::cryptoquant))
+            .collect();
+        match lookup("LAB_PREDICTORS_FILE") {
+            Ok(path) => {
+                let raw = fs::read_to_string(path.trim()).map_err(|_| {
+                    ConfigError::InvalidPredictorsFile(PredictorConfigError::Unreadable)
+                })?;
+                let configured =
+                    parse_predictor_endpoints(&raw).map_err(ConfigError::InvalidPredictorsFile)?;
+                for endpoint in configured {
+                    if predictors
+                        .iter()
+                        .any(|existing| existing.source == endpoint.source)
+                    {
+                        return Err(ConfigError::InvalidPredictorsFile(
+                            PredictorConfigError::DuplicateLabel(
+                                endpoint.source.as_str().to_string(),
+                            ),
+                        ));
+                    }
+                    predictors.push(endpoint);
+                }
+            }
+            Err(env::VarError::NotPresent) => {}
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodePredictorsFile);
+            }
+        }
+
+        let max_market_position = match lookup("LAB_MAX_MARKET_POSITION") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed.is_finite() && parsed > 0.0 => parsed,
+                _ => return Err(ConfigError::InvalidMaxMarketPosition),
+            },
+            Err(env::VarError::NotPresent) => DEFAULT_MAX_MARKET_POSITION,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeMaxMarketPosition);
+            }
+        };
+
+        let bracket_stop = match lookup("LAB_BRACKET_STOP") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed > 0.0 && parsed < 1.0 => parsed,
+                _ => return Err(ConfigError::InvalidBracketStop),
+            },
+            Err(env::VarError::NotPresent) => DEFAULT_BRACKET_STOP,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeBracketStop);
+            }
+        };
+
+        let bracket_target = match lookup("LAB_BRACKET_TARGET") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed > 0.0 && parsed < 1.0 => parsed,
+                _ => return Err(ConfigError::InvalidBracketTarget),
+            },
+            Err(env::VarError::NotPresent) => DEFAULT_BRACKET_TARGET,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeBracketTarget);
+            }
+        };
+        let brackets = BracketConfig::new(bracket_stop, bracket_target)
+            .map_err(|_| ConfigError::InvalidBracketTarget)?;
+
+        let max_spread = match lookup("LAB_MAX_SPREAD") {
+            Ok(value) => parse_spread_limit(&value).ok_or(ConfigError::InvalidMaxSpread)?,
+            Err(env::VarError::NotPresent) => DEFAULT_MAX_SPREAD,
+            Err(env::VarError::NotUnicode(_)) => return Err(ConfigError::NonUnicodeMaxSpread),
+        };
+
+        let min_touch_qty = match lookup("LAB_MIN_TOUCH_QTY") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed.is_finite() && parsed >= 0.0 => parsed,
+                _ => return Err(ConfigError::InvalidMinTouchQty),
+            },
+            Err(env::VarError::NotPresent) => DEFAULT_MIN_TOUCH_QTY,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeMinTouchQty);
+            }
+        };
+
+        let webhook_urls = match lookup("LAB_WEBHOOK_URLS") {
+            Ok(value) => {
+                let urls: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|url| !url.is_empty())
+                    .map(str::to_owned)
+                    .collect();
+                if urls
+                    .iter()
+                    .any(|url| !(url.starts_with("http://") || url.starts_with("https://")))
+                {
+                    return Err(ConfigError::InvalidWebhookUrls);
+                }
+                urls
+            }
+            Err(env::VarError::NotPresent) => Vec::new(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeWebhookUrls);
+            }
+        };
+        let webhook_secret = match lookup("LAB_WEBHOOK_SECRET") {
+            Ok(value) => {
+                if value.trim().is_empty() {
+                    return Err(ConfigError::InvalidWebhookSecret);
+                }
+                Some(value)
+            }
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeWebhookSecret);
+            }
+        };
+        let webhook_max_attempts = parse_bounded_u64_env(
+            lookup,
+            "LAB_WEBHOOK_MAX_ATTEMPTS",
+            DEFAULT_WEBHOOK_MAX_ATTEMPTS,
+            1..=MAX_WEBHOOK_MAX_ATTEMPTS,
+            ConfigError::InvalidWebhookMaxAttempts,
+            ConfigError::NonUnicodeWebhookMaxAttempts,
+        )? as u32;
+
+        let chat_webhook_url = match lookup("LAB_CHAT_WEBHOOK_URL") {
+            Ok(value) => {
+                let url = value.trim();
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(ConfigError::InvalidChatWebhookUrl);
+                }
+                Some(url.to_owned())
+            }
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeChatWebhookUrl);
+            }
+        };
+        let chat_categories = match lookup("LAB_CHAT_ALERTS") {
+            Ok(value) => parse_alert_categories(&value).ok_or(ConfigError::InvalidChatAlerts)?,
+            Err(env::VarError::NotPresent) => AlertCategory::ALL.to_vec(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeChatAlerts);
+            }
+        };
+        let chat_large_fill_usd = match lookup("LAB_CHAT_LARGE_FILL_USD") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(parsed) if parsed.is_finite() && parsed > 0.0 => parsed,
+                _ => return Err(ConfigError::InvalidChatLargeFillUsd),
+            },
+            Err(env::VarError::NotPresent) => DEFAULT_CHAT_LARGE_FILL_USD,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeChatLargeFillUsd);
+            }
+        };
+        let chat_max_per_min = parse_bounded_u64_env(
+            lookup,
+            "LAB_CHAT_MAX_ALERTS_PER_MIN",
+            DEFAULT_CHAT_MAX_ALERTS_PER_MIN,
+            1..=MAX_CHAT_MAX_ALERTS_PER_MIN,
+            ConfigError::InvalidChatMaxAlertsPerMin,
+            ConfigError::NonUnicodeChatMaxAlertsPerMin,
+        )? as u32;
+        let chat_alerts = chat_webhook_url.map(|url| ChatAlertConfig {
+            url,
+            categories: chat_categories,
+            large_fill_usd: chat_large_fill_usd,
+            max_per_min: chat_max_per_min,
+        });
+
+        let statsd_addr = match lookup("LAB_STATSD_ADDR") {
+            Ok(value) => {
+                let addr = value.trim();
+                let has_port = addr.rsplit_once(':').is_some_and(|(host, port)| {
+                    !host.is_empty() && port.parse::<u16>().is_ok_and(|port| port > 0)
+                });
+                if !has_port {
+                    return Err(ConfigError::InvalidStatsdAddr);
+                }
+                Some(addr.to_owned())
+            }
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeStatsdAddr);
+            }
+        };
+        let statsd_prefix = match lookup("LAB_STATSD_PREFIX") {
+            Ok(value) => {
+                let prefix = value.trim();
+                if !is_valid_statsd_prefix(prefix) {
+                    return Err(ConfigError::InvalidStatsdPrefix);
+                }
+                prefix.to_owned()
+            }
+            Err(env::VarError::NotPresent) => DEFAULT_STATSD_PREFIX.to_string(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeStatsdPrefix);
+            }
+        };
+        let statsd_tags = match lookup("LAB_STATSD_TAGS") {
+            Ok(value) => parse_statsd_tags(&value).ok_or(ConfigError::InvalidStatsdTags)?,
+            Err(env::VarError::NotPresent) => Vec::new(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeStatsdTags);
+            }
+        };
+        let statsd = statsd_addr.map(|addr| StatsdConfig {
+            addr,
+            prefix: statsd_prefix,
+            tags: statsd_tags,
+        });
+
+        let redis_channel_prefix = match lookup("LAB_REDIS_CHANNEL_PREFIX") {
+            Ok(value) => {
+                let prefix = value.trim();
+                if prefix.is_empty() || prefix.contains(char::is_whitespace) {
+                    return Err(ConfigError::InvalidRedisChannelPrefix);
+                }
+                prefix.to_owned()
+            }
+            Err(env::VarError::NotPresent) => DEFAULT_REDIS_CHANNEL_PREFIX.to_owned(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeRedisChannelPrefix);
+            }
+        };
+        let redis_mirror = match lookup("LAB_REDIS_URL") {
+            Ok(value) => Some(
+                RedisMirrorConfig::from_url(&value, redis_channel_prefix)
+                    .ok_or(ConfigError::InvalidRedisUrl)?,
+            ),
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeRedisUrl);
+            }
+        };
+
+        let clob_private_key = match lookup("LAB_CLOB_PRIVATE_KEY") {
+            Ok(value) => {
+                let key = value.trim();
+                let digits = key.strip_prefix("0x").unwrap_or(key);
+                if digits.len() != 64 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(ConfigError::InvalidClobPrivateKey);
+                }
+                Some(key.to_owned())
+            }
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeClobPrivateKey);
+            }
+        };
+        let clob_api_key = match lookup("LAB_CLOB_API_KEY") {
+            Ok(value) => Some(value.trim().to_owned()).filter(|value| !value.is_empty()),
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeClobApiKey);
+            }
+        };
+        let clob_api_secret = match lookup("LAB_CLOB_API_SECRET") {
+            Ok(value) => Some(value.trim().to_owned()).filter(|value| !value.is_empty()),
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeClobApiSecret);
+            }
+        };
+        let clob_api_passphrase = match lookup("LAB_CLOB_API_PASSPHRASE") {
+            Ok(value) => Some(value.trim().to_owned()).filter(|value| !value.is_empty()),
+            Err(env::VarError::NotPresent) => None,
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeClobApiPassphrase);
+            }
+        };
+        let clob = match (
+            clob_private_key,
+            clob_api_key,
+            clob_api_secret,
+            clob_api_passphrase,
+        ) {
+            (Some(private_key), Some(api_key), Some(api_secret), Some(api_passphrase)) => {
+                Some(ClobCredentials {
+                    private_key,
+                    api_key,
+                    api_secret,
+                    api_passphrase,
+                })
+            }
+            (None, None, None, None) => None,
+            _ => return Err(ConfigError::IncompleteClobCredentials),
+        };
+
+        let default_proxy = match lookup("LAB_PROXY_URL") {
+            Ok(value) => parse_proxy_url(&value)
+                .map_err(|err| ConfigError::InvalidProxyUrl("LAB_PROXY_URL", err))?,
+            Err(env::VarError::NotPresent) => {
+                let mut fallback = None;
+                for key in PROXY_ENV_FALLBACKS {
+                    match lookup(key) {
+                        Ok(value) if value.trim().is_empty() => {}
+                        Ok(value) => {
+                            fallback = parse_proxy_url(&value)
+                                .map_err(|err| ConfigError::InvalidProxyUrl(key, err))?;
+                            break;
+                        }
+                        Err(env::VarError::NotPresent) => {}
+                        Err(env::VarError::NotUnicode(_)) => {
+                            return Err(ConfigError::NonUnicodeProxyUrl(key));
+                        }
+                    }
+                }
+                fallback
+            }
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeProxyUrl("LAB_PROXY_URL"));
+            }
+        };
+        let venue_proxies = match lookup("LAB_VENUE_PROXIES") {
+            Ok(value) => parse_venue_proxies(&value).ok_or(ConfigError::InvalidVenueProxies)?,
+            Err(env::VarError::NotPresent) => HashMap::new(),
+            Err(env::VarError::NotUnicode(_)) => {
+                return Err(ConfigError::NonUnicodeVenueProxies);
+            }
+        };
+
+        Ok(Self {
+            listen_addr,
+            tls,
+            grpc_addr,
+            mode,
+            replay_output_path,
+            execution_mode,
+            live_feature_enabled,
+            lag_threshold_pct,
+            per_trade_risk_pct,
+            daily_loss_cap_pct,
+            trading_day_utc_offset_mins,
+            max_drawdown_pct,
+            var_limit_pct,
+            lag_cooldown_secs,
+            lag_persistence_evals,
+            max_intents_per_min,
+            decision_interval_ms,
+            quote_poll_ms,
+            feed_block_cooldown_secs,
+            loss_streak_limit,
+            loss_streak_cooldown_secs,
+            btc_composite: BtcCompositeConfig {
+                aggregator: btc_aggregator,
+                vwap_window_ms: btc_vwap_window_ms,
+                ewma_half_life_ms: btc_ewma_half_life_ms,
+                outlier_mode: btc_outlier_mode,
+            },
+            assets,
+            markets,
+            recorder: TickRecorderConfig {
+                dir: record_dir.into(),
+                rotate_bytes: record_rotate_mb * 1_024 * 1_024,
+                format: record_format,
+                streams: record_streams,
+            },
+            replay_input_dir,
+            replay_speed,
+            backfill_trades,
+            regime_thresholds,
+            predictor_weights_path,
+            predictors,
+            max_market_position,
+            brackets,
+            max_spread,
+            min_touch_qty,
+            webhooks: WebhookConfig {
+                urls: webhook_urls,
+                secret: webhook_secret,
+                max_attempts: webhook_max_attempts,
+            },
+            chat_alerts,
+            statsd,
+            redis_mirror,
+            clob,
+            proxies: ProxyConfig {
+                default: default_proxy,
+                venues: venue_proxies,
+            },
+        })
+    }
+}
+
+/// Parses an absolute YES price width such as `0.05`, or a width relative
+/// to the mid such as `8%`.
+fn parse_spread_limit(value: &str) -> Option<SpreadLimit> {
+    let value = value.trim();
+    match value.strip_suffix('%') {
+        Some(pct) => match pct.trim().parse::<f64>() {
+            Ok(parsed) if parsed > 0.0 && parsed <= 100.0 => {
+                Some(SpreadLimit::Relative(parsed / 100.0))
+            }
+            _ => None,
+        },
+        None => match value.parse::<f64>() {
+            Ok(parsed) if parsed > 0.0 && parsed <= 1.0 => Some(SpreadLimit::Absolute(parsed)),
+            _ => None,
+        },
+    }
+}
+
+/// Parses a comma-separated asset list. BTC drives the headline forecast, so
+/// it is tracked even when not listed; duplicates are dropped.
+fn parse_assets(value: &str) -> Option<Vec<Asset>> {
+    let mut assets = vec![Asset::Btc];
+    for entry in value.split(',').filter(|entry| !entry.trim().is_empty()) {
+        let asset = Asset::parse(entry)?;
+        if !assets.contains(&asset) {
+            assets.push(asset);
+        }
+    }
+    Some(assets)
+}
+
+/// Parses a comma-separated list of streams to record; `all` selects every
+/// stream and an empty list leaves recording off.
+fn parse_record_streams(value: &str) -> Option<Vec<RecordStream>> {
+    let mut streams = Vec::new();
+    for entry in value.split(',').filter(|entry| !entry.trim().is_empty()) {
+        let selected = match entry.trim() {
+            "all" => RecordStream::ALL.to_vec(),
+            other => vec![RecordStream::parse(other)?],
+        };
+        for stream in selected {
+            if !streams.contains(&stream) {
+                streams.push(stream);
+            }
+        }
+    }
+    Some(streams)
+}
+
+fn parse_alert_categories(value: &str) -> Option<Vec<AlertCategory>> {
+    let mut categories = Vec::new();
+    for entry in value.split(',').filter(|entry| !entry.trim().is_empty()) {
+        let selected = match entry.trim() {
+            "all" => AlertCategory::ALL.to_vec(),
+            other => vec![AlertCategory::parse(other)?],
+        };
+        for category in selected {
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+    }
+    Some(categories)
+}
+
+/// An empty prefix is allowed and sends bare metric names.
+fn is_valid_statsd_prefix(prefix: &str) -> bool {
+    !prefix.starts_with('.')
+        && !prefix.ends_with('.')
+        && prefix
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'))
+}
+
+fn parse_statsd_tags(value: &str) -> Option<Vec<String>> {
+    let mut tags = Vec::new();
+    for tag in value
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+    {
+        if tag.starts_with(':')
+            || tag
+                .chars()
+                .any(|ch| ch.is_whitespace() || matches!(ch, '|' | '#' | '@'))
+        {
+            return None;
+        }
+        tags.push(tag.to_owned());
+    }
+    Some(tags)
+}
+
+/// `none` or `direct` turns the proxy off.
+fn parse_proxy_url(value: &str) -> Result<Option<HttpProxy>, HttpProxyError> {
+    match value.trim() {
+        "none" | "direct" => Ok(None),
+        url => HttpProxy::parse(url).map(Some),
+    }
+}
+
+fn parse_venue_proxies(value: &str) -> Option<HashMap<String, Option<HttpProxy>>> {
+    let mut venues = HashMap::new();
+    for entry in value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (venue, url) = entry.split_once('=')?;
+        let venue = venue.trim();
+        if !PROXY_VENUES.contains(&venue) {
+            return None;
+        }
+        let proxy = parse_proxy_url(url).ok()?;
+        if venues.insert(venue.to_string(), proxy).is_some() {
+            return None;
+        }
+    }
+    Some(venues)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_percentage_env(
+    lookup: Lookup<'_>,
+    key: &str,
+    default_value: f64,
+    invalid_error: ConfigError,
+    non_unicode_error: ConfigError,
+) -> Result<f64, ConfigError> {
+    match lookup(key) {
+        Ok(value) => {
+            let parsed = match value.parse::<f64>() {
+                Ok(parsed) => parsed,
+                Err(_) => return Err(invalid_error),
+            };
+            if !parsed.is_finite() || parsed <= 0.0 || parsed > 100.0 {
+                return Err(invalid_error);
+            }
+            Ok(parsed)
+        }
+        Err(env::VarError::NotPresent) => Ok(default_value),
+        Err(env::VarError::NotUnicode(_)) => Err(non_unicode_error),
+    }
+}
+
+/// Parses `Z` or a `+HH:MM`/`-HH:MM` offset into minutes east of UTC.
+fn parse_utc_offset(value: &str) -> Option<i32> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("z") {
+        return Some(0);
+    }
+    let (sign, rest) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    let total = hours * 60 + minutes;
+    (minutes < 60 && total <= MAX_TRADING_DAY_UTC_OFFSET_MINS).then_some(sign * total)
+}
+
+fn parse_bounded_u64_env(
+    lookup: Lookup<'_>,
+    key: &str,
+    default_value: u64,
+    bounds: std::ops::RangeInclusive<u64>,
+    invalid_error: ConfigError,
+    non_unicode_error: ConfigError,
+) -> Result<u64, ConfigError> {
+    match lookup(key) {
+        Ok(value) => match value.trim().parse::<u64>() {
+            Ok(parsed) if bounds.contains(&parsed) => Ok(parsed),
+            _ => Err(invalid_error),
+        },
+        Err(env::VarError::NotPresent) => Ok(default_value),
+        Err(env::VarError::NotUnicode(_)) => Err(non_unicode_error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, sync::Mutex};
+
+    use api::state::SettingsConflict;
+    use runtime::live::{
+        Asset, HttpProxy, HttpProxyError, MarketFilterError, MarketSelection, SpreadLimit,
+    };
+    use runtime::metrics::StatsdConfig;
+    use runtime::recorder::{RecordFormat, RecordStream};
+
+    use super::{
+        BtcAggregatorMode, BtcOutlierMode, Config, ConfigError, ExecutionMode, ProxyConfig,
+        RunMode, TlsConfig, WebhookConfig,
+    };
+    use crate::chat_alerts::{AlertCategory, ChatAlertConfig};
+    use crate::predictors::PredictorConfigError;
+    use crate::redis_mirror::RedisMirrorConfig;
+
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+    const ENV_ADDR_KEY: &str = "LAB_SERVER_ADDR";
+    const ENV_MODE_KEY: &str = "LAB_SERVER_MODE";
+    const ENV_REPLAY_KEY: &str = "LAB_SERVER_REPLAY_OUTPUT";
+    const ENV_LAG_COOLDOWN_KEY: &str = "LAB_LAG_COOLDOWN_SECS";
+    const ENV_LAG_PERSISTENCE_KEY: &str = "LAB_LAG_PERSISTENCE_EVALS";
+    const ENV_BTC_AGGREGATOR_KEY: &str = "LAB_BTC_AGGREGATOR";
+    const ENV_BTC_VWAP_WINDOW_KEY: &str = "LAB_BTC_VWAP_WINDOW_MS";
+    const ENV_BTC_EWMA_HALF_LIFE_KEY: &str = "LAB_BTC_EWMA_HALF_LIFE_MS";
+    const ENV_BTC_OUTLIER_MODE_KEY: &str = "LAB_BTC_OUTLIER_MODE";
+    const ENV_ASSETS_KEY: &str = "LAB_ASSETS";
+    const ENV_MARKET_FILTERS_KEY: &str = "LAB_MARKET_FILTERS";
+    const ENV_MARKET_HORIZON_KEY: &str = "LAB_MARKET_HORIZON_MINS";
+    const ENV_RECORD_DIR_KEY: &str = "LAB_RECORD_DIR";
+    const ENV_RECORD_ROTATE_KEY: &str = "LAB_RECORD_ROTATE_MB";
+    const ENV_RECORD_SOURCES_KEY: &str = "LAB_RECORD_SOURCES";
+    const ENV_RECORD_FORMAT_KEY: &str = "LAB_RECORD_FORMAT";
+    const ENV_REPLAY_INPUT_DIR_KEY: &str = "LAB_REPLAY_INPUT_DIR";
+    const ENV_REPLAY_SPEED_KEY: &str = "LAB_REPLAY_SPEED";
+    const ENV_BACKFILL_TRADES_KEY: &str = "LAB_BACKFILL_TRADES";
+    const ENV_REGIME_CALM_KEY: &str = "LAB_REGIME_CALM_BPS";
+    const ENV_REGIME_VOLATILE_KEY: &str = "LAB_REGIME_VOLATILE_BPS";
+    const ENV_PREDICTOR_WEIGHTS_PATH_KEY: &str = "LAB_PREDICTOR_WEIGHTS_PATH";
+    const ENV_PREDICTORS_FILE_KEY: &str = "LAB_PREDICTORS_FILE";
+    const ENV_TRADINGVIEW_URL_KEY: &str = "LAB_TRADINGVIEW_PREDICT_URL";
+    const ENV_CRYPTOQUANT_URL_KEY: &str = "LAB_CRYPTOQUANT_PREDICT_URL";
+    const ENV_MAX_MARKET_POSITION_KEY: &str = "LAB_MAX_MARKET_POSITION";
+    const ENV_BRACKET_STOP_KEY: &str = "LAB_BRACKET_STOP";
+    const ENV_BRACKET_TARGET_KEY: &str = "LAB_BRACKET_TARGET";
+    const ENV_MAX_DRAWDOWN_KEY: &str = "LAB_MAX_DRAWDOWN_PCT";
+    const ENV_TRADING_DAY_OFFSET_KEY: &str = "LAB_TRADING_DAY_UTC_OFFSET";
+    const ENV_VAR_LIMIT_KEY: &str = "LAB_VAR_LIMIT_PCT";
+    const ENV_MAX_INTENTS_KEY: &str = "LAB_MAX_INTENTS_PER_MIN";
+    const ENV_TLS_CERT_KEY: &str = "LAB_TLS_CERT_PATH";
+    const ENV_TLS_KEY_KEY: &str = "LAB_TLS_KEY_PATH";
+    const ENV_TLS_REDIRECT_KEY: &str = "LAB_TLS_REDIRECT_ADDR";
+    const ENV_GRPC_ADDR_KEY: &str = "LAB_GRPC_ADDR";
+    const ENV_DECISION_INTERVAL_KEY: &str = "LAB_DECISION_INTERVAL_MS";
+    const ENV_QUOTE_POLL_KEY: &str = "LAB_QUOTE_POLL_MS";
+    const ENV_FEED_BLOCK_COOLDOWN_KEY: &str = "LAB_FEED_BLOCK_COOLDOWN_SECS";
+    const ENV_LOSS_STREAK_LIMIT_KEY: &str = "LAB_LOSS_STREAK_LIMIT";
+    const ENV_LOSS_STREAK_COOLDOWN_KEY: &str = "LAB_LOSS_STREAK_COOLDOWN_SECS";
+    const ENV_MAX_SPREAD_KEY: &str = "LAB_MAX_SPREAD";
+    const ENV_MIN_TOUCH_QTY_KEY: &str = "LAB_MIN_TOUCH_QTY";
+    const ENV_WEBHOOK_URLS_KEY: &str = "LAB_WEBHOOK_URLS";
+    const ENV_WEBHOOK_SECRET_KEY: &str = "LAB_WEBHOOK_SECRET";
+    const ENV_WEBHOOK_MAX_ATTEMPTS_KEY: &str = "LAB_WEBHOOK_MAX_ATTEMPTS";
+    const ENV_CHAT_WEBHOOK_URL_KEY: &str = "LAB_CHAT_WEBHOOK_URL";
+    const ENV_CHAT_ALERTS_KEY: &str = "LAB_CHAT_ALERTS";
+    const ENV_CHAT_LARGE_FILL_KEY: &str = "LAB_CHAT_LARGE_FILL_USD";
+    const ENV_CHAT_MAX_ALERTS_KEY: &str = "LAB_CHAT_MAX_ALERTS_PER_MIN";
+    const ENV_STATSD_ADDR_KEY: &str = "LAB_STATSD_ADDR";
+    const ENV_STATSD_PREFIX_KEY: &str = "LAB_STATSD_PREFIX";
+    const ENV_STATSD_TAGS_KEY: &str = "LAB_STATSD_TAGS";
+    const ENV_REDIS_URL_KEY: &str = "LAB_REDIS_URL";
+    const ENV_REDIS_CHANNEL_PREFIX_KEY: &str = "LAB_REDIS_CHANNEL_PREFIX";
+    const ENV_EXECUTION_MODE_KEY: &str = "LAB_EXECUTION_MODE";
+    const ENV_CLOB_PRIVATE_KEY_KEY: &str = "LAB_CLOB_PRIVATE_KEY";
+    const ENV_CLOB_API_KEY_KEY: &str = "LAB_CLOB_API_KEY";
+    const ENV_CLOB_API_SECRET_KEY: &str = "LAB_CLOB_API_SECRET";
+    const ENV_CLOB_API_PASSPHRASE_KEY: &str = "LAB_CLOB_API_PASSPHRASE";
+    const ENV_PROXY_URL_KEY: &str = "LAB_PROXY_URL";
+    const ENV_VENUE_PROXIES_KEY: &str = "LAB_VENUE_PROXIES";
+
+    struct EnvVarGuard {
+        key: &'static str,
+        previous: Option<std::ffi::OsString>,
+    }
+
+    impl EnvVarGuard {
+        fn set(key: &'static str, value: &str) -> Self {
+            let previous = env::var_os(key);
+            env::set_var(key, value);
+            Self { key, previous }
+        }
+
+        fn unset(key: &'static str) -> Self {
+            let previous = env::var_os(key);
+            env::remove_var(key);
+            Self { key, previous }
+        }
+
+        #[cfg(unix)]
+        fn set_os(key: &'static str, value: std::ffi::OsString) -> Self {
+            let previous = env::var_os(key);
+            env::set_var(key, value);
+            Self { key, previous }
+        }
+    }
+
+    impl Drop for EnvVarGuard {
+        fn drop(&mut self) {
+            match self.previous.take() {
+                Some(value) => env::set_var(self.key, value),
+                None => env::remove_var(self.key),
+            }
+        }
+    }
+
+    fn reset_config_env_baseline() -> [EnvVarGuard; 66] {
+        [
+            EnvVarGuard::unset(ENV_ADDR_KEY),
+            EnvVarGuard::unset(ENV_MODE_KEY),
+            EnvVarGuard::unset(ENV_REPLAY_KEY),
+            EnvVarGuard::unset(ENV_LAG_COOLDOWN_KEY),
+            EnvVarGuard::unset(ENV_LAG_PERSISTENCE_KEY),
+            EnvVarGuard::unset(ENV_BTC_AGGREGATOR_KEY),
+            EnvVarGuard::unset(ENV_BTC_VWAP_WINDOW_KEY),
+            EnvVarGuard::unset(ENV_BTC_EWMA_HALF_LIFE_KEY),
+            EnvVarGuard::unset(ENV_BTC_OUTLIER_MODE_KEY),
+            EnvVarGuard::unset(ENV_ASSETS_KEY),
+            EnvVarGuard::unset(ENV_MARKET_FILTERS_KEY),
+            EnvVarGuard::unset(ENV_MARKET_HORIZON_KEY),
+            EnvVarGuard::unset(ENV_RECORD_DIR_KEY),
+            EnvVarGuard::unset(ENV_RECORD_ROTATE_KEY),
+            EnvVarGuard::unset(ENV_RECORD_SOURCES_KEY),
+            EnvVarGuard::unset(ENV_RECORD_FORMAT_KEY),
+            EnvVarGuard::unset(ENV_REPLAY_INPUT_DIR_KEY),
+            EnvVarGuard::unset(ENV_REPLAY_SPEED_KEY),
+            EnvVarGuard::unset(ENV_BACKFILL_TRADES_KEY),
+            EnvVarGuard::unset(ENV_REGIME_CALM_KEY),
+            EnvVarGuard::unset(ENV_REGIME_VOLATILE_KEY),
+            EnvVarGuard::unset(ENV_PREDICTOR_WEIGHTS_PATH_KEY),
+            EnvVarGuard::unset(ENV_PREDICTORS_FILE_KEY),
+            EnvVarGuard::unset(ENV_TRADINGVIEW_URL_KEY),
+            EnvVarGuard::unset(ENV_CRYPTOQUANT_URL_KEY),
+            EnvVarGuard::unset(ENV_MAX_MARKET_POSITION_KEY),
+            EnvVarGuard::unset(ENV_BRACKET_STOP_KEY),
+            EnvVarGuard::unset(ENV_BRACKET_TARGET_KEY),
+            EnvVarGuard::unset(ENV_MAX_DRAWDOWN_KEY),
+            EnvVarGuard::unset(ENV_TRADING_DAY_OFFSET_KEY),
+            EnvVarGuard::unset(ENV_VAR_LIMIT_KEY),
+            EnvVarGuard::unset(ENV_MAX_INTENTS_KEY),
+            EnvVarGuard::unset(ENV_TLS_CERT_KEY),
+            EnvVarGuard::unset(ENV_TLS_KEY_KEY),
+            EnvVarGuard::unset(ENV_TLS_REDIRECT_KEY),
+            EnvVarGuard::unset(ENV_DECISION_INTERVAL_KEY),
+            EnvVarGuard::unset(ENV_QUOTE_POLL_KEY),
+            EnvVarGuard::unset(ENV_FEED_BLOCK_COOLDOWN_KEY),
+            EnvVarGuard::unset(ENV_LOSS_STREAK_LIMIT_KEY),
+            EnvVarGuard::unset(ENV_LOSS_STREAK_COOLDOWN_KEY),
+            EnvVarGuard::unset(ENV_MAX_SPREAD_KEY),
+            EnvVarGuard::unset(ENV_MIN_TOUCH_QTY_KEY),
+            EnvVarGuard::unset(ENV_WEBHOOK_URLS_KEY),
+            EnvVarGuard::unset(ENV_WEBHOOK_SECRET_KEY),
+            EnvVarGuard::unset(ENV_WEBHOOK_MAX_ATTEMPTS_KEY),
+            EnvVarGuard::unset(ENV_CHAT_WEBHOOK_URL_KEY),
+            EnvVarGuard::unset(ENV_CHAT_ALERTS_KEY),
+            EnvVarGuard::unset(ENV_CHAT_LARGE_FILL_KEY),
+            EnvVarGuard::unset(ENV_CHAT_MAX_ALERTS_KEY),
+            EnvVarGuard::unset(ENV_STATSD_ADDR_KEY),
+            EnvVarGuard::unset(ENV_STATSD_PREFIX_KEY),
+            EnvVarGuard::unset(ENV_STATSD_TAGS_KEY),
+            EnvVarGuard::unset(ENV_REDIS_URL_KEY),
+            EnvVarGuard::unset(ENV_REDIS_CHANNEL_PREFIX_KEY),
+            EnvVarGuard::unset(ENV_EXECUTION_MODE_KEY),
+            EnvVarGuard::unset(ENV_CLOB_PRIVATE_KEY_KEY),
+            EnvVarGuard::unset(ENV_CLOB_API_KEY_KEY),
+            EnvVarGuard::unset(ENV_CLOB_API_SECRET_KEY),
+            EnvVarGuard::unset(ENV_CLOB_API_PASSPHRASE_KEY),
+            EnvVarGuard::unset(ENV_PROXY_URL_KEY),
+            EnvVarGuard::unset(ENV_VENUE_PROXIES_KEY),
+            EnvVarGuard::unset("HTTPS_PROXY"),
+            EnvVarGuard::unset("https_proxy"),
+            EnvVarGuard::unset("HTTP_PROXY"),
+            EnvVarGuard::unset("http_proxy"),
+            EnvVarGuard::unset(ENV_GRPC_ADDR_KEY),
+        ]
+    }
+
+    #[test]
+    fn defaults_listen_address_when_env_is_unset() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let config = Config::from_env().unwrap();
+
+        assert_eq!(config.listen_addr, "0.0.0.0:8080".parse().unwrap());
+    }
+
+    #[test]
+    fn defaults_listen_address_ignores_unrelated_mode_and_replay_env_vars() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _ambient_mode_guard = EnvVarGuard::set(ENV_MODE_KEY, "invalid");
+        let _ambient_replay_guard = EnvVarGuard::set(ENV_REPLAY_KEY, "  ");
+        let _baseline = reset_config_env_baseline();
+
+        let config = Config::from_env().unwrap();
+
+        assert_eq!(config.listen_addr, "0.0.0.0:8080".parse().unwrap());
+    }
+
+    #[test]
+    fn uses_listen_address_override_from_env() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+        let _guard = EnvVarGuard::set(ENV_ADDR_KEY, "127.0.0.1:9090");
+
+        let config = Config::from_env().unwrap();
+
+        assert_eq!(config.listen_addr, "127.0.0.1:9090".parse().unwrap());
+    }
+
+    #[test]
+    fn returns_error_for_invalid_listen_address_override() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+        let _guard = EnvVarGuard::set(ENV_ADDR_KEY, "not-an-addr");
+
+        let err = Config::from_env().unwrap_err();
+
+        assert!(matches!(err, ConfigError::InvalidListenAddr(_)));
+    }
+
+    #[test]
+    fn defaults_to_paper_live_mode() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let cfg = Config::from_env().unwrap();
+
+        assert_eq!(cfg.mode, RunMode::PaperLive);
+    }
+
+    #[test]
+    fn defaults_execution_mode_to_paper() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let cfg = Config::from_env().unwrap();
+
+        assert_eq!(cfg.execution_mode, ExecutionMode::Paper);
+    }
+
+    #[test]
+    fn parses_dry_run_execution_mode() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+        let _guard = EnvVarGuard::set(ENV_EXECUTION_MODE_KEY, "dry_run");
+
+        let cfg = Config::from_env().unwrap();
+        assert_eq!(cfg.execution_mode, ExecutionMode::DryRun);
+
+        let _guard = EnvVarGuard::set(ENV_EXECUTION_MODE_KEY, "dry-run");
+        assert!(matches!(
+            Config::from_env(),
+            Err(ConfigError::InvalidExecutionMode)
+        ));
+    }
+
+    #[test]
+    fn defaults_lag_threshold_and_risk_caps() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let cfg = Config::from_env().unwrap();
+
+        assert_eq!(cfg.lag_threshold_pct, 0.3);
+        assert_eq!(cfg.per_trade_risk_pct, 0.5);
+        assert_eq!(cfg.daily_loss_cap_pct, 2.0);
+    }
+
+    #[test]
+    fn defaults_lag_cooldown_and_persistence() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let cfg = Config::from_env().unwrap();
+
+        assert_eq!(cfg.lag_cooldown_secs, 30);
+        assert_eq!(cfg.lag_persistence_evals, 2);
+    }
+
+    #[test]
+    fn uses_lag_cooldown_and_persistence_overrides_from_env() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+        let _cooldown = EnvVarGuard::set(ENV_LAG_COOLDOWN_KEY, "0");
+        let _persistence = EnvVarGuard::set(ENV_LAG_PERSISTENCE_KEY, "5");
+
+        let cfg = Config::from_env().unwrap();
+
+        assert_eq!(cfg.lag_cooldown_secs, 0);
+        assert_eq!(cfg.lag_persistence_evals, 5);
+    }
+
+    #[test]
+    fn returns_error_for_zero_lag_persistence_override() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+        let _guard = EnvVarGuard::set(ENV_LAG_PERSISTENCE_KEY, "0");
+
+        let err = Config::from_env().unwrap_err();
+
+        assert!(matches!(err, ConfigError::InvalidLagPersistenceEvals));
+    }
+
+    #[test]
+    fn btc_aggregator_defaults_to_median_and_accepts_vwap() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let cfg = Config::from_env().unwrap();
+        assert_eq!(cfg.btc_composite.aggregator, BtcAggregatorMode::Median);
+        assert_eq!(cfg.btc_composite.vwap_window_ms, 3_000);
+
+        let _aggregator = EnvVarGuard::set(ENV_BTC_AGGREGATOR_KEY, "vwap");
+        let _window = EnvVarGuard::set(ENV_BTC_VWAP_WINDOW_KEY, "10000");
+        let cfg = Config::from_env().unwrap();
+        assert_eq!(cfg.btc_composite.aggregator, BtcAggregatorMode::Vwap);
+        assert_eq!(cfg.btc_composite.vwap_window_ms, 10_000);
+    }
+
+    #[test]
+    fn returns_error_for_invalid_btc_aggregator_settings() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let _aggregator = EnvVarGuard::set(ENV_BTC_AGGREGATOR_KEY, "mean");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidBtcAggregator
+        ));
+
+        let _aggregator = EnvVarGuard::set(ENV_BTC_AGGREGATOR_KEY, "vwap");
+        let _window = EnvVarGuard::set(ENV_BTC_VWAP_WINDOW_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidBtcVwapWindowMs
+        ));
+    }
+
+    #[test]
+    fn btc_ewma_half_life_defaults_and_rejects_zero() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(
+            Config::from_env().unwrap().btc_composite.ewma_half_life_ms,
+            2_000
+        );
+
+        let _guard = EnvVarGuard::set(ENV_BTC_EWMA_HALF_LIFE_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidBtcEwmaHalfLifeMs
+        ));
+    }
+
+    #[test]
+    fn btc_outlier_mode_defaults_to_fixed_and_accepts_mad() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(
+            Config::from_env().unwrap().btc_composite.outlier_mode,
+            BtcOutlierMode::Fixed
+        );
+
+        let _guard = EnvVarGuard::set(ENV_BTC_OUTLIER_MODE_KEY, "mad");
+        assert_eq!(
+            Config::from_env().unwrap().btc_composite.outlier_mode,
+            BtcOutlierMode::Mad
+        );
+
+        let _guard = EnvVarGuard::set(ENV_BTC_OUTLIER_MODE_KEY, "zscore");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidBtcOutlierMode
+        ));
+    }
+
+    #[test]
+    fn assets_default_to_btc_and_always_lead_with_it() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().assets, vec![Asset::Btc]);
+
+        let _guard = EnvVarGuard::set(ENV_ASSETS_KEY, "sol, eth,sol");
+        assert_eq!(
+            Config::from_env().unwrap().assets,
+            vec![Asset::Btc, Asset::Sol, Asset::Eth]
+        );
+
+        let _guard = EnvVarGuard::set(ENV_ASSETS_KEY, "btc,doge");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidAssets
+        ));
+    }
+
+    #[test]
+    fn market_filters_and_horizon_replace_the_built_in_selection() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(
+            Config::from_env().unwrap().markets,
+            MarketSelection::new(15 * 60 * 1_000)
+        );
+
+        let _filters = EnvVarGuard::set(ENV_MARKET_FILTERS_KEY, "btc=bitcoin & !daily | btc");
+        let _horizon = EnvVarGuard::set(ENV_MARKET_HORIZON_KEY, "60");
+        let markets = Config::from_env().unwrap().markets;
+        assert_eq!(markets.horizon_ms, 3_600_000);
+        assert!(markets.matches(Asset::Btc, "bitcoin-hourly", ""));
+        assert!(!markets.matches(Asset::Btc, "bitcoin-daily", ""));
+
+        let _filters = EnvVarGuard::set(ENV_MARKET_FILTERS_KEY, "btc=!daily");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidMarketFilters(MarketFilterError::NegatedOnly)
+        ));
+        let _filters = EnvVarGuard::unset(ENV_MARKET_FILTERS_KEY);
+        let _horizon = EnvVarGuard::set(ENV_MARKET_HORIZON_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidMarketHorizonMins
+        ));
+    }
+
+    #[test]
+    fn recorder_is_off_until_sources_are_listed() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let recorder = Config::from_env().unwrap().recorder;
+        assert!(!recorder.is_enabled());
+        assert_eq!(recorder.dir, std::path::PathBuf::from("artifacts/ticks"));
+        assert_eq!(recorder.rotate_bytes, 64 * 1_024 * 1_024);
+        assert_eq!(recorder.format, RecordFormat::Csv);
+
+        let _sources = EnvVarGuard::set(ENV_RECORD_SOURCES_KEY, "quotes, spot,quotes");
+        let _rotate = EnvVarGuard::set(ENV_RECORD_ROTATE_KEY, "8");
+        let recorder = Config::from_env().unwrap().recorder;
+        assert_eq!(
+            recorder.streams,
+            vec![RecordStream::Quotes, RecordStream::Spot]
+        );
+        assert_eq!(recorder.rotate_bytes, 8 * 1_024 * 1_024);
+
+        let _sources = EnvVarGuard::set(ENV_RECORD_SOURCES_KEY, "all");
+        assert_eq!(
+            Config::from_env().unwrap().recorder.streams,
+            RecordStream::ALL.to_vec()
+        );
+
+        let _sources = EnvVarGuard::set(ENV_RECORD_SOURCES_KEY, "spot,orders");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidRecordSources
+        ));
+
+        let _sources = EnvVarGuard::unset(ENV_RECORD_SOURCES_KEY);
+        let _rotate = EnvVarGuard::set(ENV_RECORD_ROTATE_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidRecordRotateMb
+        ));
+
+        let _rotate = EnvVarGuard::unset(ENV_RECORD_ROTATE_KEY);
+        let _format = EnvVarGuard::set(ENV_RECORD_FORMAT_KEY, "parquet");
+        match Config::from_env() {
+            Ok(config) => {
+                assert!(cfg!(feature = "parquet"));
+                assert_eq!(config.recorder.format, RecordFormat::Parquet);
+            }
+            Err(err) => {
+                assert!(!cfg!(feature = "parquet"));
+                assert!(matches!(err, ConfigError::InvalidRecordFormat));
+            }
+        }
+        let _format = EnvVarGuard::set(ENV_RECORD_FORMAT_KEY, "arrow");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidRecordFormat
+        ));
+    }
 
-        let lag_threshold_pct = parse_percentage_env(
-            "LAB_LAG_THRESHOLD_PCT",
-            DEFAULT_LAG_THRESHOLD_PCT,
-            ConfigError::InvalidLagThresholdPct,
-            ConfigError::NonUnicodeLagThresholdPct,
-        )?;
+    #[test]
+    fn replay_input_is_optional_and_speed_is_bounded() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
 
-        let per_trade_risk_pct = parse_percentage_env(
-            "LAB_RISK_PER_TRADE_PCT",
-            DEFAULT_PER_TRADE_RISK_PCT,
-            ConfigError::InvalidPerTradeRiskPct,
-            ConfigError::NonUnicodePerTradeRiskPct,
-        )?;
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.replay_input_dir, None);
+        assert_eq!(config.replay_speed, 1.0);
 
-        let daily_loss_cap_pct = parse_percentage_env(
-            "LAB_DAILY_LOSS_CAP_PCT",
-            DEFAULT_DAILY_LOSS_CAP_PCT,
-            ConfigError::InvalidDailyLossCapPct,
-            ConfigError::NonUnicodeDailyLossCapPct,
-        )?;
+        let _dir = EnvVarGuard::set(ENV_REPLAY_INPUT_DIR_KEY, "artifacts/ticks");
+        let _speed = EnvVarGuard::set(ENV_REPLAY_SPEED_KEY, "20");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.replay_input_dir.as_deref(), Some("artifacts/ticks"));
+        assert_eq!(config.replay_speed, 20.0);
+        assert_eq!(config.mode, RunMode::PaperLive);
+
+        for invalid in ["0", "-1", "NaN", "5000"] {
+            let _speed = EnvVarGuard::set(ENV_REPLAY_SPEED_KEY, invalid);
+            assert!(matches!(
+                Config::from_env().unwrap_err(),
+                ConfigError::InvalidReplaySpeed
+            ));
+        }
+    }
 
-        Ok(Self {
-            listen_addr,
-            mode,
-            replay_output_path,
-            execution_mode,
-            live_feature_enabled,
-            lag_threshold_pct,
-            per_trade_risk_pct,
-            daily_loss_cap_pct,
-        })
+    #[test]
+    fn backfill_defaults_on_and_can_be_turned_off() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().backfill_trades, 200);
+        let _trades = EnvVarGuard::set(ENV_BACKFILL_TRADES_KEY, "0");
+        assert_eq!(Config::from_env().unwrap().backfill_trades, 0);
+        let _trades = EnvVarGuard::set(ENV_BACKFILL_TRADES_KEY, "501");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidBackfillTrades
+        ));
     }
-}
 
-fn parse_bool(value: &str) -> Option<bool> {
-    match value {
-        "true" => Some(true),
-        "false" => Some(false),
-        _ => None,
+    #[test]
+    fn predictor_weights_path_defaults_and_rejects_blank_values() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let config = Config::from_env().unwrap();
+        assert_eq!(
+            config.predictor_weights_path,
+            "artifacts/predictor_weights.json"
+        );
+
+        let _path = EnvVarGuard::set(ENV_PREDICTOR_WEIGHTS_PATH_KEY, "state/weights.json");
+        assert_eq!(
+            Config::from_env().unwrap().predictor_weights_path,
+            "state/weights.json"
+        );
+
+        let _path = EnvVarGuard::set(ENV_PREDICTOR_WEIGHTS_PATH_KEY, "  ");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidPredictorWeightsPath
+        ));
     }
-}
 
-fn parse_percentage_env(
-    key: &str,
-    default_value: f64,
-    invalid_error: ConfigError,
-    non_unicode_error: ConfigError,
-) -> Result<f64, ConfigError> {
-    match env::var(key) {
-        Ok(value) => {
-            let parsed = match value.parse::<f64>() {
-                Ok(parsed) => parsed,
-                Err(_) => return Err(invalid_error),
-            };
-            if !parsed.is_finite() || parsed <= 0.0 || parsed > 100.0 {
-                return Err(invalid_error);
-            }
-            Ok(parsed)
-        }
-        Err(env::VarError::NotPresent) => Ok(default_value),
-        Err(env::VarError::NotUnicode(_)) => Err(non_unicode_error),
+    #[test]
+    fn predictors_combine_builtin_urls_with_the_configured_file() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert!(Config::from_env().unwrap().predictors.is_empty());
+
+        let dir = env::temp_dir().join(format!("lab-predictors-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("predictors.toml");
+        fs::write(
+            &path,
+            "[[predictors]]\nlabel = \"glassnode\"\nurl = \"https://example.com/f\"\n\
+             prediction_pointer = \"/yes\"\nconfidence_pointer = \"/conf\"\n",
+        )
+        .unwrap();
+        let _tv = EnvVarGuard::set(ENV_TRADINGVIEW_URL_KEY, "https://tv.example.com");
+        let _cq = EnvVarGuard::set(ENV_CRYPTOQUANT_URL_KEY, " ");
+        let _file = EnvVarGuard::set(ENV_PREDICTORS_FILE_KEY, path.to_str().unwrap());
+
+        let predictors = Config::from_env().unwrap().predictors;
+        let labels: Vec<&str> = predictors.iter().map(|p| p.source.as_str()).collect();
+        assert_eq!(labels, ["tradingview", "glassnode"]);
+        assert_eq!(predictors[1].prediction_pointer, "/yes");
+
+        fs::write(
+            &path,
+            "[[predictors]]\nlabel = \"tradingview\"\nurl = \"https://example.com/f\"\n\
+             prediction_pointer = \"/yes\"\nconfidence_pointer = \"/conf\"\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidPredictorsFile(PredictorConfigError::DuplicateLabel(_))
+        ));
+
+        let _file = EnvVarGuard::set(ENV_PREDICTORS_FILE_KEY, "/nonexistent/predictors.toml");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidPredictorsFile(PredictorConfigError::Unreadable)
+        ));
+        let _ = fs::remove_dir_all(dir);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::{env, sync::Mutex};
+    #[test]
+    fn max_market_position_defaults_and_must_be_positive() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
 
-    use super::{Config, ConfigError, ExecutionMode, RunMode};
+        assert_eq!(Config::from_env().unwrap().max_market_position, 100.0);
 
-    static ENV_LOCK: Mutex<()> = Mutex::new(());
-    const ENV_ADDR_KEY: &str = "LAB_SERVER_ADDR";
-    const ENV_MODE_KEY: &str = "LAB_SERVER_MODE";
-    const ENV_REPLAY_KEY: &str = "LAB_SERVER_REPLAY_OUTPUT";
+        let _max = EnvVarGuard::set(ENV_MAX_MARKET_POSITION_KEY, "25");
+        assert_eq!(Config::from_env().unwrap().max_market_position, 25.0);
 
-    struct EnvVarGuard {
-        key: &'static str,
-        previous: Option<std::ffi::OsString>,
+        for invalid in ["0", "-5", "inf", "lots"] {
+            let _max = EnvVarGuard::set(ENV_MAX_MARKET_POSITION_KEY, invalid);
+            assert!(matches!(
+                Config::from_env().unwrap_err(),
+                ConfigError::InvalidMaxMarketPosition
+            ));
+        }
     }
 
-    impl EnvVarGuard {
-        fn set(key: &'static str, value: &str) -> Self {
-            let previous = env::var_os(key);
-            env::set_var(key, value);
-            Self { key, previous }
-        }
+    #[test]
+    fn bracket_distances_default_and_must_be_inside_the_unit_interval() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
 
-        fn unset(key: &'static str) -> Self {
-            let previous = env::var_os(key);
-            env::remove_var(key);
-            Self { key, previous }
+        let brackets = Config::from_env().unwrap().brackets;
+        assert_eq!(brackets.stop_distance(), 0.10);
+        assert_eq!(brackets.target_distance(), 0.20);
+
+        let _stop = EnvVarGuard::set(ENV_BRACKET_STOP_KEY, "0.05");
+        let _target = EnvVarGuard::set(ENV_BRACKET_TARGET_KEY, "0.3");
+        let brackets = Config::from_env().unwrap().brackets;
+        assert_eq!(brackets.stop_distance(), 0.05);
+        assert_eq!(brackets.target_distance(), 0.3);
+
+        let _target = EnvVarGuard::set(ENV_BRACKET_TARGET_KEY, "1");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidBracketTarget
+        ));
+        let _stop = EnvVarGuard::set(ENV_BRACKET_STOP_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidBracketStop
+        ));
+    }
+
+    #[test]
+    fn trading_day_offset_defaults_to_utc_and_parses_signed_offsets() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().trading_day_utc_offset_mins, 0);
+
+        for (value, expected) in [("Z", 0), ("-05:00", -300), ("+05:30", 330)] {
+            let _offset = EnvVarGuard::set(ENV_TRADING_DAY_OFFSET_KEY, value);
+            assert_eq!(
+                Config::from_env().unwrap().trading_day_utc_offset_mins,
+                expected
+            );
         }
 
-        #[cfg(unix)]
-        fn set_os(key: &'static str, value: std::ffi::OsString) -> Self {
-            let previous = env::var_os(key);
-            env::set_var(key, value);
-            Self { key, previous }
+        for value in ["05:00", "+5:00", "+15:00", "+01:60"] {
+            let _offset = EnvVarGuard::set(ENV_TRADING_DAY_OFFSET_KEY, value);
+            assert!(matches!(
+                Config::from_env().unwrap_err(),
+                ConfigError::InvalidTradingDayUtcOffset
+            ));
         }
     }
 
-    impl Drop for EnvVarGuard {
-        fn drop(&mut self) {
-            match self.previous.take() {
-                Some(value) => env::set_var(self.key, value),
-                None => env::remove_var(self.key),
-            }
-        }
+    #[test]
+    fn rejects_per_trade_risk_above_the_daily_cap() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let _risk = EnvVarGuard::set("LAB_RISK_PER_TRADE_PCT", "3");
+        let _cap = EnvVarGuard::set("LAB_DAILY_LOSS_CAP_PCT", "2");
+        let err = Config::from_env().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::ConflictingSettings(SettingsConflict::RiskAboveDailyCap { .. })
+        ));
+        assert!(err.to_string().contains("raise the daily cap"));
+
+        let _cap = EnvVarGuard::set("LAB_DAILY_LOSS_CAP_PCT", "3");
+        assert_eq!(Config::from_env().unwrap().per_trade_risk_pct, 3.0);
+
+        let _lag = EnvVarGuard::set("LAB_LAG_THRESHOLD_PCT", "99");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::ConflictingSettings(SettingsConflict::LagThresholdTooHigh { .. })
+        ));
     }
 
-    fn reset_config_env_baseline() -> [EnvVarGuard; 3] {
-        [
-            EnvVarGuard::unset(ENV_ADDR_KEY),
-            EnvVarGuard::unset(ENV_MODE_KEY),
-            EnvVarGuard::unset(ENV_REPLAY_KEY),
-        ]
+    #[test]
+    fn max_drawdown_defaults_and_is_a_percentage() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().max_drawdown_pct, 5.0);
+
+        let _drawdown = EnvVarGuard::set(ENV_MAX_DRAWDOWN_KEY, "3.5");
+        assert_eq!(Config::from_env().unwrap().max_drawdown_pct, 3.5);
+
+        let _drawdown = EnvVarGuard::set(ENV_MAX_DRAWDOWN_KEY, "150");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidMaxDrawdownPct
+        ));
     }
 
     #[test]
-    fn defaults_listen_address_when_env_is_unset() {
+    fn var_limit_is_optional_and_a_percentage() {
         let _lock = ENV_LOCK.lock().unwrap();
         let _baseline = reset_config_env_baseline();
 
-        let config = Config::from_env().unwrap();
+        assert_eq!(Config::from_env().unwrap().var_limit_pct, None);
 
-        assert_eq!(config.listen_addr, "0.0.0.0:8080".parse().unwrap());
+        let _var_limit = EnvVarGuard::set(ENV_VAR_LIMIT_KEY, "1.5");
+        assert_eq!(Config::from_env().unwrap().var_limit_pct, Some(1.5));
+
+        let _var_limit = EnvVarGuard::set(ENV_VAR_LIMIT_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidVarLimitPct
+        ));
     }
 
     #[test]
-    fn defaults_listen_address_ignores_unrelated_mode_and_replay_env_vars() {
+    fn max_intents_per_min_defaults_and_rejects_zero() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().max_intents_per_min, 30);
+
+        let _max_intents = EnvVarGuard::set(ENV_MAX_INTENTS_KEY, "120");
+        assert_eq!(Config::from_env().unwrap().max_intents_per_min, 120);
+
+        let _max_intents = EnvVarGuard::set(ENV_MAX_INTENTS_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidMaxIntentsPerMin
+        ));
+    }
+
+    #[test]
+    fn loop_intervals_default_and_reject_out_of_range_values() {
         let _lock = ENV_LOCK.lock().unwrap();
-        let _ambient_mode_guard = EnvVarGuard::set(ENV_MODE_KEY, "invalid");
-        let _ambient_replay_guard = EnvVarGuard::set(ENV_REPLAY_KEY, "  ");
         let _baseline = reset_config_env_baseline();
 
         let config = Config::from_env().unwrap();
+        assert_eq!(config.decision_interval_ms, 1_500);
+        assert_eq!(config.quote_poll_ms, 15_000);
+        assert_eq!(config.feed_block_cooldown_secs, 900);
 
-        assert_eq!(config.listen_addr, "0.0.0.0:8080".parse().unwrap());
+        let _decision = EnvVarGuard::set(ENV_DECISION_INTERVAL_KEY, "500");
+        let _quote_poll = EnvVarGuard::set(ENV_QUOTE_POLL_KEY, "5000");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.decision_interval_ms, 500);
+        assert_eq!(config.quote_poll_ms, 5_000);
+
+        let _decision = EnvVarGuard::set(ENV_DECISION_INTERVAL_KEY, "50");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidDecisionIntervalMs
+        ));
+
+        let _decision = EnvVarGuard::unset(ENV_DECISION_INTERVAL_KEY);
+        let _quote_poll = EnvVarGuard::set(ENV_QUOTE_POLL_KEY, "999");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidQuotePollMs
+        ));
+
+        let _quote_poll = EnvVarGuard::unset(ENV_QUOTE_POLL_KEY);
+        let _block = EnvVarGuard::set(ENV_FEED_BLOCK_COOLDOWN_KEY, "3600");
+        assert_eq!(Config::from_env().unwrap().feed_block_cooldown_secs, 3_600);
+        let _block = EnvVarGuard::set(ENV_FEED_BLOCK_COOLDOWN_KEY, "30");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidFeedBlockCooldownSecs
+        ));
     }
 
     #[test]
-    fn uses_listen_address_override_from_env() {
+    fn loss_streak_cooldown_defaults_and_accepts_overrides() {
         let _lock = ENV_LOCK.lock().unwrap();
         let _baseline = reset_config_env_baseline();
-        let _guard = EnvVarGuard::set(ENV_ADDR_KEY, "127.0.0.1:9090");
 
         let config = Config::from_env().unwrap();
+        assert_eq!(config.loss_streak_limit, 3);
+        assert_eq!(config.loss_streak_cooldown_secs, 300);
 
-        assert_eq!(config.listen_addr, "127.0.0.1:9090".parse().unwrap());
+        let _limit = EnvVarGuard::set(ENV_LOSS_STREAK_LIMIT_KEY, "5");
+        let _cooldown = EnvVarGuard::set(ENV_LOSS_STREAK_COOLDOWN_KEY, "900");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.loss_streak_limit, 5);
+        assert_eq!(config.loss_streak_cooldown_secs, 900);
+
+        let _cooldown = EnvVarGuard::set(ENV_LOSS_STREAK_COOLDOWN_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidLossStreakCooldownSecs
+        ));
     }
 
     #[test]
-    fn returns_error_for_invalid_listen_address_override() {
+    fn max_spread_accepts_absolute_or_percentage_widths() {
         let _lock = ENV_LOCK.lock().unwrap();
         let _baseline = reset_config_env_baseline();
-        let _guard = EnvVarGuard::set(ENV_ADDR_KEY, "not-an-addr");
 
-        let err = Config::from_env().unwrap_err();
+        assert_eq!(
+            Config::from_env().unwrap().max_spread,
+            SpreadLimit::Absolute(0.05)
+        );
 
-        assert!(matches!(err, ConfigError::InvalidListenAddr(_)));
+        let _spread = EnvVarGuard::set(ENV_MAX_SPREAD_KEY, "0.1");
+        assert_eq!(
+            Config::from_env().unwrap().max_spread,
+            SpreadLimit::Absolute(0.1)
+        );
+
+        let _spread = EnvVarGuard::set(ENV_MAX_SPREAD_KEY, "8%");
+        assert_eq!(
+            Config::from_env().unwrap().max_spread,
+            SpreadLimit::Relative(0.08)
+        );
+
+        for invalid in ["0", "1.5", "150%", "wide"] {
+            let _spread = EnvVarGuard::set(ENV_MAX_SPREAD_KEY, invalid);
+            assert!(matches!(
+                Config::from_env().unwrap_err(),
+                ConfigError::InvalidMaxSpread
+            ));
+        }
     }
 
     #[test]
-    fn defaults_to_paper_live_mode() {
+    fn min_touch_qty_defaults_and_may_be_zero() {
         let _lock = ENV_LOCK.lock().unwrap();
         let _baseline = reset_config_env_baseline();
 
-        let cfg = Config::from_env().unwrap();
+        assert_eq!(Config::from_env().unwrap().min_touch_qty, 5.0);
 
-        assert_eq!(cfg.mode, RunMode::PaperLive);
+        let _touch = EnvVarGuard::set(ENV_MIN_TOUCH_QTY_KEY, "0");
+        assert_eq!(Config::from_env().unwrap().min_touch_qty, 0.0);
+
+        let _touch = EnvVarGuard::set(ENV_MIN_TOUCH_QTY_KEY, "-1");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidMinTouchQty
+        ));
     }
 
     #[test]
-    fn defaults_execution_mode_to_paper() {
+    fn webhooks_are_off_by_default_and_validate_urls() {
         let _lock = ENV_LOCK.lock().unwrap();
         let _baseline = reset_config_env_baseline();
 
-        let cfg = Config::from_env().unwrap();
+        let webhooks = Config::from_env().unwrap().webhooks;
+        assert!(webhooks.urls.is_empty());
+        assert_eq!((webhooks.secret, webhooks.max_attempts), (None, 5));
 
-        assert_eq!(cfg.execution_mode, ExecutionMode::Paper);
+        let _urls = EnvVarGuard::set(
+            ENV_WEBHOOK_URLS_KEY,
+            "https://hooks.example.com/a, http://10.0.0.2:9000/page,",
+        );
+        let _secret = EnvVarGuard::set(ENV_WEBHOOK_SECRET_KEY, "s3cret");
+        let _attempts = EnvVarGuard::set(ENV_WEBHOOK_MAX_ATTEMPTS_KEY, "3");
+        assert_eq!(
+            Config::from_env().unwrap().webhooks,
+            WebhookConfig {
+                urls: vec![
+                    "https://hooks.example.com/a".to_string(),
+                    "http://10.0.0.2:9000/page".to_string(),
+                ],
+                secret: Some("s3cret".to_string()),
+                max_attempts: 3,
+            }
+        );
+
+        let _attempts = EnvVarGuard::set(ENV_WEBHOOK_MAX_ATTEMPTS_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidWebhookMaxAttempts
+        ));
+        let _urls = EnvVarGuard::set(ENV_WEBHOOK_URLS_KEY, "hooks.example.com");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidWebhookUrls
+        ));
     }
 
     #[test]
-    fn defaults_lag_threshold_and_risk_caps() {
+    fn chat_alerts_need_a_url_and_take_category_flags() {
         let _lock = ENV_LOCK.lock().unwrap();
         let _baseline = reset_config_env_baseline();
 
-        let cfg = Config::from_env().unwrap();
+        let _alerts = EnvVarGuard::set(ENV_CHAT_ALERTS_KEY, "halts");
+        assert_eq!(Config::from_env().unwrap().chat_alerts, None);
+
+        let _url = EnvVarGuard::set(ENV_CHAT_WEBHOOK_URL_KEY, "https://hooks.slack.com/x");
+        let _alerts = EnvVarGuard::set(ENV_CHAT_ALERTS_KEY, "halts, stale_feeds,halts");
+        let _max = EnvVarGuard::set(ENV_CHAT_MAX_ALERTS_KEY, "2");
+        assert_eq!(
+            Config::from_env().unwrap().chat_alerts,
+            Some(ChatAlertConfig {
+                url: "https://hooks.slack.com/x".to_string(),
+                categories: vec![AlertCategory::Halts, AlertCategory::StaleFeeds],
+                large_fill_usd: 50.0,
+                max_per_min: 2,
+            })
+        );
 
-        assert_eq!(cfg.lag_threshold_pct, 0.3);
-        assert_eq!(cfg.per_trade_risk_pct, 0.5);
-        assert_eq!(cfg.daily_loss_cap_pct, 2.0);
+        let _alerts = EnvVarGuard::set(ENV_CHAT_ALERTS_KEY, "halts,pnl");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidChatAlerts
+        ));
+        let _alerts = EnvVarGuard::set(ENV_CHAT_ALERTS_KEY, "all");
+        let _fill = EnvVarGuard::set(ENV_CHAT_LARGE_FILL_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidChatLargeFillUsd
+        ));
+    }
+
+    #[test]
+    fn redis_mirror_needs_a_url_and_takes_a_channel_prefix() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let _prefix = EnvVarGuard::set(ENV_REDIS_CHANNEL_PREFIX_KEY, "desk1");
+        assert_eq!(Config::from_env().unwrap().redis_mirror, None);
+
+        let _url = EnvVarGuard::set(ENV_REDIS_URL_KEY, "redis://:pw@127.0.0.1:6380");
+        assert_eq!(
+            Config::from_env().unwrap().redis_mirror,
+            Some(RedisMirrorConfig {
+                addr: "127.0.0.1:6380".to_string(),
+                username: None,
+                password: Some("pw".to_string()),
+                channel_prefix: "desk1".to_string(),
+            })
+        );
+
+        let _prefix = EnvVarGuard::unset(ENV_REDIS_CHANNEL_PREFIX_KEY);
+        let _url = EnvVarGuard::set(ENV_REDIS_URL_KEY, "redis://localhost");
+        let mirror = Config::from_env().unwrap().redis_mirror.unwrap();
+        assert_eq!(mirror.addr, "localhost:6379");
+        assert_eq!(mirror.channel("risk_alert"), "lab:events:risk_alert");
+
+        let _url = EnvVarGuard::set(ENV_REDIS_URL_KEY, "http://localhost:6379");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidRedisUrl
+        ));
+        let _url = EnvVarGuard::set(ENV_REDIS_URL_KEY, "redis://localhost");
+        let _prefix = EnvVarGuard::set(ENV_REDIS_CHANNEL_PREFIX_KEY, "lab events");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidRedisChannelPrefix
+        ));
+    }
+
+    #[test]
+    fn clob_credentials_are_all_or_nothing() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().clob, None);
+
+        let key = format!("0x{}", "ab".repeat(32));
+        let _key = EnvVarGuard::set(ENV_CLOB_PRIVATE_KEY_KEY, &key);
+        let _api_key = EnvVarGuard::set(ENV_CLOB_API_KEY_KEY, "key-1");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::IncompleteClobCredentials
+        ));
+
+        let _secret = EnvVarGuard::set(ENV_CLOB_API_SECRET_KEY, "c2VjcmV0");
+        let _passphrase = EnvVarGuard::set(ENV_CLOB_API_PASSPHRASE_KEY, "pass");
+        let clob = Config::from_env().unwrap().clob.unwrap();
+        assert_eq!(clob.private_key, key);
+        assert_eq!(clob.api_secret, "c2VjcmV0");
+        assert!(!format!("{clob:?}").contains("c2VjcmV0"));
+
+        let _key = EnvVarGuard::set(ENV_CLOB_PRIVATE_KEY_KEY, "0x1234");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidClobPrivateKey
+        ));
+    }
+
+    #[test]
+    fn statsd_needs_an_address_and_takes_prefix_and_tags() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let _tags = EnvVarGuard::set(ENV_STATSD_TAGS_KEY, "env:paper");
+        assert_eq!(Config::from_env().unwrap().statsd, None);
+
+        let _addr = EnvVarGuard::set(ENV_STATSD_ADDR_KEY, "localhost:8125");
+        let _tags = EnvVarGuard::set(ENV_STATSD_TAGS_KEY, "env:paper, region:eu,canary");
+        assert_eq!(
+            Config::from_env().unwrap().statsd,
+            Some(StatsdConfig {
+                addr: "localhost:8125".to_string(),
+                prefix: "lab".to_string(),
+                tags: vec![
+                    "env:paper".to_string(),
+                    "region:eu".to_string(),
+                    "canary".to_string()
+                ],
+            })
+        );
+
+        let _tags = EnvVarGuard::set(ENV_STATSD_TAGS_KEY, "env:paper|x");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidStatsdTags
+        ));
+        let _tags = EnvVarGuard::unset(ENV_STATSD_TAGS_KEY);
+        let _prefix = EnvVarGuard::set(ENV_STATSD_PREFIX_KEY, "lab.");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidStatsdPrefix
+        ));
+        let _prefix = EnvVarGuard::unset(ENV_STATSD_PREFIX_KEY);
+        let _addr = EnvVarGuard::set(ENV_STATSD_ADDR_KEY, "localhost");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidStatsdAddr
+        ));
+    }
+
+    #[test]
+    fn proxies_fall_back_to_the_environment_and_take_venue_overrides() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+        assert_eq!(Config::from_env().unwrap().proxies, ProxyConfig::default());
+
+        let _http = EnvVarGuard::set("HTTP_PROXY", "http://plain.corp:8080");
+        let _https = EnvVarGuard::set("HTTPS_PROXY", "proxy.corp:3128");
+        let _venues = EnvVarGuard::set(
+            ENV_VENUE_PROXIES_KEY,
+            "kalshi=none, binance=http://u:p@eu.corp:3128",
+        );
+        let proxies = Config::from_env().unwrap().proxies;
+        assert_eq!(
+            proxies.for_venue("coinbase").map(HttpProxy::url),
+            Some("http://proxy.corp:3128")
+        );
+        assert_eq!(proxies.for_venue("kalshi"), None);
+        assert_eq!(
+            proxies.for_venue("binance").map(HttpProxy::url),
+            Some("http://u:p@eu.corp:3128")
+        );
+
+        let _url = EnvVarGuard::set(ENV_PROXY_URL_KEY, "none");
+        let proxies = Config::from_env().unwrap().proxies;
+        assert_eq!(proxies.for_venue("coinbase"), None);
+        assert!(proxies.for_venue("binance").is_some());
+
+        let _url = EnvVarGuard::set(ENV_PROXY_URL_KEY, "socks5://proxy.corp:1080");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidProxyUrl("LAB_PROXY_URL", HttpProxyError::UnsupportedScheme)
+        ));
+        let _url = EnvVarGuard::unset(ENV_PROXY_URL_KEY);
+        let _venues = EnvVarGuard::set(ENV_VENUE_PROXIES_KEY, "binanse=none");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidVenueProxies
+        ));
+    }
+
+    #[test]
+    fn regime_thresholds_must_be_ordered() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.regime_thresholds.calm_below_bps(), 4.0);
+        assert_eq!(config.regime_thresholds.volatile_above_bps(), 12.0);
+
+        let _calm = EnvVarGuard::set(ENV_REGIME_CALM_KEY, "2.5");
+        let _volatile = EnvVarGuard::set(ENV_REGIME_VOLATILE_KEY, "20");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.regime_thresholds.calm_below_bps(), 2.5);
+        assert_eq!(config.regime_thresholds.volatile_above_bps(), 20.0);
+
+        let _volatile = EnvVarGuard::set(ENV_REGIME_VOLATILE_KEY, "2");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidRegimeVolatileBps
+        ));
+        let _calm = EnvVarGuard::set(ENV_REGIME_CALM_KEY, "0");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidRegimeCalmBps
+        ));
     }
 
     #[test]
@@ -431,6 +3236,75 @@ mod tests {
         assert_eq!(cfg.mode, RunMode::Sim);
     }
 
+    #[test]
+    fn replay_mode_requires_a_replay_input_dir() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+        let _mode = EnvVarGuard::set(ENV_MODE_KEY, "replay");
+
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::MissingReplayInputDir
+        ));
+
+        let _dir = EnvVarGuard::set(ENV_REPLAY_INPUT_DIR_KEY, "artifacts/ticks");
+        let cfg = Config::from_env().unwrap();
+        assert_eq!(cfg.mode, RunMode::Replay);
+        assert_eq!(cfg.replay_input_dir.as_deref(), Some("artifacts/ticks"));
+    }
+
+    #[test]
+    fn tls_needs_both_cert_and_key_and_carries_the_redirect_addr() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().tls, None);
+
+        let _cert = EnvVarGuard::set(ENV_TLS_CERT_KEY, "certs/lab.pem");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::IncompleteTlsConfig
+        ));
+
+        let _key = EnvVarGuard::set(ENV_TLS_KEY_KEY, "certs/lab.key");
+        let _redirect = EnvVarGuard::set(ENV_TLS_REDIRECT_KEY, "0.0.0.0:8081");
+        assert_eq!(
+            Config::from_env().unwrap().tls,
+            Some(TlsConfig {
+                cert_path: "certs/lab.pem".to_string(),
+                key_path: "certs/lab.key".to_string(),
+                redirect_addr: Some("0.0.0.0:8081".parse().unwrap()),
+            })
+        );
+
+        let _cert = EnvVarGuard::unset(ENV_TLS_CERT_KEY);
+        let _key = EnvVarGuard::unset(ENV_TLS_KEY_KEY);
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::RedirectWithoutTls
+        ));
+    }
+
+    #[test]
+    fn grpc_is_off_unless_an_address_is_set() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let _baseline = reset_config_env_baseline();
+
+        assert_eq!(Config::from_env().unwrap().grpc_addr, None);
+
+        let _addr = EnvVarGuard::set(ENV_GRPC_ADDR_KEY, "127.0.0.1:50051");
+        assert_eq!(
+            Config::from_env().unwrap().grpc_addr,
+            Some("127.0.0.1:50051".parse().unwrap())
+        );
+
+        let _addr = EnvVarGuard::set(ENV_GRPC_ADDR_KEY, "50051");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidGrpcAddr(_)
+        ));
+    }
+
     #[test]
     fn returns_error_for_invalid_mode_override() {
         let _lock = ENV_LOCK.lock().unwrap();
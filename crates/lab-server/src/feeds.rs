@@ -0,0 +1,432 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use runtime::clock::{Clock, SystemClock};
+use runtime::live::{
+    run_venue_feed, Asset, BtcAggregator, BtcComposite, BtcWsFeedConfig, EwmaSmoother,
+    FeedReconnect, MarketSelection, MedianAggregator, NormalizedBtcTick, PolymarketQuoteTick,
+    QuarantinedPayload, QuoteSource, ReconnectBackoff, ReconnectPolicy, RejectedPayload,
+    ReplayFeed, SharedSupervisor, SharedVenueBoard, VenueBoard, VenueFeed, VenueFeedError,
+    VenueHealth, VwapAggregator, WsBtcFeed,
+};
+use runtime::recorder::TickRecordSender;
+use runtime::supervisor::{Supervisor, TaskId};
+
+use crate::config::{BtcAggregatorMode, BtcCompositeConfig, BtcOutlierMode, ProxyConfig};
+use crate::http::{market_data_client, RateLimitedClient};
+use crate::quotes::PolledQuoteFeed;
+
+/// Venue ticks older than this (relative to wall clock) are not reported.
+pub const BTC_FEED_STALENESS_MS: u64 = 5_000;
+const BTC_FEED_OUTLIER_BPS: f64 = 200.0;
+/// Robust band width in scaled MADs, and the narrowest band it may shrink to.
+const BTC_FEED_OUTLIER_MAD_K: f64 = 3.0;
+const BTC_FEED_OUTLIER_FLOOR_BPS: f64 = 5.0;
+
+/// Shared state for every market data venue the lab consumes.
+#[derive(Clone)]
+pub struct MarketFeeds {
+    pub board: SharedVenueBoard,
+    pub supervisor: SharedSupervisor,
+    /// How long a venue that blocks this host is left alone.
+    blocked_cooldown_ms: u64,
+    /// Time ticks are received at, which the decision loop ages them against.
+    clock: Arc<dyn Clock>,
+}
+
+impl MarketFeeds {
+    pub fn new(composite: BtcCompositeConfig) -> Self {
+        let aggregator: BtcAggregator = match (composite.aggregator, composite.outlier_mode) {
+            (BtcAggregatorMode::Median, BtcOutlierMode::Fixed) => {
+                MedianAggregator::new(BTC_FEED_STALENESS_MS, BTC_FEED_OUTLIER_BPS)
+                    .expect("static median aggregator parameters are valid")
+                    .into()
+            }
+            (BtcAggregatorMode::Median, BtcOutlierMode::Mad) => MedianAggregator::robust(
+                BTC_FEED_STALENESS_MS,
+                BTC_FEED_OUTLIER_MAD_K,
+                BTC_FEED_OUTLIER_FLOOR_BPS,
+            )
+            .expect("static median aggregator parameters are valid")
+            .into(),
+            (BtcAggregatorMode::Vwap, _) => VwapAggregator::new(composite.vwap_window_ms)
+                .expect("config bounds the vwap window above zero")
+                .into(),
+        };
+        let mut board = VenueBoard::new(aggregator);
+        board.set_smoother(
+            EwmaSmoother::new(composite.ewma_half_life_ms)
+                .expect("config bounds the half-life above zero"),
+        );
+
+        Self {
+            board: Arc::new(Mutex::new(board)),
+            supervisor: Arc::new(Mutex::new(Supervisor::new())),
+            blocked_cooldown_ms: ReconnectPolicy::default().blocked_cooldown_ms,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    pub fn with_blocked_cooldown_ms(mut self, blocked_cooldown_ms: u64) -> Self {
+        self.blocked_cooldown_ms = blocked_cooldown_ms;
+        self
+    }
+
+    /// Receives ticks at `clock`'s time; it should be the one the decision
+    /// loop runs on.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Backoff for every source the lab polls or streams.
+    pub fn reconnect_policy(&self) -> ReconnectPolicy {
+        ReconnectPolicy {
+            blocked_cooldown_ms: self.blocked_cooldown_ms,
+            ..ReconnectPolicy::default()
+        }
+    }
+
+    /// Spawns one supervised task per venue adapter: a trade stream per venue
+    /// and asset, and one polled quote feed per prediction market venue
+    /// covering every asset, polled every `quote_poll`.
+    ///
+    /// Adding a venue only means adding its adapter here; the strategy loop
+    /// reads everything back through the shared board.
+    ///
+    /// `client` already goes through the default proxy; venues with their own
+    /// proxy get a client of their own that shares its rate limits.
+    pub fn spawn_all(
+        &self,
+        client: &RateLimitedClient,
+        proxies: &ProxyConfig,
+        assets: &[Asset],
+        selection: &MarketSelection,
+        quote_poll: Duration,
+    ) -> reqwest::Result<()> {
+        let mut adapters: Vec<Box<dyn VenueFeed>> = assets
+            .iter()
+            .flat_map(|asset| BtcWsFeedConfig::all(*asset))
+            .map(|mut config| {
+                // Labels of non-BTC feeds carry an asset suffix, e.g. `okx-eth`.
+                let venue = config.venue.split('-').next().unwrap_or_default();
+                config.proxy = proxies.for_venue(venue).cloned();
+                config.reconnect.blocked_cooldown_ms = self.blocked_cooldown_ms;
+                Box::new(WsBtcFeed::new(config)) as Box<dyn VenueFeed>
+            })
+            .collect();
+        for source in [QuoteSource::Polymarket, QuoteSource::Kalshi] {
+            let client = if proxies.venues.contains_key(source.as_str()) {
+                client.with_client(market_data_client(proxies.for_venue(source.as_str()))?)
+            } else {
+                client.clone()
+            };
+            adapters.push(Box::new(
+                PolledQuoteFeed::new(
+                    source,
+                    assets.to_vec(),
+                    selection.clone(),
+                    client,
+                    quote_poll,
+                )
+                .with_reconnect_policy(self.reconnect_policy())
+                .with_clock(self.clock.clone()),
+            ));
+        }
+        self.spawn_adapters(adapters);
+        Ok(())
+    }
+
+    /// Spawns recorded feeds in place of the live venues, so a session can be
+    /// reproduced without network access.
+    pub fn spawn_replay(&self, feeds: Vec<ReplayFeed>) {
+        self.spawn_adapters(
+            feeds
+                .into_iter()
+                .map(|feed| Box::new(feed) as Box<dyn VenueFeed>)
+                .collect(),
+        );
+    }
+
+    fn spawn_adapters(&self, adapters: Vec<Box<dyn VenueFeed>>) {
+        for (idx, adapter) in adapters.into_iter().enumerate() {
+            tokio::spawn(run_venue_feed(
+                adapter,
+                self.board.clone(),
+                self.supervisor.clone(),
+                TaskId(idx as u64 + 1),
+                self.clock.clone(),
+            ));
+        }
+    }
+
+    /// Latest streamed `asset` tick for `venue` if it is no older than the
+    /// staleness window.
+    pub fn fresh_tick(&self, asset: Asset, venue: &str, now_ms: u64) -> Option<NormalizedBtcTick> {
+        self.lock_board()
+            .latest_spot(asset, venue)
+            .filter(|tick| now_ms.saturating_sub(tick.ts) <= BTC_FEED_STALENESS_MS)
+            .cloned()
+    }
+
+    /// Fresh `asset` prices from every streaming venue, in registration order.
+    pub fn fresh_spot_prices(&self, asset: Asset, now_ms: u64) -> Vec<f64> {
+        let board = self.lock_board();
+        board
+            .update_counts()
+            .iter()
+            .filter_map(|(venue, _)| board.latest_spot(asset, venue))
+            .filter(|tick| now_ms.saturating_sub(tick.ts) <= BTC_FEED_STALENESS_MS)
+            .map(|tick| tick.px)
+            .collect()
+    }
+
+    /// Raw `asset` composite and its EWMA-smoothed value.
+    pub fn composite(&self, asset: Asset) -> Option<BtcComposite> {
+        self.lock_board().spot_composite(asset)
+    }
+
+    /// Quotes from every prediction market venue, in registration order.
+    pub fn quotes(&self) -> Vec<PolymarketQuoteTick> {
+        self.lock_board().quotes()
+    }
+
+    /// `(venue, updates)` pairs in registration order.
+    pub fn update_counts(&self) -> Vec<(String, u64)> {
+        self.lock_board().update_counts()
+    }
+
+    pub fn health(&self, venue: &str) -> Option<VenueHealth> {
+        self.lock_board().health(venue)
+    }
+
+    /// Endpoint `venue` last connected to, when its feed has fallbacks.
+    pub fn endpoint(&self, venue: &str) -> Option<String> {
+        self.lock_board().endpoint(venue).map(str::to_string)
+    }
+
+    /// Copies every tick the venue tasks receive to the tick recorder.
+    pub fn set_recorder(&self, recorder: TickRecordSender) {
+        self.lock_board().set_recorder(recorder);
+    }
+
+    /// Keeps a payload `source` could not parse for `/feed/errors`.
+    pub fn quarantine(&self, source: &str, rejected: RejectedPayload, ts_ms: u64) {
+        self.lock_board().quarantine(source, vec![rejected], ts_ms);
+    }
+
+    /// Parse failures per source and the most recent rejected payloads.
+    pub fn quarantine_report(&self) -> (Vec<(String, u64)>, Vec<QuarantinedPayload>) {
+        let board = self.lock_board();
+        (board.quarantine_counts(), board.quarantined())
+    }
+
+    /// Reconnects scheduled by venue tasks since the last call.
+    pub fn drain_reconnects(&self) -> Vec<FeedReconnect> {
+        self.lock_board().drain_reconnects()
+    }
+
+    fn lock_board(&self) -> MutexGuard<'_, VenueBoard> {
+        self.board
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Backoff and circuit breaker for HTTP sources polled by their own task
+/// rather than through a [`VenueFeed`] adapter.
+pub struct PollGate {
+    source: String,
+    backoff: ReconnectBackoff,
+    retry_at_ms: u64,
+}
+
+impl PollGate {
+    pub fn new(source: &str, policy: ReconnectPolicy) -> Self {
+        Self {
+            source: source.to_string(),
+            backoff: ReconnectBackoff::new(policy),
+            retry_at_ms: 0,
+        }
+    }
+
+    /// Runs `fetch` unless the source is backing off.
+    ///
+    /// `fetch` resolves to `None` when the source is not configured, which is
+    /// neither a success nor a failure. A failure schedules the next attempt
+    /// and is returned so the caller can publish it.
+    pub async fn poll<T, F>(&mut self, now_ms: u64, fetch: F) -> (Option<T>, Option<FeedReconnect>)
+    where
+        F: Future<Output = Option<Result<T, VenueFeedError>>>,
+    {
+        if now_ms < self.retry_at_ms {
+            return (None, None);
+        }
+
+        match fetch.await {
+            None => (None, None),
+            Some(Ok(value)) => {
+                self.backoff.record_success();
+                (Some(value), None)
+            }
+            Some(Err(reason)) => {
+                let reconnect = self.backoff.record_failure(&self.source, reason);
+                self.retry_at_ms = now_ms.saturating_add(reconnect.delay_ms);
+                (None, Some(reconnect))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use runtime::live::VenueTick;
+
+    fn ingest(feeds: &MarketFeeds, venue: &str, px: f64, now_ms: u64) {
+        apply(feeds, Asset::Btc, venue, px, 0.0, now_ms);
+    }
+
+    fn apply(feeds: &MarketFeeds, asset: Asset, venue: &str, px: f64, size: f64, now_ms: u64) {
+        feeds.lock_board().apply(
+            venue,
+            VenueTick::Spot(
+                asset,
+                NormalizedBtcTick {
+                    venue: venue.to_string(),
+                    px,
+                    size,
+                    ts: now_ms,
+                },
+            ),
+        );
+    }
+
+    #[tokio::test]
+    async fn poll_gate_backs_off_after_failures_and_resets_on_success() {
+        let mut gate = PollGate::new("tradingview", ReconnectPolicy::default());
+
+        let (value, reconnect) = gate
+            .poll(1_000, async { Some(Err::<u8, _>(VenueFeedError::Request)) })
+            .await;
+        let reconnect = reconnect.unwrap();
+        assert!(value.is_none());
+        assert_eq!(reconnect.attempt, 1);
+        assert!(reconnect.delay_ms > 0);
+
+        let (skipped, none) = gate.poll(1_000, async { Some(Ok(7_u8)) }).await;
+        assert_eq!((skipped, none), (None, None));
+
+        let (value, reconnect) = gate.poll(100_000, async { Some(Ok(7_u8)) }).await;
+        assert_eq!(value, Some(7));
+        assert!(reconnect.is_none());
+
+        let (value, reconnect) = gate.poll(100_001, async { None::<Result<u8, _>> }).await;
+        assert_eq!((value, reconnect), (None, None));
+    }
+
+    #[test]
+    fn fresh_tick_drops_stale_venue_ticks() {
+        let feeds = median_feeds();
+        ingest(&feeds, "coinbase", 64_000.0, 10_000);
+
+        assert_eq!(
+            feeds
+                .fresh_tick(Asset::Btc, "coinbase", 14_000)
+                .map(|tick| tick.px),
+            Some(64_000.0)
+        );
+        assert!(feeds.fresh_tick(Asset::Btc, "coinbase", 16_000).is_none());
+        assert!(feeds.fresh_tick(Asset::Btc, "kraken", 10_000).is_none());
+    }
+
+    #[test]
+    fn fresh_spot_prices_cover_every_streaming_venue() {
+        let feeds = median_feeds();
+        for (venue, px) in [
+            ("coinbase", 64_000.0),
+            ("okx", 64_001.0),
+            ("bybit", 64_002.0),
+            ("bitstamp", 64_003.0),
+        ] {
+            ingest(&feeds, venue, px, 10_000);
+        }
+        ingest(&feeds, "kraken", 64_004.0, 2_000);
+
+        assert_eq!(
+            feeds.fresh_spot_prices(Asset::Btc, 12_000),
+            vec![64_000.0, 64_001.0, 64_002.0, 64_003.0]
+        );
+    }
+
+    #[test]
+    fn update_counts_track_every_applied_tick() {
+        let feeds = median_feeds();
+        ingest(&feeds, "binance", 64_000.0, 10_000);
+        ingest(&feeds, "binance", 64_005.0, 11_200);
+
+        assert_eq!(feeds.update_counts(), vec![("binance".to_string(), 2)]);
+    }
+
+    #[test]
+    fn composite_combines_streamed_venues() {
+        let feeds = median_feeds();
+        ingest(&feeds, "coinbase", 64_000.0, 10_000);
+        assert!(feeds.composite(Asset::Btc).is_none());
+
+        ingest(&feeds, "binance", 64_020.0, 10_100);
+        let composite = feeds.composite(Asset::Btc).unwrap();
+        assert_eq!(composite.raw.px_median, 64_010.0);
+        assert_eq!(composite.smoothed_px, 64_010.0);
+    }
+
+    #[test]
+    fn vwap_mode_weights_composite_by_trade_size() {
+        let feeds = MarketFeeds::new(BtcCompositeConfig {
+            aggregator: BtcAggregatorMode::Vwap,
+            vwap_window_ms: 5_000,
+            ..BtcCompositeConfig::default()
+        });
+        apply(&feeds, Asset::Btc, "coinbase", 64_000.0, 3.0, 10_000);
+        apply(&feeds, Asset::Btc, "kraken", 64_100.0, 1.0, 10_100);
+
+        assert_eq!(
+            feeds
+                .composite(Asset::Btc)
+                .map(|composite| composite.raw.px_median),
+            Some(64_025.0)
+        );
+    }
+
+    #[test]
+    fn composites_and_fresh_prices_are_kept_per_asset() {
+        let feeds = median_feeds();
+        ingest(&feeds, "coinbase", 64_000.0, 10_000);
+        ingest(&feeds, "binance", 64_020.0, 10_000);
+        apply(&feeds, Asset::Eth, "coinbase-eth", 3_400.0, 0.0, 10_000);
+        apply(&feeds, Asset::Eth, "binance-eth", 3_404.0, 0.0, 10_000);
+
+        assert_eq!(
+            feeds.fresh_spot_prices(Asset::Eth, 10_500),
+            vec![3_400.0, 3_404.0]
+        );
+        assert_eq!(
+            feeds
+                .composite(Asset::Eth)
+                .map(|composite| composite.raw.px_median),
+            Some(3_402.0)
+        );
+        assert_eq!(
+            feeds
+                .composite(Asset::Btc)
+                .map(|composite| composite.raw.px_median),
+            Some(64_010.0)
+        );
+        assert!(feeds.composite(Asset::Sol).is_none());
+    }
+
+    fn median_feeds() -> MarketFeeds {
+        MarketFeeds::new(BtcCompositeConfig::default())
+    }
+}
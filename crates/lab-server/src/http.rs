@@ -0,0 +1,577 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER};
+use reqwest::{Client, NoProxy, Proxy, RequestBuilder, Response, StatusCode};
+use runtime::live::{HttpProxy, VenueFeedError};
+
+/// Used when a 429 carries no usable `Retry-After` header.
+const DEFAULT_RETRY_AFTER_MS: u64 = 30_000;
+/// Throttle events kept until the strategy loop drains them.
+const MAX_PENDING_THROTTLES: usize = 256;
+/// Requests one [`RateLimitedClient::get_texts`] call keeps in flight, and
+/// idle connections kept per host for the next round.
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Token bucket parameters for one host.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostLimit {
+    pub capacity: f64,
+    pub refill_per_sec: f64,
+}
+
+impl HostLimit {
+    const DEFAULT: Self = Self {
+        capacity: 10.0,
+        refill_per_sec: 2.0,
+    };
+
+    /// Conservative budgets for hosts the lab polls, well under each venue's
+    /// published public limit.
+    fn for_host(host: &str) -> Self {
+        match host {
+            "gamma-api.polymarket.com" | "clob.polymarket.com" | "data-api.polymarket.com" => {
+                Self {
+                    capacity: 5.0,
+                    refill_per_sec: 1.0,
+                }
+            }
+            "api.elections.kalshi.com" => Self {
+                capacity: 5.0,
+                refill_per_sec: 2.0,
+            },
+            "api.binance.com" | "data-api.binance.vision" => Self {
+                capacity: 20.0,
+                refill_per_sec: 5.0,
+            },
+            _ => Self::DEFAULT,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    limit: HostLimit,
+    tokens: f64,
+    refilled_at: Instant,
+    blocked_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(limit: HostLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: limit.capacity,
+            refilled_at: now,
+            blocked_until: None,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> Result<(), ThrottleReason> {
+        if self.blocked_until.is_some_and(|until| now < until) {
+            return Err(ThrottleReason::RetryAfter);
+        }
+        self.blocked_until = None;
+
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.refill_per_sec).min(self.limit.capacity);
+        self.refilled_at = now;
+
+        if self.tokens < 1.0 {
+            return Err(ThrottleReason::TokenBucket);
+        }
+        self.tokens -= 1.0;
+        Ok(())
+    }
+
+    fn block_for(&mut self, now: Instant, retry_after: Duration) {
+        self.blocked_until = Some(now + retry_after);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleReason {
+    /// The host answered 429 and we are honoring its `Retry-After`.
+    RetryAfter,
+    /// The local per-host budget is spent.
+    TokenBucket,
+}
+
+impl ThrottleReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RetryAfter => "retry_after",
+            Self::TokenBucket => "token_bucket",
+        }
+    }
+}
+
+/// A request that was held back or rejected by rate limiting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottleEvent {
+    pub host: String,
+    pub reason: ThrottleReason,
+    /// Set when the host sent a 429; how long requests are held back.
+    pub retry_after_ms: Option<u64>,
+}
+
+/// Validators of the last response to a conditional GET, sent back so an
+/// unchanged resource is neither downloaded nor parsed again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// For servers that send no validators, or ignore them.
+    body_hash: Option<u64>,
+}
+
+/// Answer to [`RateLimitedClient::get_text_if_changed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conditional {
+    /// The server answered 304, or sent the cached body again.
+    Unchanged,
+    /// New content, with the validators to keep once it has been used.
+    Changed {
+        body: String,
+        validators: CacheValidators,
+    },
+}
+
+/// Conditional GETs answered since startup, by outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The server answered 304 Not Modified.
+    pub not_modified: u64,
+    /// The body was downloaded but matched the cached one.
+    pub unchanged: u64,
+    pub changed: u64,
+}
+
+impl CacheStats {
+    pub fn hits(&self) -> u64 {
+        self.not_modified + self.unchanged
+    }
+}
+
+#[derive(Debug, Default)]
+struct Limiter {
+    buckets: HashMap<String, TokenBucket>,
+    pending: Vec<ThrottleEvent>,
+}
+
+impl Limiter {
+    fn bucket(&mut self, host: &str, now: Instant) -> &mut TokenBucket {
+        self.buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(HostLimit::for_host(host), now))
+    }
+
+    fn record(&mut self, event: ThrottleEvent) {
+        if self.pending.len() >= MAX_PENDING_THROTTLES {
+            self.pending.remove(0);
+        }
+        self.pending.push(event);
+    }
+}
+
+/// Shared HTTP client for every polled source.
+///
+/// Requests draw from a per-host token bucket and a 429 blocks the host for
+/// its `Retry-After`; in both cases the call fails fast with
+/// [`VenueFeedError::Throttled`] so the caller's backoff takes over.
+#[derive(Clone)]
+pub struct RateLimitedClient {
+    client: Client,
+    limiter: Arc<Mutex<Limiter>>,
+    cache_stats: Arc<Mutex<CacheStats>>,
+}
+
+impl RateLimitedClient {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            limiter: Arc::new(Mutex::new(Limiter::default())),
+            cache_stats: Arc::default(),
+        }
+    }
+
+    /// A client that sends through `client` but draws from the same host
+    /// budgets, for venues routed through their own proxy.
+    pub fn with_client(&self, client: Client) -> Self {
+        Self {
+            client,
+            limiter: self.limiter.clone(),
+            cache_stats: self.cache_stats.clone(),
+        }
+    }
+
+    pub async fn get_text(&self, url: &str) -> Result<String, VenueFeedError> {
+        self.send(url, self.client.get(url))
+            .await?
+            .text()
+            .await
+            .map_err(|_| VenueFeedError::Request)
+    }
+
+    /// GETs every URL concurrently, at most eight at a time, and gives each
+    /// one up after `timeout`. Results come back in the order of `urls`.
+    pub async fn get_texts(
+        &self,
+        urls: &[String],
+        timeout: Duration,
+    ) -> Vec<Result<String, VenueFeedError>> {
+        // Built up front: a stream closure borrowing `url` would keep the
+        // returned future from being `Send`.
+        let requests: Vec<_> = urls
+            .iter()
+            .map(|url| self.get_text_within(url, timeout))
+            .collect();
+        stream::iter(requests)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await
+    }
+
+    async fn get_text_within(
+        &self,
+        url: &str,
+        timeout: Duration,
+    ) -> Result<String, VenueFeedError> {
+        tokio::time::timeout(timeout, self.get_text(url))
+            .await
+            .unwrap_or(Err(VenueFeedError::Request))
+    }
+
+    /// GETs `url` with the validators of the copy the caller already has,
+    /// and tells it whether that copy is still current. A 200 whose body
+    /// hashes the same as `cached` counts as unchanged too.
+    pub async fn get_text_if_changed(
+        &self,
+        url: &str,
+        cached: &CacheValidators,
+    ) -> Result<Conditional, VenueFeedError> {
+        let mut request = self.client.get(url);
+        if let Some(etag) = &cached.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = self.send(url, request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            self.lock_cache_stats().not_modified += 1;
+            return Ok(Conditional::Unchanged);
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await.map_err(|_| VenueFeedError::Request)?;
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        let body_hash = hasher.finish();
+
+        let mut stats = self.lock_cache_stats();
+        if cached.body_hash == Some(body_hash) {
+            stats.unchanged += 1;
+            return Ok(Conditional::Unchanged);
+        }
+        stats.changed += 1;
+        Ok(Conditional::Changed {
+            body,
+            validators: CacheValidators {
+                etag,
+                last_modified,
+                body_hash: Some(body_hash),
+            },
+        })
+    }
+
+    /// Conditional GET outcomes since startup, across every client sharing
+    /// these budgets.
+    pub fn cache_stats(&self) -> CacheStats {
+        *self.lock_cache_stats()
+    }
+
+    /// Sends `request` for `url` under the host's budget. Statuses other
+    /// than 2xx and 304 are errors.
+    async fn send(&self, url: &str, request: RequestBuilder) -> Result<Response, VenueFeedError> {
+        let host = host_of(url).ok_or(VenueFeedError::Request)?;
+        self.acquire(&host, Instant::now())?;
+
+        let response = request.send().await.map_err(|_| VenueFeedError::Request)?;
+
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after_ms = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after_ms)
+                .unwrap_or(DEFAULT_RETRY_AFTER_MS);
+            self.block_host(&host, Instant::now(), retry_after_ms);
+            return Err(VenueFeedError::Throttled);
+        }
+        if let Some(blocked) = VenueFeedError::blocked_by(response.status().as_u16()) {
+            return Err(blocked);
+        }
+
+        response
+            .error_for_status()
+            .map_err(|_| VenueFeedError::Request)
+    }
+
+    /// Throttle events recorded since the last call.
+    pub fn drain_throttles(&self) -> Vec<ThrottleEvent> {
+        std::mem::take(&mut self.lock().pending)
+    }
+
+    fn acquire(&self, host: &str, now: Instant) -> Result<(), VenueFeedError> {
+        let mut limiter = self.lock();
+        match limiter.bucket(host, now).try_take(now) {
+            Ok(()) => Ok(()),
+            Err(reason) => {
+                limiter.record(ThrottleEvent {
+                    host: host.to_string(),
+                    reason,
+                    retry_after_ms: None,
+                });
+                Err(VenueFeedError::Throttled)
+            }
+        }
+    }
+
+    fn block_host(&self, host: &str, now: Instant, retry_after_ms: u64) {
+        let mut limiter = self.lock();
+        limiter
+            .bucket(host, now)
+            .block_for(now, Duration::from_millis(retry_after_ms));
+        limiter.record(ThrottleEvent {
+            host: host.to_string(),
+            reason: ThrottleReason::RetryAfter,
+            retry_after_ms: Some(retry_after_ms),
+        });
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Limiter> {
+        self.limiter
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_cache_stats(&self) -> MutexGuard<'_, CacheStats> {
+        self.cache_stats
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// HTTP client for market data polls, sent through `proxy` when one is set.
+/// Hosts listed in `NO_PROXY` still connect directly.
+pub fn market_data_client(proxy: Option<&HttpProxy>) -> reqwest::Result<Client> {
+    let builder = Client::builder()
+        .user_agent("market-latency-risk-lab/paper-live")
+        .connect_timeout(Duration::from_secs(4))
+        .timeout(Duration::from_secs(8))
+        .pool_max_idle_per_host(MAX_CONCURRENT_REQUESTS)
+        .pool_idle_timeout(Duration::from_secs(90));
+    let builder = match proxy {
+        Some(proxy) => builder.proxy(Proxy::all(proxy.url())?.no_proxy(NoProxy::from_env())),
+        None => builder.no_proxy(),
+    };
+    builder.build()
+}
+
+fn host_of(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(|host| host.to_ascii_lowercase())
+}
+
+/// Parses the delay-seconds form of `Retry-After`. HTTP-date values fall back
+/// to the default wait.
+fn parse_retry_after_ms(value: &str) -> Option<u64> {
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|secs| secs.saturating_mul(1_000))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::Path;
+    use axum::http::HeaderMap;
+    use axum::routing::get;
+    use axum::Router;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn token_bucket_spends_capacity_then_refills() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(
+            HostLimit {
+                capacity: 2.0,
+                refill_per_sec: 1.0,
+            },
+            start,
+        );
+
+        assert!(bucket.try_take(start).is_ok());
+        assert!(bucket.try_take(start).is_ok());
+        assert_eq!(bucket.try_take(start), Err(ThrottleReason::TokenBucket));
+        assert!(bucket
+            .try_take(start + Duration::from_millis(1_000))
+            .is_ok());
+    }
+
+    #[test]
+    fn retry_after_blocks_host_until_deadline() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(HostLimit::DEFAULT, start);
+        bucket.block_for(start, Duration::from_secs(5));
+
+        assert_eq!(
+            bucket.try_take(start + Duration::from_secs(4)),
+            Err(ThrottleReason::RetryAfter)
+        );
+        assert!(bucket.try_take(start + Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn acquire_records_throttle_events_per_host() {
+        let client = RateLimitedClient::new(Client::new());
+        let now = Instant::now();
+        for _ in 0..5 {
+            client.acquire("gamma-api.polymarket.com", now).unwrap();
+        }
+
+        assert_eq!(
+            client.acquire("gamma-api.polymarket.com", now),
+            Err(VenueFeedError::Throttled)
+        );
+        assert!(client.acquire("api.elections.kalshi.com", now).is_ok());
+
+        let throttles = client.drain_throttles();
+        assert_eq!(throttles.len(), 1);
+        assert_eq!(throttles[0].host, "gamma-api.polymarket.com");
+        assert_eq!(throttles[0].reason, ThrottleReason::TokenBucket);
+        assert!(client.drain_throttles().is_empty());
+    }
+
+    #[test]
+    fn parses_retry_after_seconds_and_host() {
+        assert_eq!(parse_retry_after_ms("12"), Some(12_000));
+        assert_eq!(parse_retry_after_ms("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(
+            host_of("https://Gamma-API.polymarket.com/markets?limit=1").as_deref(),
+            Some("gamma-api.polymarket.com")
+        );
+    }
+
+    #[tokio::test]
+    async fn get_texts_runs_requests_concurrently_in_order() {
+        let app = Router::new()
+            .route(
+                "/slow/:id",
+                get(|Path(id): Path<u64>| async move {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    id.to_string()
+                }),
+            )
+            .route(
+                "/stuck",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    ""
+                }),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = RateLimitedClient::new(market_data_client(None).unwrap());
+
+        let mut urls: Vec<String> = (0..4)
+            .map(|id| format!("http://{addr}/slow/{id}"))
+            .collect();
+        urls.insert(2, format!("http://{addr}/stuck"));
+        let started = Instant::now();
+        let texts = client.get_texts(&urls, Duration::from_secs(1)).await;
+
+        // Run one after another, the four slow responses alone take 800ms.
+        assert!(started.elapsed() < Duration::from_millis(1_600));
+        assert_eq!(
+            texts,
+            vec![
+                Ok("0".to_string()),
+                Ok("1".to_string()),
+                Err(VenueFeedError::Request),
+                Ok("2".to_string()),
+                Ok("3".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn conditional_gets_reuse_unchanged_payloads() {
+        let app = Router::new()
+            .route(
+                "/tagged",
+                get(|headers: HeaderMap| async move {
+                    if headers
+                        .get("if-none-match")
+                        .is_some_and(|tag| tag == "\"v1\"")
+                    {
+                        (StatusCode::NOT_MODIFIED, [("etag", "\"v1\"")], "")
+                    } else {
+                        (StatusCode::OK, [("etag", "\"v1\"")], "[1,2]")
+                    }
+                }),
+            )
+            .route("/plain", get(|| async { "[3]" }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = RateLimitedClient::new(Client::new());
+
+        for path in ["tagged", "plain"] {
+            let url = format!("http://{addr}/{path}");
+            let Conditional::Changed { body, validators } = client
+                .get_text_if_changed(&url, &CacheValidators::default())
+                .await
+                .unwrap()
+            else {
+                panic!("{path}: first fetch must return the body");
+            };
+            assert!(body.starts_with('['));
+            assert_eq!(validators.etag.is_some(), path == "tagged");
+            assert_eq!(
+                client.get_text_if_changed(&url, &validators).await,
+                Ok(Conditional::Unchanged)
+            );
+        }
+
+        assert_eq!(
+            client.cache_stats(),
+            CacheStats {
+                not_modified: 1,
+                unchanged: 1,
+                changed: 2,
+            }
+        );
+        assert_eq!(client.cache_stats().hits(), 2);
+    }
+}
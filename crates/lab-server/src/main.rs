@@ -1,62 +1,143 @@
+mod chat_alerts;
+mod cli;
 mod config;
+mod feeds;
+mod http;
+mod paper_live;
 mod predictors;
+mod quotes;
+mod redis_mirror;
+mod sim;
+mod tasks;
+mod webhooks;
 mod wiring;
 
+use std::collections::VecDeque;
 use std::env;
 use std::error::Error;
 use std::fs::{self, File};
+use std::net::SocketAddr;
 use std::path::Path;
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
 
+use api::export::{self, JsonlJournal};
 use api::state::{
-    AppState, BtcForecastSummary, DiscoveredMarket, ExecutionLogEntry,
-    ExecutionMode as StateExecutionMode, FeedMode, PaperOrderSide, PortfolioSummary, PriceSnapshot,
-    RuntimeEvent, RuntimeSettings, SourceCount, StrategyPerfSummary, StrategyStatsSummary,
+    AppState, DiscoveredMarket, ExecutionLogEntry, ExecutionMode as StateExecutionMode, FeedMode,
+    PaperOrderSide, RuntimeEvent, RuntimeSettings, SignalAction, SizingMode as StateSizingMode,
+    StrategySignal,
 };
+use axum_server::tls_rustls::RustlsConfig;
+use clap::Parser;
 use config::ExecutionMode as ConfigExecutionMode;
+use predictors::PredictorEndpoint;
 use reqwest::Client;
-use runtime::events::RuntimeStage;
+use runtime::brackets::BracketConfig;
+use runtime::clock::{Clock, SystemClock};
+#[cfg(feature = "parquet")]
+use runtime::columnar::{journal_batch, journal_schema, ParquetSeries};
+use runtime::execution::ClobCredentials;
 use runtime::live::{
-    fuse_predictors, BtcMedianTick, PolymarketQuoteTick, PredictorTick, RawPolymarketQuote,
+    Asset, BtcComposite, PolymarketQuoteTick, RegimeThresholds, ReplayRecording, SpreadLimit,
 };
-use runtime::live_runner::{run_paper_live_once_with_lag, JoinedLiveInputs};
 use runtime::logging::{PaperJournalRow, PaperJournalRowKind};
-use runtime::replay::ReplayCsvWriter;
-use serde::Deserialize;
+use runtime::metrics::StatsdConfig;
+use runtime::positions::{to_f64, PositionManager};
+#[cfg(feature = "parquet")]
+use runtime::recorder::RecordFormat;
+use runtime::recorder::{run_tick_recorder, TickRecordSender, TickRecorder, TickRecorderConfig};
+use runtime::replay::{ReplayCsvRow, ReplayCsvWriter};
+use rust_decimal::Decimal;
+use strategy::{
+    DivergenceSizer, KellySizer, SizingMode, StrategyRegistry, YesProbabilityModel,
+    DEFAULT_STRATEGY,
+};
 use tokio::net::TcpListener;
+use tokio::sync::mpsc;
 use tokio::time::{self, Duration, MissedTickBehavior};
 
+use crate::tasks::VenueExecutors;
+
 const BOOTSTRAP_ROWS_ENV: &str = "LAB_SERVER_INITIAL_PAPER_JOURNAL_ROWS";
 const PAPER_MARKET_ID: &str = "btc-15m-forecast";
-const PAPER_ORDER_QTY: f64 = 1.0;
-const LIVE_LOOP_INTERVAL_MS: u64 = 1500;
-const POLY_REFRESH_EVERY_TICKS: u64 = 10;
-const MAX_TRACKED_POLY_MARKETS: usize = 3;
-const BTC_COINBASE_URL: &str = "https://api.coinbase.com/v2/prices/BTC-USD/spot";
-const BTC_BINANCE_URL: &str = "https://api.binance.com/api/v3/ticker/price?symbol=BTCUSDT";
-const BTC_KRAKEN_URL: &str = "https://api.kraken.com/0/public/Ticker?pair=XBTUSD";
-const POLY_GAMMA_MARKETS_URL: &str =
-    "https://gamma-api.polymarket.com/markets?active=true&closed=false&limit=200";
 const BTC_MOMENTUM_MULTIPLIER: f64 = 60.0;
-const SPREAD_SIGNAL_TO_YES_COEFF: f64 = 0.00001;
-const DEFAULT_STARTING_EQUITY: f64 = 10_000.0;
-
-#[derive(Debug, Clone, Copy)]
+/// Spot volatility assumed until the regime classifier has an estimate, and
+/// for assets it does not track.
+const DEFAULT_VOL_BPS_PER_SQRT_MIN: f64 = 8.0;
+const MARKET_WINDOW_MS: u64 = 15 * 60_000;
+const DEFAULT_STARTING_EQUITY: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+/// BTC reference price before any venue has printed.
+const FALLBACK_BTC_USD: f64 = 64_000.0;
+/// Markets that drop out of discovery are polled for resolution this often.
+const RESOLUTION_CHECK_EVERY_TICKS: u64 = 10;
+/// Half-life of the BTC realized volatility estimate behind the sizing regime.
+const REGIME_HALF_LIFE_MS: u64 = 300_000;
+/// How sharply a resolved miss cuts a predictor's ensemble weight.
+const PREDICTOR_LEARNING_RATE: f64 = 2.0;
+/// Equity changes feeding the VaR estimate are sampled once a minute.
+const VAR_SAMPLE_MS: u64 = 60_000;
+/// Closed trading days kept for `/pnl/daily`.
+const DAILY_PNL_HISTORY: usize = 366;
+/// One hour of one-minute PnL samples.
+const VAR_WINDOW: usize = 60;
+/// Winning or losing closes behind the rolling win rate.
+const RECENT_CLOSES: usize = 20;
+/// Rolling win rate below which a `win_rate_collapse` alert fires.
+const WIN_RATE_COLLAPSE_PCT: f64 = 30.0;
+/// `/strategy/perf` reports decision latency over the last five minutes, in
+/// one-minute windows.
+const DECISION_LATENCY_WINDOW_MS: u64 = 60_000;
+const DECISION_LATENCY_WINDOWS: usize = 5;
+
+#[derive(Debug, Clone)]
 struct RuntimeTradingConfig {
     live_feature_enabled: bool,
-    starting_equity: f64,
+    starting_equity: Decimal,
+    assets: Vec<Asset>,
+    regime_thresholds: RegimeThresholds,
+    predictor_weights_path: String,
+    predictors: Vec<PredictorEndpoint>,
+    max_market_position: f64,
+    brackets: BracketConfig,
+    max_drawdown_fraction: f64,
+    trading_day_utc_offset_mins: i32,
+    /// VaR in USD at which the soft gate stops new orders.
+    var_limit: Option<f64>,
+    max_intents_per_min: u32,
+    loss_streak_limit: u32,
+    loss_streak_cooldown_ms: u64,
+    max_spread: SpreadLimit,
+    min_touch_qty: f64,
+    statsd: Option<StatsdConfig>,
+    /// Trades fetched to warm strategies up on each newly quoted Polymarket
+    /// market; zero turns the backfill off.
+    backfill_trades: usize,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+/// One asset's spot reference and momentum for the current tick.
+#[derive(Debug, Clone, Copy)]
+struct AssetSignal {
+    asset: Asset,
+    composite: Option<BtcComposite>,
+    spot_px: f64,
+    spread_signal: f64,
+    venue_count: u32,
+    forecast_px: f64,
+    forecast_delta_pct: f64,
+}
+
+#[derive(Debug, Default, Clone)]
 struct TradeOutcomeTracker {
     open_qty: f64,
     avg_entry: f64,
     winning_closes: u64,
     losing_closes: u64,
+    /// Whether each of the last [`RECENT_CLOSES`] closes was a winner.
+    recent: VecDeque<bool>,
 }
 
 impl TradeOutcomeTracker {
-    fn apply_fill(&mut self, side: PaperOrderSide, fill_px: f64, qty: f64) {
+    /// Returns the realized PnL when the fill closes some of the open qty.
+    fn apply_fill(&mut self, side: PaperOrderSide, fill_px: f64, qty: f64) -> Option<f64> {
         let signed_qty = match side {
             PaperOrderSide::Buy => qty,
             PaperOrderSide::Sell => -qty,
@@ -70,7 +151,7 @@ impl TradeOutcomeTracker {
                 self.avg_entry = weighted_cost / total_qty;
             }
             self.open_qty += signed_qty;
-            return;
+            return None;
         }
 
         let close_qty = self.open_qty.abs().min(signed_qty.abs());
@@ -85,6 +166,12 @@ impl TradeOutcomeTracker {
         } else if realized < 0.0 {
             self.losing_closes = self.losing_closes.saturating_add(1);
         }
+        if realized != 0.0 {
+            if self.recent.len() == RECENT_CLOSES {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(realized > 0.0);
+        }
 
         self.open_qty += signed_qty;
         if self.open_qty == 0.0 {
@@ -92,9 +179,20 @@ impl TradeOutcomeTracker {
         } else if self.open_qty.signum() == signed_qty.signum() && signed_qty.abs() > close_qty {
             self.avg_entry = fill_px;
         }
+        Some(realized)
     }
 
-    fn win_rate_pct(self) -> f64 {
+    /// Win rate over the last [`RECENT_CLOSES`] closes, once there are that
+    /// many.
+    fn recent_win_rate_pct(&self) -> Option<f64> {
+        if self.recent.len() < RECENT_CLOSES {
+            return None;
+        }
+        let wins = self.recent.iter().filter(|won| **won).count();
+        Some(wins as f64 / RECENT_CLOSES as f64 * 100.0)
+    }
+
+    fn win_rate_pct(&self) -> f64 {
         let total = self.winning_closes + self.losing_closes;
         if total == 0 {
             return 0.0;
@@ -104,76 +202,35 @@ impl TradeOutcomeTracker {
     }
 }
 
-#[derive(Default)]
-struct SourceCounters {
-    coinbase: u64,
-    binance: u64,
-    kraken: u64,
-    polymarket: u64,
-}
-
-impl SourceCounters {
-    fn as_source_counts(&self) -> Vec<SourceCount> {
-        vec![
-            SourceCount {
-                source: "coinbase".to_string(),
-                count: self.coinbase,
-            },
-            SourceCount {
-                source: "binance".to_string(),
-                count: self.binance,
-            },
-            SourceCount {
-                source: "kraken".to_string(),
-                count: self.kraken,
-            },
-            SourceCount {
-                source: "polymarket".to_string(),
-                count: self.polymarket,
-            },
-        ]
-    }
-}
-
-#[derive(Debug, Deserialize)]
-struct CoinbaseSpotResponse {
-    data: CoinbaseSpotData,
-}
-
-#[derive(Debug, Deserialize)]
-struct CoinbaseSpotData {
-    amount: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct BinanceTickerResponse {
-    price: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct GammaMarket {
-    slug: String,
-    #[serde(default)]
-    question: String,
-    #[serde(rename = "bestBid", default)]
-    best_bid: Option<serde_json::Value>,
-    #[serde(rename = "bestAsk", default)]
-    best_ask: Option<serde_json::Value>,
-    #[serde(rename = "outcomePrices", default)]
-    outcome_prices_raw: Option<serde_json::Value>,
-    #[serde(default)]
-    outcomes_raw: Option<serde_json::Value>,
-}
-
-struct PolymarketSnapshot {
-    discovered: Vec<DiscoveredMarket>,
-    quotes: Vec<PolymarketQuoteTick>,
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = cli::Cli::parse();
+    let config = config::Config::from_env_with_overrides(&cli.overrides())?;
+    if let cli::Command::Backtest {
+        runs,
+        ticks,
+        seed,
+        scenario,
+    } = cli.command()
+    {
+        let sim_config = cli::backtest_sim_config(&config);
+        let report = tokio::task::spawn_blocking(move || {
+            cli::run_backtest(&sim_config, runs, ticks, seed, scenario.as_ref())
+        })
+        .await??;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    if let cli::Command::ImportForecasts { file, into, source } = cli.command() {
+        let summary = cli::run_import_forecasts(&file, &into, source.as_deref())?;
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
     let config::Config {
         listen_addr,
+        tls,
+        grpc_addr,
         mode,
         replay_output_path,
         execution_mode,
@@ -181,671 +238,451 @@ async fn main() -> Result<(), Box<dyn Error>> {
         lag_threshold_pct,
         per_trade_risk_pct,
         daily_loss_cap_pct,
-    } = config::Config::from_env()?;
+        trading_day_utc_offset_mins,
+        max_drawdown_pct,
+        var_limit_pct,
+        lag_cooldown_secs,
+        lag_persistence_evals,
+        max_intents_per_min,
+        decision_interval_ms,
+        quote_poll_ms,
+        feed_block_cooldown_secs,
+        loss_streak_limit,
+        loss_streak_cooldown_secs,
+        btc_composite,
+        assets,
+        markets,
+        recorder,
+        replay_input_dir,
+        replay_speed,
+        regime_thresholds,
+        predictor_weights_path,
+        predictors,
+        max_market_position,
+        brackets,
+        max_spread,
+        min_touch_qty,
+        webhooks,
+        chat_alerts,
+        statsd,
+        backfill_trades,
+        redis_mirror,
+        clob,
+        proxies,
+    } = config;
 
     let runtime_trading_config = RuntimeTradingConfig {
         live_feature_enabled,
         starting_equity: DEFAULT_STARTING_EQUITY,
+        assets,
+        regime_thresholds,
+        predictor_weights_path,
+        predictors,
+        max_market_position,
+        brackets,
+        max_drawdown_fraction: max_drawdown_pct / 100.0,
+        trading_day_utc_offset_mins,
+        var_limit: var_limit_pct.map(|pct| to_f64(DEFAULT_STARTING_EQUITY) * pct / 100.0),
+        max_intents_per_min,
+        loss_streak_limit,
+        loss_streak_cooldown_ms: loss_streak_cooldown_secs.saturating_mul(1_000),
+        max_spread,
+        min_touch_qty,
+        statsd,
+        // Replays stay offline, and their markets have long since traded.
+        backfill_trades: if replay_input_dir.is_some() {
+            0
+        } else {
+            backfill_trades
+        },
     };
 
     println!("{}", startup_mode_banner(mode));
-    initialize_replay_output(&replay_output_path)?;
-    let app_state = AppState::new();
+    let replay_csv = initialize_replay_output(&replay_output_path)?;
+    let replay = replay_input_dir
+        .as_deref()
+        .filter(|_| mode != config::RunMode::Sim)
+        .map(|dir| {
+            ReplayRecording::load_dir(Path::new(dir), replay_speed)
+                .map_err(|err| format!("cannot replay recorded ticks from {dir}: {err:?}"))
+        })
+        .transpose()?;
+    // A replay keeps the recording's time from the feeds through the loop to
+    // the API, so throttles, cooldowns and staleness checks play out as they
+    // did when it was recorded.
+    let clock: Arc<dyn Clock> = match &replay {
+        Some(replay) => Arc::new(replay.clock.clone()),
+        None => Arc::new(SystemClock),
+    };
+    let app_state = match mode {
+        config::RunMode::PaperLive => AppState::new(),
+        config::RunMode::Sim => AppState::with_feed_mode(FeedMode::Sim),
+        config::RunMode::Replay => AppState::with_feed_mode(FeedMode::Replay),
+    }
+    .with_clock(clock.clone());
+    if let Some(replay_dir) = Path::new(&replay_output_path).parent() {
+        app_state.set_run_summary_dir(replay_dir.to_path_buf());
+        app_state.set_export_dir(replay_dir.to_path_buf());
+    }
+    spawn_event_journal(&app_state);
+    let journal = PaperJournal {
+        state: app_state.clone(),
+        csv: replay_csv,
+        jsonl: app_state
+            .export_dir()
+            .map(|dir| JsonlJournal::open(&dir.join(export::JOURNAL_FILE)))
+            .transpose()?,
+        #[cfg(feature = "parquet")]
+        parquet: (recorder.format == RecordFormat::Parquet)
+            .then(|| app_state.export_dir())
+            .flatten()
+            .map(|dir| {
+                ParquetSeries::new(
+                    dir,
+                    format!("journal-{}", now_unix_ms()),
+                    journal_schema(),
+                    recorder.rotate_bytes,
+                )
+            }),
+    };
     app_state.set_runtime_settings(RuntimeSettings {
         execution_mode: to_state_execution_mode(execution_mode),
         trading_paused: false,
         lag_threshold_pct,
         risk_per_trade_pct: per_trade_risk_pct,
         daily_loss_cap_pct,
+        lag_cooldown_secs,
+        lag_persistence_evals,
         market: "BTC/USD".to_string(),
         forecast_horizon_minutes: 15,
         live_feature_enabled,
+        strategy: DEFAULT_STRATEGY.to_string(),
+        available_strategies: StrategyRegistry::with_builtins()
+            .names()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        sizing_mode: StateSizingMode::Fixed,
+        kelly_fraction: 0.5,
+        decision_interval_ms,
     });
-
-    if mode == config::RunMode::PaperLive {
+    if !webhooks.urls.is_empty() {
+        let notifier = webhooks::WebhookNotifier::new(
+            Client::builder()
+                .user_agent("market-latency-risk-lab/webhooks")
+                .build()?,
+            webhooks,
+        );
+        tokio::spawn(notifier.run(app_state.subscribe_events()));
+    }
+    if let Some(chat_alerts) = chat_alerts {
         let client = Client::builder()
-            .user_agent("market-latency-risk-lab/paper-live")
-            .connect_timeout(Duration::from_secs(4))
-            .timeout(Duration::from_secs(8))
+            .user_agent("market-latency-risk-lab/alerts")
             .build()?;
-        tokio::spawn(run_paper_live_loop(
-            app_state.clone(),
-            client,
-            runtime_trading_config,
+        tokio::spawn(
+            chat_alerts::ChatAlerts::new(chat_alerts).run(client, app_state.subscribe_events()),
+        );
+    }
+    if let Some(redis_mirror) = redis_mirror {
+        tokio::spawn(redis_mirror::run(
+            redis_mirror,
+            app_state.subscribe_events(),
         ));
     }
 
-    let listener = TcpListener::bind(listen_addr).await?;
-    axum::serve(listener, wiring::build_app_with_state(app_state)).await?;
-    Ok(())
-}
-
-async fn run_paper_live_loop(state: AppState, client: Client, runtime_cfg: RuntimeTradingConfig) {
-    let mut interval = time::interval(Duration::from_millis(LIVE_LOOP_INTERVAL_MS));
-    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
-
-    let mut tick = 0_u64;
-    let mut counters = SourceCounters::default();
-    let mut last_btc_median: Option<f64> = None;
-    let mut tracked_quotes: Vec<PolymarketQuoteTick> = Vec::new();
-
-    let mut cash = runtime_cfg.starting_equity;
-    let mut position_qty = 0.0_f64;
-    let mut fills = 0_u64;
-    let mut outcomes = TradeOutcomeTracker::default();
-    let mut last_pause_state = false;
-
-    state.set_discovered_markets(vec![DiscoveredMarket {
-        source: "polymarket".to_string(),
-        market_id: PAPER_MARKET_ID.to_string(),
-    }]);
-
-    loop {
-        interval.tick().await;
-        tick = tick.saturating_add(1);
-        let mut tick_intents = 0_u64;
-        let mut tick_fills = 0_u64;
-        let mut tick_lag_triggers = 0_u64;
-
-        let (coinbase_px, binance_px, kraken_px) = tokio::join!(
-            fetch_coinbase_btc_usd(&client),
-            fetch_binance_btc_usdt(&client),
-            fetch_kraken_btc_usd(&client),
-        );
-
-        let mut btc_samples = Vec::new();
-        if let Some(px) = coinbase_px {
-            counters.coinbase = counters.coinbase.saturating_add(1);
-            btc_samples.push(px);
-        }
-        if let Some(px) = binance_px {
-            counters.binance = counters.binance.saturating_add(1);
-            btc_samples.push(px);
-        }
-        if let Some(px) = kraken_px {
-            counters.kraken = counters.kraken.saturating_add(1);
-            btc_samples.push(px);
-        }
-
-        let btc_median = median_f64(&btc_samples)
-            .or(last_btc_median)
-            .unwrap_or(64_000.0);
-        let spread_signal = match last_btc_median {
-            Some(previous) if previous > 0.0 => {
-                ((btc_median - previous) / previous) * 10_000.0 * BTC_MOMENTUM_MULTIPLIER
-            }
-            _ => 0.0,
+    if mode != config::RunMode::Sim {
+        // Replays never reach a venue.
+        let venues = match (mode, clob) {
+            (config::RunMode::PaperLive, Some(clob)) => venue_executors(clob)?,
+            _ => VenueExecutors::default(),
         };
-        last_btc_median = Some(btc_median);
-
-        let settings = state.runtime_settings();
-        let (forecast_btc_usd, forecast_delta_pct) = forecast_btc_15m(btc_median, spread_signal);
-        let forecast_summary = BtcForecastSummary {
-            horizon_minutes: 15,
-            current_btc_usd: btc_median,
-            forecast_btc_usd,
-            delta_pct: forecast_delta_pct,
-            ts: tick,
-        };
-        state.set_btc_forecast_summary(forecast_summary);
-        let _ = state.publish_event(RuntimeEvent::btc_forecast(forecast_summary));
-
-        if tick == 1 || tick % POLY_REFRESH_EVERY_TICKS == 0 || tracked_quotes.is_empty() {
-            if let Some(snapshot) = fetch_polymarket_snapshot(&client, tick).await {
-                if !snapshot.quotes.is_empty() {
-                    counters.polymarket = counters.polymarket.saturating_add(1);
-                    tracked_quotes = snapshot.quotes;
-                    state.set_discovered_markets(snapshot.discovered);
-                }
-            }
-        }
-
-        if tracked_quotes.is_empty() {
-            tracked_quotes.push(PolymarketQuoteTick {
-                market_slug: PAPER_MARKET_ID.to_string(),
-                best_yes_bid: 0.48,
-                best_yes_ask: 0.52,
-                mid_yes: 0.50,
-                ts: tick,
-            });
+        let client =
+            http::RateLimitedClient::new(http::market_data_client(proxies.default.as_ref())?);
+        let market_feeds = feeds::MarketFeeds::new(btc_composite)
+            .with_blocked_cooldown_ms(feed_block_cooldown_secs * 1_000)
+            .with_clock(clock.clone());
+        let recorder = spawn_tick_recorder(recorder, &app_state);
+        if let Some(recorder) = &recorder {
+            market_feeds.set_recorder(recorder.clone());
         }
-
-        let primary_quote = tracked_quotes.first();
-        let price_snapshot = PriceSnapshot {
-            coinbase_btc_usd: coinbase_px,
-            binance_btc_usdt: binance_px,
-            kraken_btc_usd: kraken_px,
-            polymarket_market_id: primary_quote.map(|quote| quote.market_slug.clone()),
-            polymarket_yes_bid: primary_quote.map(|quote| quote.best_yes_bid),
-            polymarket_yes_ask: primary_quote.map(|quote| quote.best_yes_ask),
-            polymarket_yes_mid: primary_quote.map(|quote| quote.mid_yes),
-            ts: tick,
-        };
-        state.set_price_snapshot(price_snapshot.clone());
-        let _ = state.publish_event(RuntimeEvent::price_snapshot(price_snapshot));
-
-        let predictor_now_ms = now_unix_ms();
-        let (tradingview_predictor, cryptoquant_predictor) = tokio::join!(
-            fetch_tradingview_predictor(&client, predictor_now_ms),
-            fetch_cryptoquant_predictor(&client, predictor_now_ms),
-        );
-        let predictor_ticks: Vec<PredictorTick> = [tradingview_predictor, cryptoquant_predictor]
-            .into_iter()
-            .flatten()
-            .collect();
-        let fused_fair_yes = fuse_predictors(&predictor_ticks, predictor_now_ms)
-            .ok()
-            .map(|fused| fused.fair_yes_px);
-
-        let source_counts = counters.as_source_counts();
-        state.set_feed_source_counts(source_counts.clone());
-        let _ = state.publish_event(RuntimeEvent::feed_health(
-            FeedMode::PaperLive,
-            source_counts,
-        ));
-
-        let current_mark = tracked_quotes
-            .first()
-            .map(|quote| quote.mid_yes)
-            .unwrap_or(0.5);
-        let equity_before = cash + (position_qty * current_mark);
-        let pnl_before = equity_before - runtime_cfg.starting_equity;
-        let daily_loss_limit = runtime_cfg.starting_equity * (settings.daily_loss_cap_pct / 100.0);
-        let daily_halted = pnl_before <= -daily_loss_limit;
-
-        let decision_started = Instant::now();
-
-        if settings.trading_paused != last_pause_state {
-            let status = if settings.trading_paused {
-                "Trading Paused"
-            } else {
-                "Trading Resumed"
-            };
-            let log = ExecutionLogEntry {
-                ts: tick,
-                event: "pause_state".to_string(),
-                headline: status.to_string(),
-                detail: format!("execution_mode={:?}", settings.execution_mode),
-            };
-            state.push_execution_log(log.clone(), 500);
-            let _ = state.publish_event(RuntimeEvent::execution_log(log));
-            last_pause_state = settings.trading_paused;
-        }
-
-        for quote in tracked_quotes.iter().take(MAX_TRACKED_POLY_MARKETS) {
-            if settings.trading_paused {
-                continue;
+        let replayed_predictors = match replay {
+            Some(replay) => {
+                market_feeds.spawn_replay(replay.feeds);
+                Some(replay.predictors)
             }
-
-            if daily_halted {
-                let _ = state.publish_event(RuntimeEvent::risk_reject(
-                    &quote.market_slug,
-                    "daily loss cap reached",
-                    PAPER_ORDER_QTY,
-                ));
-                let log = ExecutionLogEntry {
-                    ts: tick,
-                    event: "risk_reject".to_string(),
-                    headline: "Daily Cap Halt".to_string(),
-                    detail: format!("{} qty={}", quote.market_slug, PAPER_ORDER_QTY),
-                };
-                state.push_execution_log(log.clone(), 500);
-                let _ = state.publish_event(RuntimeEvent::execution_log(log));
-                continue;
-            }
-
-            let joined = JoinedLiveInputs {
-                btc_tick: BtcMedianTick::new(
-                    btc_median,
-                    spread_signal,
-                    btc_samples.len() as u32,
-                    tick,
-                ),
-                quote_tick: quote.clone(),
-            };
-
-            let fair_yes_px = fused_fair_yes
-                .unwrap_or_else(|| fallback_fair_yes_from_spread(quote.mid_yes, spread_signal));
-
-            let runtime_events = run_paper_live_once_with_lag(
-                tick,
-                &joined,
-                fair_yes_px,
-                settings.lag_threshold_pct,
-                settings.risk_per_trade_pct / 100.0,
-                runtime_cfg.starting_equity,
-                settings.daily_loss_cap_pct / 100.0,
-            );
-            let has_intent = runtime_events
-                .iter()
-                .any(|event| event.stage == RuntimeStage::PaperIntentCreated);
-            if !has_intent {
-                continue;
+            None => {
+                market_feeds.spawn_all(
+                    &client,
+                    &proxies,
+                    &runtime_trading_config.assets,
+                    &markets,
+                    Duration::from_millis(quote_poll_ms),
+                )?;
+                None
             }
-            tick_intents = tick_intents.saturating_add(1);
-            tick_lag_triggers = tick_lag_triggers.saturating_add(1);
-
-            let side = if fair_yes_px >= quote.mid_yes {
-                PaperOrderSide::Buy
-            } else {
-                PaperOrderSide::Sell
-            };
-            let limit_px = if matches!(side, PaperOrderSide::Buy) {
-                quote.best_yes_ask
-            } else {
-                quote.best_yes_bid
-            };
-            let _ = state.publish_event(RuntimeEvent::paper_intent(
-                &quote.market_slug,
-                side,
-                PAPER_ORDER_QTY,
-                limit_px,
-            ));
-            let intent_log = ExecutionLogEntry {
-                ts: tick,
-                event: "paper_intent".to_string(),
-                headline: format!("Intent {:?}", side),
-                detail: format!(
-                    "{} qty={} @ {:.4}",
-                    quote.market_slug, PAPER_ORDER_QTY, limit_px
-                ),
-            };
-            state.push_execution_log(intent_log.clone(), 500);
-            let _ = state.publish_event(RuntimeEvent::execution_log(intent_log));
-
-            let has_fill = runtime_events
-                .iter()
-                .any(|event| event.stage == RuntimeStage::PaperFillRecorded);
-            if has_fill {
-                if settings.execution_mode == StateExecutionMode::Live
-                    && !runtime_cfg.live_feature_enabled
-                {
-                    let _ = state.publish_event(RuntimeEvent::risk_reject(
-                        &quote.market_slug,
-                        "live mode disabled by feature flag",
-                        PAPER_ORDER_QTY,
-                    ));
-                    let log = ExecutionLogEntry {
-                        ts: tick,
-                        event: "risk_reject".to_string(),
-                        headline: "Live Mode Blocked".to_string(),
-                        detail: "Enable LAB_LIVE_FEATURE_ENABLED to allow live mode".to_string(),
-                    };
-                    state.push_execution_log(log.clone(), 500);
-                    let _ = state.publish_event(RuntimeEvent::execution_log(log));
-                    continue;
-                }
-
-                let fill_px = if matches!(side, PaperOrderSide::Buy) {
-                    quote.best_yes_ask
-                } else {
-                    quote.best_yes_bid
-                };
-
-                if matches!(side, PaperOrderSide::Buy) {
-                    cash -= fill_px * PAPER_ORDER_QTY;
-                    position_qty += PAPER_ORDER_QTY;
-                } else {
-                    cash += fill_px * PAPER_ORDER_QTY;
-                    position_qty -= PAPER_ORDER_QTY;
-                }
-                fills = fills.saturating_add(1);
-                tick_fills = tick_fills.saturating_add(1);
-                outcomes.apply_fill(side, fill_px, PAPER_ORDER_QTY);
-
-                let _ = state.publish_event(RuntimeEvent::paper_fill(
-                    &quote.market_slug,
-                    side,
-                    PAPER_ORDER_QTY,
-                    fill_px,
-                ));
-                let fill_log = ExecutionLogEntry {
-                    ts: tick,
-                    event: "paper_fill".to_string(),
-                    headline: format!("Filled {:?}", side),
-                    detail: format!(
-                        "{} qty={} @ {:.4}",
-                        quote.market_slug, PAPER_ORDER_QTY, fill_px
-                    ),
-                };
-                state.push_execution_log(fill_log.clone(), 500);
-                let _ = state.publish_event(RuntimeEvent::execution_log(fill_log));
-            } else {
-                let _ = state.publish_event(RuntimeEvent::risk_reject(
-                    &quote.market_slug,
-                    "risk gate rejected",
-                    PAPER_ORDER_QTY,
-                ));
-                let reject_log = ExecutionLogEntry {
-                    ts: tick,
-                    event: "risk_reject".to_string(),
-                    headline: "Risk Rejected".to_string(),
-                    detail: format!("{} qty={}", quote.market_slug, PAPER_ORDER_QTY),
-                };
-                state.push_execution_log(reject_log.clone(), 500);
-                let _ = state.publish_event(RuntimeEvent::execution_log(reject_log));
-            }
-        }
-
-        let throughput_scale = 1000.0 / (LIVE_LOOP_INTERVAL_MS as f64);
-        let perf_summary = StrategyPerfSummary {
-            execution_mode: match settings.execution_mode {
-                StateExecutionMode::Paper => "paper".to_string(),
-                StateExecutionMode::Live => "live".to_string(),
-            },
-            lag_threshold_pct: settings.lag_threshold_pct,
-            decision_p95_us: decision_started.elapsed().as_micros() as u64,
-            intents_per_sec: ((tick_intents as f64) * throughput_scale).round() as u64,
-            fills_per_sec: ((tick_fills as f64) * throughput_scale).round() as u64,
-            lag_triggers: tick_lag_triggers,
-            halted: daily_halted,
-        };
-        state.set_strategy_perf_summary(perf_summary.clone());
-        let _ = state.publish_event(RuntimeEvent::strategy_perf(perf_summary));
-
-        let mark_price = tracked_quotes
-            .first()
-            .map(|quote| quote.mid_yes)
-            .unwrap_or(0.5);
-        let equity = cash + (position_qty * mark_price);
-        let summary = PortfolioSummary {
-            equity,
-            pnl: equity - runtime_cfg.starting_equity,
-            position_qty,
-            fills,
         };
-
-        let stats_summary = StrategyStatsSummary {
-            balance: equity,
-            total_pnl: summary.pnl,
-            exec_latency_us: decision_started.elapsed().as_micros() as u64,
-            win_rate: outcomes.win_rate_pct(),
-            btc_usd: btc_median,
+        let setup = paper_live::PaperLiveSetup {
+            state: app_state.clone(),
+            client,
+            market_feeds,
+            runtime_cfg: runtime_trading_config,
+            replayed_predictors,
+            venues,
+            recorder,
+            journal,
         };
-        state.set_strategy_stats_summary(stats_summary);
-        let _ = state.publish_event(RuntimeEvent::strategy_stats(stats_summary));
+        tokio::spawn(paper_live::run_paper_live_loop(setup, clock));
+    } else {
+        tokio::spawn(sim::run_sim_loop(app_state.clone(), now_unix_ms()));
+    }
 
-        state.set_portfolio_summary(summary);
-        let _ = state.publish_event(RuntimeEvent::portfolio_snapshot(summary));
+    if let Some(grpc_addr) = grpc_addr {
+        spawn_grpc(grpc_addr, app_state.clone());
     }
+    let app = wiring::build_app_with_state(app_state);
+    match tls {
+        Some(tls) => serve_https(listen_addr, &tls, app).await?,
+        None => {
+            let listener = TcpListener::bind(listen_addr).await?;
+            axum::serve(listener, app).await?;
+        }
+    }
+    Ok(())
 }
 
-fn now_unix_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as u64)
-        .unwrap_or(0)
+#[cfg(feature = "polymarket-clob")]
+fn venue_executors(credentials: ClobCredentials) -> Result<VenueExecutors, Box<dyn Error>> {
+    let setup_error = |err| format!("cannot set up Polymarket CLOB execution: {err:?}");
+    let executor = runtime::clob::ClobExecutor::new(credentials.clone()).map_err(setup_error)?;
+    let dry_run = runtime::clob::ClobExecutor::dry_run(credentials).map_err(setup_error)?;
+    println!(
+        "live orders go to the Polymarket CLOB as {}",
+        executor.address()
+    );
+    executor.spawn_user_stream();
+    Ok(VenueExecutors {
+        live: Some(Box::new(executor)),
+        dry_run: Some(Box::new(dry_run)),
+    })
 }
 
-fn fallback_fair_yes_from_spread(poly_mid_yes: f64, spread_signal: f64) -> f64 {
-    (poly_mid_yes + (spread_signal * SPREAD_SIGNAL_TO_YES_COEFF)).clamp(0.0, 1.0)
+#[cfg(not(feature = "polymarket-clob"))]
+fn venue_executors(_credentials: ClobCredentials) -> Result<VenueExecutors, Box<dyn Error>> {
+    eprintln!("LAB_CLOB_* ignored: lab-server was built without the polymarket-clob feature");
+    Ok(VenueExecutors::default())
 }
 
-fn forecast_btc_15m(current_btc_usd: f64, spread_signal: f64) -> (f64, f64) {
-    let immediate_bps = spread_signal / BTC_MOMENTUM_MULTIPLIER;
-    let projected_pct = ((immediate_bps * 15.0) / 10_000.0).clamp(-0.01, 0.01);
-    let forecast = current_btc_usd * (1.0 + projected_pct);
-    (forecast, projected_pct * 100.0)
+#[cfg(feature = "grpc")]
+fn spawn_grpc(addr: SocketAddr, state: AppState) {
+    println!("gRPC API listening on {addr}");
+    tokio::spawn(async move {
+        if let Err(err) = api::grpc::serve(addr, state).await {
+            eprintln!("gRPC server stopped: {err}");
+        }
+    });
 }
 
-fn to_state_execution_mode(mode: ConfigExecutionMode) -> StateExecutionMode {
-    match mode {
-        ConfigExecutionMode::Paper => StateExecutionMode::Paper,
-        ConfigExecutionMode::Live => StateExecutionMode::Live,
-    }
+#[cfg(not(feature = "grpc"))]
+fn spawn_grpc(addr: SocketAddr, _state: AppState) {
+    eprintln!("LAB_GRPC_ADDR={addr} ignored: lab-server was built without the grpc feature");
 }
 
-async fn fetch_tradingview_predictor(client: &Client, ts_ms: u64) -> Option<PredictorTick> {
-    let url = env::var("LAB_TRADINGVIEW_PREDICT_URL").ok()?;
-    if url.trim().is_empty() {
-        return None;
-    }
-
-    let payload = client
-        .get(url)
-        .send()
+/// Serves `app` over HTTPS, plus the HTTP redirect listener when one is
+/// configured.
+async fn serve_https(
+    listen_addr: SocketAddr,
+    tls: &config::TlsConfig,
+    app: axum::Router,
+) -> Result<(), Box<dyn Error>> {
+    // reqwest also links rustls; pin the process-wide provider so the two do
+    // not disagree.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let rustls_config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
         .await
-        .ok()?
-        .error_for_status()
-        .ok()?
-        .text()
-        .await
-        .ok()?;
-
-    predictors::parse_tradingview_payload(&payload, ts_ms).ok()
-}
-
-async fn fetch_cryptoquant_predictor(client: &Client, ts_ms: u64) -> Option<PredictorTick> {
-    let url = env::var("LAB_CRYPTOQUANT_PREDICT_URL").ok()?;
-    if url.trim().is_empty() {
-        return None;
+        .map_err(|err| {
+            format!(
+                "cannot load TLS certificate {} and key {}: {err}",
+                tls.cert_path, tls.key_path
+            )
+        })?;
+
+    if let Some(redirect_addr) = tls.redirect_addr {
+        let listener = TcpListener::bind(redirect_addr).await?;
+        let redirect = wiring::build_https_redirect(listen_addr.port());
+        tokio::spawn(async move {
+            if let Err(err) = axum::serve(listener, redirect).await {
+                eprintln!("https redirect listener stopped: {err}");
+            }
+        });
     }
 
-    let payload = client
-        .get(url)
-        .send()
-        .await
-        .ok()?
-        .error_for_status()
-        .ok()?
-        .text()
-        .await
-        .ok()?;
-
-    predictors::parse_cryptoquant_payload(&payload, ts_ms).ok()
-}
-
-async fn fetch_coinbase_btc_usd(client: &Client) -> Option<f64> {
-    let response = client
-        .get(BTC_COINBASE_URL)
-        .send()
-        .await
-        .ok()?
-        .error_for_status()
-        .ok()?;
-    let payload: CoinbaseSpotResponse = response.json().await.ok()?;
-    parse_positive_f64(&payload.data.amount)
-}
-
-async fn fetch_binance_btc_usdt(client: &Client) -> Option<f64> {
-    let response = client
-        .get(BTC_BINANCE_URL)
-        .send()
-        .await
-        .ok()?
-        .error_for_status()
-        .ok()?;
-    let payload: BinanceTickerResponse = response.json().await.ok()?;
-    parse_positive_f64(&payload.price)
+    axum_server::bind_rustls(listen_addr, rustls_config)
+        .serve(app.into_make_service())
+        .await?;
+    Ok(())
 }
 
-async fn fetch_kraken_btc_usd(client: &Client) -> Option<f64> {
-    let response = client
-        .get(BTC_KRAKEN_URL)
-        .send()
-        .await
-        .ok()?
-        .error_for_status()
-        .ok()?;
-    let payload: serde_json::Value = response.json().await.ok()?;
-    let result = payload.get("result")?.as_object()?;
-    let first = result.values().next()?;
-    let close = first.get("c")?.as_array()?.first()?.as_str()?;
-    parse_positive_f64(close)
+fn discovered_markets(quotes: &[PolymarketQuoteTick]) -> Vec<DiscoveredMarket> {
+    let selected = preferred_quotes(quotes);
+    quotes
+        .iter()
+        .map(|quote| DiscoveredMarket {
+            source: quote.source.as_str().to_string(),
+            market_id: quote.market_slug.clone(),
+            liquidity_score: quote.liquidity_score,
+            selected: selected
+                .iter()
+                .any(|preferred| std::ptr::eq(*preferred, quote)),
+        })
+        .collect()
 }
 
-async fn fetch_polymarket_snapshot(client: &Client, tick: u64) -> Option<PolymarketSnapshot> {
-    let response = client
-        .get(POLY_GAMMA_MARKETS_URL)
-        .send()
-        .await
-        .ok()?
-        .error_for_status()
-        .ok()?;
-    let markets: Vec<GammaMarket> = response.json().await.ok()?;
-
-    let mut discovered = Vec::new();
-    let mut quotes = Vec::new();
-
-    for market in markets.iter() {
-        if !is_btc_15m_market(&market.slug, &market.question) {
-            continue;
-        }
-
-        if let Some(quote) = gamma_market_to_quote(market, tick) {
-            discovered.push(DiscoveredMarket {
-                source: "polymarket".to_string(),
-                market_id: market.slug.clone(),
-            });
-            quotes.push(quote);
-        }
-
-        if quotes.len() >= MAX_TRACKED_POLY_MARKETS {
-            break;
+/// The quote to trade for each source and asset. Feeds report markets best
+/// liquidity score first, so that is the first quote seen per pair.
+fn preferred_quotes(quotes: &[PolymarketQuoteTick]) -> Vec<&PolymarketQuoteTick> {
+    let mut preferred: Vec<&PolymarketQuoteTick> = Vec::new();
+    for quote in quotes {
+        if !preferred
+            .iter()
+            .any(|existing| existing.source == quote.source && existing.asset == quote.asset)
+        {
+            preferred.push(quote);
         }
     }
-
-    if quotes.is_empty() {
-        return None;
-    }
-
-    Some(PolymarketSnapshot { discovered, quotes })
+    preferred
 }
 
-fn is_btc_15m_market(slug: &str, question: &str) -> bool {
-    let haystack = format!(
-        "{} {}",
-        slug.to_ascii_lowercase(),
-        question.to_ascii_lowercase()
-    );
-
-    let has_btc = haystack.contains("btc") || haystack.contains("bitcoin");
-    if !has_btc {
-        return false;
+/// A journal row for an event on `quote`'s market, with the book columns as
+/// they stand once the event is applied.
+fn journal_row(
+    log: &ExecutionLogEntry,
+    kind: PaperJournalRowKind,
+    quote: &PolymarketQuoteTick,
+    positions: &PositionManager,
+    halted: bool,
+) -> ReplayCsvRow {
+    ReplayCsvRow {
+        tick: log.tick,
+        kind,
+        action_detail: format!("{} {}", log.headline, log.detail),
+        external_px: None,
+        market_px: Some(quote.mid_yes),
+        divergence: None,
+        equity: to_f64(positions.equity()),
+        realized_pnl: to_f64(positions.realized_pnl()),
+        position: positions.market_qty(&quote.market_slug),
+        halted,
+        run_id: None,
     }
+}
 
-    const FIFTEEN_MINUTE_TOKENS: [&str; 8] = [
-        "15m",
-        "15-min",
-        "15 min",
-        "15 minute",
-        "15-minute",
-        "15 minutes",
-        "next 15",
-        "in 15",
-    ];
-
-    FIFTEEN_MINUTE_TOKENS
-        .iter()
-        .any(|token| haystack.contains(token))
+/// The replay CSV, its JSONL copy for `/journal/export`, and a Parquet
+/// copy when ticks are recorded as Parquet.
+struct PaperJournal {
+    /// Names the active run on each row.
+    state: AppState,
+    csv: ReplayCsvWriter<File>,
+    jsonl: Option<JsonlJournal>,
+    #[cfg(feature = "parquet")]
+    parquet: Option<ParquetSeries>,
 }
 
-fn gamma_market_to_quote(market: &GammaMarket, tick: u64) -> Option<PolymarketQuoteTick> {
-    let fallback_mid = match (
-        market.best_bid.as_ref().and_then(parse_probability_json),
-        market.best_ask.as_ref().and_then(parse_probability_json),
-    ) {
-        (Some(best_bid), Some(best_ask)) => (best_bid + best_ask) / 2.0,
-        _ => 0.5,
+/// The journal is a debugging aid, so a failed write is reported and the
+/// loop carries on.
+fn append_journal(journal: &mut PaperJournal, row: ReplayCsvRow) {
+    let row = ReplayCsvRow {
+        run_id: journal.state.active_run_id(),
+        ..row
     };
-    let yes_mid = yes_price_from_market(market).unwrap_or(fallback_mid.clamp(0.0, 1.0));
-    let fallback_bid = (yes_mid - 0.01).clamp(0.0, 1.0);
-    let fallback_ask = (yes_mid + 0.01).clamp(0.0, 1.0);
-    let mut best_bid = market
-        .best_bid
-        .as_ref()
-        .and_then(parse_probability_json)
-        .unwrap_or(fallback_bid);
-    let mut best_ask = market
-        .best_ask
-        .as_ref()
-        .and_then(parse_probability_json)
-        .unwrap_or(fallback_ask);
-
-    if best_bid > best_ask {
-        std::mem::swap(&mut best_bid, &mut best_ask);
+    if let Err(err) = journal.csv.append_row(&row) {
+        eprintln!("replay journal write failed: {err}");
     }
-
-    RawPolymarketQuote {
-        market_slug: market.slug.clone(),
-        best_yes_bid: best_bid,
-        best_yes_ask: best_ask,
-        ts: tick,
+    if let Some(jsonl) = &mut journal.jsonl {
+        if let Err(err) = jsonl.append(now_unix_ms(), &row) {
+            eprintln!("journal export write failed: {err}");
+        }
     }
-    .normalize()
-    .ok()
-}
-
-fn yes_price_from_market(market: &GammaMarket) -> Option<f64> {
-    let outcomes = parse_string_list(market.outcomes_raw.as_ref());
-    let outcome_prices = parse_string_list(market.outcome_prices_raw.as_ref());
-
-    if !outcomes.is_empty() && outcomes.len() == outcome_prices.len() {
-        for (idx, outcome) in outcomes.iter().enumerate() {
-            if outcome.eq_ignore_ascii_case("yes") {
-                return parse_probability_str(&outcome_prices[idx]);
-            }
+    #[cfg(feature = "parquet")]
+    if let Some(parquet) = &mut journal.parquet {
+        let recorded_ms = now_unix_ms();
+        let written = journal_batch(&[(recorded_ms, row)])
+            .map_err(std::io::Error::other)
+            .and_then(|batch| parquet.write(recorded_ms, &batch));
+        if let Err(err) = written {
+            eprintln!("parquet journal write failed: {err}");
         }
     }
-
-    outcome_prices
-        .first()
-        .and_then(|value| parse_probability_str(value))
 }
 
-fn parse_string_list(value: Option<&serde_json::Value>) -> Vec<String> {
-    let Some(value) = value else {
-        return Vec::new();
+/// Stores a strategy evaluation with the action the loop took on it and
+/// publishes it. Quotes the lag detector cannot price are not recorded.
+fn record_signal(state: &AppState, evaluation: Option<StrategySignal>, action: SignalAction) {
+    let Some(signal) = evaluation else {
+        return;
     };
+    let signal = StrategySignal { action, ..signal };
+    state.push_strategy_signal(signal.clone(), 500);
+    let _ = state.publish_event(RuntimeEvent::strategy_signal(signal));
+}
 
-    match value {
-        serde_json::Value::Array(items) => items
-            .iter()
-            .filter_map(|item| item.as_str().map(ToOwned::to_owned))
-            .collect(),
-        serde_json::Value::String(text) => {
-            if let Ok(items) = serde_json::from_str::<Vec<String>>(text) {
-                return items;
-            }
+fn now_unix_ms() -> u64 {
+    SystemClock.now_ms()
+}
 
-            text.split(',')
-                .map(str::trim)
-                .map(|entry| entry.trim_matches(|ch| ch == '[' || ch == ']' || ch == '"'))
-                .filter(|entry| !entry.is_empty())
-                .map(ToOwned::to_owned)
-                .collect()
+/// Kelly sizing falls back to the fixed size if the settings cannot build a
+/// sizer; the settings route validates both inputs, so that should not happen.
+fn order_sizing(settings: &RuntimeSettings) -> SizingMode {
+    match settings.sizing_mode {
+        StateSizingMode::Fixed => SizingMode::Fixed,
+        StateSizingMode::Kelly => {
+            KellySizer::new(settings.kelly_fraction, settings.risk_per_trade_pct / 100.0)
+                .map_or(SizingMode::Fixed, SizingMode::Kelly)
         }
-        _ => Vec::new(),
+        StateSizingMode::Divergence => DivergenceSizer::new(settings.risk_per_trade_pct / 100.0)
+            .map_or(SizingMode::Fixed, SizingMode::Divergence),
     }
 }
 
-fn parse_positive_f64(value: &str) -> Option<f64> {
-    let parsed = value.parse::<f64>().ok()?;
-    if parsed.is_finite() && parsed > 0.0 {
-        Some(parsed)
-    } else {
-        None
-    }
+/// Minutes until the current 15m window closes; windows are aligned to the
+/// quarter hour.
+fn minutes_to_window_close(now_ms: u64) -> f64 {
+    let remaining_ms = MARKET_WINDOW_MS - now_ms % MARKET_WINDOW_MS;
+    remaining_ms as f64 / 60_000.0
 }
 
-fn parse_probability_str(value: &str) -> Option<f64> {
-    let parsed = value.parse::<f64>().ok()?;
-    parse_probability(parsed)
+/// Fair YES probability from the asset's 15m forecast, scaled back to the
+/// time left in the window.
+fn model_fair_yes(signal: &AssetSignal, strike: f64, vol_bps: f64, now_ms: u64) -> Option<f64> {
+    let minutes_remaining = minutes_to_window_close(now_ms);
+    let drift_pct = signal.forecast_delta_pct * minutes_remaining / 15.0;
+    let projected_px = signal.spot_px * (1.0 + drift_pct / 100.0);
+    YesProbabilityModel::new(vol_bps)
+        .and_then(|model| model.fair_yes(projected_px, strike, minutes_remaining))
+        .ok()
 }
 
-fn parse_probability_json(value: &serde_json::Value) -> Option<f64> {
-    match value {
-        serde_json::Value::Number(number) => parse_probability(number.as_f64()?),
-        serde_json::Value::String(text) => parse_probability_str(text),
-        _ => None,
-    }
+/// Ticker for the decision loop, first firing one period from now so a
+/// changed interval does not add an extra tick; a tick missed while a
+/// decision overran is skipped rather than replayed.
+fn decision_interval(period_ms: u64) -> time::Interval {
+    let period = Duration::from_millis(period_ms);
+    let mut interval = time::interval_at(time::Instant::now() + period, period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    interval
 }
 
-fn parse_probability(value: f64) -> Option<f64> {
-    if value.is_finite() && (0.0..=1.0).contains(&value) {
-        Some(value)
-    } else {
-        None
+fn forecast_15m(current_px: f64, spread_signal: f64) -> (f64, f64) {
+    let immediate_bps = spread_signal / BTC_MOMENTUM_MULTIPLIER;
+    let projected_pct = ((immediate_bps * 15.0) / 10_000.0).clamp(-0.01, 0.01);
+    let forecast = current_px * (1.0 + projected_pct);
+    (forecast, projected_pct * 100.0)
+}
+
+fn to_state_execution_mode(mode: ConfigExecutionMode) -> StateExecutionMode {
+    match mode {
+        ConfigExecutionMode::Paper => StateExecutionMode::Paper,
+        ConfigExecutionMode::DryRun => StateExecutionMode::DryRun,
+        ConfigExecutionMode::Live => StateExecutionMode::Live,
     }
 }
 
@@ -872,7 +709,49 @@ fn startup_mode_banner(mode: config::RunMode) -> String {
     format!("lab-server startup mode: {}", mode.as_str())
 }
 
-fn initialize_replay_output(path: &str) -> Result<(), std::io::Error> {
+/// Starts the tick recorder on a blocking thread when any stream is enabled
+/// and returns the channel feeds copy their ticks into. Ticks are stamped
+/// with the run active in `state` as they are written.
+fn spawn_tick_recorder(config: TickRecorderConfig, state: &AppState) -> Option<TickRecordSender> {
+    if !config.is_enabled() {
+        return None;
+    }
+
+    let recorder = TickRecorder::new(config, now_unix_ms())
+        .expect("config checks the rotation size and format");
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let state = state.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(err) = run_tick_recorder(recorder, receiver, || state.active_run_id()) {
+            eprintln!("tick recorder stopped: {err}");
+        }
+    });
+    Some(sender)
+}
+
+/// Journals every bus event to the export dir on a blocking thread.
+fn spawn_event_journal(state: &AppState) {
+    let Some(dir) = state.export_dir() else {
+        return;
+    };
+    let journal = match JsonlJournal::open(&dir.join(export::EVENTS_FILE)) {
+        Ok(journal) => journal,
+        Err(err) => {
+            eprintln!("event journal disabled: {err}");
+            return;
+        }
+    };
+    let events = state.subscribe_events();
+    tokio::task::spawn_blocking(move || {
+        if let Err(err) = export::record_events(events, journal) {
+            eprintln!("event journal stopped: {err}");
+        }
+    });
+}
+
+/// Creates the replay CSV with its header and bootstrap rows, and returns the
+/// writer the paper loop keeps journaling to.
+fn initialize_replay_output(path: &str) -> Result<ReplayCsvWriter<File>, std::io::Error> {
     let replay_path = Path::new(path);
 
     if let Some(parent) = replay_path
@@ -886,7 +765,7 @@ fn initialize_replay_output(path: &str) -> Result<(), std::io::Error> {
     let mut replay_writer = ReplayCsvWriter::new(replay_file);
     replay_writer.write_header()?;
     replay_writer.append_paper_journal_rows(&initial_paper_journal_rows())?;
-    Ok(())
+    Ok(replay_writer)
 }
 
 fn initial_paper_journal_rows() -> Vec<PaperJournalRow> {
@@ -930,10 +809,19 @@ mod tests {
     use runtime::logging::PaperJournalRowKind;
     use runtime::replay::REPLAY_CSV_HEADER;
 
+    use api::export::{self, JsonlJournal};
+    use api::state::{AppState, ExecutionLogEntry};
+    use runtime::positions::PositionManager;
+    use rust_decimal::Decimal;
+
     use super::{
-        initial_paper_journal_rows, initialize_replay_output, is_btc_15m_market, median_f64,
-        parse_probability_str, startup_mode_banner,
+        append_journal, decision_interval, discovered_markets, forecast_15m,
+        initial_paper_journal_rows, initialize_replay_output, journal_row, median_f64,
+        minutes_to_window_close, model_fair_yes, preferred_quotes, startup_mode_banner,
+        AssetSignal, PaperJournal, TradeOutcomeTracker, RECENT_CLOSES,
     };
+    use api::state::PaperOrderSide;
+    use runtime::live::{Asset, BookDepth, PolymarketQuoteTick, QuoteSource};
 
     static ENV_LOCK: Mutex<()> = Mutex::new(());
     const ENV_BOOTSTRAP_ROWS: &str = "LAB_SERVER_INITIAL_PAPER_JOURNAL_ROWS";
@@ -986,6 +874,73 @@ mod tests {
         fs::remove_dir_all(&root).expect("temp replay directory should be removable");
     }
 
+    #[test]
+    fn paper_loop_journals_fills_after_the_header() {
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+        let _bootstrap_guard = EnvVarGuard::unset(ENV_BOOTSTRAP_ROWS);
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("lab-server-journal-{unique}"));
+        let replay_path = root.join("replay.csv");
+        let state = AppState::new();
+        state.start_run().unwrap();
+        let mut journal = PaperJournal {
+            state,
+            csv: initialize_replay_output(replay_path.to_str().unwrap()).unwrap(),
+            jsonl: Some(JsonlJournal::open(&root.join(export::JOURNAL_FILE)).unwrap()),
+            #[cfg(feature = "parquet")]
+            parquet: None,
+        };
+
+        let quote = quote("btc-1215", QuoteSource::Polymarket, Asset::Btc);
+        let mut positions = PositionManager::new(Decimal::from(100));
+        positions.apply_fill(QuoteSource::Polymarket, "btc-1215", 10.0, 0.52);
+        let log = ExecutionLogEntry {
+            tick: 3,
+            ts: 1_700_000_000_000,
+            event: "paper_fill".to_string(),
+            headline: "Filled Buy".to_string(),
+            detail: "polymarket:btc-1215 qty=10 @ 0.5200".to_string(),
+        };
+        append_journal(
+            &mut journal,
+            journal_row(
+                &log,
+                PaperJournalRowKind::PaperFill,
+                &quote,
+                &positions,
+                false,
+            ),
+        );
+        drop(journal);
+
+        let actual = fs::read_to_string(&replay_path).unwrap();
+        assert_eq!(
+            actual,
+            format!(
+                "{REPLAY_CSV_HEADER}3,,0.5,,paper_fill:Filled Buy polymarket:btc-1215 qty=10 @ 0.5200,100,0,10,false,1\n"
+            )
+        );
+        let exported: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(root.join(export::JOURNAL_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(exported["kind"], "paper_fill");
+        assert_eq!(exported["position"], 10.0);
+        assert_eq!(exported["run_id"], 1);
+        assert!(exported["recorded_ms"].as_u64().is_some());
+
+        fs::remove_dir_all(&root).expect("temp journal directory should be removable");
+    }
+
+    #[tokio::test]
+    async fn decision_interval_waits_a_full_period_before_its_first_tick() {
+        let mut interval = decision_interval(60_000);
+        let first = tokio::time::timeout(std::time::Duration::from_millis(50), interval.tick());
+        assert!(first.await.is_err());
+    }
+
     #[test]
     fn startup_mode_banner_reports_selected_mode() {
         assert_eq!(
@@ -996,6 +951,10 @@ mod tests {
             startup_mode_banner(RunMode::Sim),
             "lab-server startup mode: sim"
         );
+        assert_eq!(
+            startup_mode_banner(RunMode::Replay),
+            "lab-server startup mode: replay"
+        );
     }
 
     #[test]
@@ -1044,7 +1003,7 @@ mod tests {
         let actual = fs::read_to_string(&replay_path).expect("replay output file should exist");
         assert_eq!(
             actual,
-            format!("{REPLAY_CSV_HEADER}17,,,,paper_fill:buy:market-1@0.62x5,,,,\n")
+            format!("{REPLAY_CSV_HEADER}17,,,,paper_fill:buy:market-1@0.62x5,,,,,\n")
         );
 
         fs::remove_dir_all(&root).expect("temp replay directory should be removable");
@@ -1057,29 +1016,90 @@ mod tests {
     }
 
     #[test]
-    fn parse_probability_str_rejects_out_of_range_values() {
-        assert_eq!(parse_probability_str("1.1"), None);
-        assert_eq!(parse_probability_str("-0.1"), None);
-        assert_eq!(parse_probability_str("0.42"), Some(0.42));
+    fn preferred_quotes_keep_first_market_per_source_and_asset() {
+        let quotes = vec![
+            quote("btc-near", QuoteSource::Polymarket, Asset::Btc),
+            quote("btc-later", QuoteSource::Polymarket, Asset::Btc),
+            quote("eth-near", QuoteSource::Polymarket, Asset::Eth),
+            quote("KXBTC15M-A", QuoteSource::Kalshi, Asset::Btc),
+            quote("eth-later", QuoteSource::Polymarket, Asset::Eth),
+        ];
+
+        let slugs: Vec<&str> = preferred_quotes(&quotes)
+            .into_iter()
+            .map(|quote| quote.market_slug.as_str())
+            .collect();
+
+        assert_eq!(slugs, vec!["btc-near", "eth-near", "KXBTC15M-A"]);
+
+        let selected: Vec<bool> = discovered_markets(&quotes)
+            .into_iter()
+            .map(|market| market.selected)
+            .collect();
+        assert_eq!(selected, vec![true, false, true, true, false]);
     }
 
     #[test]
-    fn btc_15m_market_filter_accepts_matching_market() {
-        assert!(is_btc_15m_market(
-            "bitcoin-15m-forecast",
-            "Will BTC be above 66k in the next 15 minutes?"
-        ));
+    fn forecast_projects_momentum_over_the_horizon_with_a_cap() {
+        let (flat_px, flat_pct) = forecast_15m(3_400.0, 0.0);
+        assert_eq!((flat_px, flat_pct), (3_400.0, 0.0));
+
+        let (capped_px, capped_pct) = forecast_15m(150.0, 1_000_000.0);
+        assert!((capped_px - 151.5).abs() < 1e-9);
+        assert!((capped_pct - 1.0).abs() < 1e-9);
     }
 
     #[test]
-    fn btc_15m_market_filter_rejects_non_15m_or_non_btc_market() {
-        assert!(!is_btc_15m_market(
-            "bitcoin-daily-forecast",
-            "Will BTC be above 70k tomorrow?"
-        ));
-        assert!(!is_btc_15m_market(
-            "eth-15m-forecast",
-            "Will ETH rise in 15 minutes?"
-        ));
+    fn model_fair_yes_leans_with_the_forecast_and_time_left() {
+        let signal = AssetSignal {
+            asset: Asset::Btc,
+            composite: None,
+            spot_px: 64_000.0,
+            spread_signal: 0.0,
+            venue_count: 3,
+            forecast_px: 64_064.0,
+            forecast_delta_pct: 0.1,
+        };
+        // 12:05:00 UTC leaves ten minutes in the 12:00-12:15 window.
+        let now_ms = 1_772_280_300_000;
+
+        assert!((minutes_to_window_close(now_ms) - 10.0).abs() < 1e-9);
+        let at_strike = model_fair_yes(&signal, 64_000.0, 8.0, now_ms).unwrap();
+        let below_strike = model_fair_yes(&signal, 64_200.0, 8.0, now_ms).unwrap();
+        assert!(at_strike > 0.5);
+        assert!(below_strike < 0.5);
+    }
+
+    #[test]
+    fn recent_win_rate_covers_only_the_last_closes() {
+        let mut outcomes = TradeOutcomeTracker::default();
+        let mut round_trip = |exit_px: f64| {
+            outcomes.apply_fill(PaperOrderSide::Buy, 0.5, 1.0);
+            outcomes.apply_fill(PaperOrderSide::Sell, exit_px, 1.0);
+        };
+        for _ in 0..RECENT_CLOSES {
+            round_trip(0.6);
+        }
+        for _ in 0..15 {
+            round_trip(0.4);
+        }
+
+        assert_eq!(outcomes.recent_win_rate_pct(), Some(25.0));
+        assert!((outcomes.win_rate_pct() - 20.0 / 35.0 * 100.0).abs() < 1e-9);
+        assert_eq!(TradeOutcomeTracker::default().recent_win_rate_pct(), None);
+    }
+
+    fn quote(slug: &str, source: QuoteSource, asset: Asset) -> PolymarketQuoteTick {
+        PolymarketQuoteTick {
+            market_slug: slug.to_string(),
+            source,
+            asset,
+            best_yes_bid: 0.48,
+            best_yes_ask: 0.52,
+            mid_yes: 0.5,
+            ts: 1,
+            depth: BookDepth::default(),
+            liquidity_score: None,
+        }
     }
 }
@@ -0,0 +1,1763 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
+
+use api::state::{
+    AppState, AssetPriceSnapshot, BtcForecastSummary, DayPnl, DiscoveredMarket, ExecutionLogEntry,
+    ExecutionMode as StateExecutionMode, FeedBlock, FeedCacheStats, FeedErrorCount,
+    FeedErrorsResponse, MarketPortfolio, PaperOrderSide, PortfolioSummary, PriceSnapshot,
+    QuarantinedMessage, RiskAlertKind, RiskStatus, RuntimeEvent, RuntimeSettings, SignalAction,
+    SourceCount, StrategyPerfSummary, StrategySignal, StrategyStatsSummary,
+};
+use runtime::brackets::{BracketBook, BracketKind, BracketTrigger};
+use runtime::clock::Clock;
+use runtime::drawdown::DrawdownWatch;
+use runtime::execution::{OrderReport, OrderRequest, OrderSide};
+use runtime::live::{
+    detect_lag, fuse_weighted_predictors, Asset, BookDepth, BtcMedianTick, FeedReconnect,
+    LagTriggerGate, PolymarketQuoteTick, PredictorTick, PredictorWeights, QuoteSource,
+    RegimeClassifier, ReplayPredictors, VenueFeedError, VenueHealth,
+};
+use runtime::live_runner::{has_fill, run_strategy_once_gated, JoinedLiveInputs, LagRunParams};
+use runtime::logging::PaperJournalRowKind;
+use runtime::metrics::{StatsdEmitter, WindowedLatencyHistogram};
+use runtime::positions::{to_f64, MarketOutcome, PositionManager};
+use runtime::recorder::{RecordedTick, TickRecordSender};
+use runtime::replay::ReplayCsvRow;
+use runtime::throttle::IntentThrottle;
+use runtime::trading_day::TradingDay;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use strategy::{
+    risk_budget_qty, var_size_scale, LossStreakCooldown, Regime, Signal, Strategy, StrategyParams,
+    StrategyRegistry, VarEstimator, DEFAULT_STRATEGY,
+};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::watch;
+use tokio::time::{self, Duration};
+
+use crate::tasks::{
+    self, DecisionInputs, ExecutionChannel, ExecutionRejection, ExecutionRequest, PollEvent,
+    VenueExecutors,
+};
+use crate::{
+    append_journal, decision_interval, discovered_markets, feeds, forecast_15m, http, journal_row,
+    median_f64, minutes_to_window_close, model_fair_yes, order_sizing, preferred_quotes,
+    record_signal, AssetSignal, PaperJournal, RuntimeTradingConfig, TradeOutcomeTracker,
+    BTC_MOMENTUM_MULTIPLIER, DAILY_PNL_HISTORY, DECISION_LATENCY_WINDOWS,
+    DECISION_LATENCY_WINDOW_MS, DEFAULT_VOL_BPS_PER_SQRT_MIN, FALLBACK_BTC_USD, PAPER_MARKET_ID,
+    PREDICTOR_LEARNING_RATE, RECENT_CLOSES, REGIME_HALF_LIFE_MS, RESOLUTION_CHECK_EVERY_TICKS,
+    VAR_SAMPLE_MS, VAR_WINDOW, WIN_RATE_COLLAPSE_PCT,
+};
+
+/// Execution log entries kept for `/execution/logs`.
+const EXECUTION_LOG_LIMIT: usize = 500;
+
+/// What the paper-live loop is started with.
+pub struct PaperLiveSetup {
+    pub state: AppState,
+    pub client: http::RateLimitedClient,
+    pub market_feeds: feeds::MarketFeeds,
+    pub runtime_cfg: RuntimeTradingConfig,
+    pub replayed_predictors: Option<ReplayPredictors>,
+    pub venues: VenueExecutors,
+    pub recorder: Option<TickRecordSender>,
+    pub journal: PaperJournal,
+}
+
+/// Runs the decision loop over the feeds' latest ticks. Predictors,
+/// resolutions, backfills and order placement each run as their own
+/// supervised task, so a hung venue only delays what depends on it.
+pub async fn run_paper_live_loop(setup: PaperLiveSetup, clock: impl Clock) {
+    PaperLive::new(setup, clock).run().await;
+}
+
+/// An admitted order as the loop needs it back to book the fill.
+#[derive(Debug, Clone)]
+struct PendingOrder {
+    quote: PolymarketQuoteTick,
+    side: PaperOrderSide,
+    qty: f64,
+    limit_px: f64,
+    origin: OrderOrigin,
+}
+
+/// What an order was placed for, which decides how its outcome is logged.
+#[derive(Debug, Clone)]
+enum OrderOrigin {
+    Strategy {
+        decision: Decision,
+        evaluation: Option<StrategySignal>,
+    },
+    /// Flattens a position whose stop or target was reached.
+    Bracket(BracketTrigger),
+}
+
+impl PendingOrder {
+    /// YES shares the order adds to its market; negative sells.
+    fn signed_qty(&self) -> f64 {
+        match self.side {
+            PaperOrderSide::Buy => self.qty,
+            PaperOrderSide::Sell => -self.qty,
+        }
+    }
+
+    fn decision(&self) -> Option<Decision> {
+        match self.origin {
+            OrderOrigin::Strategy { decision, .. } => Some(decision),
+            OrderOrigin::Bracket(_) => None,
+        }
+    }
+}
+
+/// The spot reference and fair value a quote was traded against, journaled
+/// with every row about the decision.
+#[derive(Debug, Clone, Copy)]
+struct Decision {
+    spot_px: f64,
+    fair_yes_px: f64,
+}
+
+/// Inputs read once at the start of a tick and shared by its stages.
+struct TickInputs {
+    tick: u64,
+    ts: u64,
+    settings: RuntimeSettings,
+    signals: Vec<AssetSignal>,
+    btc_signal: AssetSignal,
+    regime: Regime,
+    quotes: Vec<PolymarketQuoteTick>,
+    fused_fair_yes: Option<f64>,
+}
+
+/// Counts behind `/strategy/perf` for one tick.
+#[derive(Debug, Default)]
+struct TickCounts {
+    intents: u64,
+    fills: u64,
+    lag_triggers: u64,
+}
+
+/// Where the risk checks left the book before this tick's decisions.
+#[derive(Debug, Clone, Copy)]
+struct RiskGate {
+    equity: Decimal,
+    daily_halted: bool,
+    drawdown_halted: bool,
+    cooling_down: bool,
+    var_scale: f64,
+}
+
+impl RiskGate {
+    fn halted(&self) -> bool {
+        self.daily_halted || self.drawdown_halted
+    }
+}
+
+struct PaperLive<C> {
+    state: AppState,
+    client: http::RateLimitedClient,
+    market_feeds: feeds::MarketFeeds,
+    runtime_cfg: RuntimeTradingConfig,
+    recorder: Option<TickRecordSender>,
+    journal: PaperJournal,
+    clock: C,
+    // Replays keep their resolutions and predictor weights to themselves.
+    replaying: bool,
+    ingest: DecisionInputs,
+    execution: ExecutionChannel<PendingOrder>,
+
+    interval_ms: u64,
+    interval: time::Interval,
+    // Predictor endpoints without their own interval poll at the decision
+    // rate and follow it when it changes.
+    poll_interval_tx: watch::Sender<Duration>,
+    tick: u64,
+    last_spot_px: HashMap<Asset, f64>,
+    last_discovered: Vec<DiscoveredMarket>,
+    // Up/down markets resolve against spot at the window open; the first
+    // spot seen for a market stands in for that strike.
+    market_strikes: HashMap<String, f64>,
+    // Backfilled YES prices per market, kept to warm up a newly selected
+    // strategy as well.
+    backfill_requested: HashSet<String>,
+    backfilled: HashMap<String, Vec<f64>>,
+    // Reused by every evaluation so joining a quote stops allocating.
+    joined_buffer: Option<JoinedLiveInputs>,
+    decision_latency: WindowedLatencyHistogram,
+    // Latest tick per predictor source, as delivered by its poll task or the
+    // replay.
+    latest_predictors: Vec<PredictorTick>,
+
+    positions: PositionManager,
+    // Signed YES shares per market in orders the execution task has not
+    // answered yet, counted with the position so orders in flight cannot
+    // stack past the position limit.
+    pending_qty: HashMap<String, f64>,
+    brackets: BracketBook,
+    // The daily cap is checked per tick against the live setting.
+    drawdown_watch: DrawdownWatch,
+    trading_day: TradingDay,
+    var_estimator: VarEstimator,
+    var_anchor_equity: Decimal,
+    intent_throttle: IntentThrottle,
+    loss_cooldown: LossStreakCooldown,
+    was_cooling_down: bool,
+    // Last known halt state, for journal rows written before this tick's.
+    halted: bool,
+    daily_cap_breached: bool,
+    win_rate_collapsed: bool,
+    // Feeds whose reconnect circuit is open, so each outage alerts once.
+    degraded_feeds: HashSet<String>,
+    // Feeds refusing this host, so each block alerts once until the feed
+    // gets past it.
+    blocked_feeds: HashSet<String>,
+    statsd: Option<StatsdEmitter>,
+    // Update totals already reported, so statsd gets per-tick deltas.
+    reported_feed_counts: HashMap<String, u64>,
+    reported_cache: http::CacheStats,
+    spread_skips: u64,
+    fills: u64,
+    outcomes: TradeOutcomeTracker,
+    last_pause_state: bool,
+    lag_gate: LagTriggerGate,
+    strategy_registry: StrategyRegistry,
+    active_strategy: Box<dyn Strategy>,
+    regime_classifier: RegimeClassifier,
+    last_regime: Regime,
+    predictor_weights: PredictorWeights,
+}
+
+impl<C: Clock> PaperLive<C> {
+    fn new(setup: PaperLiveSetup, clock: C) -> Self {
+        let PaperLiveSetup {
+            state,
+            client,
+            market_feeds,
+            runtime_cfg,
+            replayed_predictors,
+            venues,
+            recorder,
+            journal,
+        } = setup;
+        let interval_ms = state.runtime_settings().decision_interval_ms;
+        let (poll_interval_tx, poll_interval) = watch::channel(Duration::from_millis(interval_ms));
+        let replaying = replayed_predictors.is_some();
+        let ingest = tasks::spawn_ingest(
+            &client,
+            &market_feeds,
+            &runtime_cfg.predictors,
+            poll_interval,
+            runtime_cfg.backfill_trades,
+            replayed_predictors,
+        );
+        let execution = tasks::spawn_execution(
+            &market_feeds.supervisor,
+            venues,
+            runtime_cfg.live_feature_enabled,
+        );
+        tasks::register_decision_task(&market_feeds.supervisor);
+
+        // Metrics are best effort: an agent that cannot be resolved only
+        // costs the metrics, not the loop.
+        let statsd = runtime_cfg.statsd.as_ref().and_then(|config| {
+            StatsdEmitter::connect(config)
+                .map_err(|err| eprintln!("statsd metrics to {} disabled: {err}", config.addr))
+                .ok()
+        });
+        let initial_settings = state.runtime_settings();
+        let lag_gate = LagTriggerGate::new(
+            initial_settings.lag_cooldown_secs.saturating_mul(1_000),
+            initial_settings.lag_persistence_evals.max(1),
+        )
+        .expect("lag persistence is clamped to at least one evaluation");
+        let strategy_registry = StrategyRegistry::with_builtins();
+        let active_strategy = strategy_registry
+            .create(&initial_settings.strategy)
+            .or_else(|| strategy_registry.create(DEFAULT_STRATEGY))
+            .expect("the default strategy is always registered");
+        let predictor_weights_path = Path::new(&runtime_cfg.predictor_weights_path);
+        let predictor_weights =
+            PredictorWeights::load(predictor_weights_path, PREDICTOR_LEARNING_RATE).unwrap_or_else(
+                |err| {
+                    eprintln!(
+                        "predictor weights at {} not restored: {err:?}",
+                        predictor_weights_path.display()
+                    );
+                    PredictorWeights::default()
+                },
+            );
+
+        state.set_discovered_markets(vec![DiscoveredMarket {
+            source: "polymarket".to_string(),
+            market_id: PAPER_MARKET_ID.to_string(),
+            liquidity_score: None,
+            selected: true,
+        }]);
+
+        Self {
+            positions: PositionManager::new(runtime_cfg.starting_equity),
+            pending_qty: HashMap::new(),
+            brackets: BracketBook::new(runtime_cfg.brackets),
+            drawdown_watch: DrawdownWatch::new(
+                runtime_cfg.starting_equity,
+                runtime_cfg.max_drawdown_fraction,
+            )
+            .expect("starting equity and drawdown limit are validated"),
+            trading_day: TradingDay::new(
+                runtime_cfg.trading_day_utc_offset_mins,
+                clock.now_ms(),
+                runtime_cfg.starting_equity,
+            )
+            .expect("trading day offset is validated"),
+            var_estimator: VarEstimator::new(VAR_WINDOW).expect("VaR window is a valid size"),
+            var_anchor_equity: runtime_cfg.starting_equity,
+            intent_throttle: IntentThrottle::new(runtime_cfg.max_intents_per_min)
+                .expect("intent rate limit is validated"),
+            loss_cooldown: LossStreakCooldown::new(
+                runtime_cfg.loss_streak_limit,
+                runtime_cfg.loss_streak_cooldown_ms,
+            )
+            .expect("loss streak limit and cooldown are validated"),
+            regime_classifier: RegimeClassifier::new(
+                REGIME_HALF_LIFE_MS,
+                runtime_cfg.regime_thresholds,
+            )
+            .expect("regime half-life is non-zero"),
+            state,
+            client,
+            market_feeds,
+            runtime_cfg,
+            recorder,
+            journal,
+            clock,
+            replaying,
+            ingest,
+            execution,
+            interval_ms,
+            interval: decision_interval(interval_ms),
+            poll_interval_tx,
+            tick: 0,
+            last_spot_px: HashMap::new(),
+            last_discovered: Vec::new(),
+            market_strikes: HashMap::new(),
+            backfill_requested: HashSet::new(),
+            backfilled: HashMap::new(),
+            joined_buffer: None,
+            decision_latency: WindowedLatencyHistogram::new(
+                DECISION_LATENCY_WINDOW_MS,
+                DECISION_LATENCY_WINDOWS,
+            ),
+            latest_predictors: Vec::new(),
+            was_cooling_down: false,
+            halted: false,
+            daily_cap_breached: false,
+            win_rate_collapsed: false,
+            degraded_feeds: HashSet::new(),
+            blocked_feeds: HashSet::new(),
+            statsd,
+            reported_feed_counts: HashMap::new(),
+            reported_cache: http::CacheStats::default(),
+            spread_skips: 0,
+            fills: 0,
+            outcomes: TradeOutcomeTracker::default(),
+            last_pause_state: false,
+            lag_gate,
+            strategy_registry,
+            active_strategy,
+            last_regime: Regime::Normal,
+            predictor_weights,
+        }
+    }
+
+    async fn run(mut self) {
+        loop {
+            self.interval.tick().await;
+            let mut inputs = self.read_signals();
+            self.track_regime(&inputs);
+            self.apply_settings(&inputs);
+            self.apply_backfills(&inputs);
+            self.publish_forecast(&inputs);
+            self.track_quotes(&mut inputs);
+            self.publish_price_snapshot(&inputs);
+            let predictor_reconnects = self.read_predictors(&mut inputs);
+            self.publish_feed_status(&inputs, predictor_reconnects);
+
+            let mut counts = TickCounts::default();
+            self.book_executions(&inputs, &mut counts);
+            self.check_brackets(&inputs);
+            self.request_resolutions(&inputs);
+            self.apply_resolutions(&inputs);
+            let risk = self.update_risk(&inputs);
+
+            let decision_started = Instant::now();
+            self.publish_pause_state(&inputs);
+            if !inputs.settings.trading_paused {
+                for quote in preferred_quotes(&inputs.quotes) {
+                    self.decide(&inputs, &risk, quote, &mut counts);
+                }
+            }
+            self.publish_snapshots(&inputs, &risk, &counts, decision_started);
+        }
+    }
+
+    /// Starts a tick: the spot reference and momentum of every tracked
+    /// asset, from the freshest venue prices.
+    fn read_signals(&mut self) -> TickInputs {
+        self.tick = self.tick.saturating_add(1);
+        let tick_ts = self.clock.now_ms();
+        self.state.record_loop_heartbeat(self.tick, tick_ts);
+
+        let feed_now_ms = self.clock.now_ms();
+        let mut signals: Vec<AssetSignal> = Vec::with_capacity(self.runtime_cfg.assets.len());
+        for &asset in &self.runtime_cfg.assets {
+            let samples = self.market_feeds.fresh_spot_prices(asset, feed_now_ms);
+            let composite = self.market_feeds.composite(asset);
+            let last_px = self.last_spot_px.get(&asset).copied();
+            let fallback_px = (asset == Asset::Btc).then_some(FALLBACK_BTC_USD);
+            let Some(spot_px) = composite
+                .map(|composite| composite.raw.px_median)
+                .or_else(|| median_f64(&samples))
+                .or(last_px)
+                .or(fallback_px)
+            else {
+                continue;
+            };
+            // Momentum is measured against the smoothed composite when the
+            // feeds have one, so a single noisy print does not read as a move.
+            let reference = composite.map(|composite| composite.smoothed_px).or(last_px);
+            let spread_signal = match reference {
+                Some(reference) if reference > 0.0 => {
+                    ((spot_px - reference) / reference) * 10_000.0 * BTC_MOMENTUM_MULTIPLIER
+                }
+                _ => 0.0,
+            };
+            self.last_spot_px.insert(asset, spot_px);
+
+            let (forecast_px, forecast_delta_pct) = forecast_15m(spot_px, spread_signal);
+            signals.push(AssetSignal {
+                asset,
+                composite,
+                spot_px,
+                spread_signal,
+                venue_count: samples.len() as u32,
+                forecast_px,
+                forecast_delta_pct,
+            });
+        }
+        let btc_signal = *signals
+            .iter()
+            .find(|signal| signal.asset == Asset::Btc)
+            .expect("btc is always tracked and falls back to a fixed price");
+        let regime = self
+            .regime_classifier
+            .update(btc_signal.spot_px, self.clock.now_ms());
+
+        TickInputs {
+            tick: self.tick,
+            ts: tick_ts,
+            settings: self.state.runtime_settings(),
+            signals,
+            btc_signal,
+            regime,
+            quotes: self.market_feeds.quotes(),
+            fused_fair_yes: None,
+        }
+    }
+
+    fn track_regime(&mut self, inputs: &TickInputs) {
+        if inputs.regime == self.last_regime {
+            return;
+        }
+        self.publish_log(ExecutionLogEntry {
+            tick: inputs.tick,
+            ts: inputs.ts,
+            event: "regime_change".to_string(),
+            headline: format!("Regime {}", inputs.regime.as_str()),
+            detail: format!(
+                "{} -> {} vol={:.2}bps/min",
+                self.last_regime.as_str(),
+                inputs.regime.as_str(),
+                self.regime_classifier.volatility_bps().unwrap_or(0.0)
+            ),
+        });
+        self.last_regime = inputs.regime;
+    }
+
+    /// Applies settings changed through the API: the decision interval, the
+    /// lag gate, the selected strategy and its parameters.
+    fn apply_settings(&mut self, inputs: &TickInputs) {
+        let settings = &inputs.settings;
+        if settings.decision_interval_ms != self.interval_ms {
+            self.interval_ms = settings.decision_interval_ms;
+            self.interval = decision_interval(self.interval_ms);
+            let _ = self
+                .poll_interval_tx
+                .send(Duration::from_millis(self.interval_ms));
+        }
+        let _ = self.lag_gate.reconfigure(
+            settings.lag_cooldown_secs.saturating_mul(1_000),
+            settings.lag_persistence_evals,
+        );
+        let api_params = self.state.strategy_params();
+        let strategy_params = StrategyParams {
+            momentum_lookback_ms: api_params.momentum_lookback_secs.saturating_mul(1_000),
+            momentum_threshold_bps: api_params.momentum_threshold_bps,
+            exit_threshold_fraction: api_params.exit_threshold_fraction,
+        };
+        if settings.strategy != self.active_strategy.name() {
+            if let Some(strategy) = self.strategy_registry.create(&settings.strategy) {
+                self.publish_log(ExecutionLogEntry {
+                    tick: inputs.tick,
+                    ts: inputs.ts,
+                    event: "strategy_switch".to_string(),
+                    headline: "Strategy Switched".to_string(),
+                    detail: format!("{} -> {}", self.active_strategy.name(), strategy.name()),
+                });
+                self.active_strategy = strategy;
+                for (market_id, mids) in &self.backfilled {
+                    self.active_strategy.warm_up(market_id, mids);
+                }
+            }
+        }
+        self.active_strategy.apply_params(&strategy_params);
+    }
+
+    fn apply_backfills(&mut self, inputs: &TickInputs) {
+        while let Ok(backfill) = self.ingest.backfills.try_recv() {
+            let mids: Vec<f64> = backfill.trades.iter().map(|(_, yes_px)| *yes_px).collect();
+            self.active_strategy.warm_up(&backfill.market_id, &mids);
+            self.publish_log(ExecutionLogEntry {
+                tick: inputs.tick,
+                ts: inputs.ts,
+                event: "trade_backfill".to_string(),
+                headline: "Trades Backfilled".to_string(),
+                detail: format!("{} trades for {}", mids.len(), backfill.market_id),
+            });
+            self.backfilled.insert(backfill.market_id, mids);
+        }
+    }
+
+    fn publish_forecast(&self, inputs: &TickInputs) {
+        let forecast_summary = BtcForecastSummary {
+            horizon_minutes: 15,
+            current_btc_usd: inputs.btc_signal.spot_px,
+            forecast_btc_usd: inputs.btc_signal.forecast_px,
+            delta_pct: inputs.btc_signal.forecast_delta_pct,
+            tick: inputs.tick,
+            ts: inputs.ts,
+        };
+        self.state.set_btc_forecast_summary(forecast_summary);
+        let _ = self
+            .state
+            .publish_event(RuntimeEvent::btc_forecast(forecast_summary));
+    }
+
+    /// Publishes newly discovered markets, records each market's strike and
+    /// asks for the trade backfill of new Polymarket markets.
+    fn track_quotes(&mut self, inputs: &mut TickInputs) {
+        let discovered = discovered_markets(&inputs.quotes);
+        if !discovered.is_empty() && discovered != self.last_discovered {
+            self.state.set_discovered_markets(discovered.clone());
+            self.last_discovered = discovered;
+        }
+
+        if inputs.quotes.is_empty() {
+            inputs.quotes.push(PolymarketQuoteTick {
+                market_slug: PAPER_MARKET_ID.to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
+                best_yes_bid: 0.48,
+                best_yes_ask: 0.52,
+                mid_yes: 0.50,
+                ts: inputs.ts,
+                depth: BookDepth::default(),
+                liquidity_score: None,
+            });
+        }
+        let quotes = &inputs.quotes;
+
+        self.market_strikes
+            .retain(|slug, _| quotes.iter().any(|quote| &quote.market_slug == slug));
+        for quote in quotes {
+            if let Some(signal) = inputs
+                .signals
+                .iter()
+                .find(|signal| signal.asset == quote.asset)
+            {
+                self.market_strikes
+                    .entry(quote.market_slug.clone())
+                    .or_insert(signal.spot_px);
+            }
+        }
+        let is_tracked =
+            |market_id: &String| quotes.iter().any(|quote| &quote.market_slug == market_id);
+        self.backfilled.retain(|market_id, _| is_tracked(market_id));
+        self.backfill_requested.retain(is_tracked);
+        if self.runtime_cfg.backfill_trades > 0 {
+            for quote in quotes {
+                if quote.source == QuoteSource::Polymarket
+                    && quote.market_slug != PAPER_MARKET_ID
+                    && self.backfill_requested.insert(quote.market_slug.clone())
+                {
+                    let _ = self
+                        .ingest
+                        .backfill_requests
+                        .try_send(quote.market_slug.clone());
+                }
+            }
+        }
+    }
+
+    fn publish_price_snapshot(&self, inputs: &TickInputs) {
+        let feed_now_ms = self.clock.now_ms();
+        let venue_px = |venue: &str| {
+            self.market_feeds
+                .fresh_tick(Asset::Btc, venue, feed_now_ms)
+                .map(|tick| tick.px)
+        };
+        let quotes_for = |asset: Asset, source: QuoteSource| {
+            inputs
+                .quotes
+                .iter()
+                .find(|quote| quote.asset == asset && quote.source == source)
+        };
+        let primary_quote = quotes_for(Asset::Btc, QuoteSource::Polymarket);
+        let kalshi_quote = quotes_for(Asset::Btc, QuoteSource::Kalshi);
+        let asset_snapshots: Vec<AssetPriceSnapshot> = inputs
+            .signals
+            .iter()
+            .map(|signal| {
+                let polymarket = quotes_for(signal.asset, QuoteSource::Polymarket);
+                let kalshi = quotes_for(signal.asset, QuoteSource::Kalshi);
+                AssetPriceSnapshot {
+                    asset: signal.asset.as_str().to_string(),
+                    composite_raw: signal.composite.map(|composite| composite.raw.px_median),
+                    composite_smoothed: signal.composite.map(|composite| composite.smoothed_px),
+                    forecast_15m: Some(signal.forecast_px),
+                    polymarket_market_id: polymarket.map(|quote| quote.market_slug.clone()),
+                    polymarket_yes_mid: polymarket.map(|quote| quote.mid_yes),
+                    kalshi_market_id: kalshi.map(|quote| quote.market_slug.clone()),
+                    kalshi_yes_mid: kalshi.map(|quote| quote.mid_yes),
+                }
+            })
+            .collect();
+        let btc_composite = inputs.btc_signal.composite;
+        let price_snapshot = PriceSnapshot {
+            coinbase_btc_usd: venue_px("coinbase"),
+            binance_btc_usdt: venue_px("binance"),
+            kraken_btc_usd: venue_px("kraken"),
+            polymarket_market_id: primary_quote.map(|quote| quote.market_slug.clone()),
+            polymarket_yes_bid: primary_quote.map(|quote| quote.best_yes_bid),
+            polymarket_yes_ask: primary_quote.map(|quote| quote.best_yes_ask),
+            polymarket_yes_mid: primary_quote.map(|quote| quote.mid_yes),
+            kalshi_market_id: kalshi_quote.map(|quote| quote.market_slug.clone()),
+            kalshi_yes_bid: kalshi_quote.map(|quote| quote.best_yes_bid),
+            kalshi_yes_ask: kalshi_quote.map(|quote| quote.best_yes_ask),
+            kalshi_yes_mid: kalshi_quote.map(|quote| quote.mid_yes),
+            btc_composite_raw: btc_composite.map(|composite| composite.raw.px_median),
+            btc_composite_smoothed: btc_composite.map(|composite| composite.smoothed_px),
+            assets: asset_snapshots,
+            tick: inputs.tick,
+            ts: inputs.ts,
+        };
+        self.state.set_price_snapshot(price_snapshot.clone());
+        let _ = self
+            .state
+            .publish_event(RuntimeEvent::price_snapshot(price_snapshot));
+    }
+
+    /// Takes in the predictor poll tasks' ticks, records them and fuses the
+    /// fresh ones into this tick's fair value. Returns the predictors'
+    /// scheduled retries for the feed alerts.
+    fn read_predictors(&mut self, inputs: &mut TickInputs) -> Vec<FeedReconnect> {
+        let mut predictor_reconnects = Vec::new();
+        while let Ok(event) = self.ingest.predictors.try_recv() {
+            match event {
+                PollEvent::Value(predictor) => {
+                    if let Some(recorder) = &self.recorder {
+                        let _ = recorder
+                            .send((predictor.ts_ms, RecordedTick::Predictor(predictor.clone())));
+                    }
+                    self.latest_predictors
+                        .retain(|latest| latest.source != predictor.source);
+                    self.latest_predictors.push(predictor);
+                }
+                PollEvent::Reconnect(reconnect) => predictor_reconnects.push(reconnect),
+            }
+        }
+        let predictor_now_ms = self.clock.now_ms();
+        self.latest_predictors.retain(|predictor| {
+            predictor_now_ms.saturating_sub(predictor.ts_ms) <= feeds::BTC_FEED_STALENESS_MS
+        });
+        inputs.fused_fair_yes = fuse_weighted_predictors(
+            &self.latest_predictors,
+            &self.predictor_weights,
+            predictor_now_ms,
+        )
+        .ok()
+        .map(|fused| fused.fair_yes_px);
+        let primary_quote = inputs
+            .quotes
+            .iter()
+            .find(|quote| quote.asset == Asset::Btc && quote.source == QuoteSource::Polymarket);
+        if let Some(quote) = primary_quote.filter(|quote| quote.market_slug != PAPER_MARKET_ID) {
+            self.predictor_weights
+                .observe(&quote.market_slug, &self.latest_predictors);
+        }
+        predictor_reconnects
+    }
+
+    /// Alerts on feed reconnects, blocks and throttles, and publishes the
+    /// feeds' update counts, cache stats and quarantined payloads.
+    fn publish_feed_status(
+        &mut self,
+        inputs: &TickInputs,
+        predictor_reconnects: Vec<FeedReconnect>,
+    ) {
+        let reconnects = self
+            .market_feeds
+            .drain_reconnects()
+            .into_iter()
+            .chain(predictor_reconnects);
+        for reconnect in reconnects {
+            self.alert_reconnect(inputs.ts, reconnect);
+        }
+
+        for throttle in self.client.drain_throttles() {
+            let _ = self.state.publish_event(RuntimeEvent::feed_throttle(
+                throttle.host,
+                throttle.reason.as_str(),
+                throttle.retry_after_ms,
+            ));
+        }
+
+        let source_counts: Vec<SourceCount> = self
+            .market_feeds
+            .update_counts()
+            .into_iter()
+            .map(|(source, count)| SourceCount {
+                endpoint: self.market_feeds.endpoint(&source),
+                blocked: match self.market_feeds.health(&source) {
+                    Some(VenueHealth::Blocked { status, until_ms }) => {
+                        Some(FeedBlock { status, until_ms })
+                    }
+                    _ => None,
+                },
+                source,
+                count,
+            })
+            .collect();
+        self.state.set_feed_source_counts(source_counts.clone());
+        let cache = self.client.cache_stats();
+        self.state.set_market_list_cache(FeedCacheStats {
+            not_modified: cache.not_modified,
+            unchanged: cache.unchanged,
+            changed: cache.changed,
+        });
+        if let Some(statsd) = &self.statsd {
+            for source_count in &source_counts {
+                let reported = self
+                    .reported_feed_counts
+                    .insert(source_count.source.clone(), source_count.count)
+                    .unwrap_or(0);
+                statsd.count(
+                    &format!("feed.updates.{}", source_count.source),
+                    source_count.count.saturating_sub(reported),
+                );
+            }
+            statsd.count("feed.cache.hits", cache.hits() - self.reported_cache.hits());
+            statsd.count(
+                "feed.cache.misses",
+                cache.changed - self.reported_cache.changed,
+            );
+        }
+        self.reported_cache = cache;
+        let (error_counts, quarantined) = self.market_feeds.quarantine_report();
+        self.state.set_feed_errors(FeedErrorsResponse {
+            counts: error_counts
+                .into_iter()
+                .map(|(source, count)| FeedErrorCount { source, count })
+                .collect(),
+            recent: quarantined
+                .into_iter()
+                .map(|payload| QuarantinedMessage {
+                    source: payload.source,
+                    reason: payload.reason,
+                    payload: payload.raw,
+                    ts: payload.ts_ms,
+                })
+                .collect(),
+        });
+        let _ = self.state.publish_event(RuntimeEvent::feed_health(
+            self.state.feed_health().mode,
+            source_counts,
+        ));
+    }
+
+    fn alert_reconnect(&mut self, tick_ts: u64, reconnect: FeedReconnect) {
+        if let Some(statsd) = &self.statsd {
+            statsd.count(&format!("feed.reconnects.{}", reconnect.venue), 1);
+        }
+        if let VenueFeedError::Blocked(status) = reconnect.reason {
+            if self.blocked_feeds.insert(reconnect.venue.clone()) {
+                let _ = self.state.publish_event(RuntimeEvent::risk_alert(
+                    RiskAlertKind::FeedBlocked,
+                    format!(
+                        "{} refused this host with HTTP {status}; retrying in {}s",
+                        reconnect.venue,
+                        reconnect.delay_ms / 1_000
+                    ),
+                    tick_ts,
+                ));
+            }
+        } else {
+            self.blocked_feeds.remove(&reconnect.venue);
+        }
+        if !reconnect.circuit_open {
+            self.degraded_feeds.remove(&reconnect.venue);
+        } else if self.degraded_feeds.insert(reconnect.venue.clone()) {
+            let _ = self.state.publish_event(RuntimeEvent::risk_alert(
+                RiskAlertKind::FeedDegraded,
+                format!(
+                    "{} circuit open after {} failures: {}",
+                    reconnect.venue,
+                    reconnect.attempt,
+                    reconnect.reason.as_str()
+                ),
+                tick_ts,
+            ));
+        }
+        let _ = self.state.publish_event(RuntimeEvent::feed_reconnect(
+            reconnect.venue,
+            reconnect.attempt,
+            reconnect.delay_ms,
+            reconnect.circuit_open,
+            reconnect.reason.as_str(),
+        ));
+    }
+
+    /// Books what the execution task made of the orders sent so far. Marks
+    /// and brackets follow, so a fill is marked on the tick it is booked.
+    fn book_executions(&mut self, inputs: &TickInputs, counts: &mut TickCounts) {
+        while let Ok(result) = self.execution.results.try_recv() {
+            let pending = result.context;
+            self.release_pending(&pending);
+            match result.report {
+                Ok(report) => self.book_fill(inputs, pending, report, counts),
+                Err(rejection) => self.reject_order(inputs, pending, rejection),
+            }
+        }
+    }
+
+    fn book_fill(
+        &mut self,
+        inputs: &TickInputs,
+        pending: PendingOrder,
+        report: OrderReport,
+        counts: &mut TickCounts,
+    ) {
+        let PendingOrder {
+            quote,
+            side,
+            limit_px,
+            origin,
+            ..
+        } = pending;
+        if let Some(request) = &report.unsent_request {
+            self.publish_log(ExecutionLogEntry {
+                tick: inputs.tick,
+                ts: inputs.ts,
+                event: "dry_run_order".to_string(),
+                headline: "Dry Run Order Signed".to_string(),
+                detail: format!(
+                    "{} est_fee={:.4} request={request}",
+                    report.order_id, report.fee
+                ),
+            });
+        }
+        // Live venues may fill less than asked, or better than the limit.
+        let order_qty = report.filled_qty;
+        let signed_qty = if matches!(side, PaperOrderSide::Buy) {
+            order_qty
+        } else {
+            -order_qty
+        };
+        let fill_px = report.avg_fill_px.unwrap_or(limit_px);
+
+        let realized_before = self.positions.realized_pnl();
+        self.positions
+            .apply_fill(quote.source, &quote.market_slug, signed_qty, fill_px);
+        self.positions.charge_fee(&quote.market_slug, report.fee);
+        let realized_pnl = self.positions.realized_pnl() - realized_before;
+        self.brackets.sync(
+            &quote.market_slug,
+            self.positions.position(&quote.market_slug),
+        );
+        self.fills = self.fills.saturating_add(1);
+        counts.fills = counts.fills.saturating_add(1);
+        if let Some(realized) = self.outcomes.apply_fill(side, fill_px, order_qty) {
+            let _ = self
+                .loss_cooldown
+                .record_close(realized, self.clock.now_ms());
+        }
+
+        let _ = self.state.publish_event(RuntimeEvent::paper_fill(
+            &quote.market_slug,
+            side,
+            order_qty,
+            fill_px,
+            report.fee,
+        ));
+        let (decision, evaluation) = match origin {
+            OrderOrigin::Strategy {
+                decision,
+                evaluation,
+            } => (decision, evaluation),
+            OrderOrigin::Bracket(trigger) => {
+                self.book_bracket_exit(inputs, &quote, &trigger, signed_qty, fill_px, realized_pnl);
+                return;
+            }
+        };
+        let fill_log = ExecutionLogEntry {
+            tick: inputs.tick,
+            ts: inputs.ts,
+            event: "paper_fill".to_string(),
+            headline: format!("Filled {side:?}"),
+            detail: format!(
+                "{}:{} qty={} @ {:.4} fee={:.4}",
+                quote.source.as_str(),
+                quote.market_slug,
+                order_qty,
+                fill_px,
+                report.fee
+            ),
+        };
+        self.journal_decision(
+            &fill_log,
+            PaperJournalRowKind::PaperFill,
+            &quote,
+            Some(decision),
+        );
+        self.publish_log(fill_log);
+        record_signal(&self.state, evaluation, SignalAction::Filled);
+    }
+
+    fn book_bracket_exit(
+        &mut self,
+        inputs: &TickInputs,
+        quote: &PolymarketQuoteTick,
+        trigger: &BracketTrigger,
+        signed_qty: f64,
+        fill_px: f64,
+        realized_pnl: Decimal,
+    ) {
+        let _ = self.state.publish_event(RuntimeEvent::bracket_triggered(
+            &quote.market_slug,
+            trigger.kind.as_str(),
+            trigger.bracket.entry_px,
+            trigger.trigger_px,
+            fill_px,
+            signed_qty,
+            realized_pnl,
+        ));
+        let log = ExecutionLogEntry {
+            tick: inputs.tick,
+            ts: inputs.ts,
+            event: "bracket_triggered".to_string(),
+            headline: match trigger.kind {
+                BracketKind::StopLoss => "Stop Loss Hit".to_string(),
+                BracketKind::TakeProfit => "Take Profit Hit".to_string(),
+            },
+            detail: format!(
+                "{}:{} entry={:.4} trigger={:.4} qty={} @ {:.4} pnl={:.4}",
+                quote.source.as_str(),
+                quote.market_slug,
+                trigger.bracket.entry_px,
+                trigger.trigger_px,
+                signed_qty,
+                fill_px,
+                realized_pnl
+            ),
+        };
+        self.journal_decision(&log, PaperJournalRowKind::PaperFill, quote, None);
+        self.publish_log(log);
+    }
+
+    fn reject_order(
+        &mut self,
+        inputs: &TickInputs,
+        pending: PendingOrder,
+        rejection: ExecutionRejection,
+    ) {
+        self.reject_intent(
+            inputs,
+            &pending.quote,
+            pending.decision(),
+            pending.qty,
+            rejection,
+        );
+        match pending.origin {
+            OrderOrigin::Strategy { evaluation, .. } => {
+                record_signal(&self.state, evaluation, SignalAction::RiskReject);
+            }
+            // The exit was not placed, so the bracket is re-armed to try
+            // again on the next quote through it.
+            OrderOrigin::Bracket(_) => {
+                let market_id = &pending.quote.market_slug;
+                self.brackets
+                    .sync(market_id, self.positions.position(market_id));
+            }
+        }
+    }
+
+    /// A quote through a position's stop or target sends an order that
+    /// flattens it at the book's impact price, ahead of any new decision.
+    fn check_brackets(&mut self, inputs: &TickInputs) {
+        for quote in &inputs.quotes {
+            self.positions.mark(&quote.market_slug, quote.mid_yes);
+        }
+
+        for quote in &inputs.quotes {
+            let Some(trigger) = self.brackets.check(&quote.market_slug, quote.mid_yes) else {
+                continue;
+            };
+            let close_qty = trigger.close_qty();
+            let (side, touch_px) = if close_qty > 0.0 {
+                (PaperOrderSide::Buy, quote.best_yes_ask)
+            } else {
+                (PaperOrderSide::Sell, quote.best_yes_bid)
+            };
+            let pending = PendingOrder {
+                quote: quote.clone(),
+                side,
+                qty: close_qty.abs(),
+                limit_px: quote.impact_price(close_qty).unwrap_or(touch_px),
+                origin: OrderOrigin::Bracket(trigger),
+            };
+            self.submit(inputs, pending, true);
+        }
+    }
+
+    /// A market that is no longer quoted has expired; the resolver task
+    /// looks up its outcome off the decision path. Predictor forecasts are
+    /// scored on the primary market whether or not it was traded.
+    fn request_resolutions(&self, inputs: &TickInputs) {
+        if inputs.tick % RESOLUTION_CHECK_EVERY_TICKS != 0 {
+            return;
+        }
+        let candidates: Vec<(QuoteSource, String)> = self
+            .positions
+            .positions()
+            .map(|position| (position.source, position.market_id.clone()))
+            .chain(
+                self.predictor_weights
+                    .pending_markets()
+                    .map(|market_id| (QuoteSource::Polymarket, market_id.to_string())),
+            )
+            .filter(|(_, market_id)| {
+                !inputs
+                    .quotes
+                    .iter()
+                    .any(|quote| quote.market_slug == *market_id)
+            })
+            .collect();
+        let mut lookups: Vec<(QuoteSource, String)> = Vec::new();
+        for candidate in candidates {
+            if !lookups.contains(&candidate) {
+                lookups.push(candidate);
+            }
+        }
+        for lookup in lookups {
+            let _ = self.ingest.resolution_requests.try_send(lookup);
+        }
+    }
+
+    fn apply_resolutions(&mut self, inputs: &TickInputs) {
+        while let Ok(resolution) = self.ingest.resolutions.try_recv() {
+            let market_id = resolution.market_id;
+            if let Some(settlement) = self.positions.settle(&market_id, resolution.outcome) {
+                self.brackets.sync(&market_id, None);
+                let close_side = if settlement.qty > 0.0 {
+                    PaperOrderSide::Sell
+                } else {
+                    PaperOrderSide::Buy
+                };
+                if let Some(realized) =
+                    self.outcomes
+                        .apply_fill(close_side, settlement.settle_px, settlement.qty.abs())
+                {
+                    let _ = self
+                        .loss_cooldown
+                        .record_close(realized, self.clock.now_ms());
+                }
+
+                let _ = self.state.publish_event(RuntimeEvent::market_resolved(
+                    settlement.source.as_str(),
+                    &settlement.market_id,
+                    settlement.outcome.as_str(),
+                    settlement.settle_px,
+                    settlement.qty,
+                    settlement.realized_pnl,
+                ));
+                self.publish_log(ExecutionLogEntry {
+                    tick: inputs.tick,
+                    ts: inputs.ts,
+                    event: "market_resolved".to_string(),
+                    headline: format!("Resolved {}", settlement.outcome.as_str().to_uppercase()),
+                    detail: format!(
+                        "{}:{} qty={} pnl={:.4}",
+                        settlement.source.as_str(),
+                        settlement.market_id,
+                        settlement.qty,
+                        settlement.realized_pnl
+                    ),
+                });
+            }
+
+            if resolution.source != QuoteSource::Polymarket
+                || !self
+                    .predictor_weights
+                    .resolve(&market_id, resolution.outcome == MarketOutcome::Yes)
+            {
+                continue;
+            }
+            if !self.replaying {
+                let _ = self
+                    .predictor_weights
+                    .save(Path::new(&self.runtime_cfg.predictor_weights_path));
+            }
+            self.publish_log(ExecutionLogEntry {
+                tick: inputs.tick,
+                ts: inputs.ts,
+                event: "predictor_weights".to_string(),
+                headline: "Predictor Weights Updated".to_string(),
+                detail: self
+                    .predictor_weights
+                    .weights()
+                    .iter()
+                    .map(|entry| format!("{}={:.3}", entry.source.as_str(), entry.weight))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            });
+        }
+    }
+
+    /// Rolls the trading day and updates the daily cap, drawdown, loss
+    /// streak and VaR checks against the equity before this tick's
+    /// decisions, alerting on every change of state.
+    fn update_risk(&mut self, inputs: &TickInputs) -> RiskGate {
+        let (tick, tick_ts) = (inputs.tick, inputs.ts);
+        let equity_before = self.positions.equity();
+        if let Some(closed) = self.trading_day.roll(self.clock.now_ms(), equity_before) {
+            let day = DayPnl {
+                date: closed.date,
+                start_equity: closed.start_equity,
+                end_equity: closed.end_equity,
+                pnl: closed.pnl,
+            };
+            let log = ExecutionLogEntry {
+                tick,
+                ts: tick_ts,
+                event: "day_rolled".to_string(),
+                headline: format!("Trading Day {} Closed", day.date),
+                detail: format!(
+                    "start={:.2} end={:.2} pnl={:.2}; daily cap reset",
+                    day.start_equity, day.end_equity, day.pnl
+                ),
+            };
+            self.state.push_daily_pnl(day.clone(), DAILY_PNL_HISTORY);
+            let _ = self.state.publish_event(RuntimeEvent::day_rolled(day));
+            self.publish_log(log);
+        }
+        let pnl_before = self.trading_day.pnl(equity_before);
+        let daily_loss_limit = self.trading_day.start_equity()
+            * Decimal::from_f64(inputs.settings.daily_loss_cap_pct / 100.0).unwrap_or_default();
+        let daily_halted = pnl_before <= -daily_loss_limit;
+        let drawdown_was_halted = self.drawdown_watch.is_halted();
+        self.drawdown_watch.update(equity_before);
+        let drawdown_halted = self.drawdown_watch.is_halted();
+        self.halted = daily_halted || drawdown_halted;
+        if drawdown_halted && !drawdown_was_halted {
+            let log = ExecutionLogEntry {
+                tick,
+                ts: tick_ts,
+                event: "drawdown_halt".to_string(),
+                headline: "Drawdown Halt".to_string(),
+                detail: format!(
+                    "equity={:.2} peak={:.2} drawdown={:.2}%",
+                    equity_before,
+                    self.drawdown_watch.peak_equity(),
+                    self.drawdown_watch.drawdown_fraction(equity_before) * 100.0
+                ),
+            };
+            let _ = self.state.publish_event(RuntimeEvent::risk_alert(
+                RiskAlertKind::Halt,
+                log.detail.clone(),
+                tick_ts,
+            ));
+            self.publish_log(log);
+        }
+        if daily_halted && !self.daily_cap_breached {
+            let _ = self.state.publish_event(RuntimeEvent::risk_alert(
+                RiskAlertKind::DailyCapBreach,
+                format!(
+                    "{} pnl={:.2} cap={:.2}",
+                    self.trading_day.date(),
+                    pnl_before,
+                    daily_loss_limit
+                ),
+                tick_ts,
+            ));
+        }
+        self.daily_cap_breached = daily_halted;
+
+        let cooling_down = self.loss_cooldown.is_cooling_down(self.clock.now_ms());
+        if cooling_down && !self.was_cooling_down {
+            let resume_at_ms = self.loss_cooldown.resume_at_ms().unwrap_or_default();
+            let _ = self.state.publish_event(RuntimeEvent::cooldown_started(
+                self.loss_cooldown.max_losing_streak(),
+                resume_at_ms,
+            ));
+            self.publish_log(ExecutionLogEntry {
+                tick,
+                ts: tick_ts,
+                event: "cooldown_started".to_string(),
+                headline: "Loss Streak Cooldown".to_string(),
+                detail: format!(
+                    "{} losing closes; entries resume at {}",
+                    self.loss_cooldown.max_losing_streak(),
+                    resume_at_ms
+                ),
+            });
+        } else if !cooling_down && self.was_cooling_down {
+            self.publish_log(ExecutionLogEntry {
+                tick,
+                ts: tick_ts,
+                event: "cooldown_ended".to_string(),
+                headline: "Cooldown Over".to_string(),
+                detail: "new entries allowed again".to_string(),
+            });
+        }
+        self.was_cooling_down = cooling_down;
+
+        if tick % (VAR_SAMPLE_MS / self.interval_ms).max(1) == 0 {
+            let _ = self
+                .var_estimator
+                .push(to_f64(equity_before - self.var_anchor_equity));
+            self.var_anchor_equity = equity_before;
+        }
+        let var_95 = self.var_estimator.var_95();
+        let var_scale = match (var_95, self.runtime_cfg.var_limit) {
+            (Some(var), Some(limit)) => var_size_scale(var, limit),
+            _ => 1.0,
+        };
+        self.state.set_risk_status(RiskStatus {
+            halted: daily_halted || drawdown_halted,
+            equity: equity_before,
+            peak_equity: self.drawdown_watch.peak_equity(),
+            drawdown_pct: self.drawdown_watch.drawdown_fraction(equity_before) * 100.0,
+            var_95,
+            var_limit: self.runtime_cfg.var_limit,
+            var_size_scale: var_scale,
+            var_samples: self.var_estimator.sample_count(),
+            losing_streak: self.loss_cooldown.losing_streak(),
+            cooldown_resume_at_ms: self.loss_cooldown.resume_at_ms().filter(|_| cooling_down),
+        });
+
+        RiskGate {
+            equity: equity_before,
+            daily_halted,
+            drawdown_halted,
+            cooling_down,
+            var_scale,
+        }
+    }
+
+    fn publish_pause_state(&mut self, inputs: &TickInputs) {
+        let settings = &inputs.settings;
+        if settings.trading_paused == self.last_pause_state {
+            return;
+        }
+        let status = if settings.trading_paused {
+            "Trading Paused"
+        } else {
+            "Trading Resumed"
+        };
+        let log = ExecutionLogEntry {
+            tick: inputs.tick,
+            ts: inputs.ts,
+            event: "pause_state".to_string(),
+            headline: status.to_string(),
+            detail: format!("execution_mode={:?}", settings.execution_mode),
+        };
+        if settings.trading_paused {
+            let _ = self.state.publish_event(RuntimeEvent::risk_alert(
+                RiskAlertKind::KillSwitch,
+                log.detail.clone(),
+                inputs.ts,
+            ));
+        }
+        self.publish_log(log);
+        self.last_pause_state = settings.trading_paused;
+    }
+
+    /// Runs the strategy on `quote` and, if it wants an order the risk
+    /// checks admit, hands the order to the execution task.
+    fn decide(
+        &mut self,
+        inputs: &TickInputs,
+        risk: &RiskGate,
+        quote: &PolymarketQuoteTick,
+        counts: &mut TickCounts,
+    ) {
+        let settings = &inputs.settings;
+        // Orders risk the configured share of current equity.
+        let base_qty = risk_budget_qty(
+            settings.risk_per_trade_pct / 100.0,
+            to_f64(risk.equity),
+            quote.best_yes_bid,
+            quote.best_yes_ask,
+        )
+        .unwrap_or(0.0);
+
+        if risk.halted() {
+            let (reason, headline) = if risk.daily_halted {
+                ("daily loss cap reached", "Daily Cap Halt")
+            } else {
+                ("max drawdown from peak reached", "Drawdown Halt")
+            };
+            let rejection = ExecutionRejection {
+                reason,
+                headline,
+                detail: order_detail(quote, base_qty),
+            };
+            self.reject_intent(inputs, quote, None, base_qty, rejection);
+            return;
+        }
+
+        // Quotes on an asset without a spot reference yet are not traded.
+        let Some(signal) = inputs
+            .signals
+            .iter()
+            .find(|signal| signal.asset == quote.asset)
+        else {
+            return;
+        };
+        let decision_ms = self.clock.now_ms();
+        let decision = Decision {
+            spot_px: signal.spot_px,
+            fair_yes_px: self.fair_yes_px(inputs, signal, quote, decision_ms),
+        };
+        let market_position_qty = self.market_qty(&quote.market_slug);
+        let joined = JoinedLiveInputs::refill(
+            &mut self.joined_buffer,
+            BtcMedianTick::new(
+                signal.spot_px,
+                signal.spread_signal,
+                signal.venue_count,
+                inputs.tick,
+            ),
+            quote,
+        );
+        let lag_params = LagRunParams {
+            fair_yes_px: decision.fair_yes_px,
+            lag_threshold_pct: settings.lag_threshold_pct,
+            per_trade_risk_fraction: settings.risk_per_trade_pct / 100.0,
+            starting_equity: to_f64(self.runtime_cfg.starting_equity),
+            daily_loss_cap_fraction: settings.daily_loss_cap_pct / 100.0,
+            base_qty,
+            sizing: order_sizing(settings),
+            regime: inputs.regime,
+            seconds_to_expiry: Some(minutes_to_window_close(decision_ms) * 60.0),
+            market_position_qty,
+            max_position_qty: self.runtime_cfg.max_market_position,
+            size_scale: risk.var_scale,
+            min_touch_qty: self.runtime_cfg.min_touch_qty,
+        };
+        // Every evaluation is kept with what came of it, so signals that
+        // never reach a fill still show up in `/strategy/signals`.
+        let evaluation = detect_lag(
+            &quote.market_slug,
+            quote.mid_yes,
+            decision.fair_yes_px,
+            settings.lag_threshold_pct,
+        )
+        .ok()
+        .map(|lag| StrategySignal {
+            tick: inputs.tick,
+            ts: inputs.ts,
+            source: quote.source.as_str().to_string(),
+            market_id: lag.market_id,
+            mid_yes: lag.poly_mid,
+            fair_yes_px: lag.fair_yes_px,
+            divergence_pct: lag.divergence_pct,
+            triggered: lag.triggered,
+            action: SignalAction::NoIntent,
+        });
+        let (intent, runtime_events) = run_strategy_once_gated(
+            inputs.tick,
+            joined,
+            self.active_strategy.as_mut(),
+            &lag_params,
+            &mut self.lag_gate,
+            self.clock.now_ms(),
+        );
+        let Some(intent) = intent else {
+            record_signal(&self.state, evaluation, SignalAction::NoIntent);
+            return;
+        };
+        counts.lag_triggers = counts.lag_triggers.saturating_add(1);
+
+        // During a loss-streak cooldown only intents that shrink the market's
+        // position go through.
+        let intent_signed_qty = if intent.side == Signal::Buy {
+            intent.qty
+        } else {
+            -intent.qty
+        };
+        if risk.cooling_down
+            && (market_position_qty == 0.0
+                || market_position_qty.signum() == intent_signed_qty.signum())
+        {
+            let rejection = ExecutionRejection {
+                reason: "loss streak cooldown",
+                headline: "Cooldown Rejected",
+                detail: order_detail(quote, intent.qty),
+            };
+            self.reject_intent(inputs, quote, Some(decision), intent.qty, rejection);
+            record_signal(&self.state, evaluation, SignalAction::CooldownReject);
+            return;
+        }
+        if !self.runtime_cfg.max_spread.allows(quote) {
+            self.spread_skips = self.spread_skips.saturating_add(1);
+            record_signal(&self.state, evaluation, SignalAction::SpreadSkip);
+            return;
+        }
+        // Only intents the risk gate admits spend a throttle token, so a run
+        // of risk rejects cannot throttle the orders that follow it.
+        let risk_admitted = has_fill(&runtime_events);
+        if risk_admitted && !self.intent_throttle.try_acquire(self.clock.now_ms()) {
+            let rejection = ExecutionRejection {
+                reason: "throttled",
+                headline: "Throttled",
+                detail: format!(
+                    "{} limit={}/min",
+                    order_detail(quote, intent.qty),
+                    self.intent_throttle.max_per_minute()
+                ),
+            };
+            self.reject_intent(inputs, quote, Some(decision), intent.qty, rejection);
+            record_signal(&self.state, evaluation, SignalAction::Throttled);
+            return;
+        }
+        counts.intents = counts.intents.saturating_add(1);
+
+        let side = if intent.side == Signal::Buy {
+            PaperOrderSide::Buy
+        } else {
+            PaperOrderSide::Sell
+        };
+        let (signed_qty, touch_px) = if matches!(side, PaperOrderSide::Buy) {
+            (intent.qty, quote.best_yes_ask)
+        } else {
+            (-intent.qty, quote.best_yes_bid)
+        };
+        let pending = PendingOrder {
+            quote: quote.clone(),
+            side,
+            qty: intent.qty,
+            limit_px: quote.impact_price(signed_qty).unwrap_or(touch_px),
+            origin: OrderOrigin::Strategy {
+                decision,
+                evaluation,
+            },
+        };
+        self.submit(inputs, pending, risk_admitted);
+    }
+
+    /// Journals an order and, when the strategy's risk gate let it through,
+    /// hands it to the execution task. Bracket exits are always admitted.
+    fn submit(&mut self, inputs: &TickInputs, pending: PendingOrder, risk_admitted: bool) {
+        let quote = &pending.quote;
+        let _ = self.state.publish_event(RuntimeEvent::paper_intent(
+            &quote.market_slug,
+            pending.side,
+            pending.qty,
+            pending.limit_px,
+        ));
+        let intent_log = ExecutionLogEntry {
+            tick: inputs.tick,
+            ts: inputs.ts,
+            event: "paper_intent".to_string(),
+            headline: format!("Intent {:?}", pending.side),
+            detail: format!(
+                "{} @ {:.4}",
+                order_detail(quote, pending.qty),
+                pending.limit_px
+            ),
+        };
+        self.journal_decision(
+            &intent_log,
+            PaperJournalRowKind::PaperIntent,
+            quote,
+            pending.decision(),
+        );
+        self.publish_log(intent_log);
+
+        if !risk_admitted {
+            let rejection = ExecutionRejection {
+                reason: "risk gate rejected",
+                headline: "Risk Rejected",
+                detail: order_detail(quote, pending.qty),
+            };
+            self.reject_order(inputs, pending, rejection);
+            return;
+        }
+        // The cooldown starts only once an order is actually placed.
+        if matches!(pending.origin, OrderOrigin::Strategy { .. }) {
+            self.lag_gate
+                .commit(&quote.market_slug, self.clock.now_ms());
+        }
+        let (market_id, signed_qty) = (quote.market_slug.clone(), pending.signed_qty());
+        let request = ExecutionRequest {
+            mode: inputs.settings.execution_mode,
+            order: OrderRequest {
+                market_id: quote.market_slug.clone(),
+                side: match pending.side {
+                    PaperOrderSide::Buy => OrderSide::Buy,
+                    PaperOrderSide::Sell => OrderSide::Sell,
+                },
+                qty: pending.qty,
+                limit_px: pending.limit_px,
+            },
+            context: pending,
+        };
+        // A venue still working through earlier orders does not hold up the
+        // next decision; orders beyond its queue are rejected instead.
+        match self.execution.orders.try_send(request) {
+            Ok(()) => *self.pending_qty.entry(market_id).or_default() += signed_qty,
+            Err(err) => {
+                let (reason, headline) = match &err {
+                    TrySendError::Full(_) => ("execution queue full", "Execution Busy"),
+                    TrySendError::Closed(_) => ("execution task stopped", "Execution Stopped"),
+                };
+                let pending = err.into_inner().context;
+                let rejection = ExecutionRejection {
+                    reason,
+                    headline,
+                    detail: order_detail(&pending.quote, pending.qty),
+                };
+                self.reject_order(inputs, pending, rejection);
+            }
+        }
+    }
+
+    /// Net YES shares in `market_id`, booked and still in flight.
+    fn market_qty(&self, market_id: &str) -> f64 {
+        self.positions.market_qty(market_id) + self.pending_qty.get(market_id).unwrap_or(&0.0)
+    }
+
+    /// Stops counting an order as in flight once the execution task has
+    /// answered it, filled or not.
+    fn release_pending(&mut self, pending: &PendingOrder) {
+        let market_id = &pending.quote.market_slug;
+        let Some(qty) = self.pending_qty.get_mut(market_id) else {
+            return;
+        };
+        *qty -= pending.signed_qty();
+        if qty.abs() < f64::EPSILON {
+            self.pending_qty.remove(market_id);
+        }
+    }
+
+    /// The fair YES price `quote` is traded against. External predictors
+    /// forecast BTC only; other assets price off their own forecast through
+    /// the probability model.
+    fn fair_yes_px(
+        &self,
+        inputs: &TickInputs,
+        signal: &AssetSignal,
+        quote: &PolymarketQuoteTick,
+        decision_ms: u64,
+    ) -> f64 {
+        let vol_bps = self
+            .regime_classifier
+            .volatility_bps()
+            .filter(|vol| quote.asset == Asset::Btc && *vol > 0.0)
+            .unwrap_or(DEFAULT_VOL_BPS_PER_SQRT_MIN);
+        // The predictors forecast a full 15m horizon, so their weight fades
+        // towards the time-aware model as the window runs out.
+        let model_fair = self
+            .market_strikes
+            .get(&quote.market_slug)
+            .and_then(|strike| model_fair_yes(signal, *strike, vol_bps, decision_ms));
+        let window_left = minutes_to_window_close(decision_ms) / 15.0;
+        match (
+            inputs.fused_fair_yes.filter(|_| quote.asset == Asset::Btc),
+            model_fair,
+        ) {
+            (Some(fused), Some(model)) => window_left * fused + (1.0 - window_left) * model,
+            (Some(fair), None) | (None, Some(fair)) => fair,
+            (None, None) => quote.mid_yes,
+        }
+    }
+
+    /// Publishes, logs and journals an intent or order on `quote` that will
+    /// not be filled.
+    fn reject_intent(
+        &mut self,
+        inputs: &TickInputs,
+        quote: &PolymarketQuoteTick,
+        decision: Option<Decision>,
+        qty: f64,
+        rejection: ExecutionRejection,
+    ) {
+        let _ = self.state.publish_event(RuntimeEvent::risk_reject(
+            &quote.market_slug,
+            rejection.reason,
+            qty,
+        ));
+        let log = ExecutionLogEntry {
+            tick: inputs.tick,
+            ts: inputs.ts,
+            event: "risk_reject".to_string(),
+            headline: rejection.headline.to_string(),
+            detail: rejection.detail,
+        };
+        self.journal_decision(&log, PaperJournalRowKind::RiskReject, quote, decision);
+        self.publish_log(log);
+    }
+
+    /// Publishes decision latency and throughput, strategy stats, the
+    /// win-rate alert and the portfolio once the tick's decisions are made.
+    fn publish_snapshots(
+        &mut self,
+        inputs: &TickInputs,
+        risk: &RiskGate,
+        counts: &TickCounts,
+        decision_started: Instant,
+    ) {
+        let settings = &inputs.settings;
+        let throughput_scale = 1000.0 / (self.interval_ms as f64);
+        let decision_nanos = decision_started.elapsed().as_nanos() as u64;
+        self.decision_latency.record(inputs.ts, decision_nanos);
+        let perf_summary = StrategyPerfSummary {
+            execution_mode: match settings.execution_mode {
+                StateExecutionMode::Paper => "paper".to_string(),
+                StateExecutionMode::DryRun => "dry_run".to_string(),
+                StateExecutionMode::Live => "live".to_string(),
+            },
+            lag_threshold_pct: settings.lag_threshold_pct,
+            decision_p95_us: self
+                .decision_latency
+                .percentile_nanos(inputs.ts, 95)
+                .unwrap_or(decision_nanos)
+                / 1_000,
+            intents_per_sec: ((counts.intents as f64) * throughput_scale).round() as u64,
+            fills_per_sec: ((counts.fills as f64) * throughput_scale).round() as u64,
+            lag_triggers: counts.lag_triggers,
+            throttled_intents: self.intent_throttle.throttled(),
+            spread_skips: self.spread_skips,
+            halted: risk.halted(),
+        };
+        if let Some(statsd) = &self.statsd {
+            statsd.timing_us("decision.latency", decision_nanos / 1_000);
+            statsd.count("fills", counts.fills);
+        }
+        self.state.set_strategy_perf_summary(perf_summary.clone());
+        let _ = self
+            .state
+            .publish_event(RuntimeEvent::strategy_perf(perf_summary));
+
+        let equity = self.positions.equity();
+        let summary = PortfolioSummary {
+            equity,
+            pnl: equity - self.runtime_cfg.starting_equity,
+            position_qty: self.positions.net_qty(),
+            fills: self.fills,
+        };
+
+        let stats_summary = StrategyStatsSummary {
+            balance: equity,
+            total_pnl: summary.pnl,
+            exec_latency_us: decision_started.elapsed().as_micros() as u64,
+            win_rate: self.outcomes.win_rate_pct(),
+            btc_usd: inputs.btc_signal.spot_px,
+        };
+        self.state.set_strategy_stats_summary(stats_summary);
+        let _ = self
+            .state
+            .publish_event(RuntimeEvent::strategy_stats(stats_summary));
+        let recent_win_rate = self.outcomes.recent_win_rate_pct();
+        let collapsed = recent_win_rate.is_some_and(|pct| pct < WIN_RATE_COLLAPSE_PCT);
+        if collapsed && !self.win_rate_collapsed {
+            let _ = self.state.publish_event(RuntimeEvent::risk_alert(
+                RiskAlertKind::WinRateCollapse,
+                format!(
+                    "{:.0}% of the last {RECENT_CLOSES} closes won",
+                    recent_win_rate.unwrap_or_default()
+                ),
+                inputs.ts,
+            ));
+        }
+        self.win_rate_collapsed = collapsed;
+
+        self.state.set_market_portfolios(
+            self.positions
+                .markets()
+                .iter()
+                .map(|market| MarketPortfolio {
+                    source: market.source.as_str().to_string(),
+                    market_id: market.market_id.clone(),
+                    position_qty: to_f64(market.qty),
+                    avg_px: to_f64(market.avg_px()),
+                    mark_px: to_f64(market.mark_px),
+                    realized_pnl: market.realized_pnl,
+                    unrealized_pnl: market.unrealized_pnl(),
+                })
+                .collect(),
+        );
+        self.state.set_portfolio_summary(summary);
+        let _ = self
+            .state
+            .publish_event(RuntimeEvent::portfolio_snapshot(summary));
+    }
+
+    /// Journals an event on `quote`'s market, with the spot and divergence
+    /// it was decided on when there was a decision.
+    fn journal_decision(
+        &mut self,
+        log: &ExecutionLogEntry,
+        kind: PaperJournalRowKind,
+        quote: &PolymarketQuoteTick,
+        decision: Option<Decision>,
+    ) {
+        let row = journal_row(log, kind, quote, &self.positions, self.halted);
+        let row = match decision {
+            Some(decision) => ReplayCsvRow {
+                external_px: Some(decision.spot_px),
+                divergence: Some(decision.fair_yes_px / quote.mid_yes - 1.0),
+                ..row
+            },
+            None => row,
+        };
+        append_journal(&mut self.journal, row);
+    }
+
+    fn publish_log(&self, log: ExecutionLogEntry) {
+        self.state
+            .push_execution_log(log.clone(), EXECUTION_LOG_LIMIT);
+        let _ = self.state.publish_event(RuntimeEvent::execution_log(log));
+    }
+}
+
+/// The market and quantity an execution log entry is about.
+fn order_detail(quote: &PolymarketQuoteTick, qty: f64) -> String {
+    format!(
+        "{}:{} qty={}",
+        quote.source.as_str(),
+        quote.market_slug,
+        qty
+    )
+}
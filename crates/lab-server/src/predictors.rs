@@ -1,51 +1,191 @@
+use std::fmt;
+
 use runtime::live::{PredictorSource, PredictorTick};
 use serde::Deserialize;
+use serde_json::Value;
+
+/// Bounds on the poll period an endpoint may ask for.
+const MIN_POLL_MS: u64 = 100;
+const MAX_POLL_MS: u64 = 300_000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum ParsePredictorError {
     InvalidJson,
     InvalidPrediction,
     InvalidConfidence,
 }
 
+impl ParsePredictorError {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidJson => "invalid_json",
+            Self::InvalidPrediction => "invalid_prediction",
+            Self::InvalidConfidence => "invalid_confidence",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PredictorConfigError {
+    /// The predictors file could not be read.
+    Unreadable,
+    InvalidToml,
+    InvalidLabel(String),
+    DuplicateLabel(String),
+    InvalidUrl(String),
+    /// Field mappings must be JSON pointers, i.e. empty or starting with `/`.
+    InvalidPointer(String),
+    InvalidPollMs(String),
+}
+
+impl fmt::Display for PredictorConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable => write!(f, "file could not be read"),
+            Self::InvalidToml => write!(f, "file is not a valid predictor list"),
+            Self::InvalidLabel(label) => write!(
+                f,
+                "label `{label}` must be lowercase letters, digits, `-` or `_`"
+            ),
+            Self::DuplicateLabel(label) => write!(f, "label `{label}` is used more than once"),
+            Self::InvalidUrl(label) => write!(f, "`{label}` needs an http(s) url"),
+            Self::InvalidPointer(label) => write!(
+                f,
+                "`{label}` field mappings must be JSON pointers such as `/data/yes`"
+            ),
+            Self::InvalidPollMs(label) => write!(
+                f,
+                "`{label}` poll_ms must be between {MIN_POLL_MS} and {MAX_POLL_MS}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PredictorConfigError {}
+
+/// An HTTP endpoint polled for BTC up/down forecasts, with the JSON pointers
+/// that locate the YES probability and confidence in its responses.
+///
+/// ```toml
+/// [[predictors]]
+/// label = "glassnode"
+/// url = "https://example.com/btc/15m"
+/// prediction_pointer = "/data/yes"
+/// confidence_pointer = "/data/confidence"
+/// poll_ms = 5000
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredictorEndpoint {
+    pub source: PredictorSource,
+    pub url: String,
+    pub prediction_pointer: String,
+    pub confidence_pointer: String,
+    /// Poll period; `None` polls once per decision interval.
+    pub poll_ms: Option<u64>,
+}
+
 #[derive(Debug, Deserialize)]
-struct TradingViewPayload {
-    yes_prediction: f64,
-    confidence: f64,
+#[serde(deny_unknown_fields)]
+struct PredictorsFile {
+    #[serde(default)]
+    predictors: Vec<EndpointEntry>,
 }
 
 #[derive(Debug, Deserialize)]
-struct CryptoQuantPayload {
-    prediction_yes: f64,
-    confidence: f64,
+#[serde(deny_unknown_fields)]
+struct EndpointEntry {
+    label: String,
+    url: String,
+    prediction_pointer: String,
+    confidence_pointer: String,
+    poll_ms: Option<u64>,
 }
 
-pub fn parse_tradingview_payload(
-    payload: &str,
-    ts_ms: u64,
-) -> Result<PredictorTick, ParsePredictorError> {
-    let payload: TradingViewPayload =
-        serde_json::from_str(payload).map_err(|_| ParsePredictorError::InvalidJson)?;
-    normalize_predictor_tick(
-        PredictorSource::TradingView,
-        payload.yes_prediction,
-        payload.confidence,
-        ts_ms,
-    )
+impl PredictorEndpoint {
+    pub fn tradingview(url: String) -> Self {
+        Self::builtin("tradingview", url, "/yes_prediction")
+    }
+
+    pub fn cryptoquant(url: String) -> Self {
+        Self::builtin("cryptoquant", url, "/prediction_yes")
+    }
+
+    fn builtin(label: &str, url: String, prediction_pointer: &str) -> Self {
+        Self {
+            source: PredictorSource::parse(label).expect("built-in labels are valid"),
+            url,
+            prediction_pointer: prediction_pointer.to_string(),
+            confidence_pointer: "/confidence".to_string(),
+            poll_ms: None,
+        }
+    }
+
+    /// Reads this endpoint's forecast out of a response body.
+    ///
+    /// Numbers sent as JSON strings are accepted, since several vendors
+    /// quote them to preserve precision.
+    pub fn parse_payload(
+        &self,
+        payload: &str,
+        ts_ms: u64,
+    ) -> Result<PredictorTick, ParsePredictorError> {
+        let payload: Value =
+            serde_json::from_str(payload).map_err(|_| ParsePredictorError::InvalidJson)?;
+        let predicted_yes_px = number_at(&payload, &self.prediction_pointer)
+            .ok_or(ParsePredictorError::InvalidPrediction)?;
+        let confidence = number_at(&payload, &self.confidence_pointer)
+            .ok_or(ParsePredictorError::InvalidConfidence)?;
+        normalize_predictor_tick(self.source.clone(), predicted_yes_px, confidence, ts_ms)
+    }
 }
 
-pub fn parse_cryptoquant_payload(
-    payload: &str,
-    ts_ms: u64,
-) -> Result<PredictorTick, ParsePredictorError> {
-    let payload: CryptoQuantPayload =
-        serde_json::from_str(payload).map_err(|_| ParsePredictorError::InvalidJson)?;
-    normalize_predictor_tick(
-        PredictorSource::CryptoQuant,
-        payload.prediction_yes,
-        payload.confidence,
-        ts_ms,
-    )
+/// Parses a TOML list of `[[predictors]]` endpoints.
+pub fn parse_predictor_endpoints(
+    source: &str,
+) -> Result<Vec<PredictorEndpoint>, PredictorConfigError> {
+    let file: PredictorsFile =
+        toml::from_str(source).map_err(|_| PredictorConfigError::InvalidToml)?;
+
+    let mut endpoints: Vec<PredictorEndpoint> = Vec::with_capacity(file.predictors.len());
+    for entry in file.predictors {
+        let source = PredictorSource::parse(&entry.label)
+            .ok_or_else(|| PredictorConfigError::InvalidLabel(entry.label.clone()))?;
+        if endpoints.iter().any(|endpoint| endpoint.source == source) {
+            return Err(PredictorConfigError::DuplicateLabel(entry.label));
+        }
+        let url = entry.url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(PredictorConfigError::InvalidUrl(entry.label));
+        }
+        let is_pointer = |pointer: &str| pointer.is_empty() || pointer.starts_with('/');
+        if !is_pointer(&entry.prediction_pointer) || !is_pointer(&entry.confidence_pointer) {
+            return Err(PredictorConfigError::InvalidPointer(entry.label));
+        }
+        if entry
+            .poll_ms
+            .is_some_and(|poll_ms| !(MIN_POLL_MS..=MAX_POLL_MS).contains(&poll_ms))
+        {
+            return Err(PredictorConfigError::InvalidPollMs(entry.label));
+        }
+
+        endpoints.push(PredictorEndpoint {
+            source,
+            url: url.to_string(),
+            prediction_pointer: entry.prediction_pointer,
+            confidence_pointer: entry.confidence_pointer,
+            poll_ms: entry.poll_ms,
+        });
+    }
+    Ok(endpoints)
+}
+
+fn number_at(payload: &Value, pointer: &str) -> Option<f64> {
+    match payload.pointer(pointer)? {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
 }
 
 fn normalize_predictor_tick(
@@ -71,17 +211,17 @@ fn normalize_predictor_tick(
 
 #[cfg(test)]
 mod tests {
-    use runtime::live::PredictorSource;
-
     use super::*;
 
     #[test]
     fn parses_tradingview_payload_into_predictor_tick() {
         let payload = r#"{"yes_prediction":0.512,"confidence":0.82}"#;
 
-        let tick = parse_tradingview_payload(payload, 100).unwrap();
+        let tick = PredictorEndpoint::tradingview(String::new())
+            .parse_payload(payload, 100)
+            .unwrap();
 
-        assert_eq!(tick.source, PredictorSource::TradingView);
+        assert_eq!(tick.source.as_str(), "tradingview");
         assert_eq!(tick.predicted_yes_px, 0.512);
         assert_eq!(tick.confidence, 0.82);
         assert_eq!(tick.ts_ms, 100);
@@ -91,11 +231,78 @@ mod tests {
     fn parses_cryptoquant_payload_into_predictor_tick() {
         let payload = r#"{"prediction_yes":0.507,"confidence":0.76}"#;
 
-        let tick = parse_cryptoquant_payload(payload, 100).unwrap();
+        let tick = PredictorEndpoint::cryptoquant(String::new())
+            .parse_payload(payload, 100)
+            .unwrap();
 
-        assert_eq!(tick.source, PredictorSource::CryptoQuant);
+        assert_eq!(tick.source.as_str(), "cryptoquant");
         assert_eq!(tick.predicted_yes_px, 0.507);
         assert_eq!(tick.confidence, 0.76);
         assert_eq!(tick.ts_ms, 100);
     }
+
+    #[test]
+    fn configured_endpoints_map_nested_fields() {
+        let endpoints = parse_predictor_endpoints(
+            r#"
+            [[predictors]]
+            label = "glassnode"
+            url = "https://example.com/btc/15m"
+            prediction_pointer = "/data/0/yes"
+            confidence_pointer = "/data/0/confidence"
+            poll_ms = 5000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].poll_ms, Some(5_000));
+
+        let tick = endpoints[0]
+            .parse_payload(r#"{"data":[{"yes":"0.55","confidence":2}]}"#, 7)
+            .unwrap();
+        assert_eq!(tick.source.as_str(), "glassnode");
+        assert_eq!((tick.predicted_yes_px, tick.confidence), (0.55, 2.0));
+        assert_eq!(
+            endpoints[0].parse_payload(r#"{"data":[{"yes":0.5}]}"#, 7),
+            Err(ParsePredictorError::InvalidConfidence)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_endpoint_lists() {
+        let entry = |label: &str, url: &str, pointer: &str, poll_ms: u64| {
+            format!(
+                "[[predictors]]\nlabel = \"{label}\"\nurl = \"{url}\"\n\
+                 prediction_pointer = \"{pointer}\"\nconfidence_pointer = \"/c\"\n\
+                 poll_ms = {poll_ms}\n"
+            )
+        };
+        let ok = entry("a", "https://a.test", "/p", 1_000);
+
+        assert_eq!(parse_predictor_endpoints("").unwrap(), Vec::new());
+        assert_eq!(
+            parse_predictor_endpoints(&entry("Bad", "https://a.test", "/p", 1_000)),
+            Err(PredictorConfigError::InvalidLabel("Bad".to_string()))
+        );
+        assert_eq!(
+            parse_predictor_endpoints(&format!("{ok}{ok}")),
+            Err(PredictorConfigError::DuplicateLabel("a".to_string()))
+        );
+        assert_eq!(
+            parse_predictor_endpoints(&entry("a", "ftp://a.test", "/p", 1_000)),
+            Err(PredictorConfigError::InvalidUrl("a".to_string()))
+        );
+        assert_eq!(
+            parse_predictor_endpoints(&entry("a", "https://a.test", "p", 1_000)),
+            Err(PredictorConfigError::InvalidPointer("a".to_string()))
+        );
+        assert_eq!(
+            parse_predictor_endpoints(&entry("a", "https://a.test", "/p", 10)),
+            Err(PredictorConfigError::InvalidPollMs("a".to_string()))
+        );
+        assert_eq!(
+            parse_predictor_endpoints("[[predictors]]\nlabel = \"a\"\n"),
+            Err(PredictorConfigError::InvalidToml)
+        );
+    }
 }
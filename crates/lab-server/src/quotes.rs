@@ -0,0 +1,697 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use runtime::clock::{Clock, SystemClock};
+use runtime::live::{
+    parse_end_date_ms, parse_kalshi_market, parse_kalshi_markets, parse_polymarket_book,
+    parse_strike_usd, select_expiring, Asset, KalshiMarket, MarketMetadata, MarketSelection,
+    PolymarketQuoteTick, QuoteSource, RawPolymarketQuote, ReconnectPolicy, RejectedPayload,
+    VenueFeed, VenueFeedError, VenueFuture, VenueTick,
+};
+use runtime::positions::MarketOutcome;
+use serde::Deserialize;
+
+use crate::http::{CacheValidators, Conditional, RateLimitedClient};
+
+const POLY_GAMMA_MARKETS_URL: &str =
+    "https://gamma-api.polymarket.com/markets?active=true&closed=false&limit=200";
+const KALSHI_MARKETS_URL: &str = "https://api.elections.kalshi.com/trade-api/v2/markets";
+const POLY_CLOB_BOOK_URL: &str = "https://clob.polymarket.com/book";
+const POLY_GAMMA_MARKET_BY_SLUG_URL: &str = "https://gamma-api.polymarket.com/markets?slug=";
+const KALSHI_MARKET_URL: &str = "https://api.elections.kalshi.com/trade-api/v2/markets/";
+const POLY_DATA_TRADES_URL: &str = "https://data-api.polymarket.com/trades";
+/// A closed market counts as resolved once its YES price is this close to 0 or 1.
+const RESOLVED_PRICE_TOLERANCE: f64 = 0.01;
+const MAX_TRACKED_MARKETS_PER_ASSET: usize = 3;
+const POLY_BOOK_LEVELS: usize = 5;
+/// Markets with less resting liquidity (USD) rank behind every liquid one.
+const MIN_MARKET_LIQUIDITY_USD: f64 = 500.0;
+/// Per-market requests, run concurrently, each get this long.
+const MARKET_FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// HTTP-polled prediction market venue.
+///
+/// Each `next_tick` waits out the poll interval and then returns quotes for the
+/// venue's markets that pass each tracked asset's filter and settle within the
+/// selection's horizon, best-scoring market first; a failed or empty fetch
+/// ends the session so the feed runner backs off before polling again.
+pub struct PolledQuoteFeed {
+    source: QuoteSource,
+    assets: Vec<Asset>,
+    selection: MarketSelection,
+    client: RateLimitedClient,
+    poll_interval: Duration,
+    reconnect: ReconnectPolicy,
+    clock: Arc<dyn Clock>,
+    polled_once: bool,
+    rejected: Vec<RejectedPayload>,
+    /// Last Gamma listing that parsed, reused while Gamma reports it
+    /// unchanged.
+    gamma_listing: Option<GammaListing>,
+}
+
+struct GammaListing {
+    validators: CacheValidators,
+    markets: Vec<GammaMarket>,
+}
+
+impl PolledQuoteFeed {
+    pub fn new(
+        source: QuoteSource,
+        assets: Vec<Asset>,
+        selection: MarketSelection,
+        client: RateLimitedClient,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            source,
+            assets,
+            selection,
+            client,
+            poll_interval,
+            reconnect: ReconnectPolicy::default(),
+            clock: Arc::new(SystemClock),
+            polled_once: false,
+            rejected: Vec::new(),
+            gamma_listing: None,
+        }
+    }
+
+    pub fn with_reconnect_policy(mut self, reconnect: ReconnectPolicy) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Stamps quotes with `clock`'s time instead of the system clock's.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    async fn poll(&mut self) -> Result<Option<VenueTick>, VenueFeedError> {
+        if self.polled_once {
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        self.polled_once = true;
+
+        let ts = self.clock.now_ms();
+        let quotes = match self.source {
+            QuoteSource::Polymarket => {
+                fetch_polymarket_quotes(
+                    &self.client,
+                    &self.assets,
+                    &self.selection,
+                    ts,
+                    &mut self.gamma_listing,
+                    &mut self.rejected,
+                )
+                .await?
+            }
+            QuoteSource::Kalshi => {
+                fetch_kalshi_quotes(
+                    &self.client,
+                    &self.assets,
+                    &self.selection,
+                    ts,
+                    &mut self.rejected,
+                )
+                .await?
+            }
+        };
+        if quotes.is_empty() {
+            return Err(VenueFeedError::Request);
+        }
+        Ok(Some(VenueTick::Quotes(quotes)))
+    }
+}
+
+impl VenueFeed for PolledQuoteFeed {
+    fn venue(&self) -> &str {
+        self.source.as_str()
+    }
+
+    fn connect(&mut self) -> VenueFuture<'_, Result<(), VenueFeedError>> {
+        self.polled_once = false;
+        Box::pin(async { Ok(()) })
+    }
+
+    fn next_tick(&mut self) -> VenueFuture<'_, Result<Option<VenueTick>, VenueFeedError>> {
+        Box::pin(self.poll())
+    }
+
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.reconnect
+    }
+
+    fn drain_rejected(&mut self) -> Vec<RejectedPayload> {
+        std::mem::take(&mut self.rejected)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GammaMarket {
+    slug: String,
+    #[serde(default)]
+    question: String,
+    #[serde(rename = "bestBid", default)]
+    best_bid: Option<serde_json::Value>,
+    #[serde(rename = "bestAsk", default)]
+    best_ask: Option<serde_json::Value>,
+    #[serde(rename = "outcomePrices", default)]
+    outcome_prices_raw: Option<serde_json::Value>,
+    #[serde(default)]
+    outcomes_raw: Option<serde_json::Value>,
+    #[serde(rename = "clobTokenIds", default)]
+    clob_token_ids_raw: Option<serde_json::Value>,
+    #[serde(rename = "conditionId", default)]
+    condition_id: Option<String>,
+    #[serde(rename = "endDate", default)]
+    end_date: Option<String>,
+    #[serde(rename = "liquidityNum", default)]
+    liquidity_num: Option<f64>,
+    #[serde(rename = "volume24hr", default)]
+    volume_24hr: Option<f64>,
+    #[serde(default)]
+    closed: bool,
+}
+
+impl GammaMarket {
+    fn metadata(&self) -> Option<MarketMetadata> {
+        Some(MarketMetadata {
+            slug: self.slug.clone(),
+            end_ms: parse_end_date_ms(self.end_date.as_deref()?)?,
+            strike: parse_strike_usd(&self.question),
+            liquidity: self
+                .liquidity_num
+                .filter(|liquidity| liquidity.is_finite())
+                .unwrap_or(0.0),
+            volume: self
+                .volume_24hr
+                .filter(|volume| volume.is_finite())
+                .unwrap_or(0.0),
+            spread: match (
+                self.best_bid.as_ref().and_then(parse_probability_json),
+                self.best_ask.as_ref().and_then(parse_probability_json),
+            ) {
+                (Some(best_bid), Some(best_ask)) => Some((best_ask - best_bid).abs()),
+                _ => None,
+            },
+        })
+    }
+
+    /// Gamma flags settled markets `closed` and pins the winning outcome's
+    /// price at 1; a closed market still awaiting its oracle has no outcome.
+    fn outcome(&self) -> Option<MarketOutcome> {
+        if !self.closed {
+            return None;
+        }
+        let yes_px = yes_price_from_market(self)?;
+        if yes_px >= 1.0 - RESOLVED_PRICE_TOLERANCE {
+            Some(MarketOutcome::Yes)
+        } else if yes_px <= RESOLVED_PRICE_TOLERANCE {
+            Some(MarketOutcome::No)
+        } else {
+            None
+        }
+    }
+}
+
+/// Looks up whether `market_id` on `source` has resolved.
+///
+/// `Ok(None)` means the market is still open or awaiting resolution.
+pub async fn fetch_resolution(
+    client: &RateLimitedClient,
+    source: QuoteSource,
+    market_id: &str,
+) -> Result<Option<MarketOutcome>, VenueFeedError> {
+    match source {
+        QuoteSource::Polymarket => {
+            let payload = client
+                .get_text(&format!("{POLY_GAMMA_MARKET_BY_SLUG_URL}{market_id}"))
+                .await?;
+            let markets: Vec<GammaMarket> =
+                serde_json::from_str(&payload).map_err(|_| VenueFeedError::Parse)?;
+            Ok(markets
+                .iter()
+                .find(|market| market.slug == market_id)
+                .and_then(GammaMarket::outcome))
+        }
+        QuoteSource::Kalshi => {
+            let payload = client
+                .get_text(&format!("{KALSHI_MARKET_URL}{market_id}"))
+                .await?;
+            let market = parse_kalshi_market(&payload).map_err(|_| VenueFeedError::Parse)?;
+            Ok(market.outcome())
+        }
+    }
+}
+
+/// Up to `limit` of the latest trades in a Polymarket market, as
+/// `(ts_ms, yes_px)` oldest first.
+pub async fn fetch_trade_history(
+    client: &RateLimitedClient,
+    market_slug: &str,
+    limit: usize,
+) -> Result<Vec<(u64, f64)>, VenueFeedError> {
+    let payload = client
+        .get_text(&format!("{POLY_GAMMA_MARKET_BY_SLUG_URL}{market_slug}"))
+        .await?;
+    let markets: Vec<GammaMarket> =
+        serde_json::from_str(&payload).map_err(|_| VenueFeedError::Parse)?;
+    let market = markets
+        .iter()
+        .find(|market| market.slug == market_slug)
+        .ok_or(VenueFeedError::Parse)?;
+    let condition_id = market
+        .condition_id
+        .as_deref()
+        .ok_or(VenueFeedError::Parse)?;
+    let yes_token = yes_token_id(market).ok_or(VenueFeedError::Parse)?;
+
+    let payload = client
+        .get_text(&format!(
+            "{POLY_DATA_TRADES_URL}?market={condition_id}&limit={limit}"
+        ))
+        .await?;
+    parse_trade_history(&payload, &yes_token)
+}
+
+/// Trades in either outcome priced as YES, oldest first.
+fn parse_trade_history(payload: &str, yes_token: &str) -> Result<Vec<(u64, f64)>, VenueFeedError> {
+    #[derive(Deserialize)]
+    struct DataApiTrade {
+        asset: String,
+        price: f64,
+        /// Unix seconds.
+        timestamp: u64,
+    }
+
+    let trades: Vec<DataApiTrade> =
+        serde_json::from_str(payload).map_err(|_| VenueFeedError::Parse)?;
+    let mut history: Vec<(u64, f64)> = trades
+        .into_iter()
+        .filter(|trade| trade.price.is_finite() && (0.0..=1.0).contains(&trade.price))
+        .map(|trade| {
+            let yes_px = if trade.asset == yes_token {
+                trade.price
+            } else {
+                1.0 - trade.price
+            };
+            (trade.timestamp.saturating_mul(1_000), yes_px)
+        })
+        .collect();
+    history.sort_by_key(|(ts_ms, _)| *ts_ms);
+    Ok(history)
+}
+
+/// Unparseable responses are pushed to `rejected` so they can be quarantined.
+///
+/// The market listing is fetched conditionally against `listing` and only
+/// parsed again when Gamma sends something new; books are always fetched.
+async fn fetch_polymarket_quotes(
+    client: &RateLimitedClient,
+    assets: &[Asset],
+    selection: &MarketSelection,
+    ts: u64,
+    listing: &mut Option<GammaListing>,
+    rejected: &mut Vec<RejectedPayload>,
+) -> Result<Vec<PolymarketQuoteTick>, VenueFeedError> {
+    let no_validators = CacheValidators::default();
+    let cached = listing
+        .as_ref()
+        .map_or(&no_validators, |listing| &listing.validators);
+    if let Conditional::Changed { body, validators } = client
+        .get_text_if_changed(POLY_GAMMA_MARKETS_URL, cached)
+        .await?
+    {
+        let markets = serde_json::from_str(&body).map_err(|err| {
+            rejected.push(RejectedPayload::new(format!("gamma_markets: {err}"), &body));
+            VenueFeedError::Parse
+        })?;
+        *listing = Some(GammaListing {
+            validators,
+            markets,
+        });
+    }
+    let markets = &listing.as_ref().ok_or(VenueFeedError::Parse)?.markets;
+
+    let mut quotes = Vec::new();
+    let mut book_urls = Vec::new();
+    for &asset in assets {
+        let mut tracked = 0;
+        for (market, score) in rank_markets(markets, ts, selection.horizon_ms, |market| {
+            selection
+                .matches(asset, &market.slug, &market.question)
+                .then(|| market.metadata())
+                .flatten()
+        }) {
+            if let Some(mut quote) = gamma_market_to_quote(market, ts) {
+                quote.asset = asset;
+                quote.liquidity_score = Some(score);
+                if let Some(token_id) = yes_token_id(market) {
+                    book_urls.push((
+                        quotes.len(),
+                        format!("{POLY_CLOB_BOOK_URL}?token_id={token_id}"),
+                    ));
+                }
+                quotes.push(quote);
+                tracked += 1;
+            }
+
+            if tracked >= MAX_TRACKED_MARKETS_PER_ASSET {
+                break;
+            }
+        }
+    }
+
+    // Without a book the quote still trades at the touch, so a failed or
+    // slow depth fetch is not fatal.
+    let (idxs, urls): (Vec<usize>, Vec<String>) = book_urls.into_iter().unzip();
+    let books = client.get_texts(&urls, MARKET_FETCH_TIMEOUT).await;
+    for (idx, raw) in idxs.into_iter().zip(books) {
+        let Ok(raw) = raw else { continue };
+        match parse_polymarket_book(&raw, POLY_BOOK_LEVELS) {
+            Ok(depth) => quotes[idx].depth = depth,
+            Err(err) => rejected.push(RejectedPayload::new(format!("clob_book: {err:?}"), &raw)),
+        }
+    }
+
+    Ok(quotes)
+}
+
+async fn fetch_kalshi_quotes(
+    client: &RateLimitedClient,
+    assets: &[Asset],
+    selection: &MarketSelection,
+    ts: u64,
+    rejected: &mut Vec<RejectedPayload>,
+) -> Result<Vec<PolymarketQuoteTick>, VenueFeedError> {
+    let urls: Vec<String> = assets
+        .iter()
+        .map(|&asset| {
+            format!(
+                "{KALSHI_MARKETS_URL}?series_ticker={}&status=open&limit=50",
+                kalshi_series_ticker(asset)
+            )
+        })
+        .collect();
+    let listings = client.get_texts(&urls, MARKET_FETCH_TIMEOUT).await;
+
+    let mut quotes = Vec::new();
+    for (&asset, payload) in assets.iter().zip(listings) {
+        let payload = payload?;
+        let markets = parse_kalshi_markets(&payload).map_err(|err| {
+            rejected.push(RejectedPayload::new(
+                format!("kalshi_markets: {err:?}"),
+                &payload,
+            ));
+            VenueFeedError::Parse
+        })?;
+
+        // The series already scopes the listing to one asset; the filter
+        // still applies so families can be excluded by keyword.
+        let typed = |market: &KalshiMarket| {
+            selection
+                .matches(asset, &market.ticker, &market.title)
+                .then(|| market.metadata())
+                .flatten()
+        };
+        for (market, score) in rank_markets(&markets, ts, selection.horizon_ms, typed)
+            .into_iter()
+            .filter_map(|(market, score)| {
+                let quote = market.to_raw_quote(ts)?.normalize().ok()?;
+                Some((quote, score))
+            })
+            .take(MAX_TRACKED_MARKETS_PER_ASSET)
+        {
+            quotes.push(PolymarketQuoteTick {
+                asset,
+                liquidity_score: Some(score),
+                ..market
+            });
+        }
+    }
+
+    Ok(quotes)
+}
+
+/// Kalshi series listing an asset's 15m up/down markets.
+fn kalshi_series_ticker(asset: Asset) -> String {
+    format!("KX{}15M", asset.ticker())
+}
+
+/// Orders markets settling within `horizon_ms` of `now_ms` by trading
+/// preference, each with its liquidity score; markets `metadata` cannot type
+/// are dropped.
+fn rank_markets<M>(
+    markets: &[M],
+    now_ms: u64,
+    horizon_ms: u64,
+    metadata: impl Fn(&M) -> Option<MarketMetadata>,
+) -> Vec<(&M, f64)> {
+    let typed: Vec<(MarketMetadata, &M)> = markets
+        .iter()
+        .filter_map(|market| metadata(market).map(|meta| (meta, market)))
+        .collect();
+    let ranked = select_expiring(
+        typed.iter().map(|(meta, _)| meta.clone()).collect(),
+        now_ms,
+        horizon_ms,
+        MIN_MARKET_LIQUIDITY_USD,
+    );
+
+    ranked
+        .iter()
+        .filter_map(|meta| {
+            typed
+                .iter()
+                .find(|(candidate, _)| candidate.slug == meta.slug)
+                .map(|(_, market)| (*market, meta.liquidity_score(now_ms, horizon_ms)))
+        })
+        .collect()
+}
+
+fn gamma_market_to_quote(market: &GammaMarket, ts: u64) -> Option<PolymarketQuoteTick> {
+    let fallback_mid = match (
+        market.best_bid.as_ref().and_then(parse_probability_json),
+        market.best_ask.as_ref().and_then(parse_probability_json),
+    ) {
+        (Some(best_bid), Some(best_ask)) => (best_bid + best_ask) / 2.0,
+        _ => 0.5,
+    };
+    let yes_mid = yes_price_from_market(market).unwrap_or(fallback_mid.clamp(0.0, 1.0));
+    let fallback_bid = (yes_mid - 0.01).clamp(0.0, 1.0);
+    let fallback_ask = (yes_mid + 0.01).clamp(0.0, 1.0);
+    let mut best_bid = market
+        .best_bid
+        .as_ref()
+        .and_then(parse_probability_json)
+        .unwrap_or(fallback_bid);
+    let mut best_ask = market
+        .best_ask
+        .as_ref()
+        .and_then(parse_probability_json)
+        .unwrap_or(fallback_ask);
+
+    if best_bid > best_ask {
+        std::mem::swap(&mut best_bid, &mut best_ask);
+    }
+
+    RawPolymarketQuote {
+        market_slug: market.slug.clone(),
+        source: QuoteSource::Polymarket,
+        best_yes_bid: best_bid,
+        best_yes_ask: best_ask,
+        ts,
+    }
+    .normalize()
+    .ok()
+}
+
+/// CLOB token for the YES outcome, paired with `outcomes` by position.
+fn yes_token_id(market: &GammaMarket) -> Option<String> {
+    let outcomes = parse_string_list(market.outcomes_raw.as_ref());
+    let token_ids = parse_string_list(market.clob_token_ids_raw.as_ref());
+    let yes_idx = outcomes
+        .iter()
+        .position(|outcome| outcome.eq_ignore_ascii_case("yes"))
+        .unwrap_or(0);
+
+    token_ids.get(yes_idx).cloned()
+}
+
+fn yes_price_from_market(market: &GammaMarket) -> Option<f64> {
+    let outcomes = parse_string_list(market.outcomes_raw.as_ref());
+    let outcome_prices = parse_string_list(market.outcome_prices_raw.as_ref());
+
+    if !outcomes.is_empty() && outcomes.len() == outcome_prices.len() {
+        for (idx, outcome) in outcomes.iter().enumerate() {
+            if outcome.eq_ignore_ascii_case("yes") {
+                return parse_probability_str(&outcome_prices[idx]);
+            }
+        }
+    }
+
+    outcome_prices
+        .first()
+        .and_then(|value| parse_probability_str(value))
+}
+
+fn parse_string_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    let Some(value) = value else {
+        return Vec::new();
+    };
+
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(ToOwned::to_owned))
+            .collect(),
+        serde_json::Value::String(text) => {
+            if let Ok(items) = serde_json::from_str::<Vec<String>>(text) {
+                return items;
+            }
+
+            text.split(',')
+                .map(str::trim)
+                .map(|entry| entry.trim_matches(|ch| ch == '[' || ch == ']' || ch == '"'))
+                .filter(|entry| !entry.is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn parse_probability_str(value: &str) -> Option<f64> {
+    let parsed = value.parse::<f64>().ok()?;
+    parse_probability(parsed)
+}
+
+fn parse_probability_json(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(number) => parse_probability(number.as_f64()?),
+        serde_json::Value::String(text) => parse_probability_str(text),
+        _ => None,
+    }
+}
+
+fn parse_probability(value: f64) -> Option<f64> {
+    if value.is_finite() && (0.0..=1.0).contains(&value) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prices_traded_outcomes_as_yes_oldest_first() {
+        let payload = r#"[
+            {"asset": "111", "side": "BUY", "price": 0.62, "size": 10, "timestamp": 1772280060},
+            {"asset": "222", "side": "SELL", "price": 0.45, "size": 4, "timestamp": 1772280000},
+            {"asset": "111", "side": "BUY", "price": 1.7, "size": 1, "timestamp": 1772280030}
+        ]"#;
+
+        let history = parse_trade_history(payload, "111").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].0, 1_772_280_000_000);
+        assert!((history[0].1 - 0.55).abs() < 1e-9);
+        assert_eq!(history[1], (1_772_280_060_000, 0.62));
+        assert!(parse_trade_history("{}", "111").is_err());
+    }
+
+    #[test]
+    fn parse_probability_str_rejects_out_of_range_values() {
+        assert_eq!(parse_probability_str("1.1"), None);
+        assert_eq!(parse_probability_str("-0.1"), None);
+        assert_eq!(parse_probability_str("0.42"), Some(0.42));
+    }
+
+    #[test]
+    fn default_selection_matches_asset_in_slug_or_question() {
+        let selection = MarketSelection::new(15 * 60 * 1_000);
+        assert!(selection.matches(
+            Asset::Btc,
+            "bitcoin-15m-forecast",
+            "Will BTC be above 66k in the next 15 minutes?"
+        ));
+        assert!(selection.matches(
+            Asset::Btc,
+            "KXBTC15M-26FEB281215-15",
+            "BTC price up in next 15 mins?"
+        ));
+        assert!(!selection.matches(
+            Asset::Btc,
+            "eth-15m-forecast",
+            "Will ETH rise in 15 minutes?"
+        ));
+        assert!(selection.matches(
+            Asset::Eth,
+            "eth-15m-forecast",
+            "Will ETH rise in 15 minutes?"
+        ));
+        assert!(!selection.matches(
+            Asset::Sol,
+            "btc-updown-15m",
+            "Resolves to Up if BTC closes higher"
+        ));
+        assert_eq!(kalshi_series_ticker(Asset::Sol), "KXSOL15M");
+    }
+
+    #[test]
+    fn gamma_outcome_requires_closed_market_pinned_to_zero_or_one() {
+        let raw = r#"[
+            {"slug":"yes-won","closed":true,"outcomes":"[\"Yes\", \"No\"]","outcomePrices":"[\"1\", \"0\"]"},
+            {"slug":"no-won","closed":true,"outcomes":"[\"Yes\", \"No\"]","outcomePrices":"[\"0.0005\", \"0.9995\"]"},
+            {"slug":"awaiting-oracle","closed":true,"outcomes":"[\"Yes\", \"No\"]","outcomePrices":"[\"0.62\", \"0.38\"]"},
+            {"slug":"still-open","closed":false,"outcomes":"[\"Yes\", \"No\"]","outcomePrices":"[\"1\", \"0\"]"}
+        ]"#;
+        let markets: Vec<GammaMarket> = serde_json::from_str(raw).unwrap();
+
+        let outcomes: Vec<Option<MarketOutcome>> =
+            markets.iter().map(GammaMarket::outcome).collect();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                Some(MarketOutcome::Yes),
+                Some(MarketOutcome::No),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn gamma_markets_are_ranked_by_expiry_within_horizon() {
+        let raw = r#"[
+            {"slug":"btc-updown-1215","question":"Bitcoin Up or Down - 12:00PM-12:15PM ET","endDate":"2026-02-28T12:15:00Z","liquidityNum":40.0},
+            {"slug":"btc-above-66k-1225","question":"Will BTC be above $66,000 at 12:25PM ET?","endDate":"2026-02-28T12:25:00Z","liquidityNum":8000.0},
+            {"slug":"btc-updown-1230","question":"Bitcoin Up or Down - 12:15PM-12:30PM ET","endDate":"2026-02-28T12:30:00Z","liquidityNum":8000.0},
+            {"slug":"btc-above-70k-daily","question":"Will BTC be above $70,000 tomorrow?","endDate":"2026-03-01T17:00:00Z","liquidityNum":90000.0},
+            {"slug":"eth-updown-1215","question":"Ethereum Up or Down","endDate":"2026-02-28T12:15:00Z","liquidityNum":8000.0},
+            {"slug":"btc-no-end-date","question":"Bitcoin 15m"}
+        ]"#;
+        let markets: Vec<GammaMarket> = serde_json::from_str(raw).unwrap();
+        let now_ms = parse_end_date_ms("2026-02-28T12:14:00Z").unwrap();
+
+        let selection = MarketSelection::new(15 * 60 * 1_000);
+
+        let slugs: Vec<&str> = rank_markets(&markets, now_ms, selection.horizon_ms, |market| {
+            selection
+                .matches(Asset::Btc, &market.slug, &market.question)
+                .then(|| market.metadata())
+                .flatten()
+        })
+        .into_iter()
+        .map(|(market, _)| market.slug.as_str())
+        .collect();
+
+        // The 12:15 market settles first but is too thin to be preferred.
+        assert_eq!(slugs, vec!["btc-above-66k-1225", "btc-updown-1215"]);
+    }
+}
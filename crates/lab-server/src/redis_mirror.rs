@@ -0,0 +1,246 @@
+use std::sync::Arc;
+
+use api::state::{PublishedEvent, RuntimeEvent};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{self, Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MIN_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Redis server that bus events are published to, one channel per event
+/// type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisMirrorConfig {
+    /// `host:port` of the server.
+    pub addr: String,
+    /// ACL user; `AUTH` is sent with the password alone when unset.
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Events go to `<channel_prefix>:<event_type>`.
+    pub channel_prefix: String,
+}
+
+impl RedisMirrorConfig {
+    /// Reads `redis://[[user]:password@]host[:port][/db]`. The database is
+    /// ignored, since pub/sub channels are shared by every database.
+    pub fn from_url(url: &str, channel_prefix: impl Into<String>) -> Option<Self> {
+        let rest = url.trim().strip_prefix("redis://")?;
+        let authority = rest.split('/').next().unwrap_or_default();
+        let (credentials, host) = match authority.rsplit_once('@') {
+            Some((credentials, host)) => (Some(credentials), host),
+            None => (None, authority),
+        };
+        let (username, password) = match credentials {
+            Some(credentials) => match credentials.split_once(':') {
+                Some((user, password)) => (
+                    (!user.is_empty()).then(|| user.to_string()),
+                    Some(password.to_string()),
+                ),
+                None => (None, Some(credentials.to_string())),
+            },
+            None => (None, None),
+        };
+        let addr = match host.rsplit_once(':') {
+            Some((name, port)) if !name.is_empty() && port.parse::<u16>().is_ok_and(|p| p > 0) => {
+                host.to_string()
+            }
+            None if !host.is_empty() => format!("{host}:6379"),
+            _ => return None,
+        };
+        Some(Self {
+            addr,
+            username,
+            password,
+            channel_prefix: channel_prefix.into(),
+        })
+    }
+
+    pub fn channel(&self, event_type: &str) -> String {
+        format!("{}:{event_type}", self.channel_prefix)
+    }
+}
+
+/// Encodes `args` as a RESP array of bulk strings.
+fn command(args: &[&[u8]]) -> Vec<u8> {
+    let mut encoded = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        encoded.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        encoded.extend_from_slice(arg);
+        encoded.extend_from_slice(b"\r\n");
+    }
+    encoded
+}
+
+/// `PUBLISH` of the event's JSON, as sent on `/ws/events`, to its type's
+/// channel.
+fn publish_command(config: &RedisMirrorConfig, event: &RuntimeEvent) -> Option<Vec<u8>> {
+    let json = serde_json::to_value(event).ok()?;
+    let channel = config.channel(json["event_type"].as_str()?);
+    let payload = json.to_string();
+    Some(command(&[
+        b"PUBLISH",
+        channel.as_bytes(),
+        payload.as_bytes(),
+    ]))
+}
+
+/// Connects and authenticates, then leaves a task draining the server's
+/// replies so its output buffer never fills.
+async fn connect(config: &RedisMirrorConfig) -> Result<OwnedWriteHalf, String> {
+    let stream = time::timeout(CONNECT_TIMEOUT, TcpStream::connect(&config.addr))
+        .await
+        .map_err(|_| "connect timed out".to_string())?
+        .map_err(|err| err.to_string())?;
+    let (reader, mut writer) = stream.into_split();
+    let mut replies = BufReader::new(reader).lines();
+
+    if let Some(password) = &config.password {
+        let auth = match &config.username {
+            Some(user) => command(&[b"AUTH", user.as_bytes(), password.as_bytes()]),
+            None => command(&[b"AUTH", password.as_bytes()]),
+        };
+        writer
+            .write_all(&auth)
+            .await
+            .map_err(|err| err.to_string())?;
+        match replies.next_line().await {
+            Ok(Some(reply)) if reply.starts_with('+') => {}
+            Ok(Some(reply)) => return Err(format!("AUTH rejected: {reply}")),
+            Ok(None) => return Err("connection closed during AUTH".to_string()),
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    tokio::spawn(async move {
+        while let Ok(Some(reply)) = replies.next_line().await {
+            if let Some(err) = reply.strip_prefix('-') {
+                eprintln!("redis mirror: {err}");
+            }
+        }
+    });
+    Ok(writer)
+}
+
+/// Publishes every bus event until the bus closes. Events that arrive while
+/// the server is unreachable are dropped, as pub/sub would drop them for a
+/// disconnected subscriber; reconnects back off from 1s up to 30s.
+pub async fn run(config: RedisMirrorConfig, mut events: broadcast::Receiver<Arc<PublishedEvent>>) {
+    let mut connection: Option<OwnedWriteHalf> = None;
+    let mut backoff = MIN_RECONNECT_BACKOFF;
+    let mut retry_at = Instant::now();
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                eprintln!("redis mirror missed {skipped} events");
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+        let Some(publish) = publish_command(&config, &event.event) else {
+            continue;
+        };
+
+        if connection.is_none() && Instant::now() >= retry_at {
+            match connect(&config).await {
+                Ok(writer) => {
+                    connection = Some(writer);
+                    backoff = MIN_RECONNECT_BACKOFF;
+                }
+                Err(err) => {
+                    eprintln!("redis mirror to {} unavailable: {err}", config.addr);
+                    retry_at = Instant::now() + backoff;
+                    backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+                }
+            }
+        }
+        let Some(writer) = &mut connection else {
+            continue;
+        };
+        if let Err(err) = writer.write_all(&publish).await {
+            eprintln!("redis mirror to {} lost: {err}", config.addr);
+            connection = None;
+            retry_at = Instant::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::state::PaperOrderSide;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn reads_redis_urls() {
+        let config =
+            RedisMirrorConfig::from_url("redis://:s3cret@cache.local:6380/2", "lab").unwrap();
+        assert_eq!(config.addr, "cache.local:6380");
+        assert_eq!(config.username, None);
+        assert_eq!(config.password.as_deref(), Some("s3cret"));
+        assert_eq!(config.channel("paper_fill"), "lab:paper_fill");
+
+        let config = RedisMirrorConfig::from_url("redis://lab:pw@10.0.0.5", "lab").unwrap();
+        assert_eq!(config.addr, "10.0.0.5:6379");
+        assert_eq!(config.username.as_deref(), Some("lab"));
+
+        for invalid in [
+            "rediss://cache:6380",
+            "redis://",
+            "redis://cache:0",
+            "cache:6379",
+        ] {
+            assert_eq!(
+                RedisMirrorConfig::from_url(invalid, "lab"),
+                None,
+                "{invalid}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn authenticates_and_publishes_events_to_their_type_channel() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = RedisMirrorConfig {
+            addr: listener.local_addr().unwrap().to_string(),
+            username: None,
+            password: Some("pw".to_string()),
+            channel_prefix: "lab:events".to_string(),
+        };
+        let (sender, receiver) = broadcast::channel(8);
+        tokio::spawn(run(config, receiver));
+        sender
+            .send(Arc::new(PublishedEvent::new(RuntimeEvent::paper_fill(
+                "btc-a",
+                PaperOrderSide::Buy,
+                2.0,
+                0.5,
+                0.0,
+            ))))
+            .unwrap();
+
+        let (mut server, _) = listener.accept().await.unwrap();
+        let auth = command(&[b"AUTH", b"pw"]);
+        let mut received = vec![0; auth.len()];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, auth);
+        server.write_all(b"+OK\r\n").await.unwrap();
+
+        let mut published = Vec::new();
+        while !published.ends_with(b"}\r\n") {
+            let mut chunk = [0; 256];
+            let read = server.read(&mut chunk).await.unwrap();
+            assert!(read > 0, "connection closed before PUBLISH");
+            published.extend_from_slice(&chunk[..read]);
+        }
+        let published = String::from_utf8(published).unwrap();
+        assert!(published.starts_with("*3\r\n$7\r\nPUBLISH\r\n$21\r\nlab:events:paper_fill\r\n"));
+        assert!(published.contains(r#""market_id":"btc-a""#));
+    }
+}
@@ -0,0 +1,553 @@
+use api::state::{
+    AppState, AssetPriceSnapshot, ExecutionLogEntry, ExecutionMode as StateExecutionMode,
+    PaperOrderSide, PortfolioSummary, PriceSnapshot, RiskStatus, RuntimeEvent, RuntimeSettings,
+    SourceCount, StrategyPerfSummary,
+};
+use core_sim::{
+    Fill, MultiVenueGenerator, MultiVenueTick, PriceProcess, SessionCalendar, SessionPhase,
+    SimConfig, SimState, SimYesQuote, VenueSpec, YesMarketSpec, YesQuoteSimulator,
+};
+use runtime::positions::to_f64;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use strategy::{
+    risk_budget_qty, Signal, Strategy, StrategyInputs, StrategyRegistry, DEFAULT_STRATEGY,
+};
+use tokio::time::{self, Duration, MissedTickBehavior};
+
+use crate::{now_unix_ms, DEFAULT_STARTING_EQUITY};
+
+const SIM_LOOP_INTERVAL_MS: u64 = 250;
+const SIM_START_BTC_USD: f64 = 64_000.0;
+const SIM_VOL_BPS_PER_SQRT_MIN: f64 = 8.0;
+const SIM_YES_SPREAD: f64 = 0.02;
+
+/// Spot venues and the lag each one trails the latent price by.
+const SIM_VENUES: [(&str, u64, u64, f64); 3] = [
+    ("coinbase", 40, 20, 0.5),
+    ("binance", 20, 10, 0.5),
+    ("kraken", 120, 60, 1.0),
+];
+
+/// Synthetic BTC spot and 15m YES markets driving the paper book, so
+/// `LAB_SERVER_MODE=sim` serves a working dashboard without any network.
+pub struct SimEngine {
+    seed: u64,
+    start_ms: u64,
+    venues: MultiVenueGenerator,
+    calendar: SessionCalendar,
+    polymarket: Option<SimMarket>,
+    kalshi: Option<SimMarket>,
+    strategy_name: String,
+    strategy: Box<dyn Strategy>,
+    starting_equity: f64,
+    account: SimState,
+    peak_equity: f64,
+    last_spot: Option<f64>,
+    source_counts: Vec<u64>,
+    fills: u64,
+    lag_triggers: u64,
+    /// Steps taken so far; the sim's equivalent of the live loop tick.
+    steps: u64,
+}
+
+struct SimMarket {
+    market_id: String,
+    strike: f64,
+    expiry_ms: u64,
+    quotes: YesQuoteSimulator,
+}
+
+/// Everything one step of the engine publishes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimStep {
+    pub price_snapshot: PriceSnapshot,
+    pub fills: Vec<SimFill>,
+    pub logs: Vec<ExecutionLogEntry>,
+    pub perf: StrategyPerfSummary,
+    pub portfolio: PortfolioSummary,
+    pub risk: RiskStatus,
+    pub source_counts: Vec<SourceCount>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimFill {
+    pub market_id: String,
+    pub side: PaperOrderSide,
+    pub qty: f64,
+    pub fill_px: f64,
+}
+
+impl SimEngine {
+    pub fn new(seed: u64, start_ms: u64, starting_equity: f64) -> Self {
+        let venues = SIM_VENUES
+            .iter()
+            .map(|(name, base_lag_ms, jitter_ms, noise_bps)| VenueSpec {
+                name: name.to_string(),
+                base_lag_ms: *base_lag_ms,
+                jitter_ms: *jitter_ms,
+                noise_bps: *noise_bps,
+            })
+            .collect();
+        let per_step_vol =
+            SIM_VOL_BPS_PER_SQRT_MIN / 10_000.0 * (SIM_LOOP_INTERVAL_MS as f64 / 60_000.0).sqrt();
+
+        Self {
+            seed,
+            start_ms,
+            venues: MultiVenueGenerator::new(
+                seed,
+                PriceProcess::Gbm {
+                    drift: 0.0,
+                    volatility: per_step_vol,
+                },
+                SIM_START_BTC_USD,
+                SIM_LOOP_INTERVAL_MS,
+                venues,
+            ),
+            calendar: SessionCalendar::default(),
+            polymarket: None,
+            kalshi: None,
+            strategy_name: DEFAULT_STRATEGY.to_string(),
+            strategy: StrategyRegistry::with_builtins()
+                .create(DEFAULT_STRATEGY)
+                .expect("default strategy is registered"),
+            starting_equity,
+            account: SimState {
+                equity: starting_equity,
+                cash: starting_equity,
+                day_start_equity: starting_equity,
+                ..SimState::default()
+            },
+            peak_equity: starting_equity,
+            last_spot: None,
+            source_counts: vec![0; SIM_VENUES.len() + 2],
+            fills: 0,
+            lag_triggers: 0,
+            steps: 0,
+        }
+    }
+
+    /// Advances the sim by one loop interval, trading the Polymarket-style
+    /// market with the strategy the settings select.
+    pub fn step(&mut self, settings: &RuntimeSettings) -> SimStep {
+        self.steps += 1;
+        self.select_strategy(&settings.strategy);
+        let tick = self.venues.next_tick();
+        let ts = self.start_ms + tick.ts_ms;
+        let spot_px = median(&tick);
+        let spread_signal = self.last_spot.map_or(0.0, |last| spot_px - last);
+        self.last_spot = Some(spot_px);
+        for count in &mut self.source_counts {
+            *count += 1;
+        }
+
+        let mut fills = Vec::new();
+        let mut logs = Vec::new();
+        self.roll_markets(ts, spot_px, &mut fills, &mut logs);
+
+        let polymarket = self
+            .polymarket
+            .as_mut()
+            .and_then(|market| market.quote(ts, spot_px));
+        let kalshi = self
+            .kalshi
+            .as_mut()
+            .and_then(|market| market.quote(ts, spot_px));
+
+        let config = SimConfig {
+            daily_loss_cap_pct: settings.daily_loss_cap_pct / 100.0,
+            ..SimConfig::default()
+        };
+        let mut intents = 0;
+        let entries_open = matches!(self.calendar.phase(ts), SessionPhase::Open { .. });
+        let market_id = self
+            .polymarket
+            .as_ref()
+            .map(|market| market.market_id.clone());
+        if let (Some(market_id), Some(quote)) = (market_id, polymarket) {
+            if !settings.trading_paused && !self.account.halted {
+                let inputs = StrategyInputs {
+                    market_id: &market_id,
+                    underlying: "btc",
+                    spot_px,
+                    ts_ms: ts,
+                    mid_yes: quote.mid_yes,
+                    best_yes_bid: quote.best_yes_bid,
+                    best_yes_ask: quote.best_yes_ask,
+                    fair_yes_px: quote.fair_yes,
+                    spread_signal,
+                    threshold_pct: settings.lag_threshold_pct,
+                    base_qty: risk_budget_qty(
+                        settings.risk_per_trade_pct / 100.0,
+                        self.account.equity,
+                        quote.best_yes_bid,
+                        quote.best_yes_ask,
+                    )
+                    .unwrap_or(0.0),
+                    position_qty: self.account.position,
+                };
+                for intent in self.strategy.on_tick(&inputs) {
+                    intents += 1;
+                    let (side, fill_px) = match intent.side {
+                        Signal::Buy => (core_sim::Side::Buy, quote.best_yes_ask),
+                        Signal::Sell => (core_sim::Side::Sell, quote.best_yes_bid),
+                        Signal::Hold => continue,
+                    };
+                    let reduces = (self.account.position > 0.0 && side == core_sim::Side::Sell)
+                        || (self.account.position < 0.0 && side == core_sim::Side::Buy);
+                    if !reduces && !entries_open {
+                        continue;
+                    }
+                    if !reduces {
+                        self.lag_triggers += 1;
+                    }
+                    self.fill(
+                        &market_id, side, intent.qty, fill_px, ts, &mut fills, &mut logs,
+                    );
+                }
+            }
+            self.account.mark_to_market(quote.mid_yes, &config);
+        }
+
+        let equity = self.account.equity;
+        self.peak_equity = self.peak_equity.max(equity);
+        let throughput_scale = 1000.0 / SIM_LOOP_INTERVAL_MS as f64;
+        SimStep {
+            price_snapshot: self.price_snapshot(&tick, ts, spot_px, polymarket, kalshi),
+            perf: StrategyPerfSummary {
+                execution_mode: match settings.execution_mode {
+                    StateExecutionMode::Paper => "paper".to_string(),
+                    StateExecutionMode::DryRun => "dry_run".to_string(),
+                    StateExecutionMode::Live => "live".to_string(),
+                },
+                lag_threshold_pct: settings.lag_threshold_pct,
+                decision_p95_us: 0,
+                intents_per_sec: (intents as f64 * throughput_scale).round() as u64,
+                fills_per_sec: (fills.len() as f64 * throughput_scale).round() as u64,
+                lag_triggers: self.lag_triggers,
+                throttled_intents: 0,
+                spread_skips: 0,
+                halted: self.account.halted,
+            },
+            portfolio: PortfolioSummary {
+                equity: usd(equity),
+                pnl: usd(equity - self.starting_equity),
+                position_qty: self.account.position,
+                fills: self.fills,
+            },
+            risk: RiskStatus {
+                halted: self.account.halted,
+                equity: usd(equity),
+                peak_equity: usd(self.peak_equity),
+                drawdown_pct: (self.peak_equity - equity) / self.peak_equity * 100.0,
+                var_95: None,
+                var_limit: None,
+                var_size_scale: 1.0,
+                var_samples: 0,
+                losing_streak: 0,
+                cooldown_resume_at_ms: None,
+            },
+            source_counts: self.source_counts(),
+            fills,
+            logs,
+        }
+    }
+
+    fn select_strategy(&mut self, name: &str) {
+        if name == self.strategy_name {
+            return;
+        }
+        if let Some(strategy) = StrategyRegistry::with_builtins().create(name) {
+            self.strategy = strategy;
+            self.strategy_name = name.to_string();
+        }
+    }
+
+    /// Settles markets whose window has closed and lists the next ones,
+    /// struck at the current spot.
+    fn roll_markets(
+        &mut self,
+        ts: u64,
+        spot_px: f64,
+        fills: &mut Vec<SimFill>,
+        logs: &mut Vec<ExecutionLogEntry>,
+    ) {
+        let Some(expiry_ms) = self.calendar.expiry_at(ts) else {
+            return;
+        };
+        if self
+            .polymarket
+            .as_ref()
+            .is_some_and(|market| market.expiry_ms == expiry_ms)
+        {
+            return;
+        }
+
+        if let Some(market) = self.polymarket.take() {
+            if self.account.position != 0.0 {
+                let settle_px = if spot_px > market.strike { 1.0 } else { 0.0 };
+                let side = if self.account.position > 0.0 {
+                    PaperOrderSide::Sell
+                } else {
+                    PaperOrderSide::Buy
+                };
+                let qty = self.account.position.abs();
+                let realized_before = self.account.realized_pnl;
+                self.account.settle(settle_px);
+                self.fills += 1;
+                fills.push(SimFill {
+                    market_id: market.market_id.clone(),
+                    side,
+                    qty,
+                    fill_px: settle_px,
+                });
+                logs.push(ExecutionLogEntry {
+                    tick: self.steps,
+                    ts,
+                    event: "market_resolved".to_string(),
+                    headline: "Market Resolved".to_string(),
+                    detail: format!(
+                        "sim:{} settle={settle_px} qty={qty} pnl={:.4}",
+                        market.market_id,
+                        self.account.realized_pnl - realized_before
+                    ),
+                });
+            }
+        }
+
+        let market_seed = self.seed ^ expiry_ms;
+        self.polymarket = SimMarket::new("polymarket", market_seed, spot_px, expiry_ms, 600);
+        self.kalshi = SimMarket::new(
+            "kalshi",
+            market_seed.rotate_left(1),
+            spot_px,
+            expiry_ms,
+            900,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn fill(
+        &mut self,
+        market_id: &str,
+        side: core_sim::Side,
+        qty: f64,
+        fill_px: f64,
+        ts: u64,
+        fills: &mut Vec<SimFill>,
+        logs: &mut Vec<ExecutionLogEntry>,
+    ) {
+        self.account.apply_fill(
+            side,
+            &Fill {
+                price: fill_px,
+                qty,
+                fee: 0.0,
+            },
+        );
+        self.fills += 1;
+        let side = match side {
+            core_sim::Side::Buy => PaperOrderSide::Buy,
+            core_sim::Side::Sell => PaperOrderSide::Sell,
+        };
+        fills.push(SimFill {
+            market_id: market_id.to_string(),
+            side,
+            qty,
+            fill_px,
+        });
+        logs.push(ExecutionLogEntry {
+            tick: self.steps,
+            ts,
+            event: "paper_fill".to_string(),
+            headline: "Paper Fill".to_string(),
+            detail: format!("sim:{market_id} {side:?} qty={qty} @ {fill_px:.4}"),
+        });
+    }
+
+    fn price_snapshot(
+        &self,
+        tick: &MultiVenueTick,
+        ts: u64,
+        spot_px: f64,
+        polymarket: Option<SimYesQuote>,
+        kalshi: Option<SimYesQuote>,
+    ) -> PriceSnapshot {
+        let venue_px = |name: &str| {
+            tick.quotes
+                .iter()
+                .find(|quote| quote.venue == name)
+                .map(|quote| quote.px)
+        };
+        let polymarket_id = self.polymarket.as_ref().map(|m| m.market_id.clone());
+        let kalshi_id = self.kalshi.as_ref().map(|m| m.market_id.clone());
+
+        PriceSnapshot {
+            coinbase_btc_usd: venue_px("coinbase"),
+            binance_btc_usdt: venue_px("binance"),
+            kraken_btc_usd: venue_px("kraken"),
+            polymarket_market_id: polymarket_id.clone(),
+            polymarket_yes_bid: polymarket.map(|quote| quote.best_yes_bid),
+            polymarket_yes_ask: polymarket.map(|quote| quote.best_yes_ask),
+            polymarket_yes_mid: polymarket.map(|quote| quote.mid_yes),
+            kalshi_market_id: kalshi_id.clone(),
+            kalshi_yes_bid: kalshi.map(|quote| quote.best_yes_bid),
+            kalshi_yes_ask: kalshi.map(|quote| quote.best_yes_ask),
+            kalshi_yes_mid: kalshi.map(|quote| quote.mid_yes),
+            btc_composite_raw: Some(spot_px),
+            btc_composite_smoothed: Some(spot_px),
+            assets: vec![AssetPriceSnapshot {
+                asset: "btc".to_string(),
+                composite_raw: Some(spot_px),
+                composite_smoothed: Some(spot_px),
+                forecast_15m: None,
+                polymarket_market_id: polymarket_id,
+                polymarket_yes_mid: polymarket.map(|quote| quote.mid_yes),
+                kalshi_market_id: kalshi_id,
+                kalshi_yes_mid: kalshi.map(|quote| quote.mid_yes),
+            }],
+            tick: self.steps,
+            ts,
+        }
+    }
+
+    fn source_counts(&self) -> Vec<SourceCount> {
+        SIM_VENUES
+            .iter()
+            .map(|(name, ..)| *name)
+            .chain(["polymarket", "kalshi"])
+            .zip(&self.source_counts)
+            .map(|(source, count)| SourceCount {
+                source: format!("sim_{source}"),
+                count: *count,
+                endpoint: None,
+                blocked: None,
+            })
+            .collect()
+    }
+}
+
+impl SimMarket {
+    fn new(venue: &str, seed: u64, strike: f64, expiry_ms: u64, base_lag_ms: u64) -> Option<Self> {
+        let spec = YesMarketSpec {
+            strike,
+            expiry_ms,
+            vol_bps_per_sqrt_min: SIM_VOL_BPS_PER_SQRT_MIN,
+            base_lag_ms,
+            jitter_ms: base_lag_ms / 2,
+            spread: SIM_YES_SPREAD,
+        };
+        Some(Self {
+            market_id: format!("sim-{venue}-btc-updown-15m-{}", expiry_ms / 1_000),
+            strike,
+            expiry_ms,
+            quotes: YesQuoteSimulator::new(seed, spec).ok()?,
+        })
+    }
+
+    fn quote(&mut self, ts: u64, spot_px: f64) -> Option<SimYesQuote> {
+        self.quotes.on_spot(ts, spot_px).ok()
+    }
+}
+
+/// The simulated account books in floats; the API reports money as decimals.
+fn usd(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+fn median(tick: &MultiVenueTick) -> f64 {
+    let mut prices: Vec<f64> = tick.quotes.iter().map(|quote| quote.px).collect();
+    prices.sort_by(f64::total_cmp);
+    match prices.len() {
+        0 => tick.true_px,
+        len if len % 2 == 1 => prices[len / 2],
+        len => (prices[len / 2 - 1] + prices[len / 2]) / 2.0,
+    }
+}
+
+/// Steps a [`SimEngine`] on a timer and publishes each step into `state`
+/// the way the paper-live loop does.
+pub async fn run_sim_loop(state: AppState, seed: u64) {
+    let mut engine = SimEngine::new(seed, now_unix_ms(), to_f64(DEFAULT_STARTING_EQUITY));
+    let mut interval = time::interval(Duration::from_millis(SIM_LOOP_INTERVAL_MS));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        let step = engine.step(&state.runtime_settings());
+        let tick = step.price_snapshot.tick;
+        publish_step(&state, step);
+        state.record_loop_heartbeat(tick, now_unix_ms());
+    }
+}
+
+pub fn publish_step(state: &AppState, step: SimStep) {
+    state.set_feed_source_counts(step.source_counts.clone());
+    let _ = state.publish_event(RuntimeEvent::feed_health(
+        state.feed_health().mode,
+        step.source_counts,
+    ));
+    state.set_price_snapshot(step.price_snapshot.clone());
+    let _ = state.publish_event(RuntimeEvent::price_snapshot(step.price_snapshot));
+    for fill in step.fills {
+        let _ = state.publish_event(RuntimeEvent::paper_fill(
+            &fill.market_id,
+            fill.side,
+            fill.qty,
+            fill.fill_px,
+            0.0,
+        ));
+    }
+    for log in step.logs {
+        state.push_execution_log(log.clone(), 500);
+        let _ = state.publish_event(RuntimeEvent::execution_log(log));
+    }
+    state.set_risk_status(step.risk);
+    state.set_strategy_perf_summary(step.perf.clone());
+    let _ = state.publish_event(RuntimeEvent::strategy_perf(step.perf));
+    state.set_portfolio_summary(step.portfolio);
+    let _ = state.publish_event(RuntimeEvent::portfolio_snapshot(step.portfolio));
+}
+
+#[cfg(test)]
+mod tests {
+    use api::state::{AppState, FeedMode, RuntimeSettings};
+
+    use super::{publish_step, SimEngine};
+
+    #[test]
+    fn sim_steps_fill_the_dashboard_state() {
+        let state = AppState::with_feed_mode(FeedMode::Sim);
+        let settings = RuntimeSettings {
+            lag_threshold_pct: 0.5,
+            ..RuntimeSettings::default()
+        };
+        let mut engine = SimEngine::new(7, 0, 10_000.0);
+        let steps: Vec<_> = (0..8_000).map(|_| engine.step(&settings)).collect();
+
+        let last = steps.last().unwrap();
+        assert!(last.price_snapshot.coinbase_btc_usd.is_some());
+        assert!(last.price_snapshot.polymarket_yes_mid.is_some());
+        assert!(last.price_snapshot.kalshi_market_id.is_some());
+        assert!(steps.iter().any(|step| !step.fills.is_empty()));
+        // 8,000 quarter-second steps cross at least one 15m expiry.
+        assert!(steps
+            .iter()
+            .flat_map(|step| &step.logs)
+            .any(|log| log.event == "market_resolved"));
+        assert_eq!(
+            last.portfolio.fills,
+            steps
+                .iter()
+                .map(|step| step.fills.len() as u64)
+                .sum::<u64>()
+        );
+
+        publish_step(&state, last.clone());
+        assert_eq!(state.feed_health().mode, FeedMode::Sim);
+        assert_eq!(state.feed_health().source_counts.len(), 5);
+        assert_eq!(state.price_snapshot(), last.price_snapshot);
+        assert_eq!(state.portfolio_summary(), last.portfolio);
+    }
+}
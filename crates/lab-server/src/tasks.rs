@@ -0,0 +1,664 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use api::state::ExecutionMode;
+use runtime::execution::{ExecutionAdapter, OrderReport, OrderRequest, PaperExecutor};
+use runtime::live::{
+    FeedReconnect, PredictorTick, QuoteSource, ReconnectPolicy, RejectedPayload, ReplayPredictors,
+    SharedSupervisor, VenueFeedError,
+};
+use runtime::positions::MarketOutcome;
+use runtime::supervisor::{Supervisor, TaskId};
+use tokio::sync::{mpsc, watch, Mutex as AsyncMutex};
+use tokio::time::{self, Duration, Instant, MissedTickBehavior};
+
+use crate::feeds::{MarketFeeds, PollGate};
+use crate::http::RateLimitedClient;
+use crate::predictors::PredictorEndpoint;
+use crate::{now_unix_ms, quotes};
+
+/// Ids below this belong to the venue adapters spawned by [`MarketFeeds`].
+const PREDICTOR_TASK_BASE: u64 = 1_000;
+pub const RESOLVER_TASK_ID: TaskId = TaskId(2_000);
+pub const BACKFILL_TASK_ID: TaskId = TaskId(2_001);
+pub const DECISION_TASK_ID: TaskId = TaskId(3_000);
+pub const EXECUTION_TASK_ID: TaskId = TaskId(3_001);
+/// Resolution lookups queued beyond this are dropped and retried on the
+/// next check.
+const RESOLUTION_QUEUE: usize = 64;
+/// Markets awaiting a trade backfill beyond this are not backfilled.
+const BACKFILL_QUEUE: usize = 16;
+/// Orders waiting on a slow venue beyond this are rejected by the decision
+/// loop instead of queued.
+const EXECUTION_QUEUE: usize = 16;
+/// Pause before a panicked task is spawned again.
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// What a polled source hands to the decision loop.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PollEvent<T> {
+    Value(T),
+    Reconnect(FeedReconnect),
+}
+
+/// A market whose venue reported an outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolution {
+    pub source: QuoteSource,
+    pub market_id: String,
+    pub outcome: MarketOutcome,
+}
+
+/// YES prices traded in a Polymarket market before it was first quoted this
+/// session, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backfill {
+    pub market_id: String,
+    pub trades: Vec<(u64, f64)>,
+}
+
+/// Channels between the supervised ingest tasks and the decision loop.
+pub struct DecisionInputs {
+    pub predictors: mpsc::UnboundedReceiver<PollEvent<PredictorTick>>,
+    pub resolution_requests: mpsc::Sender<(QuoteSource, String)>,
+    pub resolutions: mpsc::UnboundedReceiver<Resolution>,
+    pub backfill_requests: mpsc::Sender<String>,
+    pub backfills: mpsc::UnboundedReceiver<Backfill>,
+}
+
+/// Spawns one poll task per predictor endpoint, the resolution lookup task
+/// and the trade backfill task, all registered with the feeds' supervisor, so
+/// a slow HTTP source only delays its own updates. Endpoints without their
+/// own `poll_ms` are polled at `default_poll_interval`, and follow it when it
+/// changes; backfills fetch up to `backfill_trades` trades. A replay's
+/// predictor ticks stand in for the endpoints, which are then not polled, and
+/// its markets are not resolved against the live venues.
+pub fn spawn_ingest(
+    client: &RateLimitedClient,
+    market_feeds: &MarketFeeds,
+    endpoints: &[PredictorEndpoint],
+    default_poll_interval: watch::Receiver<Duration>,
+    backfill_trades: usize,
+    replayed_predictors: Option<ReplayPredictors>,
+) -> DecisionInputs {
+    let (predictor_tx, predictors) = mpsc::unbounded_channel();
+    let replaying = replayed_predictors.is_some();
+    let endpoints = match replayed_predictors {
+        Some(replayed) => {
+            tokio::spawn(run_predictor_replay(replayed, predictor_tx.clone()));
+            &[]
+        }
+        None => endpoints,
+    };
+    for (idx, endpoint) in endpoints.iter().enumerate() {
+        let client = client.clone();
+        let feeds = market_feeds.clone();
+        let endpoint = endpoint.clone();
+        let poll_interval = match endpoint.poll_ms {
+            // Nothing ever changes a fixed interval, so its sender is dropped.
+            Some(poll_ms) => watch::channel(Duration::from_millis(poll_ms)).1,
+            None => default_poll_interval.clone(),
+        };
+        tokio::spawn(run_poll_task(
+            endpoint.source.as_str().to_string(),
+            market_feeds.supervisor.clone(),
+            TaskId(PREDICTOR_TASK_BASE + idx as u64),
+            market_feeds.reconnect_policy(),
+            poll_interval,
+            predictor_tx.clone(),
+            move |ts_ms| fetch_predictor(client.clone(), feeds.clone(), endpoint.clone(), ts_ms),
+        ));
+    }
+
+    let (resolution_requests, request_rx) = mpsc::channel(RESOLUTION_QUEUE);
+    let (resolution_tx, resolutions) = mpsc::unbounded_channel();
+    if !replaying {
+        let request_rx = Arc::new(AsyncMutex::new(request_rx));
+        let resolver_client = client.clone();
+        tokio::spawn(supervise(
+            market_feeds.supervisor.clone(),
+            RESOLVER_TASK_ID,
+            move || {
+                run_resolver(
+                    resolver_client.clone(),
+                    request_rx.clone(),
+                    resolution_tx.clone(),
+                )
+            },
+        ));
+    }
+
+    let (backfill_requests, backfill_rx) = mpsc::channel(BACKFILL_QUEUE);
+    let (backfill_tx, backfills) = mpsc::unbounded_channel();
+    let backfill_rx = Arc::new(AsyncMutex::new(backfill_rx));
+    let backfill_client = client.clone();
+    tokio::spawn(supervise(
+        market_feeds.supervisor.clone(),
+        BACKFILL_TASK_ID,
+        move || {
+            run_backfiller(
+                backfill_client.clone(),
+                backfill_trades,
+                backfill_rx.clone(),
+                backfill_tx.clone(),
+            )
+        },
+    ));
+
+    DecisionInputs {
+        predictors,
+        resolution_requests,
+        resolutions,
+        backfill_requests,
+        backfills,
+    }
+}
+
+/// Venue adapters for the non-paper execution modes.
+#[derive(Default)]
+pub struct VenueExecutors {
+    pub live: Option<Box<dyn ExecutionAdapter>>,
+    /// Signs the live adapter's orders without sending them.
+    pub dry_run: Option<Box<dyn ExecutionAdapter>>,
+}
+
+/// An order the decision loop admitted, with whatever the loop needs to book
+/// the outcome once the venue answers.
+#[derive(Debug, Clone)]
+pub struct ExecutionRequest<C> {
+    pub mode: ExecutionMode,
+    pub order: OrderRequest,
+    pub context: C,
+}
+
+/// Why an admitted order did not fill, in the terms the loop reports it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionRejection {
+    pub reason: &'static str,
+    pub headline: &'static str,
+    pub detail: String,
+}
+
+/// The venue's answer to an [`ExecutionRequest`]; only reports with a
+/// filled quantity come back as `Ok`.
+#[derive(Debug, Clone)]
+pub struct ExecutionResult<C> {
+    pub context: C,
+    pub report: Result<OrderReport, ExecutionRejection>,
+}
+
+/// Channels between the decision loop and the execution task.
+pub struct ExecutionChannel<C> {
+    pub orders: mpsc::Sender<ExecutionRequest<C>>,
+    pub results: mpsc::UnboundedReceiver<ExecutionResult<C>>,
+}
+
+/// The adapters the execution task places orders with, kept outside the
+/// task so a restart picks up the same venues and paper order history.
+#[derive(Default)]
+struct Executors {
+    venues: VenueExecutors,
+    paper: PaperExecutor,
+}
+
+/// Spawns the execution task, supervised under `supervisor`, so an order
+/// waiting on a venue never holds up the next decision. Paper orders fill
+/// on a [`PaperExecutor`] owned by the task; live ones need
+/// `live_feature_enabled` as well as a live venue. An order in flight when
+/// the task panics gets no report.
+pub fn spawn_execution<C: Send + 'static>(
+    supervisor: &SharedSupervisor,
+    venues: VenueExecutors,
+    live_feature_enabled: bool,
+) -> ExecutionChannel<C> {
+    let (orders, requests) = mpsc::channel(EXECUTION_QUEUE);
+    let (result_tx, results) = mpsc::unbounded_channel();
+    let requests = Arc::new(AsyncMutex::new(requests));
+    let executors = Arc::new(AsyncMutex::new(Executors {
+        venues,
+        ..Executors::default()
+    }));
+    tokio::spawn(supervise(
+        supervisor.clone(),
+        EXECUTION_TASK_ID,
+        move || {
+            run_execution(
+                executors.clone(),
+                live_feature_enabled,
+                requests.clone(),
+                result_tx.clone(),
+            )
+        },
+    ));
+    ExecutionChannel { orders, results }
+}
+
+/// Places the decision loop's orders one at a time, in the order they were
+/// admitted, until the loop hangs up.
+async fn run_execution<C>(
+    executors: Arc<AsyncMutex<Executors>>,
+    live_feature_enabled: bool,
+    requests: Arc<AsyncMutex<mpsc::Receiver<ExecutionRequest<C>>>>,
+    results: mpsc::UnboundedSender<ExecutionResult<C>>,
+) {
+    let mut requests = requests.lock().await;
+    let mut executors = executors.lock().await;
+    let Executors { venues, paper } = &mut *executors;
+
+    while let Some(request) = requests.recv().await {
+        let report = place_order(
+            venues,
+            paper,
+            live_feature_enabled,
+            request.mode,
+            &request.order,
+        )
+        .await;
+        let result = ExecutionResult {
+            context: request.context,
+            report,
+        };
+        if results.send(result).is_err() {
+            break;
+        }
+    }
+}
+
+/// Registers `task_id` and runs the task `spawn` builds, building and
+/// spawning it again after a panic, which the supervisor sees as a failure
+/// and restart. A task that returns has stopped for good.
+pub async fn supervise<F, Fut>(supervisor: SharedSupervisor, task_id: TaskId, mut spawn: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    lock(&supervisor).register(task_id);
+    loop {
+        lock(&supervisor).mark_running(task_id);
+        match tokio::spawn(spawn()).await {
+            Err(err) if err.is_panic() => {
+                eprintln!("task {} panicked; restarting", task_id.0);
+                let mut guard = lock(&supervisor);
+                if guard.mark_failed(task_id).is_some() {
+                    guard.mark_restarting(task_id);
+                }
+            }
+            // Returned, or cancelled as the runtime shuts down.
+            _ => break,
+        }
+        time::sleep(RESTART_DELAY).await;
+    }
+    stop(&supervisor, task_id);
+}
+
+/// Live orders go to the venue only when both the mode and the feature flag
+/// allow it; dry runs never send anything.
+async fn place_order(
+    venues: &mut VenueExecutors,
+    paper: &mut PaperExecutor,
+    live_feature_enabled: bool,
+    mode: ExecutionMode,
+    order: &OrderRequest,
+) -> Result<OrderReport, ExecutionRejection> {
+    let rejection = |reason, headline, detail| ExecutionRejection {
+        reason,
+        headline,
+        detail,
+    };
+    let placed = match mode {
+        ExecutionMode::Live => match (venues.live.as_mut(), live_feature_enabled) {
+            (_, false) => Err(rejection(
+                "live mode disabled by feature flag",
+                "Live Mode Blocked",
+                "Enable LAB_LIVE_FEATURE_ENABLED to allow live mode".to_string(),
+            )),
+            (None, true) => Err(rejection(
+                "no live execution venue",
+                "Live Venue Missing",
+                "Set LAB_CLOB_* in a build with the polymarket-clob feature".to_string(),
+            )),
+            (Some(live), true) => live.place(order).await.map_err(|err| {
+                rejection("live order failed", "Live Order Failed", format!("{err:?}"))
+            }),
+        },
+        ExecutionMode::DryRun => match venues.dry_run.as_mut() {
+            None => Err(rejection(
+                "no dry-run execution venue",
+                "Dry Run Venue Missing",
+                "Set LAB_CLOB_* in a build with the polymarket-clob feature".to_string(),
+            )),
+            Some(dry_run) => dry_run.place(order).await.map_err(|err| {
+                rejection(
+                    "dry-run order failed",
+                    "Dry Run Order Failed",
+                    format!("{err:?}"),
+                )
+            }),
+        },
+        ExecutionMode::Paper => paper
+            .place(order)
+            .await
+            .map_err(|err| rejection("paper fill failed", "Paper Fill Failed", format!("{err:?}"))),
+    };
+    placed.and_then(|report| {
+        if report.filled_qty > 0.0 {
+            Ok(report)
+        } else {
+            Err(rejection(
+                "order not filled",
+                "Order Not Filled",
+                format!("order {} {:?}", report.order_id, report.status),
+            ))
+        }
+    })
+}
+
+/// Forwards replayed predictor ticks as if they had been polled. Their
+/// recorded stamps are kept, since the decision loop runs on the replay clock.
+async fn run_predictor_replay(
+    mut predictors: ReplayPredictors,
+    sender: mpsc::UnboundedSender<PollEvent<PredictorTick>>,
+) {
+    while let Some((_, tick)) = predictors.next().await {
+        if sender.send(PollEvent::Value(tick)).is_err() {
+            return;
+        }
+    }
+}
+
+/// Polls `fetch` every `interval` behind a [`PollGate`] and forwards values
+/// and scheduled retries until the decision loop hangs up. The first poll is
+/// immediate; a new interval takes effect one period after it is sent.
+pub async fn run_poll_task<T, F, Fut>(
+    source: String,
+    supervisor: SharedSupervisor,
+    task_id: TaskId,
+    policy: ReconnectPolicy,
+    mut interval: watch::Receiver<Duration>,
+    sender: mpsc::UnboundedSender<PollEvent<T>>,
+    mut fetch: F,
+) where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Option<Result<T, VenueFeedError>>>,
+{
+    let mut gate = PollGate::new(&source, policy);
+    let mut period = *interval.borrow_and_update();
+    let mut ticker = poll_ticker(Instant::now(), period);
+    let mut follow_interval = true;
+    lock(&supervisor).register(task_id);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            changed = interval.changed(), if follow_interval => {
+                match changed {
+                    Ok(()) => {
+                        let next = *interval.borrow_and_update();
+                        if next != period {
+                            period = next;
+                            ticker = poll_ticker(Instant::now() + period, period);
+                        }
+                    }
+                    Err(_) => follow_interval = false,
+                }
+                continue;
+            }
+        }
+        let now_ms = now_unix_ms();
+        let (value, reconnect) = gate.poll(now_ms, fetch(now_ms)).await;
+        if value.is_some() {
+            lock(&supervisor).mark_running(task_id);
+        }
+        if reconnect.is_some() {
+            let mut guard = lock(&supervisor);
+            if guard.mark_failed(task_id).is_some() {
+                guard.mark_restarting(task_id);
+            }
+        }
+
+        let events = value
+            .map(PollEvent::Value)
+            .into_iter()
+            .chain(reconnect.map(PollEvent::Reconnect));
+        for event in events {
+            if sender.send(event).is_err() {
+                stop(&supervisor, task_id);
+                return;
+            }
+        }
+        if sender.is_closed() {
+            stop(&supervisor, task_id);
+            return;
+        }
+    }
+}
+
+fn poll_ticker(start: Instant, period: Duration) -> time::Interval {
+    let mut ticker = time::interval_at(start, period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    ticker
+}
+
+/// Looks up market outcomes requested by the decision loop and reports the
+/// ones that have resolved.
+async fn run_resolver(
+    client: RateLimitedClient,
+    requests: Arc<AsyncMutex<mpsc::Receiver<(QuoteSource, String)>>>,
+    results: mpsc::UnboundedSender<Resolution>,
+) {
+    let mut requests = requests.lock().await;
+    while let Some((source, market_id)) = requests.recv().await {
+        let Ok(Some(outcome)) = quotes::fetch_resolution(&client, source, &market_id).await else {
+            continue;
+        };
+        let resolution = Resolution {
+            source,
+            market_id,
+            outcome,
+        };
+        if results.send(resolution).is_err() {
+            break;
+        }
+    }
+}
+
+/// Fetches the recent trades of Polymarket markets the decision loop starts
+/// quoting. A failed fetch is logged and the market trades without a warm-up.
+async fn run_backfiller(
+    client: RateLimitedClient,
+    limit: usize,
+    requests: Arc<AsyncMutex<mpsc::Receiver<String>>>,
+    results: mpsc::UnboundedSender<Backfill>,
+) {
+    let mut requests = requests.lock().await;
+    while let Some(market_id) = requests.recv().await {
+        let trades = match quotes::fetch_trade_history(&client, &market_id, limit).await {
+            Ok(trades) if !trades.is_empty() => trades,
+            Ok(_) => continue,
+            Err(err) => {
+                eprintln!("trade backfill for {market_id} failed: {err:?}");
+                continue;
+            }
+        };
+        if results.send(Backfill { market_id, trades }).is_err() {
+            break;
+        }
+    }
+}
+
+/// Registers the decision loop itself so its state is visible alongside the
+/// tasks feeding it.
+pub fn register_decision_task(supervisor: &SharedSupervisor) {
+    let mut guard = lock(supervisor);
+    guard.register(DECISION_TASK_ID);
+    guard.mark_running(DECISION_TASK_ID);
+}
+
+async fn fetch_predictor(
+    client: RateLimitedClient,
+    market_feeds: MarketFeeds,
+    endpoint: PredictorEndpoint,
+    ts_ms: u64,
+) -> Option<Result<PredictorTick, VenueFeedError>> {
+    Some(client.get_text(&endpoint.url).await.and_then(|payload| {
+        endpoint.parse_payload(&payload, ts_ms).map_err(|err| {
+            market_feeds.quarantine(
+                endpoint.source.as_str(),
+                RejectedPayload::new(err.as_str(), &payload),
+                ts_ms,
+            );
+            VenueFeedError::Parse
+        })
+    }))
+}
+
+fn stop(supervisor: &SharedSupervisor, task_id: TaskId) {
+    let mut guard = lock(supervisor);
+    if !guard.mark_stopped(task_id) {
+        // A task that never got a value is still starting; stopping needs it
+        // to have run once.
+        guard.mark_running(task_id);
+        guard.mark_stopped(task_id);
+    }
+}
+
+fn lock(supervisor: &Mutex<Supervisor>) -> MutexGuard<'_, Supervisor> {
+    supervisor
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    use runtime::execution::OrderSide;
+    use runtime::supervisor::TaskLifecycle;
+
+    fn order(qty: f64) -> OrderRequest {
+        OrderRequest {
+            market_id: "btc-updown-15m".to_string(),
+            side: OrderSide::Buy,
+            qty,
+            limit_px: 0.52,
+        }
+    }
+
+    #[tokio::test]
+    async fn execution_task_reports_each_order_and_stops_when_the_loop_hangs_up() {
+        let supervisor: SharedSupervisor = Arc::new(Mutex::new(Supervisor::new()));
+        let mut execution = spawn_execution(&supervisor, VenueExecutors::default(), false);
+
+        for (id, mode) in [(1_u8, ExecutionMode::Paper), (2, ExecutionMode::Live)] {
+            let request = ExecutionRequest {
+                mode,
+                order: order(10.0),
+                context: id,
+            };
+            execution.orders.send(request).await.unwrap();
+        }
+        let filled = execution.results.recv().await.unwrap();
+        assert_eq!(filled.context, 1);
+        assert_eq!(filled.report.unwrap().filled_qty, 10.0);
+        let blocked = execution.results.recv().await.unwrap();
+        assert_eq!(blocked.context, 2);
+        assert_eq!(
+            blocked.report.unwrap_err().reason,
+            "live mode disabled by feature flag"
+        );
+        assert_eq!(
+            lock(&supervisor).state(EXECUTION_TASK_ID),
+            Some(TaskLifecycle::Running)
+        );
+
+        drop(execution.orders);
+        assert!(execution.results.recv().await.is_none());
+        let stopped = time::timeout(Duration::from_secs(5), async {
+            while lock(&supervisor).state(EXECUTION_TASK_ID) != Some(TaskLifecycle::Stopped) {
+                time::sleep(Duration::from_millis(1)).await;
+            }
+        });
+        assert!(stopped.await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn supervise_restarts_a_task_that_panics() {
+        let supervisor: SharedSupervisor = Arc::new(Mutex::new(Supervisor::new()));
+        let runs = Arc::new(AtomicU8::new(0));
+        let counted = runs.clone();
+        supervise(supervisor.clone(), TaskId(9), move || {
+            let run = counted.fetch_add(1, Ordering::SeqCst);
+            async move {
+                assert!(run > 0, "first run fails");
+            }
+        })
+        .await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            lock(&supervisor).state(TaskId(9)),
+            Some(TaskLifecycle::Stopped)
+        );
+    }
+
+    #[tokio::test]
+    async fn poll_task_forwards_values_and_stops_when_the_loop_hangs_up() {
+        let supervisor: SharedSupervisor = Arc::new(Mutex::new(Supervisor::new()));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut calls = 0_u8;
+        let task = tokio::spawn(run_poll_task(
+            "tradingview".to_string(),
+            supervisor.clone(),
+            TaskId(7),
+            ReconnectPolicy::default(),
+            watch::channel(Duration::from_millis(1)).1,
+            tx,
+            move |_| {
+                calls += 1;
+                let value = calls;
+                async move { Some(Ok::<_, VenueFeedError>(value)) }
+            },
+        ));
+
+        assert_eq!(rx.recv().await, Some(PollEvent::Value(1)));
+        assert_eq!(rx.recv().await, Some(PollEvent::Value(2)));
+        assert_eq!(
+            lock(&supervisor).state(TaskId(7)),
+            Some(TaskLifecycle::Running)
+        );
+
+        drop(rx);
+        task.await.unwrap();
+        assert_eq!(
+            lock(&supervisor).state(TaskId(7)),
+            Some(TaskLifecycle::Stopped)
+        );
+    }
+
+    #[tokio::test]
+    async fn poll_task_follows_a_changed_interval() {
+        let supervisor: SharedSupervisor = Arc::new(Mutex::new(Supervisor::new()));
+        let (interval_tx, interval_rx) = watch::channel(Duration::from_secs(3_600));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut calls = 0_u8;
+        tokio::spawn(run_poll_task(
+            "tradingview".to_string(),
+            supervisor,
+            TaskId(8),
+            ReconnectPolicy::default(),
+            interval_rx,
+            tx,
+            move |_| {
+                calls += 1;
+                let value = calls;
+                async move { Some(Ok::<_, VenueFeedError>(value)) }
+            },
+        ));
+
+        assert_eq!(rx.recv().await, Some(PollEvent::Value(1)));
+        interval_tx.send(Duration::from_millis(1)).unwrap();
+        let next = time::timeout(Duration::from_secs(5), rx.recv()).await;
+        assert_eq!(next.ok().flatten(), Some(PollEvent::Value(2)));
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use api::state::{PublishedEvent, RuntimeEvent};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use runtime::live::ReconnectPolicy;
+use sha2::Sha256;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{self, Duration};
+
+use crate::config::WebhookConfig;
+
+const SIGNATURE_HEADER: &str = "X-Lab-Signature";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Posts risk alerts from the event bus to every configured webhook.
+///
+/// Each alert is delivered to each URL on its own task, so a receiver that
+/// is down only delays its own retries. Failed deliveries back off on the
+/// feeds' reconnect schedule and are dropped after `max_attempts`.
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    client: Client,
+    config: WebhookConfig,
+    retry: ReconnectPolicy,
+}
+
+impl WebhookNotifier {
+    pub fn new(client: Client, config: WebhookConfig) -> Self {
+        Self {
+            client,
+            config,
+            retry: ReconnectPolicy {
+                base_ms: 1_000,
+                max_ms: 60_000,
+                jitter_ratio: 0.0,
+                // Webhooks never trip the breaker; `max_attempts` bounds them.
+                breaker_threshold: u32::MAX,
+                breaker_cooldown_ms: 60_000,
+                blocked_cooldown_ms: 60_000,
+            },
+        }
+    }
+
+    /// Forwards alerts until the event bus closes.
+    pub async fn run(self, mut events: broadcast::Receiver<Arc<PublishedEvent>>) {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    eprintln!("webhook notifier missed {skipped} events");
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+            if !matches!(event.event, RuntimeEvent::RiskAlert { .. }) {
+                continue;
+            }
+            let Some(body) = event.json() else {
+                continue;
+            };
+            for url in &self.config.urls {
+                let notifier = self.clone();
+                let url = url.clone();
+                let body = body.clone();
+                tokio::spawn(async move { notifier.deliver(&url, &body).await });
+            }
+        }
+    }
+
+    /// Posts `body` to `url`, retrying with backoff; `true` once a 2xx came
+    /// back.
+    pub async fn deliver(&self, url: &str, body: &str) -> bool {
+        for attempt in 1..=self.config.max_attempts {
+            let mut request = self
+                .client
+                .post(url)
+                .timeout(REQUEST_TIMEOUT)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.to_string());
+            if let Some(secret) = &self.config.secret {
+                request = request.header(SIGNATURE_HEADER, sign(secret, body));
+            }
+
+            let outcome = match request.send().await {
+                Ok(response) if response.status().is_success() => return true,
+                Ok(response) => format!("status {}", response.status()),
+                Err(err) => err.to_string(),
+            };
+            if attempt == self.config.max_attempts {
+                eprintln!("webhook {url} gave up after {attempt} attempts: {outcome}");
+                break;
+            }
+            time::sleep(Duration::from_millis(self.retry.delay_ms(attempt, 0.0))).await;
+        }
+        false
+    }
+}
+
+/// `sha256=<hex>` HMAC of the request body, so receivers can check that an
+/// alert came from this lab.
+pub fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body.as_bytes());
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256={digest}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn signs_the_body_with_hmac_sha256() {
+        assert_eq!(
+            sign("key", "The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_failed_deliveries_with_a_signature() {
+        let received: Arc<Mutex<Vec<Option<String>>>> = Arc::default();
+        let seen = received.clone();
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap| {
+                let seen = seen.clone();
+                async move {
+                    let mut seen = seen.lock().unwrap();
+                    seen.push(
+                        headers
+                            .get(SIGNATURE_HEADER)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_owned),
+                    );
+                    if seen.len() < 2 {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    } else {
+                        StatusCode::NO_CONTENT
+                    }
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut notifier = WebhookNotifier::new(
+            Client::new(),
+            WebhookConfig {
+                urls: Vec::new(),
+                secret: Some("s3cret".to_string()),
+                max_attempts: 3,
+            },
+        );
+        notifier.retry = ReconnectPolicy::new(1, 2, 0.0, u32::MAX, 2).unwrap();
+        let body = r#"{"event_type":"risk_alert","kind":"halt"}"#;
+
+        assert!(notifier.deliver(&format!("http://{addr}/hook"), body).await);
+        {
+            let received = received.lock().unwrap();
+            assert_eq!(received.len(), 2);
+            assert_eq!(received[1].as_deref(), Some(sign("s3cret", body).as_str()));
+        }
+
+        notifier.config.max_attempts = 1;
+        assert!(!notifier.deliver("http://127.0.0.1:9/hook", body).await);
+    }
+}
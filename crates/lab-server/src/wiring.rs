@@ -1,6 +1,12 @@
 use api::state::AppState;
-use axum::{routing::get, Router};
+use axum::{
+    http::{header, HeaderMap, Uri},
+    response::Redirect,
+    routing::get,
+    Router,
+};
 
+#[cfg(test)]
 pub fn build_app() -> Router {
     build_app_with_state(AppState::new())
 }
@@ -17,6 +23,33 @@ async fn healthcheck() -> &'static str {
     "ok"
 }
 
+/// Plain HTTP app that permanently redirects every request to the same host
+/// and path on the HTTPS port.
+pub fn build_https_redirect(https_port: u16) -> Router {
+    Router::new().fallback(move |headers: HeaderMap, uri: Uri| async move {
+        Redirect::permanent(&https_url(&headers, &uri, https_port))
+    })
+}
+
+fn https_url(headers: &HeaderMap, uri: &Uri, https_port: u16) -> String {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .or_else(|| uri.host())
+        .unwrap_or("localhost");
+    // Drop any port from the Host header; IPv6 literals keep their brackets.
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if !port.contains(']') => name,
+        _ => host,
+    };
+    let path = uri.path_and_query().map_or("/", |path| path.as_str());
+    if https_port == 443 {
+        format!("https://{host}{path}")
+    } else {
+        format!("https://{host}:{https_port}{path}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use axum::{
@@ -39,6 +72,38 @@ mod tests {
         assert_eq!(body, "ok");
     }
 
+    #[tokio::test]
+    async fn http_redirect_keeps_host_and_path_on_the_https_port() {
+        let app = super::build_https_redirect(8443);
+
+        let response = app
+            .oneshot(
+                Request::get("/settings?verbose=1")
+                    .header("host", "lab.example:8080")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()["location"],
+            "https://lab.example:8443/settings?verbose=1"
+        );
+
+        let response = super::build_https_redirect(443)
+            .oneshot(
+                Request::get("/")
+                    .header("host", "[::1]:8080")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["location"], "https://[::1]/");
+    }
+
     #[tokio::test]
     async fn server_preserves_api_routes_from_build_app() {
         let app = super::build_app();
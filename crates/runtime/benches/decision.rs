@@ -0,0 +1,200 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use runtime::live::{
+    Asset, BookDepth, BookLevel, BtcMedianTick, LagTriggerGate, PolymarketQuoteTick, QuoteSource,
+};
+use runtime::live_runner::{has_fill, run_strategy_once_gated, JoinedLiveInputs, LagRunParams};
+use runtime::metrics::DecisionLatencyMetrics;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use strategy::{LagDivergenceStrategy, Regime, SizingMode};
+
+/// Evaluations per second the decision path is budgeted for.
+const TARGET_EVALS_PER_SEC: u64 = 10_000;
+const DECISION_SAMPLES: u64 = 10_000;
+const MARKETS: [&str; 4] = [
+    "btc-updown-15m-a",
+    "btc-updown-15m-b",
+    "eth-updown-15m-a",
+    "eth-updown-15m-b",
+];
+
+/// Counts allocations so the bench can show that standing aside never
+/// allocates.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+struct Decider {
+    quotes: Vec<PolymarketQuoteTick>,
+    joined: Option<JoinedLiveInputs>,
+    strategy: LagDivergenceStrategy,
+    gate: LagTriggerGate,
+    params: LagRunParams,
+}
+
+impl Decider {
+    /// `fair_yes_px` against mids of 0.50: equal stands aside, anything past
+    /// the threshold trades on every evaluation.
+    fn new(fair_yes_px: f64) -> Self {
+        let quotes = MARKETS
+            .iter()
+            .map(|market| PolymarketQuoteTick {
+                market_slug: market.to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
+                best_yes_bid: 0.49,
+                best_yes_ask: 0.51,
+                mid_yes: 0.50,
+                ts: 0,
+                depth: BookDepth {
+                    bids: vec![
+                        BookLevel {
+                            px: 0.49,
+                            qty: 50.0,
+                        },
+                        BookLevel {
+                            px: 0.48,
+                            qty: 80.0,
+                        },
+                    ],
+                    asks: vec![
+                        BookLevel {
+                            px: 0.51,
+                            qty: 50.0,
+                        },
+                        BookLevel {
+                            px: 0.52,
+                            qty: 80.0,
+                        },
+                    ],
+                },
+                liquidity_score: None,
+            })
+            .collect();
+        Self {
+            quotes,
+            joined: None,
+            strategy: LagDivergenceStrategy::default(),
+            gate: LagTriggerGate::new(0, 1).expect("gate config is valid"),
+            params: LagRunParams {
+                fair_yes_px,
+                lag_threshold_pct: 0.3,
+                per_trade_risk_fraction: 0.5,
+                starting_equity: 10_000.0,
+                daily_loss_cap_fraction: 0.02,
+                base_qty: 1.0,
+                sizing: SizingMode::Fixed,
+                regime: Regime::Normal,
+                seconds_to_expiry: Some(600.0),
+                market_position_qty: 0.0,
+                max_position_qty: 100.0,
+                size_scale: 1.0,
+                min_touch_qty: 1.0,
+            },
+        }
+    }
+
+    /// One evaluation as the live loop runs it: join the next quote, then
+    /// decide.
+    fn evaluate(&mut self, tick: u64) -> usize {
+        let quote = &self.quotes[tick as usize % self.quotes.len()];
+        let joined = JoinedLiveInputs::refill(
+            &mut self.joined,
+            BtcMedianTick::new(64_000.0, 8.0, 3, tick),
+            quote,
+        );
+        let (intent, events) = run_strategy_once_gated(
+            tick,
+            joined,
+            &mut self.strategy,
+            &self.params,
+            &mut self.gate,
+            tick,
+        );
+        if has_fill(&events) {
+            self.gate.commit(&quote.market_slug, tick);
+        }
+        usize::from(intent.is_some()) + events.len()
+    }
+}
+
+fn bench_decision_latency(c: &mut Criterion) {
+    let budget_nanos = 1_000_000_000 / TARGET_EVALS_PER_SEC;
+
+    let mut idle = Decider::new(0.50);
+    // The first pass over each market sizes the reused buffers.
+    for tick in 0..MARKETS.len() as u64 {
+        black_box(idle.evaluate(tick));
+    }
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for tick in 0..DECISION_SAMPLES {
+        black_box(idle.evaluate(tick));
+    }
+    let idle_allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("decision_idle_allocations={idle_allocations} samples={DECISION_SAMPLES}");
+    assert_eq!(
+        idle_allocations, 0,
+        "evaluations that stand aside must not allocate"
+    );
+
+    for (name, fair_yes_px) in [("idle", 0.50), ("trading", 0.60)] {
+        let mut decider = Decider::new(fair_yes_px);
+        let mut metrics = DecisionLatencyMetrics::new();
+        for tick in 0..DECISION_SAMPLES {
+            let started = Instant::now();
+            let outcome = decider.evaluate(tick);
+            metrics.record_latency_nanos(started.elapsed().as_nanos() as u64);
+            black_box(outcome);
+        }
+        let report = metrics.percentiles().expect("samples were recorded");
+        println!(
+            "decision_{name} budget_nanos={budget_nanos} p50_nanos={} p99_nanos={} max_nanos={} samples={}",
+            report.p50_nanos, report.p99_nanos, report.max_nanos, report.count
+        );
+        assert!(
+            report.p99_nanos < budget_nanos,
+            "{name} decisions miss the {TARGET_EVALS_PER_SEC}/s budget: p99 {}ns",
+            report.p99_nanos
+        );
+    }
+
+    c.bench_function("decision_idle", |b| {
+        let mut decider = Decider::new(0.50);
+        let mut tick = 0;
+        b.iter(|| {
+            tick += 1;
+            black_box(decider.evaluate(tick))
+        });
+    });
+    c.bench_function("decision_trading", |b| {
+        let mut decider = Decider::new(0.60);
+        let mut tick = 0;
+        b.iter(|| {
+            tick += 1;
+            black_box(decider.evaluate(tick))
+        });
+    });
+}
+
+criterion_group!(benches, bench_decision_latency);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use runtime::live::{
+    fuse_predictors, Asset, BookDepth, BookLevel, BtcMedianTick, MedianAggregator,
+    NormalizedBtcTick, PolymarketQuoteTick, PredictorSource, PredictorTick, QuoteSource,
+};
+use runtime::live_runner::{run_paper_live_once, JoinedLiveInputs};
+use runtime::paper_exec::{paper_fill_buy, paper_fill_sell};
+
+/// Spot venues the server aggregates when every feed is enabled.
+const VENUES: [&str; 7] = [
+    "binance", "coinbase", "kraken", "bybit", "okx", "bitstamp", "laggard",
+];
+const PREDICTORS: [&str; 4] = ["tradingview", "cryptoquant", "lstm", "model"];
+const NOW_MS: u64 = 1_000_000;
+
+fn quote(mid_yes: f64) -> PolymarketQuoteTick {
+    PolymarketQuoteTick {
+        market_slug: "btc-updown-15m-a".to_string(),
+        source: QuoteSource::Polymarket,
+        asset: Asset::Btc,
+        best_yes_bid: mid_yes - 0.01,
+        best_yes_ask: mid_yes + 0.01,
+        mid_yes,
+        ts: NOW_MS,
+        depth: BookDepth {
+            bids: vec![BookLevel {
+                px: mid_yes - 0.01,
+                qty: 50.0,
+            }],
+            asks: vec![BookLevel {
+                px: mid_yes + 0.01,
+                qty: 50.0,
+            }],
+        },
+        liquidity_score: None,
+    }
+}
+
+/// One fresh tick per venue around 64k, with the last venue lagging far
+/// enough behind to be dropped as stale.
+fn filled(mut aggregator: MedianAggregator) -> MedianAggregator {
+    for (idx, venue) in VENUES.iter().enumerate() {
+        let lagging = idx == VENUES.len() - 1;
+        aggregator.ingest(NormalizedBtcTick {
+            venue: venue.to_string(),
+            px: 64_000.0 + idx as f64 * 3.5 - if lagging { 400.0 } else { 0.0 },
+            size: 1.0,
+            ts: if lagging { NOW_MS - 10_000 } else { NOW_MS },
+        });
+    }
+    aggregator
+}
+
+fn bench_live_decision(c: &mut Criterion) {
+    // The paper signal leans the mid by the spot spread across venues.
+    for (name, px_spread) in [("hold", 2.0), ("signal", 20.0)] {
+        let joined = JoinedLiveInputs {
+            btc_tick: BtcMedianTick::new(64_000.0, px_spread, 3, NOW_MS),
+            quote_tick: quote(0.50),
+        };
+        assert_eq!(
+            !run_paper_live_once(0, &joined).is_empty(),
+            name == "signal",
+            "{name} inputs should {}emit events",
+            if name == "signal" { "" } else { "not " }
+        );
+        c.bench_function(&format!("run_paper_live_once_{name}"), |b| {
+            let mut tick = 0;
+            b.iter(|| {
+                tick += 1;
+                black_box(run_paper_live_once(tick, black_box(&joined)))
+            });
+        });
+    }
+}
+
+fn bench_median(c: &mut Criterion) {
+    let fixed = filled(MedianAggregator::new(2_000, 200.0).expect("config is valid"));
+    c.bench_function("median_compute_fixed_band", |b| {
+        b.iter(|| black_box(black_box(&fixed).compute()))
+    });
+
+    let robust = filled(MedianAggregator::robust(2_000, 3.0, 5.0).expect("config is valid"));
+    c.bench_function("median_compute_mad_band", |b| {
+        b.iter(|| black_box(black_box(&robust).compute()))
+    });
+}
+
+fn bench_fuse_predictors(c: &mut Criterion) {
+    let ticks: Vec<PredictorTick> = PREDICTORS
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| PredictorTick {
+            source: PredictorSource::parse(label).expect("label is valid"),
+            predicted_yes_px: 0.48 + idx as f64 * 0.02,
+            confidence: 0.5 + idx as f64 * 0.1,
+            ts_ms: NOW_MS - idx as u64 * 500,
+        })
+        .collect();
+    c.bench_function("fuse_predictors", |b| {
+        b.iter(|| black_box(fuse_predictors(black_box(&ticks), NOW_MS)))
+    });
+}
+
+fn bench_paper_fills(c: &mut Criterion) {
+    c.bench_function("paper_fill_buy", |b| {
+        b.iter(|| black_box(paper_fill_buy(black_box(0.51), 10.0, 5.0, 10.0)))
+    });
+    c.bench_function("paper_fill_sell", |b| {
+        b.iter(|| black_box(paper_fill_sell(black_box(0.49), 10.0, 5.0, 10.0)))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_live_decision,
+    bench_median,
+    bench_fuse_predictors,
+    bench_paper_fills
+);
+criterion_main!(benches);
@@ -0,0 +1,229 @@
+use crate::live::QuoteSource;
+use crate::positions::{to_f64, Position};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketConfigError {
+    /// Stop and target distances must be finite and strictly between 0 and 1.
+    InvalidDistance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketKind {
+    StopLoss,
+    TakeProfit,
+}
+
+impl BracketKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StopLoss => "stop_loss",
+            Self::TakeProfit => "take_profit",
+        }
+    }
+}
+
+/// Distances from the average entry, in YES price, at which an open paper
+/// position is flattened.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BracketConfig {
+    stop_distance: f64,
+    target_distance: f64,
+}
+
+impl BracketConfig {
+    pub fn new(stop_distance: f64, target_distance: f64) -> Result<Self, BracketConfigError> {
+        let valid = |distance: f64| distance.is_finite() && distance > 0.0 && distance < 1.0;
+        if !valid(stop_distance) || !valid(target_distance) {
+            return Err(BracketConfigError::InvalidDistance);
+        }
+
+        Ok(Self {
+            stop_distance,
+            target_distance,
+        })
+    }
+
+    pub fn stop_distance(&self) -> f64 {
+        self.stop_distance
+    }
+
+    pub fn target_distance(&self) -> f64 {
+        self.target_distance
+    }
+}
+
+impl Default for BracketConfig {
+    fn default() -> Self {
+        Self {
+            stop_distance: 0.10,
+            target_distance: 0.20,
+        }
+    }
+}
+
+/// Stop and target armed on one open position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bracket {
+    pub market_id: String,
+    pub source: QuoteSource,
+    /// Signed YES shares covered; negative is short YES.
+    pub qty: f64,
+    pub entry_px: f64,
+    pub stop_px: f64,
+    pub target_px: f64,
+}
+
+/// A bracket whose stop or target the market reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BracketTrigger {
+    pub kind: BracketKind,
+    pub bracket: Bracket,
+    /// Mid that crossed the level.
+    pub trigger_px: f64,
+}
+
+impl BracketTrigger {
+    /// Signed YES shares that flatten the position.
+    pub fn close_qty(&self) -> f64 {
+        -self.bracket.qty
+    }
+}
+
+/// Stop-loss and take-profit levels for every open paper position.
+///
+/// [`sync`](Self::sync) re-arms a market's bracket around the position's
+/// average entry after each fill, and drops it once the market is flat;
+/// [`check`](Self::check) fires at most once per bracket.
+#[derive(Debug, Clone, Default)]
+pub struct BracketBook {
+    config: BracketConfig,
+    brackets: Vec<Bracket>,
+}
+
+impl BracketBook {
+    pub fn new(config: BracketConfig) -> Self {
+        Self {
+            config,
+            brackets: Vec::new(),
+        }
+    }
+
+    /// Arms, moves, or clears the bracket for `market_id` to match
+    /// `position`, which is `None` once the market is flat.
+    pub fn sync(&mut self, market_id: &str, position: Option<&Position>) {
+        self.brackets
+            .retain(|bracket| bracket.market_id != market_id);
+        let Some(position) = position.filter(|position| !position.is_flat()) else {
+            return;
+        };
+
+        let qty = to_f64(position.qty);
+        let entry_px = to_f64(position.avg_px());
+        let (stop_px, target_px) = if qty > 0.0 {
+            (
+                entry_px - self.config.stop_distance,
+                entry_px + self.config.target_distance,
+            )
+        } else {
+            (
+                entry_px + self.config.stop_distance,
+                entry_px - self.config.target_distance,
+            )
+        };
+        self.brackets.push(Bracket {
+            market_id: market_id.to_string(),
+            source: position.source,
+            qty,
+            entry_px,
+            stop_px,
+            target_px,
+        });
+    }
+
+    /// Compares `mid_yes` against the bracket on `market_id` and, when the
+    /// stop or target is reached, disarms and returns it.
+    pub fn check(&mut self, market_id: &str, mid_yes: f64) -> Option<BracketTrigger> {
+        if !mid_yes.is_finite() {
+            return None;
+        }
+        let idx = self
+            .brackets
+            .iter()
+            .position(|bracket| bracket.market_id == market_id)?;
+        let bracket = &self.brackets[idx];
+
+        let long = bracket.qty > 0.0;
+        let kind = if (long && mid_yes <= bracket.stop_px) || (!long && mid_yes >= bracket.stop_px)
+        {
+            BracketKind::StopLoss
+        } else if (long && mid_yes >= bracket.target_px) || (!long && mid_yes <= bracket.target_px)
+        {
+            BracketKind::TakeProfit
+        } else {
+            return None;
+        };
+
+        Some(BracketTrigger {
+            kind,
+            bracket: self.brackets.remove(idx),
+            trigger_px: mid_yes,
+        })
+    }
+
+    pub fn brackets(&self) -> &[Bracket] {
+        &self.brackets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::*;
+    use crate::positions::PositionManager;
+
+    fn position(qty: f64, px: f64) -> Position {
+        let mut book = PositionManager::new(Decimal::from(100));
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", qty, px);
+        book.position("btc-1215").unwrap().clone()
+    }
+
+    #[test]
+    fn long_bracket_fires_once_at_the_stop() {
+        let mut brackets = BracketBook::default();
+        brackets.sync("btc-1215", Some(&position(10.0, 0.50)));
+
+        assert_eq!(brackets.check("btc-1215", 0.45), None);
+        let trigger = brackets.check("btc-1215", 0.39).unwrap();
+
+        assert_eq!(trigger.kind, BracketKind::StopLoss);
+        assert!((trigger.bracket.stop_px - 0.40).abs() < 1e-12);
+        assert_eq!(trigger.close_qty(), -10.0);
+        assert_eq!(brackets.check("btc-1215", 0.10), None);
+    }
+
+    #[test]
+    fn short_bracket_takes_profit_when_yes_falls() {
+        let mut brackets = BracketBook::new(BracketConfig::new(0.05, 0.10).unwrap());
+        brackets.sync("btc-1215", Some(&position(-4.0, 0.60)));
+
+        assert_eq!(brackets.check("btc-1215", 0.62), None);
+        let trigger = brackets.check("btc-1215", 0.50).unwrap();
+
+        assert_eq!(trigger.kind, BracketKind::TakeProfit);
+        assert_eq!(trigger.close_qty(), 4.0);
+    }
+
+    #[test]
+    fn flat_market_clears_its_bracket() {
+        let mut brackets = BracketBook::default();
+        brackets.sync("btc-1215", Some(&position(1.0, 0.50)));
+        brackets.sync("btc-1215", None);
+
+        assert!(brackets.brackets().is_empty());
+        assert_eq!(
+            BracketConfig::new(0.0, 0.2),
+            Err(BracketConfigError::InvalidDistance)
+        );
+    }
+}
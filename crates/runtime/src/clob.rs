@@ -0,0 +1,984 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::URL_SAFE;
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use k256::ecdsa::SigningKey;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::execution::{
+    ClobCredentials, ExecutionAdapter, ExecutionError, ExecutionFuture, OrderReport, OrderRequest,
+    OrderSide, OrderStatus,
+};
+use crate::paper_exec::PaperExecError;
+
+pub const CLOB_URL: &str = "https://clob.polymarket.com";
+pub const CLOB_USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
+const GAMMA_MARKETS_URL: &str = "https://gamma-api.polymarket.com/markets";
+
+const POLYGON_CHAIN_ID: u64 = 137;
+const CTF_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+const NEG_RISK_CTF_EXCHANGE: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+const DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const ORDER_TYPE: &str = "Order(uint256 salt,address maker,address signer,address taker,\
+uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,\
+uint256 feeRateBps,uint8 side,uint8 signatureType)";
+const DOMAIN_NAME: &str = "Polymarket CTF Exchange";
+const DOMAIN_VERSION: &str = "1";
+/// USDC and outcome shares both have six decimals on chain.
+const AMOUNT_UNITS: f64 = 1_000_000.0;
+const DEFAULT_TICK_SIZE: f64 = 0.01;
+/// Finished orders are forgotten once more than this many are tracked.
+const MAX_TRACKED_ORDERS: usize = 1_000;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const WS_PING_INTERVAL: Duration = Duration::from_secs(10);
+const MIN_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClobConfigError {
+    InvalidPrivateKey,
+    HttpClient,
+}
+
+/// Signs orders for the address behind a secp256k1 key.
+pub struct ClobSigner {
+    key: SigningKey,
+    address: [u8; 20],
+}
+
+impl ClobSigner {
+    pub fn from_hex(private_key: &str) -> Option<Self> {
+        let bytes = decode_hex(private_key)?;
+        let key = SigningKey::from_slice(&bytes).ok()?;
+        let point = key.verifying_key().to_encoded_point(false);
+        let hash = keccak256(&point.as_bytes()[1..]);
+        let mut address = [0; 20];
+        address.copy_from_slice(&hash[12..]);
+        Some(Self { key, address })
+    }
+
+    /// EIP-55 checksummed address.
+    pub fn address(&self) -> String {
+        checksum_address(&self.address)
+    }
+
+    /// 65-byte `r || s || v` signature of `digest`, with `v` as 27 or 28.
+    fn sign_digest(&self, digest: &[u8; 32]) -> Option<String> {
+        let (signature, recovery_id) = self.key.sign_prehash_recoverable(digest).ok()?;
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(27 + recovery_id.to_byte());
+        Some(format!("0x{}", encode_hex(&bytes)))
+    }
+}
+
+/// The CTF exchange's `Order` struct, in on-chain units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ClobOrder {
+    salt: u64,
+    maker: [u8; 20],
+    signer: [u8; 20],
+    token_id: String,
+    maker_amount: u64,
+    taker_amount: u64,
+    fee_rate_bps: u64,
+    side: OrderSide,
+}
+
+impl ClobOrder {
+    fn side_index(&self) -> u64 {
+        match self.side {
+            OrderSide::Buy => 0,
+            OrderSide::Sell => 1,
+        }
+    }
+
+    fn struct_hash(&self) -> Option<[u8; 32]> {
+        let mut encoded = keccak256(ORDER_TYPE.as_bytes()).to_vec();
+        encoded.extend(uint_word(self.salt));
+        encoded.extend(address_word(&self.maker));
+        encoded.extend(address_word(&self.signer));
+        // Open to any taker.
+        encoded.extend(address_word(&[0; 20]));
+        encoded.extend(decimal_word(&self.token_id)?);
+        encoded.extend(uint_word(self.maker_amount));
+        encoded.extend(uint_word(self.taker_amount));
+        // No expiration and nonce 0.
+        encoded.extend(uint_word(0));
+        encoded.extend(uint_word(0));
+        encoded.extend(uint_word(self.fee_rate_bps));
+        encoded.extend(uint_word(self.side_index()));
+        // Signed directly by an EOA.
+        encoded.extend(uint_word(0));
+        Some(keccak256(&encoded))
+    }
+
+    /// EIP-712 digest of the order for the given exchange contract.
+    fn digest(&self, exchange: &[u8; 20]) -> Option<[u8; 32]> {
+        let mut encoded = vec![0x19, 0x01];
+        encoded.extend(domain_separator(exchange));
+        encoded.extend(self.struct_hash()?);
+        Some(keccak256(&encoded))
+    }
+
+    /// `POST /order` payload field for the signed order.
+    fn to_json(&self, signature: &str) -> Value {
+        json!({
+            "salt": self.salt,
+            "maker": checksum_address(&self.maker),
+            "signer": checksum_address(&self.signer),
+            "taker": checksum_address(&[0; 20]),
+            "tokenId": self.token_id,
+            "makerAmount": self.maker_amount.to_string(),
+            "takerAmount": self.taker_amount.to_string(),
+            "expiration": "0",
+            "nonce": "0",
+            "feeRateBps": self.fee_rate_bps.to_string(),
+            "side": match self.side {
+                OrderSide::Buy => "BUY",
+                OrderSide::Sell => "SELL",
+            },
+            "signatureType": 0,
+            "signature": signature,
+        })
+    }
+}
+
+fn domain_separator(exchange: &[u8; 20]) -> [u8; 32] {
+    let mut encoded = keccak256(DOMAIN_TYPE.as_bytes()).to_vec();
+    encoded.extend(keccak256(DOMAIN_NAME.as_bytes()));
+    encoded.extend(keccak256(DOMAIN_VERSION.as_bytes()));
+    encoded.extend(uint_word(POLYGON_CHAIN_ID));
+    encoded.extend(address_word(exchange));
+    keccak256(&encoded)
+}
+
+/// Tradable YES outcome of a market, as listed by Gamma.
+#[derive(Debug, Clone, PartialEq)]
+struct ClobMarket {
+    yes_token: String,
+    neg_risk: bool,
+    tick_size: f64,
+    /// Fee rate the venue charges takers, which signed orders must carry.
+    taker_fee_bps: u64,
+}
+
+impl ClobMarket {
+    fn from_gamma(payload: &str, slug: &str) -> Option<Self> {
+        let markets: Vec<Value> = serde_json::from_str(payload).ok()?;
+        let market = markets.iter().find(|market| market["slug"] == slug)?;
+        let outcomes = string_list(&market["outcomes"]);
+        let token_ids = string_list(&market["clobTokenIds"]);
+        let yes_idx = outcomes
+            .iter()
+            .position(|outcome| outcome.eq_ignore_ascii_case("yes"))
+            .unwrap_or(0);
+        Some(Self {
+            yes_token: token_ids.get(yes_idx)?.clone(),
+            neg_risk: market["negRisk"].as_bool().unwrap_or(false),
+            tick_size: market["orderPriceMinTickSize"]
+                .as_f64()
+                .filter(|tick| *tick > 0.0 && *tick < 1.0)
+                .unwrap_or(DEFAULT_TICK_SIZE),
+            taker_fee_bps: market["takerBaseFee"].as_u64().unwrap_or(0),
+        })
+    }
+
+    fn exchange(&self) -> [u8; 20] {
+        let exchange = if self.neg_risk {
+            NEG_RISK_CTF_EXCHANGE
+        } else {
+            CTF_EXCHANGE
+        };
+        decode_address(exchange).expect("exchange addresses are valid")
+    }
+}
+
+/// Order reports kept current by the user WebSocket channel.
+pub type SharedOrderReports = Arc<Mutex<HashMap<String, OrderReport>>>;
+
+fn track(reports: &mut HashMap<String, OrderReport>, report: OrderReport) {
+    reports.insert(report.order_id.clone(), report);
+    if reports.len() > MAX_TRACKED_ORDERS {
+        reports.retain(|_, report| !report.status.is_final());
+    }
+}
+
+/// An order signed for the CLOB, with the terms it was signed at.
+struct SignedOrder {
+    salt: u64,
+    /// `POST /order` body.
+    body: Value,
+    px: f64,
+    size: f64,
+    /// Taker fee in USDC if the order matches in full.
+    est_fee: f64,
+}
+
+/// [`ExecutionAdapter`] for the Polymarket CLOB.
+///
+/// Orders are EIP-712 signed by the configured EOA and sent fill-or-kill, so
+/// `place` returns with each order either filled or cancelled. Requests are
+/// authenticated with the L2 API key; `query` answers from the user
+/// WebSocket channel when it has seen the order and asks the REST API
+/// otherwise.
+///
+/// A dry-run executor signs orders the same way but never sends them: each
+/// one is reported filled in full at its signed price and estimated fee,
+/// with the request it would have sent.
+pub struct ClobExecutor {
+    http: reqwest::Client,
+    signer: ClobSigner,
+    credentials: ClobCredentials,
+    markets: HashMap<String, ClobMarket>,
+    reports: SharedOrderReports,
+    dry_run: bool,
+}
+
+impl ClobExecutor {
+    pub fn new(credentials: ClobCredentials) -> Result<Self, ClobConfigError> {
+        let signer = ClobSigner::from_hex(&credentials.private_key)
+            .ok_or(ClobConfigError::InvalidPrivateKey)?;
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|_| ClobConfigError::HttpClient)?;
+        Ok(Self {
+            http,
+            signer,
+            credentials,
+            markets: HashMap::new(),
+            reports: SharedOrderReports::default(),
+            dry_run: false,
+        })
+    }
+
+    /// An executor that signs orders but never sends them.
+    pub fn dry_run(credentials: ClobCredentials) -> Result<Self, ClobConfigError> {
+        Ok(Self {
+            dry_run: true,
+            ..Self::new(credentials)?
+        })
+    }
+
+    pub fn address(&self) -> String {
+        self.signer.address()
+    }
+
+    /// Spawns the user channel that keeps order reports current.
+    pub fn spawn_user_stream(&self) {
+        tokio::spawn(run_user_stream(
+            self.credentials.clone(),
+            self.reports.clone(),
+        ));
+    }
+
+    async fn market(&mut self, slug: &str) -> Result<ClobMarket, ExecutionError> {
+        if let Some(market) = self.markets.get(slug) {
+            return Ok(market.clone());
+        }
+        let payload = self
+            .http
+            .get(GAMMA_MARKETS_URL)
+            .query(&[("slug", slug)])
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(transport_error)?
+            .text()
+            .await
+            .map_err(transport_error)?;
+        let market = ClobMarket::from_gamma(&payload, slug)
+            .ok_or_else(|| ExecutionError::UnknownMarket(slug.to_string()))?;
+        self.markets.insert(slug.to_string(), market.clone());
+        Ok(market)
+    }
+
+    /// Sends an L2-authenticated request and returns its JSON body.
+    async fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, ExecutionError> {
+        let body = body.map(|body| body.to_string()).unwrap_or_default();
+        let timestamp = unix_secs().to_string();
+        let signature = l2_signature(
+            &self.credentials.api_secret,
+            &timestamp,
+            method.as_str(),
+            path,
+            &body,
+        )
+        .ok_or_else(|| ExecutionError::Rejected("API secret is not base64url".to_string()))?;
+
+        let mut request = self
+            .http
+            .request(method, format!("{CLOB_URL}{path}"))
+            .header("POLY_ADDRESS", self.signer.address())
+            .header("POLY_SIGNATURE", signature)
+            .header("POLY_TIMESTAMP", timestamp)
+            .header("POLY_API_KEY", &self.credentials.api_key)
+            .header("POLY_PASSPHRASE", &self.credentials.api_passphrase);
+        if !body.is_empty() {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body);
+        }
+        let response = request.send().await.map_err(transport_error)?;
+        let status = response.status();
+        let payload: Value = response.json().await.map_err(transport_error)?;
+        if !status.is_success() {
+            let reason = payload["error"]
+                .as_str()
+                .or(payload["errorMsg"].as_str())
+                .map_or_else(|| format!("HTTP {status}"), str::to_string);
+            return Err(ExecutionError::Rejected(reason));
+        }
+        Ok(payload)
+    }
+
+    async fn sign_order(&mut self, order: &OrderRequest) -> Result<SignedOrder, ExecutionError> {
+        let market = self.market(&order.market_id).await?;
+        let (px, size) = order_terms(order, market.tick_size)?;
+        let shares = (size * AMOUNT_UNITS).round() as u64;
+        // The CLOB takes USDC amounts to four decimals.
+        let usdc = ((px * size * 10_000.0).round() as u64) * 100;
+        let (maker_amount, taker_amount) = match order.side {
+            OrderSide::Buy => (usdc, shares),
+            OrderSide::Sell => (shares, usdc),
+        };
+        let clob_order = ClobOrder {
+            // Kept below 2^53 so the JSON number survives a double.
+            salt: fastrand::u64(..1 << 53),
+            maker: self.signer.address,
+            signer: self.signer.address,
+            token_id: market.yes_token.clone(),
+            maker_amount,
+            taker_amount,
+            fee_rate_bps: market.taker_fee_bps,
+            side: order.side,
+        };
+        let signature = clob_order
+            .digest(&market.exchange())
+            .and_then(|digest| self.signer.sign_digest(&digest))
+            .ok_or_else(|| ExecutionError::Rejected("order could not be signed".to_string()))?;
+
+        let body = json!({
+            "order": clob_order.to_json(&signature),
+            "owner": self.credentials.api_key,
+            "orderType": "FOK",
+        });
+        Ok(SignedOrder {
+            salt: clob_order.salt,
+            body,
+            px,
+            size,
+            est_fee: taker_fee(market.taker_fee_bps, px, size),
+        })
+    }
+
+    async fn place_order(&mut self, order: &OrderRequest) -> Result<OrderReport, ExecutionError> {
+        let signed = self.sign_order(order).await?;
+        let report = if self.dry_run {
+            dry_run_report(&signed)
+        } else {
+            let response = self
+                .request(reqwest::Method::POST, "/order", Some(signed.body))
+                .await?;
+            placement_report(&response, order.side, signed.size)?
+        };
+        track(&mut self.lock_reports(), report.clone());
+        Ok(report)
+    }
+
+    async fn cancel_order(&mut self, order_id: &str) -> Result<OrderReport, ExecutionError> {
+        if self.dry_run {
+            return self.query_order(order_id).await;
+        }
+        let response = self
+            .request(
+                reqwest::Method::DELETE,
+                "/order",
+                Some(json!({ "orderID": order_id })),
+            )
+            .await?;
+        if let Some(reason) = response["not_canceled"][order_id].as_str() {
+            return Err(ExecutionError::Rejected(reason.to_string()));
+        }
+        self.query_order(order_id).await
+    }
+
+    async fn query_order(&mut self, order_id: &str) -> Result<OrderReport, ExecutionError> {
+        if let Some(report) = self
+            .lock_reports()
+            .get(order_id)
+            .filter(|report| report.status.is_final())
+        {
+            return Ok(report.clone());
+        }
+        if self.dry_run {
+            return Err(ExecutionError::UnknownOrder(order_id.to_string()));
+        }
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/data/order/{order_id}"),
+                None,
+            )
+            .await?;
+        let report = order_report(&response)
+            .ok_or_else(|| ExecutionError::UnknownOrder(order_id.to_string()))?;
+        track(&mut self.lock_reports(), report.clone());
+        Ok(report)
+    }
+
+    fn lock_reports(&self) -> MutexGuard<'_, HashMap<String, OrderReport>> {
+        self.reports
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ExecutionAdapter for ClobExecutor {
+    fn name(&self) -> &str {
+        if self.dry_run {
+            "polymarket-clob-dry-run"
+        } else {
+            "polymarket-clob"
+        }
+    }
+
+    fn place(
+        &mut self,
+        order: &OrderRequest,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>> {
+        let order = order.clone();
+        Box::pin(async move { self.place_order(&order).await })
+    }
+
+    fn cancel(
+        &mut self,
+        order_id: &str,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>> {
+        let order_id = order_id.to_string();
+        Box::pin(async move { self.cancel_order(&order_id).await })
+    }
+
+    fn query(
+        &mut self,
+        order_id: &str,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>> {
+        let order_id = order_id.to_string();
+        Box::pin(async move { self.query_order(&order_id).await })
+    }
+}
+
+/// The order's price on the market's tick grid, rounded away from the book
+/// so it never crosses further than asked, and its size in whole cents of a
+/// share.
+fn order_terms(order: &OrderRequest, tick_size: f64) -> Result<(f64, f64), ExecutionError> {
+    if !order.limit_px.is_finite() || !(0.0..=1.0).contains(&order.limit_px) {
+        return Err(ExecutionError::InvalidOrder(PaperExecError::InvalidPrice));
+    }
+    let ticks = order.limit_px / tick_size;
+    // Guards the rounding against float noise such as 0.58 / 0.01.
+    let ticks = match order.side {
+        OrderSide::Buy => (ticks + 1e-9).floor(),
+        OrderSide::Sell => (ticks - 1e-9).ceil(),
+    };
+    let px = (ticks * tick_size).clamp(tick_size, 1.0 - tick_size);
+    let size = (order.qty * 100.0 + 1e-9).floor() / 100.0;
+    if !size.is_finite() || size <= 0.0 {
+        return Err(ExecutionError::InvalidOrder(
+            PaperExecError::InvalidQuantity,
+        ));
+    }
+    Ok((px, size))
+}
+
+/// Polymarket's taker fee for `size` shares at `px`, charged on the cheaper
+/// side of the book.
+fn taker_fee(fee_rate_bps: u64, px: f64, size: f64) -> f64 {
+    fee_rate_bps as f64 / 10_000.0 * px.min(1.0 - px) * size
+}
+
+/// Report for a signed order that was never sent, as if it matched in full.
+fn dry_run_report(signed: &SignedOrder) -> OrderReport {
+    OrderReport {
+        order_id: format!("dry-run-{}", signed.salt),
+        status: OrderStatus::Filled,
+        filled_qty: signed.size,
+        avg_fill_px: Some(signed.px),
+        fee: signed.est_fee,
+        unsent_request: Some(signed.body.to_string()),
+    }
+}
+
+/// Report for a fill-or-kill placement: matched in full, or not at all.
+fn placement_report(
+    response: &Value,
+    side: OrderSide,
+    size: f64,
+) -> Result<OrderReport, ExecutionError> {
+    let error = response["errorMsg"].as_str().unwrap_or_default();
+    if response["success"] == false || !error.is_empty() {
+        return Err(ExecutionError::Rejected(error.to_string()));
+    }
+    let order_id = response["orderID"]
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| ExecutionError::Transport("placement without an order id".to_string()))?;
+
+    let matched = response["status"]
+        .as_str()
+        .is_some_and(|status| status.eq_ignore_ascii_case("matched"));
+    if !matched {
+        return Ok(OrderReport {
+            order_id: order_id.to_string(),
+            status: OrderStatus::Cancelled,
+            filled_qty: 0.0,
+            avg_fill_px: None,
+            fee: 0.0,
+            unsent_request: None,
+        });
+    }
+    // Buys make USDC and take shares; sells the other way round.
+    let making = decimal_field(&response["makingAmount"]);
+    let taking = decimal_field(&response["takingAmount"]);
+    let (usdc, shares) = match side {
+        OrderSide::Buy => (making, taking),
+        OrderSide::Sell => (taking, making),
+    };
+    let filled_qty = shares.filter(|shares| *shares > 0.0).unwrap_or(size);
+    Ok(OrderReport {
+        order_id: order_id.to_string(),
+        status: OrderStatus::Filled,
+        filled_qty,
+        avg_fill_px: usdc.map(|usdc| usdc / filled_qty),
+        fee: 0.0,
+        unsent_request: None,
+    })
+}
+
+/// Reads an order as returned by `GET /data/order/{id}` or pushed on the
+/// user channel.
+fn order_report(order: &Value) -> Option<OrderReport> {
+    let order_id = order["id"].as_str()?.to_string();
+    let original = decimal_field(&order["original_size"])?;
+    let matched = decimal_field(&order["size_matched"]).unwrap_or(0.0);
+    let status = order["status"].as_str().unwrap_or_default();
+    let cancelled = status.eq_ignore_ascii_case("canceled")
+        || order["type"]
+            .as_str()
+            .is_some_and(|kind| kind.eq_ignore_ascii_case("cancellation"));
+
+    let status = if cancelled {
+        OrderStatus::Cancelled
+    } else if matched >= original {
+        OrderStatus::Filled
+    } else if matched > 0.0 {
+        OrderStatus::PartiallyFilled
+    } else {
+        OrderStatus::Open
+    };
+    Some(OrderReport {
+        order_id,
+        status,
+        filled_qty: matched,
+        avg_fill_px: decimal_field(&order["price"]).filter(|_| matched > 0.0),
+        fee: 0.0,
+        unsent_request: None,
+    })
+}
+
+/// Applies the order updates in one user channel frame.
+fn apply_user_frame(frame: &str, reports: &SharedOrderReports) {
+    let Ok(payload) = serde_json::from_str::<Value>(frame) else {
+        return;
+    };
+    let events = match payload {
+        Value::Array(events) => events,
+        event => vec![event],
+    };
+    let mut reports = reports
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    for event in events.iter().filter(|event| event["event_type"] == "order") {
+        if let Some(report) = order_report(event) {
+            track(&mut reports, report);
+        }
+    }
+}
+
+/// Follows the account's orders on the user channel until the process
+/// exits, reconnecting with backoff from 1s up to 30s.
+pub async fn run_user_stream(credentials: ClobCredentials, reports: SharedOrderReports) {
+    let subscribe = json!({
+        "auth": {
+            "apiKey": credentials.api_key,
+            "secret": credentials.api_secret,
+            "passphrase": credentials.api_passphrase,
+        },
+        "markets": [],
+        "type": "user",
+    })
+    .to_string();
+    let mut backoff = MIN_RECONNECT_BACKOFF;
+    loop {
+        if let Ok((mut socket, _)) = connect_async(CLOB_USER_WS_URL).await {
+            if socket.send(Message::Text(subscribe.clone())).await.is_ok() {
+                backoff = MIN_RECONNECT_BACKOFF;
+                let mut ping = tokio::time::interval(WS_PING_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = ping.tick() => {
+                            if socket.send(Message::Text("PING".to_string())).await.is_err() {
+                                break;
+                            }
+                        }
+                        frame = socket.next() => match frame {
+                            Some(Ok(Message::Text(text))) => apply_user_frame(&text, &reports),
+                            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                            Some(Ok(_)) => {}
+                        },
+                    }
+                }
+            }
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+    }
+}
+
+/// `POLY_SIGNATURE` header: base64url HMAC-SHA256 of the request, keyed by
+/// the decoded API secret.
+fn l2_signature(
+    secret: &str,
+    timestamp: &str,
+    method: &str,
+    path: &str,
+    body: &str,
+) -> Option<String> {
+    let key = URL_SAFE.decode(secret).ok()?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key).ok()?;
+    mac.update(format!("{timestamp}{method}{path}{body}").as_bytes());
+    Some(URL_SAFE.encode(mac.finalize().into_bytes()))
+}
+
+fn transport_error(err: reqwest::Error) -> ExecutionError {
+    ExecutionError::Transport(err.to_string())
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+fn uint_word(value: u64) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn address_word(address: &[u8; 20]) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[12..].copy_from_slice(address);
+    word
+}
+
+/// Big-endian `uint256` of a decimal string, such as a CLOB token id.
+fn decimal_word(value: &str) -> Option<[u8; 32]> {
+    if value.is_empty() {
+        return None;
+    }
+    let mut word = [0u8; 32];
+    for digit in value.chars() {
+        let mut carry = digit.to_digit(10)?;
+        for byte in word.iter_mut().rev() {
+            let next = u32::from(*byte) * 10 + carry;
+            *byte = next as u8;
+            carry = next >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(word)
+}
+
+/// A decimal that the CLOB may send as a string or a number.
+fn decimal_field(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(text) => text.parse().ok(),
+        other => other.as_f64(),
+    }
+}
+
+/// A list Gamma sends either as JSON or as a JSON-encoded string.
+fn string_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(text) => serde_json::from_str(text).unwrap_or_default(),
+        other => serde_json::from_value(other.clone()).unwrap_or_default(),
+    }
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim();
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(digits.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+fn decode_address(value: &str) -> Option<[u8; 20]> {
+    decode_hex(value)?.try_into().ok()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn checksum_address(address: &[u8; 20]) -> String {
+    let lower = encode_hex(address);
+    let hash = keccak256(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(idx, ch)| {
+            let nibble = (hash[idx / 2] >> if idx % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 {
+                ch.to_ascii_uppercase()
+            } else {
+                ch
+            }
+        })
+        .collect();
+    format!("0x{checksummed}")
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+    use super::*;
+
+    const KEY_ONE: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+    fn order(side: OrderSide) -> ClobOrder {
+        let signer = ClobSigner::from_hex(KEY_ONE).unwrap();
+        ClobOrder {
+            salt: 42,
+            maker: signer.address,
+            signer: signer.address,
+            token_id:
+                "71321045679252212594626385532706912750332728571942532289631379312455583992563"
+                    .to_string(),
+            maker_amount: 5_000_000,
+            taker_amount: 10_000_000,
+            fee_rate_bps: 0,
+            side,
+        }
+    }
+
+    #[test]
+    fn derives_checksummed_addresses_from_keys() {
+        let signer = ClobSigner::from_hex(KEY_ONE).unwrap();
+        assert_eq!(
+            signer.address(),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+        assert!(ClobSigner::from_hex("0x1234").is_none());
+        assert!(ClobSigner::from_hex(&"0".repeat(64)).is_none());
+    }
+
+    #[test]
+    fn encodes_eip712_words() {
+        assert_eq!(
+            encode_hex(&keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(decimal_word("258").unwrap()[30..], [1, 2]);
+        assert_eq!(
+            decimal_word(&u64::MAX.to_string()),
+            Some(uint_word(u64::MAX))
+        );
+        assert!(decimal_word("12a").is_none());
+        assert!(decimal_word(&"9".repeat(80)).is_none());
+    }
+
+    #[test]
+    fn signed_orders_recover_to_the_signer_for_their_exchange() {
+        let signer = ClobSigner::from_hex(KEY_ONE).unwrap();
+        let order = order(OrderSide::Buy);
+        let exchange = decode_address(CTF_EXCHANGE).unwrap();
+        let digest = order.digest(&exchange).unwrap();
+        let signature = decode_hex(&signer.sign_digest(&digest).unwrap()).unwrap();
+        assert_eq!(signature.len(), 65);
+
+        let recovered = VerifyingKey::recover_from_prehash(
+            &digest,
+            &Signature::from_slice(&signature[..64]).unwrap(),
+            RecoveryId::from_byte(signature[64] - 27).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(&recovered, signer.key.verifying_key());
+
+        let neg_risk = decode_address(NEG_RISK_CTF_EXCHANGE).unwrap();
+        assert_ne!(order.digest(&neg_risk), Some(digest));
+        assert_ne!(
+            order.struct_hash(),
+            self::order(OrderSide::Sell).struct_hash()
+        );
+        let json = order.to_json("0xsig");
+        assert_eq!(json["side"], "BUY");
+        assert_eq!(json["makerAmount"], "5000000");
+        let with_fee = ClobOrder {
+            fee_rate_bps: 200,
+            ..order.clone()
+        };
+        assert_ne!(with_fee.struct_hash(), order.struct_hash());
+        assert_eq!(with_fee.to_json("0xsig")["feeRateBps"], "200");
+    }
+
+    #[test]
+    fn dry_runs_report_the_unsent_request_and_estimated_fee() {
+        assert!((taker_fee(200, 0.8, 10.0) - 0.04).abs() < 1e-12);
+        assert_eq!(taker_fee(0, 0.5, 10.0), 0.0);
+
+        let signed = SignedOrder {
+            salt: 42,
+            body: json!({"order": order(OrderSide::Buy).to_json("0xsig"), "orderType": "FOK"}),
+            px: 0.5,
+            size: 10.0,
+            est_fee: 0.1,
+        };
+        let report = dry_run_report(&signed);
+        assert_eq!(report.order_id, "dry-run-42");
+        assert_eq!(report.status, OrderStatus::Filled);
+        assert_eq!(report.filled_qty, 10.0);
+        assert_eq!(report.avg_fill_px, Some(0.5));
+        assert_eq!(report.fee, 0.1);
+        let sent: Value = serde_json::from_str(&report.unsent_request.unwrap()).unwrap();
+        assert_eq!(sent, signed.body);
+    }
+
+    #[test]
+    fn rounds_prices_away_from_the_book_and_sizes_down() {
+        let request = |side, limit_px, qty| OrderRequest {
+            market_id: "btc-a".to_string(),
+            side,
+            qty,
+            limit_px,
+        };
+        let (px, size) = order_terms(&request(OrderSide::Buy, 0.587, 10.009), 0.01).unwrap();
+        assert!((px - 0.58).abs() < 1e-12);
+        assert!((size - 10.0).abs() < 1e-12);
+        let (px, _) = order_terms(&request(OrderSide::Sell, 0.581, 1.0), 0.01).unwrap();
+        assert!((px - 0.59).abs() < 1e-12);
+        let (px, _) = order_terms(&request(OrderSide::Buy, 0.58, 1.0), 0.01).unwrap();
+        assert!((px - 0.58).abs() < 1e-12);
+        assert_eq!(
+            order_terms(&request(OrderSide::Buy, 0.5, 0.001), 0.01),
+            Err(ExecutionError::InvalidOrder(
+                PaperExecError::InvalidQuantity
+            ))
+        );
+    }
+
+    #[test]
+    fn reads_fill_or_kill_placements() {
+        let filled = placement_report(
+            &json!({"success": true, "errorMsg": "", "orderID": "0xabc", "status": "matched",
+                "makingAmount": "2.9", "takingAmount": "5"}),
+            OrderSide::Buy,
+            5.0,
+        )
+        .unwrap();
+        assert_eq!(filled.status, OrderStatus::Filled);
+        assert_eq!(filled.filled_qty, 5.0);
+        assert!((filled.avg_fill_px.unwrap() - 0.58).abs() < 1e-12);
+
+        let killed = placement_report(
+            &json!({"success": true, "orderID": "0xdef", "status": "unmatched"}),
+            OrderSide::Sell,
+            5.0,
+        )
+        .unwrap();
+        assert_eq!(killed.status, OrderStatus::Cancelled);
+        assert_eq!(
+            placement_report(
+                &json!({"success": false, "errorMsg": "not enough balance"}),
+                OrderSide::Buy,
+                5.0
+            ),
+            Err(ExecutionError::Rejected("not enough balance".to_string()))
+        );
+    }
+
+    #[test]
+    fn user_channel_updates_order_reports() {
+        let reports = SharedOrderReports::default();
+        apply_user_frame(
+            r#"[{"event_type":"order","id":"0xabc","type":"UPDATE","original_size":"10","size_matched":"4","price":"0.55"},
+                {"event_type":"trade","id":"t1"}]"#,
+            &reports,
+        );
+        let report = reports.lock().unwrap()["0xabc"].clone();
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.filled_qty, 4.0);
+
+        apply_user_frame(
+            r#"{"event_type":"order","id":"0xabc","type":"CANCELLATION","original_size":"10","size_matched":"4","price":"0.55"}"#,
+            &reports,
+        );
+        assert_eq!(
+            reports.lock().unwrap()["0xabc"].status,
+            OrderStatus::Cancelled
+        );
+    }
+
+    #[test]
+    fn reads_yes_tokens_from_gamma_and_signs_requests() {
+        let payload = r#"[{"slug":"btc-a","outcomes":"[\"No\",\"Yes\"]",
+            "clobTokenIds":"[\"111\",\"222\"]","negRisk":true,"orderPriceMinTickSize":0.001,
+            "takerBaseFee":200}]"#;
+        let market = ClobMarket::from_gamma(payload, "btc-a").unwrap();
+        assert_eq!(market.yes_token, "222");
+        assert_eq!(market.tick_size, 0.001);
+        assert_eq!(market.taker_fee_bps, 200);
+        assert_eq!(
+            market.exchange(),
+            decode_address(NEG_RISK_CTF_EXCHANGE).unwrap()
+        );
+        assert!(ClobMarket::from_gamma(payload, "btc-b").is_none());
+
+        let secret = URL_SAFE.encode(b"secret");
+        let signature = l2_signature(&secret, "1700000000", "GET", "/data/order/1", "").unwrap();
+        assert_eq!(URL_SAFE.decode(&signature).unwrap().len(), 32);
+        assert_ne!(
+            l2_signature(&secret, "1700000001", "GET", "/data/order/1", ""),
+            Some(signature)
+        );
+        assert!(l2_signature("not base64!", "1", "GET", "/", "").is_none());
+    }
+}
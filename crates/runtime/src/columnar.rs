@@ -0,0 +1,397 @@
+//! Parquet series for recorded ticks and paper journal rows. Columns are
+//! typed, so a session loads into pandas or polars without CSV parsing.
+
+use std::fs::{self, File};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use crate::recorder::{RecordStream, RecordedTick};
+use crate::replay::ReplayCsvRow;
+
+/// A Parquet file cannot be read until its footer is written, so an open
+/// file is also closed once it has been written to for this long; a killed
+/// process loses at most this much of each series.
+pub const PARQUET_ROLL_MS: u64 = 10 * 60 * 1_000;
+
+struct OpenParquet {
+    writer: ArrowWriter<File>,
+    opened_ms: u64,
+}
+
+/// Appends record batches to rotating Parquet files named
+/// `<prefix>-<seq>.parquet` under `dir`.
+pub struct ParquetSeries {
+    dir: PathBuf,
+    prefix: String,
+    schema: SchemaRef,
+    rotate_bytes: u64,
+    open: Option<OpenParquet>,
+    next_seq: u32,
+}
+
+impl ParquetSeries {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        prefix: impl Into<String>,
+        schema: SchemaRef,
+        rotate_bytes: u64,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: prefix.into(),
+            schema,
+            rotate_bytes,
+            open: None,
+            next_seq: 0,
+        }
+    }
+
+    pub fn file_path(&self, seq: u32) -> PathBuf {
+        self.dir.join(format!("{}-{seq:04}.parquet", self.prefix))
+    }
+
+    /// Writes `batch` to the open file, starting one if needed. The file is
+    /// closed once its encoded size reaches `rotate_bytes` or it is older
+    /// than [`PARQUET_ROLL_MS`] at `now_ms`.
+    pub fn write(&mut self, now_ms: u64, batch: &RecordBatch) -> io::Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        if self.open.is_none() {
+            self.open = Some(self.open_next(now_ms)?);
+        }
+        let open = self.open.as_mut().expect("parquet file was opened above");
+        open.writer.write(batch)?;
+
+        let bytes = (open.writer.bytes_written() + open.writer.in_progress_size()) as u64;
+        if bytes >= self.rotate_bytes || now_ms.saturating_sub(open.opened_ms) >= PARQUET_ROLL_MS {
+            self.close()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the open file's footer, leaving it complete and readable.
+    pub fn close(&mut self) -> io::Result<()> {
+        if let Some(open) = self.open.take() {
+            open.writer.close()?;
+        }
+        Ok(())
+    }
+
+    fn open_next(&mut self, now_ms: u64) -> io::Result<OpenParquet> {
+        fs::create_dir_all(&self.dir)?;
+        let file = File::create(self.file_path(self.next_seq))?;
+        self.next_seq += 1;
+
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        Ok(OpenParquet {
+            writer: ArrowWriter::try_new(file, self.schema.clone(), Some(props))?,
+            opened_ms: now_ms,
+        })
+    }
+}
+
+/// Columns of a recorded stream, in the order of its CSV header.
+pub fn tick_schema(stream: RecordStream) -> SchemaRef {
+    let millis = |name| Field::new(name, DataType::UInt64, false);
+    let text = |name| Field::new(name, DataType::Utf8, false);
+    let number = |name| Field::new(name, DataType::Float64, false);
+    let mut fields = match stream {
+        RecordStream::Spot => vec![
+            millis("recv_ms"),
+            text("asset"),
+            text("venue"),
+            number("px"),
+            number("size"),
+            millis("ts"),
+        ],
+        RecordStream::Quotes => vec![
+            millis("recv_ms"),
+            text("source"),
+            text("asset"),
+            text("market_slug"),
+            number("best_yes_bid"),
+            number("best_yes_ask"),
+            number("mid_yes"),
+            millis("ts"),
+        ],
+        RecordStream::Predictors => vec![
+            millis("recv_ms"),
+            text("source"),
+            number("predicted_yes_px"),
+            number("confidence"),
+            millis("ts_ms"),
+        ],
+    };
+    fields.push(Field::new("run_id", DataType::UInt64, true));
+    Arc::new(Schema::new(fields))
+}
+
+/// The `stream` ticks among `rows`, each with its receive time and run, as
+/// a batch of [`tick_schema`].
+pub fn tick_batch(
+    stream: RecordStream,
+    rows: &[(u64, Option<u64>, RecordedTick)],
+) -> Result<RecordBatch, ArrowError> {
+    let rows: Vec<_> = rows
+        .iter()
+        .filter(|(_, _, tick)| tick.stream() == stream)
+        .collect();
+    let mut columns = match stream {
+        RecordStream::Spot => {
+            let rows: Vec<_> = rows
+                .iter()
+                .filter_map(|(recv_ms, _, tick)| match tick {
+                    RecordedTick::Spot(asset, spot) => Some((*recv_ms, *asset, spot)),
+                    _ => None,
+                })
+                .collect();
+            vec![
+                millis(&rows, |row| row.0),
+                text(&rows, |row| row.1.as_str()),
+                text(&rows, |row| &row.2.venue),
+                number(&rows, |row| row.2.px),
+                number(&rows, |row| row.2.size),
+                millis(&rows, |row| row.2.ts),
+            ]
+        }
+        RecordStream::Quotes => {
+            let rows: Vec<_> = rows
+                .iter()
+                .filter_map(|(recv_ms, _, tick)| match tick {
+                    RecordedTick::Quote(quote) => Some((*recv_ms, quote)),
+                    _ => None,
+                })
+                .collect();
+            vec![
+                millis(&rows, |row| row.0),
+                text(&rows, |row| row.1.source.as_str()),
+                text(&rows, |row| row.1.asset.as_str()),
+                text(&rows, |row| &row.1.market_slug),
+                number(&rows, |row| row.1.best_yes_bid),
+                number(&rows, |row| row.1.best_yes_ask),
+                number(&rows, |row| row.1.mid_yes),
+                millis(&rows, |row| row.1.ts),
+            ]
+        }
+        RecordStream::Predictors => {
+            let rows: Vec<_> = rows
+                .iter()
+                .filter_map(|(recv_ms, _, tick)| match tick {
+                    RecordedTick::Predictor(predictor) => Some((*recv_ms, predictor)),
+                    _ => None,
+                })
+                .collect();
+            vec![
+                millis(&rows, |row| row.0),
+                text(&rows, |row| row.1.source.as_str()),
+                number(&rows, |row| row.1.predicted_yes_px),
+                number(&rows, |row| row.1.confidence),
+                millis(&rows, |row| row.1.ts_ms),
+            ]
+        }
+    };
+    columns.push(Arc::new(UInt64Array::from_iter(
+        rows.iter().map(|(_, run_id, _)| *run_id),
+    )));
+    RecordBatch::try_new(tick_schema(stream), columns)
+}
+
+/// Columns of the paper journal: the replay CSV row split into typed fields,
+/// with the Unix milliseconds it was written at.
+pub fn journal_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("recorded_ms", DataType::UInt64, false),
+        Field::new("tick", DataType::UInt64, false),
+        Field::new("kind", DataType::Utf8, false),
+        Field::new("action_detail", DataType::Utf8, false),
+        Field::new("external_px", DataType::Float64, true),
+        Field::new("market_px", DataType::Float64, true),
+        Field::new("divergence", DataType::Float64, true),
+        Field::new("equity", DataType::Float64, false),
+        Field::new("realized_pnl", DataType::Float64, false),
+        Field::new("position", DataType::Float64, false),
+        Field::new("halted", DataType::Boolean, false),
+        Field::new("run_id", DataType::UInt64, true),
+    ]))
+}
+
+/// `rows`, each with its `recorded_ms`, as a batch of [`journal_schema`].
+pub fn journal_batch(rows: &[(u64, ReplayCsvRow)]) -> Result<RecordBatch, ArrowError> {
+    let optional = |value: fn(&ReplayCsvRow) -> Option<f64>| -> ArrayRef {
+        Arc::new(Float64Array::from_iter(
+            rows.iter().map(|(_, row)| value(row)),
+        ))
+    };
+    let columns = vec![
+        millis(rows, |(recorded_ms, _)| *recorded_ms),
+        millis(rows, |(_, row)| row.tick),
+        text(rows, |(_, row)| row.kind.as_replay_action()),
+        text(rows, |(_, row)| &row.action_detail),
+        optional(|row| row.external_px),
+        optional(|row| row.market_px),
+        optional(|row| row.divergence),
+        number(rows, |(_, row)| row.equity),
+        number(rows, |(_, row)| row.realized_pnl),
+        number(rows, |(_, row)| row.position),
+        Arc::new(BooleanArray::from_iter(
+            rows.iter().map(|(_, row)| Some(row.halted)),
+        )),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter().map(|(_, row)| row.run_id),
+        )),
+    ];
+    RecordBatch::try_new(journal_schema(), columns)
+}
+
+fn millis<T>(rows: &[T], value: impl Fn(&T) -> u64) -> ArrayRef {
+    Arc::new(UInt64Array::from_iter_values(rows.iter().map(value)))
+}
+
+fn text<'a, T>(rows: &'a [T], value: impl Fn(&'a T) -> &'a str) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(rows.iter().map(value)))
+}
+
+fn number<T>(rows: &[T], value: impl Fn(&T) -> f64) -> ArrayRef {
+    Arc::new(Float64Array::from_iter_values(rows.iter().map(value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Float64Type, UInt64Type};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+    use crate::live::{Asset, NormalizedBtcTick};
+    use crate::logging::PaperJournalRowKind;
+    use crate::recorder::{RecordFormat, TickRecorder, TickRecorderConfig};
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("runtime-columnar-{label}-{unique}"))
+    }
+
+    fn read_batches(path: PathBuf) -> Vec<RecordBatch> {
+        ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn spot(px: f64, ts: u64) -> RecordedTick {
+        RecordedTick::Spot(
+            Asset::Btc,
+            NormalizedBtcTick {
+                venue: "coinbase".to_string(),
+                px,
+                size: 0.5,
+                ts,
+            },
+        )
+    }
+
+    #[test]
+    fn records_ticks_as_typed_parquet_columns() {
+        let dir = temp_dir("ticks");
+        let mut recorder = TickRecorder::new(
+            TickRecorderConfig {
+                dir: dir.clone(),
+                rotate_bytes: 64 * 1_024 * 1_024,
+                format: RecordFormat::Parquet,
+                streams: vec![RecordStream::Spot],
+            },
+            42,
+        )
+        .unwrap();
+        for idx in 0..3 {
+            recorder
+                .record(1_000 + idx, Some(2), &spot(64_000.0 + idx as f64, idx))
+                .unwrap();
+        }
+        recorder.flush().unwrap();
+        recorder.record(1_003, None, &spot(64_010.0, 3)).unwrap();
+        recorder.close().unwrap();
+
+        let path = recorder.file_path(RecordStream::Spot, 0);
+        assert!(path.ends_with("spot-42-0000.parquet"));
+        let batches = read_batches(path);
+        assert_eq!(batches[0].schema(), tick_schema(RecordStream::Spot));
+        let rows: usize = batches.iter().map(RecordBatch::num_rows).sum();
+        assert_eq!(rows, 4);
+        let px = batches[0].column(3).as_primitive::<Float64Type>();
+        assert_eq!(px.value(2), 64_002.0);
+        let venue = batches[0].column(2).as_string::<i32>();
+        assert_eq!(venue.value(0), "coinbase");
+        let run_id = batches[0].column(6).as_primitive::<UInt64Type>();
+        assert_eq!(run_id.value(0), 2);
+        let last = batches.last().unwrap();
+        assert!(last.column(6).is_null(last.num_rows() - 1));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn journals_rows_and_rolls_files_by_age() {
+        let dir = temp_dir("journal");
+        let mut series = ParquetSeries::new(&dir, "journal-7", journal_schema(), u64::MAX);
+        let row = |tick, kind, market_px, run_id| ReplayCsvRow {
+            tick,
+            kind,
+            action_detail: String::new(),
+            external_px: None,
+            market_px,
+            divergence: None,
+            equity: 1_000.0,
+            realized_pnl: 0.0,
+            position: 2.0,
+            halted: false,
+            run_id,
+        };
+
+        let fill = row(1, PaperJournalRowKind::PaperFill, Some(0.52), Some(5));
+        series
+            .write(5_000, &journal_batch(&[(5_000, fill)]).unwrap())
+            .unwrap();
+        let reject = row(2, PaperJournalRowKind::RiskReject, None, None);
+        let late = 5_000 + PARQUET_ROLL_MS;
+        series
+            .write(late, &journal_batch(&[(late, reject)]).unwrap())
+            .unwrap();
+        // The age check closed the first file, which is readable already.
+        let first = read_batches(series.file_path(0));
+        let batch = &first[0];
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(0).as_primitive::<UInt64Type>().value(1), late);
+        assert_eq!(batch.column(2).as_string::<i32>().value(1), "risk_reject");
+        assert!(!batch.column(5).is_null(0));
+        assert!(batch.column(5).is_null(1));
+        assert_eq!(batch.column(11).as_primitive::<UInt64Type>().value(0), 5);
+        assert!(batch.column(11).is_null(1));
+
+        let intent = row(3, PaperJournalRowKind::PaperIntent, Some(0.5), Some(5));
+        series
+            .write(late + 1, &journal_batch(&[(late + 1, intent)]).unwrap())
+            .unwrap();
+        series.close().unwrap();
+        assert_eq!(read_batches(series.file_path(1))[0].num_rows(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawdownConfigError {
+    /// The watermark needs a positive starting equity to measure against.
+    NonPositiveStartingEquity,
+    /// The limit is a fraction of the peak in `(0, 1]`.
+    LimitOutOfRange,
+}
+
+/// Running equity peak for the paper book, halting once equity falls
+/// `max_drawdown_fraction` below it.
+///
+/// Equity is compared in decimal, the same units the positions book in, so
+/// the halt trips on the exact booked loss rather than a rounded float.
+#[derive(Debug, Clone)]
+pub struct DrawdownWatch {
+    peak_equity: Decimal,
+    max_drawdown: Decimal,
+    halted: bool,
+}
+
+impl DrawdownWatch {
+    pub fn new(
+        starting_equity: Decimal,
+        max_drawdown_fraction: f64,
+    ) -> Result<Self, DrawdownConfigError> {
+        if starting_equity <= Decimal::ZERO {
+            return Err(DrawdownConfigError::NonPositiveStartingEquity);
+        }
+        let max_drawdown = Decimal::from_f64(max_drawdown_fraction)
+            .filter(|limit| *limit > Decimal::ZERO && *limit <= Decimal::ONE)
+            .ok_or(DrawdownConfigError::LimitOutOfRange)?;
+
+        Ok(Self {
+            peak_equity: starting_equity,
+            max_drawdown,
+            halted: false,
+        })
+    }
+
+    /// Raises the watermark on new highs and halts when the drawdown from
+    /// it reaches the limit. The halt latches.
+    pub fn update(&mut self, equity: Decimal) {
+        self.peak_equity = self.peak_equity.max(equity);
+        if self.drawdown(equity) >= self.max_drawdown {
+            self.halted = true;
+        }
+    }
+
+    pub fn peak_equity(&self) -> Decimal {
+        self.peak_equity
+    }
+
+    /// Fraction of the peak lost at `equity`, as a float for reporting;
+    /// zero at or above the peak.
+    pub fn drawdown_fraction(&self, equity: Decimal) -> f64 {
+        self.drawdown(equity).to_f64().unwrap_or(0.0)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    fn drawdown(&self, equity: Decimal) -> Decimal {
+        ((self.peak_equity - equity) / self.peak_equity).max(Decimal::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn halts_on_the_exact_drawdown_from_the_peak() {
+        let mut watch = DrawdownWatch::new(Decimal::from(1_000), 0.1).unwrap();
+
+        watch.update(Decimal::from(1_200));
+        watch.update(Decimal::new(108_001, 2));
+        assert!(!watch.is_halted());
+        assert_eq!(watch.peak_equity(), Decimal::from(1_200));
+
+        watch.update(Decimal::from(1_080));
+        assert!(watch.is_halted());
+        assert_eq!(watch.drawdown_fraction(Decimal::from(1_080)), 0.1);
+
+        watch.update(Decimal::from(1_300));
+        assert!(watch.is_halted());
+        assert_eq!(watch.drawdown_fraction(Decimal::from(1_400)), 0.0);
+    }
+
+    #[test]
+    fn rejects_limits_outside_a_fraction_of_the_peak() {
+        assert_eq!(
+            DrawdownWatch::new(Decimal::ZERO, 0.1).unwrap_err(),
+            DrawdownConfigError::NonPositiveStartingEquity
+        );
+        for limit in [0.0, 1.5, f64::NAN] {
+            assert_eq!(
+                DrawdownWatch::new(Decimal::from(1_000), limit).unwrap_err(),
+                DrawdownConfigError::LimitOutOfRange
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::events::{RuntimeEvent, RuntimeStage};
+use crate::events::{DecisionEvents, RuntimeEvent, RuntimeStage};
 use crate::live_runner::{self, JoinedLiveInputs};
 
 pub struct SimEngine {
@@ -26,7 +26,7 @@ impl SimEngine {
         ]
     }
 
-    pub async fn step_live_once(&mut self, joined: JoinedLiveInputs) -> Vec<RuntimeEvent> {
+    pub async fn step_live_once(&mut self, joined: JoinedLiveInputs) -> DecisionEvents {
         self.tick += 1;
         live_runner::run_paper_live_once(self.tick, &joined)
     }
@@ -36,7 +36,7 @@ impl SimEngine {
 mod tests {
     use super::SimEngine;
     use crate::events::RuntimeStage;
-    use crate::live::{BtcMedianTick, PolymarketQuoteTick};
+    use crate::live::{Asset, BookDepth, BtcMedianTick, PolymarketQuoteTick, QuoteSource};
     use crate::live_runner::JoinedLiveInputs;
 
     #[tokio::test]
@@ -75,10 +75,14 @@ mod tests {
             btc_tick: BtcMedianTick::new(64_000.0, 8.0, 3, tick),
             quote_tick: PolymarketQuoteTick {
                 market_slug: "btc-up-down".to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
                 best_yes_bid: 0.48,
                 best_yes_ask: 0.52,
                 mid_yes: 0.50,
                 ts: tick,
+                depth: BookDepth::default(),
+                liquidity_score: None,
             },
         }
     }
@@ -88,10 +92,14 @@ mod tests {
             btc_tick: BtcMedianTick::new(64_000.0, 0.0, 3, tick),
             quote_tick: PolymarketQuoteTick {
                 market_slug: "btc-up-down".to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
                 best_yes_bid: 0.48,
                 best_yes_ask: 0.52,
                 mid_yes: 0.50,
                 ts: tick,
+                depth: BookDepth::default(),
+                liquidity_score: None,
             },
         }
     }
@@ -101,10 +109,14 @@ mod tests {
             btc_tick: BtcMedianTick::new(64_000.0, 12.0, 3, tick),
             quote_tick: PolymarketQuoteTick {
                 market_slug: "btc-up-down".to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
                 best_yes_bid: 0.89,
                 best_yes_ask: 0.91,
                 mid_yes: 0.90,
                 ts: tick,
+                depth: BookDepth::default(),
+                liquidity_score: None,
             },
         }
     }
@@ -1,3 +1,5 @@
+use smallvec::SmallVec;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuntimeStage {
     TickStarted,
@@ -9,6 +11,9 @@ pub enum RuntimeStage {
     PaperFillRecorded,
 }
 
+/// Events from one decision: an intent and at most one fill, kept inline.
+pub type DecisionEvents = SmallVec<[RuntimeEvent; 2]>;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeEvent {
     pub tick: u64,
@@ -0,0 +1,245 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::paper_exec::{paper_fill_buy, paper_fill_sell, PaperExecError};
+
+/// Paper reports kept for `query`, oldest dropped first.
+const PAPER_ORDER_HISTORY: usize = 1_000;
+
+pub type ExecutionFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+/// A limit order for the YES outcome of a prediction market.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    /// Venue slug of the market.
+    pub market_id: String,
+    pub side: OrderSide,
+    pub qty: f64,
+    /// Worst YES price the order may fill at.
+    pub limit_px: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    /// Resting on the book with nothing filled yet.
+    Open,
+    PartiallyFilled,
+    Filled,
+    /// Cancelled, or killed unfilled, with `filled_qty` done before that.
+    Cancelled,
+}
+
+impl OrderStatus {
+    /// Whether the order can still change.
+    pub fn is_final(self) -> bool {
+        matches!(self, Self::Filled | Self::Cancelled)
+    }
+}
+
+/// Where an order stands, as last reported by its venue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderReport {
+    pub order_id: String,
+    pub status: OrderStatus,
+    pub filled_qty: f64,
+    /// Average price of the filled quantity.
+    pub avg_fill_px: Option<f64>,
+    pub fee: f64,
+    /// Venue request built for the order but deliberately never sent, as
+    /// in a dry run.
+    pub unsent_request: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionError {
+    /// The order failed local checks before reaching the venue.
+    InvalidOrder(PaperExecError),
+    /// The market is not tradable through this adapter.
+    UnknownMarket(String),
+    /// The venue refused the request, with its reason.
+    Rejected(String),
+    UnknownOrder(String),
+    /// The venue could not be reached, or answered with something
+    /// unreadable.
+    Transport(String),
+}
+
+/// A wallet key plus the Polymarket CLOB API credentials derived for it.
+/// `Debug` leaves the secrets out.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ClobCredentials {
+    /// Hex secp256k1 key of the EOA that signs and funds orders.
+    pub private_key: String,
+    pub api_key: String,
+    /// Base64url secret for request signatures.
+    pub api_secret: String,
+    pub api_passphrase: String,
+}
+
+impl fmt::Debug for ClobCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClobCredentials")
+            .field("api_key", &self.api_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Places, cancels and looks up orders at one venue.
+pub trait ExecutionAdapter: Send {
+    fn name(&self) -> &str;
+
+    fn place(
+        &mut self,
+        order: &OrderRequest,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>>;
+
+    fn cancel(
+        &mut self,
+        order_id: &str,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>>;
+
+    fn query(&mut self, order_id: &str)
+        -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>>;
+}
+
+/// Fills every order in full at its limit price, adjusted by the configured
+/// slippage and fee, the way the paper loop always has.
+#[derive(Debug, Default)]
+pub struct PaperExecutor {
+    slippage_bps: f64,
+    fee_bps: f64,
+    next_id: u64,
+    orders: HashMap<String, OrderReport>,
+    order_ids: VecDeque<String>,
+}
+
+impl PaperExecutor {
+    pub fn new(slippage_bps: f64, fee_bps: f64) -> Self {
+        Self {
+            slippage_bps,
+            fee_bps,
+            ..Self::default()
+        }
+    }
+
+    fn fill(&mut self, order: &OrderRequest) -> Result<OrderReport, ExecutionError> {
+        let fill = match order.side {
+            OrderSide::Buy => {
+                paper_fill_buy(order.limit_px, order.qty, self.slippage_bps, self.fee_bps)
+            }
+            OrderSide::Sell => {
+                paper_fill_sell(order.limit_px, order.qty, self.slippage_bps, self.fee_bps)
+            }
+        }
+        .map_err(ExecutionError::InvalidOrder)?;
+
+        self.next_id += 1;
+        let report = OrderReport {
+            order_id: format!("paper-{}", self.next_id),
+            status: OrderStatus::Filled,
+            filled_qty: fill.qty,
+            avg_fill_px: Some(fill.fill_px),
+            fee: fill.fee,
+            unsent_request: None,
+        };
+        self.orders.insert(report.order_id.clone(), report.clone());
+        self.order_ids.push_back(report.order_id.clone());
+        if self.order_ids.len() > PAPER_ORDER_HISTORY {
+            if let Some(oldest) = self.order_ids.pop_front() {
+                self.orders.remove(&oldest);
+            }
+        }
+        Ok(report)
+    }
+
+    fn lookup(&self, order_id: &str) -> Result<OrderReport, ExecutionError> {
+        self.orders
+            .get(order_id)
+            .cloned()
+            .ok_or_else(|| ExecutionError::UnknownOrder(order_id.to_string()))
+    }
+}
+
+impl ExecutionAdapter for PaperExecutor {
+    fn name(&self) -> &str {
+        "paper"
+    }
+
+    fn place(
+        &mut self,
+        order: &OrderRequest,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>> {
+        let result = self.fill(order);
+        Box::pin(async move { result })
+    }
+
+    /// Paper orders fill on placement, so there is never anything left to
+    /// cancel; the final report is returned as is.
+    fn cancel(
+        &mut self,
+        order_id: &str,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>> {
+        let result = self.lookup(order_id);
+        Box::pin(async move { result })
+    }
+
+    fn query(
+        &mut self,
+        order_id: &str,
+    ) -> ExecutionFuture<'_, Result<OrderReport, ExecutionError>> {
+        let result = self.lookup(order_id);
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(side: OrderSide, limit_px: f64) -> OrderRequest {
+        OrderRequest {
+            market_id: "btc-a".to_string(),
+            side,
+            qty: 10.0,
+            limit_px,
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn paper_orders_fill_in_full_on_placement() {
+        let mut paper = PaperExecutor::new(0.0, 10.0);
+
+        let report = paper.place(&order(OrderSide::Buy, 0.5)).await.unwrap();
+        assert_eq!(report.status, OrderStatus::Filled);
+        assert_eq!(report.filled_qty, 10.0);
+        assert_eq!(report.avg_fill_px, Some(0.5));
+        assert!((report.fee - 0.005).abs() < 1e-12);
+        assert_eq!(paper.query(&report.order_id).await.unwrap(), report);
+        assert_eq!(paper.cancel(&report.order_id).await.unwrap(), report);
+
+        let second = paper.place(&order(OrderSide::Sell, 0.5)).await.unwrap();
+        assert_ne!(second.order_id, report.order_id);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn paper_rejects_invalid_orders_and_unknown_ids() {
+        let mut paper = PaperExecutor::default();
+
+        assert_eq!(
+            paper.place(&order(OrderSide::Buy, 1.5)).await,
+            Err(ExecutionError::InvalidOrder(PaperExecError::InvalidPrice))
+        );
+        assert_eq!(
+            paper.query("paper-9").await,
+            Err(ExecutionError::UnknownOrder("paper-9".to_string()))
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::live::{PredictorSource, PredictorTick};
+use crate::recorder::{split_csv_row, RecordStream, RecordedTick};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForecastImportError {
+    /// The file has no header line.
+    Empty,
+    /// A required column is missing from the header.
+    MissingColumn(&'static str),
+    /// Rows carry no `source` and no default label was given.
+    MissingSource,
+    /// The row on this 1-based line could not be read.
+    InvalidRow(usize),
+}
+
+/// Reads externally produced forecasts as predictor ticks, oldest first.
+///
+/// Columns are found by header name: `ts_ms` and `predicted_yes_px` are
+/// required, `source` falls back to `default_source` when absent or blank,
+/// and `confidence` defaults to 1. Other columns are ignored.
+pub fn parse_forecasts_csv(
+    raw: &str,
+    default_source: Option<&PredictorSource>,
+) -> Result<Vec<PredictorTick>, ForecastImportError> {
+    let mut lines = raw.lines().enumerate();
+    let header = lines
+        .next()
+        .map(|(_, line)| split_csv_row(line.trim_end_matches('\r')))
+        .ok_or(ForecastImportError::Empty)?;
+    let column = |name: &str| header.iter().position(|field| field.trim() == name);
+    let ts_col = column("ts_ms").ok_or(ForecastImportError::MissingColumn("ts_ms"))?;
+    let px_col =
+        column("predicted_yes_px").ok_or(ForecastImportError::MissingColumn("predicted_yes_px"))?;
+    let source_col = column("source");
+    let confidence_col = column("confidence");
+    if source_col.is_none() && default_source.is_none() {
+        return Err(ForecastImportError::MissingSource);
+    }
+
+    let mut forecasts = Vec::new();
+    for (idx, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+        let invalid = || ForecastImportError::InvalidRow(idx + 1);
+        let fields = split_csv_row(line.trim_end_matches('\r'));
+        let field = |col: usize| fields.get(col).map(|field| field.trim());
+
+        let source = match source_col.and_then(field).filter(|label| !label.is_empty()) {
+            Some(label) => PredictorSource::parse(label).ok_or_else(invalid)?,
+            None => default_source.cloned().ok_or_else(invalid)?,
+        };
+        let predicted_yes_px = field(px_col)
+            .and_then(|px| px.parse::<f64>().ok())
+            .filter(|px| (0.0..=1.0).contains(px))
+            .ok_or_else(invalid)?;
+        let confidence = match confidence_col.and_then(field) {
+            Some(confidence) => confidence
+                .parse::<f64>()
+                .ok()
+                .filter(|confidence| confidence.is_finite() && *confidence > 0.0)
+                .ok_or_else(invalid)?,
+            None => 1.0,
+        };
+        let ts_ms = field(ts_col)
+            .and_then(|ts| ts.parse::<u64>().ok())
+            .ok_or_else(invalid)?;
+
+        forecasts.push(PredictorTick {
+            source,
+            predicted_yes_px,
+            confidence,
+            ts_ms,
+        });
+    }
+    forecasts.sort_by_key(|forecast| forecast.ts_ms);
+    Ok(forecasts)
+}
+
+/// Writes `forecasts` into `dir` as a predictors recording named
+/// `predictors-import-<name>.csv`, so replaying `dir` releases each forecast
+/// at its own timestamp alongside the recorded feeds.
+pub fn write_forecast_recording(
+    dir: &Path,
+    name: &str,
+    forecasts: &[PredictorTick],
+) -> io::Result<PathBuf> {
+    let path = dir.join(format!(
+        "{}-import-{name}.csv",
+        RecordStream::Predictors.as_str()
+    ));
+    let mut csv = RecordStream::Predictors.csv_header().to_string();
+    for forecast in forecasts {
+        csv.push_str(&RecordedTick::Predictor(forecast.clone()).csv_row(forecast.ts_ms, None));
+    }
+    fs::write(&path, csv)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn reads_forecasts_by_header_name_with_defaults() {
+        let model = PredictorSource::parse("model").unwrap();
+        let raw = "predicted_yes_px,ts_ms,note\n0.61,2000,late\n0.55,1000,early\n";
+
+        let forecasts = parse_forecasts_csv(raw, Some(&model)).unwrap();
+        assert_eq!(forecasts.len(), 2);
+        assert_eq!(forecasts[0].ts_ms, 1_000);
+        assert_eq!(forecasts[0].predicted_yes_px, 0.55);
+        assert_eq!(forecasts[0].confidence, 1.0);
+        assert_eq!(forecasts[1].source, model);
+
+        let raw = "ts_ms,source,predicted_yes_px,confidence\n1000,lstm,0.4,0.8\n";
+        let forecasts = parse_forecasts_csv(raw, None).unwrap();
+        assert_eq!(forecasts[0].source.as_str(), "lstm");
+        assert_eq!(forecasts[0].confidence, 0.8);
+    }
+
+    #[test]
+    fn rejects_missing_columns_and_bad_rows() {
+        assert_eq!(
+            parse_forecasts_csv("ts_ms,source\n", None),
+            Err(ForecastImportError::MissingColumn("predicted_yes_px"))
+        );
+        assert_eq!(
+            parse_forecasts_csv("ts_ms,predicted_yes_px\n1000,0.5\n", None),
+            Err(ForecastImportError::MissingSource)
+        );
+        assert_eq!(
+            parse_forecasts_csv(
+                "ts_ms,source,predicted_yes_px\n1000,lstm,0.5\n1001,lstm,1.5\n",
+                None
+            ),
+            Err(ForecastImportError::InvalidRow(3))
+        );
+    }
+
+    #[test]
+    fn written_recordings_read_back_as_predictor_ticks() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("runtime-forecasts-{unique}"));
+        fs::create_dir_all(&dir).unwrap();
+        let forecasts =
+            parse_forecasts_csv("ts_ms,source,predicted_yes_px\n1000,lstm,0.5\n", None).unwrap();
+
+        let path = write_forecast_recording(&dir, "lstm", &forecasts).unwrap();
+        assert!(path.ends_with("predictors-import-lstm.csv"));
+        let raw = fs::read_to_string(&path).unwrap();
+        let mut lines = raw.lines();
+        let stream = RecordStream::from_header(lines.next().unwrap()).unwrap();
+        assert_eq!(
+            RecordedTick::parse_csv_row(stream, lines.next().unwrap()),
+            Some((1_000, RecordedTick::Predictor(forecasts[0].clone())))
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
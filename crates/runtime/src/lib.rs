@@ -1,13 +1,26 @@
 pub mod benchmark;
+pub mod brackets;
+#[cfg(feature = "polymarket-clob")]
+pub mod clob;
+pub mod clock;
+#[cfg(feature = "parquet")]
+pub mod columnar;
+pub mod drawdown;
 pub mod engine;
 pub mod events;
+pub mod execution;
+pub mod forecasts;
 pub mod live;
 pub mod live_runner;
 pub mod logging;
 pub mod metrics;
 pub mod paper_exec;
+pub mod positions;
+pub mod recorder;
 pub mod replay;
 pub mod supervisor;
+pub mod throttle;
+pub mod trading_day;
 
 pub const TARGET_ORDERS_PER_SEC: u64 = 1000;
 
@@ -71,7 +84,7 @@ mod tests {
 
         assert_eq!(
             String::from_utf8(output).unwrap(),
-            "t,external_px,market_px,divergence,action,equity,realized_pnl,position,halted\n"
+            "t,external_px,market_px,divergence,action,equity,realized_pnl,position,halted,run_id\n"
         );
     }
 
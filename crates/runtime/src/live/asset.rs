@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// Spot asset underlying a family of 15m prediction markets.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Asset {
+    #[default]
+    Btc,
+    Eth,
+    Sol,
+}
+
+impl Asset {
+    pub const ALL: [Asset; 3] = [Asset::Btc, Asset::Eth, Asset::Sol];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "btc" => Some(Self::Btc),
+            "eth" => Some(Self::Eth),
+            "sol" => Some(Self::Sol),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Btc => "btc",
+            Self::Eth => "eth",
+            Self::Sol => "sol",
+        }
+    }
+
+    /// Upper-case ticker venues build their symbols from (`BTC-USD`, `ETHUSDT`).
+    pub fn ticker(self) -> &'static str {
+        match self {
+            Self::Btc => "BTC",
+            Self::Eth => "ETH",
+            Self::Sol => "SOL",
+        }
+    }
+
+    /// Words that identify the asset in a market slug or question.
+    pub fn market_keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::Btc => &["btc", "bitcoin"],
+            Self::Eth => &["eth", "ethereum"],
+            Self::Sol => &["sol", "solana"],
+        }
+    }
+
+    /// Whether `text` names the asset as a whole word, so "sol" does not match
+    /// "resolve".
+    pub fn matches_text(self, text: &str) -> bool {
+        let lowered = text.to_ascii_lowercase();
+        lowered
+            .split(|ch: char| !ch.is_ascii_alphanumeric())
+            .any(|word| self.market_keywords().contains(&word))
+    }
+
+    /// Name a streaming feed for this asset registers under. BTC feeds keep the
+    /// bare venue name; other assets are suffixed so each connection has its
+    /// own health and update counters.
+    pub fn venue_label(self, venue: &str) -> String {
+        match self {
+            Self::Btc => venue.to_string(),
+            other => format!("{venue}-{}", other.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_labels_assets() {
+        assert_eq!(Asset::parse(" ETH "), Some(Asset::Eth));
+        assert_eq!(Asset::parse("doge"), None);
+        assert_eq!(Asset::Btc.venue_label("coinbase"), "coinbase");
+        assert_eq!(Asset::Sol.venue_label("coinbase"), "coinbase-sol");
+    }
+
+    #[test]
+    fn matches_market_text_on_whole_words() {
+        assert!(Asset::Sol.matches_text("sol-updown-15m-1772280000"));
+        assert!(Asset::Eth.matches_text("Will Ethereum be up in 15 minutes?"));
+        assert!(!Asset::Sol.matches_text("How will this market resolve?"));
+        assert!(!Asset::Btc.matches_text("eth-updown-15m-1772280000"));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::live::btc_feed::NormalizedBtcTick;
+use crate::live::btc_parse::ParseBtcTradeError;
+use serde::Deserialize;
+
+/// Parses a Binance `<symbol>@trade` stream event into a normalized tick.
+pub fn parse_binance_trade(raw: &str) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
+    let trade: BinanceTrade =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+
+    if trade.event_type != "trade" {
+        return Err(ParseBtcTradeError::UnsupportedMessageType);
+    }
+
+    normalize_binance_trade(trade)
+}
+
+/// Parses one frame from the Binance raw trade stream.
+///
+/// Trade events yield a tick; subscription acknowledgements (`{"result":null,"id":1}`)
+/// and other non-trade events yield `Ok(None)`.
+pub fn parse_binance_ws_frame(raw: &str) -> Result<Option<NormalizedBtcTick>, ParseBtcTradeError> {
+    let envelope: BinanceEnvelope =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    if envelope.event_type.as_deref() != Some("trade") {
+        return Ok(None);
+    }
+
+    let trade: BinanceTrade =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    normalize_binance_trade(trade).map(Some)
+}
+
+fn normalize_binance_trade(trade: BinanceTrade) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
+    let px = trade
+        .price
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .ok_or(ParseBtcTradeError::InvalidPrice)?;
+
+    let size = trade
+        .quantity
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .ok_or(ParseBtcTradeError::InvalidSize)?;
+
+    if trade.trade_time_ms == 0 {
+        return Err(ParseBtcTradeError::InvalidTimestamp);
+    }
+
+    Ok(NormalizedBtcTick {
+        venue: "binance".to_string(),
+        px,
+        size,
+        ts: trade.trade_time_ms,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceEnvelope {
+    #[serde(rename = "e", default)]
+    event_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceTrade {
+    #[serde(rename = "e")]
+    event_type: String,
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "q")]
+    quantity: String,
+    #[serde(rename = "T")]
+    trade_time_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_binance_trade, parse_binance_ws_frame, ParseBtcTradeError};
+
+    #[test]
+    fn parses_binance_trade_into_normalized_tick() {
+        let raw = r#"{"e":"trade","E":1772280000005,"s":"BTCUSDT","t":12345,"p":"64001.20","q":"0.015","T":1772280000000,"m":true,"M":true}"#;
+        let tick = parse_binance_trade(raw).unwrap();
+
+        assert_eq!(tick.venue, "binance");
+        assert_eq!(tick.px, 64_001.2);
+        assert_eq!(tick.size, 0.015);
+        assert_eq!(tick.ts, 1_772_280_000_000);
+    }
+
+    #[test]
+    fn rejects_binance_events_other_than_trade() {
+        let raw = r#"{"e":"aggTrade","p":"64001.20","q":"0.015","T":1772280000000}"#;
+
+        assert_eq!(
+            parse_binance_trade(raw).unwrap_err(),
+            ParseBtcTradeError::UnsupportedMessageType
+        );
+    }
+
+    #[test]
+    fn rejects_binance_trades_with_invalid_price_or_timestamp() {
+        let bad_px = r#"{"e":"trade","p":"nan","q":"0.015","T":1772280000000}"#;
+        let bad_ts = r#"{"e":"trade","p":"64001.20","q":"0.015","T":0}"#;
+
+        assert_eq!(
+            parse_binance_trade(bad_px).unwrap_err(),
+            ParseBtcTradeError::InvalidPrice
+        );
+        assert_eq!(
+            parse_binance_trade(bad_ts).unwrap_err(),
+            ParseBtcTradeError::InvalidTimestamp
+        );
+    }
+
+    #[test]
+    fn binance_ws_frame_skips_subscription_acks() {
+        assert_eq!(
+            parse_binance_ws_frame(r#"{"result":null,"id":1}"#),
+            Ok(None)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::live::btc_feed::NormalizedBtcTick;
+use crate::live::btc_parse::{parse_positive_decimal, ParseBtcTradeError};
+use serde::Deserialize;
+
+/// Parses one frame from the Bitstamp `live_trades_btcusd` channel.
+///
+/// `trade` events yield a tick stamped from `microtimestamp`; subscription
+/// acknowledgements and other events yield `Ok(None)`, while `bts:error` is
+/// surfaced as [`ParseBtcTradeError::UnsupportedMessageType`].
+pub fn parse_bitstamp_ws_frame(raw: &str) -> Result<Option<NormalizedBtcTick>, ParseBtcTradeError> {
+    let envelope: BitstampEnvelope =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    match envelope.event.as_str() {
+        "trade" => {}
+        "bts:error" => return Err(ParseBtcTradeError::UnsupportedMessageType),
+        _ => return Ok(None),
+    }
+
+    let message: BitstampTradeMessage =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    normalize_bitstamp_trade(&message.data).map(Some)
+}
+
+fn normalize_bitstamp_trade(
+    trade: &BitstampTrade,
+) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
+    let px = parse_positive_decimal(&trade.price_str, ParseBtcTradeError::InvalidPrice)?;
+    let size = parse_positive_decimal(&trade.amount_str, ParseBtcTradeError::InvalidSize)?;
+    let ts = trade
+        .microtimestamp
+        .parse::<u64>()
+        .ok()
+        .map(|micros| micros / 1_000)
+        .filter(|ts| *ts > 0)
+        .ok_or(ParseBtcTradeError::InvalidTimestamp)?;
+
+    Ok(NormalizedBtcTick {
+        venue: "bitstamp".to_string(),
+        px,
+        size,
+        ts,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct BitstampEnvelope {
+    event: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitstampTradeMessage {
+    data: BitstampTrade,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitstampTrade {
+    price_str: String,
+    amount_str: String,
+    microtimestamp: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bitstamp_ws_frame, ParseBtcTradeError};
+
+    #[test]
+    fn parses_bitstamp_trade_event() {
+        let raw = r#"{"data":{"id":1,"timestamp":"1772280000","amount":0.05,"amount_str":"0.05000000","price":64003,"price_str":"64003","type":0,"microtimestamp":"1772280000345678","buy_order_id":1,"sell_order_id":2},"channel":"live_trades_btcusd","event":"trade"}"#;
+        let tick = parse_bitstamp_ws_frame(raw).unwrap().unwrap();
+
+        assert_eq!(tick.venue, "bitstamp");
+        assert_eq!(tick.px, 64_003.0);
+        assert_eq!(tick.size, 0.05);
+        assert_eq!(tick.ts, 1_772_280_000_345);
+    }
+
+    #[test]
+    fn bitstamp_frame_skips_subscription_acks() {
+        assert_eq!(
+            parse_bitstamp_ws_frame(
+                r#"{"event":"bts:subscription_succeeded","channel":"live_trades_btcusd","data":{}}"#
+            ),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn bitstamp_frame_surfaces_errors_and_bad_sizes() {
+        assert_eq!(
+            parse_bitstamp_ws_frame(
+                r#"{"event":"bts:error","channel":"","data":{"code":null,"message":"Bad subscription string."}}"#
+            ),
+            Err(ParseBtcTradeError::UnsupportedMessageType)
+        );
+        assert_eq!(
+            parse_bitstamp_ws_frame(
+                r#"{"data":{"price_str":"64003","amount_str":"0","microtimestamp":"1772280000345678"},"event":"trade"}"#
+            ),
+            Err(ParseBtcTradeError::InvalidSize)
+        );
+    }
+}
@@ -1,4 +1,47 @@
+use std::time::{Duration, Instant};
+
+use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{client_async_tls, connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::live::asset::Asset;
+use crate::live::binance_parse::parse_binance_ws_frame;
+use crate::live::bitstamp_parse::parse_bitstamp_ws_frame;
+use crate::live::btc_parse::{parse_coinbase_ws_frame, ParseBtcTradeError};
+use crate::live::bybit_parse::parse_bybit_ws_frame;
+use crate::live::kraken_parse::parse_kraken_ws_frame;
+use crate::live::okx_parse::parse_okx_ws_frame;
+use crate::live::proxy::HttpProxy;
+use crate::live::quarantine::RejectedPayload;
+use crate::live::reconnect::ReconnectPolicy;
+use crate::live::venue::{VenueFeed, VenueFeedError, VenueFuture, VenueTick};
+
+pub const COINBASE_WS_URL: &str = "wss://ws-feed.exchange.coinbase.com";
+/// Legacy Coinbase Pro host, still served from a separate edge.
+pub const COINBASE_FALLBACK_WS_URL: &str = "wss://ws-feed.pro.coinbase.com";
+/// Raw trade streams; Binance pushes frames without an explicit subscribe.
+pub const BINANCE_WS_BASE_URL: &str = "wss://stream.binance.com:9443/ws";
+/// Market-data-only mirror of the global streams.
+pub const BINANCE_VISION_WS_BASE_URL: &str = "wss://data-stream.binance.vision/ws";
+/// Binance.US, for hosts where the global exchange answers 451.
+pub const BINANCE_US_WS_BASE_URL: &str = "wss://stream.binance.us:9443/ws";
+const BINANCE_MAX_SESSION_MS: u64 = 23 * 60 * 60 * 1_000;
+pub const KRAKEN_WS_URL: &str = "wss://ws.kraken.com/v2";
+pub const KRAKEN_FALLBACK_WS_URL: &str = "wss://beta-ws.kraken.com/v2";
+pub const OKX_WS_URL: &str = "wss://ws.okx.com:8443/ws/v5/public";
+/// OKX closes connections that stay silent for 30 seconds.
+const OKX_HEARTBEAT_MS: u64 = 20_000;
+pub const BYBIT_WS_URL: &str = "wss://stream.bybit.com/v5/public/spot";
+/// Bybit recommends a ping every 20 seconds to keep the session alive.
+const BYBIT_HEARTBEAT_MS: u64 = 20_000;
+pub const BITSTAMP_WS_URL: &str = "wss://ws.bitstamp.net";
+/// Bitstamp prints fewer trades than the larger venues; quiet spells of
+/// several seconds are normal.
+const BITSTAMP_IDLE_TIMEOUT_MS: u64 = 60_000;
+const DEFAULT_IDLE_TIMEOUT_MS: u64 = 15_000;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NormalizedBtcTick {
@@ -7,3 +50,520 @@ pub struct NormalizedBtcTick {
     pub size: f64,
     pub ts: u64,
 }
+
+/// Parses one text frame; `Ok(None)` marks frames that carry no trade.
+pub type BtcFrameParser = fn(&str) -> Result<Option<NormalizedBtcTick>, ParseBtcTradeError>;
+
+/// Connection settings for one venue's streaming trade feed of one asset.
+#[derive(Debug, Clone)]
+pub struct BtcWsFeedConfig {
+    pub venue: String,
+    pub asset: Asset,
+    /// Endpoints to try in order; later entries are fallbacks for hosts that
+    /// refuse or geo-block the primary.
+    pub urls: Vec<String>,
+    pub subscribe_message: Option<String>,
+    pub parser: BtcFrameParser,
+    pub reconnect: ReconnectPolicy,
+    pub idle_timeout_ms: u64,
+    /// Proactively reconnect after this long, ahead of venue-side session caps.
+    pub max_session_ms: Option<u64>,
+    /// Application-level keepalive sent every `heartbeat_interval_ms`.
+    pub heartbeat_message: Option<String>,
+    pub heartbeat_interval_ms: u64,
+    /// Forward proxy the session is tunnelled through; `None` dials directly.
+    pub proxy: Option<HttpProxy>,
+}
+
+impl BtcWsFeedConfig {
+    /// Coinbase Exchange `matches` channel for `<ASSET>-USD`.
+    pub fn coinbase(asset: Asset) -> Self {
+        Self {
+            venue: asset.venue_label("coinbase"),
+            asset,
+            urls: vec![
+                COINBASE_WS_URL.to_string(),
+                COINBASE_FALLBACK_WS_URL.to_string(),
+            ],
+            subscribe_message: Some(format!(
+                r#"{{"type":"subscribe","product_ids":["{}-USD"],"channels":["matches"]}}"#,
+                asset.ticker()
+            )),
+            parser: parse_coinbase_ws_frame,
+            reconnect: ReconnectPolicy::default(),
+            idle_timeout_ms: DEFAULT_IDLE_TIMEOUT_MS,
+            max_session_ms: None,
+            heartbeat_message: None,
+            heartbeat_interval_ms: 0,
+            proxy: None,
+        }
+    }
+
+    /// Binance spot `<asset>usdt@trade` raw stream.
+    ///
+    /// Binance drops every connection after 24 hours, so sessions are recycled
+    /// an hour early instead of waiting for the server-side close. The global
+    /// exchange rejects US hosts, so the data mirror and Binance.US follow it.
+    pub fn binance(asset: Asset) -> Self {
+        let stream = format!("{}usdt@trade", asset.as_str());
+        Self {
+            venue: asset.venue_label("binance"),
+            asset,
+            urls: [
+                BINANCE_WS_BASE_URL,
+                BINANCE_VISION_WS_BASE_URL,
+                BINANCE_US_WS_BASE_URL,
+            ]
+            .iter()
+            .map(|base| format!("{base}/{stream}"))
+            .collect(),
+            subscribe_message: None,
+            parser: parse_binance_ws_frame,
+            reconnect: ReconnectPolicy::default(),
+            idle_timeout_ms: DEFAULT_IDLE_TIMEOUT_MS,
+            max_session_ms: Some(BINANCE_MAX_SESSION_MS),
+            heartbeat_message: None,
+            heartbeat_interval_ms: 0,
+            proxy: None,
+        }
+    }
+
+    /// Kraken WebSocket v2 `trade` channel for `<ASSET>/USD`.
+    pub fn kraken(asset: Asset) -> Self {
+        Self {
+            venue: asset.venue_label("kraken"),
+            asset,
+            urls: vec![
+                KRAKEN_WS_URL.to_string(),
+                KRAKEN_FALLBACK_WS_URL.to_string(),
+            ],
+            subscribe_message: Some(format!(
+                r#"{{"method":"subscribe","params":{{"channel":"trade","symbol":["{}/USD"],"snapshot":false}}}}"#,
+                asset.ticker()
+            )),
+            parser: parse_kraken_ws_frame,
+            reconnect: ReconnectPolicy::default(),
+            idle_timeout_ms: DEFAULT_IDLE_TIMEOUT_MS,
+            max_session_ms: None,
+            heartbeat_message: None,
+            heartbeat_interval_ms: 0,
+            proxy: None,
+        }
+    }
+
+    /// OKX v5 public `trades` channel for `<ASSET>-USDT`, kept alive with `ping`.
+    pub fn okx(asset: Asset) -> Self {
+        Self {
+            venue: asset.venue_label("okx"),
+            asset,
+            urls: vec![OKX_WS_URL.to_string()],
+            subscribe_message: Some(format!(
+                r#"{{"op":"subscribe","args":[{{"channel":"trades","instId":"{}-USDT"}}]}}"#,
+                asset.ticker()
+            )),
+            parser: parse_okx_ws_frame,
+            reconnect: ReconnectPolicy::default(),
+            idle_timeout_ms: DEFAULT_IDLE_TIMEOUT_MS,
+            max_session_ms: None,
+            heartbeat_message: Some("ping".to_string()),
+            heartbeat_interval_ms: OKX_HEARTBEAT_MS,
+            proxy: None,
+        }
+    }
+
+    /// Bybit v5 spot `publicTrade.<ASSET>USDT` topic.
+    pub fn bybit(asset: Asset) -> Self {
+        Self {
+            venue: asset.venue_label("bybit"),
+            asset,
+            urls: vec![BYBIT_WS_URL.to_string()],
+            subscribe_message: Some(format!(
+                r#"{{"op":"subscribe","args":["publicTrade.{}USDT"]}}"#,
+                asset.ticker()
+            )),
+            parser: parse_bybit_ws_frame,
+            reconnect: ReconnectPolicy::default(),
+            idle_timeout_ms: DEFAULT_IDLE_TIMEOUT_MS,
+            max_session_ms: None,
+            heartbeat_message: Some(r#"{"op":"ping"}"#.to_string()),
+            heartbeat_interval_ms: BYBIT_HEARTBEAT_MS,
+            proxy: None,
+        }
+    }
+
+    /// Bitstamp `live_trades_<asset>usd` channel.
+    pub fn bitstamp(asset: Asset) -> Self {
+        Self {
+            venue: asset.venue_label("bitstamp"),
+            asset,
+            urls: vec![BITSTAMP_WS_URL.to_string()],
+            subscribe_message: Some(format!(
+                r#"{{"event":"bts:subscribe","data":{{"channel":"live_trades_{}usd"}}}}"#,
+                asset.as_str()
+            )),
+            parser: parse_bitstamp_ws_frame,
+            reconnect: ReconnectPolicy::default(),
+            idle_timeout_ms: BITSTAMP_IDLE_TIMEOUT_MS,
+            max_session_ms: None,
+            heartbeat_message: None,
+            heartbeat_interval_ms: 0,
+            proxy: None,
+        }
+    }
+
+    /// Every streaming venue's config for `asset`, in registration order.
+    pub fn all(asset: Asset) -> Vec<Self> {
+        vec![
+            Self::coinbase(asset),
+            Self::binance(asset),
+            Self::kraken(asset),
+            Self::okx(asset),
+            Self::bybit(asset),
+            Self::bitstamp(asset),
+        ]
+    }
+}
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// [`VenueFeed`] over a venue's public WebSocket trade stream.
+pub struct WsBtcFeed {
+    config: BtcWsFeedConfig,
+    socket: Option<WsStream>,
+    /// Index into `config.urls` of the endpoint that last connected.
+    active_url: usize,
+    session_deadline: Option<Instant>,
+    next_heartbeat: Option<Instant>,
+    rejected: Vec<RejectedPayload>,
+}
+
+impl WsBtcFeed {
+    pub fn new(config: BtcWsFeedConfig) -> Self {
+        Self {
+            config,
+            socket: None,
+            active_url: 0,
+            session_deadline: None,
+            next_heartbeat: None,
+            rejected: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &BtcWsFeedConfig {
+        &self.config
+    }
+
+    /// Endpoint the feed is connected to, or will try first on reconnect.
+    pub fn active_url(&self) -> Option<&str> {
+        self.config.urls.get(self.active_url).map(String::as_str)
+    }
+
+    /// Endpoint indices in connection order: the last endpoint that worked,
+    /// then the rest of the list, wrapping around.
+    fn url_order(&self) -> impl Iterator<Item = usize> {
+        let active = self.active_url;
+        let len = self.config.urls.len();
+        (0..len).map(move |offset| (active + offset) % len)
+    }
+
+    /// Parses a text frame; malformed frames are dropped so one bad message
+    /// cannot tear down the session.
+    ///
+    /// Parsers name the venue only, so the tick is re-labelled with the feed's
+    /// venue and tagged with its asset.
+    pub fn parse_frame(&self, raw: &str) -> Option<VenueTick> {
+        self.try_parse_frame(raw).ok().flatten()
+    }
+
+    fn try_parse_frame(&self, raw: &str) -> Result<Option<VenueTick>, ParseBtcTradeError> {
+        Ok((self.config.parser)(raw)?.map(|mut tick| {
+            tick.venue.clone_from(&self.config.venue);
+            VenueTick::Spot(self.config.asset, tick)
+        }))
+    }
+
+    /// Connects to the first endpoint that accepts the session, starting from
+    /// the one that worked last, and fails only when every endpoint does.
+    async fn open(&mut self) -> Result<(), VenueFeedError> {
+        self.socket = None;
+        let order: Vec<usize> = self.url_order().collect();
+        let mut last_error = VenueFeedError::Connect;
+        let mut connected = None;
+        for idx in order {
+            match self.open_url(&self.config.urls[idx]).await {
+                Ok(socket) => {
+                    connected = Some((idx, socket));
+                    break;
+                }
+                Err(reason) => last_error = reason,
+            }
+        }
+        let (idx, socket) = connected.ok_or(last_error)?;
+        self.active_url = idx;
+
+        self.session_deadline = self
+            .config
+            .max_session_ms
+            .map(|max_session_ms| Instant::now() + Duration::from_millis(max_session_ms));
+        self.next_heartbeat = heartbeat_after(&self.config, Instant::now());
+        self.socket = Some(socket);
+        Ok(())
+    }
+
+    async fn open_url(&self, url: &str) -> Result<WsStream, VenueFeedError> {
+        let mut socket = connect(url, self.config.proxy.as_ref()).await?;
+
+        if let Some(subscribe) = &self.config.subscribe_message {
+            socket
+                .send(Message::Text(subscribe.clone()))
+                .await
+                .map_err(|_| VenueFeedError::Subscribe)?;
+        }
+        Ok(socket)
+    }
+
+    async fn read(&mut self) -> Result<Option<VenueTick>, VenueFeedError> {
+        let socket = self.socket.as_mut().ok_or(VenueFeedError::Closed)?;
+        if self
+            .session_deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            let _ = socket.close(None).await;
+            self.socket = None;
+            return Err(VenueFeedError::SessionExpired);
+        }
+
+        if let (Some(due), Some(heartbeat)) = (self.next_heartbeat, &self.config.heartbeat_message)
+        {
+            let now = Instant::now();
+            if now >= due {
+                if socket.send(Message::Text(heartbeat.clone())).await.is_err() {
+                    self.socket = None;
+                    return Err(VenueFeedError::Stream);
+                }
+                self.next_heartbeat = heartbeat_after(&self.config, now);
+            }
+        }
+
+        let idle_timeout = Duration::from_millis(self.config.idle_timeout_ms);
+        let frame = tokio::time::timeout(idle_timeout, socket.next()).await;
+        let result = match frame {
+            Err(_) => Err(VenueFeedError::IdleTimeout),
+            Ok(Some(Ok(Message::Text(text)))) => match self.try_parse_frame(&text) {
+                Ok(tick) => Ok(tick),
+                Err(err) => {
+                    self.rejected
+                        .push(RejectedPayload::new(err.as_str(), &text));
+                    Ok(None)
+                }
+            },
+            Ok(Some(Ok(Message::Close(_)))) | Ok(None) => Err(VenueFeedError::Closed),
+            Ok(Some(Ok(_))) => Ok(None),
+            Ok(Some(Err(_))) => Err(VenueFeedError::Stream),
+        };
+        if result.is_err() {
+            self.socket = None;
+        }
+        result
+    }
+}
+
+/// Opens the WebSocket session, through a CONNECT tunnel when a proxy is set.
+async fn connect(url: &str, proxy: Option<&HttpProxy>) -> Result<WsStream, VenueFeedError> {
+    let Some(proxy) = proxy else {
+        let (socket, _) = connect_async(url).await.map_err(handshake_error)?;
+        return Ok(socket);
+    };
+
+    let request = url
+        .into_client_request()
+        .map_err(|_| VenueFeedError::Connect)?;
+    let uri = request.uri();
+    let host = uri.host().ok_or(VenueFeedError::Connect)?.to_string();
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+    let stream = proxy
+        .tunnel(&host, port)
+        .await
+        .map_err(|_| VenueFeedError::Connect)?;
+    let (socket, _) = client_async_tls(request, stream)
+        .await
+        .map_err(handshake_error)?;
+    Ok(socket)
+}
+
+/// A handshake the venue answered with a blocking status is reported as
+/// such, so the feed backs off instead of hammering it.
+fn handshake_error(err: WsError) -> VenueFeedError {
+    match err {
+        WsError::Http(response) => VenueFeedError::blocked_by(response.status().as_u16())
+            .unwrap_or(VenueFeedError::Connect),
+        _ => VenueFeedError::Connect,
+    }
+}
+
+fn heartbeat_after(config: &BtcWsFeedConfig, now: Instant) -> Option<Instant> {
+    config
+        .heartbeat_message
+        .as_ref()
+        .filter(|_| config.heartbeat_interval_ms > 0)
+        .map(|_| now + Duration::from_millis(config.heartbeat_interval_ms))
+}
+
+impl VenueFeed for WsBtcFeed {
+    fn venue(&self) -> &str {
+        &self.config.venue
+    }
+
+    fn connect(&mut self) -> VenueFuture<'_, Result<(), VenueFeedError>> {
+        Box::pin(self.open())
+    }
+
+    fn next_tick(&mut self) -> VenueFuture<'_, Result<Option<VenueTick>, VenueFeedError>> {
+        Box::pin(self.read())
+    }
+
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.config.reconnect
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        self.active_url()
+    }
+
+    fn drain_rejected(&mut self) -> Vec<RejectedPayload> {
+        std::mem::take(&mut self.rejected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binance_config_streams_without_subscribe_and_recycles_sessions() {
+        let config = BtcWsFeedConfig::binance(Asset::Btc);
+
+        assert_eq!(config.venue, "binance");
+        assert!(config.subscribe_message.is_none());
+        assert!(config.max_session_ms.unwrap() < 24 * 60 * 60 * 1_000);
+    }
+
+    #[test]
+    fn okx_and_bybit_configs_send_heartbeats() {
+        for config in [
+            BtcWsFeedConfig::okx(Asset::Btc),
+            BtcWsFeedConfig::bybit(Asset::Btc),
+        ] {
+            assert!(config.subscribe_message.is_some());
+            assert!(config.heartbeat_message.is_some());
+            assert!(config.heartbeat_interval_ms > 0);
+            assert!(config.heartbeat_interval_ms < config.idle_timeout_ms * 2);
+        }
+        assert!(BtcWsFeedConfig::bitstamp(Asset::Btc)
+            .heartbeat_message
+            .is_none());
+    }
+
+    #[test]
+    fn ws_feed_parses_additional_venue_frames() {
+        let okx = WsBtcFeed::new(BtcWsFeedConfig::okx(Asset::Btc));
+        let bybit = WsBtcFeed::new(BtcWsFeedConfig::bybit(Asset::Btc));
+        let bitstamp = WsBtcFeed::new(BtcWsFeedConfig::bitstamp(Asset::Btc));
+
+        assert!(okx.parse_frame("pong").is_none());
+        assert!(okx
+            .parse_frame(
+                r#"{"arg":{"channel":"trades"},"data":[{"px":"64000","sz":"0.1","ts":"1772280000000"}]}"#
+            )
+            .is_some());
+        assert!(bybit
+            .parse_frame(
+                r#"{"topic":"publicTrade.BTCUSDT","data":[{"T":1772280000000,"v":"0.1","p":"64000"}]}"#
+            )
+            .is_some());
+        assert!(bitstamp
+            .parse_frame(
+                r#"{"data":{"price_str":"64000","amount_str":"0.1","microtimestamp":"1772280000000000"},"event":"trade"}"#
+            )
+            .is_some());
+    }
+
+    #[test]
+    fn ws_feed_parses_binance_trade_frames() {
+        let feed = WsBtcFeed::new(BtcWsFeedConfig::binance(Asset::Btc));
+
+        let tick = feed.parse_frame(r#"{"e":"trade","p":"64010.00","q":"0.2","T":1772280000000}"#);
+
+        match tick {
+            Some(VenueTick::Spot(Asset::Btc, btc)) => assert_eq!(btc.px, 64_010.0),
+            other => panic!("unexpected tick: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fallback_endpoints_follow_the_last_working_one() {
+        let config = BtcWsFeedConfig::binance(Asset::Btc);
+        assert_eq!(config.urls.len(), 3);
+        assert!(config.urls[2].starts_with(BINANCE_US_WS_BASE_URL));
+
+        let mut feed = WsBtcFeed::new(config);
+        assert_eq!(feed.endpoint(), Some(feed.config().urls[0].as_str()));
+        assert_eq!(feed.url_order().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        feed.active_url = 2;
+        assert_eq!(feed.url_order().collect::<Vec<_>>(), vec![2, 0, 1]);
+        assert!(feed.endpoint().unwrap().contains("binance.us"));
+    }
+
+    #[test]
+    fn configs_subscribe_to_the_requested_asset() {
+        let eth = BtcWsFeedConfig::coinbase(Asset::Eth);
+        assert_eq!(eth.venue, "coinbase-eth");
+        assert!(eth.subscribe_message.unwrap().contains(r#"["ETH-USD"]"#));
+        assert!(BtcWsFeedConfig::binance(Asset::Sol)
+            .urls
+            .iter()
+            .all(|url| url.ends_with("/solusdt@trade")));
+        assert!(BtcWsFeedConfig::bitstamp(Asset::Btc)
+            .subscribe_message
+            .unwrap()
+            .contains("live_trades_btcusd"));
+
+        let feed = WsBtcFeed::new(BtcWsFeedConfig::okx(Asset::Sol));
+        match feed.parse_frame(
+            r#"{"arg":{"channel":"trades"},"data":[{"px":"142.5","sz":"3","ts":"1772280000000"}]}"#,
+        ) {
+            Some(VenueTick::Spot(Asset::Sol, tick)) => assert_eq!(tick.venue, "okx-sol"),
+            other => panic!("unexpected tick: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ws_feed_drops_control_and_malformed_coinbase_frames() {
+        let feed = WsBtcFeed::new(BtcWsFeedConfig::coinbase(Asset::Btc));
+
+        assert!(feed
+            .parse_frame(r#"{"type":"subscriptions","channels":[]}"#)
+            .is_none());
+        assert!(feed.parse_frame("not json").is_none());
+        assert!(feed
+            .parse_frame(
+                r#"{"type":"match","price":"64000.5","size":"0.1","time":"2026-02-28T12:00:00Z"}"#
+            )
+            .is_some());
+    }
+
+    #[test]
+    fn ws_feed_reports_venue_and_configured_backoff() {
+        let feed = WsBtcFeed::new(BtcWsFeedConfig::kraken(Asset::Btc));
+
+        assert_eq!(feed.venue(), "kraken");
+        assert_eq!(feed.reconnect_policy(), ReconnectPolicy::default());
+    }
+}
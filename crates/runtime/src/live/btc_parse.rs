@@ -13,6 +13,19 @@ pub enum ParseBtcTradeError {
     TimestampOutOfRange,
 }
 
+impl ParseBtcTradeError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidJson => "invalid_json",
+            Self::UnsupportedMessageType => "unsupported_message_type",
+            Self::InvalidPrice => "invalid_price",
+            Self::InvalidSize => "invalid_size",
+            Self::InvalidTimestamp => "invalid_timestamp",
+            Self::TimestampOutOfRange => "timestamp_out_of_range",
+        }
+    }
+}
+
 pub fn parse_coinbase_trade(raw: &str) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
     let trade: CoinbaseTrade =
         serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
@@ -21,6 +34,29 @@ pub fn parse_coinbase_trade(raw: &str) -> Result<NormalizedBtcTick, ParseBtcTrad
         return Err(ParseBtcTradeError::UnsupportedMessageType);
     }
 
+    normalize_coinbase_trade(trade)
+}
+
+/// Parses one frame from the Coinbase `matches` WebSocket channel.
+///
+/// `match` and `last_match` frames yield a tick; control frames such as
+/// `subscriptions` or `heartbeat` yield `Ok(None)` so the stream can skip them.
+pub fn parse_coinbase_ws_frame(raw: &str) -> Result<Option<NormalizedBtcTick>, ParseBtcTradeError> {
+    let envelope: CoinbaseEnvelope =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    if envelope.kind == "error" {
+        return Err(ParseBtcTradeError::UnsupportedMessageType);
+    }
+    if envelope.kind != "match" && envelope.kind != "last_match" {
+        return Ok(None);
+    }
+
+    let trade: CoinbaseTrade =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    normalize_coinbase_trade(trade).map(Some)
+}
+
+fn normalize_coinbase_trade(trade: CoinbaseTrade) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
     let px = trade
         .price
         .parse::<f64>()
@@ -45,13 +81,31 @@ pub fn parse_coinbase_trade(raw: &str) -> Result<NormalizedBtcTick, ParseBtcTrad
     })
 }
 
-fn parse_timestamp_ms(timestamp: &str) -> Result<u64, ParseBtcTradeError> {
+pub(crate) fn parse_timestamp_ms(timestamp: &str) -> Result<u64, ParseBtcTradeError> {
     let parsed = OffsetDateTime::parse(timestamp, &Rfc3339)
         .map_err(|_| ParseBtcTradeError::InvalidTimestamp)?;
     let unix_millis = parsed.unix_timestamp_nanos() / 1_000_000;
     u64::try_from(unix_millis).map_err(|_| ParseBtcTradeError::TimestampOutOfRange)
 }
 
+/// Parses a decimal string that must be finite and strictly positive.
+pub(crate) fn parse_positive_decimal(
+    value: &str,
+    error: ParseBtcTradeError,
+) -> Result<f64, ParseBtcTradeError> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|parsed| parsed.is_finite() && *parsed > 0.0)
+        .ok_or(error)
+}
+
+#[derive(Debug, Deserialize)]
+struct CoinbaseEnvelope {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct CoinbaseTrade {
     #[serde(rename = "type")]
@@ -63,7 +117,7 @@ struct CoinbaseTrade {
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_coinbase_trade, ParseBtcTradeError};
+    use super::{parse_coinbase_trade, parse_coinbase_ws_frame, ParseBtcTradeError};
 
     #[test]
     fn parses_coinbase_trade_into_normalized_tick() {
@@ -116,4 +170,31 @@ mod tests {
 
         assert_eq!(error, ParseBtcTradeError::InvalidPrice);
     }
+
+    #[test]
+    fn coinbase_ws_frame_accepts_match_and_last_match() {
+        let live = r#"{"type":"match","trade_id":1,"product_id":"BTC-USD","price":"64001.2","size":"0.01","side":"buy","time":"2026-02-28T12:00:00Z"}"#;
+        let last = r#"{"type":"last_match","price":"64000.0","size":"0.02","time":"2026-02-28T12:00:00Z"}"#;
+
+        assert_eq!(parse_coinbase_ws_frame(live).unwrap().unwrap().px, 64_001.2);
+        assert_eq!(parse_coinbase_ws_frame(last).unwrap().unwrap().px, 64_000.0);
+    }
+
+    #[test]
+    fn coinbase_ws_frame_skips_control_messages() {
+        let raw =
+            r#"{"type":"subscriptions","channels":[{"name":"matches","product_ids":["BTC-USD"]}]}"#;
+
+        assert_eq!(parse_coinbase_ws_frame(raw), Ok(None));
+    }
+
+    #[test]
+    fn coinbase_ws_frame_surfaces_error_messages() {
+        let raw = r#"{"type":"error","message":"Failed to subscribe"}"#;
+
+        assert_eq!(
+            parse_coinbase_ws_frame(raw),
+            Err(ParseBtcTradeError::UnsupportedMessageType)
+        );
+    }
 }
@@ -0,0 +1,112 @@
+use crate::live::btc_feed::NormalizedBtcTick;
+use crate::live::btc_parse::{parse_positive_decimal, ParseBtcTradeError};
+use serde::Deserialize;
+
+/// Parses one frame from the Bybit v5 spot `publicTrade` topic.
+///
+/// The most recent trade in `data` becomes the tick. Subscribe and ping
+/// acknowledgements yield `Ok(None)`; a failed operation (`"success":false`)
+/// is surfaced as [`ParseBtcTradeError::UnsupportedMessageType`].
+pub fn parse_bybit_ws_frame(raw: &str) -> Result<Option<NormalizedBtcTick>, ParseBtcTradeError> {
+    let envelope: BybitEnvelope =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    if envelope.success == Some(false) {
+        return Err(ParseBtcTradeError::UnsupportedMessageType);
+    }
+    if !envelope
+        .topic
+        .as_deref()
+        .is_some_and(|topic| topic.starts_with("publicTrade."))
+    {
+        return Ok(None);
+    }
+
+    match envelope.data.last() {
+        Some(trade) => normalize_bybit_trade(trade).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn normalize_bybit_trade(trade: &BybitTrade) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
+    let px = parse_positive_decimal(&trade.price, ParseBtcTradeError::InvalidPrice)?;
+    let size = parse_positive_decimal(&trade.volume, ParseBtcTradeError::InvalidSize)?;
+    if trade.trade_time_ms == 0 {
+        return Err(ParseBtcTradeError::InvalidTimestamp);
+    }
+
+    Ok(NormalizedBtcTick {
+        venue: "bybit".to_string(),
+        px,
+        size,
+        ts: trade.trade_time_ms,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitEnvelope {
+    #[serde(default)]
+    topic: Option<String>,
+    #[serde(default)]
+    success: Option<bool>,
+    #[serde(default)]
+    data: Vec<BybitTrade>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitTrade {
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "v")]
+    volume: String,
+    #[serde(rename = "T")]
+    trade_time_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bybit_ws_frame, ParseBtcTradeError};
+
+    #[test]
+    fn parses_latest_trade_from_bybit_snapshot() {
+        let raw = r#"{"topic":"publicTrade.BTCUSDT","type":"snapshot","ts":1772280000130,"data":[
+            {"T":1772280000100,"s":"BTCUSDT","S":"Buy","v":"0.002","p":"64000.50","L":"PlusTick","i":"1","BT":false},
+            {"T":1772280000125,"s":"BTCUSDT","S":"Sell","v":"0.4","p":"63999.80","L":"MinusTick","i":"2","BT":false}
+        ]}"#;
+        let tick = parse_bybit_ws_frame(raw).unwrap().unwrap();
+
+        assert_eq!(tick.venue, "bybit");
+        assert_eq!(tick.px, 63_999.8);
+        assert_eq!(tick.size, 0.4);
+        assert_eq!(tick.ts, 1_772_280_000_125);
+    }
+
+    #[test]
+    fn bybit_frame_skips_subscribe_and_pong_acks() {
+        assert_eq!(
+            parse_bybit_ws_frame(
+                r#"{"success":true,"ret_msg":"subscribe","conn_id":"c1","op":"subscribe"}"#
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            parse_bybit_ws_frame(r#"{"success":true,"ret_msg":"pong","conn_id":"c1","op":"ping"}"#),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn bybit_frame_surfaces_failed_operations_and_zero_timestamps() {
+        assert_eq!(
+            parse_bybit_ws_frame(
+                r#"{"success":false,"ret_msg":"error:handler not found","op":"subscribe"}"#
+            ),
+            Err(ParseBtcTradeError::UnsupportedMessageType)
+        );
+        assert_eq!(
+            parse_bybit_ws_frame(
+                r#"{"topic":"publicTrade.BTCUSDT","data":[{"T":0,"v":"0.1","p":"64000"}]}"#
+            ),
+            Err(ParseBtcTradeError::InvalidTimestamp)
+        );
+    }
+}
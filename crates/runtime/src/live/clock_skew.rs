@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+/// Per-venue clock offset tracker.
+///
+/// Each observation compares a venue-reported trade time with the local
+/// receive time. The EWMA of `received - reported` maps venue timestamps onto
+/// the local clock, folding in both exchange clock skew and typical transport
+/// delay, so staleness and lag checks compare like with like.
+#[derive(Debug, Clone)]
+pub struct ClockSkewEstimator {
+    alpha: f64,
+    max_offset_ms: f64,
+    offsets: HashMap<String, VenueOffset>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct VenueOffset {
+    offset_ms: f64,
+    samples: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSkewConfigError {
+    InvalidAlpha,
+    InvalidMaxOffset,
+}
+
+impl ClockSkewEstimator {
+    /// `alpha` weights the newest sample; offsets beyond `max_offset_ms` are
+    /// treated as bad samples (replayed or buffered frames) and ignored.
+    pub fn new(alpha: f64, max_offset_ms: f64) -> Result<Self, ClockSkewConfigError> {
+        if !alpha.is_finite() || alpha <= 0.0 || alpha > 1.0 {
+            return Err(ClockSkewConfigError::InvalidAlpha);
+        }
+        if !max_offset_ms.is_finite() || max_offset_ms <= 0.0 {
+            return Err(ClockSkewConfigError::InvalidMaxOffset);
+        }
+
+        Ok(Self {
+            alpha,
+            max_offset_ms,
+            offsets: HashMap::new(),
+        })
+    }
+
+    /// Folds one sample into the venue's offset and returns the updated estimate.
+    pub fn observe(&mut self, venue: &str, venue_ts: u64, received_ts: u64) -> Option<f64> {
+        let sample = received_ts as f64 - venue_ts as f64;
+        if sample.abs() > self.max_offset_ms {
+            return self.offset_ms(venue);
+        }
+
+        let alpha = self.alpha;
+        let entry = self
+            .offsets
+            .entry(venue.to_string())
+            .and_modify(|current| {
+                current.offset_ms += alpha * (sample - current.offset_ms);
+                current.samples = current.samples.saturating_add(1);
+            })
+            .or_insert(VenueOffset {
+                offset_ms: sample,
+                samples: 1,
+            });
+        Some(entry.offset_ms)
+    }
+
+    pub fn offset_ms(&self, venue: &str) -> Option<f64> {
+        self.offsets.get(venue).map(|entry| entry.offset_ms)
+    }
+
+    pub fn samples(&self, venue: &str) -> u64 {
+        self.offsets.get(venue).map_or(0, |entry| entry.samples)
+    }
+
+    /// Venue timestamp shifted onto the local clock; unchanged until the venue
+    /// has an estimate.
+    pub fn adjust(&self, venue: &str, venue_ts: u64) -> u64 {
+        match self.offset_ms(venue) {
+            Some(offset) => (venue_ts as f64 + offset).round().max(0.0) as u64,
+            None => venue_ts,
+        }
+    }
+
+    /// `(venue, offset_ms)` pairs sorted by venue.
+    pub fn offsets(&self) -> Vec<(String, f64)> {
+        let mut offsets: Vec<(String, f64)> = self
+            .offsets
+            .iter()
+            .map(|(venue, entry)| (venue.clone(), entry.offset_ms))
+            .collect();
+        offsets.sort_by(|left, right| left.0.cmp(&right.0));
+        offsets
+    }
+}
+
+impl Default for ClockSkewEstimator {
+    fn default() -> Self {
+        Self {
+            alpha: 0.05,
+            max_offset_ms: 60_000.0,
+            offsets: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sample_seeds_offset_and_later_samples_smooth() {
+        let mut estimator = ClockSkewEstimator::new(0.5, 60_000.0).unwrap();
+
+        assert_eq!(estimator.observe("coinbase", 10_000, 10_200), Some(200.0));
+        assert_eq!(estimator.observe("coinbase", 11_000, 11_100), Some(150.0));
+        assert_eq!(estimator.samples("coinbase"), 2);
+        assert_eq!(estimator.offset_ms("kraken"), None);
+    }
+
+    #[test]
+    fn adjust_maps_venue_time_onto_local_clock() {
+        let mut estimator = ClockSkewEstimator::new(1.0, 60_000.0).unwrap();
+        estimator.observe("bitstamp", 20_000, 19_250);
+
+        assert_eq!(estimator.adjust("bitstamp", 21_000), 20_250);
+        assert_eq!(estimator.adjust("okx", 21_000), 21_000);
+    }
+
+    #[test]
+    fn ignores_implausible_offsets() {
+        let mut estimator = ClockSkewEstimator::new(0.5, 5_000.0).unwrap();
+        estimator.observe("bybit", 10_000, 10_100);
+
+        assert_eq!(estimator.observe("bybit", 10_000, 90_000), Some(100.0));
+        assert_eq!(estimator.samples("bybit"), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert_eq!(
+            ClockSkewEstimator::new(0.0, 1_000.0).unwrap_err(),
+            ClockSkewConfigError::InvalidAlpha
+        );
+        assert_eq!(
+            ClockSkewEstimator::new(0.2, f64::NAN).unwrap_err(),
+            ClockSkewConfigError::InvalidMaxOffset
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::live::{BtcMedianTick, MedianAggregator, NormalizedBtcTick, VwapAggregator};
+
+/// Raw and smoothed BTC composite read together.
+///
+/// `raw` is the aggregator output as-is; `smoothed_px` is the same series after
+/// the EWMA stage, a steadier reference for lag checks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BtcComposite {
+    pub raw: BtcMedianTick,
+    pub smoothed_px: f64,
+}
+
+/// The BTC composite stage selected at startup.
+#[derive(Debug, Clone)]
+pub enum BtcAggregator {
+    /// One vote per venue: the median of each venue's latest print.
+    Median(MedianAggregator),
+    /// Size-weighted average over a trailing window of trades.
+    Vwap(VwapAggregator),
+}
+
+impl BtcAggregator {
+    pub fn ingest(&mut self, tick: NormalizedBtcTick) {
+        match self {
+            Self::Median(aggregator) => aggregator.ingest(tick),
+            Self::Vwap(aggregator) => aggregator.ingest(tick),
+        }
+    }
+
+    pub fn latest(&self, venue: &str) -> Option<&NormalizedBtcTick> {
+        match self {
+            Self::Median(aggregator) => aggregator.latest(venue),
+            Self::Vwap(aggregator) => aggregator.latest(venue),
+        }
+    }
+
+    pub fn compute(&self) -> Option<BtcMedianTick> {
+        match self {
+            Self::Median(aggregator) => aggregator.compute(),
+            Self::Vwap(aggregator) => aggregator.compute(),
+        }
+    }
+}
+
+impl From<MedianAggregator> for BtcAggregator {
+    fn from(aggregator: MedianAggregator) -> Self {
+        Self::Median(aggregator)
+    }
+}
+
+impl From<VwapAggregator> for BtcAggregator {
+    fn from(aggregator: VwapAggregator) -> Self {
+        Self::Vwap(aggregator)
+    }
+}
@@ -0,0 +1,102 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EwmaSmootherConfigError {
+    /// `half_life_ms` must be greater than zero.
+    InvalidHalfLife,
+}
+
+/// Time-aware exponential smoothing for the BTC composite.
+///
+/// The weight given to each new value depends on the time since the previous
+/// one, so a sample arriving after one half-life moves the smoothed value half
+/// way towards it regardless of how irregularly venues print.
+#[derive(Debug, Clone)]
+pub struct EwmaSmoother {
+    half_life_ms: u64,
+    state: Option<(f64, u64)>,
+}
+
+impl EwmaSmoother {
+    pub fn new(half_life_ms: u64) -> Result<Self, EwmaSmootherConfigError> {
+        if half_life_ms == 0 {
+            return Err(EwmaSmootherConfigError::InvalidHalfLife);
+        }
+
+        Ok(Self {
+            half_life_ms,
+            state: None,
+        })
+    }
+
+    pub fn half_life_ms(&self) -> u64 {
+        self.half_life_ms
+    }
+
+    /// Folds `value` observed at `ts` into the average and returns it.
+    ///
+    /// The first value seeds the average. Non-finite values and samples older
+    /// than the last one leave the average unchanged.
+    pub fn update(&mut self, value: f64, ts: u64) -> Option<f64> {
+        if !value.is_finite() {
+            return self.value();
+        }
+
+        let next = match self.state {
+            None => value,
+            Some((_, last_ts)) if ts < last_ts => return self.value(),
+            Some((current, last_ts)) => {
+                let elapsed = (ts - last_ts) as f64;
+                let alpha = 1.0 - 0.5_f64.powf(elapsed / self.half_life_ms as f64);
+                current + alpha * (value - current)
+            }
+        };
+        self.state = Some((next, ts));
+        Some(next)
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.state.map(|(value, _)| value)
+    }
+
+    pub fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+impl Default for EwmaSmoother {
+    fn default() -> Self {
+        Self {
+            half_life_ms: 2_000,
+            state: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_half_life_moves_half_way() {
+        let mut smoother = EwmaSmoother::new(1_000).unwrap();
+
+        assert_eq!(smoother.update(100.0, 10_000), Some(100.0));
+        assert_eq!(smoother.update(200.0, 11_000), Some(150.0));
+        assert_eq!(smoother.update(150.0, 11_000), Some(150.0));
+    }
+
+    #[test]
+    fn ignores_out_of_order_and_non_finite_samples() {
+        let mut smoother = EwmaSmoother::new(1_000).unwrap();
+        smoother.update(100.0, 10_000);
+
+        assert_eq!(smoother.update(500.0, 9_000), Some(100.0));
+        assert_eq!(smoother.update(f64::NAN, 12_000), Some(100.0));
+
+        smoother.reset();
+        assert_eq!(smoother.value(), None);
+        assert_eq!(
+            EwmaSmoother::new(0).unwrap_err(),
+            EwmaSmootherConfigError::InvalidHalfLife
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use serde::Deserialize;
+
+use crate::live::polymarket_discovery::{parse_end_date_ms, parse_strike_usd, MarketMetadata};
+use crate::live::polymarket_quote::{QuoteSource, RawPolymarketQuote};
+use crate::positions::MarketOutcome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseKalshiMarketsError {
+    InvalidJson,
+}
+
+/// One market row from the Kalshi `GET /trade-api/v2/markets` response.
+///
+/// Kalshi quotes YES prices in integer cents; a side with no resting orders is
+/// reported as `0` (bid) or `100` (ask).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct KalshiMarket {
+    pub ticker: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub yes_bid: Option<u32>,
+    #[serde(default)]
+    pub yes_ask: Option<u32>,
+    #[serde(default)]
+    pub close_time: Option<String>,
+    /// Resting liquidity in cents.
+    #[serde(default)]
+    pub liquidity: Option<u64>,
+    /// Contracts traded over the last day; each pays at most $1.
+    #[serde(default)]
+    pub volume_24h: Option<u64>,
+    /// `"yes"` or `"no"` once the market is determined, empty before.
+    #[serde(default)]
+    pub result: Option<String>,
+}
+
+impl KalshiMarket {
+    /// Converts the cent-denominated top of book into a YES quote.
+    ///
+    /// Returns `None` when either side of the book is empty.
+    pub fn to_raw_quote(&self, ts: u64) -> Option<RawPolymarketQuote> {
+        let bid = self.yes_bid.filter(|cents| (1..100).contains(cents))?;
+        let ask = self.yes_ask.filter(|cents| (1..100).contains(cents))?;
+
+        Some(RawPolymarketQuote {
+            market_slug: self.ticker.clone(),
+            source: QuoteSource::Kalshi,
+            best_yes_bid: f64::from(bid) / 100.0,
+            best_yes_ask: f64::from(ask) / 100.0,
+            ts,
+        })
+    }
+
+    pub fn outcome(&self) -> Option<MarketOutcome> {
+        match self.result.as_deref()?.trim() {
+            "yes" => Some(MarketOutcome::Yes),
+            "no" => Some(MarketOutcome::No),
+            _ => None,
+        }
+    }
+
+    /// Expiry, strike, USD liquidity and volume, and the listed spread;
+    /// `None` without a parseable `close_time`.
+    pub fn metadata(&self) -> Option<MarketMetadata> {
+        Some(MarketMetadata {
+            slug: self.ticker.clone(),
+            end_ms: parse_end_date_ms(self.close_time.as_deref()?)?,
+            strike: parse_strike_usd(&self.title),
+            liquidity: self.liquidity.unwrap_or(0) as f64 / 100.0,
+            volume: self.volume_24h.unwrap_or(0) as f64,
+            spread: self
+                .to_raw_quote(0)
+                .map(|quote| quote.best_yes_ask - quote.best_yes_bid),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KalshiMarketsResponse {
+    markets: Vec<KalshiMarket>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KalshiMarketResponse {
+    market: KalshiMarket,
+}
+
+pub fn parse_kalshi_markets(raw: &str) -> Result<Vec<KalshiMarket>, ParseKalshiMarketsError> {
+    serde_json::from_str::<KalshiMarketsResponse>(raw)
+        .map(|response| response.markets)
+        .map_err(|_| ParseKalshiMarketsError::InvalidJson)
+}
+
+/// Parses the single-market `GET /trade-api/v2/markets/{ticker}` response.
+pub fn parse_kalshi_market(raw: &str) -> Result<KalshiMarket, ParseKalshiMarketsError> {
+    serde_json::from_str::<KalshiMarketResponse>(raw)
+        .map(|response| response.market)
+        .map_err(|_| ParseKalshiMarketsError::InvalidJson)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_kalshi_market, parse_kalshi_markets, ParseKalshiMarketsError};
+    use crate::live::QuoteSource;
+    use crate::positions::MarketOutcome;
+
+    #[test]
+    fn parses_kalshi_markets_and_normalizes_cent_quotes() {
+        let raw = r#"{"markets":[{"ticker":"KXBTC15M-26FEB281215-15","title":"BTC price up in next 15 mins?","yes_bid":47,"yes_ask":53,"status":"active"}],"cursor":""}"#;
+
+        let markets = parse_kalshi_markets(raw).unwrap();
+        let quote = markets[0].to_raw_quote(9).unwrap().normalize().unwrap();
+
+        assert_eq!(quote.market_slug, "KXBTC15M-26FEB281215-15");
+        assert_eq!(quote.source, QuoteSource::Kalshi);
+        assert_eq!(quote.best_yes_bid, 0.47);
+        assert_eq!(quote.best_yes_ask, 0.53);
+        assert!((quote.mid_yes - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn kalshi_market_without_two_sided_book_has_no_quote() {
+        let raw = r#"{"markets":[
+            {"ticker":"KXBTC15M-A","yes_bid":0,"yes_ask":52},
+            {"ticker":"KXBTC15M-B","yes_bid":48,"yes_ask":100},
+            {"ticker":"KXBTC15M-C"}
+        ]}"#;
+
+        let markets = parse_kalshi_markets(raw).unwrap();
+
+        assert!(markets
+            .iter()
+            .all(|market| market.to_raw_quote(1).is_none()));
+    }
+
+    #[test]
+    fn kalshi_metadata_reads_close_time_liquidity_and_spread() {
+        let raw = r#"{"markets":[
+            {"ticker":"KXBTC15M-A","title":"BTC above $66,000?","close_time":"2026-02-28T12:15:00Z","liquidity":1250050,"volume_24h":830,"yes_bid":47,"yes_ask":50},
+            {"ticker":"KXBTC15M-B","title":"BTC up?"}
+        ]}"#;
+
+        let markets = parse_kalshi_markets(raw).unwrap();
+        let metadata = markets[0].metadata().unwrap();
+
+        assert_eq!(metadata.end_ms, 1_772_280_900_000);
+        assert_eq!(metadata.strike, Some(66_000.0));
+        assert_eq!(metadata.liquidity, 12_500.5);
+        assert_eq!(metadata.volume, 830.0);
+        assert!((metadata.spread.unwrap() - 0.03).abs() < 1e-12);
+        assert!(markets[1].metadata().is_none());
+    }
+
+    #[test]
+    fn kalshi_single_market_reports_result_once_determined() {
+        let settled = parse_kalshi_market(
+            r#"{"market":{"ticker":"KXBTC15M-A","status":"finalized","result":"no"}}"#,
+        )
+        .unwrap();
+        let open = parse_kalshi_market(
+            r#"{"market":{"ticker":"KXBTC15M-B","status":"active","result":""}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(settled.outcome(), Some(MarketOutcome::No));
+        assert_eq!(open.outcome(), None);
+    }
+
+    #[test]
+    fn rejects_payload_without_markets_array() {
+        assert_eq!(
+            parse_kalshi_markets(r#"{"error":"unauthorized"}"#),
+            Err(ParseKalshiMarketsError::InvalidJson)
+        );
+    }
+}
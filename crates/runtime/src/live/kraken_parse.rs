@@ -0,0 +1,115 @@
+use crate::live::btc_feed::NormalizedBtcTick;
+use crate::live::btc_parse::{parse_timestamp_ms, ParseBtcTradeError};
+use serde::Deserialize;
+
+/// Parses one frame from the Kraken WebSocket v2 `trade` channel.
+///
+/// Kraken batches trades per frame; the most recent trade in `data` becomes the
+/// tick. Heartbeats, status updates and subscribe acknowledgements yield
+/// `Ok(None)`.
+pub fn parse_kraken_ws_frame(raw: &str) -> Result<Option<NormalizedBtcTick>, ParseBtcTradeError> {
+    let envelope: KrakenEnvelope =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    if envelope.success == Some(false) {
+        return Err(ParseBtcTradeError::UnsupportedMessageType);
+    }
+    if envelope.channel.as_deref() != Some("trade") {
+        return Ok(None);
+    }
+
+    let message: KrakenTradeMessage =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    match message.data.last() {
+        Some(trade) => normalize_kraken_trade(trade).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn normalize_kraken_trade(trade: &KrakenTrade) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
+    if !trade.price.is_finite() || trade.price <= 0.0 {
+        return Err(ParseBtcTradeError::InvalidPrice);
+    }
+    if !trade.qty.is_finite() || trade.qty <= 0.0 {
+        return Err(ParseBtcTradeError::InvalidSize);
+    }
+
+    Ok(NormalizedBtcTick {
+        venue: "kraken".to_string(),
+        px: trade.price,
+        size: trade.qty,
+        ts: parse_timestamp_ms(&trade.timestamp)?,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenEnvelope {
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    success: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenTradeMessage {
+    data: Vec<KrakenTrade>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenTrade {
+    price: f64,
+    qty: f64,
+    timestamp: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_kraken_ws_frame, ParseBtcTradeError};
+
+    #[test]
+    fn parses_latest_trade_from_kraken_batch() {
+        let raw = r#"{"channel":"trade","type":"update","data":[
+            {"symbol":"BTC/USD","side":"buy","price":64000.1,"qty":0.01,"ord_type":"market","trade_id":1,"timestamp":"2026-02-28T12:00:00.000000Z"},
+            {"symbol":"BTC/USD","side":"sell","price":64002.5,"qty":0.2,"ord_type":"limit","trade_id":2,"timestamp":"2026-02-28T12:00:00.250000Z"}
+        ]}"#;
+        let tick = parse_kraken_ws_frame(raw).unwrap().unwrap();
+
+        assert_eq!(tick.venue, "kraken");
+        assert_eq!(tick.px, 64_002.5);
+        assert_eq!(tick.size, 0.2);
+        assert_eq!(tick.ts, 1_772_280_000_250);
+    }
+
+    #[test]
+    fn kraken_frame_skips_heartbeats_and_acks() {
+        assert_eq!(
+            parse_kraken_ws_frame(r#"{"channel":"heartbeat"}"#),
+            Ok(None)
+        );
+        assert_eq!(
+            parse_kraken_ws_frame(
+                r#"{"method":"subscribe","result":{"channel":"trade","symbol":"BTC/USD"},"success":true}"#
+            ),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn kraken_frame_surfaces_failed_subscriptions() {
+        let raw = r#"{"method":"subscribe","error":"Currency pair not supported","success":false}"#;
+
+        assert_eq!(
+            parse_kraken_ws_frame(raw),
+            Err(ParseBtcTradeError::UnsupportedMessageType)
+        );
+    }
+
+    #[test]
+    fn kraken_frame_rejects_non_positive_price() {
+        let raw = r#"{"channel":"trade","type":"update","data":[{"price":0.0,"qty":0.1,"timestamp":"2026-02-28T12:00:00Z"}]}"#;
+
+        assert_eq!(
+            parse_kraken_ws_frame(raw),
+            Err(ParseBtcTradeError::InvalidPrice)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, VecDeque};
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -23,6 +25,26 @@ pub fn detect_lag(
     fair_yes_px: f64,
     threshold_pct: f64,
 ) -> Result<LagSignal, LagError> {
+    let (divergence_pct, triggered) =
+        lag_divergence(market_id, poly_mid, fair_yes_px, threshold_pct)?;
+
+    Ok(LagSignal {
+        market_id: market_id.to_string(),
+        poly_mid,
+        fair_yes_px,
+        divergence_pct,
+        triggered,
+    })
+}
+
+/// Divergence in percent of the mid and whether it crosses the threshold,
+/// without building a [`LagSignal`].
+pub(crate) fn lag_divergence(
+    market_id: &str,
+    poly_mid: f64,
+    fair_yes_px: f64,
+    threshold_pct: f64,
+) -> Result<(f64, bool), LagError> {
     if market_id.trim().is_empty() {
         return Err(LagError::InvalidMarketId);
     }
@@ -37,15 +59,279 @@ pub fn detect_lag(
     }
 
     let divergence_pct = ((fair_yes_px - poly_mid) / poly_mid) * 100.0;
-    let triggered = divergence_pct.abs() > threshold_pct;
+    Ok((divergence_pct, divergence_pct.abs() > threshold_pct))
+}
 
-    Ok(LagSignal {
-        market_id: market_id.to_string(),
-        poly_mid,
-        fair_yes_px,
-        divergence_pct,
-        triggered,
-    })
+/// Outcome of passing a lag signal through a [`LagTriggerGate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LagGateDecision {
+    /// The signal did not trigger; any pending streak was reset.
+    Idle,
+    /// The signal triggered but has not persisted for enough evaluations yet.
+    Debouncing { seen: u32, required: u32 },
+    /// The market traded too recently; `remaining_ms` until it may trade again.
+    CoolingDown { remaining_ms: u64 },
+    /// The signal persisted and the market is outside its cooldown window.
+    Admit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LagGateConfigError {
+    /// `min_consecutive` must be at least one evaluation.
+    InvalidMinConsecutive,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct MarketGateState {
+    streak: u32,
+    streak_direction: i8,
+    last_admit_ms: Option<u64>,
+}
+
+/// Per-market debounce and cooldown applied on top of [`detect_lag`].
+///
+/// A market is admitted only after its lag signal has triggered in the same
+/// direction for `min_consecutive` evaluations in a row, and at least
+/// `cooldown_ms` has elapsed since the market was last admitted.
+#[derive(Debug, Clone)]
+pub struct LagTriggerGate {
+    cooldown_ms: u64,
+    min_consecutive: u32,
+    by_market: HashMap<String, MarketGateState>,
+}
+
+impl LagTriggerGate {
+    pub fn new(cooldown_ms: u64, min_consecutive: u32) -> Result<Self, LagGateConfigError> {
+        if min_consecutive == 0 {
+            return Err(LagGateConfigError::InvalidMinConsecutive);
+        }
+
+        Ok(Self {
+            cooldown_ms,
+            min_consecutive,
+            by_market: HashMap::new(),
+        })
+    }
+
+    pub fn cooldown_ms(&self) -> u64 {
+        self.cooldown_ms
+    }
+
+    pub fn min_consecutive(&self) -> u32 {
+        self.min_consecutive
+    }
+
+    /// Updates gate parameters in place, keeping per-market streaks and
+    /// cooldown timestamps so a settings change does not re-arm every market.
+    pub fn reconfigure(
+        &mut self,
+        cooldown_ms: u64,
+        min_consecutive: u32,
+    ) -> Result<(), LagGateConfigError> {
+        if min_consecutive == 0 {
+            return Err(LagGateConfigError::InvalidMinConsecutive);
+        }
+
+        self.cooldown_ms = cooldown_ms;
+        self.min_consecutive = min_consecutive;
+        Ok(())
+    }
+
+    /// [`Self::check_market`] followed by [`Self::commit`] on admission, for
+    /// callers that act on every admitted signal.
+    pub fn evaluate(&mut self, signal: &LagSignal, now_ms: u64) -> LagGateDecision {
+        let decision = self.check_market(
+            &signal.market_id,
+            signal.triggered,
+            signal.divergence_pct,
+            now_ms,
+        );
+        if decision == LagGateDecision::Admit {
+            self.commit(&signal.market_id, now_ms);
+        }
+        decision
+    }
+
+    /// Updates the market's debounce streak and reports whether a signal
+    /// would be admitted. Admission does not start the cooldown; call
+    /// [`Self::commit`] once the signal actually produces an order, so a
+    /// signal rejected further down does not hold back the next one. The
+    /// market id is only copied the first time the market is seen.
+    pub fn check_market(
+        &mut self,
+        market_id: &str,
+        triggered: bool,
+        divergence_pct: f64,
+        now_ms: u64,
+    ) -> LagGateDecision {
+        if !self.by_market.contains_key(market_id) {
+            self.by_market
+                .insert(market_id.to_string(), MarketGateState::default());
+        }
+        let state = self
+            .by_market
+            .get_mut(market_id)
+            .expect("gate state was inserted above");
+
+        if !triggered {
+            state.streak = 0;
+            state.streak_direction = 0;
+            return LagGateDecision::Idle;
+        }
+
+        let direction = if divergence_pct > 0.0 { 1 } else { -1 };
+        if state.streak_direction == direction {
+            state.streak = state.streak.saturating_add(1);
+        } else {
+            state.streak = 1;
+            state.streak_direction = direction;
+        }
+
+        if state.streak < self.min_consecutive {
+            return LagGateDecision::Debouncing {
+                seen: state.streak,
+                required: self.min_consecutive,
+            };
+        }
+
+        if let Some(last_admit_ms) = state.last_admit_ms {
+            let elapsed_ms = now_ms.saturating_sub(last_admit_ms);
+            if elapsed_ms < self.cooldown_ms {
+                return LagGateDecision::CoolingDown {
+                    remaining_ms: self.cooldown_ms - elapsed_ms,
+                };
+            }
+        }
+
+        LagGateDecision::Admit
+    }
+
+    /// Starts the market's cooldown at `now_ms`, after an admitted signal
+    /// became an order.
+    pub fn commit(&mut self, market_id: &str, now_ms: u64) {
+        if let Some(state) = self.by_market.get_mut(market_id) {
+            state.last_admit_ms = Some(now_ms);
+        }
+    }
+}
+
+/// Lag signal enriched with the rolling-window view kept by [`WindowedLagDetector`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowedLagSignal {
+    /// Single-sample signal for the latest observation.
+    pub signal: LagSignal,
+    /// Samples in the window over threshold in the latest sample's direction.
+    pub over_threshold: u32,
+    /// Number of samples currently held for the market.
+    pub samples: u32,
+    /// True once `over_threshold` reaches the detector's `min_over_threshold`.
+    pub sustained: bool,
+    /// Fraction of the window over threshold times the mean divergence of
+    /// those samples in threshold units; zero unless `sustained`.
+    pub strength: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowedLagConfigError {
+    InvalidThresholdPct,
+    InvalidWindowLen,
+    InvalidMinOverThreshold,
+}
+
+/// Rolling-window lag detector that ignores single-tick divergence spikes.
+///
+/// Each market keeps its last `window_len` divergence samples. A market is
+/// reported as `sustained` only when at least `min_over_threshold` of them
+/// exceed the threshold in the same direction as the latest sample.
+#[derive(Debug, Clone)]
+pub struct WindowedLagDetector {
+    threshold_pct: f64,
+    window_len: usize,
+    min_over_threshold: usize,
+    by_market: HashMap<String, VecDeque<f64>>,
+}
+
+impl WindowedLagDetector {
+    pub fn new(
+        threshold_pct: f64,
+        window_len: usize,
+        min_over_threshold: usize,
+    ) -> Result<Self, WindowedLagConfigError> {
+        if !threshold_pct.is_finite() || threshold_pct <= 0.0 || threshold_pct > 100.0 {
+            return Err(WindowedLagConfigError::InvalidThresholdPct);
+        }
+        if window_len == 0 {
+            return Err(WindowedLagConfigError::InvalidWindowLen);
+        }
+        if min_over_threshold == 0 || min_over_threshold > window_len {
+            return Err(WindowedLagConfigError::InvalidMinOverThreshold);
+        }
+
+        Ok(Self {
+            threshold_pct,
+            window_len,
+            min_over_threshold,
+            by_market: HashMap::new(),
+        })
+    }
+
+    pub fn threshold_pct(&self) -> f64 {
+        self.threshold_pct
+    }
+
+    pub fn window_len(&self) -> usize {
+        self.window_len
+    }
+
+    pub fn min_over_threshold(&self) -> usize {
+        self.min_over_threshold
+    }
+
+    pub fn observe(
+        &mut self,
+        market_id: &str,
+        poly_mid: f64,
+        fair_yes_px: f64,
+    ) -> Result<WindowedLagSignal, LagError> {
+        let signal = detect_lag(market_id, poly_mid, fair_yes_px, self.threshold_pct)?;
+
+        let window = self.by_market.entry(signal.market_id.clone()).or_default();
+        if window.len() == self.window_len {
+            window.pop_front();
+        }
+        window.push_back(signal.divergence_pct);
+
+        let direction = signal.divergence_pct.signum();
+        let (over_threshold, divergence_sum) = window
+            .iter()
+            .filter(|divergence| {
+                divergence.abs() > self.threshold_pct && divergence.signum() == direction
+            })
+            .fold((0_usize, 0.0_f64), |(count, sum), divergence| {
+                (count + 1, sum + divergence.abs())
+            });
+
+        let sustained = signal.triggered && over_threshold >= self.min_over_threshold;
+        let strength = if sustained {
+            let over_fraction = over_threshold as f64 / self.window_len as f64;
+            let mean_ratio = divergence_sum / over_threshold as f64 / self.threshold_pct;
+            over_fraction * mean_ratio
+        } else {
+            0.0
+        };
+
+        Ok(WindowedLagSignal {
+            over_threshold: over_threshold as u32,
+            samples: window.len() as u32,
+            sustained,
+            strength,
+            signal,
+        })
+    }
+
+    pub fn reset_market(&mut self, market_id: &str) {
+        self.by_market.remove(market_id);
+    }
 }
 
 #[cfg(test)]
@@ -65,4 +351,131 @@ mod tests {
 
         assert!(!signal.triggered);
     }
+
+    #[test]
+    fn gate_requires_consecutive_triggers_in_same_direction() {
+        let mut gate = LagTriggerGate::new(0, 3).unwrap();
+        let up = detect_lag("btc", 0.50, 0.52, 0.3).unwrap();
+        let down = detect_lag("btc", 0.50, 0.48, 0.3).unwrap();
+
+        assert_eq!(
+            gate.evaluate(&up, 0),
+            LagGateDecision::Debouncing {
+                seen: 1,
+                required: 3
+            }
+        );
+        assert_eq!(
+            gate.evaluate(&down, 1),
+            LagGateDecision::Debouncing {
+                seen: 1,
+                required: 3
+            }
+        );
+        assert_eq!(
+            gate.evaluate(&down, 2),
+            LagGateDecision::Debouncing {
+                seen: 2,
+                required: 3
+            }
+        );
+        assert_eq!(gate.evaluate(&down, 3), LagGateDecision::Admit);
+    }
+
+    #[test]
+    fn gate_resets_streak_when_signal_stops_triggering() {
+        let mut gate = LagTriggerGate::new(0, 2).unwrap();
+        let triggered = detect_lag("btc", 0.50, 0.52, 0.3).unwrap();
+        let quiet = detect_lag("btc", 0.50, 0.5005, 0.3).unwrap();
+
+        gate.evaluate(&triggered, 0);
+        assert_eq!(gate.evaluate(&quiet, 1), LagGateDecision::Idle);
+        assert!(matches!(
+            gate.evaluate(&triggered, 2),
+            LagGateDecision::Debouncing { seen: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn gate_enforces_cooldown_per_market() {
+        let mut gate = LagTriggerGate::new(30_000, 1).unwrap();
+        let btc = detect_lag("btc", 0.50, 0.52, 0.3).unwrap();
+        let eth = detect_lag("eth", 0.50, 0.52, 0.3).unwrap();
+
+        assert_eq!(gate.evaluate(&btc, 1_000), LagGateDecision::Admit);
+        assert_eq!(
+            gate.evaluate(&btc, 11_000),
+            LagGateDecision::CoolingDown {
+                remaining_ms: 20_000
+            }
+        );
+        assert_eq!(gate.evaluate(&eth, 11_000), LagGateDecision::Admit);
+        assert_eq!(gate.evaluate(&btc, 31_000), LagGateDecision::Admit);
+    }
+
+    #[test]
+    fn gate_rejects_zero_persistence_requirement() {
+        assert_eq!(
+            LagTriggerGate::new(1_000, 0).unwrap_err(),
+            LagGateConfigError::InvalidMinConsecutive
+        );
+    }
+
+    #[test]
+    fn windowed_detector_ignores_single_tick_spike() {
+        let mut detector = WindowedLagDetector::new(0.3, 5, 3).unwrap();
+
+        detector.observe("btc", 0.50, 0.5005).unwrap();
+        let spike = detector.observe("btc", 0.50, 0.52).unwrap();
+        let after = detector.observe("btc", 0.50, 0.5005).unwrap();
+
+        assert!(spike.signal.triggered);
+        assert!(!spike.sustained);
+        assert_eq!(spike.strength, 0.0);
+        assert!(!after.sustained);
+    }
+
+    #[test]
+    fn windowed_detector_requires_sustained_divergence_in_one_direction() {
+        let mut detector = WindowedLagDetector::new(0.3, 5, 3).unwrap();
+
+        detector.observe("btc", 0.50, 0.52).unwrap();
+        detector.observe("btc", 0.50, 0.48).unwrap();
+        let second_up = detector.observe("btc", 0.50, 0.52).unwrap();
+        assert_eq!(second_up.over_threshold, 2);
+        assert!(!second_up.sustained);
+
+        detector.observe("btc", 0.50, 0.5005).unwrap();
+        let third_up = detector.observe("btc", 0.50, 0.52).unwrap();
+        assert_eq!(third_up.samples, 5);
+        assert_eq!(third_up.over_threshold, 3);
+        assert!(third_up.sustained);
+        assert!((third_up.strength - 0.6 * (4.0 / 0.3)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn windowed_detector_drops_samples_outside_window() {
+        let mut detector = WindowedLagDetector::new(0.3, 3, 2).unwrap();
+
+        detector.observe("btc", 0.50, 0.52).unwrap();
+        detector.observe("btc", 0.50, 0.5005).unwrap();
+        detector.observe("btc", 0.50, 0.5005).unwrap();
+        let latest = detector.observe("btc", 0.50, 0.52).unwrap();
+
+        assert_eq!(latest.samples, 3);
+        assert_eq!(latest.over_threshold, 1);
+        assert!(!latest.sustained);
+    }
+
+    #[test]
+    fn windowed_detector_rejects_unreachable_persistence() {
+        assert_eq!(
+            WindowedLagDetector::new(0.3, 3, 4).unwrap_err(),
+            WindowedLagConfigError::InvalidMinOverThreshold
+        );
+        assert_eq!(
+            WindowedLagDetector::new(0.3, 0, 0).unwrap_err(),
+            WindowedLagConfigError::InvalidWindowLen
+        );
+    }
 }
@@ -8,12 +8,29 @@ pub enum MedianAggregatorConfigError {
     InvalidStalenessMs,
     /// `outlier_bps` must be finite and non-negative.
     InvalidOutlierBps,
+    /// The MAD multiplier must be finite and greater than zero.
+    InvalidMadMultiplier,
+}
+
+/// Scale factor that makes the median absolute deviation a consistent
+/// estimator of the standard deviation for normally distributed prices.
+const MAD_TO_SIGMA: f64 = 1.4826;
+
+/// How far from the baseline median a venue may print before it is dropped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutlierFilter {
+    /// Fixed band of `bps` basis points around the baseline median.
+    FixedBps(f64),
+    /// Band of `k` scaled median absolute deviations of the fresh tick set,
+    /// never narrower than `floor_bps`, so it widens with dispersion during
+    /// volatile periods and does not collapse to zero when venues agree.
+    Mad { k: f64, floor_bps: f64 },
 }
 
 #[derive(Debug, Clone)]
 pub struct MedianAggregator {
     staleness_ms: u64,
-    outlier_bps: f64,
+    outlier: OutlierFilter,
     latest_by_venue: HashMap<String, NormalizedBtcTick>,
 }
 
@@ -35,11 +52,45 @@ impl MedianAggregator {
 
         Ok(Self {
             staleness_ms,
-            outlier_bps,
+            outlier: OutlierFilter::FixedBps(outlier_bps),
+            latest_by_venue: HashMap::new(),
+        })
+    }
+
+    /// Creates a median aggregator whose outlier band adapts to the spread of
+    /// the fresh tick set (see [`OutlierFilter::Mad`]).
+    ///
+    /// Returns an error when `staleness_ms == 0`, when `mad_k` is not finite
+    /// and positive, or when `floor_bps` is not finite or negative.
+    pub fn robust(
+        staleness_ms: u64,
+        mad_k: f64,
+        floor_bps: f64,
+    ) -> Result<Self, MedianAggregatorConfigError> {
+        if staleness_ms == 0 {
+            return Err(MedianAggregatorConfigError::InvalidStalenessMs);
+        }
+        if !mad_k.is_finite() || mad_k <= 0.0 {
+            return Err(MedianAggregatorConfigError::InvalidMadMultiplier);
+        }
+        if !floor_bps.is_finite() || floor_bps < 0.0 {
+            return Err(MedianAggregatorConfigError::InvalidOutlierBps);
+        }
+
+        Ok(Self {
+            staleness_ms,
+            outlier: OutlierFilter::Mad {
+                k: mad_k,
+                floor_bps,
+            },
             latest_by_venue: HashMap::new(),
         })
     }
 
+    pub fn outlier_filter(&self) -> OutlierFilter {
+        self.outlier
+    }
+
     /// Ingests a normalized venue tick into the latest-per-venue cache.
     ///
     /// Ticks with invalid prices (`NaN`, infinite, or `<= 0.0`) are silently
@@ -59,11 +110,16 @@ impl MedianAggregator {
         }
     }
 
+    /// Returns the newest tick retained for `venue`, if any.
+    pub fn latest(&self, venue: &str) -> Option<&NormalizedBtcTick> {
+        self.latest_by_venue.get(venue)
+    }
+
     /// Computes a robust median snapshot across currently tracked venues.
     ///
     /// The aggregator starts from the latest tick per venue, removes stale ticks
     /// relative to the freshest timestamp, computes a baseline median, then drops
-    /// outliers outside the configured basis-point band.
+    /// outliers outside the configured [`OutlierFilter`] band.
     ///
     /// Returns `Some(BtcMedianTick)` only when at least two venues survive all
     /// filtering steps.
@@ -84,7 +140,17 @@ impl MedianAggregator {
         }
 
         let baseline_median = median_price(&fresh_ticks)?;
-        let threshold = baseline_median * (self.outlier_bps / 10_000.0);
+        let threshold = match self.outlier {
+            OutlierFilter::FixedBps(bps) => baseline_median * (bps / 10_000.0),
+            OutlierFilter::Mad { k, floor_bps } => {
+                let deviations: Vec<f64> = fresh_ticks
+                    .iter()
+                    .map(|tick| (tick.px - baseline_median).abs())
+                    .collect();
+                let mad = median_value(deviations)?;
+                (k * MAD_TO_SIGMA * mad).max(baseline_median * (floor_bps / 10_000.0))
+            }
+        };
 
         let filtered_ticks: Vec<&NormalizedBtcTick> = fresh_ticks
             .into_iter()
@@ -116,26 +182,88 @@ impl MedianAggregator {
 }
 
 fn median_price(ticks: &[&NormalizedBtcTick]) -> Option<f64> {
-    if ticks.is_empty() {
+    median_value(ticks.iter().map(|tick| tick.px).collect())
+}
+
+fn median_value(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
         return None;
     }
 
-    let mut prices: Vec<f64> = ticks.iter().map(|tick| tick.px).collect();
-    prices.sort_by(|a, b| a.total_cmp(b));
+    values.sort_by(|a, b| a.total_cmp(b));
 
-    let mid = prices.len() / 2;
-    if prices.len() % 2 == 0 {
-        Some((prices[mid - 1] + prices[mid]) / 2.0)
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[mid - 1] + values[mid]) / 2.0)
     } else {
-        Some(prices[mid])
+        Some(values[mid])
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MedianAggregator;
+    use core_sim::{MultiVenueGenerator, PriceProcess, VenueSpec};
+
+    use super::{MedianAggregator, MedianAggregatorConfigError};
     use crate::live::NormalizedBtcTick;
 
+    #[test]
+    fn median_tracks_the_latent_price_of_simulated_lagged_venues() {
+        let venue = |name: &str, base_lag_ms: u64| VenueSpec {
+            name: name.to_string(),
+            base_lag_ms,
+            jitter_ms: base_lag_ms / 4,
+            noise_bps: 2.0,
+        };
+        let mut generator = MultiVenueGenerator::new(
+            17,
+            PriceProcess::JumpDiffusion {
+                drift: 0.0,
+                volatility: 0.0002,
+                jump_intensity: 0.02,
+                jump_mean: 0.0,
+                jump_std: 0.05,
+            },
+            60_000.0,
+            100,
+            vec![
+                venue("binance", 40),
+                venue("coinbase", 60),
+                venue("kraken", 80),
+                venue("bybit", 50),
+                venue("laggard", 2_000),
+            ],
+        );
+        let mut agg = MedianAggregator::new(2_000, 200.0).unwrap();
+        let mut laggard_dropped = 0;
+        let mut previous_true_px = 60_000.0;
+
+        for _ in 0..2_000 {
+            let tick = generator.next_tick();
+            for quote in tick.quotes {
+                agg.ingest(NormalizedBtcTick {
+                    venue: quote.venue,
+                    px: quote.px,
+                    size: 1.0,
+                    ts: quote.ts_ms,
+                });
+            }
+            let median = agg.compute().unwrap();
+            if median.venue_count == 4 {
+                laggard_dropped += 1;
+            }
+            // Every fast venue is one 100ms interval behind the latent price.
+            let error_bps = (median.px_median / previous_true_px - 1.0).abs() * 10_000.0;
+            assert!(
+                error_bps < 10.0,
+                "median {} vs true {previous_true_px}",
+                median.px_median
+            );
+            previous_true_px = tick.true_px;
+        }
+        assert!(laggard_dropped > 0);
+    }
+
     #[test]
     fn median_ignores_stale_and_outlier_ticks() {
         let mut agg = MedianAggregator::new(2_000, 200.0).unwrap();
@@ -206,6 +334,58 @@ mod tests {
         assert!(MedianAggregator::new(5_000, -0.1).is_err());
     }
 
+    #[test]
+    fn robust_band_widens_with_venue_dispersion() {
+        // Venues spread ~300 USD apart: a fixed 20 bps band leaves fewer than
+        // two venues and yields nothing, the MAD band keeps them and still
+        // drops the far-off feed.
+        let mut agg = MedianAggregator::robust(5_000, 3.0, 5.0).unwrap();
+        agg.ingest(tick("binance", 60_000.0, 10_000));
+        agg.ingest(tick("coinbase", 60_300.0, 10_000));
+        agg.ingest(tick("kraken", 59_700.0, 10_000));
+        agg.ingest(tick("okx", 60_150.0, 10_000));
+        agg.ingest(tick("bad-feed", 63_000.0, 10_000));
+
+        let out = agg.compute().unwrap();
+        assert_eq!(out.venue_count, 4);
+        assert_eq!(out.px_median, 60_075.0);
+
+        let mut fixed = MedianAggregator::new(5_000, 20.0).unwrap();
+        for venue in ["binance", "coinbase", "kraken", "okx", "bad-feed"] {
+            fixed.ingest(agg.latest(venue).unwrap().clone());
+        }
+        assert!(fixed.compute().is_none());
+    }
+
+    #[test]
+    fn robust_band_falls_back_to_floor_when_venues_agree() {
+        let mut agg = MedianAggregator::robust(5_000, 3.0, 10.0).unwrap();
+        agg.ingest(tick("binance", 60_000.0, 10_000));
+        agg.ingest(tick("coinbase", 60_000.0, 10_000));
+        agg.ingest(tick("kraken", 60_000.0, 10_000));
+        agg.ingest(tick("okx", 60_040.0, 10_000));
+        agg.ingest(tick("bybit", 60_100.0, 10_000));
+
+        // MAD is zero; the 10 bps floor (60 USD) keeps okx and drops bybit.
+        assert_eq!(agg.compute().unwrap().venue_count, 4);
+    }
+
+    #[test]
+    fn robust_rejects_invalid_constructor_params() {
+        assert_eq!(
+            MedianAggregator::robust(5_000, 0.0, 5.0).unwrap_err(),
+            MedianAggregatorConfigError::InvalidMadMultiplier
+        );
+        assert_eq!(
+            MedianAggregator::robust(5_000, 3.0, -1.0).unwrap_err(),
+            MedianAggregatorConfigError::InvalidOutlierBps
+        );
+        assert_eq!(
+            MedianAggregator::robust(0, 3.0, 5.0).unwrap_err(),
+            MedianAggregatorConfigError::InvalidStalenessMs
+        );
+    }
+
     #[test]
     fn new_accepts_boundary_constructor_params() {
         assert!(MedianAggregator::new(1, 0.0).is_ok());
@@ -1,19 +1,75 @@
+pub mod asset;
+pub mod binance_parse;
+pub mod bitstamp_parse;
 pub mod btc_feed;
 pub mod btc_parse;
+pub mod bybit_parse;
+pub mod clock_skew;
+pub mod composite;
+pub mod ewma;
+pub mod kalshi_quote;
+pub mod kraken_parse;
 pub mod lag_detector;
 pub mod median;
+pub mod okx_parse;
+pub mod polymarket_book;
 pub mod polymarket_discovery;
 pub mod polymarket_quote;
+pub mod predictor_weights;
 pub mod predictors;
+pub mod proxy;
+pub mod quarantine;
+pub mod reconnect;
+pub mod regime;
+pub mod replay_feed;
 pub mod types;
+pub mod venue;
+pub mod vwap;
 
-pub use btc_feed::NormalizedBtcTick;
-pub use btc_parse::{parse_coinbase_trade, ParseBtcTradeError};
-pub use lag_detector::{detect_lag, LagError, LagSignal};
-pub use median::MedianAggregator;
-pub use polymarket_discovery::{filter_markets, PolymarketMarket};
+pub use asset::Asset;
+pub use binance_parse::{parse_binance_trade, parse_binance_ws_frame};
+pub use bitstamp_parse::parse_bitstamp_ws_frame;
+pub use btc_feed::{BtcFrameParser, BtcWsFeedConfig, NormalizedBtcTick, WsBtcFeed};
+pub use btc_parse::{parse_coinbase_trade, parse_coinbase_ws_frame, ParseBtcTradeError};
+pub use bybit_parse::parse_bybit_ws_frame;
+pub use clock_skew::{ClockSkewConfigError, ClockSkewEstimator};
+pub use composite::{BtcAggregator, BtcComposite};
+pub use ewma::{EwmaSmoother, EwmaSmootherConfigError};
+pub use kalshi_quote::{
+    parse_kalshi_market, parse_kalshi_markets, KalshiMarket, ParseKalshiMarketsError,
+};
+pub use kraken_parse::parse_kraken_ws_frame;
+pub use lag_detector::{
+    detect_lag, LagError, LagGateConfigError, LagGateDecision, LagSignal, LagTriggerGate,
+    WindowedLagConfigError, WindowedLagDetector, WindowedLagSignal,
+};
+pub use median::{MedianAggregator, MedianAggregatorConfigError, OutlierFilter};
+pub use okx_parse::parse_okx_ws_frame;
+pub use polymarket_book::{parse_polymarket_book, ParsePolymarketBookError};
+pub use polymarket_discovery::{
+    filter_markets, parse_end_date_ms, parse_strike_usd, select_expiring, MarketFilter,
+    MarketFilterError, MarketMetadata, MarketSelection, PolymarketMarket,
+};
 pub use polymarket_quote::{
-    NormalizePolymarketQuoteError, PolymarketQuoteTick, RawPolymarketQuote,
+    BookDepth, BookLevel, NormalizePolymarketQuoteError, PolymarketQuoteTick, QuoteSource,
+    RawPolymarketQuote, SpreadLimit,
+};
+pub use predictor_weights::{PredictorWeights, PredictorWeightsError, SourceWeight};
+pub use predictors::{
+    fuse_predictors, fuse_weighted_predictors, FusedFairValue, PredictorSource, PredictorTick,
+};
+pub use proxy::{HttpProxy, HttpProxyError};
+pub use quarantine::{
+    QuarantineBuffer, QuarantinedPayload, RejectedPayload, MAX_QUARANTINED_RAW_BYTES,
+};
+pub use reconnect::{FeedReconnect, ReconnectBackoff, ReconnectPolicy, ReconnectPolicyError};
+pub use regime::{RegimeClassifier, RegimeClassifierConfigError, RegimeThresholds};
+pub use replay_feed::{
+    ReplayClock, ReplayFeed, ReplayFeedError, ReplayPredictors, ReplayRecording,
 };
-pub use predictors::{fuse_predictors, FusedFairValue, PredictorSource, PredictorTick};
 pub use types::{BtcMedianTick, LiveIngestEvent};
+pub use venue::{
+    run_venue_feed, SharedSupervisor, SharedVenueBoard, VenueBoard, VenueFeed, VenueFeedError,
+    VenueFuture, VenueHealth, VenueTick,
+};
+pub use vwap::{VwapAggregator, VwapAggregatorConfigError};
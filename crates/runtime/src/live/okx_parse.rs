@@ -0,0 +1,114 @@
+use crate::live::btc_feed::NormalizedBtcTick;
+use crate::live::btc_parse::{parse_positive_decimal, ParseBtcTradeError};
+use serde::Deserialize;
+
+/// Parses one frame from the OKX v5 public `trades` channel.
+///
+/// The most recent trade in `data` becomes the tick. Subscribe acknowledgements
+/// and the plain-text `pong` heartbeat reply yield `Ok(None)`; `error` events are
+/// surfaced as [`ParseBtcTradeError::UnsupportedMessageType`].
+pub fn parse_okx_ws_frame(raw: &str) -> Result<Option<NormalizedBtcTick>, ParseBtcTradeError> {
+    if raw == "pong" {
+        return Ok(None);
+    }
+
+    let envelope: OkxEnvelope =
+        serde_json::from_str(raw).map_err(|_| ParseBtcTradeError::InvalidJson)?;
+    match envelope.event.as_deref() {
+        Some("error") => return Err(ParseBtcTradeError::UnsupportedMessageType),
+        Some(_) => return Ok(None),
+        None => {}
+    }
+    if envelope.arg.map(|arg| arg.channel).as_deref() != Some("trades") {
+        return Ok(None);
+    }
+
+    match envelope.data.last() {
+        Some(trade) => normalize_okx_trade(trade).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn normalize_okx_trade(trade: &OkxTrade) -> Result<NormalizedBtcTick, ParseBtcTradeError> {
+    let px = parse_positive_decimal(&trade.px, ParseBtcTradeError::InvalidPrice)?;
+    let size = parse_positive_decimal(&trade.sz, ParseBtcTradeError::InvalidSize)?;
+    let ts = trade
+        .ts
+        .parse::<u64>()
+        .ok()
+        .filter(|ts| *ts > 0)
+        .ok_or(ParseBtcTradeError::InvalidTimestamp)?;
+
+    Ok(NormalizedBtcTick {
+        venue: "okx".to_string(),
+        px,
+        size,
+        ts,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct OkxEnvelope {
+    #[serde(default)]
+    event: Option<String>,
+    #[serde(default)]
+    arg: Option<OkxArg>,
+    #[serde(default)]
+    data: Vec<OkxTrade>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OkxArg {
+    channel: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OkxTrade {
+    px: String,
+    sz: String,
+    ts: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_okx_ws_frame, ParseBtcTradeError};
+
+    #[test]
+    fn parses_latest_trade_from_okx_push() {
+        let raw = r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[
+            {"instId":"BTC-USDT","tradeId":"1","px":"64000.1","sz":"0.01","side":"buy","ts":"1772280000000","count":"1"},
+            {"instId":"BTC-USDT","tradeId":"2","px":"64001.9","sz":"0.25","side":"sell","ts":"1772280000120","count":"2"}
+        ]}"#;
+        let tick = parse_okx_ws_frame(raw).unwrap().unwrap();
+
+        assert_eq!(tick.venue, "okx");
+        assert_eq!(tick.px, 64_001.9);
+        assert_eq!(tick.size, 0.25);
+        assert_eq!(tick.ts, 1_772_280_000_120);
+    }
+
+    #[test]
+    fn okx_frame_skips_acks_and_pong() {
+        assert_eq!(parse_okx_ws_frame("pong"), Ok(None));
+        assert_eq!(
+            parse_okx_ws_frame(
+                r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"},"connId":"a4d3ae55"}"#
+            ),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn okx_frame_surfaces_error_events_and_bad_prices() {
+        assert_eq!(
+            parse_okx_ws_frame(r#"{"event":"error","code":"60012","msg":"Invalid request"}"#),
+            Err(ParseBtcTradeError::UnsupportedMessageType)
+        );
+        assert_eq!(
+            parse_okx_ws_frame(
+                r#"{"arg":{"channel":"trades"},"data":[{"px":"-1","sz":"0.1","ts":"1772280000000"}]}"#
+            ),
+            Err(ParseBtcTradeError::InvalidPrice)
+        );
+    }
+}
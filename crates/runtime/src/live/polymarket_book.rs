@@ -0,0 +1,122 @@
+use serde::Deserialize;
+
+use crate::live::polymarket_quote::{BookDepth, BookLevel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsePolymarketBookError {
+    InvalidJson,
+    InvalidLevel,
+}
+
+/// Parses a Polymarket CLOB `GET /book?token_id=...` response for the YES
+/// token, keeping the best `max_levels` on each side.
+///
+/// The CLOB does not promise any ordering, so bids are sorted high to low and
+/// asks low to high. Levels with a price outside `[0, 1]` or a non-positive
+/// size are rejected.
+pub fn parse_polymarket_book(
+    raw: &str,
+    max_levels: usize,
+) -> Result<BookDepth, ParsePolymarketBookError> {
+    let book: ClobBook =
+        serde_json::from_str(raw).map_err(|_| ParsePolymarketBookError::InvalidJson)?;
+
+    let mut bids = normalize_levels(&book.bids)?;
+    let mut asks = normalize_levels(&book.asks)?;
+    bids.sort_by(|left, right| right.px.total_cmp(&left.px));
+    asks.sort_by(|left, right| left.px.total_cmp(&right.px));
+    bids.truncate(max_levels);
+    asks.truncate(max_levels);
+
+    Ok(BookDepth { bids, asks })
+}
+
+fn normalize_levels(levels: &[ClobLevel]) -> Result<Vec<BookLevel>, ParsePolymarketBookError> {
+    levels
+        .iter()
+        .map(|level| {
+            let px = level
+                .price
+                .parse::<f64>()
+                .ok()
+                .filter(|px| px.is_finite() && (0.0..=1.0).contains(px));
+            let qty = level
+                .size
+                .parse::<f64>()
+                .ok()
+                .filter(|qty| qty.is_finite() && *qty > 0.0);
+            match (px, qty) {
+                (Some(px), Some(qty)) => Ok(BookLevel { px, qty }),
+                _ => Err(ParsePolymarketBookError::InvalidLevel),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct ClobBook {
+    #[serde(default)]
+    bids: Vec<ClobLevel>,
+    #[serde(default)]
+    asks: Vec<ClobLevel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClobLevel {
+    price: String,
+    size: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_and_truncates_clob_book_levels() {
+        let raw = r#"{"market":"0xabc","asset_id":"123","bids":[
+            {"price":"0.45","size":"100"},{"price":"0.47","size":"20"},{"price":"0.46","size":"50"}
+        ],"asks":[
+            {"price":"0.55","size":"80"},{"price":"0.52","size":"10"},{"price":"0.53","size":"40"}
+        ],"hash":"h"}"#;
+
+        let depth = parse_polymarket_book(raw, 2).unwrap();
+
+        assert_eq!(
+            depth.bids,
+            vec![
+                BookLevel {
+                    px: 0.47,
+                    qty: 20.0
+                },
+                BookLevel {
+                    px: 0.46,
+                    qty: 50.0
+                },
+            ]
+        );
+        assert_eq!(
+            depth.asks[0],
+            BookLevel {
+                px: 0.52,
+                qty: 10.0
+            }
+        );
+        assert_eq!(depth.asks.len(), 2);
+    }
+
+    #[test]
+    fn rejects_malformed_books() {
+        assert_eq!(
+            parse_polymarket_book("nope", 5),
+            Err(ParsePolymarketBookError::InvalidJson)
+        );
+        assert_eq!(
+            parse_polymarket_book(r#"{"bids":[{"price":"1.2","size":"5"}],"asks":[]}"#, 5),
+            Err(ParsePolymarketBookError::InvalidLevel)
+        );
+        assert_eq!(
+            parse_polymarket_book(r#"{"bids":[],"asks":[{"price":"0.5","size":"0"}]}"#, 5),
+            Err(ParsePolymarketBookError::InvalidLevel)
+        );
+    }
+}
@@ -1,8 +1,290 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::live::Asset;
+
+/// Share of the liquidity score from each factor; they sum to one.
+const SPREAD_WEIGHT: f64 = 0.4;
+const LIQUIDITY_WEIGHT: f64 = 0.25;
+const VOLUME_WEIGHT: f64 = 0.15;
+const EXPIRY_WEIGHT: f64 = 0.2;
+/// YES spread at which a market gets no credit for tightness.
+const MAX_SCORED_SPREAD: f64 = 0.10;
+/// Liquidity and volume, in USD, past which more earns no extra credit.
+const LIQUIDITY_SATURATION_USD: f64 = 50_000.0;
+const VOLUME_SATURATION_USD: f64 = 100_000.0;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PolymarketMarket {
     pub slug: String,
 }
 
+/// Typed view of a candidate market: when it settles, the price it settles
+/// against (if the question names one) and how liquid it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketMetadata {
+    pub slug: String,
+    pub end_ms: u64,
+    pub strike: Option<f64>,
+    pub liquidity: f64,
+    /// Recent traded volume in USD.
+    pub volume: f64,
+    /// YES spread at the touch as listed, when the listing carries a book.
+    pub spread: Option<f64>,
+}
+
+impl MarketMetadata {
+    /// Ranks candidates in `0..=1`: tight spreads, deep liquidity, recent
+    /// volume and settlement early in the `horizon_ms` window score higher.
+    /// A market without a listed spread gets no tightness credit.
+    pub fn liquidity_score(&self, now_ms: u64, horizon_ms: u64) -> f64 {
+        let tightness = self.spread.map_or(0.0, |spread| {
+            1.0 - (spread.max(0.0) / MAX_SCORED_SPREAD).min(1.0)
+        });
+        let saturating =
+            |usd: f64, saturation: f64| (usd.max(0.0).ln_1p() / saturation.ln_1p()).min(1.0);
+        let remaining_ms = self.end_ms.saturating_sub(now_ms);
+        let expiry = 1.0 - (remaining_ms as f64 / horizon_ms.max(1) as f64).min(1.0);
+
+        SPREAD_WEIGHT * tightness
+            + LIQUIDITY_WEIGHT * saturating(self.liquidity, LIQUIDITY_SATURATION_USD)
+            + VOLUME_WEIGHT * saturating(self.volume, VOLUME_SATURATION_USD)
+            + EXPIRY_WEIGHT * expiry
+    }
+}
+
+/// Parses an RFC 3339 market end date (e.g. Gamma `endDate`, Kalshi
+/// `close_time`) into Unix milliseconds.
+pub fn parse_end_date_ms(value: &str) -> Option<u64> {
+    let parsed = OffsetDateTime::parse(value.trim(), &Rfc3339).ok()?;
+    u64::try_from(parsed.unix_timestamp_nanos() / 1_000_000).ok()
+}
+
+/// Extracts a USD strike from a market question such as
+/// `"Will BTC be above $66,250 at 12:15?"` or `"BTC above 66.5k?"`.
+///
+/// Only numbers prefixed with `$` or suffixed with `k` count, so dates and
+/// times in the question are not mistaken for strikes.
+pub fn parse_strike_usd(text: &str) -> Option<f64> {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !bytes[idx].is_ascii_digit() {
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+        while idx < bytes.len()
+            && (bytes[idx].is_ascii_digit() || bytes[idx] == b',' || bytes[idx] == b'.')
+        {
+            idx += 1;
+        }
+        let number = text[start..idx]
+            .trim_end_matches(['.', ','])
+            .replace(',', "");
+        let dollar = start > 0 && bytes[start - 1] == b'$';
+        let thousands = bytes
+            .get(idx)
+            .is_some_and(|next| next.eq_ignore_ascii_case(&b'k'))
+            && !bytes
+                .get(idx + 1)
+                .is_some_and(|after| after.is_ascii_alphanumeric());
+
+        if dollar || thousands {
+            let value = number.parse::<f64>().ok()?;
+            let value = if thousands { value * 1_000.0 } else { value };
+            if value.is_finite() && value > 0.0 {
+                return Some(value);
+            }
+        }
+    }
+
+    None
+}
+
+/// Keeps markets that settle within `horizon_ms` of `now_ms` and orders them
+/// by trading preference: markets with at least `min_liquidity` first, then
+/// by [`MarketMetadata::liquidity_score`], then nearest expiry.
+pub fn select_expiring(
+    markets: Vec<MarketMetadata>,
+    now_ms: u64,
+    horizon_ms: u64,
+    min_liquidity: f64,
+) -> Vec<MarketMetadata> {
+    let mut selected: Vec<MarketMetadata> = markets
+        .into_iter()
+        .filter(|market| market.end_ms > now_ms && market.end_ms - now_ms <= horizon_ms)
+        .collect();
+    selected.sort_by(|left, right| {
+        let left_thin = left.liquidity < min_liquidity;
+        let right_thin = right.liquidity < min_liquidity;
+        let left_score = left.liquidity_score(now_ms, horizon_ms);
+        let right_score = right.liquidity_score(now_ms, horizon_ms);
+        left_thin
+            .cmp(&right_thin)
+            .then(right_score.total_cmp(&left_score))
+            .then(left.end_ms.cmp(&right.end_ms))
+    });
+    selected
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarketFilterError {
+    Empty,
+    /// Terms are single words of ASCII letters and digits.
+    InvalidTerm(String),
+    /// Every alternative needs a word the market must contain, or it would
+    /// match other assets' markets.
+    NegatedOnly,
+    /// Per-asset entries are written `asset=expression`.
+    InvalidEntry(String),
+    UnknownAsset(String),
+}
+
+impl fmt::Display for MarketFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "filter expression is empty"),
+            Self::InvalidTerm(term) => {
+                write!(f, "`{term}` is not a word of letters and digits")
+            }
+            Self::NegatedOnly => {
+                write!(f, "each `|` alternative needs at least one required word")
+            }
+            Self::InvalidEntry(entry) => write!(f, "`{entry}` is not `asset=expression`"),
+            Self::UnknownAsset(asset) => write!(f, "`{asset}` is not a tracked asset"),
+        }
+    }
+}
+
+impl std::error::Error for MarketFilterError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FilterTerm {
+    word: String,
+    negated: bool,
+}
+
+/// Keyword expression a market's slug and question must satisfy.
+///
+/// Terms are whole words matched case-insensitively; `!` negates a term, `&`
+/// joins terms that must all hold and binds tighter than `|`, which separates
+/// alternatives. `bitcoin & !daily | btc & 15m` tracks non-daily Bitcoin
+/// markets and any BTC market naming `15m`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketFilter {
+    any_of: Vec<Vec<FilterTerm>>,
+}
+
+impl MarketFilter {
+    pub fn parse(expression: &str) -> Result<Self, MarketFilterError> {
+        if expression.trim().is_empty() {
+            return Err(MarketFilterError::Empty);
+        }
+
+        let mut any_of = Vec::new();
+        for alternative in expression.split('|') {
+            let mut terms = Vec::new();
+            for term in alternative.split('&').map(str::trim) {
+                let (negated, word) = match term.strip_prefix('!') {
+                    Some(word) => (true, word.trim()),
+                    None => (false, term),
+                };
+                if word.is_empty() || !word.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+                    return Err(MarketFilterError::InvalidTerm(term.to_string()));
+                }
+                terms.push(FilterTerm {
+                    word: word.to_ascii_lowercase(),
+                    negated,
+                });
+            }
+            if terms.iter().all(|term| term.negated) {
+                return Err(MarketFilterError::NegatedOnly);
+            }
+            any_of.push(terms);
+        }
+        Ok(Self { any_of })
+    }
+
+    /// Matches any one of `keywords`, the built-in filter for an asset.
+    pub fn any_keyword(keywords: &[&str]) -> Self {
+        Self {
+            any_of: keywords
+                .iter()
+                .map(|keyword| {
+                    vec![FilterTerm {
+                        word: keyword.to_ascii_lowercase(),
+                        negated: false,
+                    }]
+                })
+                .collect(),
+        }
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        let lowered = text.to_ascii_lowercase();
+        let words: Vec<&str> = lowered
+            .split(|ch: char| !ch.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        self.any_of.iter().any(|terms| {
+            terms
+                .iter()
+                .all(|term| words.contains(&term.word.as_str()) != term.negated)
+        })
+    }
+}
+
+/// Which listed markets are tracked: a filter per asset and how soon a
+/// market must settle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketSelection {
+    filters: BTreeMap<Asset, MarketFilter>,
+    pub horizon_ms: u64,
+}
+
+impl MarketSelection {
+    /// Every asset matched by its own name, as in [`Asset::market_keywords`].
+    pub fn new(horizon_ms: u64) -> Self {
+        Self {
+            filters: Asset::ALL
+                .iter()
+                .map(|&asset| (asset, MarketFilter::any_keyword(asset.market_keywords())))
+                .collect(),
+            horizon_ms,
+        }
+    }
+
+    /// Replaces the filters of the assets named in `spec`, a `;`-separated
+    /// list of `asset=expression` entries.
+    pub fn with_filters(mut self, spec: &str) -> Result<Self, MarketFilterError> {
+        for entry in spec
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (asset, expression) = entry
+                .split_once('=')
+                .ok_or_else(|| MarketFilterError::InvalidEntry(entry.to_string()))?;
+            let asset = Asset::parse(asset)
+                .ok_or_else(|| MarketFilterError::UnknownAsset(asset.trim().to_string()))?;
+            self.filters.insert(asset, MarketFilter::parse(expression)?);
+        }
+        Ok(self)
+    }
+
+    /// Whether a market with this slug and question belongs to `asset`.
+    pub fn matches(&self, asset: Asset, slug: &str, question: &str) -> bool {
+        self.filters
+            .get(&asset)
+            .is_some_and(|filter| filter.matches(&format!("{slug} {question}")))
+    }
+}
+
 pub fn filter_markets(markets: Vec<PolymarketMarket>, needle: &str) -> Vec<PolymarketMarket> {
     let needle = needle.trim().to_ascii_lowercase();
     if needle.is_empty() {
@@ -17,7 +299,11 @@ pub fn filter_markets(markets: Vec<PolymarketMarket>, needle: &str) -> Vec<Polym
 
 #[cfg(test)]
 mod tests {
-    use super::{filter_markets, PolymarketMarket};
+    use super::{
+        filter_markets, parse_end_date_ms, parse_strike_usd, select_expiring, MarketFilter,
+        MarketFilterError, MarketMetadata, MarketSelection, PolymarketMarket,
+    };
+    use crate::live::Asset;
 
     #[test]
     fn discovery_filters_market_candidates() {
@@ -47,6 +333,130 @@ mod tests {
         assert_eq!(out, markets);
     }
 
+    #[test]
+    fn parses_end_dates_and_question_strikes() {
+        assert_eq!(
+            parse_end_date_ms("2026-02-28T12:15:00Z"),
+            Some(1_772_280_900_000)
+        );
+        assert_eq!(parse_end_date_ms("tomorrow"), None);
+
+        assert_eq!(
+            parse_strike_usd("Will BTC be above $66,250 at 12:15 on Feb 28?"),
+            Some(66_250.0)
+        );
+        assert_eq!(
+            parse_strike_usd("BTC above 66.5k in 15 min?"),
+            Some(66_500.0)
+        );
+        assert_eq!(
+            parse_strike_usd("Bitcoin Up or Down - February 28, 12:00PM-12:15PM ET"),
+            None
+        );
+    }
+
+    #[test]
+    fn select_expiring_prefers_nearest_liquid_market() {
+        let now = 1_000_000;
+        let markets = vec![
+            metadata("later-liquid", now + 600_000, 5_000.0),
+            metadata("nearest-thin", now + 60_000, 50.0),
+            metadata("near-liquid", now + 120_000, 2_000.0),
+            metadata("expired", now - 1, 9_000.0),
+            metadata("tomorrow", now + 86_400_000, 9_000.0),
+        ];
+
+        let slugs: Vec<String> = select_expiring(markets, now, 900_000, 500.0)
+            .into_iter()
+            .map(|market| market.slug)
+            .collect();
+
+        assert_eq!(slugs, vec!["near-liquid", "later-liquid", "nearest-thin"]);
+    }
+
+    #[test]
+    fn liquidity_score_rewards_tight_spreads_and_volume() {
+        let now = 1_000_000;
+        let wide = MarketMetadata {
+            spread: Some(0.12),
+            ..metadata("wide", now + 300_000, 5_000.0)
+        };
+        let tight = MarketMetadata {
+            spread: Some(0.02),
+            volume: 20_000.0,
+            ..metadata("tight", now + 600_000, 5_000.0)
+        };
+
+        let slugs: Vec<String> =
+            select_expiring(vec![wide.clone(), tight.clone()], now, 900_000, 500.0)
+                .into_iter()
+                .map(|market| market.slug)
+                .collect();
+        assert_eq!(slugs, vec!["tight", "wide"]);
+
+        let score = tight.liquidity_score(now, 900_000);
+        assert!(score > wide.liquidity_score(now, 900_000));
+        assert!((0.0..=1.0).contains(&score));
+        let perfect = MarketMetadata {
+            spread: Some(0.0),
+            volume: 1e9,
+            ..metadata("perfect", now, 1e9)
+        };
+        assert!((perfect.liquidity_score(now, 900_000) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn market_filters_combine_words_with_and_or_not() {
+        let filter = MarketFilter::parse("bitcoin & !daily | BTC & 15m").unwrap();
+
+        assert!(filter.matches("bitcoin-up-or-down Bitcoin Up or Down - 12:15PM ET"));
+        assert!(!filter.matches("bitcoin-daily Will Bitcoin close higher? daily"));
+        assert!(filter.matches("btc-15m-above-66k"));
+        assert!(!filter.matches("btc-hourly"));
+
+        assert_eq!(MarketFilter::parse(" "), Err(MarketFilterError::Empty));
+        assert_eq!(
+            MarketFilter::parse("btc & up-or-down"),
+            Err(MarketFilterError::InvalidTerm("up-or-down".to_string()))
+        );
+        assert_eq!(
+            MarketFilter::parse("btc | !daily"),
+            Err(MarketFilterError::NegatedOnly)
+        );
+    }
+
+    #[test]
+    fn market_selection_overrides_only_the_named_assets() {
+        let selection = MarketSelection::new(900_000)
+            .with_filters("eth = ether & updown;")
+            .unwrap();
+
+        assert!(selection.matches(Asset::Btc, "btc-updown-15m", ""));
+        assert!(selection.matches(Asset::Eth, "ether-updown", ""));
+        assert!(!selection.matches(Asset::Eth, "eth-updown", "Ethereum Up or Down"));
+        assert!(!selection.matches(Asset::Sol, "btc-updown-15m", "Resolves to Up"));
+
+        assert_eq!(
+            MarketSelection::new(1).with_filters("doge=doge"),
+            Err(MarketFilterError::UnknownAsset("doge".to_string()))
+        );
+        assert_eq!(
+            MarketSelection::new(1).with_filters("btc"),
+            Err(MarketFilterError::InvalidEntry("btc".to_string()))
+        );
+    }
+
+    fn metadata(slug: &str, end_ms: u64, liquidity: f64) -> MarketMetadata {
+        MarketMetadata {
+            slug: slug.to_string(),
+            end_ms,
+            strike: None,
+            liquidity,
+            volume: 0.0,
+            spread: None,
+        }
+    }
+
     fn sample_market(slug: &str) -> PolymarketMarket {
         PolymarketMarket {
             slug: slug.to_string(),
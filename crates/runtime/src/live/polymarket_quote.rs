@@ -1,12 +1,218 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+use crate::live::asset::Asset;
+
+/// Prediction-market venue a normalized YES quote came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteSource {
+    #[default]
+    Polymarket,
+    Kalshi,
+}
+
+impl QuoteSource {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "polymarket" => Some(Self::Polymarket),
+            "kalshi" => Some(Self::Kalshi),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Polymarket => "polymarket",
+            Self::Kalshi => "kalshi",
+        }
+    }
+}
+
+/// One resting price level on the YES book.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub px: f64,
+    pub qty: f64,
+}
+
+/// The top few YES book levels on each side, best price first.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BookDepth {
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+}
+
+impl Clone for BookDepth {
+    fn clone(&self) -> Self {
+        Self {
+            bids: self.bids.clone(),
+            asks: self.asks.clone(),
+        }
+    }
+
+    /// Copies the levels into the existing buffers.
+    fn clone_from(&mut self, source: &Self) {
+        self.bids.clone_from(&source.bids);
+        self.asks.clone_from(&source.asks);
+    }
+}
+
+impl BookDepth {
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct PolymarketQuoteTick {
     pub market_slug: String,
+    #[serde(default)]
+    pub source: QuoteSource,
+    /// Spot asset the market settles on.
+    #[serde(default)]
+    pub asset: Asset,
     pub best_yes_bid: f64,
     pub best_yes_ask: f64,
     pub mid_yes: f64,
     pub ts: u64,
+    #[serde(default)]
+    pub depth: BookDepth,
+    /// Discovery's liquidity score for the market, for quotes taken from a
+    /// venue listing.
+    #[serde(default)]
+    pub liquidity_score: Option<f64>,
+}
+
+impl Clone for PolymarketQuoteTick {
+    fn clone(&self) -> Self {
+        Self {
+            market_slug: self.market_slug.clone(),
+            source: self.source,
+            asset: self.asset,
+            best_yes_bid: self.best_yes_bid,
+            best_yes_ask: self.best_yes_ask,
+            mid_yes: self.mid_yes,
+            ts: self.ts,
+            depth: self.depth.clone(),
+            liquidity_score: self.liquidity_score,
+        }
+    }
+
+    /// Copies `source` into the existing slug and depth buffers, so a quote
+    /// reused across evaluations stops allocating once it has grown.
+    fn clone_from(&mut self, source: &Self) {
+        self.market_slug.clone_from(&source.market_slug);
+        self.source = source.source;
+        self.asset = source.asset;
+        self.best_yes_bid = source.best_yes_bid;
+        self.best_yes_ask = source.best_yes_ask;
+        self.mid_yes = source.mid_yes;
+        self.ts = source.ts;
+        self.depth.clone_from(&source.depth);
+        self.liquidity_score = source.liquidity_score;
+    }
+}
+
+impl PolymarketQuoteTick {
+    /// Width of the YES book at the touch.
+    pub fn spread(&self) -> f64 {
+        self.best_yes_ask - self.best_yes_bid
+    }
+
+    /// Average YES price for an immediate fill of `qty` contracts.
+    ///
+    /// Positive `qty` buys through the asks, negative `qty` sells into the
+    /// bids. Returns `None` when the ingested depth cannot absorb the full
+    /// size. Quotes without ingested depth (venues that only publish top of
+    /// book) price the whole size at the touch.
+    pub fn impact_price(&self, qty: f64) -> Option<f64> {
+        if !qty.is_finite() || qty == 0.0 {
+            return None;
+        }
+
+        let (levels, touch) = if qty > 0.0 {
+            (&self.depth.asks, self.best_yes_ask)
+        } else {
+            (&self.depth.bids, self.best_yes_bid)
+        };
+        if levels.is_empty() {
+            return Some(touch);
+        }
+
+        let mut remaining = qty.abs();
+        let mut notional = 0.0;
+        for level in levels {
+            let take = remaining.min(level.qty);
+            notional += take * level.px;
+            remaining -= take;
+            if remaining <= 0.0 {
+                return Some(notional / qty.abs());
+            }
+        }
+        None
+    }
+
+    /// Contracts resting at the best level on one side. `None` when the quote
+    /// carries no depth.
+    pub fn touch_qty(&self, buy: bool) -> Option<f64> {
+        let levels = if buy {
+            &self.depth.asks
+        } else {
+            &self.depth.bids
+        };
+        levels.first().map(|level| level.qty)
+    }
+
+    /// Contracts available on one side at prices no worse than `limit_px`.
+    /// `None` when the quote carries no depth.
+    pub fn depth_within(&self, buy: bool, limit_px: f64) -> Option<f64> {
+        let levels = if buy {
+            &self.depth.asks
+        } else {
+            &self.depth.bids
+        };
+        if levels.is_empty() {
+            return None;
+        }
+
+        Some(
+            levels
+                .iter()
+                .filter(|level| {
+                    if buy {
+                        level.px <= limit_px
+                    } else {
+                        level.px >= limit_px
+                    }
+                })
+                .map(|level| level.qty)
+                .sum(),
+        )
+    }
+}
+
+/// Widest YES spread a quote can show and still be traded; crossing a wide
+/// book costs more than the lag being chased.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpreadLimit {
+    /// In YES price units.
+    Absolute(f64),
+    /// As a fraction of the mid.
+    Relative(f64),
+}
+
+impl SpreadLimit {
+    pub fn allows(self, quote: &PolymarketQuoteTick) -> bool {
+        let spread = quote.spread();
+        if !spread.is_finite() {
+            return false;
+        }
+
+        match self {
+            Self::Absolute(max) => spread <= max,
+            Self::Relative(max) => quote.mid_yes > 0.0 && spread / quote.mid_yes <= max,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +225,7 @@ pub enum NormalizePolymarketQuoteError {
 #[derive(Debug, Clone, PartialEq)]
 pub struct RawPolymarketQuote {
     pub market_slug: String,
+    pub source: QuoteSource,
     pub best_yes_bid: f64,
     pub best_yes_ask: f64,
     pub ts: u64,
@@ -44,22 +251,106 @@ impl RawPolymarketQuote {
 
         Ok(PolymarketQuoteTick {
             market_slug: self.market_slug,
+            source: self.source,
+            asset: Asset::default(),
             best_yes_bid: self.best_yes_bid,
             best_yes_ask: self.best_yes_ask,
             mid_yes,
             ts: self.ts,
+            depth: BookDepth::default(),
+            liquidity_score: None,
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{NormalizePolymarketQuoteError, RawPolymarketQuote};
+    use super::{
+        Asset, BookDepth, BookLevel, NormalizePolymarketQuoteError, PolymarketQuoteTick,
+        QuoteSource, RawPolymarketQuote, SpreadLimit,
+    };
+
+    fn quote_with_depth() -> PolymarketQuoteTick {
+        PolymarketQuoteTick {
+            market_slug: "btc-up-down".to_string(),
+            source: QuoteSource::Polymarket,
+            asset: Asset::Btc,
+            best_yes_bid: 0.48,
+            best_yes_ask: 0.52,
+            mid_yes: 0.50,
+            ts: 1,
+            depth: BookDepth {
+                bids: vec![
+                    BookLevel {
+                        px: 0.48,
+                        qty: 10.0,
+                    },
+                    BookLevel {
+                        px: 0.46,
+                        qty: 30.0,
+                    },
+                ],
+                asks: vec![
+                    BookLevel { px: 0.52, qty: 5.0 },
+                    BookLevel {
+                        px: 0.55,
+                        qty: 15.0,
+                    },
+                ],
+            },
+            liquidity_score: None,
+        }
+    }
+
+    #[test]
+    fn impact_price_walks_levels_on_each_side() {
+        let quote = quote_with_depth();
+
+        assert_eq!(quote.impact_price(5.0), Some(0.52));
+        assert!((quote.impact_price(10.0).unwrap() - 0.535).abs() < 1e-12);
+        assert!((quote.impact_price(-20.0).unwrap() - 0.47).abs() < 1e-12);
+    }
+
+    #[test]
+    fn impact_price_rejects_size_beyond_depth() {
+        let quote = quote_with_depth();
+
+        assert_eq!(quote.impact_price(21.0), None);
+        assert_eq!(quote.impact_price(0.0), None);
+        assert_eq!(quote.depth_within(true, 0.53), Some(5.0));
+        assert_eq!(quote.depth_within(false, 0.46), Some(40.0));
+        assert_eq!(quote.touch_qty(true), Some(5.0));
+        assert_eq!(quote.touch_qty(false), Some(10.0));
+    }
+
+    #[test]
+    fn spread_limit_checks_absolute_or_relative_width() {
+        let quote = quote_with_depth();
+
+        assert!((quote.spread() - 0.04).abs() < 1e-12);
+        assert!(SpreadLimit::Absolute(0.05).allows(&quote));
+        assert!(!SpreadLimit::Absolute(0.03).allows(&quote));
+        assert!(SpreadLimit::Relative(0.09).allows(&quote));
+        assert!(!SpreadLimit::Relative(0.05).allows(&quote));
+    }
+
+    #[test]
+    fn impact_price_uses_touch_without_depth() {
+        let quote = PolymarketQuoteTick {
+            depth: BookDepth::default(),
+            ..quote_with_depth()
+        };
+
+        assert_eq!(quote.impact_price(1_000.0), Some(0.52));
+        assert_eq!(quote.impact_price(-1_000.0), Some(0.48));
+        assert_eq!(quote.depth_within(true, 1.0), None);
+    }
 
     #[test]
     fn normalize_quote_computes_mid() {
         let quote = RawPolymarketQuote {
             market_slug: "btc-up-down".to_string(),
+            source: QuoteSource::Polymarket,
             best_yes_bid: 0.45,
             best_yes_ask: 0.55,
             ts: 1,
@@ -73,6 +364,7 @@ mod tests {
     fn normalize_quote_rejects_non_finite_values() {
         let quote = RawPolymarketQuote {
             market_slug: "btc-up-down".to_string(),
+            source: QuoteSource::Polymarket,
             best_yes_bid: f64::NAN,
             best_yes_ask: 0.55,
             ts: 1,
@@ -86,6 +378,7 @@ mod tests {
     fn normalize_quote_rejects_negative_bid() {
         let quote = RawPolymarketQuote {
             market_slug: "btc-up-down".to_string(),
+            source: QuoteSource::Polymarket,
             best_yes_bid: -0.01,
             best_yes_ask: 0.55,
             ts: 1,
@@ -99,6 +392,7 @@ mod tests {
     fn normalize_quote_rejects_ask_above_one() {
         let quote = RawPolymarketQuote {
             market_slug: "btc-up-down".to_string(),
+            source: QuoteSource::Polymarket,
             best_yes_bid: 0.45,
             best_yes_ask: 1.01,
             ts: 1,
@@ -112,6 +406,7 @@ mod tests {
     fn normalize_quote_rejects_crossed_book() {
         let quote = RawPolymarketQuote {
             market_slug: "btc-up-down".to_string(),
+            source: QuoteSource::Polymarket,
             best_yes_bid: 0.56,
             best_yes_ask: 0.55,
             ts: 1,
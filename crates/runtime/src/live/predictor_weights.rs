@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::live::predictors::{PredictorSource, PredictorTick};
+
+const DEFAULT_LEARNING_RATE: f64 = 2.0;
+/// Weights never fall below this, so a source that had a bad run can earn
+/// its way back.
+const MIN_WEIGHT: f64 = 0.05;
+/// Markets awaiting resolution; the oldest is forgotten past this.
+const MAX_PENDING_MARKETS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PredictorWeightsError {
+    /// `learning_rate` must be positive and finite.
+    InvalidLearningRate,
+    /// The weights file could not be read or written.
+    Io,
+    /// The weights file is not valid JSON.
+    InvalidFile,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceWeight {
+    pub source: PredictorSource,
+    pub weight: f64,
+    /// Resolved markets this source has been scored on.
+    pub resolved: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PendingMarket {
+    market_id: String,
+    forecasts: Vec<(PredictorSource, f64)>,
+}
+
+/// Learns how much to trust each predictor from how its forecasts fared once
+/// markets resolved.
+///
+/// Every resolution scores each source's last forecast for that market by its
+/// squared error against the 0/1 outcome and scales its weight by
+/// `exp(-learning_rate * error)`. Weights are renormalized to average 1, so
+/// they multiply a tick's own confidence without changing its scale. Only the
+/// weights are persisted; forecasts for unresolved markets start over on
+/// restart.
+#[derive(Debug, Clone)]
+pub struct PredictorWeights {
+    learning_rate: f64,
+    weights: Vec<SourceWeight>,
+    pending: VecDeque<PendingMarket>,
+}
+
+impl PredictorWeights {
+    pub fn new(learning_rate: f64) -> Result<Self, PredictorWeightsError> {
+        if !learning_rate.is_finite() || learning_rate <= 0.0 {
+            return Err(PredictorWeightsError::InvalidLearningRate);
+        }
+
+        Ok(Self {
+            learning_rate,
+            weights: Vec::new(),
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Restores weights saved by [`save`](Self::save); a missing file starts
+    /// every source at weight 1.
+    pub fn load(path: &Path, learning_rate: f64) -> Result<Self, PredictorWeightsError> {
+        let mut weights = Self::new(learning_rate)?;
+        let raw = match fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(weights),
+            Err(_) => return Err(PredictorWeightsError::Io),
+        };
+        let saved: Vec<SourceWeight> =
+            serde_json::from_str(&raw).map_err(|_| PredictorWeightsError::InvalidFile)?;
+        if saved
+            .iter()
+            .any(|entry| !entry.weight.is_finite() || entry.weight <= 0.0)
+        {
+            return Err(PredictorWeightsError::InvalidFile);
+        }
+        weights.weights = saved;
+        Ok(weights)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PredictorWeightsError> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|_| PredictorWeightsError::Io)?;
+        }
+        let raw = serde_json::to_string_pretty(&self.weights)
+            .map_err(|_| PredictorWeightsError::InvalidFile)?;
+        fs::write(path, raw).map_err(|_| PredictorWeightsError::Io)
+    }
+
+    /// Current weight for `source`; 1 until it has been scored.
+    pub fn weight(&self, source: &PredictorSource) -> f64 {
+        self.weights
+            .iter()
+            .find(|entry| &entry.source == source)
+            .map_or(1.0, |entry| entry.weight)
+    }
+
+    pub fn weights(&self) -> &[SourceWeight] {
+        &self.weights
+    }
+
+    /// Remembers the latest forecast from each tick as its source's call on
+    /// `market_id`.
+    pub fn observe(&mut self, market_id: &str, ticks: &[PredictorTick]) {
+        if ticks.is_empty() {
+            return;
+        }
+
+        let idx = match self
+            .pending
+            .iter()
+            .position(|pending| pending.market_id == market_id)
+        {
+            Some(idx) => idx,
+            None => {
+                if self.pending.len() >= MAX_PENDING_MARKETS {
+                    self.pending.pop_front();
+                }
+                self.pending.push_back(PendingMarket {
+                    market_id: market_id.to_string(),
+                    forecasts: Vec::new(),
+                });
+                self.pending.len() - 1
+            }
+        };
+        let forecasts = &mut self.pending[idx].forecasts;
+        for tick in ticks {
+            match forecasts
+                .iter_mut()
+                .find(|(source, _)| *source == tick.source)
+            {
+                Some((_, px)) => *px = tick.predicted_yes_px,
+                None => forecasts.push((tick.source.clone(), tick.predicted_yes_px)),
+            }
+        }
+    }
+
+    /// Markets with forecasts still waiting on an outcome, oldest first.
+    pub fn pending_markets(&self) -> impl Iterator<Item = &str> {
+        self.pending
+            .iter()
+            .map(|pending| pending.market_id.as_str())
+    }
+
+    /// Scores the forecasts made for `market_id` now that it resolved
+    /// `resolved_yes` and updates the weights. Returns `false` when no
+    /// forecasts were recorded for the market.
+    pub fn resolve(&mut self, market_id: &str, resolved_yes: bool) -> bool {
+        let Some(idx) = self
+            .pending
+            .iter()
+            .position(|pending| pending.market_id == market_id)
+        else {
+            return false;
+        };
+        let Some(pending) = self.pending.remove(idx) else {
+            return false;
+        };
+
+        let outcome = if resolved_yes { 1.0 } else { 0.0 };
+        for (source, px) in pending.forecasts {
+            let loss = (px - outcome) * (px - outcome);
+            let factor = (-self.learning_rate * loss).exp();
+            match self.weights.iter_mut().find(|entry| entry.source == source) {
+                Some(entry) => {
+                    entry.weight *= factor;
+                    entry.resolved = entry.resolved.saturating_add(1);
+                }
+                None => self.weights.push(SourceWeight {
+                    source,
+                    weight: factor,
+                    resolved: 1,
+                }),
+            }
+        }
+
+        let mean =
+            self.weights.iter().map(|entry| entry.weight).sum::<f64>() / self.weights.len() as f64;
+        for entry in &mut self.weights {
+            entry.weight = (entry.weight / mean).max(MIN_WEIGHT);
+        }
+        true
+    }
+}
+
+impl Default for PredictorWeights {
+    fn default() -> Self {
+        Self {
+            learning_rate: DEFAULT_LEARNING_RATE,
+            weights: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(source: &str, predicted_yes_px: f64) -> PredictorTick {
+        PredictorTick {
+            source: PredictorSource::parse(source).unwrap(),
+            predicted_yes_px,
+            confidence: 0.9,
+            ts_ms: 0,
+        }
+    }
+
+    #[test]
+    fn shifts_weight_toward_the_more_accurate_source() {
+        let mut weights = PredictorWeights::default();
+
+        for i in 0..2 {
+            let market_id = format!("btc-{i}");
+            weights.observe(
+                &market_id,
+                &[tick("tradingview", 0.5), tick("cryptoquant", 0.3)],
+            );
+            weights.observe(&market_id, &[tick("tradingview", 0.8)]);
+            assert!(weights.resolve(&market_id, true));
+        }
+
+        let tv = weights.weight(&PredictorSource::parse("tradingview").unwrap());
+        let cq = weights.weight(&PredictorSource::parse("cryptoquant").unwrap());
+        assert!(tv > 1.0 && cq < 1.0, "tv={tv} cq={cq}");
+        assert!(((tv + cq) / 2.0 - 1.0).abs() < 1e-9);
+        assert_eq!(weights.weights()[0].resolved, 2);
+        assert!(!weights.resolve("btc-0", true));
+        assert_eq!(weights.pending_markets().count(), 0);
+    }
+
+    #[test]
+    fn round_trips_through_a_file_and_defaults_when_missing() {
+        let dir = std::env::temp_dir().join(format!(
+            "predictor-weights-{}-{}",
+            std::process::id(),
+            line!()
+        ));
+        let path = dir.join("weights.json");
+        let mut weights = PredictorWeights::default();
+        weights.observe("btc-a", &[tick("tradingview", 0.9)]);
+        weights.observe("btc-a", &[tick("cryptoquant", 0.2)]);
+        weights.resolve("btc-a", false);
+
+        weights.save(&path).unwrap();
+        let restored = PredictorWeights::load(&path, DEFAULT_LEARNING_RATE).unwrap();
+
+        assert_eq!(restored.weights(), weights.weights());
+        let fresh = PredictorWeights::load(&dir.join("missing.json"), 1.0).unwrap();
+        assert_eq!(
+            fresh.weight(&PredictorSource::parse("cryptoquant").unwrap()),
+            1.0
+        );
+        assert_eq!(
+            PredictorWeights::new(0.0).unwrap_err(),
+            PredictorWeightsError::InvalidLearningRate
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -1,14 +1,53 @@
 use serde::{Deserialize, Serialize};
 
+use crate::live::predictor_weights::PredictorWeights;
+
 const DEFAULT_FRESHNESS_WINDOW_MS: u64 = 5_000;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum PredictorSource {
-    TradingView,
-    CryptoQuant,
+/// Label of a configured predictor endpoint, e.g. `tradingview`.
+///
+/// Labels are lowercase ASCII letters, digits, `-` and `_`, so they can go
+/// straight into recorded CSV rows and task names.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PredictorSource(String);
+
+impl PredictorSource {
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+        valid.then(|| Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+impl TryFrom<String> for PredictorSource {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        // Weights saved before sources were configurable used the variant
+        // names of the old two-source enum.
+        let value = match value.as_str() {
+            "TradingView" => "tradingview",
+            "CryptoQuant" => "cryptoquant",
+            other => other,
+        };
+        Self::parse(value).ok_or_else(|| format!("invalid predictor label `{value}`"))
+    }
+}
+
+impl From<PredictorSource> for String {
+    fn from(source: PredictorSource) -> Self {
+        source.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PredictorTick {
     pub source: PredictorSource,
     pub predicted_yes_px: f64,
@@ -33,6 +72,24 @@ pub enum PredictorFusionError {
 pub fn fuse_predictors(
     ticks: &[PredictorTick],
     now_ms: u64,
+) -> Result<FusedFairValue, PredictorFusionError> {
+    fuse_with(ticks, now_ms, |_| 1.0)
+}
+
+/// Like [`fuse_predictors`], with each tick's confidence scaled by its
+/// source's learned weight.
+pub fn fuse_weighted_predictors(
+    ticks: &[PredictorTick],
+    weights: &PredictorWeights,
+    now_ms: u64,
+) -> Result<FusedFairValue, PredictorFusionError> {
+    fuse_with(ticks, now_ms, |source| weights.weight(source))
+}
+
+fn fuse_with(
+    ticks: &[PredictorTick],
+    now_ms: u64,
+    weight: impl Fn(&PredictorSource) -> f64,
 ) -> Result<FusedFairValue, PredictorFusionError> {
     let mut weighted_sum = 0.0;
     let mut confidence_sum = 0.0;
@@ -52,8 +109,9 @@ pub fn fuse_predictors(
             continue;
         }
 
-        weighted_sum += tick.predicted_yes_px * tick.confidence;
-        confidence_sum += tick.confidence;
+        let confidence = tick.confidence * weight(&tick.source);
+        weighted_sum += tick.predicted_yes_px * confidence;
+        confidence_sum += confidence;
         source_count += 1;
         if age_ms > max_age_ms {
             max_age_ms = age_ms;
@@ -90,9 +148,45 @@ mod tests {
         assert_eq!(fused.source_count, 1);
     }
 
+    #[test]
+    fn labels_are_validated_and_legacy_names_still_load() {
+        assert!(PredictorSource::parse("glassnode_v2").is_some());
+        assert!(PredictorSource::parse("").is_none());
+        assert!(PredictorSource::parse("Bad,Label").is_none());
+
+        let legacy: PredictorSource = serde_json::from_str("\"TradingView\"").unwrap();
+        assert_eq!(legacy.as_str(), "tradingview");
+        assert_eq!(serde_json::to_string(&legacy).unwrap(), "\"tradingview\"");
+    }
+
+    #[test]
+    fn learned_weights_pull_the_fused_value_toward_the_trusted_source() {
+        let mut weights = PredictorWeights::default();
+        weights.observe(
+            "btc-a",
+            &[
+                PredictorTick {
+                    predicted_yes_px: 0.9,
+                    ..tv_tick()
+                },
+                PredictorTick {
+                    predicted_yes_px: 0.1,
+                    ..cq_tick()
+                },
+            ],
+        );
+        weights.resolve("btc-a", false);
+
+        let plain = fuse_predictors(&[tv_tick(), cq_tick()], 10_000).unwrap();
+        let weighted = fuse_weighted_predictors(&[tv_tick(), cq_tick()], &weights, 10_000).unwrap();
+
+        assert!(weighted.fair_yes_px < plain.fair_yes_px);
+        assert_eq!(weighted.source_count, 2);
+    }
+
     fn tv_tick() -> PredictorTick {
         PredictorTick {
-            source: PredictorSource::TradingView,
+            source: PredictorSource::parse("tradingview").unwrap(),
             predicted_yes_px: 0.513,
             confidence: 0.9,
             ts_ms: 9_800,
@@ -101,7 +195,7 @@ mod tests {
 
     fn cq_tick() -> PredictorTick {
         PredictorTick {
-            source: PredictorSource::CryptoQuant,
+            source: PredictorSource::parse("cryptoquant").unwrap(),
             predicted_yes_px: 0.509,
             confidence: 0.8,
             ts_ms: 9_900,
@@ -110,7 +204,7 @@ mod tests {
 
     fn stale_tv_tick() -> PredictorTick {
         PredictorTick {
-            source: PredictorSource::TradingView,
+            source: PredictorSource::parse("tradingview").unwrap(),
             predicted_yes_px: 0.6,
             confidence: 0.9,
             ts_ms: 0,
@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+
+/// Raw payloads are cut to this many bytes before they are kept.
+pub const MAX_QUARANTINED_RAW_BYTES: usize = 2_048;
+const DEFAULT_QUARANTINE_CAPACITY: usize = 100;
+
+/// A payload a feed received but could not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedPayload {
+    pub reason: String,
+    pub raw: String,
+}
+
+impl RejectedPayload {
+    pub fn new(reason: impl Into<String>, raw: &str) -> Self {
+        Self {
+            reason: reason.into(),
+            raw: truncate_at_char_boundary(raw, MAX_QUARANTINED_RAW_BYTES).to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedPayload {
+    pub source: String,
+    pub reason: String,
+    pub raw: String,
+    pub ts_ms: u64,
+}
+
+/// Recent unparseable payloads and a running failure count per source, kept
+/// so schema drift on a venue shows up instead of vanishing as a dropped
+/// message.
+#[derive(Debug, Clone)]
+pub struct QuarantineBuffer {
+    capacity: usize,
+    recent: VecDeque<QuarantinedPayload>,
+    counts: Vec<(String, u64)>,
+}
+
+impl QuarantineBuffer {
+    /// Keeps at most `capacity` payloads, dropping the oldest; counts are
+    /// never trimmed.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            recent: VecDeque::with_capacity(capacity),
+            counts: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, source: &str, rejected: RejectedPayload, ts_ms: u64) {
+        match self.counts.iter_mut().find(|(name, _)| name == source) {
+            Some((_, count)) => *count = count.saturating_add(1),
+            None => self.counts.push((source.to_string(), 1)),
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+        if self.recent.len() >= self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(QuarantinedPayload {
+            source: source.to_string(),
+            reason: rejected.reason,
+            raw: rejected.raw,
+            ts_ms,
+        });
+    }
+
+    /// `(source, failures)` pairs in the order sources first failed.
+    pub fn counts(&self) -> &[(String, u64)] {
+        &self.counts
+    }
+
+    /// Retained payloads, oldest first.
+    pub fn recent(&self) -> impl Iterator<Item = &QuarantinedPayload> {
+        self.recent.iter()
+    }
+}
+
+impl Default for QuarantineBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUARANTINE_CAPACITY)
+    }
+}
+
+fn truncate_at_char_boundary(raw: &str, max_bytes: usize) -> &str {
+    if raw.len() <= max_bytes {
+        return raw;
+    }
+    let mut end = max_bytes;
+    while !raw.is_char_boundary(end) {
+        end -= 1;
+    }
+    &raw[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_recent_payloads_and_counts_every_failure() {
+        let mut quarantine = QuarantineBuffer::new(2);
+        quarantine.record("coinbase", RejectedPayload::new("invalid_json", "{"), 1);
+        quarantine.record("kraken", RejectedPayload::new("invalid_price", "{}"), 2);
+        quarantine.record("coinbase", RejectedPayload::new("invalid_size", "[]"), 3);
+
+        assert_eq!(
+            quarantine.counts(),
+            &[("coinbase".to_string(), 2), ("kraken".to_string(), 1)]
+        );
+        let reasons: Vec<&str> = quarantine
+            .recent()
+            .map(|payload| payload.reason.as_str())
+            .collect();
+        assert_eq!(reasons, vec!["invalid_price", "invalid_size"]);
+    }
+
+    #[test]
+    fn truncates_large_payloads_on_a_char_boundary() {
+        let raw = format!("{}é", "a".repeat(MAX_QUARANTINED_RAW_BYTES - 1));
+
+        let rejected = RejectedPayload::new("invalid_json", &raw);
+
+        assert_eq!(rejected.raw.len(), MAX_QUARANTINED_RAW_BYTES - 1);
+    }
+}
@@ -0,0 +1,249 @@
+use serde::Serialize;
+
+use crate::live::venue::VenueFeedError;
+
+const DEFAULT_BLOCKED_COOLDOWN_MS: u64 = 15 * 60 * 1_000;
+
+/// Reconnect schedule shared by every feed client, streaming or polled.
+///
+/// Consecutive failures back off exponentially from `base_ms` up to `max_ms`.
+/// Once `breaker_threshold` failures pile up the circuit opens and the client
+/// only probes the venue every `breaker_cooldown_ms`. `jitter_ratio` is the
+/// fraction of each delay that is randomized so clients recovering from the
+/// same outage do not reconnect in lockstep.
+///
+/// A venue that refuses this host outright (geo-block or ban) is left alone
+/// for `blocked_cooldown_ms` instead, since retrying sooner only invites a
+/// longer ban.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    pub base_ms: u64,
+    pub max_ms: u64,
+    pub jitter_ratio: f64,
+    pub breaker_threshold: u32,
+    pub breaker_cooldown_ms: u64,
+    pub blocked_cooldown_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectPolicyError {
+    InvalidBackoff,
+    InvalidJitter,
+    InvalidBreaker,
+}
+
+impl ReconnectPolicy {
+    pub fn new(
+        base_ms: u64,
+        max_ms: u64,
+        jitter_ratio: f64,
+        breaker_threshold: u32,
+        breaker_cooldown_ms: u64,
+    ) -> Result<Self, ReconnectPolicyError> {
+        if base_ms == 0 || max_ms < base_ms {
+            return Err(ReconnectPolicyError::InvalidBackoff);
+        }
+        if !jitter_ratio.is_finite() || !(0.0..=1.0).contains(&jitter_ratio) {
+            return Err(ReconnectPolicyError::InvalidJitter);
+        }
+        if breaker_threshold == 0 || breaker_cooldown_ms < max_ms {
+            return Err(ReconnectPolicyError::InvalidBreaker);
+        }
+
+        Ok(Self {
+            base_ms,
+            max_ms,
+            jitter_ratio,
+            breaker_threshold,
+            breaker_cooldown_ms,
+            blocked_cooldown_ms: DEFAULT_BLOCKED_COOLDOWN_MS,
+        })
+    }
+
+    pub fn circuit_open(&self, failures: u32) -> bool {
+        failures >= self.breaker_threshold
+    }
+
+    /// Un-jittered delay after `failures` consecutive failures (1-based).
+    pub fn backoff_ms(&self, failures: u32) -> u64 {
+        if self.circuit_open(failures) {
+            return self.breaker_cooldown_ms;
+        }
+        let exponent = failures.saturating_sub(1);
+        self.base_ms
+            .saturating_mul(1_u64.checked_shl(exponent).unwrap_or(u64::MAX))
+            .min(self.max_ms)
+    }
+
+    /// Delay with jitter applied; `unit` is a uniform sample in `[0, 1)`.
+    pub fn delay_ms(&self, failures: u32, unit: f64) -> u64 {
+        let backoff = self.backoff_ms(failures);
+        let unit = if unit.is_finite() {
+            unit.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let jitter = (backoff as f64 * self.jitter_ratio * unit).round() as u64;
+        backoff.saturating_sub(jitter)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_ms: 500,
+            max_ms: 30_000,
+            jitter_ratio: 0.5,
+            breaker_threshold: 8,
+            breaker_cooldown_ms: 120_000,
+            blocked_cooldown_ms: DEFAULT_BLOCKED_COOLDOWN_MS,
+        }
+    }
+}
+
+/// A scheduled reconnect, published so operators can see flapping feeds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeedReconnect {
+    pub venue: String,
+    /// Consecutive failures so far, starting at 1.
+    pub attempt: u32,
+    pub delay_ms: u64,
+    pub circuit_open: bool,
+    pub reason: VenueFeedError,
+}
+
+/// Failure counter driving a [`ReconnectPolicy`].
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    policy: ReconnectPolicy,
+    failures: u32,
+}
+
+impl ReconnectBackoff {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self {
+            policy,
+            failures: 0,
+        }
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn circuit_open(&self) -> bool {
+        self.policy.circuit_open(self.failures)
+    }
+
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Counts a failure and schedules the next attempt with a random jitter.
+    pub fn record_failure(&mut self, venue: &str, reason: VenueFeedError) -> FeedReconnect {
+        self.record_failure_with(venue, reason, fastrand::f64())
+    }
+
+    pub fn record_failure_with(
+        &mut self,
+        venue: &str,
+        reason: VenueFeedError,
+        unit: f64,
+    ) -> FeedReconnect {
+        self.failures = self.failures.saturating_add(1);
+        let delay_ms = match reason {
+            VenueFeedError::Blocked(_) => self.policy.blocked_cooldown_ms,
+            _ => self.policy.delay_ms(self.failures, unit),
+        };
+        FeedReconnect {
+            venue: venue.to_string(),
+            attempt: self.failures,
+            delay_ms,
+            circuit_open: self.circuit_open(),
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_until_capped() {
+        let policy = ReconnectPolicy::default();
+
+        assert_eq!(policy.backoff_ms(1), 500);
+        assert_eq!(policy.backoff_ms(2), 1_000);
+        assert_eq!(policy.backoff_ms(4), 4_000);
+        assert_eq!(policy.backoff_ms(7), 30_000);
+    }
+
+    #[test]
+    fn jitter_only_shortens_delay_within_ratio() {
+        let policy = ReconnectPolicy::default();
+
+        assert_eq!(policy.delay_ms(3, 0.0), 2_000);
+        assert_eq!(policy.delay_ms(3, 0.5), 1_500);
+        assert_eq!(policy.delay_ms(3, 0.999_999), 1_000);
+        assert_eq!(policy.delay_ms(3, f64::NAN), 2_000);
+    }
+
+    #[test]
+    fn circuit_opens_after_threshold_and_resets_on_success() {
+        let policy = ReconnectPolicy::new(100, 1_000, 0.0, 3, 60_000).unwrap();
+        let mut backoff = ReconnectBackoff::new(policy);
+
+        let first = backoff.record_failure_with("okx", VenueFeedError::Connect, 0.0);
+        backoff.record_failure_with("okx", VenueFeedError::Connect, 0.0);
+        let third = backoff.record_failure_with("okx", VenueFeedError::Connect, 0.0);
+
+        assert_eq!(
+            (first.attempt, first.delay_ms, first.circuit_open),
+            (1, 100, false)
+        );
+        assert_eq!(
+            (third.attempt, third.delay_ms, third.circuit_open),
+            (3, 60_000, true)
+        );
+
+        backoff.record_success();
+        assert_eq!(backoff.failures(), 0);
+        assert!(!backoff.circuit_open());
+    }
+
+    #[test]
+    fn blocked_venues_wait_out_the_blocked_cooldown() {
+        let policy = ReconnectPolicy {
+            blocked_cooldown_ms: 600_000,
+            ..ReconnectPolicy::default()
+        };
+        let mut backoff = ReconnectBackoff::new(policy);
+
+        let blocked = backoff.record_failure_with("binance", VenueFeedError::Blocked(451), 0.5);
+        assert_eq!((blocked.attempt, blocked.delay_ms), (1, 600_000));
+        let closed = backoff.record_failure_with("binance", VenueFeedError::Closed, 0.0);
+        assert_eq!((closed.attempt, closed.delay_ms), (2, 1_000));
+        assert_eq!(
+            VenueFeedError::blocked_by(418),
+            Some(VenueFeedError::Blocked(418))
+        );
+        assert_eq!(VenueFeedError::blocked_by(404), None);
+    }
+
+    #[test]
+    fn rejects_inconsistent_policies() {
+        assert_eq!(
+            ReconnectPolicy::new(0, 1_000, 0.5, 3, 60_000),
+            Err(ReconnectPolicyError::InvalidBackoff)
+        );
+        assert_eq!(
+            ReconnectPolicy::new(100, 1_000, 1.5, 3, 60_000),
+            Err(ReconnectPolicyError::InvalidJitter)
+        );
+        assert_eq!(
+            ReconnectPolicy::new(100, 1_000, 0.5, 0, 60_000),
+            Err(ReconnectPolicyError::InvalidBreaker)
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use strategy::Regime;
+
+/// Returns seen before the classifier leaves [`Regime::Normal`].
+const WARMUP_RETURNS: u32 = 5;
+const MS_PER_MINUTE: f64 = 60_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegimeClassifierConfigError {
+    /// `half_life_ms` must be greater than zero.
+    InvalidHalfLife,
+    /// Thresholds must be positive and finite, with calm below volatile.
+    InvalidThresholds,
+}
+
+/// Realized volatility bounds, in basis points per square-root minute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegimeThresholds {
+    calm_below_bps: f64,
+    volatile_above_bps: f64,
+}
+
+impl RegimeThresholds {
+    pub fn new(
+        calm_below_bps: f64,
+        volatile_above_bps: f64,
+    ) -> Result<Self, RegimeClassifierConfigError> {
+        if !calm_below_bps.is_finite()
+            || !volatile_above_bps.is_finite()
+            || calm_below_bps <= 0.0
+            || volatile_above_bps <= calm_below_bps
+        {
+            return Err(RegimeClassifierConfigError::InvalidThresholds);
+        }
+
+        Ok(Self {
+            calm_below_bps,
+            volatile_above_bps,
+        })
+    }
+
+    pub fn calm_below_bps(&self) -> f64 {
+        self.calm_below_bps
+    }
+
+    pub fn volatile_above_bps(&self) -> f64 {
+        self.volatile_above_bps
+    }
+}
+
+impl Default for RegimeThresholds {
+    fn default() -> Self {
+        Self {
+            calm_below_bps: 4.0,
+            volatile_above_bps: 12.0,
+        }
+    }
+}
+
+/// Classifies the BTC composite into a sizing [`Regime`] from its realized
+/// volatility.
+///
+/// Each log return is scaled to a one-minute variance by the time since the
+/// previous price, then folded into a time-aware EWMA like
+/// [`EwmaSmoother`](super::EwmaSmoother), so irregular sampling does not bias
+/// the estimate.
+#[derive(Debug, Clone)]
+pub struct RegimeClassifier {
+    half_life_ms: u64,
+    thresholds: RegimeThresholds,
+    last_px: Option<(f64, u64)>,
+    variance_per_min: Option<f64>,
+    returns_seen: u32,
+}
+
+impl RegimeClassifier {
+    pub fn new(
+        half_life_ms: u64,
+        thresholds: RegimeThresholds,
+    ) -> Result<Self, RegimeClassifierConfigError> {
+        if half_life_ms == 0 {
+            return Err(RegimeClassifierConfigError::InvalidHalfLife);
+        }
+
+        Ok(Self {
+            half_life_ms,
+            thresholds,
+            last_px: None,
+            variance_per_min: None,
+            returns_seen: 0,
+        })
+    }
+
+    pub fn thresholds(&self) -> RegimeThresholds {
+        self.thresholds
+    }
+
+    /// Folds the composite price `px` observed at `ts` into the estimate and
+    /// returns the resulting regime.
+    ///
+    /// Non-positive prices and samples not newer than the last one are ignored.
+    pub fn update(&mut self, px: f64, ts: u64) -> Regime {
+        if !px.is_finite() || px <= 0.0 {
+            return self.regime();
+        }
+
+        let Some((last_px, last_ts)) = self.last_px else {
+            self.last_px = Some((px, ts));
+            return self.regime();
+        };
+        if ts <= last_ts {
+            return self.regime();
+        }
+
+        let elapsed_ms = (ts - last_ts) as f64;
+        let log_return = (px / last_px).ln();
+        let sample = log_return * log_return * (MS_PER_MINUTE / elapsed_ms);
+        self.variance_per_min = Some(match self.variance_per_min {
+            None => sample,
+            Some(current) => {
+                let alpha = 1.0 - 0.5_f64.powf(elapsed_ms / self.half_life_ms as f64);
+                current + alpha * (sample - current)
+            }
+        });
+        self.last_px = Some((px, ts));
+        self.returns_seen = self.returns_seen.saturating_add(1);
+
+        self.regime()
+    }
+
+    /// Current volatility estimate in basis points per square-root minute.
+    pub fn volatility_bps(&self) -> Option<f64> {
+        self.variance_per_min
+            .map(|variance| variance.sqrt() * 10_000.0)
+    }
+
+    pub fn regime(&self) -> Regime {
+        if self.returns_seen < WARMUP_RETURNS {
+            return Regime::Normal;
+        }
+        match self.volatility_bps() {
+            Some(vol) if vol < self.thresholds.calm_below_bps => Regime::Calm,
+            Some(vol) if vol > self.thresholds.volatile_above_bps => Regime::Volatile,
+            _ => Regime::Normal,
+        }
+    }
+}
+
+impl Default for RegimeClassifier {
+    fn default() -> Self {
+        Self {
+            half_life_ms: 300_000,
+            thresholds: RegimeThresholds::default(),
+            last_px: None,
+            variance_per_min: None,
+            returns_seen: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(classifier: &mut RegimeClassifier, step_bps: f64) -> Regime {
+        let mut px = 64_000.0;
+        let mut regime = classifier.update(px, 0);
+        for i in 1..=20_u64 {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            px *= 1.0 + sign * step_bps / 10_000.0;
+            regime = classifier.update(px, i * 60_000);
+        }
+        regime
+    }
+
+    #[test]
+    fn classifies_by_per_minute_volatility() {
+        assert_eq!(feed(&mut RegimeClassifier::default(), 1.0), Regime::Calm);
+        assert_eq!(feed(&mut RegimeClassifier::default(), 8.0), Regime::Normal);
+        assert_eq!(
+            feed(&mut RegimeClassifier::default(), 30.0),
+            Regime::Volatile
+        );
+
+        let mut classifier = RegimeClassifier::default();
+        feed(&mut classifier, 8.0);
+        let vol = classifier.volatility_bps().unwrap();
+        assert!((vol - 8.0).abs() < 0.1, "vol was {vol}");
+    }
+
+    #[test]
+    fn stays_normal_during_warmup_and_ignores_stale_samples() {
+        let mut classifier = RegimeClassifier::default();
+
+        assert_eq!(classifier.update(64_000.0, 1_000), Regime::Normal);
+        assert_eq!(classifier.update(66_000.0, 2_000), Regime::Normal);
+        assert_eq!(classifier.update(60_000.0, 2_000), Regime::Normal);
+        assert_eq!(classifier.update(f64::NAN, 3_000), Regime::Normal);
+        assert_eq!(classifier.returns_seen, 1);
+
+        assert_eq!(
+            RegimeThresholds::new(12.0, 4.0).unwrap_err(),
+            RegimeClassifierConfigError::InvalidThresholds
+        );
+        assert_eq!(
+            RegimeClassifier::new(0, RegimeThresholds::default()).unwrap_err(),
+            RegimeClassifierConfigError::InvalidHalfLife
+        );
+    }
+}
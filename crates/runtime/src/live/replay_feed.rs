@@ -0,0 +1,396 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::clock::Clock;
+use crate::live::predictors::PredictorTick;
+use crate::live::venue::{VenueFeed, VenueFeedError, VenueFuture, VenueTick};
+use crate::recorder::{RecordStream, RecordedTick};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayFeedError {
+    /// `speed` must be greater than zero and not NaN.
+    InvalidSpeed,
+    /// A recording could not be read from disk.
+    Unreadable,
+    /// A file's header does not match any recorded stream.
+    UnknownHeader,
+    /// A row does not match its stream's columns.
+    InvalidRow,
+}
+
+/// Replay start shared by every feed built from one recording, so venues stay
+/// in step with each other. As a [`Clock`] it reads the recorded time the
+/// replay has reached, so timing driven by it matches the recording whatever
+/// the replay speed.
+#[derive(Debug, Clone)]
+pub struct ReplayClock {
+    origin_ms: u64,
+    speed: f64,
+    started_at: Arc<OnceLock<Instant>>,
+    released_ms: Arc<AtomicU64>,
+}
+
+impl ReplayClock {
+    /// When the tick received at `recv_ms` is due.
+    fn due_at(&self, recv_ms: u64) -> Option<Instant> {
+        let started_at = *self.started_at.get()?;
+        if self.speed.is_infinite() {
+            return Some(started_at);
+        }
+        let offset_ms = recv_ms.saturating_sub(self.origin_ms) as f64 / self.speed;
+        Some(started_at + Duration::from_secs_f64(offset_ms / 1_000.0))
+    }
+
+    fn release(&self, recv_ms: u64) {
+        self.released_ms.fetch_max(recv_ms, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ReplayClock {
+    /// The first receive time until the replay starts, then the elapsed time
+    /// scaled by `speed`. A replay without pauses is as far as the latest
+    /// tick it released.
+    fn now_ms(&self) -> u64 {
+        let released_ms = self.released_ms.load(Ordering::Relaxed);
+        let Some(started_at) = self.started_at.get() else {
+            return released_ms;
+        };
+        if self.speed.is_infinite() {
+            return released_ms;
+        }
+        let elapsed_ms = started_at.elapsed().as_secs_f64() * 1_000.0 * self.speed;
+        self.origin_ms
+            .saturating_add(elapsed_ms as u64)
+            .max(released_ms)
+    }
+}
+
+/// [`VenueFeed`] over ticks captured by the
+/// [`TickRecorder`](crate::recorder::TickRecorder).
+///
+/// Ticks are released at their original inter-arrival times divided by
+/// `speed`; `f64::INFINITY` replays without pauses. Quote rows received
+/// together are released as one [`VenueTick::Quotes`] snapshot. Predictor rows
+/// are not venue ticks; they go to the recording's [`ReplayPredictors`]. Once
+/// the recording runs out the feed stays connected and quiet.
+pub struct ReplayFeed {
+    venue: String,
+    ticks: VecDeque<(u64, VenueTick)>,
+    clock: ReplayClock,
+}
+
+/// Recorded predictor ticks, released on the same clock as the feeds they
+/// were recorded with.
+pub struct ReplayPredictors {
+    ticks: VecDeque<(u64, PredictorTick)>,
+    clock: ReplayClock,
+}
+
+/// A recording split into its venue feeds and predictor ticks, with the clock
+/// they are released on.
+pub struct ReplayRecording {
+    pub feeds: Vec<ReplayFeed>,
+    pub predictors: ReplayPredictors,
+    pub clock: ReplayClock,
+}
+
+impl ReplayRecording {
+    /// Builds one feed per recorded venue, spot ticks by their venue label
+    /// and quotes by their source, and sets the predictor ticks aside.
+    pub fn from_rows(
+        mut rows: Vec<(u64, RecordedTick)>,
+        speed: f64,
+    ) -> Result<Self, ReplayFeedError> {
+        if speed.is_nan() || speed <= 0.0 {
+            return Err(ReplayFeedError::InvalidSpeed);
+        }
+
+        rows.sort_by_key(|(recv_ms, _)| *recv_ms);
+        let origin_ms = rows.first().map_or(0, |(recv_ms, _)| *recv_ms);
+        let clock = ReplayClock {
+            origin_ms,
+            speed,
+            started_at: Arc::new(OnceLock::new()),
+            released_ms: Arc::new(AtomicU64::new(origin_ms)),
+        };
+
+        let mut feeds: Vec<ReplayFeed> = Vec::new();
+        let mut predictors = ReplayPredictors {
+            ticks: VecDeque::new(),
+            clock: clock.clone(),
+        };
+        for (recv_ms, tick) in rows {
+            let (venue, tick) = match tick {
+                RecordedTick::Spot(asset, trade) => {
+                    (trade.venue.clone(), VenueTick::Spot(asset, trade))
+                }
+                RecordedTick::Quote(quote) => (
+                    quote.source.as_str().to_string(),
+                    VenueTick::Quotes(vec![quote]),
+                ),
+                RecordedTick::Predictor(predictor) => {
+                    predictors.ticks.push_back((recv_ms, predictor));
+                    continue;
+                }
+            };
+
+            let feed = match feeds.iter().position(|feed| feed.venue == venue) {
+                Some(idx) => &mut feeds[idx],
+                None => {
+                    feeds.push(ReplayFeed {
+                        venue,
+                        ticks: VecDeque::new(),
+                        clock: clock.clone(),
+                    });
+                    feeds.last_mut().expect("feed was pushed above")
+                }
+            };
+            match (feed.ticks.back_mut(), tick) {
+                (Some((last_ms, VenueTick::Quotes(batch))), VenueTick::Quotes(quotes))
+                    if *last_ms == recv_ms =>
+                {
+                    batch.extend(quotes);
+                }
+                (_, tick) => feed.ticks.push_back((recv_ms, tick)),
+            }
+        }
+        Ok(Self {
+            feeds,
+            predictors,
+            clock,
+        })
+    }
+
+    /// Reads every recorded `.csv` file in `dir`; each file's stream is taken
+    /// from its header.
+    pub fn load_dir(dir: &Path, speed: f64) -> Result<Self, ReplayFeedError> {
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .map_err(|_| ReplayFeedError::Unreadable)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+            .collect();
+        paths.sort();
+
+        let mut rows = Vec::new();
+        for path in paths {
+            let raw = fs::read_to_string(&path).map_err(|_| ReplayFeedError::Unreadable)?;
+            let mut lines = raw.lines();
+            let stream = lines
+                .next()
+                .and_then(RecordStream::from_header)
+                .ok_or(ReplayFeedError::UnknownHeader)?;
+            for line in lines.filter(|line| !line.trim().is_empty()) {
+                rows.push(
+                    RecordedTick::parse_csv_row(stream, line).ok_or(ReplayFeedError::InvalidRow)?,
+                );
+            }
+        }
+        Self::from_rows(rows, speed)
+    }
+}
+
+impl ReplayPredictors {
+    /// Ticks not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.ticks.len()
+    }
+
+    /// Waits until the next tick is due and returns it with its recorded
+    /// receive time, or `None` once the recording runs out. Starts the shared
+    /// replay clock if no feed has yet.
+    pub async fn next(&mut self) -> Option<(u64, PredictorTick)> {
+        let (recv_ms, _) = self.ticks.front()?;
+        self.clock.started_at.get_or_init(Instant::now);
+        if let Some(due_at) = self.clock.due_at(*recv_ms) {
+            tokio::time::sleep_until(due_at).await;
+        }
+        let (recv_ms, tick) = self.ticks.pop_front()?;
+        self.clock.release(recv_ms);
+        Some((recv_ms, tick))
+    }
+}
+
+impl ReplayFeed {
+    /// The venue feeds of [`ReplayRecording::from_rows`], without its
+    /// predictor ticks.
+    pub fn from_recording(
+        rows: Vec<(u64, RecordedTick)>,
+        speed: f64,
+    ) -> Result<Vec<Self>, ReplayFeedError> {
+        Ok(ReplayRecording::from_rows(rows, speed)?.feeds)
+    }
+
+    /// Ticks not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.ticks.len()
+    }
+
+    async fn read(&mut self) -> Result<Option<VenueTick>, VenueFeedError> {
+        let Some((recv_ms, _)) = self.ticks.front() else {
+            std::future::pending::<()>().await;
+            return Ok(None);
+        };
+        if let Some(due_at) = self.clock.due_at(*recv_ms) {
+            tokio::time::sleep_until(due_at).await;
+        }
+        Ok(self.ticks.pop_front().map(|(recv_ms, tick)| {
+            self.clock.release(recv_ms);
+            tick
+        }))
+    }
+}
+
+impl VenueFeed for ReplayFeed {
+    fn venue(&self) -> &str {
+        &self.venue
+    }
+
+    /// The first feed to connect starts the shared replay clock.
+    fn connect(&mut self) -> VenueFuture<'_, Result<(), VenueFeedError>> {
+        self.clock.started_at.get_or_init(Instant::now);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn next_tick(&mut self) -> VenueFuture<'_, Result<Option<VenueTick>, VenueFeedError>> {
+        Box::pin(self.read())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::{
+        Asset, BookDepth, NormalizedBtcTick, PolymarketQuoteTick, PredictorSource, QuoteSource,
+    };
+
+    fn spot(venue: &str, px: f64) -> RecordedTick {
+        RecordedTick::Spot(
+            Asset::Btc,
+            NormalizedBtcTick {
+                venue: venue.to_string(),
+                px,
+                size: 0.1,
+                ts: 0,
+            },
+        )
+    }
+
+    fn quote(slug: &str) -> RecordedTick {
+        RecordedTick::Quote(PolymarketQuoteTick {
+            market_slug: slug.to_string(),
+            source: QuoteSource::Polymarket,
+            asset: Asset::Btc,
+            best_yes_bid: 0.48,
+            best_yes_ask: 0.52,
+            mid_yes: 0.5,
+            ts: 0,
+            depth: BookDepth::default(),
+            liquidity_score: None,
+        })
+    }
+
+    #[test]
+    fn splits_recording_by_venue_and_batches_quotes() {
+        let feeds = ReplayFeed::from_recording(
+            vec![
+                (1_200, spot("kraken", 64_010.0)),
+                (1_000, spot("coinbase", 64_000.0)),
+                (1_100, quote("btc-a")),
+                (1_100, quote("btc-b")),
+                (1_500, quote("btc-a")),
+            ],
+            1.0,
+        )
+        .unwrap();
+
+        let venues: Vec<&str> = feeds.iter().map(|feed| feed.venue()).collect();
+        assert_eq!(venues, vec!["coinbase", "polymarket", "kraken"]);
+        assert_eq!(feeds[1].remaining(), 2);
+        match &feeds[1].ticks[0] {
+            (1_100, VenueTick::Quotes(batch)) => assert_eq!(batch.len(), 2),
+            other => panic!("unexpected tick: {other:?}"),
+        }
+        assert_eq!(
+            ReplayFeed::from_recording(Vec::new(), 0.0).err(),
+            Some(ReplayFeedError::InvalidSpeed)
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn replays_with_original_spacing_scaled_by_speed() {
+        let mut feeds = ReplayFeed::from_recording(
+            vec![
+                (10_000, spot("coinbase", 64_000.0)),
+                (10_200, spot("coinbase", 64_001.0)),
+            ],
+            4.0,
+        )
+        .unwrap();
+        let feed = &mut feeds[0];
+        feed.connect().await.unwrap();
+        let start = Instant::now();
+
+        assert!(feed.next_tick().await.unwrap().is_some());
+        assert!(start.elapsed() < Duration::from_millis(50));
+        match feed.next_tick().await.unwrap() {
+            Some(VenueTick::Spot(Asset::Btc, trade)) => assert_eq!(trade.px, 64_001.0),
+            other => panic!("unexpected tick: {other:?}"),
+        }
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(feed.remaining(), 0);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn unpaced_replay_clock_follows_released_ticks() {
+        let mut recording = ReplayRecording::from_rows(
+            vec![
+                (10_000, spot("coinbase", 64_000.0)),
+                (70_000, spot("coinbase", 64_001.0)),
+            ],
+            f64::INFINITY,
+        )
+        .unwrap();
+        let clock = recording.clock.clone();
+        let feed = &mut recording.feeds[0];
+        feed.connect().await.unwrap();
+        assert_eq!(clock.now_ms(), 10_000);
+
+        feed.next_tick().await.unwrap();
+        feed.next_tick().await.unwrap();
+        assert_eq!(clock.now_ms(), 70_000);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn sets_predictor_ticks_aside_on_the_shared_clock() {
+        let predictor = PredictorTick {
+            source: PredictorSource::parse("model").unwrap(),
+            predicted_yes_px: 0.6,
+            confidence: 1.0,
+            ts_ms: 10_150,
+        };
+        let mut recording = ReplayRecording::from_rows(
+            vec![
+                (10_000, spot("coinbase", 64_000.0)),
+                (10_200, RecordedTick::Predictor(predictor.clone())),
+            ],
+            4.0,
+        )
+        .unwrap();
+        assert_eq!(recording.feeds.len(), 1);
+        assert_eq!(recording.predictors.remaining(), 1);
+        assert_eq!(recording.clock.now_ms(), 10_000);
+
+        recording.feeds[0].connect().await.unwrap();
+        let start = Instant::now();
+        assert_eq!(recording.predictors.next().await, Some((10_200, predictor)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(recording.clock.now_ms() >= 10_200);
+        assert_eq!(recording.predictors.next().await, None);
+    }
+}
@@ -0,0 +1,692 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::Clock;
+use crate::live::clock_skew::ClockSkewEstimator;
+use crate::live::quarantine::{QuarantineBuffer, QuarantinedPayload, RejectedPayload};
+use crate::live::reconnect::{FeedReconnect, ReconnectBackoff, ReconnectPolicy};
+use crate::live::{
+    Asset, BtcAggregator, BtcComposite, BtcMedianTick, EwmaSmoother, NormalizedBtcTick,
+    PolymarketQuoteTick,
+};
+use crate::recorder::{RecordedTick, TickRecordSender};
+use crate::supervisor::{Supervisor, TaskId};
+
+pub type VenueFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+pub type SharedVenueBoard = Arc<Mutex<VenueBoard>>;
+pub type SharedSupervisor = Arc<Mutex<Supervisor>>;
+
+/// Reconnects kept on the board until the strategy loop drains them.
+const MAX_PENDING_RECONNECTS: usize = 256;
+
+/// One unit of market data produced by a [`VenueFeed`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum VenueTick {
+    /// A single spot trade in `Asset`.
+    Spot(Asset, NormalizedBtcTick),
+    /// The full set of YES quotes the venue currently tracks; replaces the
+    /// venue's previous quote set.
+    Quotes(Vec<PolymarketQuoteTick>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VenueHealth {
+    Connecting,
+    Healthy,
+    Reconnecting {
+        attempt: u32,
+    },
+    CircuitOpen {
+        attempt: u32,
+    },
+    /// The venue refused this host with `status`; it is not contacted again
+    /// before `until_ms`.
+    Blocked {
+        status: u16,
+        until_ms: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VenueFeedError {
+    Connect,
+    Subscribe,
+    Stream,
+    IdleTimeout,
+    SessionExpired,
+    Closed,
+    Request,
+    Throttled,
+    Parse,
+    /// Geo-block or ban: the venue answered 403, 418 or 451.
+    Blocked(u16),
+}
+
+impl VenueFeedError {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Connect => "connect",
+            Self::Subscribe => "subscribe",
+            Self::Stream => "stream",
+            Self::IdleTimeout => "idle_timeout",
+            Self::SessionExpired => "session_expired",
+            Self::Closed => "closed",
+            Self::Request => "request",
+            Self::Throttled => "throttled",
+            Self::Parse => "parse",
+            Self::Blocked(_) => "blocked",
+        }
+    }
+
+    /// `Blocked` for HTTP statuses venues use to refuse a host outright:
+    /// 403 and 451 for geo-blocks, and Binance's 418 for banned IPs.
+    pub fn blocked_by(status: u16) -> Option<Self> {
+        matches!(status, 403 | 418 | 451).then_some(Self::Blocked(status))
+    }
+}
+
+/// A market data adapter for one venue.
+///
+/// `connect` establishes the session (a socket handshake and subscribe, or a
+/// no-op for polled HTTP sources); `next_tick` then yields data until it
+/// returns an error, at which point [`run_venue_feed`] backs off according to
+/// the feed's [`ReconnectPolicy`] and calls `connect` again. `Ok(None)` means a frame arrived that carried no data.
+pub trait VenueFeed: Send {
+    fn venue(&self) -> &str;
+
+    fn connect(&mut self) -> VenueFuture<'_, Result<(), VenueFeedError>>;
+
+    fn next_tick(&mut self) -> VenueFuture<'_, Result<Option<VenueTick>, VenueFeedError>>;
+
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        ReconnectPolicy::default()
+    }
+
+    /// Endpoint the feed is currently using, for feeds that can fall back
+    /// between several.
+    fn endpoint(&self) -> Option<&str> {
+        None
+    }
+
+    /// Payloads that failed to parse since the last call.
+    fn drain_rejected(&mut self) -> Vec<RejectedPayload> {
+        Vec::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VenueEntry {
+    venue: String,
+    updates: u64,
+    health: VenueHealth,
+    endpoint: Option<String>,
+    quotes: Vec<PolymarketQuoteTick>,
+}
+
+#[derive(Debug, Clone)]
+struct AssetComposite {
+    asset: Asset,
+    aggregator: BtcAggregator,
+    smoother: EwmaSmoother,
+}
+
+/// Latest data from every registered venue, shared between feed tasks and
+/// the strategy loop.
+///
+/// Spot trades land in a per-asset copy of the configured [`BtcAggregator`],
+/// re-stamped onto the local clock by a per-venue [`ClockSkewEstimator`], and
+/// every new composite is fed through that asset's [`EwmaSmoother`]; quote
+/// snapshots are kept per venue. Venues are reported in registration order.
+#[derive(Debug, Clone)]
+pub struct VenueBoard {
+    aggregator_template: BtcAggregator,
+    smoother_template: EwmaSmoother,
+    composites: Vec<AssetComposite>,
+    clock_skew: ClockSkewEstimator,
+    venues: Vec<VenueEntry>,
+    pending_reconnects: Vec<FeedReconnect>,
+    quarantine: QuarantineBuffer,
+    recorder: Option<TickRecordSender>,
+}
+
+impl VenueBoard {
+    /// `aggregator` is cloned, empty, for each asset the first time one of its
+    /// trades arrives.
+    pub fn new(aggregator: impl Into<BtcAggregator>) -> Self {
+        Self {
+            aggregator_template: aggregator.into(),
+            smoother_template: EwmaSmoother::default(),
+            composites: Vec::new(),
+            clock_skew: ClockSkewEstimator::default(),
+            venues: Vec::new(),
+            pending_reconnects: Vec::new(),
+            quarantine: QuarantineBuffer::default(),
+            recorder: None,
+        }
+    }
+
+    pub fn shared(aggregator: impl Into<BtcAggregator>) -> SharedVenueBoard {
+        Arc::new(Mutex::new(Self::new(aggregator)))
+    }
+
+    /// Replaces the smoothing stage for every asset; smoothed values restart
+    /// from the next composite.
+    pub fn set_smoother(&mut self, smoother: EwmaSmoother) {
+        for composite in &mut self.composites {
+            composite.smoother = smoother.clone();
+        }
+        self.smoother_template = smoother;
+    }
+
+    pub fn register(&mut self, venue: &str) {
+        if self.entry(venue).is_none() {
+            self.venues.push(VenueEntry {
+                venue: venue.to_string(),
+                updates: 0,
+                health: VenueHealth::Connecting,
+                endpoint: None,
+                quotes: Vec::new(),
+            });
+        }
+    }
+
+    pub fn apply(&mut self, venue: &str, tick: VenueTick) {
+        self.register(venue);
+        match tick {
+            VenueTick::Spot(asset, trade) => {
+                let composite = self.composite_mut(asset);
+                composite.aggregator.ingest(trade);
+                if let Some(raw) = composite.aggregator.compute() {
+                    composite.smoother.update(raw.px_median, raw.ts);
+                }
+            }
+            VenueTick::Quotes(quotes) => {
+                if let Some(entry) = self.entry_mut(venue) {
+                    entry.quotes = quotes;
+                }
+            }
+        }
+        if let Some(entry) = self.entry_mut(venue) {
+            entry.updates = entry.updates.saturating_add(1);
+        }
+    }
+
+    /// Copies every tick passed to [`apply_received`](Self::apply_received)
+    /// to `recorder`, as received and before clock adjustment.
+    pub fn set_recorder(&mut self, recorder: TickRecordSender) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Like [`apply`](Self::apply), but first learns the venue's clock offset
+    /// from `received_ms` and shifts spot trade timestamps onto the local clock.
+    pub fn apply_received(&mut self, venue: &str, tick: VenueTick, received_ms: u64) {
+        self.record(&tick, received_ms);
+        let tick = match tick {
+            VenueTick::Spot(asset, mut trade) => {
+                self.clock_skew.observe(venue, trade.ts, received_ms);
+                trade.ts = self.clock_skew.adjust(venue, trade.ts);
+                VenueTick::Spot(asset, trade)
+            }
+            quotes => quotes,
+        };
+        self.apply(venue, tick);
+    }
+
+    fn record(&mut self, tick: &VenueTick, received_ms: u64) {
+        let Some(recorder) = &self.recorder else {
+            return;
+        };
+        let sent = match tick {
+            VenueTick::Spot(asset, trade) => {
+                recorder.send((received_ms, RecordedTick::Spot(*asset, trade.clone())))
+            }
+            VenueTick::Quotes(quotes) => quotes.iter().try_for_each(|quote| {
+                recorder.send((received_ms, RecordedTick::Quote(quote.clone())))
+            }),
+        };
+        // A recorder that has shut down stops receiving for good.
+        if sent.is_err() {
+            self.recorder = None;
+        }
+    }
+
+    /// Current `received - reported` estimate for `venue`, in milliseconds.
+    pub fn clock_offset_ms(&self, venue: &str) -> Option<f64> {
+        self.clock_skew.offset_ms(venue)
+    }
+
+    pub fn set_health(&mut self, venue: &str, health: VenueHealth) {
+        self.register(venue);
+        if let Some(entry) = self.entry_mut(venue) {
+            entry.health = health;
+        }
+    }
+
+    pub fn health(&self, venue: &str) -> Option<VenueHealth> {
+        self.entry(venue).map(|entry| entry.health)
+    }
+
+    pub fn set_endpoint(&mut self, venue: &str, endpoint: Option<&str>) {
+        self.register(venue);
+        if let Some(entry) = self.entry_mut(venue) {
+            entry.endpoint = endpoint.map(str::to_string);
+        }
+    }
+
+    /// Endpoint `venue` last connected to, if it reports one.
+    pub fn endpoint(&self, venue: &str) -> Option<&str> {
+        self.entry(venue)
+            .and_then(|entry| entry.endpoint.as_deref())
+    }
+
+    /// Queues a reconnect for the strategy loop, dropping the oldest entries if
+    /// nobody drains the queue.
+    pub fn record_reconnect(&mut self, reconnect: FeedReconnect) {
+        if self.pending_reconnects.len() >= MAX_PENDING_RECONNECTS {
+            self.pending_reconnects.remove(0);
+        }
+        self.pending_reconnects.push(reconnect);
+    }
+
+    pub fn drain_reconnects(&mut self) -> Vec<FeedReconnect> {
+        std::mem::take(&mut self.pending_reconnects)
+    }
+
+    /// Keeps payloads `source` could not parse for `/feed/errors`.
+    pub fn quarantine(&mut self, source: &str, rejected: Vec<RejectedPayload>, ts_ms: u64) {
+        for payload in rejected {
+            self.quarantine.record(source, payload, ts_ms);
+        }
+    }
+
+    /// `(source, failures)` pairs in the order sources first failed.
+    pub fn quarantine_counts(&self) -> Vec<(String, u64)> {
+        self.quarantine.counts().to_vec()
+    }
+
+    /// Retained unparseable payloads, oldest first.
+    pub fn quarantined(&self) -> Vec<QuarantinedPayload> {
+        self.quarantine.recent().cloned().collect()
+    }
+
+    /// `(venue, updates)` pairs in registration order.
+    pub fn update_counts(&self) -> Vec<(String, u64)> {
+        self.venues
+            .iter()
+            .map(|entry| (entry.venue.clone(), entry.updates))
+            .collect()
+    }
+
+    /// All tracked quotes, grouped by venue in registration order.
+    pub fn quotes(&self) -> Vec<PolymarketQuoteTick> {
+        self.venues
+            .iter()
+            .flat_map(|entry| entry.quotes.iter().cloned())
+            .collect()
+    }
+
+    pub fn latest_spot(&self, asset: Asset, venue: &str) -> Option<&NormalizedBtcTick> {
+        self.composite(asset)?.aggregator.latest(venue)
+    }
+
+    pub fn spot_median(&self, asset: Asset) -> Option<BtcMedianTick> {
+        self.composite(asset)?.aggregator.compute()
+    }
+
+    /// Current composite for `asset` alongside its smoothed value.
+    pub fn spot_composite(&self, asset: Asset) -> Option<BtcComposite> {
+        let composite = self.composite(asset)?;
+        let raw = composite.aggregator.compute()?;
+        Some(BtcComposite {
+            raw,
+            smoothed_px: composite.smoother.value().unwrap_or(raw.px_median),
+        })
+    }
+
+    fn composite(&self, asset: Asset) -> Option<&AssetComposite> {
+        self.composites
+            .iter()
+            .find(|composite| composite.asset == asset)
+    }
+
+    fn composite_mut(&mut self, asset: Asset) -> &mut AssetComposite {
+        let idx = match self
+            .composites
+            .iter()
+            .position(|composite| composite.asset == asset)
+        {
+            Some(idx) => idx,
+            None => {
+                self.composites.push(AssetComposite {
+                    asset,
+                    aggregator: self.aggregator_template.clone(),
+                    smoother: self.smoother_template.clone(),
+                });
+                self.composites.len() - 1
+            }
+        };
+        &mut self.composites[idx]
+    }
+
+    fn entry(&self, venue: &str) -> Option<&VenueEntry> {
+        self.venues.iter().find(|entry| entry.venue == venue)
+    }
+
+    fn entry_mut(&mut self, venue: &str) -> Option<&mut VenueEntry> {
+        self.venues.iter_mut().find(|entry| entry.venue == venue)
+    }
+}
+
+/// Drives `feed` forever, publishing its data into `board`.
+///
+/// The task is registered with `supervisor` under `task_id`; it is marked
+/// running once connected and failed when the session drops. Every failure is
+/// recorded on the board as a [`FeedReconnect`] before the feed sleeps out its
+/// backoff; a venue that blocks this host is marked [`VenueHealth::Blocked`]
+/// for the whole cooldown. Ticks are received, and blocks expire, at `clock`'s
+/// time. Callers stop the feed by aborting the spawned task.
+pub async fn run_venue_feed(
+    mut feed: Box<dyn VenueFeed>,
+    board: SharedVenueBoard,
+    supervisor: SharedSupervisor,
+    task_id: TaskId,
+    clock: Arc<dyn Clock>,
+) {
+    let venue = feed.venue().to_string();
+    let mut backoff = ReconnectBackoff::new(feed.reconnect_policy());
+    lock(&supervisor).register(task_id);
+    lock(&board).register(&venue);
+
+    loop {
+        lock(&board).set_health(&venue, VenueHealth::Connecting);
+        let reason = match feed.connect().await {
+            Ok(()) => {
+                lock(&supervisor).mark_running(task_id);
+                {
+                    let mut guard = lock(&board);
+                    guard.set_health(&venue, VenueHealth::Healthy);
+                    guard.set_endpoint(&venue, feed.endpoint());
+                }
+                backoff.record_success();
+
+                loop {
+                    let next = feed.next_tick().await;
+                    let rejected = feed.drain_rejected();
+                    if !rejected.is_empty() {
+                        lock(&board).quarantine(&venue, rejected, clock.now_ms());
+                    }
+                    match next {
+                        Ok(Some(tick)) => lock(&board).apply_received(&venue, tick, clock.now_ms()),
+                        Ok(None) => {}
+                        Err(reason) => break reason,
+                    }
+                }
+            }
+            Err(reason) => reason,
+        };
+
+        {
+            let mut guard = lock(&supervisor);
+            if guard.mark_failed(task_id).is_some() {
+                guard.mark_restarting(task_id);
+            }
+        }
+
+        let reconnect = backoff.record_failure(&venue, reason);
+        let health = if let VenueFeedError::Blocked(status) = reason {
+            VenueHealth::Blocked {
+                status,
+                until_ms: clock.now_ms().saturating_add(reconnect.delay_ms),
+            }
+        } else if reconnect.circuit_open {
+            VenueHealth::CircuitOpen {
+                attempt: reconnect.attempt,
+            }
+        } else {
+            VenueHealth::Reconnecting {
+                attempt: reconnect.attempt,
+            }
+        };
+        let delay = Duration::from_millis(reconnect.delay_ms);
+        {
+            let mut guard = lock(&board);
+            guard.set_health(&venue, health);
+            guard.record_reconnect(reconnect);
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn lock<T>(shared: &Mutex<T>) -> MutexGuard<'_, T> {
+    shared
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::{BookDepth, MedianAggregator, QuoteSource};
+
+    #[test]
+    fn board_routes_btc_ticks_and_replaces_quote_sets() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        board.register("coinbase");
+        board.register("polymarket");
+
+        board.apply(
+            "coinbase",
+            VenueTick::Spot(Asset::Btc, btc("coinbase", 64_000.0)),
+        );
+        board.apply(
+            "binance",
+            VenueTick::Spot(Asset::Btc, btc("binance", 64_020.0)),
+        );
+        board.apply(
+            "polymarket",
+            VenueTick::Quotes(vec![quote("a"), quote("b")]),
+        );
+        board.apply("polymarket", VenueTick::Quotes(vec![quote("c")]));
+
+        assert_eq!(board.spot_median(Asset::Btc).unwrap().px_median, 64_010.0);
+        assert_eq!(board.quotes().len(), 1);
+        assert_eq!(
+            board.update_counts(),
+            vec![
+                ("coinbase".to_string(), 1),
+                ("polymarket".to_string(), 2),
+                ("binance".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn board_restamps_btc_ticks_with_venue_clock_offset() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+
+        // Kraken's clock runs 8 s behind local time; without the correction
+        // its ticks would look stale next to Coinbase.
+        board.apply_received(
+            "coinbase",
+            VenueTick::Spot(Asset::Btc, btc("coinbase", 64_000.0)),
+            10_050,
+        );
+        let mut kraken = btc("kraken", 64_010.0);
+        kraken.ts = 2_000;
+        board.apply_received("kraken", VenueTick::Spot(Asset::Btc, kraken), 10_060);
+
+        assert_eq!(board.clock_offset_ms("kraken"), Some(8_060.0));
+        assert_eq!(board.latest_spot(Asset::Btc, "kraken").unwrap().ts, 10_060);
+        assert_eq!(board.spot_median(Asset::Btc).unwrap().venue_count, 2);
+    }
+
+    #[test]
+    fn board_smooths_composite_after_aggregation() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        board.set_smoother(EwmaSmoother::new(1_000).unwrap());
+        board.apply(
+            "coinbase",
+            VenueTick::Spot(Asset::Btc, btc("coinbase", 64_000.0)),
+        );
+        assert!(board.spot_composite(Asset::Btc).is_none());
+
+        board.apply(
+            "binance",
+            VenueTick::Spot(Asset::Btc, btc("binance", 64_000.0)),
+        );
+        let mut jump = btc("coinbase", 64_200.0);
+        jump.ts = 11_000;
+        board.apply("coinbase", VenueTick::Spot(Asset::Btc, jump));
+
+        let composite = board.spot_composite(Asset::Btc).unwrap();
+        assert_eq!(composite.raw.px_median, 64_100.0);
+        assert_eq!(composite.smoothed_px, 64_050.0);
+    }
+
+    #[test]
+    fn board_keeps_a_separate_composite_per_asset() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        board.apply(
+            "coinbase",
+            VenueTick::Spot(Asset::Btc, btc("coinbase", 64_000.0)),
+        );
+        board.apply(
+            "binance",
+            VenueTick::Spot(Asset::Btc, btc("binance", 64_020.0)),
+        );
+        board.apply(
+            "coinbase-eth",
+            VenueTick::Spot(Asset::Eth, btc("coinbase-eth", 3_400.0)),
+        );
+        board.apply(
+            "binance-eth",
+            VenueTick::Spot(Asset::Eth, btc("binance-eth", 3_402.0)),
+        );
+
+        assert_eq!(board.spot_median(Asset::Btc).unwrap().px_median, 64_010.0);
+        assert_eq!(board.spot_median(Asset::Eth).unwrap().px_median, 3_401.0);
+        assert!(board.spot_composite(Asset::Sol).is_none());
+        assert!(board.latest_spot(Asset::Btc, "coinbase-eth").is_none());
+    }
+
+    #[test]
+    fn board_forwards_received_ticks_to_the_recorder() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        board.set_recorder(sender);
+        let trade = NormalizedBtcTick {
+            venue: "kraken".to_string(),
+            px: 64_000.0,
+            size: 0.1,
+            ts: 9_000,
+        };
+
+        board.apply_received("kraken", VenueTick::Spot(Asset::Btc, trade.clone()), 10_000);
+
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            (10_000, RecordedTick::Spot(Asset::Btc, trade.clone()))
+        );
+        drop(receiver);
+        let later = NormalizedBtcTick { ts: 9_100, ..trade };
+        board.apply_received("kraken", VenueTick::Spot(Asset::Btc, later), 10_100);
+        assert!(board.recorder.is_none());
+    }
+
+    #[test]
+    fn board_quarantines_rejected_payloads_per_source() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        board.quarantine(
+            "okx",
+            vec![
+                RejectedPayload::new("invalid_price", r#"{"px":"-1"}"#),
+                RejectedPayload::new("invalid_json", "{"),
+            ],
+            10_000,
+        );
+
+        assert_eq!(board.quarantine_counts(), vec![("okx".to_string(), 2)]);
+        let quarantined = board.quarantined();
+        assert_eq!(quarantined.len(), 2);
+        assert_eq!(quarantined[0].source, "okx");
+        assert_eq!(quarantined[0].raw, r#"{"px":"-1"}"#);
+        assert_eq!(quarantined[1].ts_ms, 10_000);
+    }
+
+    #[test]
+    fn board_tracks_health_per_venue() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        board.register("kraken");
+        assert_eq!(board.health("kraken"), Some(VenueHealth::Connecting));
+
+        board.set_health("kraken", VenueHealth::Reconnecting { attempt: 2 });
+        assert_eq!(
+            board.health("kraken"),
+            Some(VenueHealth::Reconnecting { attempt: 2 })
+        );
+        assert_eq!(board.health("okx"), None);
+
+        assert_eq!(board.endpoint("kraken"), None);
+        board.set_endpoint("kraken", Some("wss://beta-ws.kraken.com/v2"));
+        assert_eq!(
+            board.endpoint("kraken"),
+            Some("wss://beta-ws.kraken.com/v2")
+        );
+    }
+
+    #[test]
+    fn board_queues_reconnects_until_drained() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        let mut backoff = ReconnectBackoff::new(ReconnectPolicy::default());
+        board.record_reconnect(backoff.record_failure_with("bybit", VenueFeedError::Closed, 0.0));
+        board.record_reconnect(backoff.record_failure_with("bybit", VenueFeedError::Connect, 0.0));
+
+        let drained = board.drain_reconnects();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[1].attempt, 2);
+        assert_eq!(drained[1].reason, VenueFeedError::Connect);
+        assert!(board.drain_reconnects().is_empty());
+    }
+
+    #[test]
+    fn board_caps_pending_reconnects() {
+        let mut board = VenueBoard::new(MedianAggregator::new(5_000, 500.0).unwrap());
+        let mut backoff = ReconnectBackoff::new(ReconnectPolicy::default());
+        for _ in 0..MAX_PENDING_RECONNECTS + 5 {
+            board.record_reconnect(backoff.record_failure("okx", VenueFeedError::Stream));
+        }
+
+        let drained = board.drain_reconnects();
+        assert_eq!(drained.len(), MAX_PENDING_RECONNECTS);
+        assert_eq!(drained[0].attempt, 6);
+    }
+
+    fn btc(venue: &str, px: f64) -> NormalizedBtcTick {
+        NormalizedBtcTick {
+            venue: venue.to_string(),
+            px,
+            size: 1.0,
+            ts: 10_000,
+        }
+    }
+
+    fn quote(slug: &str) -> PolymarketQuoteTick {
+        PolymarketQuoteTick {
+            market_slug: slug.to_string(),
+            source: QuoteSource::Polymarket,
+            asset: Asset::Btc,
+            best_yes_bid: 0.48,
+            best_yes_ask: 0.52,
+            mid_yes: 0.5,
+            ts: 1,
+            depth: BookDepth::default(),
+            liquidity_score: None,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::live::{BtcMedianTick, NormalizedBtcTick};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VwapAggregatorConfigError {
+    /// `window_ms` must be greater than zero.
+    InvalidWindowMs,
+}
+
+/// Size-weighted composite over every trade in a trailing time window.
+///
+/// Unlike [`MedianAggregator`](crate::live::MedianAggregator), which gives each
+/// venue's last print one vote, a venue contributes in proportion to the volume
+/// it actually traded, so a single small print on a thin venue barely moves the
+/// composite.
+#[derive(Debug, Clone)]
+pub struct VwapAggregator {
+    window_ms: u64,
+    window: VecDeque<NormalizedBtcTick>,
+    latest_by_venue: HashMap<String, NormalizedBtcTick>,
+}
+
+impl VwapAggregator {
+    /// `window_ms` is measured back from the freshest ingested trade.
+    pub fn new(window_ms: u64) -> Result<Self, VwapAggregatorConfigError> {
+        if window_ms == 0 {
+            return Err(VwapAggregatorConfigError::InvalidWindowMs);
+        }
+
+        Ok(Self {
+            window_ms,
+            window: VecDeque::new(),
+            latest_by_venue: HashMap::new(),
+        })
+    }
+
+    /// Adds a trade to the window and evicts trades that fell out of it.
+    ///
+    /// Trades with a non-positive or non-finite price or size are ignored.
+    pub fn ingest(&mut self, tick: NormalizedBtcTick) {
+        if !tick.px.is_finite() || tick.px <= 0.0 || !tick.size.is_finite() || tick.size <= 0.0 {
+            return;
+        }
+
+        match self.latest_by_venue.get(&tick.venue) {
+            Some(existing) if existing.ts > tick.ts => {}
+            _ => {
+                self.latest_by_venue
+                    .insert(tick.venue.clone(), tick.clone());
+            }
+        }
+
+        let position = self
+            .window
+            .iter()
+            .rposition(|existing| existing.ts <= tick.ts)
+            .map_or(0, |idx| idx + 1);
+        self.window.insert(position, tick);
+
+        let latest_ts = self.window.back().map_or(0, |tick| tick.ts);
+        while self
+            .window
+            .front()
+            .is_some_and(|oldest| latest_ts.saturating_sub(oldest.ts) > self.window_ms)
+        {
+            self.window.pop_front();
+        }
+    }
+
+    /// Returns the newest trade retained for `venue`, if any.
+    pub fn latest(&self, venue: &str) -> Option<&NormalizedBtcTick> {
+        self.latest_by_venue.get(venue)
+    }
+
+    /// Volume-weighted price of the trades in the window.
+    ///
+    /// The snapshot reuses [`BtcMedianTick`] so consumers need not care which
+    /// aggregator is configured: `px_median` carries the VWAP and `px_spread`
+    /// the range of traded prices. Returns `None` unless at least two venues
+    /// traded inside the window.
+    pub fn compute(&self) -> Option<BtcMedianTick> {
+        let venues: HashSet<&str> = self.window.iter().map(|tick| tick.venue.as_str()).collect();
+        if venues.len() < 2 {
+            return None;
+        }
+
+        let notional: f64 = self.window.iter().map(|tick| tick.px * tick.size).sum();
+        let volume: f64 = self.window.iter().map(|tick| tick.size).sum();
+        if volume <= 0.0 {
+            return None;
+        }
+
+        let min_px = self
+            .window
+            .iter()
+            .map(|tick| tick.px)
+            .fold(f64::INFINITY, f64::min);
+        let max_px = self
+            .window
+            .iter()
+            .map(|tick| tick.px)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let ts = self.window.back()?.ts;
+
+        Some(BtcMedianTick::new(
+            notional / volume,
+            max_px - min_px,
+            venues.len() as u32,
+            ts,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VwapAggregator, VwapAggregatorConfigError};
+    use crate::live::NormalizedBtcTick;
+
+    #[test]
+    fn weights_prices_by_traded_size() {
+        let mut agg = VwapAggregator::new(5_000).unwrap();
+        agg.ingest(tick("binance", 60_000.0, 9.0, 10_000));
+        agg.ingest(tick("coinbase", 60_100.0, 1.0, 10_100));
+        agg.ingest(tick("thin-venue", 61_000.0, 0.001, 10_200));
+
+        let out = agg.compute().unwrap();
+
+        assert_eq!(out.venue_count, 3);
+        assert!((out.px_median - 60_010.0).abs() < 1.0);
+        assert_eq!(out.px_spread, 1_000.0);
+        assert_eq!(out.ts, 10_200);
+    }
+
+    #[test]
+    fn evicts_trades_outside_the_window() {
+        let mut agg = VwapAggregator::new(1_000).unwrap();
+        agg.ingest(tick("binance", 59_000.0, 100.0, 10_000));
+        agg.ingest(tick("binance", 60_000.0, 1.0, 11_500));
+        agg.ingest(tick("kraken", 60_200.0, 1.0, 11_600));
+
+        let out = agg.compute().unwrap();
+
+        assert_eq!(out.px_median, 60_100.0);
+        assert_eq!(agg.latest("binance").unwrap().ts, 11_500);
+    }
+
+    #[test]
+    fn requires_two_venues_and_valid_sizes() {
+        let mut agg = VwapAggregator::new(5_000).unwrap();
+        agg.ingest(tick("binance", 60_000.0, 1.0, 10_000));
+        agg.ingest(tick("binance", 60_010.0, 1.0, 10_100));
+        agg.ingest(tick("coinbase", 60_020.0, 0.0, 10_200));
+        agg.ingest(tick("kraken", f64::NAN, 1.0, 10_200));
+
+        assert!(agg.compute().is_none());
+        assert_eq!(
+            VwapAggregator::new(0).unwrap_err(),
+            VwapAggregatorConfigError::InvalidWindowMs
+        );
+    }
+
+    fn tick(venue: &str, px: f64, size: f64, ts: u64) -> NormalizedBtcTick {
+        NormalizedBtcTick {
+            venue: venue.to_string(),
+            px,
+            size,
+            ts,
+        }
+    }
+}
@@ -1,7 +1,15 @@
-use crate::events::{RuntimeEvent, RuntimeStage};
-use crate::live::{detect_lag, BtcMedianTick, PolymarketQuoteTick};
-use crate::paper_exec::{paper_fill_buy, paper_fill_sell};
-use strategy::{live_signal, RiskState, Signal};
+use smallvec::smallvec;
+
+use crate::events::{DecisionEvents, RuntimeEvent, RuntimeStage};
+use crate::live::lag_detector::lag_divergence;
+use crate::live::{
+    BtcMedianTick, LagGateDecision, LagTriggerGate, PolymarketQuoteTick, WindowedLagDetector,
+};
+use crate::paper_exec::{paper_fill_buy, paper_fill_sell, PaperExecError, PaperFill};
+use strategy::{
+    live_signal, regime_multiplier, ExpiryGuard, OrderIntent, Regime, RiskState, Signal,
+    SizingMode, Strategy, StrategyInputs,
+};
 
 #[derive(Debug, Clone)]
 pub struct JoinedLiveInputs {
@@ -9,6 +17,28 @@ pub struct JoinedLiveInputs {
     pub quote_tick: PolymarketQuoteTick,
 }
 
+impl JoinedLiveInputs {
+    /// Fills `buffer` with the next evaluation's inputs, copying `quote_tick`
+    /// into the buffers the previous evaluation left behind.
+    pub fn refill<'b>(
+        buffer: &'b mut Option<Self>,
+        btc_tick: BtcMedianTick,
+        quote_tick: &PolymarketQuoteTick,
+    ) -> &'b Self {
+        match buffer {
+            Some(joined) => {
+                joined.btc_tick = btc_tick;
+                joined.quote_tick.clone_from(quote_tick);
+                joined
+            }
+            None => buffer.insert(Self {
+                btc_tick,
+                quote_tick: quote_tick.clone(),
+            }),
+        }
+    }
+}
+
 const BTC_SPREAD_TO_PRICE_COEFF: f64 = 0.001;
 const SIGNAL_THRESHOLD: f64 = 0.01;
 const ORDER_QTY: f64 = 1.0;
@@ -16,9 +46,8 @@ const ORDER_SLIPPAGE_BPS: f64 = 0.0;
 const ORDER_FEE_BPS: f64 = 0.0;
 const RISK_STARTING_EQUITY: f64 = 10.0;
 const RISK_DAILY_LOSS_CAP_PCT: f64 = 0.06;
-const SELL_BASE_MARKET_EXPOSURE: f64 = 1.0;
 
-pub fn run_paper_live_once(tick: u64, joined: &JoinedLiveInputs) -> Vec<RuntimeEvent> {
+pub fn run_paper_live_once(tick: u64, joined: &JoinedLiveInputs) -> DecisionEvents {
     let prediction_price =
         derive_prediction_price(joined.quote_tick.mid_yes, joined.btc_tick.px_spread);
     let live_signal = match live_signal(
@@ -27,49 +56,36 @@ pub fn run_paper_live_once(tick: u64, joined: &JoinedLiveInputs) -> Vec<RuntimeE
         SIGNAL_THRESHOLD,
     ) {
         Ok(signal) => signal,
-        Err(_) => return vec![],
+        Err(_) => return DecisionEvents::new(),
     };
 
     if live_signal.action == Signal::Hold {
-        return vec![];
+        return DecisionEvents::new();
     }
 
-    let mut events = vec![RuntimeEvent::new(tick, RuntimeStage::PaperIntentCreated)];
+    let mut events: DecisionEvents =
+        smallvec![RuntimeEvent::new(tick, RuntimeStage::PaperIntentCreated)];
     let signed_exposure_delta =
         signed_exposure_delta(live_signal.action, ORDER_QTY, joined.quote_tick.mid_yes);
-    let current_market_exposure = current_market_exposure(live_signal.action);
 
     let risk_state = match RiskState::new(RISK_STARTING_EQUITY, RISK_DAILY_LOSS_CAP_PCT) {
         Ok(state) => state,
         Err(_) => return events,
     };
 
+    // This path keeps no book, so every decision starts flat.
     if risk_state
-        .check_market_exposure(
-            &joined.quote_tick.market_slug,
-            current_market_exposure,
-            signed_exposure_delta,
-        )
+        .check_net_market_exposure(&joined.quote_tick.market_slug, 0.0, signed_exposure_delta)
         .is_err()
     {
         return events;
     }
 
-    let fill_result = match live_signal.action {
-        Signal::Buy => paper_fill_buy(
-            joined.quote_tick.best_yes_ask,
-            ORDER_QTY,
-            ORDER_SLIPPAGE_BPS,
-            ORDER_FEE_BPS,
-        ),
-        Signal::Sell => paper_fill_sell(
-            joined.quote_tick.best_yes_bid,
-            ORDER_QTY,
-            ORDER_SLIPPAGE_BPS,
-            ORDER_FEE_BPS,
-        ),
-        Signal::Hold => return vec![],
-    };
+    let fill_result =
+        match paper_fill_against_depth(&joined.quote_tick, live_signal.action, ORDER_QTY) {
+            Some(result) => result,
+            None => return events,
+        };
 
     if fill_result.is_ok() {
         events.push(RuntimeEvent::new(tick, RuntimeStage::PaperFillRecorded));
@@ -78,6 +94,36 @@ pub fn run_paper_live_once(tick: u64, joined: &JoinedLiveInputs) -> Vec<RuntimeE
     events
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LagRunParams {
+    pub fair_yes_px: f64,
+    pub lag_threshold_pct: f64,
+    pub per_trade_risk_fraction: f64,
+    pub starting_equity: f64,
+    pub daily_loss_cap_fraction: f64,
+    /// Quantity strategies are asked to trade, normally the per-trade risk
+    /// budget from [`strategy::risk_budget_qty`].
+    pub base_qty: f64,
+    /// Replaces a strategy intent's quantity unless set to fixed sizing.
+    pub sizing: SizingMode,
+    /// Volatility regime; strategy intents are scaled by its multiplier.
+    pub regime: Regime,
+    /// Seconds until the market resolves, when known. Entries are blocked and
+    /// thresholds raised near expiry by [`ExpiryGuard`].
+    pub seconds_to_expiry: Option<f64>,
+    /// Net YES shares already held in the market, negative when short.
+    pub market_position_qty: f64,
+    /// Largest net YES position allowed in one market, long or short.
+    pub max_position_qty: f64,
+    /// Further multiplier on strategy intents, such as the VaR soft gate's;
+    /// 1 leaves them unchanged.
+    pub size_scale: f64,
+    /// Contracts that must rest at the touch before a strategy intent is
+    /// emitted. Intents are also cut to the depth on their side of the book.
+    /// Quotes without depth are not checked.
+    pub min_touch_qty: f64,
+}
+
 pub fn run_paper_live_once_with_lag(
     tick: u64,
     joined: &JoinedLiveInputs,
@@ -86,40 +132,211 @@ pub fn run_paper_live_once_with_lag(
     per_trade_risk_fraction: f64,
     starting_equity: f64,
     daily_loss_cap_fraction: f64,
-) -> Vec<RuntimeEvent> {
-    let lag_signal = match detect_lag(
-        &joined.quote_tick.market_slug,
-        joined.quote_tick.mid_yes,
+) -> DecisionEvents {
+    let params = LagRunParams {
         fair_yes_px,
         lag_threshold_pct,
-    ) {
-        Ok(signal) => signal,
-        Err(_) => return vec![],
+        per_trade_risk_fraction,
+        starting_equity,
+        daily_loss_cap_fraction,
+        base_qty: ORDER_QTY,
+        sizing: SizingMode::Fixed,
+        regime: Regime::Normal,
+        seconds_to_expiry: None,
+        market_position_qty: 0.0,
+        max_position_qty: ORDER_QTY,
+        size_scale: 1.0,
+        min_touch_qty: 0.0,
     };
 
-    if !lag_signal.triggered {
-        return vec![];
+    run_lag_decision(tick, joined, &params, None)
+}
+
+/// Same as [`run_paper_live_once_with_lag`], but the lag is read through
+/// `detector`, which triggers only on divergence sustained over its window,
+/// and a sustained signal must also pass the per-market debounce/cooldown
+/// `gate` before an intent is created. The detector's own threshold applies
+/// in place of `params.lag_threshold_pct`. `now_ms` is the wall-clock time
+/// used for cooldown bookkeeping.
+pub fn run_paper_live_once_gated(
+    tick: u64,
+    joined: &JoinedLiveInputs,
+    params: &LagRunParams,
+    detector: &mut WindowedLagDetector,
+    gate: &mut LagTriggerGate,
+    now_ms: u64,
+) -> DecisionEvents {
+    run_lag_decision(tick, joined, params, Some((detector, gate, now_ms)))
+}
+
+fn run_lag_decision(
+    tick: u64,
+    joined: &JoinedLiveInputs,
+    params: &LagRunParams,
+    mut gated: Option<(&mut WindowedLagDetector, &mut LagTriggerGate, u64)>,
+) -> DecisionEvents {
+    let market_id = joined.quote_tick.market_slug.as_str();
+    let lag = match &mut gated {
+        Some((detector, _, _)) => detector
+            .observe(market_id, joined.quote_tick.mid_yes, params.fair_yes_px)
+            .map(|windowed| (windowed.signal.divergence_pct, windowed.sustained)),
+        None => lag_divergence(
+            market_id,
+            joined.quote_tick.mid_yes,
+            params.fair_yes_px,
+            params.lag_threshold_pct,
+        ),
+    };
+    let Ok((divergence_pct, triggered)) = lag else {
+        return DecisionEvents::new();
+    };
+
+    if let Some((_, gate, now_ms)) = &mut gated {
+        if gate.check_market(market_id, triggered, divergence_pct, *now_ms)
+            != LagGateDecision::Admit
+        {
+            return DecisionEvents::new();
+        }
+    }
+
+    if !triggered {
+        return DecisionEvents::new();
     }
 
-    let signal_action = if lag_signal.divergence_pct > 0.0 {
+    let signal_action = if divergence_pct > 0.0 {
         Signal::Buy
     } else {
         Signal::Sell
     };
 
-    let mut events = vec![RuntimeEvent::new(tick, RuntimeStage::PaperIntentCreated)];
+    let events = risk_check_and_fill(tick, joined, params, signal_action, ORDER_QTY);
+    // Only a signal that filled starts the cooldown.
+    if let Some((_, gate, now_ms)) = gated {
+        if has_fill(&events) {
+            gate.commit(market_id, now_ms);
+        }
+    }
+    events
+}
+
+/// Whether `events` record a paper fill.
+pub fn has_fill(events: &DecisionEvents) -> bool {
+    events
+        .iter()
+        .any(|event| event.stage == RuntimeStage::PaperFillRecorded)
+}
+
+/// Runs `strategy` on the joined inputs. Its first intent passes through the
+/// same debounce/cooldown `gate` and risk checks as a lag signal; the intent is
+/// returned only when the gate admits it. The gate is only checked: the
+/// caller runs its own checks on the intent and calls
+/// [`LagTriggerGate::commit`] once it places the order.
+pub fn run_strategy_once_gated(
+    tick: u64,
+    joined: &JoinedLiveInputs,
+    strategy: &mut dyn Strategy,
+    params: &LagRunParams,
+    gate: &mut LagTriggerGate,
+    now_ms: u64,
+) -> (Option<OrderIntent>, DecisionEvents) {
+    let threshold_pct = match params.seconds_to_expiry {
+        Some(seconds) => {
+            ExpiryGuard::default().adjusted_threshold(params.lag_threshold_pct, seconds)
+        }
+        None => Some(params.lag_threshold_pct),
+    };
+    let inputs = StrategyInputs {
+        market_id: &joined.quote_tick.market_slug,
+        underlying: joined.quote_tick.asset.as_str(),
+        spot_px: joined.btc_tick.px_median,
+        ts_ms: now_ms,
+        mid_yes: joined.quote_tick.mid_yes,
+        best_yes_bid: joined.quote_tick.best_yes_bid,
+        best_yes_ask: joined.quote_tick.best_yes_ask,
+        fair_yes_px: params.fair_yes_px,
+        spread_signal: joined.btc_tick.px_spread,
+        threshold_pct: threshold_pct.unwrap_or(params.lag_threshold_pct),
+        base_qty: params.base_qty,
+        position_qty: params.market_position_qty,
+    };
+    // Strategies still see the tick near expiry so their history stays
+    // current, but nothing they ask for is opened.
+    let intent = strategy
+        .on_tick(&inputs)
+        .into_iter()
+        .find(|intent| threshold_pct.is_some() && intent.side != Signal::Hold)
+        .and_then(|mut intent| {
+            intent.qty = match params.sizing {
+                SizingMode::Fixed => intent.qty,
+                SizingMode::Kelly(sizer) => sizer
+                    .size(
+                        intent.side,
+                        params.fair_yes_px,
+                        inputs.mid_yes,
+                        params.starting_equity,
+                    )
+                    .ok()?,
+                SizingMode::Divergence(sizer) => sizer
+                    .size(
+                        intent.side,
+                        intent.qty,
+                        params.fair_yes_px,
+                        inputs.mid_yes,
+                        inputs.threshold_pct,
+                        params.starting_equity,
+                    )
+                    .ok()?,
+            };
+            intent.qty *= regime_multiplier(params.regime) * params.size_scale;
+            intent.qty = fit_to_liquidity(
+                &joined.quote_tick,
+                intent.side,
+                intent.qty,
+                params.min_touch_qty,
+            )?;
+            (intent.qty > 0.0).then_some(intent)
+        });
+
+    // The gate only reads whether the tick triggered and in which direction.
+    let direction = match intent.as_ref().map(|intent| intent.side) {
+        Some(Signal::Buy) => 1.0,
+        Some(Signal::Sell) => -1.0,
+        _ => 0.0,
+    };
+    if gate.check_market(inputs.market_id, intent.is_some(), direction, now_ms)
+        != LagGateDecision::Admit
+    {
+        return (None, DecisionEvents::new());
+    }
+    let Some(intent) = intent else {
+        return (None, DecisionEvents::new());
+    };
+
+    let events = risk_check_and_fill(tick, joined, params, intent.side, intent.qty);
+    (Some(intent), events)
+}
+
+fn risk_check_and_fill(
+    tick: u64,
+    joined: &JoinedLiveInputs,
+    params: &LagRunParams,
+    signal_action: Signal,
+    qty: f64,
+) -> DecisionEvents {
+    let mut events: DecisionEvents =
+        smallvec![RuntimeEvent::new(tick, RuntimeStage::PaperIntentCreated)];
 
     let signed_exposure_delta =
-        signed_exposure_delta(signal_action, ORDER_QTY, joined.quote_tick.mid_yes);
-    let current_market_exposure = current_market_exposure(signal_action);
+        signed_exposure_delta(signal_action, qty, joined.quote_tick.mid_yes);
+    let current_market_exposure = params.market_position_qty * joined.quote_tick.mid_yes;
 
-    let risk_state = match RiskState::new(starting_equity, daily_loss_cap_fraction) {
+    let risk_state = match RiskState::new(params.starting_equity, params.daily_loss_cap_fraction) {
         Ok(state) => state,
         Err(_) => return events,
     };
 
     if risk_state
-        .check_market_exposure(
+        .check_net_market_exposure(
             &joined.quote_tick.market_slug,
             current_market_exposure,
             signed_exposure_delta,
@@ -129,28 +346,33 @@ pub fn run_paper_live_once_with_lag(
         return events;
     }
 
-    let trade_risk_amount = joined.quote_tick.mid_yes * ORDER_QTY;
+    let signed_qty = match signal_action {
+        Signal::Buy => qty,
+        Signal::Sell => -qty,
+        Signal::Hold => 0.0,
+    };
     if risk_state
-        .check_per_trade_risk(per_trade_risk_fraction, trade_risk_amount)
+        .check_market_position(
+            params.market_position_qty,
+            signed_qty,
+            params.max_position_qty,
+        )
         .is_err()
     {
         return events;
     }
 
-    let fill_result = match signal_action {
-        Signal::Buy => paper_fill_buy(
-            joined.quote_tick.best_yes_ask,
-            ORDER_QTY,
-            ORDER_SLIPPAGE_BPS,
-            ORDER_FEE_BPS,
-        ),
-        Signal::Sell => paper_fill_sell(
-            joined.quote_tick.best_yes_bid,
-            ORDER_QTY,
-            ORDER_SLIPPAGE_BPS,
-            ORDER_FEE_BPS,
-        ),
-        Signal::Hold => return vec![],
+    let trade_risk_amount = joined.quote_tick.mid_yes * qty;
+    if risk_state
+        .check_per_trade_risk(params.per_trade_risk_fraction, trade_risk_amount)
+        .is_err()
+    {
+        return events;
+    }
+
+    let fill_result = match paper_fill_against_depth(&joined.quote_tick, signal_action, qty) {
+        Some(result) => result,
+        None => return events,
     };
 
     if fill_result.is_ok() {
@@ -160,6 +382,49 @@ pub fn run_paper_live_once_with_lag(
     events
 }
 
+/// Cuts `qty` to the depth on the side `action` takes, or `None` when less
+/// than `min_touch_qty` rests at the touch. Quotes without depth pass as is.
+fn fit_to_liquidity(
+    quote: &PolymarketQuoteTick,
+    action: Signal,
+    qty: f64,
+    min_touch_qty: f64,
+) -> Option<f64> {
+    let buy = match action {
+        Signal::Buy => true,
+        Signal::Sell => false,
+        Signal::Hold => return None,
+    };
+    let Some(touch_qty) = quote.touch_qty(buy) else {
+        return Some(qty);
+    };
+    if touch_qty < min_touch_qty {
+        return None;
+    }
+
+    let side_limit_px = if buy { 1.0 } else { 0.0 };
+    let available = quote.depth_within(buy, side_limit_px).unwrap_or(0.0);
+    Some(qty.min(available))
+}
+
+/// Fills `qty` at the book's impact price; `None` when the order is a hold or
+/// the ingested depth cannot absorb it.
+fn paper_fill_against_depth(
+    quote: &PolymarketQuoteTick,
+    action: Signal,
+    qty: f64,
+) -> Option<Result<PaperFill, PaperExecError>> {
+    match action {
+        Signal::Buy => quote
+            .impact_price(qty)
+            .map(|px| paper_fill_buy(px, qty, ORDER_SLIPPAGE_BPS, ORDER_FEE_BPS)),
+        Signal::Sell => quote
+            .impact_price(-qty)
+            .map(|px| paper_fill_sell(px, qty, ORDER_SLIPPAGE_BPS, ORDER_FEE_BPS)),
+        Signal::Hold => None,
+    }
+}
+
 fn derive_prediction_price(mid_yes: f64, btc_spread_signal: f64) -> f64 {
     (mid_yes + (btc_spread_signal * BTC_SPREAD_TO_PRICE_COEFF)).clamp(0.0, 1.0)
 }
@@ -174,19 +439,13 @@ fn signed_exposure_delta(action: Signal, qty: f64, reference_yes_price: f64) ->
     }
 }
 
-fn current_market_exposure(action: Signal) -> f64 {
-    match action {
-        Signal::Buy => 0.0,
-        Signal::Sell => SELL_BASE_MARKET_EXPOSURE,
-        Signal::Hold => 0.0,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::{run_paper_live_once, JoinedLiveInputs};
     use crate::events::RuntimeStage;
-    use crate::live::{BtcMedianTick, PolymarketQuoteTick};
+    use crate::live::{
+        Asset, BookDepth, BookLevel, BtcMedianTick, PolymarketQuoteTick, QuoteSource,
+    };
 
     #[test]
     fn run_paper_live_once_emits_intent_then_fill_for_buy_signal() {
@@ -197,6 +456,20 @@ mod tests {
         assert_eq!(out[1].stage, RuntimeStage::PaperFillRecorded);
     }
 
+    #[test]
+    fn run_paper_live_once_skips_fill_when_depth_is_too_thin() {
+        let mut joined = joined_inputs_for_buy_signal(42);
+        joined.quote_tick.depth = BookDepth {
+            bids: vec![BookLevel { px: 0.48, qty: 5.0 }],
+            asks: vec![BookLevel { px: 0.52, qty: 0.4 }],
+        };
+
+        let out = run_paper_live_once(42, &joined);
+
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].stage, RuntimeStage::PaperIntentCreated);
+    }
+
     #[test]
     fn run_paper_live_once_emits_no_events_for_hold_signal() {
         let out = run_paper_live_once(42, &joined_inputs_for_hold_signal(42));
@@ -219,6 +492,21 @@ mod tests {
         assert!(out.is_empty());
     }
 
+    #[test]
+    fn refill_reuses_the_buffered_quote() {
+        let mut buffer = None;
+        let first = joined_inputs_for_buy_signal(1);
+        let mut second = joined_inputs_for_hold_signal(2).quote_tick;
+        second.market_slug = "eth-up-down".to_string();
+        second.depth.asks.clear();
+
+        JoinedLiveInputs::refill(&mut buffer, first.btc_tick, &first.quote_tick);
+        let joined = JoinedLiveInputs::refill(&mut buffer, first.btc_tick, &second);
+
+        assert_eq!(joined.quote_tick, second);
+        assert_eq!(joined.btc_tick, first.btc_tick);
+    }
+
     #[test]
     fn emits_intent_when_lag_exceeds_threshold() {
         let out = super::run_paper_live_once_with_lag(
@@ -251,15 +539,402 @@ mod tests {
         assert!(out.is_empty());
     }
 
+    #[test]
+    fn gated_run_waits_for_persistence_then_cools_down() {
+        let params = super::LagRunParams {
+            fair_yes_px: 0.502,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Fixed,
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut detector = crate::live::WindowedLagDetector::new(0.3, 1, 1).unwrap();
+        let mut gate = crate::live::LagTriggerGate::new(10_000, 2).unwrap();
+        let joined = joined_inputs_for_hold_signal(42);
+        let mut run = |tick, now_ms| {
+            super::run_paper_live_once_gated(
+                tick,
+                &joined,
+                &params,
+                &mut detector,
+                &mut gate,
+                now_ms,
+            )
+        };
+
+        let first = run(42, 0);
+        let second = run(43, 1_500);
+        let third = run(44, 3_000);
+
+        assert!(first.is_empty());
+        assert!(second
+            .iter()
+            .any(|event| event.stage == RuntimeStage::PaperIntentCreated));
+        assert!(third.is_empty());
+    }
+
+    #[test]
+    fn gated_run_ignores_single_tick_spikes() {
+        let mut params = super::LagRunParams {
+            fair_yes_px: 0.50,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Fixed,
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        // Three of the last five samples must lag in the same direction.
+        let mut detector = crate::live::WindowedLagDetector::new(0.3, 5, 3).unwrap();
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let joined = joined_inputs_for_hold_signal(42);
+        let mut filled = Vec::new();
+        // A one-tick spike, a quiet tick, then a sustained lag.
+        for (tick, fair_yes_px) in [(1, 0.502), (2, 0.50), (3, 0.502), (4, 0.502)] {
+            params.fair_yes_px = fair_yes_px;
+            let events = super::run_paper_live_once_gated(
+                tick,
+                &joined,
+                &params,
+                &mut detector,
+                &mut gate,
+                tick,
+            );
+            filled.push(super::has_fill(&events));
+        }
+
+        assert_eq!(filled, [false, false, false, true]);
+    }
+
+    #[test]
+    fn risk_rejected_signals_do_not_start_the_cooldown() {
+        let mut params = super::LagRunParams {
+            fair_yes_px: 0.502,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Fixed,
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 100.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut detector = crate::live::WindowedLagDetector::new(0.3, 1, 1).unwrap();
+        let mut gate = crate::live::LagTriggerGate::new(10_000, 1).unwrap();
+        let joined = joined_inputs_for_hold_signal(42);
+        let mut run = |tick, params: &super::LagRunParams, now_ms| {
+            super::run_paper_live_once_gated(
+                tick,
+                &joined,
+                params,
+                &mut detector,
+                &mut gate,
+                now_ms,
+            )
+        };
+
+        // Admitted by the gate, then refused at the position cap.
+        let rejected = run(42, &params, 0);
+        params.market_position_qty = 0.0;
+        let filled = run(43, &params, 1_000);
+        let cooling = run(44, &params, 2_000);
+
+        assert!(!super::has_fill(&rejected));
+        assert!(super::has_fill(&filled));
+        assert!(cooling.is_empty());
+    }
+
+    #[test]
+    fn strategy_run_fills_the_selected_strategy_intent() {
+        let params = super::LagRunParams {
+            fair_yes_px: 0.10,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Fixed,
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let joined = joined_inputs_for_buy_signal(42);
+        let mut strategy = strategy::SpreadMomentumStrategy;
+
+        let (intent, events) =
+            super::run_strategy_once_gated(42, &joined, &mut strategy, &params, &mut gate, 0);
+
+        assert_eq!(
+            intent.map(|intent| intent.side),
+            Some(strategy::Signal::Buy)
+        );
+        assert!(events
+            .iter()
+            .any(|event| event.stage == RuntimeStage::PaperFillRecorded));
+    }
+
+    #[test]
+    fn kelly_sizing_replaces_the_strategy_quantity() {
+        let params = super::LagRunParams {
+            fair_yes_px: 0.502,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Kelly(strategy::KellySizer::new(0.5, 0.005).unwrap()),
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let mut strategy = strategy::LagDivergenceStrategy::default();
+
+        let (intent, events) = super::run_strategy_once_gated(
+            42,
+            &joined_inputs_for_hold_signal(42),
+            &mut strategy,
+            &params,
+            &mut gate,
+            0,
+        );
+
+        // Half of (0.502 - 0.5) / 0.5 of equity, bought at 0.5 a share.
+        let qty = intent.expect("edge is above threshold").qty;
+        assert!((qty - 40.0).abs() < 1e-6);
+        assert!(events
+            .iter()
+            .any(|event| event.stage == RuntimeStage::PaperFillRecorded));
+    }
+
+    #[test]
+    fn divergence_sizing_scales_the_strategy_quantity_by_the_lag() {
+        let params = super::LagRunParams {
+            fair_yes_px: 0.51,
+            lag_threshold_pct: 0.5,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Divergence(
+                strategy::DivergenceSizer::new(0.005).unwrap(),
+            ),
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let mut strategy = strategy::LagDivergenceStrategy::default();
+
+        let (intent, _) = super::run_strategy_once_gated(
+            42,
+            &joined_inputs_for_hold_signal(42),
+            &mut strategy,
+            &params,
+            &mut gate,
+            0,
+        );
+
+        // A 2% lag over a 0.5% threshold is four thresholds.
+        let qty = intent.expect("lag is above threshold").qty;
+        assert!((qty - 4.0).abs() < 1e-9, "qty was {qty}");
+    }
+
+    #[test]
+    fn volatile_regime_halves_the_strategy_quantity() {
+        let params = super::LagRunParams {
+            fair_yes_px: 0.502,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Fixed,
+            regime: strategy::Regime::Volatile,
+            seconds_to_expiry: None,
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let mut strategy = strategy::LagDivergenceStrategy::default();
+
+        let (intent, _) = super::run_strategy_once_gated(
+            42,
+            &joined_inputs_for_hold_signal(42),
+            &mut strategy,
+            &params,
+            &mut gate,
+            0,
+        );
+
+        assert_eq!(intent.map(|intent| intent.qty), Some(0.5));
+    }
+
+    #[test]
+    fn strategy_run_stops_filling_at_the_max_market_position() {
+        let mut params = super::LagRunParams {
+            fair_yes_px: 0.502,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Fixed,
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 2.0,
+            max_position_qty: 3.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let mut strategy = strategy::LagDivergenceStrategy::default();
+        let joined = joined_inputs_for_hold_signal(42);
+        let filled = |events: &[crate::events::RuntimeEvent]| {
+            events
+                .iter()
+                .any(|event| event.stage == RuntimeStage::PaperFillRecorded)
+        };
+
+        let (_, below) =
+            super::run_strategy_once_gated(42, &joined, &mut strategy, &params, &mut gate, 0);
+        params.market_position_qty = 3.0;
+        let (_, at_cap) =
+            super::run_strategy_once_gated(43, &joined, &mut strategy, &params, &mut gate, 1);
+        // Buying back a short nets against it instead of adding exposure.
+        params.market_position_qty = -3.5;
+        let (_, covering) =
+            super::run_strategy_once_gated(44, &joined, &mut strategy, &params, &mut gate, 2);
+
+        assert!(filled(&below));
+        assert!(!filled(&at_cap));
+        assert!(filled(&covering));
+    }
+
+    #[test]
+    fn strategy_run_opens_nothing_in_the_final_seconds() {
+        let mut params = super::LagRunParams {
+            fair_yes_px: 0.502,
+            lag_threshold_pct: 0.3,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Fixed,
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: Some(20.0),
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 0.0,
+        };
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let mut strategy = strategy::LagDivergenceStrategy::default();
+        let joined = joined_inputs_for_hold_signal(42);
+
+        let (late, _) =
+            super::run_strategy_once_gated(42, &joined, &mut strategy, &params, &mut gate, 0);
+        // 0.4% clears 0.3% with the full window left but not the doubled
+        // threshold with a quarter of it.
+        params.seconds_to_expiry = Some(225.0);
+        let (quarter, _) =
+            super::run_strategy_once_gated(43, &joined, &mut strategy, &params, &mut gate, 1);
+        params.seconds_to_expiry = Some(900.0);
+        let (full, _) =
+            super::run_strategy_once_gated(44, &joined, &mut strategy, &params, &mut gate, 2);
+
+        assert!(late.is_none());
+        assert!(quarter.is_none());
+        assert!(full.is_some());
+    }
+
+    #[test]
+    fn strategy_intents_need_touch_liquidity_and_shrink_to_the_book() {
+        let mut params = super::LagRunParams {
+            fair_yes_px: 0.51,
+            lag_threshold_pct: 0.5,
+            per_trade_risk_fraction: 0.005,
+            starting_equity: 10_000.0,
+            daily_loss_cap_fraction: 0.02,
+            base_qty: 1.0,
+            sizing: strategy::SizingMode::Divergence(
+                strategy::DivergenceSizer::new(0.005).unwrap(),
+            ),
+            regime: strategy::Regime::Normal,
+            seconds_to_expiry: None,
+            market_position_qty: 0.0,
+            max_position_qty: 100.0,
+            size_scale: 1.0,
+            min_touch_qty: 1.0,
+        };
+        let mut gate = crate::live::LagTriggerGate::new(0, 1).unwrap();
+        let mut strategy = strategy::LagDivergenceStrategy::default();
+        let mut joined = joined_inputs_for_hold_signal(42);
+        joined.quote_tick.depth = BookDepth {
+            bids: vec![BookLevel { px: 0.48, qty: 5.0 }],
+            asks: vec![
+                BookLevel { px: 0.52, qty: 2.0 },
+                BookLevel { px: 0.53, qty: 0.5 },
+            ],
+        };
+
+        // Sized at four shares, but only 2.5 are offered.
+        let (shrunk, events) =
+            super::run_strategy_once_gated(42, &joined, &mut strategy, &params, &mut gate, 0);
+        params.min_touch_qty = 3.0;
+        let (thin, _) =
+            super::run_strategy_once_gated(43, &joined, &mut strategy, &params, &mut gate, 1);
+
+        assert_eq!(shrunk.map(|intent| intent.qty), Some(2.5));
+        assert!(events
+            .iter()
+            .any(|event| event.stage == RuntimeStage::PaperFillRecorded));
+        assert!(thin.is_none());
+    }
+
     fn joined_inputs_for_buy_signal(tick: u64) -> JoinedLiveInputs {
         JoinedLiveInputs {
             btc_tick: BtcMedianTick::new(64_000.0, 8.0, 3, tick),
             quote_tick: PolymarketQuoteTick {
                 market_slug: "btc-up-down".to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
                 best_yes_bid: 0.48,
                 best_yes_ask: 0.52,
                 mid_yes: 0.50,
                 ts: tick,
+                depth: BookDepth::default(),
+                liquidity_score: None,
             },
         }
     }
@@ -269,10 +944,14 @@ mod tests {
             btc_tick: BtcMedianTick::new(64_000.0, 0.0, 3, tick),
             quote_tick: PolymarketQuoteTick {
                 market_slug: "btc-up-down".to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
                 best_yes_bid: 0.48,
                 best_yes_ask: 0.52,
                 mid_yes: 0.50,
                 ts: tick,
+                depth: BookDepth::default(),
+                liquidity_score: None,
             },
         }
     }
@@ -282,10 +961,14 @@ mod tests {
             btc_tick: BtcMedianTick::new(64_000.0, 12.0, 3, tick),
             quote_tick: PolymarketQuoteTick {
                 market_slug: "btc-up-down".to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
                 best_yes_bid: 0.89,
                 best_yes_ask: 0.91,
                 mid_yes: 0.90,
                 ts: tick,
+                depth: BookDepth::default(),
+                liquidity_score: None,
             },
         }
     }
@@ -295,10 +978,14 @@ mod tests {
             btc_tick: BtcMedianTick::new(64_000.0, 8.0, 3, tick),
             quote_tick: PolymarketQuoteTick {
                 market_slug: "btc-up-down".to_string(),
+                source: QuoteSource::Polymarket,
+                asset: Asset::Btc,
                 best_yes_bid: 0.0,
                 best_yes_ask: 0.0,
                 mid_yes: 0.0,
                 ts: tick,
+                depth: BookDepth::default(),
+                liquidity_score: None,
             },
         }
     }
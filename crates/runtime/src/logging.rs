@@ -30,15 +30,20 @@ pub trait RunLogWriter {
     fn write(&mut self, event: RunLogEvent);
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PaperJournalRowKind {
+    PaperIntent,
     PaperFill,
+    RiskReject,
 }
 
 impl PaperJournalRowKind {
     pub fn as_replay_action(self) -> &'static str {
         match self {
+            Self::PaperIntent => "paper_intent",
             Self::PaperFill => "paper_fill",
+            Self::RiskReject => "risk_reject",
         }
     }
 }
@@ -1,3 +1,7 @@
+use std::collections::VecDeque;
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LatencyPercentiles {
     pub count: usize,
@@ -10,7 +14,7 @@ pub struct LatencyPercentiles {
 
 #[derive(Debug, Default, Clone)]
 pub struct DecisionLatencyMetrics {
-    latencies_nanos: Vec<u64>,
+    histogram: LatencyHistogram,
 }
 
 impl DecisionLatencyMetrics {
@@ -19,52 +23,310 @@ impl DecisionLatencyMetrics {
     }
 
     pub fn record_latency_nanos(&mut self, latency_nanos: u64) {
-        self.latencies_nanos.push(latency_nanos);
+        self.histogram.record(latency_nanos);
     }
 
     pub fn percentiles(&self) -> Option<LatencyPercentiles> {
-        if self.latencies_nanos.is_empty() {
-            return None;
+        self.histogram.percentiles()
+    }
+
+    pub fn percentile_nanos(&self, percentile: usize) -> Option<u64> {
+        self.histogram.percentile_nanos(percentile)
+    }
+}
+
+/// Values below this land in a bucket of their own.
+const EXACT_BUCKETS: usize = 64;
+/// Buckets per power of two above `EXACT_BUCKETS`, which bounds the
+/// relative error of a reported percentile to 1/32.
+const SUB_BUCKETS: usize = EXACT_BUCKETS / 2;
+const SUB_BUCKET_BITS: u32 = SUB_BUCKETS.trailing_zeros();
+const HISTOGRAM_BUCKETS: usize =
+    EXACT_BUCKETS + (u64::BITS - SUB_BUCKET_BITS - 1) as usize * SUB_BUCKETS;
+
+/// Fixed-size log-linear histogram of latencies in nanoseconds.
+///
+/// Recording is O(1) and percentiles walk a fixed number of buckets, however
+/// many samples were seen. Latencies under 64ns are exact; above that a
+/// percentile reports the top of its bucket, within 1/32 of the true
+/// sample and never above the largest one recorded. Histograms merge by
+/// adding bucket counts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+    max_nanos: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; HISTOGRAM_BUCKETS],
+            count: 0,
+            max_nanos: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, latency_nanos: u64) {
+        self.buckets[bucket_index(latency_nanos)] += 1;
+        self.count += 1;
+        self.max_nanos = self.max_nanos.max(latency_nanos);
+    }
+
+    /// Adds every sample of `other` to this histogram.
+    pub fn merge(&mut self, other: &Self) {
+        for (bucket, other_count) in self.buckets.iter_mut().zip(&other.buckets) {
+            *bucket += other_count;
         }
+        self.count += other.count;
+        self.max_nanos = self.max_nanos.max(other.max_nanos);
+    }
+
+    /// Forgets every sample, keeping the bucket storage.
+    pub fn clear(&mut self) {
+        self.buckets.fill(0);
+        self.count = 0;
+        self.max_nanos = 0;
+    }
 
-        let mut sorted = self.latencies_nanos.clone();
-        sorted.sort_unstable();
-        let count = sorted.len();
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn percentiles(&self) -> Option<LatencyPercentiles> {
+        if self.is_empty() {
+            return None;
+        }
 
         Some(LatencyPercentiles {
-            count,
-            p50_nanos: percentile_nearest_rank(&sorted, 50)?,
-            p90_nanos: percentile_nearest_rank(&sorted, 90)?,
-            p95_nanos: percentile_nearest_rank(&sorted, 95)?,
-            p99_nanos: percentile_nearest_rank(&sorted, 99)?,
-            max_nanos: sorted[count - 1],
+            count: self.count as usize,
+            p50_nanos: self.percentile_nanos(50)?,
+            p90_nanos: self.percentile_nanos(90)?,
+            p95_nanos: self.percentile_nanos(95)?,
+            p99_nanos: self.percentile_nanos(99)?,
+            max_nanos: self.max_nanos,
         })
     }
 
+    /// Nearest-rank percentile, for `percentile` in 1..=100.
     pub fn percentile_nanos(&self, percentile: usize) -> Option<u64> {
-        if self.latencies_nanos.is_empty() {
+        if self.is_empty() || !(1..=100).contains(&percentile) {
             return None;
         }
 
-        let mut sorted = self.latencies_nanos.clone();
-        sorted.sort_unstable();
-        percentile_nearest_rank(&sorted, percentile)
+        let rank = (percentile as u64 * self.count).div_ceil(100).max(1);
+        let mut seen = 0;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            seen += bucket;
+            if seen >= rank {
+                return Some(bucket_upper_nanos(index).min(self.max_nanos));
+            }
+        }
+        Some(self.max_nanos)
+    }
+}
+
+fn bucket_index(nanos: u64) -> usize {
+    if nanos < EXACT_BUCKETS as u64 {
+        return nanos as usize;
+    }
+    // Keep the top SUB_BUCKET_BITS + 1 bits; the leading one is implied.
+    let shift = u64::BITS - nanos.leading_zeros() - SUB_BUCKET_BITS - 1;
+    let sub_bucket = (nanos >> shift) as usize - SUB_BUCKETS;
+    EXACT_BUCKETS + (shift as usize - 1) * SUB_BUCKETS + sub_bucket
+}
+
+fn bucket_upper_nanos(index: usize) -> u64 {
+    if index < EXACT_BUCKETS {
+        return index as u64;
+    }
+    let offset = index - EXACT_BUCKETS;
+    let shift = (offset / SUB_BUCKETS + 1) as u32;
+    let top = (SUB_BUCKETS + offset % SUB_BUCKETS) as u64;
+    ((top + 1) << shift).wrapping_sub(1)
+}
+
+/// Latency histograms over consecutive windows of `window_ms`, keeping the
+/// newest `windows` of them so percentiles cover a rolling span.
+#[derive(Debug, Clone)]
+pub struct WindowedLatencyHistogram {
+    window_ms: u64,
+    windows: usize,
+    /// `(window start, histogram)`, oldest first.
+    recent: VecDeque<(u64, LatencyHistogram)>,
+}
+
+impl WindowedLatencyHistogram {
+    /// `window_ms` and `windows` are raised to at least 1.
+    pub fn new(window_ms: u64, windows: usize) -> Self {
+        Self {
+            window_ms: window_ms.max(1),
+            windows: windows.max(1),
+            recent: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, now_ms: u64, latency_nanos: u64) {
+        let start = now_ms - now_ms % self.window_ms;
+        self.expire(now_ms);
+        match self.recent.back_mut() {
+            // A clock that steps back keeps recording into the newest window.
+            Some((window_start, histogram)) if *window_start >= start => {
+                histogram.record(latency_nanos)
+            }
+            _ => {
+                // Reuse the oldest window's storage once the ring is full.
+                let mut histogram = if self.recent.len() >= self.windows {
+                    self.recent.pop_front().map(|(_, histogram)| histogram)
+                } else {
+                    None
+                }
+                .unwrap_or_default();
+                histogram.clear();
+                histogram.record(latency_nanos);
+                self.recent.push_back((start, histogram));
+            }
+        }
+    }
+
+    /// Every sample still inside the rolling span at `now_ms`, as one
+    /// histogram.
+    pub fn merged(&self, now_ms: u64) -> LatencyHistogram {
+        let oldest = self.oldest_start(now_ms);
+        let mut merged = LatencyHistogram::new();
+        for (_, histogram) in self.recent.iter().filter(|(start, _)| *start >= oldest) {
+            merged.merge(histogram);
+        }
+        merged
+    }
+
+    pub fn percentiles(&self, now_ms: u64) -> Option<LatencyPercentiles> {
+        self.merged(now_ms).percentiles()
+    }
+
+    pub fn percentile_nanos(&self, now_ms: u64, percentile: usize) -> Option<u64> {
+        self.merged(now_ms).percentile_nanos(percentile)
+    }
+
+    fn oldest_start(&self, now_ms: u64) -> u64 {
+        let current = now_ms - now_ms % self.window_ms;
+        current.saturating_sub((self.windows as u64 - 1) * self.window_ms)
+    }
+
+    fn expire(&mut self, now_ms: u64) {
+        let oldest = self.oldest_start(now_ms);
+        // Leave one expired window behind so `record` can reuse it.
+        while self.recent.len() > 1 && self.recent[1].0 < oldest {
+            self.recent.pop_front();
+        }
+    }
+}
+
+/// Where StatsD metrics are sent and how they are named.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsdConfig {
+    /// `host:port` of the statsd or DogStatsD agent.
+    pub addr: String,
+    /// Joined to every metric name with a `.`; empty sends bare names.
+    pub prefix: String,
+    /// DogStatsD tags (`key:value` or bare `key`) sent with every metric.
+    /// Plain statsd servers do not understand tags, so leave this empty for
+    /// them.
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatsdKind {
+    Count,
+    Gauge,
+    Timing,
+}
+
+impl StatsdKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Count => "c",
+            Self::Gauge => "g",
+            Self::Timing => "ms",
+        }
     }
 }
 
-fn percentile_nearest_rank(sorted: &[u64], percentile: usize) -> Option<u64> {
-    if sorted.is_empty() || !(1..=100).contains(&percentile) {
-        return None;
+/// Fire-and-forget UDP emitter for statsd and DogStatsD agents.
+///
+/// Sends never block the decision loop: the socket is non-blocking and a
+/// datagram the kernel will not take is dropped, as statsd expects.
+#[derive(Debug)]
+pub struct StatsdEmitter {
+    socket: UdpSocket,
+    prefix: String,
+    tags: Vec<String>,
+}
+
+impl StatsdEmitter {
+    pub fn connect(config: &StatsdConfig) -> io::Result<Self> {
+        let target = config.addr.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "statsd address did not resolve")
+        })?;
+        let local: SocketAddr = match target {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(target)?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            socket,
+            prefix: config.prefix.clone(),
+            tags: config.tags.clone(),
+        })
+    }
+
+    pub fn count(&self, name: &str, value: u64) {
+        self.send(&self.line(name, &value.to_string(), StatsdKind::Count));
+    }
+
+    pub fn gauge(&self, name: &str, value: f64) {
+        self.send(&self.line(name, &value.to_string(), StatsdKind::Gauge));
+    }
+
+    pub fn timing_us(&self, name: &str, micros: u64) {
+        let millis = format!("{:.3}", micros as f64 / 1_000.0);
+        self.send(&self.line(name, &millis, StatsdKind::Timing));
     }
 
-    let count = sorted.len();
-    let rank = (percentile * count).div_ceil(100);
-    sorted.get(rank.saturating_sub(1)).copied()
+    fn line(&self, name: &str, value: &str, kind: StatsdKind) -> String {
+        let mut line = if self.prefix.is_empty() {
+            format!("{name}:{value}|{}", kind.as_str())
+        } else {
+            format!("{}.{name}:{value}|{}", self.prefix, kind.as_str())
+        };
+        if !self.tags.is_empty() {
+            line.push_str("|#");
+            line.push_str(&self.tags.join(","));
+        }
+        line
+    }
+
+    fn send(&self, line: &str) {
+        let _ = self.socket.send(line.as_bytes());
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::DecisionLatencyMetrics;
+    use super::*;
 
     #[test]
     fn percentiles_returns_none_for_empty_input() {
@@ -106,4 +368,115 @@ mod tests {
         assert_eq!(metrics.percentile_nanos(0), None);
         assert_eq!(metrics.percentile_nanos(101), None);
     }
+
+    #[test]
+    fn histogram_percentiles_stay_within_bucket_precision() {
+        let mut histogram = LatencyHistogram::new();
+        let mut samples: Vec<u64> = (1..=10_000).map(|i| i * 37 + i * i).collect();
+        for sample in &samples {
+            histogram.record(*sample);
+        }
+        samples.sort_unstable();
+
+        for percentile in [1, 25, 50, 90, 95, 99, 100] {
+            let exact = samples[(percentile * samples.len()).div_ceil(100) - 1];
+            let estimate = histogram.percentile_nanos(percentile).unwrap();
+            assert!(estimate >= exact, "p{percentile}: {estimate} < {exact}");
+            assert!(
+                (estimate - exact) as f64 <= exact as f64 / SUB_BUCKETS as f64,
+                "p{percentile}: {estimate} too far from {exact}"
+            );
+        }
+        let report = histogram.percentiles().unwrap();
+        assert_eq!(report.count, 10_000);
+        assert_eq!(report.max_nanos, *samples.last().unwrap());
+    }
+
+    #[test]
+    fn bucket_bounds_cover_the_whole_range() {
+        for nanos in [0, 63, 64, 65, 127, 128, 1_000_000, u64::MAX / 3, u64::MAX] {
+            let index = bucket_index(nanos);
+            assert!(index < HISTOGRAM_BUCKETS, "{nanos} -> {index}");
+            assert!(bucket_upper_nanos(index) >= nanos);
+            if index > 0 {
+                assert!(bucket_upper_nanos(index - 1) < nanos);
+            }
+        }
+        assert_eq!(bucket_upper_nanos(HISTOGRAM_BUCKETS - 1), u64::MAX);
+    }
+
+    #[test]
+    fn merged_histograms_match_recording_everything_in_one() {
+        let mut left = LatencyHistogram::new();
+        let mut right = LatencyHistogram::new();
+        let mut both = LatencyHistogram::new();
+        for sample in 0..2_000_u64 {
+            let nanos = sample * 911 % 50_000;
+            if sample % 3 == 0 {
+                left.record(nanos);
+            } else {
+                right.record(nanos);
+            }
+            both.record(nanos);
+        }
+
+        left.merge(&right);
+        assert_eq!(left, both);
+    }
+
+    #[test]
+    fn windowed_histograms_drop_samples_older_than_the_span() {
+        let mut windowed = WindowedLatencyHistogram::new(1_000, 3);
+        windowed.record(500, 9_000);
+        windowed.record(1_500, 40);
+        windowed.record(2_500, 200);
+
+        let report = windowed.percentiles(2_900).unwrap();
+        assert_eq!(report.count, 3);
+        assert_eq!(report.max_nanos, 9_000);
+
+        // At 3s the window holding the 9µs sample falls out of the span.
+        windowed.record(3_100, 300);
+        let report = windowed.percentiles(3_100).unwrap();
+        assert_eq!(report.count, 3);
+        assert_eq!(report.max_nanos, 300);
+        assert_eq!(windowed.percentile_nanos(3_100, 1), Some(40));
+
+        assert_eq!(windowed.percentiles(10_000), None);
+    }
+
+    #[test]
+    fn statsd_lines_carry_prefix_kind_and_tags() {
+        let agent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        agent
+            .set_read_timeout(Some(std::time::Duration::from_secs(2)))
+            .unwrap();
+        let emitter = StatsdEmitter::connect(&StatsdConfig {
+            addr: agent.local_addr().unwrap().to_string(),
+            prefix: "lab".to_string(),
+            tags: vec!["env:paper".to_string(), "region:eu".to_string()],
+        })
+        .unwrap();
+
+        emitter.timing_us("decision.latency", 1_250);
+        emitter.count("fills", 3);
+        let mut buf = [0_u8; 256];
+        let len = agent.recv(&mut buf).unwrap();
+        assert_eq!(
+            &buf[..len],
+            b"lab.decision.latency:1.250|ms|#env:paper,region:eu"
+        );
+        let len = agent.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"lab.fills:3|c|#env:paper,region:eu");
+
+        let bare = StatsdEmitter {
+            prefix: String::new(),
+            tags: Vec::new(),
+            ..emitter
+        };
+        assert_eq!(
+            bare.line("feed.updates.okx", "12", StatsdKind::Gauge),
+            "feed.updates.okx:12|g"
+        );
+    }
 }
@@ -0,0 +1,390 @@
+use rust_decimal::prelude::{FromPrimitive, Signed, ToPrimitive};
+use rust_decimal::Decimal;
+
+use crate::live::QuoteSource;
+
+/// Quantities below this (1e-9) are treated as flat.
+const QTY_EPSILON: Decimal = Decimal::from_parts(1, 0, 0, false, 9);
+
+/// How a binary market resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketOutcome {
+    Yes,
+    No,
+}
+
+impl MarketOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Yes => "yes",
+            Self::No => "no",
+        }
+    }
+
+    /// Payout per YES share.
+    pub fn settle_px(self) -> f64 {
+        match self {
+            Self::Yes => 1.0,
+            Self::No => 0.0,
+        }
+    }
+
+    fn settle_decimal(self) -> Decimal {
+        match self {
+            Self::Yes => Decimal::ONE,
+            Self::No => Decimal::ZERO,
+        }
+    }
+}
+
+/// YES exposure and PnL in one market. `qty` is signed (negative is short
+/// YES) and `cost` is the average-cost basis of the open quantity, so
+/// `cost / qty` is the entry price. `realized_pnl` is everything booked in
+/// this market so far and survives the position going flat.
+///
+/// Amounts are decimal so thousands of fills add up without binary rounding
+/// drift; callers convert with [`to_f64`] where they report them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub market_id: String,
+    pub source: QuoteSource,
+    pub qty: Decimal,
+    pub cost: Decimal,
+    pub mark_px: Decimal,
+    pub realized_pnl: Decimal,
+}
+
+impl Position {
+    /// Average entry price of the open quantity; zero when flat.
+    pub fn avg_px(&self) -> Decimal {
+        if self.is_flat() {
+            Decimal::ZERO
+        } else {
+            self.cost / self.qty
+        }
+    }
+
+    pub fn unrealized_pnl(&self) -> Decimal {
+        self.qty * self.mark_px - self.cost
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.qty.abs() < QTY_EPSILON
+    }
+}
+
+/// `value` as a float for risk math and reporting.
+pub fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+/// The decimal closest to the shortest representation of `value`, so a
+/// price of `0.4` books as exactly 0.4; `None` when it is not finite.
+fn to_decimal(value: f64) -> Option<Decimal> {
+    Decimal::from_f64(value)
+}
+
+/// A position closed out at the market's resolution price. Quantity and
+/// price are floats for reporting; the booked PnL stays decimal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settlement {
+    pub market_id: String,
+    pub source: QuoteSource,
+    pub outcome: MarketOutcome,
+    pub qty: f64,
+    pub settle_px: f64,
+    pub realized_pnl: Decimal,
+}
+
+/// Independent per-market books for the paper portfolio.
+///
+/// Each market keeps its own quantity, cost basis, mark and realized PnL;
+/// cash, equity and PnL for the whole book are sums over the markets taken
+/// when asked for. Open positions are valued at their own market's last
+/// mark until the market resolves and [`settle`](Self::settle) pays them
+/// out at 0 or 1. Money amounts come back as decimals; convert with
+/// [`to_f64`] only where they are serialized.
+#[derive(Debug, Clone)]
+pub struct PositionManager {
+    starting_cash: Decimal,
+    markets: Vec<Position>,
+}
+
+impl PositionManager {
+    pub fn new(starting_cash: Decimal) -> Self {
+        Self {
+            starting_cash,
+            markets: Vec::new(),
+        }
+    }
+
+    /// Books a fill of `signed_qty` YES shares at `px`; positive buys. The
+    /// part that reduces the position realizes PnL against its average
+    /// entry; the rest adds to, or after a flip opens, the cost basis.
+    pub fn apply_fill(&mut self, source: QuoteSource, market_id: &str, signed_qty: f64, px: f64) {
+        let (Some(signed_qty), Some(px)) = (to_decimal(signed_qty), to_decimal(px)) else {
+            return;
+        };
+        if signed_qty.abs() < QTY_EPSILON {
+            return;
+        }
+
+        let market = match self
+            .markets
+            .iter()
+            .position(|market| market.market_id == market_id)
+        {
+            Some(idx) => &mut self.markets[idx],
+            None => {
+                self.markets.push(Position {
+                    market_id: market_id.to_string(),
+                    source,
+                    qty: Decimal::ZERO,
+                    cost: Decimal::ZERO,
+                    mark_px: px,
+                    realized_pnl: Decimal::ZERO,
+                });
+                self.markets.last_mut().expect("market was just pushed")
+            }
+        };
+
+        let closing_qty = if market.qty * signed_qty < Decimal::ZERO {
+            signed_qty.abs().min(market.qty.abs()) * market.qty.signum()
+        } else {
+            Decimal::ZERO
+        };
+        if !closing_qty.is_zero() {
+            let avg_px = market.avg_px();
+            market.realized_pnl += (px - avg_px) * closing_qty;
+            market.cost -= avg_px * closing_qty;
+            market.qty -= closing_qty;
+        }
+        let opening_qty = signed_qty + closing_qty;
+        market.qty += opening_qty;
+        market.cost += opening_qty * px;
+        if market.is_flat() {
+            market.qty = Decimal::ZERO;
+            market.cost = Decimal::ZERO;
+        }
+        market.mark_px = px;
+    }
+
+    /// Books a venue fee paid on `market_id` as realized loss.
+    pub fn charge_fee(&mut self, market_id: &str, fee: f64) {
+        let Some(fee) = to_decimal(fee) else {
+            return;
+        };
+        if let Some(market) = self
+            .markets
+            .iter_mut()
+            .find(|market| market.market_id == market_id)
+        {
+            market.realized_pnl -= fee;
+        }
+    }
+
+    /// Updates the mark for `market_id` if a position is open there.
+    pub fn mark(&mut self, market_id: &str, mid_px: f64) {
+        let Some(mid_px) = to_decimal(mid_px) else {
+            return;
+        };
+        if let Some(position) = self
+            .markets
+            .iter_mut()
+            .find(|market| market.market_id == market_id && !market.is_flat())
+        {
+            position.mark_px = mid_px;
+        }
+    }
+
+    /// Closes the position in `market_id` at the outcome's payout and books
+    /// the difference to its cost as realized PnL.
+    pub fn settle(&mut self, market_id: &str, outcome: MarketOutcome) -> Option<Settlement> {
+        let market = self
+            .markets
+            .iter_mut()
+            .find(|market| market.market_id == market_id && !market.is_flat())?;
+        let settle_px = outcome.settle_decimal();
+        let qty = market.qty;
+        let realized_pnl = qty * settle_px - market.cost;
+
+        market.realized_pnl += realized_pnl;
+        market.qty = Decimal::ZERO;
+        market.cost = Decimal::ZERO;
+        market.mark_px = settle_px;
+
+        Some(Settlement {
+            market_id: market.market_id.clone(),
+            source: market.source,
+            outcome,
+            qty: to_f64(qty),
+            settle_px: outcome.settle_px(),
+            realized_pnl,
+        })
+    }
+
+    /// Starting cash plus realized PnL, less what the open positions cost.
+    pub fn cash(&self) -> Decimal {
+        self.starting_cash
+            + self
+                .markets
+                .iter()
+                .map(|market| market.realized_pnl - market.cost)
+                .sum::<Decimal>()
+    }
+
+    pub fn realized_pnl(&self) -> Decimal {
+        self.markets.iter().map(|market| market.realized_pnl).sum()
+    }
+
+    pub fn unrealized_pnl(&self) -> Decimal {
+        self.markets.iter().map(Position::unrealized_pnl).sum()
+    }
+
+    /// Starting cash plus every market's realized and open PnL.
+    pub fn equity(&self) -> Decimal {
+        self.starting_cash + self.realized_pnl() + self.unrealized_pnl()
+    }
+
+    /// The open position in `market_id`, if any.
+    pub fn position(&self, market_id: &str) -> Option<&Position> {
+        self.positions()
+            .find(|position| position.market_id == market_id)
+    }
+
+    /// Net YES shares held in `market_id`; zero when flat.
+    pub fn market_qty(&self, market_id: &str) -> f64 {
+        self.position(market_id)
+            .map_or(0.0, |position| to_f64(position.qty))
+    }
+
+    /// Net YES shares across all markets.
+    pub fn net_qty(&self) -> f64 {
+        to_f64(self.positions().map(|position| position.qty).sum())
+    }
+
+    /// Open positions in the order their markets were first filled.
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.markets.iter().filter(|market| !market.is_flat())
+    }
+
+    /// Every market traded, flat or not, in the order first filled.
+    pub fn markets(&self) -> &[Position] {
+        &self.markets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(units: i64, scale: u32) -> Decimal {
+        Decimal::new(units, scale)
+    }
+
+    #[test]
+    fn marks_open_positions_and_settles_winners_at_one() {
+        let mut book = PositionManager::new(usd(100, 0));
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", 10.0, 0.40);
+        book.mark("btc-1215", 0.55);
+
+        assert_eq!(book.cash(), usd(96, 0));
+        assert_eq!(book.equity(), usd(1015, 1));
+
+        let settlement = book.settle("btc-1215", MarketOutcome::Yes).unwrap();
+
+        assert_eq!(settlement.settle_px, 1.0);
+        assert_eq!(settlement.realized_pnl, usd(6, 0));
+        assert_eq!(book.cash(), usd(106, 0));
+        assert_eq!(book.equity(), usd(106, 0));
+        assert_eq!(book.positions().count(), 0);
+        assert!(book.settle("btc-1215", MarketOutcome::Yes).is_none());
+    }
+
+    #[test]
+    fn short_yes_position_gains_when_market_resolves_no() {
+        let mut book = PositionManager::new(usd(100, 0));
+        book.apply_fill(QuoteSource::Kalshi, "KXBTC15M-A", -5.0, 0.60);
+
+        let settlement = book.settle("KXBTC15M-A", MarketOutcome::No).unwrap();
+
+        assert_eq!(settlement.qty, -5.0);
+        assert_eq!(settlement.realized_pnl, usd(3, 0));
+        assert_eq!(book.cash(), usd(103, 0));
+        assert_eq!(book.realized_pnl(), usd(3, 0));
+    }
+
+    #[test]
+    fn round_trip_closes_position_and_realizes_pnl() {
+        let mut book = PositionManager::new(usd(100, 0));
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", 2.0, 0.40);
+        book.apply_fill(QuoteSource::Polymarket, "btc-1230", 1.0, 0.50);
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", -2.0, 0.45);
+
+        assert_eq!(book.positions().count(), 1);
+        assert_eq!(book.net_qty(), 1.0);
+        assert_eq!(book.market_qty("btc-1215"), 0.0);
+        assert_eq!(book.market_qty("btc-1230"), 1.0);
+        assert_eq!(book.realized_pnl(), usd(1, 1));
+    }
+
+    #[test]
+    fn markets_keep_their_own_basis_marks_and_realized_pnl() {
+        let mut book = PositionManager::new(usd(100, 0));
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", 10.0, 0.40);
+        book.apply_fill(QuoteSource::Kalshi, "KXBTC15M-A", -4.0, 0.70);
+        book.mark("btc-1215", 0.50);
+        book.mark("KXBTC15M-A", 0.60);
+
+        // Selling half realizes against the 0.40 entry; the rest keeps it.
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", -5.0, 0.56);
+        let btc = book.position("btc-1215").unwrap();
+        assert_eq!(btc.realized_pnl, Decimal::new(8, 1));
+        assert_eq!(btc.avg_px(), Decimal::new(4, 1));
+        assert_eq!(btc.unrealized_pnl(), Decimal::new(8, 1));
+
+        let kalshi = book.position("KXBTC15M-A").unwrap();
+        assert_eq!(kalshi.avg_px(), Decimal::new(7, 1));
+        assert_eq!(kalshi.unrealized_pnl(), Decimal::new(4, 1));
+
+        book.settle("KXBTC15M-A", MarketOutcome::No).unwrap();
+        assert_eq!(book.markets().len(), 2);
+        assert_eq!(book.markets()[1].realized_pnl, Decimal::new(28, 1));
+        assert_eq!(book.realized_pnl(), usd(36, 1));
+        assert_eq!(book.equity(), usd(1044, 1));
+        assert_eq!(book.cash(), usd(1016, 1));
+    }
+
+    #[test]
+    fn many_small_fills_round_trip_without_drift() {
+        let mut book = PositionManager::new(usd(10_000, 0));
+        for _ in 0..10_000 {
+            book.apply_fill(QuoteSource::Polymarket, "btc-1215", 0.1, 0.37);
+        }
+        book.mark("btc-1215", 0.41);
+        assert_eq!(book.market_qty("btc-1215"), 1_000.0);
+        assert_eq!(book.cash(), usd(9_630, 0));
+        assert_eq!(book.equity(), usd(10_040, 0));
+
+        for _ in 0..10_000 {
+            book.apply_fill(QuoteSource::Polymarket, "btc-1215", -0.1, 0.41);
+        }
+        assert_eq!(book.positions().count(), 0);
+        assert_eq!(book.realized_pnl(), usd(40, 0));
+        assert_eq!(book.cash(), usd(10_040, 0));
+    }
+
+    #[test]
+    fn fees_come_out_of_realized_pnl_and_cash() {
+        let mut book = PositionManager::new(usd(1_000, 0));
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", 10.0, 0.5);
+        book.charge_fee("btc-1215", 0.25);
+        assert_eq!(book.realized_pnl(), -usd(25, 2));
+        assert_eq!(book.cash(), usd(99_475, 2));
+
+        book.apply_fill(QuoteSource::Polymarket, "btc-1215", -10.0, 0.6);
+        book.charge_fee("btc-1215", 0.25);
+        assert_eq!(book.realized_pnl(), usd(50, 2));
+        assert_eq!(book.equity(), usd(100_050, 2));
+    }
+}
@@ -0,0 +1,595 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use tokio::sync::mpsc;
+
+#[cfg(feature = "parquet")]
+use crate::columnar::{tick_batch, tick_schema, ParquetSeries};
+use crate::live::{
+    Asset, BookDepth, NormalizedBtcTick, PolymarketQuoteTick, PredictorSource, PredictorTick,
+    QuoteSource,
+};
+
+pub const SPOT_CSV_HEADER: &str = "recv_ms,asset,venue,px,size,ts,run_id\n";
+pub const QUOTES_CSV_HEADER: &str =
+    "recv_ms,source,asset,market_slug,best_yes_bid,best_yes_ask,mid_yes,ts,run_id\n";
+pub const PREDICTORS_CSV_HEADER: &str = "recv_ms,source,predicted_yes_px,confidence,ts_ms,run_id\n";
+/// Last column of every stream, absent from recordings made before runs
+/// were stamped on them.
+const RUN_ID_COLUMN: &str = ",run_id";
+
+/// Kind of tick the recorder writes, one file series per stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordStream {
+    Spot,
+    Quotes,
+    Predictors,
+}
+
+impl RecordStream {
+    pub const ALL: [RecordStream; 3] = [Self::Spot, Self::Quotes, Self::Predictors];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "spot" => Some(Self::Spot),
+            "quotes" => Some(Self::Quotes),
+            "predictors" => Some(Self::Predictors),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Spot => "spot",
+            Self::Quotes => "quotes",
+            Self::Predictors => "predictors",
+        }
+    }
+
+    pub fn csv_header(self) -> &'static str {
+        match self {
+            Self::Spot => SPOT_CSV_HEADER,
+            Self::Quotes => QUOTES_CSV_HEADER,
+            Self::Predictors => PREDICTORS_CSV_HEADER,
+        }
+    }
+
+    /// Identifies a recorded file from its header line, with or without
+    /// the `run_id` column.
+    pub fn from_header(line: &str) -> Option<Self> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        Self::ALL.into_iter().find(|stream| {
+            let header = stream.csv_header().trim_end();
+            header == line || header.strip_suffix(RUN_ID_COLUMN) == Some(line)
+        })
+    }
+
+    fn csv_columns(self) -> usize {
+        self.csv_header().split(',').count()
+    }
+}
+
+/// File format of recorded ticks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecordFormat {
+    #[default]
+    Csv,
+    /// Typed columns, written by the `parquet` build feature. Replay reads
+    /// CSV recordings only.
+    Parquet,
+}
+
+impl RecordFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "csv" => Some(Self::Csv),
+            "parquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    /// Whether this build can write the format.
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::Csv => true,
+            Self::Parquet => cfg!(feature = "parquet"),
+        }
+    }
+}
+
+/// A tick as it arrived from a feed, before any clock adjustment.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordedTick {
+    Spot(Asset, NormalizedBtcTick),
+    Quote(PolymarketQuoteTick),
+    Predictor(PredictorTick),
+}
+
+impl RecordedTick {
+    pub fn stream(&self) -> RecordStream {
+        match self {
+            Self::Spot(..) => RecordStream::Spot,
+            Self::Quote(_) => RecordStream::Quotes,
+            Self::Predictor(_) => RecordStream::Predictors,
+        }
+    }
+
+    /// One CSV row, prefixed with the local receive time and ending with
+    /// the run active when it was recorded. Book depth is not recorded;
+    /// quotes keep their top of book only.
+    pub fn csv_row(&self, recv_ms: u64, run_id: Option<u64>) -> String {
+        let fields = match self {
+            Self::Spot(asset, tick) => format!(
+                "{recv_ms},{},{},{},{},{}",
+                asset.as_str(),
+                escape_csv_field(&tick.venue),
+                tick.px,
+                tick.size,
+                tick.ts
+            ),
+            Self::Quote(quote) => format!(
+                "{recv_ms},{},{},{},{},{},{},{}",
+                quote.source.as_str(),
+                quote.asset.as_str(),
+                escape_csv_field(&quote.market_slug),
+                quote.best_yes_bid,
+                quote.best_yes_ask,
+                quote.mid_yes,
+                quote.ts
+            ),
+            Self::Predictor(tick) => format!(
+                "{recv_ms},{},{},{},{}",
+                tick.source.as_str(),
+                tick.predicted_yes_px,
+                tick.confidence,
+                tick.ts_ms
+            ),
+        };
+        let run_id = run_id.map_or_else(String::new, |run_id| run_id.to_string());
+        format!("{fields},{run_id}\n")
+    }
+
+    /// Parses a row written by [`csv_row`](Self::csv_row) back into its
+    /// receive time and tick. The run column is optional, so recordings
+    /// made before it existed still replay.
+    pub fn parse_csv_row(stream: RecordStream, line: &str) -> Option<(u64, Self)> {
+        let fields = split_csv_row(line.trim_end_matches(['\r', '\n']));
+        let columns = stream.csv_columns();
+        if fields.len() != columns && fields.len() != columns - 1 {
+            return None;
+        }
+        let field = |idx: usize| fields.get(idx).map(String::as_str);
+        let number = |idx: usize| field(idx)?.parse::<f64>().ok();
+        let millis = |idx: usize| field(idx)?.parse::<u64>().ok();
+        let recv_ms = millis(0)?;
+
+        let tick = match stream {
+            RecordStream::Spot => Self::Spot(
+                Asset::parse(field(1)?)?,
+                NormalizedBtcTick {
+                    venue: field(2)?.to_string(),
+                    px: number(3)?,
+                    size: number(4)?,
+                    ts: millis(5)?,
+                },
+            ),
+            RecordStream::Quotes => Self::Quote(PolymarketQuoteTick {
+                source: QuoteSource::parse(field(1)?)?,
+                asset: Asset::parse(field(2)?)?,
+                market_slug: field(3)?.to_string(),
+                best_yes_bid: number(4)?,
+                best_yes_ask: number(5)?,
+                mid_yes: number(6)?,
+                ts: millis(7)?,
+                depth: BookDepth::default(),
+                liquidity_score: None,
+            }),
+            RecordStream::Predictors => Self::Predictor(PredictorTick {
+                source: PredictorSource::parse(field(1)?)?,
+                predicted_yes_px: number(2)?,
+                confidence: number(3)?,
+                ts_ms: millis(4)?,
+            }),
+        };
+        Some((recv_ms, tick))
+    }
+}
+
+/// Receive time in unix milliseconds, paired with the tick.
+pub type TickRecordSender = mpsc::UnboundedSender<(u64, RecordedTick)>;
+pub type TickRecordReceiver = mpsc::UnboundedReceiver<(u64, RecordedTick)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickRecorderConfigError {
+    /// `rotate_bytes` must be greater than zero.
+    InvalidRotateBytes,
+    /// The format needs a build feature this build lacks.
+    FormatUnavailable,
+}
+
+/// Where recorded ticks go and which streams are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TickRecorderConfig {
+    pub dir: PathBuf,
+    /// A file is closed, and the next one in its series started, once it
+    /// reaches this many bytes.
+    pub rotate_bytes: u64,
+    pub format: RecordFormat,
+    /// Streams to record; the recorder is off when this is empty.
+    pub streams: Vec<RecordStream>,
+}
+
+impl TickRecorderConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.streams.is_empty()
+    }
+}
+
+struct StreamFile {
+    writer: BufWriter<File>,
+    bytes: u64,
+}
+
+/// Parquet ticks are buffered between flushes and written as one batch.
+#[cfg(feature = "parquet")]
+struct ParquetStream {
+    series: ParquetSeries,
+    pending: Vec<(u64, Option<u64>, RecordedTick)>,
+}
+
+/// Appends ticks to rotating files, one series per stream, named
+/// `<stream>-<session_ms>-<seq>.<csv|parquet>` under the configured
+/// directory.
+pub struct TickRecorder {
+    config: TickRecorderConfig,
+    session_ms: u64,
+    open: HashMap<RecordStream, StreamFile>,
+    next_seq: HashMap<RecordStream, u32>,
+    #[cfg(feature = "parquet")]
+    parquet: HashMap<RecordStream, ParquetStream>,
+}
+
+impl TickRecorder {
+    /// `session_ms` keeps file names from different runs apart.
+    pub fn new(
+        config: TickRecorderConfig,
+        session_ms: u64,
+    ) -> Result<Self, TickRecorderConfigError> {
+        if config.rotate_bytes == 0 {
+            return Err(TickRecorderConfigError::InvalidRotateBytes);
+        }
+        if !config.format.is_available() {
+            return Err(TickRecorderConfigError::FormatUnavailable);
+        }
+
+        Ok(Self {
+            config,
+            session_ms,
+            open: HashMap::new(),
+            next_seq: HashMap::new(),
+            #[cfg(feature = "parquet")]
+            parquet: HashMap::new(),
+        })
+    }
+
+    pub fn file_path(&self, stream: RecordStream, seq: u32) -> PathBuf {
+        self.config.dir.join(format!(
+            "{}-{}-{seq:04}.{}",
+            stream.as_str(),
+            self.session_ms,
+            self.config.format.extension()
+        ))
+    }
+
+    /// Writes `tick`, received during `run_id`, to its stream's current
+    /// file. Returns `false` when the stream is not enabled.
+    pub fn record(
+        &mut self,
+        recv_ms: u64,
+        run_id: Option<u64>,
+        tick: &RecordedTick,
+    ) -> io::Result<bool> {
+        let stream = tick.stream();
+        if !self.config.streams.contains(&stream) {
+            return Ok(false);
+        }
+
+        #[cfg(feature = "parquet")]
+        if self.config.format == RecordFormat::Parquet {
+            let dir = &self.config.dir;
+            let prefix = format!("{}-{}", stream.as_str(), self.session_ms);
+            let rotate_bytes = self.config.rotate_bytes;
+            self.parquet
+                .entry(stream)
+                .or_insert_with(|| ParquetStream {
+                    series: ParquetSeries::new(dir, prefix, tick_schema(stream), rotate_bytes),
+                    pending: Vec::new(),
+                })
+                .pending
+                .push((recv_ms, run_id, tick.clone()));
+            return Ok(true);
+        }
+
+        if !self.open.contains_key(&stream) {
+            let file = self.open_next(stream)?;
+            self.open.insert(stream, file);
+        }
+        let file = self
+            .open
+            .get_mut(&stream)
+            .expect("stream file was opened above");
+        let row = tick.csv_row(recv_ms, run_id);
+        file.writer.write_all(row.as_bytes())?;
+        file.bytes += row.len() as u64;
+
+        if file.bytes >= self.config.rotate_bytes {
+            if let Some(mut full) = self.open.remove(&stream) {
+                full.writer.flush()?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Writes out buffered ticks. Parquet files stay unreadable until they
+    /// roll over or [`close`](Self::close) runs.
+    pub fn flush(&mut self) -> io::Result<()> {
+        for file in self.open.values_mut() {
+            file.writer.flush()?;
+        }
+        #[cfg(feature = "parquet")]
+        for (stream, parquet) in &mut self.parquet {
+            let Some(&(last_recv_ms, ..)) = parquet.pending.last() else {
+                continue;
+            };
+            let batch = tick_batch(*stream, &parquet.pending).map_err(io::Error::other)?;
+            parquet.series.write(last_recv_ms, &batch)?;
+            parquet.pending.clear();
+        }
+        Ok(())
+    }
+
+    /// Flushes and finishes every open file.
+    pub fn close(&mut self) -> io::Result<()> {
+        self.flush()?;
+        self.open.clear();
+        #[cfg(feature = "parquet")]
+        for parquet in self.parquet.values_mut() {
+            parquet.series.close()?;
+        }
+        Ok(())
+    }
+
+    fn open_next(&mut self, stream: RecordStream) -> io::Result<StreamFile> {
+        fs::create_dir_all(&self.config.dir)?;
+        let seq = self.next_seq.entry(stream).or_insert(0);
+        let current = *seq;
+        *seq += 1;
+
+        let mut writer = BufWriter::new(File::create(self.file_path(stream, current))?);
+        let header = stream.csv_header();
+        writer.write_all(header.as_bytes())?;
+        Ok(StreamFile {
+            writer,
+            bytes: header.len() as u64,
+        })
+    }
+}
+
+/// Drains `receiver` into `recorder` until every sender is dropped, flushing
+/// after each burst. Each tick is stamped with `active_run` as it is
+/// written. Blocks the calling thread, so run it with
+/// `tokio::task::spawn_blocking`.
+pub fn run_tick_recorder(
+    mut recorder: TickRecorder,
+    mut receiver: TickRecordReceiver,
+    active_run: impl Fn() -> Option<u64>,
+) -> io::Result<()> {
+    while let Some((recv_ms, tick)) = receiver.blocking_recv() {
+        recorder.record(recv_ms, active_run(), &tick)?;
+        while let Ok((recv_ms, tick)) = receiver.try_recv() {
+            recorder.record(recv_ms, active_run(), &tick)?;
+        }
+        recorder.flush()?;
+    }
+    recorder.close()
+}
+
+fn escape_csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits a row into fields, undoing [`escape_csv_field`].
+pub(crate) fn split_csv_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut current)),
+            other => current.push(other),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("runtime-recorder-{label}-{unique}"))
+    }
+
+    fn spot(px: f64, ts: u64) -> RecordedTick {
+        RecordedTick::Spot(
+            Asset::Btc,
+            NormalizedBtcTick {
+                venue: "coinbase".to_string(),
+                px,
+                size: 0.5,
+                ts,
+            },
+        )
+    }
+
+    #[test]
+    fn formats_one_row_per_stream() {
+        let quote = RecordedTick::Quote(PolymarketQuoteTick {
+            market_slug: "btc-updown-15m-1772280000".to_string(),
+            source: QuoteSource::Polymarket,
+            asset: Asset::Btc,
+            best_yes_bid: 0.48,
+            best_yes_ask: 0.52,
+            mid_yes: 0.5,
+            ts: 1_000,
+            depth: BookDepth::default(),
+            liquidity_score: None,
+        });
+        let predictor = RecordedTick::Predictor(PredictorTick {
+            source: PredictorSource::parse("tradingview").unwrap(),
+            predicted_yes_px: 0.61,
+            confidence: 0.8,
+            ts_ms: 1_000,
+        });
+
+        assert_eq!(
+            spot(64_000.5, 990).csv_row(1_002, Some(4)),
+            "1002,btc,coinbase,64000.5,0.5,990,4\n"
+        );
+        assert_eq!(
+            quote.csv_row(1_002, Some(4)),
+            "1002,polymarket,btc,btc-updown-15m-1772280000,0.48,0.52,0.5,1000,4\n"
+        );
+        assert_eq!(
+            predictor.csv_row(1_002, None),
+            "1002,tradingview,0.61,0.8,1000,\n"
+        );
+    }
+
+    #[test]
+    fn parses_recorded_rows_back_into_ticks() {
+        let quote = RecordedTick::Quote(PolymarketQuoteTick {
+            market_slug: "odd,\"slug\"".to_string(),
+            source: QuoteSource::Kalshi,
+            asset: Asset::Eth,
+            best_yes_bid: 0.31,
+            best_yes_ask: 0.35,
+            mid_yes: 0.33,
+            ts: 2_000,
+            depth: BookDepth::default(),
+            liquidity_score: None,
+        });
+        let row = quote.csv_row(2_005, Some(2));
+
+        assert_eq!(
+            RecordedTick::parse_csv_row(RecordStream::Quotes, &row),
+            Some((2_005, quote))
+        );
+        assert_eq!(
+            RecordedTick::parse_csv_row(RecordStream::Spot, "1002,btc,coinbase,64000.5,0.5,990\n"),
+            Some((1_002, spot(64_000.5, 990)))
+        );
+        assert_eq!(
+            RecordStream::from_header(
+                "recv_ms,source,predicted_yes_px,confidence,ts_ms,run_id\r\n"
+            ),
+            Some(RecordStream::Predictors)
+        );
+        // Recordings from before the run column still load.
+        assert_eq!(
+            RecordStream::from_header("recv_ms,asset,venue,px,size,ts"),
+            Some(RecordStream::Spot)
+        );
+        assert_eq!(
+            RecordedTick::parse_csv_row(RecordStream::Spot, "1002,doge,coinbase,1,1,1"),
+            None
+        );
+        assert_eq!(
+            RecordedTick::parse_csv_row(RecordStream::Predictors, "1002,tradingview,0.5"),
+            None
+        );
+    }
+
+    #[test]
+    fn rotates_files_and_skips_disabled_streams() {
+        let dir = temp_dir("rotate");
+        let mut recorder = TickRecorder::new(
+            TickRecorderConfig {
+                dir: dir.clone(),
+                rotate_bytes: 80,
+                format: RecordFormat::Csv,
+                streams: vec![RecordStream::Spot],
+            },
+            42,
+        )
+        .unwrap();
+
+        for idx in 0..3 {
+            assert!(recorder
+                .record(1_000 + idx, Some(1), &spot(64_000.0, idx))
+                .unwrap());
+        }
+        let predictor = RecordedTick::Predictor(PredictorTick {
+            source: PredictorSource::parse("cryptoquant").unwrap(),
+            predicted_yes_px: 0.5,
+            confidence: 1.0,
+            ts_ms: 0,
+        });
+        assert!(!recorder.record(1_003, Some(1), &predictor).unwrap());
+        recorder.flush().unwrap();
+
+        let first = fs::read_to_string(recorder.file_path(RecordStream::Spot, 0)).unwrap();
+        let second = fs::read_to_string(recorder.file_path(RecordStream::Spot, 1)).unwrap();
+        assert_eq!(
+            first,
+            format!(
+                "{SPOT_CSV_HEADER}1000,btc,coinbase,64000,0.5,0,1\n1001,btc,coinbase,64000,0.5,1,1\n"
+            )
+        );
+        assert_eq!(
+            second,
+            format!("{SPOT_CSV_HEADER}1002,btc,coinbase,64000,0.5,2,1\n")
+        );
+        assert!(!recorder.file_path(RecordStream::Predictors, 0).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(
+            TickRecorder::new(
+                TickRecorderConfig {
+                    dir,
+                    rotate_bytes: 0,
+                    format: RecordFormat::Csv,
+                    streams: RecordStream::ALL.to_vec(),
+                },
+                42,
+            ),
+            Err(TickRecorderConfigError::InvalidRotateBytes)
+        ));
+    }
+}
@@ -1,9 +1,34 @@
 use std::io::{self, Write};
 
-use crate::logging::{PaperJournalRow, RunLogEvent, RunLogEventKind, RunLogWriter};
+use serde::Serialize;
+
+use crate::logging::{
+    PaperJournalRow, PaperJournalRowKind, RunLogEvent, RunLogEventKind, RunLogWriter,
+};
 
 pub const REPLAY_CSV_HEADER: &str =
-    "t,external_px,market_px,divergence,action,equity,realized_pnl,position,halted\n";
+    "t,external_px,market_px,divergence,action,equity,realized_pnl,position,halted,run_id\n";
+
+/// One journal row with its market and book columns filled in.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayCsvRow {
+    pub tick: u64,
+    pub kind: PaperJournalRowKind,
+    pub action_detail: String,
+    /// Spot reference the decision priced off, when one was available.
+    pub external_px: Option<f64>,
+    pub market_px: Option<f64>,
+    /// Fair YES price over the market mid, less one.
+    pub divergence: Option<f64>,
+    pub equity: f64,
+    pub realized_pnl: f64,
+    /// Net YES shares held in the row's market once it is applied.
+    pub position: f64,
+    pub halted: bool,
+    /// The run active when the row was written; empty before the first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<u64>,
+}
 
 pub struct ReplayCsvWriter<W: Write> {
     writer: W,
@@ -35,16 +60,40 @@ impl<W: Write> ReplayCsvWriter<W> {
 
     pub fn append_paper_journal_rows(&mut self, rows: &[PaperJournalRow]) -> io::Result<()> {
         for row in rows {
-            let action = if row.action_detail.is_empty() {
-                row.kind.as_replay_action().to_string()
-            } else {
-                format!("{}:{}", row.kind.as_replay_action(), row.action_detail)
-            };
-            let action = escape_csv_field(&action);
-            writeln!(self.writer, "{},,,,{action},,,,", row.tick)?;
+            let action = replay_action(row.kind, &row.action_detail);
+            writeln!(self.writer, "{},,,,{action},,,,,", row.tick)?;
         }
         Ok(())
     }
+
+    pub fn append_row(&mut self, row: &ReplayCsvRow) -> io::Result<()> {
+        let optional =
+            |value: Option<f64>| value.map_or_else(String::new, |value| value.to_string());
+        writeln!(
+            self.writer,
+            "{},{},{},{},{},{},{},{},{},{}",
+            row.tick,
+            optional(row.external_px),
+            optional(row.market_px),
+            optional(row.divergence),
+            replay_action(row.kind, &row.action_detail),
+            row.equity,
+            row.realized_pnl,
+            row.position,
+            row.halted,
+            row.run_id
+                .map_or_else(String::new, |run_id| run_id.to_string())
+        )
+    }
+}
+
+fn replay_action(kind: PaperJournalRowKind, detail: &str) -> String {
+    let action = if detail.is_empty() {
+        kind.as_replay_action().to_string()
+    } else {
+        format!("{}:{detail}", kind.as_replay_action())
+    };
+    escape_csv_field(&action)
 }
 
 fn escape_csv_field(value: &str) -> String {
@@ -67,7 +116,7 @@ mod tests {
         InMemoryRunLogWriter, PaperJournalRow, PaperJournalRowKind, RunLogEventKind, RunLogWriter,
     };
 
-    use super::{ReplayCsvWriter, REPLAY_CSV_HEADER};
+    use super::{ReplayCsvRow, ReplayCsvWriter, REPLAY_CSV_HEADER};
 
     struct TrackingWriter {
         bytes: Vec<u8>,
@@ -180,7 +229,45 @@ mod tests {
         let csv = write_csv_for_test(vec![sample_paper_fill_row()]).unwrap();
         assert_eq!(
             csv,
-            format!("{REPLAY_CSV_HEADER}17,,,,paper_fill:buy:market-1@0.62x5,,,,\n")
+            format!("{REPLAY_CSV_HEADER}17,,,,paper_fill:buy:market-1@0.62x5,,,,,\n")
+        );
+    }
+
+    #[test]
+    fn replay_writer_appends_rows_with_price_and_pnl_columns() {
+        let mut output = Vec::new();
+        let mut writer = ReplayCsvWriter::new(&mut output);
+        let intent = ReplayCsvRow {
+            tick: 9,
+            kind: PaperJournalRowKind::PaperIntent,
+            action_detail: "polymarket:btc-1215 Buy qty=80 @ 0.41".to_string(),
+            external_px: Some(64_010.5),
+            market_px: Some(0.4),
+            divergence: Some(0.05),
+            equity: 10_000.0,
+            realized_pnl: 0.0,
+            position: 0.0,
+            halted: false,
+            run_id: Some(3),
+        };
+        let reject = ReplayCsvRow {
+            kind: PaperJournalRowKind::RiskReject,
+            action_detail: "daily loss cap reached".to_string(),
+            external_px: None,
+            divergence: None,
+            equity: 9_790.0,
+            realized_pnl: -210.0,
+            halted: true,
+            run_id: None,
+            ..intent.clone()
+        };
+        writer.append_row(&intent).unwrap();
+        writer.append_row(&reject).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "9,64010.5,0.4,0.05,paper_intent:polymarket:btc-1215 Buy qty=80 @ 0.41,10000,0,0,false,3\n\
+             9,,0.4,,risk_reject:daily loss cap reached,9790,-210,0,true,\n"
         );
     }
 
@@ -193,7 +280,7 @@ mod tests {
 
         assert_eq!(
             csv,
-            format!("{REPLAY_CSV_HEADER}17,,,,\"paper_fill:buy,\"\"market-1\"\"\nleg2\",,,,\n")
+            format!("{REPLAY_CSV_HEADER}17,,,,\"paper_fill:buy,\"\"market-1\"\"\nleg2\",,,,,\n")
         );
     }
 }
@@ -56,6 +56,14 @@ impl Supervisor {
         }
     }
 
+    pub fn mark_restarting(&mut self, id: TaskId) -> bool {
+        self.transition_to(id, TaskLifecycle::RestartPlanned, TaskLifecycle::Starting)
+    }
+
+    pub fn state(&self, id: TaskId) -> Option<TaskLifecycle> {
+        self.tasks.get(&id).map(|task| task.state)
+    }
+
     pub fn mark_stopped(&mut self, id: TaskId) -> bool {
         if self.transition_to(id, TaskLifecycle::Running, TaskLifecycle::Stopped) {
             true
@@ -185,6 +193,21 @@ mod tests {
         assert_eq!(task.state, TaskLifecycle::Stopped);
     }
 
+    #[test]
+    fn restart_planned_task_can_return_to_starting() {
+        let mut supervisor = Supervisor::new();
+        let task_id = TaskId(26);
+        supervisor.register(task_id);
+        assert!(!supervisor.mark_restarting(task_id));
+
+        assert!(supervisor.mark_running(task_id));
+        assert!(supervisor.mark_failed(task_id).is_some());
+        assert!(supervisor.mark_restarting(task_id));
+
+        assert_eq!(supervisor.state(task_id), Some(TaskLifecycle::Starting));
+        assert!(supervisor.mark_running(task_id));
+    }
+
     #[test]
     fn unknown_task_operations_remain_distinct() {
         let mut supervisor = Supervisor::new();
@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+const WINDOW_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntentThrottleConfigError {
+    /// At least one intent per minute must be allowed.
+    ZeroLimit,
+}
+
+/// Caps how many strategy intents are let through in any rolling minute.
+///
+/// Rejected intents do not use up the budget, so a loop that keeps firing
+/// resumes at the configured rate rather than staying blocked.
+#[derive(Debug, Clone)]
+pub struct IntentThrottle {
+    max_per_minute: u32,
+    accepted_at_ms: VecDeque<u64>,
+    throttled: u64,
+}
+
+impl IntentThrottle {
+    pub fn new(max_per_minute: u32) -> Result<Self, IntentThrottleConfigError> {
+        if max_per_minute == 0 {
+            return Err(IntentThrottleConfigError::ZeroLimit);
+        }
+
+        Ok(Self {
+            max_per_minute,
+            accepted_at_ms: VecDeque::with_capacity(max_per_minute as usize),
+            throttled: 0,
+        })
+    }
+
+    /// Records an intent at `now_ms` and returns `false` when the minute's
+    /// budget is already spent.
+    pub fn try_acquire(&mut self, now_ms: u64) -> bool {
+        while self
+            .accepted_at_ms
+            .front()
+            .is_some_and(|accepted| now_ms.saturating_sub(*accepted) >= WINDOW_MS)
+        {
+            self.accepted_at_ms.pop_front();
+        }
+
+        if self.accepted_at_ms.len() >= self.max_per_minute as usize {
+            self.throttled = self.throttled.saturating_add(1);
+            return false;
+        }
+        self.accepted_at_ms.push_back(now_ms);
+        true
+    }
+
+    pub fn max_per_minute(&self) -> u32 {
+        self.max_per_minute
+    }
+
+    /// Intents rejected since the throttle was created.
+    pub fn throttled(&self) -> u64 {
+        self.throttled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_past_the_limit_until_the_window_rolls() {
+        let mut throttle = IntentThrottle::new(2).unwrap();
+
+        assert!(throttle.try_acquire(0));
+        assert!(throttle.try_acquire(10_000));
+        assert!(!throttle.try_acquire(20_000));
+        assert!(!throttle.try_acquire(59_999));
+        assert!(throttle.try_acquire(60_000));
+        assert!(!throttle.try_acquire(65_000));
+        assert!(throttle.try_acquire(70_000));
+
+        assert_eq!(throttle.throttled(), 3);
+        assert_eq!(
+            IntentThrottle::new(0).unwrap_err(),
+            IntentThrottleConfigError::ZeroLimit
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use rust_decimal::Decimal;
+
+const DAY_MS: i64 = 86_400_000;
+/// Widest offset in use by any time zone, either side of UTC.
+const MAX_UTC_OFFSET_MINS: i32 = 14 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradingDayConfigError {
+    /// Offsets beyond ±14h do not correspond to any time zone.
+    OffsetOutOfRange,
+}
+
+/// A closed trading day, archived when the boundary is crossed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayRecord {
+    /// Local calendar date of the day, `YYYY-MM-DD`.
+    pub date: String,
+    pub start_ms: u64,
+    pub start_equity: Decimal,
+    pub end_equity: Decimal,
+    pub pnl: Decimal,
+}
+
+/// Splits wall-clock time into trading days that start at local midnight at
+/// a fixed UTC offset, and tracks the equity each day opened with.
+///
+/// The daily loss cap is measured against [`TradingDay::pnl`], so it resets
+/// at every boundary instead of spanning the whole process lifetime.
+#[derive(Debug, Clone)]
+pub struct TradingDay {
+    offset_ms: i64,
+    day: i64,
+    start_equity: Decimal,
+}
+
+impl TradingDay {
+    pub fn new(
+        utc_offset_mins: i32,
+        now_ms: u64,
+        equity: Decimal,
+    ) -> Result<Self, TradingDayConfigError> {
+        if utc_offset_mins.abs() > MAX_UTC_OFFSET_MINS {
+            return Err(TradingDayConfigError::OffsetOutOfRange);
+        }
+
+        let offset_ms = i64::from(utc_offset_mins) * 60_000;
+        Ok(Self {
+            offset_ms,
+            day: day_index(offset_ms, now_ms),
+            start_equity: equity,
+        })
+    }
+
+    /// PnL since the current day opened.
+    pub fn pnl(&self, equity: Decimal) -> Decimal {
+        equity - self.start_equity
+    }
+
+    pub fn start_equity(&self) -> Decimal {
+        self.start_equity
+    }
+
+    /// Local calendar date of the current day, `YYYY-MM-DD`.
+    pub fn date(&self) -> String {
+        format_date(self.day)
+    }
+
+    /// Starts a new day at `equity` once `now_ms` is past the boundary and
+    /// returns the day that closed.
+    ///
+    /// A gap spanning several boundaries closes only the day that was open;
+    /// the days in between had no activity to archive.
+    pub fn roll(&mut self, now_ms: u64, equity: Decimal) -> Option<DayRecord> {
+        let day = day_index(self.offset_ms, now_ms);
+        if day <= self.day {
+            return None;
+        }
+
+        let record = DayRecord {
+            date: format_date(self.day),
+            start_ms: (self.day * DAY_MS - self.offset_ms).max(0) as u64,
+            start_equity: self.start_equity,
+            end_equity: equity,
+            pnl: equity - self.start_equity,
+        };
+        self.day = day;
+        self.start_equity = equity;
+        Some(record)
+    }
+}
+
+fn day_index(offset_ms: i64, now_ms: u64) -> i64 {
+    (now_ms as i64 + offset_ms).div_euclid(DAY_MS)
+}
+
+/// Civil date for days since 1970-01-01 (Howard Hinnant's algorithm).
+fn format_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(value: i64) -> Decimal {
+        Decimal::from(value)
+    }
+
+    // 2024-03-01T23:30:00Z
+    const LATE_UTC_MS: u64 = 1_709_335_800_000;
+
+    #[test]
+    fn rolls_at_local_midnight_and_archives_the_closed_day() {
+        let mut day = TradingDay::new(0, LATE_UTC_MS, usd(1_000)).unwrap();
+        assert_eq!(day.date(), "2024-03-01");
+        assert_eq!(day.pnl(usd(980)), usd(-20));
+        assert!(day.roll(LATE_UTC_MS + 20 * 60_000, usd(980)).is_none());
+
+        let record = day.roll(LATE_UTC_MS + 40 * 60_000, usd(975)).unwrap();
+        assert_eq!(record.date, "2024-03-01");
+        assert_eq!(record.start_ms, 1_709_251_200_000);
+        assert_eq!(
+            (record.start_equity, record.end_equity),
+            (usd(1_000), usd(975))
+        );
+        assert_eq!(record.pnl, usd(-25));
+        assert_eq!(day.date(), "2024-03-02");
+        assert_eq!(day.pnl(usd(975)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn offset_moves_the_boundary() {
+        // 23:30Z is already 2024-03-02 at +01:00 and still 03-01 at -05:00.
+        let east = TradingDay::new(60, LATE_UTC_MS, usd(1_000)).unwrap();
+        let west = TradingDay::new(-300, LATE_UTC_MS, usd(1_000)).unwrap();
+        assert_eq!(east.date(), "2024-03-02");
+        assert_eq!(west.date(), "2024-03-01");
+
+        assert_eq!(
+            TradingDay::new(15 * 60, 0, usd(1_000)).unwrap_err(),
+            TradingDayConfigError::OffsetOutOfRange
+        );
+    }
+}
@@ -22,6 +22,41 @@ pub enum StrategyError {
     MarketExposureCapExceeded,
     PerTradeRiskCapExceeded,
     NonFinitePnl,
+    InvalidStrategyName,
+    DuplicateStrategyName,
+    InvalidKellyFraction,
+    InvalidVolatility,
+    InvalidExpiryGuard,
+    InvalidMaxPosition,
+    MaxPositionExceeded,
+    InvalidMaxDrawdownPct,
+    NonFiniteEquity,
+    InvalidVarWindow,
+    InvalidLossStreakLimit,
+    InvalidCooldown,
+    InvalidExitThreshold,
+    InvalidWalkForwardWindow,
+}
+
+/// Side of the position a signal is evaluated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeldSide {
+    Flat,
+    Long,
+    Short,
+}
+
+impl HeldSide {
+    /// Side of a net YES quantity, negative when short.
+    pub fn from_qty(qty: f64) -> Self {
+        if qty > 0.0 {
+            Self::Long
+        } else if qty < 0.0 {
+            Self::Short
+        } else {
+            Self::Flat
+        }
+    }
 }
 
 pub fn divergence(prediction_price: f64, market_price: f64) -> Result<f64, StrategyError> {
@@ -78,6 +113,39 @@ pub fn signal_from_normalized_divergence(
     signal_from_thresholded_divergence(normalized_divergence, threshold)
 }
 
+/// Signal with separate entry and exit thresholds.
+///
+/// Opening or adding to a position needs the divergence beyond
+/// `entry_threshold`; an open position is only closed once its divergence
+/// falls back inside `exit_threshold`. Between the two it is held, so small
+/// oscillations around the entry threshold neither close nor flip it.
+pub fn signal_with_hysteresis(
+    divergence: f64,
+    entry_threshold: f64,
+    exit_threshold: f64,
+    held: HeldSide,
+) -> Result<Signal, StrategyError> {
+    if !divergence.is_finite() || !entry_threshold.is_finite() || !exit_threshold.is_finite() {
+        return Err(StrategyError::NonFiniteInput);
+    }
+    if entry_threshold < 0.0 || exit_threshold < 0.0 {
+        return Err(StrategyError::NegativeThreshold);
+    }
+    if exit_threshold > entry_threshold {
+        return Err(StrategyError::InvalidExitThreshold);
+    }
+
+    let signal = match held {
+        HeldSide::Flat => signal_from_thresholded_divergence(divergence, entry_threshold)?,
+        HeldSide::Long if divergence > entry_threshold => Signal::Buy,
+        HeldSide::Long if divergence < exit_threshold => Signal::Sell,
+        HeldSide::Short if divergence < -entry_threshold => Signal::Sell,
+        HeldSide::Short if divergence > -exit_threshold => Signal::Buy,
+        HeldSide::Long | HeldSide::Short => Signal::Hold,
+    };
+    Ok(signal)
+}
+
 pub fn emit_signal(
     prediction_price: f64,
     market_price: f64,
@@ -91,7 +159,8 @@ pub fn emit_signal(
 #[cfg(test)]
 mod tests {
     use super::{
-        signal_from_normalized_divergence, signal_from_raw_divergence, Signal, StrategyError,
+        signal_from_normalized_divergence, signal_from_raw_divergence, signal_with_hysteresis,
+        HeldSide, Signal, StrategyError,
     };
 
     #[test]
@@ -112,6 +181,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hysteresis_holds_a_position_between_the_exit_and_entry_thresholds() {
+        let signal = |divergence, held| signal_with_hysteresis(divergence, 0.004, 0.002, held);
+
+        assert_eq!(signal(0.003, HeldSide::Flat), Ok(Signal::Hold));
+        assert_eq!(signal(0.005, HeldSide::Flat), Ok(Signal::Buy));
+        assert_eq!(signal(0.003, HeldSide::Long), Ok(Signal::Hold));
+        assert_eq!(signal(0.005, HeldSide::Long), Ok(Signal::Buy));
+        assert_eq!(signal(0.001, HeldSide::Long), Ok(Signal::Sell));
+        assert_eq!(signal(-0.003, HeldSide::Short), Ok(Signal::Hold));
+        assert_eq!(signal(-0.001, HeldSide::Short), Ok(Signal::Buy));
+        assert_eq!(
+            signal_with_hysteresis(0.003, 0.002, 0.004, HeldSide::Flat),
+            Err(StrategyError::InvalidExitThreshold)
+        );
+    }
+
     #[test]
     fn normalized_divergence_rejects_negative_threshold() {
         assert_eq!(
@@ -0,0 +1,187 @@
+use crate::divergence::StrategyError;
+
+/// Scale that makes the logistic function track the standard normal CDF to
+/// within 0.01 everywhere.
+const LOGISTIC_PROBIT_SCALE: f64 = 1.702;
+
+/// Maps a spot forecast to the probability a 15m market resolves YES.
+///
+/// The market pays out when spot finishes above `strike`. Treating the
+/// remaining path as a random walk around the forecast, the move needed is
+/// `ln(projected / strike)` measured in standard deviations of the remaining
+/// time's volatility; a logistic on that z-score gives the probability.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YesProbabilityModel {
+    vol_bps_per_sqrt_min: f64,
+}
+
+impl YesProbabilityModel {
+    /// `vol_bps_per_sqrt_min` is the spot volatility in basis points per
+    /// square-root minute.
+    pub fn new(vol_bps_per_sqrt_min: f64) -> Result<Self, StrategyError> {
+        if !vol_bps_per_sqrt_min.is_finite() || vol_bps_per_sqrt_min <= 0.0 {
+            return Err(StrategyError::InvalidVolatility);
+        }
+
+        Ok(Self {
+            vol_bps_per_sqrt_min,
+        })
+    }
+
+    pub fn vol_bps_per_sqrt_min(&self) -> f64 {
+        self.vol_bps_per_sqrt_min
+    }
+
+    /// Probability spot finishes above `strike` given the forecast
+    /// `projected_px` and `minutes_remaining` until resolution. At expiry the
+    /// answer is 0, 1, or 0.5 on the strike.
+    pub fn fair_yes(
+        &self,
+        projected_px: f64,
+        strike: f64,
+        minutes_remaining: f64,
+    ) -> Result<f64, StrategyError> {
+        if !projected_px.is_finite() || !strike.is_finite() || !minutes_remaining.is_finite() {
+            return Err(StrategyError::NonFiniteInput);
+        }
+        if projected_px <= 0.0 || strike <= 0.0 {
+            return Err(StrategyError::NonPositiveMarketPrice);
+        }
+
+        let log_move = (projected_px / strike).ln();
+        if minutes_remaining <= 0.0 {
+            return Ok(match log_move.partial_cmp(&0.0) {
+                Some(std::cmp::Ordering::Greater) => 1.0,
+                Some(std::cmp::Ordering::Less) => 0.0,
+                _ => 0.5,
+            });
+        }
+
+        let remaining_sigma = self.vol_bps_per_sqrt_min / 10_000.0 * minutes_remaining.sqrt();
+        let z = log_move / remaining_sigma;
+        Ok(1.0 / (1.0 + (-LOGISTIC_PROBIT_SCALE * z).exp()))
+    }
+}
+
+/// Raises the entry threshold as a binary nears expiry and stops new entries
+/// in its final seconds.
+///
+/// A fixed move in spot shifts a binary's fair value by roughly
+/// `1 / sqrt(time remaining)`, so quotes get noisier as expiry approaches; the
+/// threshold is scaled by `sqrt(window / remaining)`, up to
+/// `max_threshold_scale`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpiryGuard {
+    window_secs: f64,
+    no_entry_secs: f64,
+    max_threshold_scale: f64,
+}
+
+impl ExpiryGuard {
+    pub fn new(
+        window_secs: f64,
+        no_entry_secs: f64,
+        max_threshold_scale: f64,
+    ) -> Result<Self, StrategyError> {
+        if !window_secs.is_finite()
+            || !no_entry_secs.is_finite()
+            || !max_threshold_scale.is_finite()
+            || window_secs <= 0.0
+            || no_entry_secs < 0.0
+            || no_entry_secs >= window_secs
+            || max_threshold_scale < 1.0
+        {
+            return Err(StrategyError::InvalidExpiryGuard);
+        }
+
+        Ok(Self {
+            window_secs,
+            no_entry_secs,
+            max_threshold_scale,
+        })
+    }
+
+    pub fn no_entry_secs(&self) -> f64 {
+        self.no_entry_secs
+    }
+
+    /// Threshold to apply with `seconds_to_expiry` left, or `None` when the
+    /// market is too close to expiry to open a position.
+    pub fn adjusted_threshold(&self, threshold_pct: f64, seconds_to_expiry: f64) -> Option<f64> {
+        if !seconds_to_expiry.is_finite() || seconds_to_expiry <= self.no_entry_secs {
+            return None;
+        }
+
+        let remaining = seconds_to_expiry.min(self.window_secs);
+        let scale = (self.window_secs / remaining)
+            .sqrt()
+            .min(self.max_threshold_scale);
+        Some(threshold_pct * scale)
+    }
+}
+
+impl Default for ExpiryGuard {
+    /// A 15m window with no entries in the last 30 seconds and at most a
+    /// tripled threshold.
+    fn default() -> Self {
+        Self {
+            window_secs: 900.0,
+            no_entry_secs: 30.0,
+            max_threshold_scale: 3.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_the_money_is_a_coin_flip_and_moves_with_the_forecast() {
+        let model = YesProbabilityModel::new(8.0).unwrap();
+
+        assert_eq!(model.fair_yes(64_000.0, 64_000.0, 10.0), Ok(0.5));
+        let above = model.fair_yes(64_100.0, 64_000.0, 10.0).unwrap();
+        let below = model.fair_yes(63_900.0, 64_000.0, 10.0).unwrap();
+        assert!(above > 0.5 && below < 0.5);
+        assert!((above + below - 1.0).abs() < 1e-3);
+
+        // One standard deviation up: close to the normal CDF's 0.841.
+        let one_sigma_px = 64_000.0 * (8.0e-4 * 4.0_f64).exp();
+        let p = model.fair_yes(one_sigma_px, 64_000.0, 16.0).unwrap();
+        assert!((p - 0.841).abs() < 0.01, "p was {p}");
+    }
+
+    #[test]
+    fn expiry_guard_raises_the_threshold_then_blocks_entries() {
+        let guard = ExpiryGuard::default();
+
+        assert_eq!(guard.adjusted_threshold(0.3, 900.0), Some(0.3));
+        assert_eq!(guard.adjusted_threshold(0.3, 1_200.0), Some(0.3));
+        let quarter = guard.adjusted_threshold(0.3, 225.0).unwrap();
+        assert!((quarter - 0.6).abs() < 1e-12);
+        let capped = guard.adjusted_threshold(0.3, 45.0).unwrap();
+        assert!((capped - 0.9).abs() < 1e-12);
+        assert_eq!(guard.adjusted_threshold(0.3, 30.0), None);
+        assert_eq!(
+            ExpiryGuard::new(900.0, 900.0, 3.0),
+            Err(StrategyError::InvalidExpiryGuard)
+        );
+    }
+
+    #[test]
+    fn less_time_remaining_makes_the_same_move_more_certain() {
+        let model = YesProbabilityModel::new(8.0).unwrap();
+
+        let early = model.fair_yes(64_050.0, 64_000.0, 14.0).unwrap();
+        let late = model.fair_yes(64_050.0, 64_000.0, 1.0).unwrap();
+
+        assert!(late > early);
+        assert_eq!(model.fair_yes(64_050.0, 64_000.0, 0.0), Ok(1.0));
+        assert_eq!(model.fair_yes(63_950.0, 64_000.0, -1.0), Ok(0.0));
+        assert_eq!(
+            YesProbabilityModel::new(0.0),
+            Err(StrategyError::InvalidVolatility)
+        );
+    }
+}
@@ -1,12 +1,31 @@
 pub mod divergence;
+pub mod fair_value;
 pub mod live_signal;
+pub mod registry;
 pub mod risk;
 pub mod sizing;
-
-pub use divergence::{divergence, emit_signal, Signal, StrategyError};
-pub use live_signal::{live_signal, LiveSignal};
-pub use risk::RiskState;
-pub use sizing::{regime_multiplier, size_for_signal, Regime, SizingConfig};
+pub mod sweep;
+pub mod var;
+pub mod walk_forward;
+
+pub use divergence::{
+    divergence, emit_signal, signal_with_hysteresis, HeldSide, Signal, StrategyError,
+};
+pub use fair_value::{ExpiryGuard, YesProbabilityModel};
+pub use live_signal::{live_signal, live_signal_with_hysteresis, LiveSignal};
+pub use registry::{
+    LagDivergenceStrategy, MeanReversionStrategy, MomentumStrategy, OrderIntent,
+    SpreadMomentumStrategy, Strategy, StrategyFactory, StrategyInputs, StrategyParams,
+    StrategyRegistry, DEFAULT_STRATEGY,
+};
+pub use risk::{LossStreakCooldown, RiskState};
+pub use sizing::{
+    regime_multiplier, risk_budget_qty, size_for_signal, DivergenceSizer, KellySizer, Regime,
+    SizingConfig, SizingMode,
+};
+pub use sweep::{run_sweep, write_sweep_csv, SweepGrid, SweepParams, SweepResult};
+pub use var::{var_size_scale, VarEstimator};
+pub use walk_forward::{run_walk_forward, WalkForwardConfig, WalkForwardFold, WalkForwardReport};
 
 pub fn module_ready() -> bool {
     true
@@ -15,7 +34,9 @@ pub fn module_ready() -> bool {
 #[cfg(test)]
 mod tests {
     use crate::divergence::{emit_signal, Signal, StrategyError};
-    use crate::sizing::{size_for_signal, Regime, SizingConfig};
+    use crate::sizing::{
+        risk_budget_qty, size_for_signal, DivergenceSizer, KellySizer, Regime, SizingConfig,
+    };
 
     #[test]
     fn emits_buy_signal_when_prediction_leads_market_above_threshold() {
@@ -101,6 +122,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn kelly_sizing_stakes_the_edge_over_the_payoff() {
+        let sizer = KellySizer::new(1.0, 1.0).expect("valid kelly sizer");
+
+        // YES at 0.40 with a 0.50 fair value: (0.5 - 0.4) / 0.6 of equity.
+        let buy = sizer.stake_fraction(Signal::Buy, 0.50, 0.40).unwrap();
+        assert!((buy - 1.0 / 6.0).abs() < 1e-12);
+        // NO at 0.40 when YES is fair at 0.50: (0.6 - 0.5) / 0.6.
+        let sell = sizer.stake_fraction(Signal::Sell, 0.50, 0.60).unwrap();
+        assert!((sell - 1.0 / 6.0).abs() < 1e-12);
+        assert_eq!(sizer.stake_fraction(Signal::Buy, 0.30, 0.40), Ok(0.0));
+
+        let shares = sizer.size(Signal::Sell, 0.50, 0.60, 120.0).unwrap();
+        assert!((shares - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn kelly_sizing_is_scaled_and_capped_by_per_trade_risk() {
+        let half = KellySizer::new(0.5, 1.0).unwrap();
+        let capped = KellySizer::new(1.0, 0.005).unwrap();
+
+        let scaled = half.stake_fraction(Signal::Buy, 0.50, 0.40).unwrap();
+        assert!((scaled - 1.0 / 12.0).abs() < 1e-12);
+        assert_eq!(capped.stake_fraction(Signal::Buy, 0.50, 0.40), Ok(0.005));
+        assert_eq!(
+            KellySizer::new(0.0, 0.005),
+            Err(StrategyError::InvalidKellyFraction)
+        );
+        assert_eq!(
+            KellySizer::new(0.5, 1.5),
+            Err(StrategyError::InvalidPerTradeRiskPct)
+        );
+        assert_eq!(
+            capped.stake_fraction(Signal::Buy, 0.50, 1.0),
+            Err(StrategyError::NonPositiveMarketPrice)
+        );
+    }
+
+    #[test]
+    fn divergence_sizing_grows_with_the_lag_up_to_the_risk_cap() {
+        let sizer = DivergenceSizer::new(0.005).expect("valid divergence sizer");
+
+        // 2% over a 0.3% threshold is 6.67 thresholds; 0.31% barely one.
+        let wide = sizer
+            .size(Signal::Buy, 1.0, 0.51, 0.50, 0.3, 10_000.0)
+            .unwrap();
+        let narrow = sizer
+            .size(Signal::Buy, 1.0, 0.50155, 0.50, 0.3, 10_000.0)
+            .unwrap();
+        assert!((wide - 20.0 / 3.0).abs() < 1e-9, "wide was {wide}");
+        assert!((narrow - 0.31 / 0.3).abs() < 1e-9, "narrow was {narrow}");
+        // Intents below the threshold still trade the base quantity.
+        assert_eq!(
+            sizer.size(Signal::Sell, 1.0, 0.499, 0.50, 0.3, 10_000.0),
+            Ok(1.0)
+        );
+
+        // 50 of 10k equity buys at most 100 YES at 0.50.
+        let capped = sizer.size(Signal::Buy, 10.0, 0.60, 0.50, 0.3, 10_000.0);
+        assert_eq!(capped, Ok(100.0));
+        // The equity cap wins over the base floor: 2.5 of 500 equity buys
+        // only 5 YES at 0.50, below a base of 10.
+        assert_eq!(
+            sizer.size(Signal::Buy, 10.0, 0.499, 0.50, 0.3, 500.0),
+            Ok(5.0)
+        );
+        assert_eq!(
+            DivergenceSizer::new(0.0),
+            Err(StrategyError::InvalidPerTradeRiskPct)
+        );
+        assert_eq!(
+            sizer.size(Signal::Buy, 1.0, 0.51, 0.50, 0.0, 10_000.0),
+            Err(StrategyError::NegativeThreshold)
+        );
+    }
+
+    #[test]
+    fn risk_budget_sizes_by_the_dearer_side_of_the_book() {
+        // 0.5% of 10k risks 50. Selling YES at 0.38 can lose 0.62 a share,
+        // more than buying at 0.40, so that side prices the budget.
+        assert_eq!(risk_budget_qty(0.005, 10_000.0, 0.38, 0.40), Ok(80.0));
+        assert_eq!(risk_budget_qty(0.005, 10_000.0, 0.78, 0.80), Ok(62.0));
+        // The budget grows and shrinks with equity.
+        assert_eq!(risk_budget_qty(0.005, 5_000.0, 0.78, 0.80), Ok(31.0));
+        assert_eq!(risk_budget_qty(0.005, 100.0, 0.49, 0.51), Ok(0.0));
+        assert_eq!(
+            risk_budget_qty(0.0, 10_000.0, 0.49, 0.51),
+            Err(StrategyError::InvalidPerTradeRiskPct)
+        );
+        assert_eq!(
+            risk_budget_qty(0.005, 0.0, 0.49, 0.51),
+            Err(StrategyError::InvalidStartingEquity)
+        );
+    }
+
     #[test]
     fn sizing_rejects_invalid_config_numeric_cases() {
         assert_eq!(
@@ -1,5 +1,6 @@
 use crate::divergence::{
-    normalized_divergence, signal_from_normalized_divergence, Signal, StrategyError,
+    normalized_divergence, signal_from_normalized_divergence, signal_with_hysteresis, HeldSide,
+    Signal, StrategyError,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -22,9 +23,29 @@ pub fn live_signal(
     })
 }
 
+/// [`live_signal`] with an exit threshold below the entry threshold, see
+/// [`signal_with_hysteresis`].
+pub fn live_signal_with_hysteresis(
+    prediction_price: f64,
+    market_price: f64,
+    entry_threshold: f64,
+    exit_threshold: f64,
+    held: HeldSide,
+) -> Result<LiveSignal, StrategyError> {
+    let normalized_divergence = normalized_divergence(prediction_price, market_price)?;
+    let action =
+        signal_with_hysteresis(normalized_divergence, entry_threshold, exit_threshold, held)?;
+
+    Ok(LiveSignal {
+        action,
+        normalized_divergence,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::live_signal;
+    use super::{live_signal, live_signal_with_hysteresis};
+    use crate::HeldSide;
     use crate::Signal;
     use crate::StrategyError;
 
@@ -46,6 +67,17 @@ mod tests {
         assert_eq!(signal.action, Signal::Hold);
     }
 
+    #[test]
+    fn held_position_is_kept_until_the_divergence_closes_inside_the_exit_threshold() {
+        let held =
+            live_signal_with_hysteresis(64_000.0, 63_800.0, 0.005, 0.002, HeldSide::Long).unwrap();
+        let closed =
+            live_signal_with_hysteresis(63_850.0, 63_800.0, 0.005, 0.002, HeldSide::Long).unwrap();
+
+        assert_eq!(held.action, Signal::Hold);
+        assert_eq!(closed.action, Signal::Sell);
+    }
+
     #[test]
     fn rejects_non_positive_market_price_for_live_signal() {
         let error = live_signal(64_200.0, 0.0, 0.003).unwrap_err();
@@ -0,0 +1,573 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::divergence::{
+    normalized_divergence, signal_from_normalized_divergence, signal_with_hysteresis, HeldSide,
+    Signal, StrategyError,
+};
+use crate::live_signal::live_signal;
+
+/// Name of the strategy selected when settings do not name one.
+pub const DEFAULT_STRATEGY: &str = LagDivergenceStrategy::NAME;
+
+/// Market state a [`Strategy`] decides on for one market and one tick. Ids
+/// are borrowed so building the inputs never allocates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyInputs<'a> {
+    pub market_id: &'a str,
+    /// Spot asset the market settles on, such as `btc`.
+    pub underlying: &'a str,
+    /// Spot composite of the underlying.
+    pub spot_px: f64,
+    pub ts_ms: u64,
+    pub mid_yes: f64,
+    pub best_yes_bid: f64,
+    pub best_yes_ask: f64,
+    /// Fair YES probability from the fused predictors or the spot fallback.
+    pub fair_yes_px: f64,
+    /// Spot momentum signal across venues, in USD.
+    pub spread_signal: f64,
+    pub threshold_pct: f64,
+    /// Quantity a strategy should request when it has no sizing of its own.
+    pub base_qty: f64,
+    /// Net YES shares already held in the market, negative when short.
+    pub position_qty: f64,
+}
+
+/// A strategy's request to trade `qty` YES shares in `market_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderIntent {
+    pub market_id: String,
+    pub side: Signal,
+    pub qty: f64,
+}
+
+impl OrderIntent {
+    fn new(inputs: &StrategyInputs, side: Signal) -> Self {
+        Self {
+            market_id: inputs.market_id.to_string(),
+            side,
+            qty: inputs.base_qty,
+        }
+    }
+}
+
+/// Tunables the runtime can change while a strategy is running. Each strategy
+/// reads the fields that apply to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyParams {
+    pub momentum_lookback_ms: u64,
+    /// Spot return over the lookback, in basis points, needed to trade.
+    pub momentum_threshold_bps: f64,
+    /// Lag strategy exit threshold as a fraction of its entry threshold.
+    pub exit_threshold_fraction: f64,
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            momentum_lookback_ms: 60_000,
+            momentum_threshold_bps: 5.0,
+            exit_threshold_fraction: 0.5,
+        }
+    }
+}
+
+/// Decision logic the live loop can swap at runtime.
+pub trait Strategy: Send {
+    fn name(&self) -> &str;
+
+    /// Intents for this tick; empty means stand aside. Strategies keep any
+    /// state they need between calls themselves.
+    fn on_tick(&mut self, inputs: &StrategyInputs) -> Vec<OrderIntent>;
+
+    fn apply_params(&mut self, _params: &StrategyParams) {}
+
+    /// Seeds `market_id` with YES prices traded before the session started,
+    /// oldest first. Strategies without per-market history ignore it.
+    fn warm_up(&mut self, _market_id: &str, _mids: &[f64]) {}
+}
+
+/// Trades when the fair value diverges from the market mid by more than
+/// `threshold_pct` percent of the mid, and closes an open position once the
+/// divergence shrinks inside the exit threshold.
+#[derive(Debug, Clone, Copy)]
+pub struct LagDivergenceStrategy {
+    exit_threshold_fraction: f64,
+}
+
+impl LagDivergenceStrategy {
+    pub const NAME: &'static str = "lag_divergence";
+
+    pub fn new(params: StrategyParams) -> Self {
+        Self {
+            exit_threshold_fraction: params.exit_threshold_fraction,
+        }
+    }
+}
+
+impl Default for LagDivergenceStrategy {
+    fn default() -> Self {
+        Self::new(StrategyParams::default())
+    }
+}
+
+impl Strategy for LagDivergenceStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_tick(&mut self, inputs: &StrategyInputs) -> Vec<OrderIntent> {
+        let entry_threshold = inputs.threshold_pct / 100.0;
+        let side = normalized_divergence(inputs.fair_yes_px, inputs.mid_yes).and_then(|ratio| {
+            signal_with_hysteresis(
+                ratio,
+                entry_threshold,
+                entry_threshold * self.exit_threshold_fraction,
+                HeldSide::from_qty(inputs.position_qty),
+            )
+        });
+        match side {
+            Ok(Signal::Hold) | Err(_) => Vec::new(),
+            Ok(side) => vec![OrderIntent::new(inputs, side)],
+        }
+    }
+
+    fn apply_params(&mut self, params: &StrategyParams) {
+        self.exit_threshold_fraction = params.exit_threshold_fraction;
+    }
+}
+
+/// Ignores the predictors and leans the mid by the spot spread signal, so it
+/// trades spot momentum alone.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpreadMomentumStrategy;
+
+impl SpreadMomentumStrategy {
+    pub const NAME: &'static str = "spread_momentum";
+    const SPREAD_TO_PRICE_COEFF: f64 = 0.001;
+}
+
+impl Strategy for SpreadMomentumStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_tick(&mut self, inputs: &StrategyInputs) -> Vec<OrderIntent> {
+        let prediction =
+            (inputs.mid_yes + inputs.spread_signal * Self::SPREAD_TO_PRICE_COEFF).clamp(0.0, 1.0);
+        match live_signal(prediction, inputs.mid_yes, inputs.threshold_pct / 100.0) {
+            Ok(signal) if signal.action != Signal::Hold => {
+                vec![OrderIntent::new(inputs, signal.action)]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Fades the YES mid when it strays from its recent rolling mean by more than
+/// `threshold_pct` percent: sells rallies and buys dips, the opposite of
+/// chasing the lag.
+#[derive(Debug, Clone)]
+pub struct MeanReversionStrategy {
+    window: usize,
+    mids: HashMap<String, VecDeque<f64>>,
+}
+
+impl MeanReversionStrategy {
+    pub const NAME: &'static str = "mean_reversion";
+    pub const DEFAULT_WINDOW: usize = 20;
+
+    /// Averages the previous `window` mids per market; a zero window is
+    /// treated as one.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            mids: HashMap::new(),
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+}
+
+impl Default for MeanReversionStrategy {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WINDOW)
+    }
+}
+
+impl Strategy for MeanReversionStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_tick(&mut self, inputs: &StrategyInputs) -> Vec<OrderIntent> {
+        if !inputs.mid_yes.is_finite() || inputs.mid_yes <= 0.0 {
+            return Vec::new();
+        }
+
+        let mids = history_mut(&mut self.mids, inputs.market_id);
+        let side = if mids.len() < self.window {
+            Ok(Signal::Hold)
+        } else {
+            let mean = mids.iter().sum::<f64>() / mids.len() as f64;
+            // Mean above the mid means the price dipped: the divergence sign
+            // already points back towards the mean.
+            normalized_divergence(mean, inputs.mid_yes).and_then(|ratio| {
+                signal_from_normalized_divergence(ratio, inputs.threshold_pct / 100.0)
+            })
+        };
+
+        if mids.len() >= self.window {
+            mids.pop_front();
+        }
+        mids.push_back(inputs.mid_yes);
+
+        match side {
+            Ok(Signal::Hold) | Err(_) => Vec::new(),
+            Ok(side) => vec![OrderIntent::new(inputs, side)],
+        }
+    }
+
+    fn warm_up(&mut self, market_id: &str, mids: &[f64]) {
+        let history = self.mids.entry(market_id.to_string()).or_default();
+        // Mids seen live are newer than any backfill, so they stay last.
+        let mut seeded: VecDeque<f64> = mids
+            .iter()
+            .copied()
+            .filter(|mid| mid.is_finite() && *mid > 0.0)
+            .collect();
+        seeded.extend(history.drain(..));
+        while seeded.len() > self.window {
+            seeded.pop_front();
+        }
+        *history = seeded;
+    }
+}
+
+/// The history kept for `key`, allocating the owned key only the first time
+/// it is seen.
+fn history_mut<'m, T>(
+    histories: &'m mut HashMap<String, VecDeque<T>>,
+    key: &str,
+) -> &'m mut VecDeque<T> {
+    if !histories.contains_key(key) {
+        histories.insert(key.to_string(), VecDeque::new());
+    }
+    histories.get_mut(key).expect("history was inserted above")
+}
+
+/// Trades YES in the direction of the underlying's spot return over the
+/// lookback once it exceeds the threshold.
+#[derive(Debug, Clone)]
+pub struct MomentumStrategy {
+    params: StrategyParams,
+    history: HashMap<String, VecDeque<(u64, f64)>>,
+}
+
+impl MomentumStrategy {
+    pub const NAME: &'static str = "momentum";
+
+    pub fn new(params: StrategyParams) -> Self {
+        Self {
+            params,
+            history: HashMap::new(),
+        }
+    }
+
+    /// Return in basis points since the newest sample at least one lookback
+    /// old; `None` until the history covers the lookback.
+    fn lookback_return_bps(&mut self, inputs: &StrategyInputs) -> Option<f64> {
+        let history = history_mut(&mut self.history, inputs.underlying);
+        // Several markets share one underlying; sample it once per tick.
+        if history.back().is_none_or(|(ts, _)| *ts < inputs.ts_ms) {
+            history.push_back((inputs.ts_ms, inputs.spot_px));
+        }
+
+        let cutoff = inputs.ts_ms.checked_sub(self.params.momentum_lookback_ms)?;
+        while history.len() > 1 && history[1].0 <= cutoff {
+            history.pop_front();
+        }
+        let (start_ts, start_px) = *history.front()?;
+        if start_ts > cutoff || start_px <= 0.0 {
+            return None;
+        }
+
+        Some((inputs.spot_px / start_px - 1.0) * 10_000.0)
+    }
+}
+
+impl Default for MomentumStrategy {
+    fn default() -> Self {
+        Self::new(StrategyParams::default())
+    }
+}
+
+impl Strategy for MomentumStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_tick(&mut self, inputs: &StrategyInputs) -> Vec<OrderIntent> {
+        if !inputs.spot_px.is_finite() || inputs.spot_px <= 0.0 {
+            return Vec::new();
+        }
+
+        let threshold_bps = self.params.momentum_threshold_bps;
+        match self.lookback_return_bps(inputs) {
+            Some(move_bps) if move_bps > threshold_bps => {
+                vec![OrderIntent::new(inputs, Signal::Buy)]
+            }
+            Some(move_bps) if move_bps < -threshold_bps => {
+                vec![OrderIntent::new(inputs, Signal::Sell)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply_params(&mut self, params: &StrategyParams) {
+        self.params = *params;
+    }
+}
+
+pub type StrategyFactory = fn() -> Box<dyn Strategy>;
+
+/// Strategies the runtime can select by name.
+#[derive(Clone)]
+pub struct StrategyRegistry {
+    entries: Vec<(&'static str, StrategyFactory)>,
+}
+
+impl StrategyRegistry {
+    pub fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registry holding every strategy shipped with this crate.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::empty();
+        registry.entries.push((LagDivergenceStrategy::NAME, || {
+            Box::new(LagDivergenceStrategy::default())
+        }));
+        registry.entries.push((SpreadMomentumStrategy::NAME, || {
+            Box::new(SpreadMomentumStrategy)
+        }));
+        registry.entries.push((MeanReversionStrategy::NAME, || {
+            Box::new(MeanReversionStrategy::default())
+        }));
+        registry.entries.push((MomentumStrategy::NAME, || {
+            Box::new(MomentumStrategy::default())
+        }));
+        registry
+    }
+
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        factory: StrategyFactory,
+    ) -> Result<(), StrategyError> {
+        if name.trim().is_empty() {
+            return Err(StrategyError::InvalidStrategyName);
+        }
+        if self.contains(name) {
+            return Err(StrategyError::DuplicateStrategyName);
+        }
+
+        self.entries.push((name, factory));
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|(entry, _)| *entry == name)
+    }
+
+    /// A fresh instance of the strategy registered as `name`.
+    pub fn create(&self, name: &str) -> Option<Box<dyn Strategy>> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| *entry == name)
+            .map(|(_, factory)| factory())
+    }
+
+    /// Registered names in registration order.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.entries.iter().map(|(name, _)| *name).collect()
+    }
+}
+
+impl Default for StrategyRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysBuy;
+
+    impl Strategy for AlwaysBuy {
+        fn name(&self) -> &str {
+            "always_buy"
+        }
+
+        fn on_tick(&mut self, inputs: &StrategyInputs) -> Vec<OrderIntent> {
+            vec![OrderIntent::new(inputs, Signal::Buy)]
+        }
+    }
+
+    fn inputs(fair_yes_px: f64, spread_signal: f64) -> StrategyInputs<'static> {
+        StrategyInputs {
+            market_id: "btc-updown-15m",
+            underlying: "btc",
+            spot_px: 64_000.0,
+            ts_ms: 0,
+            mid_yes: 0.50,
+            best_yes_bid: 0.49,
+            best_yes_ask: 0.51,
+            fair_yes_px,
+            spread_signal,
+            threshold_pct: 0.3,
+            base_qty: 1.0,
+            position_qty: 0.0,
+        }
+    }
+
+    #[test]
+    fn lag_divergence_trades_in_the_direction_of_fair_value() {
+        let mut strategy = LagDivergenceStrategy::default();
+
+        assert_eq!(strategy.on_tick(&inputs(0.502, 0.0))[0].side, Signal::Buy);
+        assert_eq!(strategy.on_tick(&inputs(0.497, 0.0))[0].side, Signal::Sell);
+        assert!(strategy.on_tick(&inputs(0.501, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn lag_divergence_holds_a_long_until_the_lag_closes_past_the_exit() {
+        let mut strategy = LagDivergenceStrategy::default();
+        let long = |fair_yes_px: f64| StrategyInputs {
+            position_qty: 2.0,
+            ..inputs(fair_yes_px, 0.0)
+        };
+
+        // 0.2% is inside the 0.3% entry but outside the 0.15% exit.
+        assert!(strategy.on_tick(&long(0.501)).is_empty());
+        assert_eq!(strategy.on_tick(&long(0.5005))[0].side, Signal::Sell);
+
+        strategy.apply_params(&StrategyParams {
+            exit_threshold_fraction: 0.0,
+            ..StrategyParams::default()
+        });
+        assert!(strategy.on_tick(&long(0.5005)).is_empty());
+    }
+
+    #[test]
+    fn spread_momentum_ignores_fair_value() {
+        let mut strategy = SpreadMomentumStrategy;
+
+        let intents = strategy.on_tick(&inputs(0.10, 8.0));
+
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].side, Signal::Buy);
+        assert!(strategy.on_tick(&inputs(0.90, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn mean_reversion_fades_moves_away_from_the_rolling_mean() {
+        let mut strategy = MeanReversionStrategy::new(3);
+        let at_mid = |mid_yes: f64| StrategyInputs {
+            mid_yes,
+            ..inputs(0.5, 0.0)
+        };
+
+        for _ in 0..3 {
+            assert!(strategy.on_tick(&at_mid(0.50)).is_empty());
+        }
+
+        assert_eq!(strategy.on_tick(&at_mid(0.55))[0].side, Signal::Sell);
+        // Mean of 0.50, 0.50, 0.55 sits above a 0.45 mid.
+        assert_eq!(strategy.on_tick(&at_mid(0.45))[0].side, Signal::Buy);
+        assert!(strategy.on_tick(&at_mid(0.50)).is_empty());
+    }
+
+    #[test]
+    fn mean_reversion_trades_on_the_first_tick_after_a_warm_up() {
+        let mut strategy = MeanReversionStrategy::new(3);
+        let at_mid = |mid_yes: f64| StrategyInputs {
+            mid_yes,
+            ..inputs(0.5, 0.0)
+        };
+
+        strategy.warm_up("btc-updown-15m", &[0.40, f64::NAN, 0.50]);
+        // Two backfilled mids and one live one fill the window.
+        assert!(strategy.on_tick(&at_mid(0.50)).is_empty());
+        strategy.warm_up("btc-updown-15m", &[0.10, 0.20]);
+        // Older than everything held, so none of it fits in the window.
+        assert_eq!(strategy.on_tick(&at_mid(0.60))[0].side, Signal::Sell);
+    }
+
+    #[test]
+    fn momentum_follows_the_spot_move_over_the_lookback() {
+        let mut strategy = MomentumStrategy::default();
+        strategy.apply_params(&StrategyParams {
+            momentum_lookback_ms: 10_000,
+            momentum_threshold_bps: 5.0,
+            ..StrategyParams::default()
+        });
+        let spot = |ts_ms: u64, spot_px: f64| StrategyInputs {
+            spot_px,
+            ts_ms,
+            ..inputs(0.5, 0.0)
+        };
+
+        assert!(strategy.on_tick(&spot(100_000, 64_000.0)).is_empty());
+        assert!(strategy.on_tick(&spot(105_000, 64_100.0)).is_empty());
+        // +6.25bps against the 100s sample.
+        assert_eq!(
+            strategy.on_tick(&spot(110_000, 64_040.0))[0].side,
+            Signal::Buy
+        );
+        // -15.6bps against the 105s sample.
+        assert_eq!(
+            strategy.on_tick(&spot(115_000, 64_000.0))[0].side,
+            Signal::Sell
+        );
+        // Within the threshold of the 110s sample.
+        assert!(strategy.on_tick(&spot(120_000, 64_030.0)).is_empty());
+    }
+
+    #[test]
+    fn registry_creates_strategies_by_name_and_rejects_duplicates() {
+        let mut registry = StrategyRegistry::with_builtins();
+
+        assert_eq!(
+            registry.register(LagDivergenceStrategy::NAME, || Box::new(AlwaysBuy)),
+            Err(StrategyError::DuplicateStrategyName)
+        );
+        assert_eq!(
+            registry.register(" ", || Box::new(AlwaysBuy)),
+            Err(StrategyError::InvalidStrategyName)
+        );
+        registry
+            .register("always_buy", || Box::new(AlwaysBuy))
+            .unwrap();
+
+        assert_eq!(
+            registry.names(),
+            vec![
+                DEFAULT_STRATEGY,
+                SpreadMomentumStrategy::NAME,
+                MeanReversionStrategy::NAME,
+                MomentumStrategy::NAME,
+                "always_buy"
+            ]
+        );
+        let mut strategy = registry.create("always_buy").unwrap();
+        assert_eq!(strategy.on_tick(&inputs(0.5, 0.0)).len(), 1);
+        assert!(registry.create("missing").is_none());
+    }
+}
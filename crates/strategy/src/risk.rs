@@ -5,6 +5,9 @@ pub struct RiskState {
     starting_equity: f64,
     realized_pnl: f64,
     daily_loss_cap_pct: f64,
+    /// Highest equity seen by [`update_equity`](Self::update_equity).
+    peak_equity: f64,
+    max_drawdown_fraction: Option<f64>,
     halted: bool,
 }
 
@@ -21,10 +24,52 @@ impl RiskState {
             starting_equity,
             realized_pnl: 0.0,
             daily_loss_cap_pct,
+            peak_equity: starting_equity,
+            max_drawdown_fraction: None,
             halted: false,
         })
     }
 
+    /// Also halts once equity falls `max_drawdown_fraction` below its
+    /// running peak, however slowly the loss built up.
+    pub fn with_max_drawdown(mut self, max_drawdown_fraction: f64) -> Result<Self, StrategyError> {
+        if !max_drawdown_fraction.is_finite()
+            || max_drawdown_fraction <= 0.0
+            || max_drawdown_fraction > 1.0
+        {
+            return Err(StrategyError::InvalidMaxDrawdownPct);
+        }
+
+        self.max_drawdown_fraction = Some(max_drawdown_fraction);
+        Ok(self)
+    }
+
+    /// Marks equity to `equity`, raising the watermark on new highs and
+    /// halting when the drawdown from it reaches the configured limit.
+    pub fn update_equity(&mut self, equity: f64) -> Result<(), StrategyError> {
+        if !equity.is_finite() {
+            return Err(StrategyError::NonFiniteEquity);
+        }
+
+        self.peak_equity = self.peak_equity.max(equity);
+        if let Some(max_drawdown) = self.max_drawdown_fraction {
+            if self.drawdown_fraction(equity) >= max_drawdown {
+                self.halted = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn peak_equity(&self) -> f64 {
+        self.peak_equity
+    }
+
+    /// Fraction of the peak lost at `equity`; zero at or above the peak.
+    pub fn drawdown_fraction(&self, equity: f64) -> f64 {
+        ((self.peak_equity - equity) / self.peak_equity).max(0.0)
+    }
+
     pub fn apply_realized_pnl(&mut self, pnl_delta: f64) -> Result<(), StrategyError> {
         if !pnl_delta.is_finite() {
             return Err(StrategyError::NonFinitePnl);
@@ -79,6 +124,59 @@ impl RiskState {
         Ok(())
     }
 
+    /// Exposure check for a book that can be short YES: exposures are signed
+    /// notional, buys and sells net against each other, and the cap applies
+    /// to the net size. Trades that shrink the net size are always allowed.
+    pub fn check_net_market_exposure(
+        &self,
+        market_id: &str,
+        current_net_exposure: f64,
+        intent_exposure_delta: f64,
+    ) -> Result<(), StrategyError> {
+        if market_id.trim().is_empty() {
+            return Err(StrategyError::InvalidMarketId);
+        }
+        if !current_net_exposure.is_finite() || !intent_exposure_delta.is_finite() {
+            return Err(StrategyError::NonFiniteMarketExposure);
+        }
+
+        let projected = current_net_exposure + intent_exposure_delta;
+        if !projected.is_finite() {
+            return Err(StrategyError::NonFiniteMarketExposure);
+        }
+
+        if projected.abs() > self.exposure_cap_amount()
+            && projected.abs() > current_net_exposure.abs()
+        {
+            return Err(StrategyError::MarketExposureCapExceeded);
+        }
+
+        Ok(())
+    }
+
+    /// Caps the net YES shares held in one market at `max_position_qty`
+    /// either side of flat; trades that shrink the position always pass.
+    pub fn check_market_position(
+        &self,
+        current_qty: f64,
+        intent_qty_delta: f64,
+        max_position_qty: f64,
+    ) -> Result<(), StrategyError> {
+        if !max_position_qty.is_finite() || max_position_qty <= 0.0 {
+            return Err(StrategyError::InvalidMaxPosition);
+        }
+        if !current_qty.is_finite() || !intent_qty_delta.is_finite() {
+            return Err(StrategyError::NonFiniteInput);
+        }
+
+        let projected = current_qty + intent_qty_delta;
+        if projected.abs() > max_position_qty && projected.abs() > current_qty.abs() {
+            return Err(StrategyError::MaxPositionExceeded);
+        }
+
+        Ok(())
+    }
+
     pub fn check_per_trade_risk(
         &self,
         per_trade_risk_fraction: f64,
@@ -107,9 +205,83 @@ impl RiskState {
     }
 }
 
+/// Pauses new entries for a fixed window after a run of losing closes.
+///
+/// A winning close resets the streak and a flat close leaves it unchanged.
+/// The streak starts over once a cooldown begins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LossStreakCooldown {
+    max_losing_streak: u32,
+    cooldown_ms: u64,
+    losing_streak: u32,
+    resume_at_ms: Option<u64>,
+}
+
+impl LossStreakCooldown {
+    pub fn new(max_losing_streak: u32, cooldown_ms: u64) -> Result<Self, StrategyError> {
+        if max_losing_streak == 0 {
+            return Err(StrategyError::InvalidLossStreakLimit);
+        }
+        if cooldown_ms == 0 {
+            return Err(StrategyError::InvalidCooldown);
+        }
+
+        Ok(Self {
+            max_losing_streak,
+            cooldown_ms,
+            losing_streak: 0,
+            resume_at_ms: None,
+        })
+    }
+
+    /// Counts a closed trade; returns when entries resume if this close
+    /// started a cooldown.
+    pub fn record_close(
+        &mut self,
+        realized_pnl: f64,
+        now_ms: u64,
+    ) -> Result<Option<u64>, StrategyError> {
+        if !realized_pnl.is_finite() {
+            return Err(StrategyError::NonFinitePnl);
+        }
+
+        if realized_pnl > 0.0 {
+            self.losing_streak = 0;
+        } else if realized_pnl < 0.0 {
+            self.losing_streak = self.losing_streak.saturating_add(1);
+        }
+        if self.losing_streak < self.max_losing_streak {
+            return Ok(None);
+        }
+
+        self.losing_streak = 0;
+        let resume_at_ms = now_ms.saturating_add(self.cooldown_ms);
+        self.resume_at_ms = Some(resume_at_ms);
+        Ok(Some(resume_at_ms))
+    }
+
+    pub fn is_cooling_down(&self, now_ms: u64) -> bool {
+        self.resume_at_ms
+            .is_some_and(|resume_at_ms| now_ms < resume_at_ms)
+    }
+
+    /// When the latest cooldown ends, even if it already has.
+    pub fn resume_at_ms(&self) -> Option<u64> {
+        self.resume_at_ms
+    }
+
+    pub fn losing_streak(&self) -> u32 {
+        self.losing_streak
+    }
+
+    pub fn max_losing_streak(&self) -> u32 {
+        self.max_losing_streak
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::RiskState;
+    use super::{LossStreakCooldown, RiskState};
     use crate::divergence::StrategyError;
 
     #[test]
@@ -187,6 +359,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nets_short_and_long_market_exposure_against_the_cap() {
+        let risk = RiskState::new(100_000.0, 0.02).expect("valid risk state");
+
+        assert_eq!(
+            risk.check_net_market_exposure("btc-up", 0.0, -1_500.0),
+            Ok(())
+        );
+        assert_eq!(
+            risk.check_net_market_exposure("btc-up", -1_500.0, -600.0),
+            Err(StrategyError::MarketExposureCapExceeded)
+        );
+        assert_eq!(
+            risk.check_net_market_exposure("btc-up", -2_500.0, 3_000.0),
+            Ok(())
+        );
+        assert_eq!(
+            risk.check_net_market_exposure("btc-up", -2_500.0, 5_100.0),
+            Err(StrategyError::MarketExposureCapExceeded)
+        );
+    }
+
+    #[test]
+    fn caps_market_position_either_side_of_flat() {
+        let risk = RiskState::new(100_000.0, 0.02).expect("valid risk state");
+
+        assert_eq!(risk.check_market_position(8.0, 2.0, 10.0), Ok(()));
+        assert_eq!(
+            risk.check_market_position(8.0, 3.0, 10.0),
+            Err(StrategyError::MaxPositionExceeded)
+        );
+        assert_eq!(
+            risk.check_market_position(-10.0, -1.0, 10.0),
+            Err(StrategyError::MaxPositionExceeded)
+        );
+        assert_eq!(risk.check_market_position(12.0, -1.0, 10.0), Ok(()));
+        assert_eq!(
+            risk.check_market_position(0.0, 1.0, 0.0),
+            Err(StrategyError::InvalidMaxPosition)
+        );
+    }
+
+    #[test]
+    fn halts_on_drawdown_from_the_trailing_peak() {
+        let mut risk = RiskState::new(100_000.0, 0.02)
+            .and_then(|risk| risk.with_max_drawdown(0.05))
+            .expect("valid risk state");
+
+        risk.update_equity(120_000.0).expect("valid equity");
+        risk.update_equity(114_500.0).expect("valid equity");
+        assert!(!risk.is_halted());
+        assert_eq!(risk.peak_equity(), 120_000.0);
+
+        // Still above the starting equity, but 5% off the peak.
+        risk.update_equity(114_000.0).expect("valid equity");
+        assert!(risk.is_halted());
+        assert!((risk.drawdown_fraction(114_000.0) - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tracks_the_watermark_without_a_drawdown_limit() {
+        let mut risk = RiskState::new(100_000.0, 0.02).expect("valid risk state");
+
+        risk.update_equity(50_000.0).expect("valid equity");
+
+        assert!(!risk.is_halted());
+        assert_eq!(
+            risk.update_equity(f64::NAN),
+            Err(StrategyError::NonFiniteEquity)
+        );
+        assert_eq!(
+            RiskState::new(100_000.0, 0.02).and_then(|risk| risk.with_max_drawdown(0.0)),
+            Err(StrategyError::InvalidMaxDrawdownPct)
+        );
+    }
+
     #[test]
     fn halts_when_daily_loss_cap_is_breached() {
         let mut risk = RiskState::new(100_000.0, 0.02).expect("valid risk state");
@@ -251,4 +499,28 @@ mod tests {
 
         assert_eq!(decision, Ok(()));
     }
+
+    #[test]
+    fn cools_down_after_consecutive_losing_closes() {
+        let mut cooldown = LossStreakCooldown::new(2, 60_000).expect("valid cooldown");
+
+        assert_eq!(cooldown.record_close(-1.0, 1_000), Ok(None));
+        assert_eq!(cooldown.record_close(2.0, 2_000), Ok(None));
+        assert_eq!(cooldown.record_close(-1.0, 3_000), Ok(None));
+        assert_eq!(cooldown.record_close(0.0, 4_000), Ok(None));
+        assert_eq!(cooldown.losing_streak(), 1);
+        assert_eq!(cooldown.record_close(-0.5, 5_000), Ok(Some(65_000)));
+
+        assert_eq!(cooldown.losing_streak(), 0);
+        assert!(cooldown.is_cooling_down(64_999));
+        assert!(!cooldown.is_cooling_down(65_000));
+        assert_eq!(
+            cooldown.record_close(f64::NAN, 6_000),
+            Err(StrategyError::NonFinitePnl)
+        );
+        assert_eq!(
+            LossStreakCooldown::new(0, 60_000),
+            Err(StrategyError::InvalidLossStreakLimit)
+        );
+    }
 }
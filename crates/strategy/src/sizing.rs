@@ -7,6 +7,16 @@ pub enum Regime {
     Volatile,
 }
 
+impl Regime {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Calm => "calm",
+            Self::Normal => "normal",
+            Self::Volatile => "volatile",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SizingConfig {
     base_order_size: f64,
@@ -34,6 +44,200 @@ impl Default for SizingConfig {
     }
 }
 
+/// How the live loop turns an intent into an order quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SizingMode {
+    /// Trade the strategy's base quantity.
+    #[default]
+    Fixed,
+    Kelly(KellySizer),
+    Divergence(DivergenceSizer),
+}
+
+/// Sizes binary-market orders by the Kelly criterion.
+///
+/// Buying YES at `p` with win probability `q` pays `(1 - p) / p` per unit
+/// staked, so the full-Kelly stake is `(q - p) / (1 - p)` of equity; selling
+/// YES is buying NO at `1 - p`, giving `(p - q) / p`. The stake is scaled by
+/// `kelly_fraction` and capped at `max_fraction` of equity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KellySizer {
+    kelly_fraction: f64,
+    max_fraction: f64,
+}
+
+impl KellySizer {
+    pub fn new(kelly_fraction: f64, max_fraction: f64) -> Result<Self, StrategyError> {
+        if !kelly_fraction.is_finite() || kelly_fraction <= 0.0 || kelly_fraction > 1.0 {
+            return Err(StrategyError::InvalidKellyFraction);
+        }
+        if !max_fraction.is_finite() || max_fraction <= 0.0 || max_fraction > 1.0 {
+            return Err(StrategyError::InvalidPerTradeRiskPct);
+        }
+
+        Ok(Self {
+            kelly_fraction,
+            max_fraction,
+        })
+    }
+
+    pub fn kelly_fraction(&self) -> f64 {
+        self.kelly_fraction
+    }
+
+    pub fn max_fraction(&self) -> f64 {
+        self.max_fraction
+    }
+
+    /// Fraction of equity to stake on `signal`; zero when the market price
+    /// already reflects the fair value or the edge points the other way.
+    pub fn stake_fraction(
+        &self,
+        signal: Signal,
+        fair_yes_px: f64,
+        mid_yes: f64,
+    ) -> Result<f64, StrategyError> {
+        if !fair_yes_px.is_finite() || !mid_yes.is_finite() {
+            return Err(StrategyError::NonFiniteInput);
+        }
+        if mid_yes <= 0.0 || mid_yes >= 1.0 {
+            return Err(StrategyError::NonPositiveMarketPrice);
+        }
+        let fair_yes_px = fair_yes_px.clamp(0.0, 1.0);
+
+        let full_kelly = match signal {
+            Signal::Buy => (fair_yes_px - mid_yes) / (1.0 - mid_yes),
+            Signal::Sell => (mid_yes - fair_yes_px) / mid_yes,
+            Signal::Hold => 0.0,
+        };
+
+        Ok((full_kelly.max(0.0) * self.kelly_fraction).min(self.max_fraction))
+    }
+
+    /// Shares to trade: the stake divided by the price of the side bought,
+    /// `mid_yes` for YES and `1 - mid_yes` for NO.
+    pub fn size(
+        &self,
+        signal: Signal,
+        fair_yes_px: f64,
+        mid_yes: f64,
+        equity: f64,
+    ) -> Result<f64, StrategyError> {
+        if !equity.is_finite() || equity <= 0.0 {
+            return Err(StrategyError::InvalidStartingEquity);
+        }
+
+        let stake = self.stake_fraction(signal, fair_yes_px, mid_yes)? * equity;
+        let price_per_share = match signal {
+            Signal::Sell => 1.0 - mid_yes,
+            Signal::Buy | Signal::Hold => mid_yes,
+        };
+        let size = stake / price_per_share;
+
+        if !size.is_finite() || size < 0.0 {
+            return Err(StrategyError::InvalidPositionSize);
+        }
+
+        Ok(size)
+    }
+}
+
+/// Scales the base quantity by how far past the entry threshold the market
+/// has diverged from fair value.
+///
+/// A divergence of `k` thresholds trades `k` times the base quantity, and
+/// never less than the base, except that the notional is capped at
+/// `max_fraction` of equity priced like [`KellySizer::size`]. When the two
+/// conflict the cap wins, so small accounts can trade below the base.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DivergenceSizer {
+    max_fraction: f64,
+}
+
+impl DivergenceSizer {
+    pub fn new(max_fraction: f64) -> Result<Self, StrategyError> {
+        if !max_fraction.is_finite() || max_fraction <= 0.0 || max_fraction > 1.0 {
+            return Err(StrategyError::InvalidPerTradeRiskPct);
+        }
+
+        Ok(Self { max_fraction })
+    }
+
+    pub fn max_fraction(&self) -> f64 {
+        self.max_fraction
+    }
+
+    /// Shares to trade on `signal` given the divergence of `mid_yes` from
+    /// `fair_yes_px` relative to `threshold_pct`.
+    pub fn size(
+        &self,
+        signal: Signal,
+        base_qty: f64,
+        fair_yes_px: f64,
+        mid_yes: f64,
+        threshold_pct: f64,
+        equity: f64,
+    ) -> Result<f64, StrategyError> {
+        if !base_qty.is_finite() || base_qty <= 0.0 {
+            return Err(StrategyError::InvalidBaseOrderSize);
+        }
+        if !fair_yes_px.is_finite() || !mid_yes.is_finite() || !threshold_pct.is_finite() {
+            return Err(StrategyError::NonFiniteInput);
+        }
+        if mid_yes <= 0.0 || mid_yes >= 1.0 {
+            return Err(StrategyError::NonPositiveMarketPrice);
+        }
+        if threshold_pct <= 0.0 {
+            return Err(StrategyError::NegativeThreshold);
+        }
+        if !equity.is_finite() || equity <= 0.0 {
+            return Err(StrategyError::InvalidStartingEquity);
+        }
+
+        let divergence_pct = ((fair_yes_px - mid_yes) / mid_yes).abs() * 100.0;
+        let price_per_share = match signal {
+            Signal::Hold => return Ok(0.0),
+            Signal::Sell => 1.0 - mid_yes,
+            Signal::Buy => mid_yes,
+        };
+        let max_qty = self.max_fraction * equity / price_per_share;
+        let size = (base_qty * (divergence_pct / threshold_pct).max(1.0)).min(max_qty);
+
+        if !size.is_finite() || size < 0.0 {
+            return Err(StrategyError::InvalidPositionSize);
+        }
+
+        Ok(size)
+    }
+}
+
+/// Whole YES shares whose worst-case loss stays within `risk_fraction` of
+/// `equity`. A buy at the ask can lose the ask per share and a sell at the
+/// bid one minus the bid, so the dearer side prices the budget and either
+/// direction fits it.
+pub fn risk_budget_qty(
+    risk_fraction: f64,
+    equity: f64,
+    best_yes_bid: f64,
+    best_yes_ask: f64,
+) -> Result<f64, StrategyError> {
+    if !risk_fraction.is_finite() || risk_fraction <= 0.0 || risk_fraction > 1.0 {
+        return Err(StrategyError::InvalidPerTradeRiskPct);
+    }
+    if !equity.is_finite() || equity <= 0.0 {
+        return Err(StrategyError::InvalidStartingEquity);
+    }
+    if !best_yes_bid.is_finite() || !best_yes_ask.is_finite() {
+        return Err(StrategyError::NonFiniteInput);
+    }
+    let loss_per_share = best_yes_ask.max(1.0 - best_yes_bid);
+    if loss_per_share <= 0.0 || loss_per_share > 1.0 {
+        return Err(StrategyError::NonPositiveMarketPrice);
+    }
+
+    Ok((risk_fraction * equity / loss_per_share).floor())
+}
+
 pub fn regime_multiplier(regime: Regime) -> f64 {
     match regime {
         Regime::Calm => 1.0,
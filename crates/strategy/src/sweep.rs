@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use rayon::prelude::*;
+
+use crate::divergence::{Signal, StrategyError};
+use crate::registry::{Strategy, StrategyInputs, StrategyParams, StrategyRegistry};
+
+/// Parameter ranges a sweep tries in every combination.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepGrid {
+    /// Entry threshold, in percent of the mid.
+    pub threshold_pcts: Vec<f64>,
+    /// Exit threshold as a fraction of the entry threshold.
+    pub exit_threshold_fractions: Vec<f64>,
+    /// Shares requested per intent.
+    pub base_qtys: Vec<f64>,
+}
+
+impl SweepGrid {
+    fn combinations(&self) -> Vec<SweepParams> {
+        let mut combinations = Vec::new();
+        for &threshold_pct in &self.threshold_pcts {
+            for &exit_threshold_fraction in &self.exit_threshold_fractions {
+                for &base_qty in &self.base_qtys {
+                    combinations.push(SweepParams {
+                        threshold_pct,
+                        exit_threshold_fraction,
+                        base_qty,
+                    });
+                }
+            }
+        }
+        combinations
+    }
+}
+
+/// One point of a [`SweepGrid`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepParams {
+    pub threshold_pct: f64,
+    pub exit_threshold_fraction: f64,
+    pub base_qty: f64,
+}
+
+/// How one parameter combination fared over the session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepResult {
+    pub params: SweepParams,
+    pub fills: u64,
+    /// Open positions marked at their last mid.
+    pub pnl: f64,
+    /// Largest fall in PnL from its running peak.
+    pub max_drawdown: f64,
+    /// Shares bought and sold.
+    pub volume: f64,
+}
+
+/// Replays `session` through a fresh `strategy_name` for every combination
+/// in `grid`, in parallel.
+///
+/// Intents fill in full at the touch: buys at the ask, sells at the bid.
+/// Each tick's `threshold_pct`, `base_qty` and `position_qty` are replaced by
+/// the combination and the simulated book. Results come back in grid order.
+pub fn run_sweep(
+    registry: &StrategyRegistry,
+    strategy_name: &str,
+    session: &[StrategyInputs],
+    grid: &SweepGrid,
+) -> Result<Vec<SweepResult>, StrategyError> {
+    if !registry.contains(strategy_name) {
+        return Err(StrategyError::InvalidStrategyName);
+    }
+
+    Ok(grid
+        .combinations()
+        .into_par_iter()
+        .filter_map(|params| evaluate(registry, strategy_name, session, params))
+        .collect())
+}
+
+/// Replays `session` through a fresh `strategy_name` with one combination,
+/// or `None` when the strategy is not registered.
+pub(crate) fn evaluate(
+    registry: &StrategyRegistry,
+    strategy_name: &str,
+    session: &[StrategyInputs],
+    params: SweepParams,
+) -> Option<SweepResult> {
+    let mut strategy = registry.create(strategy_name)?;
+    strategy.apply_params(&StrategyParams {
+        exit_threshold_fraction: params.exit_threshold_fraction,
+        ..StrategyParams::default()
+    });
+    Some(replay(strategy.as_mut(), session, params))
+}
+
+fn replay(
+    strategy: &mut dyn Strategy,
+    session: &[StrategyInputs],
+    params: SweepParams,
+) -> SweepResult {
+    // Per market: net YES shares, cash, and the last mid.
+    let mut book: HashMap<&str, (f64, f64, f64)> = HashMap::new();
+    let mut fills = 0_u64;
+    let mut volume = 0.0;
+    let mut peak_pnl = 0.0_f64;
+    let mut max_drawdown = 0.0_f64;
+
+    for tick in session {
+        let entry = book
+            .entry(tick.market_id)
+            .or_insert((0.0, 0.0, tick.mid_yes));
+        let inputs = StrategyInputs {
+            threshold_pct: params.threshold_pct,
+            base_qty: params.base_qty,
+            position_qty: entry.0,
+            ..tick.clone()
+        };
+        for intent in strategy.on_tick(&inputs) {
+            let (signed_qty, px) = match intent.side {
+                Signal::Buy => (intent.qty, tick.best_yes_ask),
+                Signal::Sell => (-intent.qty, tick.best_yes_bid),
+                Signal::Hold => continue,
+            };
+            entry.0 += signed_qty;
+            entry.1 -= signed_qty * px;
+            fills += 1;
+            volume += intent.qty;
+        }
+        entry.2 = tick.mid_yes;
+
+        let pnl: f64 = book.values().map(|(qty, cash, mid)| cash + qty * mid).sum();
+        peak_pnl = peak_pnl.max(pnl);
+        max_drawdown = max_drawdown.max(peak_pnl - pnl);
+    }
+
+    SweepResult {
+        params,
+        fills,
+        pnl: book.values().map(|(qty, cash, mid)| cash + qty * mid).sum(),
+        max_drawdown,
+        volume,
+    }
+}
+
+/// Writes one CSV row per result, with a header.
+pub fn write_sweep_csv<W: Write>(mut out: W, results: &[SweepResult]) -> io::Result<()> {
+    writeln!(
+        out,
+        "threshold_pct,exit_threshold_fraction,base_qty,fills,volume,pnl,max_drawdown"
+    )?;
+    for result in results {
+        writeln!(
+            out,
+            "{},{},{},{},{},{:.6},{:.6}",
+            result.params.threshold_pct,
+            result.params.exit_threshold_fraction,
+            result.params.base_qty,
+            result.fills,
+            result.volume,
+            result.pnl,
+            result.max_drawdown
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::LagDivergenceStrategy;
+
+    fn tick(ts_ms: u64, mid_yes: f64, fair_yes_px: f64) -> StrategyInputs<'static> {
+        StrategyInputs {
+            market_id: "btc-updown-15m",
+            underlying: "btc",
+            spot_px: 64_000.0,
+            ts_ms,
+            mid_yes,
+            best_yes_bid: mid_yes - 0.005,
+            best_yes_ask: mid_yes + 0.005,
+            fair_yes_px,
+            spread_signal: 0.0,
+            threshold_pct: 0.0,
+            base_qty: 0.0,
+            position_qty: 0.0,
+        }
+    }
+
+    #[test]
+    fn sweeps_every_combination_and_writes_csv() {
+        // The market catches up to a 4% lag.
+        let session = vec![tick(0, 0.50, 0.52), tick(1_000, 0.52, 0.52)];
+        let grid = SweepGrid {
+            threshold_pcts: vec![1.0, 5.0],
+            exit_threshold_fractions: vec![0.5],
+            base_qtys: vec![1.0, 2.0],
+        };
+
+        let results = run_sweep(
+            &StrategyRegistry::with_builtins(),
+            LagDivergenceStrategy::NAME,
+            &session,
+            &grid,
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 4);
+        // Bought at 0.505 and sold at 0.515 once the lag closed.
+        assert_eq!(results[0].fills, 2);
+        assert!((results[0].pnl - 0.01).abs() < 1e-9, "{:?}", results[0]);
+        assert!((results[1].pnl - 0.02).abs() < 1e-9);
+        // A 5% threshold never trades.
+        assert_eq!(results[2].fills, 0);
+        assert_eq!(results[3].pnl, 0.0);
+
+        let mut csv = Vec::new();
+        write_sweep_csv(&mut csv, &results).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.starts_with("threshold_pct,exit_threshold_fraction,base_qty,"));
+        assert_eq!(
+            run_sweep(&StrategyRegistry::empty(), "missing", &session, &grid).unwrap_err(),
+            StrategyError::InvalidStrategyName
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+use crate::divergence::StrategyError;
+
+/// One-sided 95% quantile of the standard normal.
+const Z_95: f64 = 1.645;
+/// Samples needed before a VaR is reported.
+const MIN_VAR_SAMPLES: usize = 5;
+/// The soft gate starts shrinking size once VaR passes this share of its
+/// limit.
+const SOFT_GATE_START: f64 = 0.5;
+
+/// Parametric 95% value at risk over a rolling window of PnL samples.
+///
+/// Samples are PnL changes over equal intervals; the VaR is the loss one
+/// interval could plausibly bring, `1.645 * stdev - mean`, floored at zero.
+#[derive(Debug, Clone)]
+pub struct VarEstimator {
+    window: usize,
+    samples: VecDeque<f64>,
+}
+
+impl VarEstimator {
+    pub fn new(window: usize) -> Result<Self, StrategyError> {
+        if window < MIN_VAR_SAMPLES {
+            return Err(StrategyError::InvalidVarWindow);
+        }
+
+        Ok(Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+        })
+    }
+
+    pub fn push(&mut self, pnl: f64) -> Result<(), StrategyError> {
+        if !pnl.is_finite() {
+            return Err(StrategyError::NonFinitePnl);
+        }
+
+        if self.samples.len() >= self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(pnl);
+        Ok(())
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// `None` until enough samples have been pushed.
+    pub fn var_95(&self) -> Option<f64> {
+        if self.samples.len() < MIN_VAR_SAMPLES {
+            return None;
+        }
+
+        let n = self.samples.len() as f64;
+        let mean = self.samples.iter().sum::<f64>() / n;
+        let variance = self
+            .samples
+            .iter()
+            .map(|sample| (sample - mean) * (sample - mean))
+            .sum::<f64>()
+            / (n - 1.0);
+        Some((Z_95 * variance.sqrt() - mean).max(0.0))
+    }
+}
+
+/// Multiplier on order size from the VaR soft gate: 1 while `var` is under
+/// half of `limit`, falling linearly to 0 at the limit.
+pub fn var_size_scale(var: f64, limit: f64) -> f64 {
+    if !var.is_finite() || !limit.is_finite() || limit <= 0.0 {
+        return 1.0;
+    }
+
+    let soft_start = limit * SOFT_GATE_START;
+    ((limit - var) / (limit - soft_start)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_var_once_warmed_up_over_a_rolling_window() {
+        let mut estimator = VarEstimator::new(5).unwrap();
+        for pnl in [1.0, -1.0, 1.0, -1.0] {
+            estimator.push(pnl).unwrap();
+        }
+        assert_eq!(estimator.var_95(), None);
+
+        estimator.push(0.0).unwrap();
+        let var = estimator.var_95().unwrap();
+        assert!((var - Z_95).abs() < 1e-12, "var was {var}");
+
+        // The window rolls: five flat samples leave nothing at risk.
+        for _ in 0..5 {
+            estimator.push(0.0).unwrap();
+        }
+        assert_eq!(estimator.sample_count(), 5);
+        assert_eq!(estimator.var_95(), Some(0.0));
+        assert_eq!(estimator.push(f64::NAN), Err(StrategyError::NonFinitePnl));
+        assert_eq!(
+            VarEstimator::new(1).unwrap_err(),
+            StrategyError::InvalidVarWindow
+        );
+    }
+
+    #[test]
+    fn soft_gate_shrinks_size_between_half_the_limit_and_the_limit() {
+        assert_eq!(var_size_scale(20.0, 100.0), 1.0);
+        assert_eq!(var_size_scale(50.0, 100.0), 1.0);
+        assert!((var_size_scale(75.0, 100.0) - 0.5).abs() < 1e-12);
+        assert_eq!(var_size_scale(120.0, 100.0), 0.0);
+    }
+}
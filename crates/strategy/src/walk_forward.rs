@@ -0,0 +1,184 @@
+use crate::divergence::StrategyError;
+use crate::registry::{StrategyInputs, StrategyRegistry};
+use crate::sweep::{evaluate, run_sweep, SweepGrid, SweepParams, SweepResult};
+
+/// Sizes of the rolling train/test windows, in session milliseconds.
+///
+/// Each fold fits on `train_ms` of data and is scored on the `test_ms` that
+/// follows; the next fold starts `test_ms` later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkForwardConfig {
+    pub train_ms: u64,
+    pub test_ms: u64,
+}
+
+impl WalkForwardConfig {
+    pub fn new(train_ms: u64, test_ms: u64) -> Result<Self, StrategyError> {
+        if train_ms == 0 || test_ms == 0 {
+            return Err(StrategyError::InvalidWalkForwardWindow);
+        }
+
+        Ok(Self { train_ms, test_ms })
+    }
+}
+
+/// One train/test split and the parameters it picked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WalkForwardFold {
+    pub train_start_ms: u64,
+    pub test_start_ms: u64,
+    pub test_end_ms: u64,
+    /// Best combination on the train slice.
+    pub params: SweepParams,
+    pub in_sample_pnl: f64,
+    pub out_of_sample: SweepResult,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WalkForwardReport {
+    pub folds: Vec<WalkForwardFold>,
+}
+
+impl WalkForwardReport {
+    pub fn in_sample_pnl(&self) -> f64 {
+        self.folds.iter().map(|fold| fold.in_sample_pnl).sum()
+    }
+
+    pub fn out_of_sample_pnl(&self) -> f64 {
+        self.folds.iter().map(|fold| fold.out_of_sample.pnl).sum()
+    }
+}
+
+/// Fits `grid` on each rolling train window of `session` and replays the
+/// winner on the window after it.
+///
+/// `session` must be ordered by `ts_ms`. The best combination is the one
+/// with the highest train PnL, the first in grid order on ties. A large gap
+/// between in-sample and out-of-sample PnL points at overfitting.
+pub fn run_walk_forward(
+    registry: &StrategyRegistry,
+    strategy_name: &str,
+    session: &[StrategyInputs],
+    grid: &SweepGrid,
+    config: WalkForwardConfig,
+) -> Result<WalkForwardReport, StrategyError> {
+    if !registry.contains(strategy_name) {
+        return Err(StrategyError::InvalidStrategyName);
+    }
+
+    let mut report = WalkForwardReport::default();
+    let (Some(first), Some(last)) = (session.first(), session.last()) else {
+        return Ok(report);
+    };
+
+    let mut train_start_ms = first.ts_ms;
+    loop {
+        let test_start_ms = train_start_ms.saturating_add(config.train_ms);
+        if test_start_ms > last.ts_ms {
+            break;
+        }
+        let test_end_ms = test_start_ms.saturating_add(config.test_ms);
+
+        let train = window(session, train_start_ms, test_start_ms);
+        let test = window(session, test_start_ms, test_end_ms);
+        let best = run_sweep(registry, strategy_name, train, grid)?
+            .into_iter()
+            .reduce(|best, candidate| {
+                if candidate.pnl > best.pnl {
+                    candidate
+                } else {
+                    best
+                }
+            });
+        if let Some(best) = best {
+            if let Some(out_of_sample) = evaluate(registry, strategy_name, test, best.params) {
+                report.folds.push(WalkForwardFold {
+                    train_start_ms,
+                    test_start_ms,
+                    test_end_ms,
+                    params: best.params,
+                    in_sample_pnl: best.pnl,
+                    out_of_sample,
+                });
+            }
+        }
+
+        train_start_ms = train_start_ms.saturating_add(config.test_ms);
+    }
+
+    Ok(report)
+}
+
+fn window<'s, 'a>(
+    session: &'s [StrategyInputs<'a>],
+    start_ms: u64,
+    end_ms: u64,
+) -> &'s [StrategyInputs<'a>] {
+    let start = session.partition_point(|tick| tick.ts_ms < start_ms);
+    let end = session.partition_point(|tick| tick.ts_ms < end_ms);
+    &session[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::LagDivergenceStrategy;
+
+    fn tick(ts_ms: u64, mid_yes: f64, fair_yes_px: f64) -> StrategyInputs<'static> {
+        StrategyInputs {
+            market_id: "btc-updown-15m",
+            underlying: "btc",
+            spot_px: 64_000.0,
+            ts_ms,
+            mid_yes,
+            best_yes_bid: mid_yes - 0.005,
+            best_yes_ask: mid_yes + 0.005,
+            fair_yes_px,
+            spread_signal: 0.0,
+            threshold_pct: 0.0,
+            base_qty: 0.0,
+            position_qty: 0.0,
+        }
+    }
+
+    #[test]
+    fn fits_on_each_train_window_and_scores_the_next() {
+        // The lag closes profitably three times, then the market runs away.
+        let session = vec![
+            tick(0, 0.50, 0.52),
+            tick(1_000, 0.52, 0.52),
+            tick(2_000, 0.50, 0.52),
+            tick(3_000, 0.52, 0.52),
+            tick(4_000, 0.50, 0.52),
+            tick(5_000, 0.48, 0.48),
+        ];
+        let grid = SweepGrid {
+            threshold_pcts: vec![5.0, 1.0],
+            exit_threshold_fractions: vec![0.5],
+            base_qtys: vec![1.0],
+        };
+        let config = WalkForwardConfig::new(2_000, 2_000).unwrap();
+
+        let report = run_walk_forward(
+            &StrategyRegistry::with_builtins(),
+            LagDivergenceStrategy::NAME,
+            &session,
+            &grid,
+            config,
+        )
+        .unwrap();
+
+        assert_eq!(report.folds.len(), 2);
+        assert_eq!(report.folds[0].params.threshold_pct, 1.0);
+        assert_eq!(report.folds[1].test_start_ms, 4_000);
+        assert!((report.folds[0].out_of_sample.pnl - 0.01).abs() < 1e-9);
+        // Bought at 0.505 and sold at 0.475.
+        assert!((report.folds[1].out_of_sample.pnl + 0.03).abs() < 1e-9);
+        assert!((report.in_sample_pnl() - 0.02).abs() < 1e-9);
+        assert!((report.out_of_sample_pnl() + 0.02).abs() < 1e-9);
+        assert_eq!(
+            WalkForwardConfig::new(0, 1_000),
+            Err(StrategyError::InvalidWalkForwardWindow)
+        );
+    }
+}
@@ -12,6 +12,12 @@ const settingsPausedEl = document.getElementById("settings-trading-paused");
 const settingsLagEl = document.getElementById("settings-lag-threshold");
 const settingsRiskEl = document.getElementById("settings-risk-per-trade");
 const settingsDailyEl = document.getElementById("settings-daily-loss-cap");
+const settingsCooldownEl = document.getElementById("settings-lag-cooldown");
+const settingsPersistenceEl = document.getElementById("settings-lag-persistence");
+const settingsDecisionIntervalEl = document.getElementById("settings-decision-interval");
+const settingsStrategyEl = document.getElementById("settings-strategy");
+const settingsSizingModeEl = document.getElementById("settings-sizing-mode");
+const settingsKellyFractionEl = document.getElementById("settings-kelly-fraction");
 const settingsMarketEl = document.getElementById("settings-market");
 const settingsHorizonEl = document.getElementById("settings-horizon");
 const settingsStatusEl = document.getElementById("settings-status");
@@ -193,6 +199,34 @@ function updateSettingsForm(settings) {
   if (settingsDailyEl && Number.isFinite(settings.daily_loss_cap_pct)) {
     settingsDailyEl.value = String(settings.daily_loss_cap_pct);
   }
+  if (settingsCooldownEl && Number.isFinite(settings.lag_cooldown_secs)) {
+    settingsCooldownEl.value = String(settings.lag_cooldown_secs);
+  }
+  if (settingsPersistenceEl && Number.isFinite(settings.lag_persistence_evals)) {
+    settingsPersistenceEl.value = String(settings.lag_persistence_evals);
+  }
+  if (settingsDecisionIntervalEl && Number.isFinite(settings.decision_interval_ms)) {
+    settingsDecisionIntervalEl.value = String(settings.decision_interval_ms);
+  }
+  if (settingsStrategyEl && Array.isArray(settings.available_strategies)) {
+    settingsStrategyEl.replaceChildren(
+      ...settings.available_strategies.map((name) => {
+        const option = document.createElement("option");
+        option.value = name;
+        option.textContent = name;
+        return option;
+      }),
+    );
+  }
+  if (settingsStrategyEl && typeof settings.strategy === "string") {
+    settingsStrategyEl.value = settings.strategy;
+  }
+  if (settingsSizingModeEl && typeof settings.sizing_mode === "string") {
+    settingsSizingModeEl.value = settings.sizing_mode;
+  }
+  if (settingsKellyFractionEl && Number.isFinite(settings.kelly_fraction)) {
+    settingsKellyFractionEl.value = String(settings.kelly_fraction);
+  }
   if (settingsMarketEl && typeof settings.market === "string") {
     settingsMarketEl.textContent = `Market: ${settings.market}`;
   }
@@ -210,6 +244,14 @@ function collectSettingsPayload() {
     lag_threshold_pct: settingsLagEl ? Number(settingsLagEl.value) : null,
     risk_per_trade_pct: settingsRiskEl ? Number(settingsRiskEl.value) : null,
     daily_loss_cap_pct: settingsDailyEl ? Number(settingsDailyEl.value) : null,
+    lag_cooldown_secs: settingsCooldownEl ? Number(settingsCooldownEl.value) : null,
+    lag_persistence_evals: settingsPersistenceEl ? Number(settingsPersistenceEl.value) : null,
+    decision_interval_ms: settingsDecisionIntervalEl
+      ? Number(settingsDecisionIntervalEl.value)
+      : null,
+    strategy: settingsStrategyEl ? settingsStrategyEl.value : null,
+    sizing_mode: settingsSizingModeEl ? settingsSizingModeEl.value : "fixed",
+    kelly_fraction: settingsKellyFractionEl ? Number(settingsKellyFractionEl.value) : null,
   };
 }
 
@@ -335,6 +377,15 @@ function updateForecast(snapshot) {
 }
 
 function updatePriceSnapshot(snapshot) {
+  const composite = asFiniteNumber(snapshot.btc_composite_raw);
+  if (Number.isFinite(composite)) {
+    latestBtcUsd = composite;
+    if (kpiBtcUsdEl) {
+      kpiBtcUsdEl.textContent = formatUsd(composite);
+    }
+    return;
+  }
+
   const coinbase = asFiniteNumber(snapshot.coinbase_btc_usd);
   const binance = asFiniteNumber(snapshot.binance_btc_usdt);
   const kraken = asFiniteNumber(snapshot.kraken_btc_usd);
@@ -375,12 +426,21 @@ function updateFeedHealth(data) {
     return best;
   }, null);
 
+  const endpoints = validSourceCounts
+    .filter((entry) => typeof entry.endpoint === "string" && entry.endpoint.length > 0)
+    .map((entry) => `${entry.source} via ${entry.endpoint}`);
+  const endpointSuffix = endpoints.length > 0 ? ` | endpoints: ${endpoints.join(", ")}` : "";
+  const blocked = validSourceCounts
+    .filter((entry) => entry.blocked && Number.isFinite(entry.blocked.status))
+    .map((entry) => `${entry.source} (${entry.blocked.status})`);
+  const blockedSuffix = blocked.length > 0 ? ` | blocked: ${blocked.join(", ")}` : "";
+
   if (topSource) {
-    feedHealthEl.textContent = `mode: ${mode} | sources: ${totalSources} | top source: ${topSource.source} (${topSource.count})`;
+    feedHealthEl.textContent = `mode: ${mode} | sources: ${totalSources} | top source: ${topSource.source} (${topSource.count})${endpointSuffix}${blockedSuffix}`;
     return;
   }
 
-  feedHealthEl.textContent = `mode: ${mode} | sources: ${totalSources}`;
+  feedHealthEl.textContent = `mode: ${mode} | sources: ${totalSources}${endpointSuffix}${blockedSuffix}`;
 }
 
 function logClassForEvent(eventName) {
@@ -444,19 +504,16 @@ function pushExecutionLog(entry) {
   }
 }
 
-function routeTelemetry(rawEvent) {
-  let parsed = null;
-  try {
-    parsed = JSON.parse(rawEvent);
-  } catch {
-    return;
-  }
-
+function routeTelemetry(parsed) {
   if (!parsed || typeof parsed !== "object") {
     return;
   }
 
   const eventType = parsed.event_type;
+  if (eventType === "connected" || eventType === "run_started") {
+    setStatus(parsed.run_id == null ? "Connected" : `Connected - run ${parsed.run_id}`, "state-open");
+    return;
+  }
   if (eventType === "feed_health") {
     updateFeedHealth(parsed);
     return;
@@ -492,6 +549,47 @@ function routeTelemetry(rawEvent) {
     return;
   }
 
+  if (eventType === "feed_reconnect") {
+    pushExecutionLog({
+      ts: Date.now(),
+      event: eventType,
+      headline: parsed.circuit_open ? "Feed Circuit Open" : "Feed Reconnect",
+      detail: `${parsed.source} attempt=${parsed.attempt} retry_in=${parsed.delay_ms}ms reason=${parsed.reason}`,
+    });
+    return;
+  }
+
+  if (eventType === "feed_throttle") {
+    const retry = parsed.retry_after_ms == null ? "" : ` retry_in=${parsed.retry_after_ms}ms`;
+    pushExecutionLog({
+      ts: Date.now(),
+      event: eventType,
+      headline: "Feed Throttled",
+      detail: `${parsed.host} reason=${parsed.reason}${retry}`,
+    });
+    return;
+  }
+
+  if (eventType === "bracket_triggered") {
+    pushExecutionLog({
+      ts: Date.now(),
+      event: eventType,
+      headline: parsed.kind === "take_profit" ? "Take Profit Hit" : "Stop Loss Hit",
+      detail: `${parsed.market_id} entry=${parsed.entry_px} trigger=${parsed.trigger_px} fill=${parsed.fill_px} qty=${parsed.qty} pnl=${formatSignedUsd(parsed.realized_pnl)}`,
+    });
+    return;
+  }
+
+  if (eventType === "market_resolved") {
+    pushExecutionLog({
+      ts: Date.now(),
+      event: eventType,
+      headline: `Market Resolved ${String(parsed.outcome).toUpperCase()}`,
+      detail: `${parsed.source} ${parsed.market_id} qty=${parsed.qty} settle=${parsed.settle_px} pnl=${formatSignedUsd(parsed.realized_pnl)}`,
+    });
+    return;
+  }
+
   if (eventType === "paper_intent" || eventType === "paper_fill" || eventType === "risk_reject") {
     pushExecutionLog({
       ts: Date.now(),
@@ -504,7 +602,8 @@ function routeTelemetry(rawEvent) {
 
 function connect() {
   const protocol = window.location.protocol === "https:" ? "wss:" : "ws:";
-  const socketUrl = `${protocol}//${window.location.host}/ws/events`;
+  // Events are coalesced into one array frame per 50ms window.
+  const socketUrl = `${protocol}//${window.location.host}/ws/events?batch_ms=50`;
   const ws = new WebSocket(socketUrl);
 
   setStatus("Connecting...", "state-pending");
@@ -514,7 +613,15 @@ function connect() {
   });
 
   ws.addEventListener("message", (event) => {
-    routeTelemetry(event.data);
+    let parsed = null;
+    try {
+      parsed = JSON.parse(event.data);
+    } catch {
+      return;
+    }
+    for (const item of Array.isArray(parsed) ? parsed : [parsed]) {
+      routeTelemetry(item);
+    }
   });
 
   ws.addEventListener("close", () => {